@@ -0,0 +1,16 @@
+DROP TABLE IF EXISTS discovery_frontier;
+
+-- Paused jobs have no checkpoint to resume from any more.
+UPDATE jobs SET status = 'cancelled' WHERE status = 'paused';
+
+PRAGMA writable_schema = ON;
+
+UPDATE sqlite_master
+SET sql = replace(
+    sql,
+    '''completed'', ''failed'', ''cancelled'', ''paused'')',
+    '''completed'', ''failed'', ''cancelled'')'
+)
+WHERE type = 'table' AND name = 'jobs';
+
+PRAGMA writable_schema = RESET;
@@ -0,0 +1,36 @@
+-- Pause/resume support for analysis jobs.
+--
+-- 1. Allow 'paused' in jobs.status.
+--
+-- Migration 0023 widened this CHECK by rebuilding the table, but
+-- migrations run inside a transaction with foreign_keys = ON, so the
+-- implicit DELETE performed by `DROP TABLE jobs` cascades into pages,
+-- issues and page_queue. Adding a value to the IN list only relaxes the
+-- constraint — every existing row still satisfies it — which is the one
+-- case SQLite documents as safe to do by editing the stored schema text.
+PRAGMA writable_schema = ON;
+
+UPDATE sqlite_master
+SET sql = replace(
+    sql,
+    '''completed'', ''failed'', ''cancelled'')',
+    '''completed'', ''failed'', ''cancelled'', ''paused'')'
+)
+WHERE type = 'table' AND name = 'jobs';
+
+PRAGMA writable_schema = RESET;
+
+-- 2. Discovery checkpoint for paused jobs.
+--
+-- Pages fetched before the pause already live in page_queue with their
+-- cached HTML; this row keeps the rest of the crawl state. to_visit and
+-- visited are JSON arrays of URLs. One row per paused job, removed when
+-- the job resumes past discovery or is cancelled.
+CREATE TABLE IF NOT EXISTS discovery_frontier (
+    job_id TEXT PRIMARY KEY NOT NULL,
+    to_visit TEXT NOT NULL DEFAULT '[]',
+    visited TEXT NOT NULL DEFAULT '[]',
+    discovery_complete INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+-- Per-job crawl scoping rules (include/exclude patterns, query-parameter
+-- policy, normalization and trap limits), stored as JSON.
+-- NULL means the default rules: nothing excluded, no URL rewritten.
+
+ALTER TABLE jobs ADD COLUMN url_rules TEXT;
@@ -0,0 +1,5 @@
+-- How a job finds its pages: 'spider' follows links from the start URL,
+-- 'list' analyses only the URLs queued when the job was created.
+
+ALTER TABLE jobs ADD COLUMN crawl_mode TEXT NOT NULL DEFAULT 'spider'
+    CHECK (crawl_mode IN ('spider', 'list'));
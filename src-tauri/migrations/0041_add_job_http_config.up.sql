@@ -0,0 +1,9 @@
+-- Per-job HTTP configuration (extra headers, seed cookies, credentials),
+-- stored as JSON. Kept out of the jobs table because it holds secrets:
+-- job rows are read for listings, exports and reports, this table only
+-- by the worker that runs the job. No row means plain requests.
+CREATE TABLE IF NOT EXISTS job_http_config (
+    job_id TEXT PRIMARY KEY NOT NULL,
+    config TEXT NOT NULL,
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
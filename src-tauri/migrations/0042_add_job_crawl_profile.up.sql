@@ -0,0 +1,5 @@
+-- Per-job browser emulation / user-agent profile, stored as JSON.
+-- NULL means the default Firefox emulation. The older user_agent column
+-- was never read and keeps its placeholder value.
+
+ALTER TABLE jobs ADD COLUMN crawl_profile TEXT;
@@ -0,0 +1,7 @@
+-- Result of the verified TLS handshake made once per job, stored as JSON.
+-- No row means the site isn't served over https or wasn't inspected.
+CREATE TABLE IF NOT EXISTS job_tls_inspections (
+    job_id TEXT PRIMARY KEY NOT NULL,
+    inspection TEXT NOT NULL,
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+DELETE FROM report_patterns WHERE id IN (
+    'builtin-missing-hsts',
+    'builtin-missing-csp',
+    'builtin-missing-nosniff',
+    'builtin-missing-referrer-policy',
+    'builtin-missing-cache-control',
+    'builtin-server-version',
+    'builtin-powered-by'
+);
+ALTER TABLE pages DROP COLUMN response_headers;
+ALTER TABLE page_queue DROP COLUMN cached_headers;
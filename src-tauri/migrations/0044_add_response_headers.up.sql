@@ -0,0 +1,76 @@
+-- Response headers of each fetched page, as a JSON array of
+-- [name, value] pairs, for the security and delivery checks.
+-- page_queue carries them from discovery to analysis alongside
+-- cached_html; NULL for rows written before this migration.
+
+ALTER TABLE page_queue ADD COLUMN cached_headers TEXT;
+ALTER TABLE pages ADD COLUMN response_headers TEXT;
+
+-- Built-in patterns for the Security & Delivery pillar. They read
+-- header:<name> fields, which match nothing on pages without headers.
+INSERT OR IGNORE INTO report_patterns
+    (id, name, description, category, severity, field, operator, threshold, min_prevalence, business_impact, fix_effort, recommendation, is_builtin, enabled)
+VALUES
+(
+    'builtin-missing-hsts',
+    'Missing HSTS',
+    'Without Strict-Transport-Security browsers may reach the site over plain http first, exposing visitors to downgrade attacks.',
+    'security', 'warning', 'header:strict-transport-security', 'missing', NULL, 0.1,
+    'high', 'low',
+    'Send Strict-Transport-Security: max-age=31536000; includeSubDomains on every HTTPS response.',
+    1, 1
+),
+(
+    'builtin-missing-csp',
+    'Missing Content-Security-Policy',
+    'A Content-Security-Policy limits where scripts and styles may load from, the main defence against injected code.',
+    'security', 'warning', 'header:content-security-policy', 'missing', NULL, 0.1,
+    'medium', 'high',
+    'Start with a report-only policy listing the origins the site really uses, then enforce it.',
+    1, 1
+),
+(
+    'builtin-missing-nosniff',
+    'Missing X-Content-Type-Options',
+    'Without nosniff browsers may guess a response''s type and run files as scripts that were never meant to be.',
+    'security', 'suggestion', 'header:x-content-type-options', 'missing', NULL, 0.1,
+    'low', 'low',
+    'Send X-Content-Type-Options: nosniff on every response.',
+    1, 1
+),
+(
+    'builtin-missing-referrer-policy',
+    'Missing Referrer-Policy',
+    'Without a Referrer-Policy, older browsers send full page URLs, including query strings, to every site linked to.',
+    'security', 'suggestion', 'header:referrer-policy', 'missing', NULL, 0.1,
+    'low', 'low',
+    'Send Referrer-Policy: strict-origin-when-cross-origin.',
+    1, 1
+),
+(
+    'builtin-missing-cache-control',
+    'Pages Without Cache-Control',
+    'HTML served without Cache-Control leaves browsers and CDNs to guess how long a page may be reused.',
+    'security', 'suggestion', 'header:cache-control', 'missing', NULL, 0.2,
+    'medium', 'low',
+    'Send an explicit Cache-Control on HTML (for example no-cache with an ETag) so caches revalidate instead of guessing.',
+    1, 1
+),
+(
+    'builtin-server-version',
+    'Server Version Disclosed',
+    'A Server header carrying a version number tells attackers exactly which known vulnerabilities to try.',
+    'security', 'suggestion', 'header:server', 'contains', '/', 0.1,
+    'low', 'low',
+    'Configure the web server to send its name without a version (nginx: server_tokens off; Apache: ServerTokens Prod).',
+    1, 1
+),
+(
+    'builtin-powered-by',
+    'X-Powered-By Disclosed',
+    'X-Powered-By advertises the application framework and its version without helping visitors.',
+    'security', 'suggestion', 'header:x-powered-by', 'present', NULL, 0.1,
+    'low', 'low',
+    'Remove the X-Powered-By header in the application or at the proxy.',
+    1, 1
+);
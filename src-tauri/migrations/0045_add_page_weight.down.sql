@@ -0,0 +1,5 @@
+DELETE FROM report_patterns
+WHERE id IN ('builtin-heavy-pages', 'builtin-render-blocking', 'builtin-third-party-heavy');
+
+ALTER TABLE pages DROP COLUMN page_weight;
+ALTER TABLE jobs DROP COLUMN resource_analysis;
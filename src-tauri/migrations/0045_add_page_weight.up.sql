@@ -0,0 +1,40 @@
+-- Optional resource pass: fetch each page's stylesheets, scripts and
+-- fonts to measure page weight without Lighthouse. jobs.resource_analysis
+-- opts a job in; pages.page_weight holds the result as JSON and stays
+-- NULL for jobs that skipped it.
+
+ALTER TABLE jobs ADD COLUMN resource_analysis INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE pages ADD COLUMN page_weight TEXT;
+
+-- Built-in performance patterns over the weight fields, which match
+-- nothing on pages without a resource pass.
+INSERT OR IGNORE INTO report_patterns
+    (id, name, description, category, severity, field, operator, threshold, min_prevalence, business_impact, fix_effort, recommendation, is_builtin, enabled)
+VALUES
+(
+    'builtin-heavy-pages',
+    'Heavy Pages',
+    'Pages shipping over 2 MB of HTML, CSS, JavaScript and fonts load slowly on mobile connections and use up visitors'' data.',
+    'performance', 'warning', 'total_weight_bytes', 'gt', '2097152', 0.2,
+    'high', 'medium',
+    'Split large bundles, drop unused CSS and JavaScript, and subset web fonts to the characters the site uses.',
+    1, 1
+),
+(
+    'builtin-render-blocking',
+    'Render-Blocking Resources',
+    'Scripts and stylesheets in the page head hold up the first paint until they have downloaded.',
+    'performance', 'warning', 'render_blocking_count', 'gt', '2', 0.3,
+    'medium', 'medium',
+    'Load scripts with async or defer, inline critical CSS and give print or optional stylesheets a media attribute.',
+    1, 1
+),
+(
+    'builtin-third-party-heavy',
+    'Third-Party Heavy Pages',
+    'Pages where most requests go to other domains depend on servers the site doesn''t control for speed and uptime.',
+    'performance', 'suggestion', 'third_party_share', 'gt', '50', 0.3,
+    'medium', 'medium',
+    'Audit tags and widgets, remove the unused ones and self-host critical libraries and fonts.',
+    1, 1
+);
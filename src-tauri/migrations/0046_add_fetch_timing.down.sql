@@ -0,0 +1,6 @@
+DELETE FROM report_patterns WHERE id = 'builtin-slow-server-response';
+
+ALTER TABLE page_queue DROP COLUMN cached_download_ms;
+ALTER TABLE page_queue DROP COLUMN cached_ttfb_ms;
+ALTER TABLE pages DROP COLUMN download_ms;
+ALTER TABLE pages DROP COLUMN ttfb_ms;
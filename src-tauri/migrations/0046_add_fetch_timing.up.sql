@@ -0,0 +1,22 @@
+-- Split each page fetch into time to first byte and body download, so
+-- slow servers can be told apart from heavy pages. NULL for pages crawled
+-- before timing was recorded and for the deep auditor, which doesn't
+-- expose it.
+
+ALTER TABLE pages ADD COLUMN ttfb_ms INTEGER;
+ALTER TABLE pages ADD COLUMN download_ms INTEGER;
+ALTER TABLE page_queue ADD COLUMN cached_ttfb_ms REAL;
+ALTER TABLE page_queue ADD COLUMN cached_download_ms REAL;
+
+INSERT OR IGNORE INTO report_patterns
+    (id, name, description, category, severity, field, operator, threshold, min_prevalence, business_impact, fix_effort, recommendation, is_builtin, enabled)
+VALUES
+(
+    'builtin-slow-server-response',
+    'Slow Server Response',
+    'Pages whose server takes over 800 ms to send the first byte delay everything that follows, however light the page is.',
+    'performance', 'warning', 'ttfb_ms', 'gt', '800', 0.2,
+    'high', 'medium',
+    'Cache rendered pages or put a CDN in front of the site, and profile slow database queries and backend calls.',
+    1, 1
+);
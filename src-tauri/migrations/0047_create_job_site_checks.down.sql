@@ -0,0 +1,16 @@
+ALTER TABLE jobs ADD COLUMN sitemap_found BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE jobs ADD COLUMN robots_txt_found BOOLEAN NOT NULL DEFAULT 0;
+
+UPDATE jobs SET
+    sitemap_found = EXISTS (
+        SELECT 1 FROM job_site_checks c
+        WHERE c.job_id = jobs.id AND c.check_id = 'sitemap_xml'
+          AND c.status IN ('found', 'invalid', 'unauthorized')
+    ),
+    robots_txt_found = EXISTS (
+        SELECT 1 FROM job_site_checks c
+        WHERE c.job_id = jobs.id AND c.check_id = 'robots_txt'
+          AND c.status IN ('found', 'invalid', 'unauthorized')
+    );
+
+DROP TABLE IF EXISTS job_site_checks;
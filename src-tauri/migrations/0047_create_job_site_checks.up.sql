@@ -0,0 +1,25 @@
+-- One row per site-level file check (robots.txt, sitemap, favicon,
+-- security.txt, ...) per job, replacing the two boolean columns that
+-- could only say whether robots.txt and the sitemap answered 200.
+-- `problems` is a JSON array of what's wrong with the served content.
+CREATE TABLE IF NOT EXISTS job_site_checks (
+    job_id TEXT NOT NULL,
+    check_id TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    label TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('found', 'invalid', 'unauthorized', 'missing', 'error')),
+    url TEXT,
+    status_code INTEGER,
+    problems TEXT NOT NULL DEFAULT '[]',
+    PRIMARY KEY (job_id, check_id),
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
+
+-- Older jobs only know that the two files were found.
+INSERT INTO job_site_checks (job_id, check_id, position, label, status)
+SELECT id, 'robots_txt', 0, 'robots.txt', 'found' FROM jobs WHERE robots_txt_found = 1;
+INSERT INTO job_site_checks (job_id, check_id, position, label, status)
+SELECT id, 'sitemap_xml', 1, 'sitemap.xml', 'found' FROM jobs WHERE sitemap_found = 1;
+
+ALTER TABLE jobs DROP COLUMN sitemap_found;
+ALTER TABLE jobs DROP COLUMN robots_txt_found;
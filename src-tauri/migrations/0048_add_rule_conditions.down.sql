@@ -0,0 +1,2 @@
+ALTER TABLE report_patterns DROP COLUMN condition;
+ALTER TABLE custom_checks DROP COLUMN condition;
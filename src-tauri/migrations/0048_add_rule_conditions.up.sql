@@ -0,0 +1,5 @@
+-- Compound conditions for custom checks and report patterns, stored as
+-- JSON. NULL keeps the single field/operator/threshold rule.
+
+ALTER TABLE custom_checks ADD COLUMN condition TEXT;
+ALTER TABLE report_patterns ADD COLUMN condition TEXT;
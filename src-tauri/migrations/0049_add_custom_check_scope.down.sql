@@ -0,0 +1,2 @@
+ALTER TABLE custom_checks DROP COLUMN aggregate;
+ALTER TABLE custom_checks DROP COLUMN scope;
@@ -0,0 +1,5 @@
+-- Site-scoped custom checks run once per job over every page. The
+-- aggregate they compute is stored as JSON; NULL for page checks.
+
+ALTER TABLE custom_checks ADD COLUMN scope TEXT NOT NULL DEFAULT 'page';
+ALTER TABLE custom_checks ADD COLUMN aggregate TEXT;
@@ -0,0 +1,3 @@
+ALTER TABLE custom_extractors DROP COLUMN data_type;
+ALTER TABLE custom_extractors DROP COLUMN output;
+ALTER TABLE custom_extractors DROP COLUMN kind;
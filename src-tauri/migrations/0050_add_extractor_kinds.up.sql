@@ -0,0 +1,7 @@
+-- Custom extractors can read XPath, regex, JSON-LD and header values as
+-- well as CSS selectors, publish counts and lengths, and convert to a
+-- declared data type (NULL infers it).
+
+ALTER TABLE custom_extractors ADD COLUMN kind TEXT NOT NULL DEFAULT 'css';
+ALTER TABLE custom_extractors ADD COLUMN output TEXT NOT NULL DEFAULT 'value';
+ALTER TABLE custom_extractors ADD COLUMN data_type TEXT;
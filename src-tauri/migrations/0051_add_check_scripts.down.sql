@@ -0,0 +1 @@
+ALTER TABLE custom_checks DROP COLUMN script;
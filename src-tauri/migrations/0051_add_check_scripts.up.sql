@@ -0,0 +1,4 @@
+-- Custom checks can run a Rhai script instead of a rule. NULL keeps the
+-- rule.
+
+ALTER TABLE custom_checks ADD COLUMN script TEXT;
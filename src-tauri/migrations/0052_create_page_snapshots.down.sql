@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS page_snapshots;
+DROP TABLE IF EXISTS html_blobs;
@@ -0,0 +1,27 @@
+-- The HTML and response headers each page was served with, so checks,
+-- extractors and reports can be re-run on old jobs and page source
+-- compared between crawls. Bodies are zstd-compressed and stored once
+-- per SHA-256 of the uncompressed HTML: an unchanged page re-crawled
+-- adds a snapshot row, not another copy of its source.
+CREATE TABLE IF NOT EXISTS html_blobs (
+    hash TEXT PRIMARY KEY NOT NULL,
+    content BLOB NOT NULL,
+    size INTEGER NOT NULL,
+    stored_size INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS page_snapshots (
+    page_id TEXT PRIMARY KEY NOT NULL,
+    job_id TEXT NOT NULL,
+    url TEXT NOT NULL,
+    html_hash TEXT NOT NULL,
+    headers TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (page_id) REFERENCES pages(id) ON DELETE CASCADE,
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+    FOREIGN KEY (html_hash) REFERENCES html_blobs(hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_page_snapshots_job_url ON page_snapshots(job_id, url);
+CREATE INDEX IF NOT EXISTS idx_page_snapshots_url ON page_snapshots(url, created_at);
+CREATE INDEX IF NOT EXISTS idx_page_snapshots_hash ON page_snapshots(html_hash);
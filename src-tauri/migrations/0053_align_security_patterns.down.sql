@@ -0,0 +1,10 @@
+UPDATE report_patterns
+SET operator = 'contains',
+    threshold = '/'
+WHERE id = 'builtin-server-version';
+
+UPDATE report_patterns
+SET name = 'Pages Without Cache-Control',
+    description = 'HTML served without Cache-Control leaves browsers and CDNs to guess how long a page may be reused.',
+    condition = NULL
+WHERE id = 'builtin-missing-cache-control';
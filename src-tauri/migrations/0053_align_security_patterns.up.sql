@@ -0,0 +1,21 @@
+-- Align two built-in Security & Delivery patterns with the page checks
+-- they summarise:
+--  * a page is only uncacheable when it sends none of Cache-Control,
+--    ETag and Last-Modified (HtmlCachingCheck);
+--  * a Server header discloses a version when it carries a digit, not a
+--    slash (ServerDisclosureCheck): "cloudflare/edge" names no version,
+--    "Apache 2.4" does.
+
+UPDATE report_patterns
+SET name = 'Pages Without Cache Headers',
+    description = 'HTML served without Cache-Control, ETag or Last-Modified can''t be revalidated, so browsers and CDNs either refetch it in full or guess how long to reuse it.',
+    condition = '{"kind":"all","children":['
+        || '{"kind":"compare","field":"header:cache-control","operator":"missing"},'
+        || '{"kind":"compare","field":"header:etag","operator":"missing"},'
+        || '{"kind":"compare","field":"header:last-modified","operator":"missing"}]}'
+WHERE id = 'builtin-missing-cache-control';
+
+UPDATE report_patterns
+SET operator = 'matches',
+    threshold = '[0-9]'
+WHERE id = 'builtin-server-version';
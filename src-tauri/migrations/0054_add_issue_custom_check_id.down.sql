@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_issues_job_custom_check;
+ALTER TABLE issues DROP COLUMN custom_check_id;
@@ -0,0 +1,11 @@
+-- The custom check that raised an issue, so re-applying checks replaces
+-- exactly their issues even after a check is renamed. NULL for built-in
+-- checks. Issues raised before this column are matched to checks by
+-- name once here.
+
+ALTER TABLE issues ADD COLUMN custom_check_id TEXT;
+
+UPDATE issues
+SET custom_check_id = (SELECT id FROM custom_checks WHERE custom_checks.name = issues.type);
+
+CREATE INDEX IF NOT EXISTS idx_issues_job_custom_check ON issues(job_id, custom_check_id);
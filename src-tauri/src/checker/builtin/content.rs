@@ -3,6 +3,8 @@ use crate::contexts::analysis::{IssueSeverity, NewIssue};
 
 const MIN_WORD_COUNT: i64 = 300;
 const MAX_LOAD_TIME_MS: i64 = 3000;
+/// Google's "good" bound for time to first byte.
+const MAX_TTFB_MS: i64 = 800;
 
 pub struct WordCountCheck;
 impl Check for WordCountCheck {
@@ -32,6 +34,33 @@ impl Check for LoadTimeCheck {
     }
 }
 
+/// Flags slow servers by time to first byte, so a large body on a slow
+/// connection isn't blamed on the server. Pages whose fetch wasn't timed
+/// are left to [`LoadTimeCheck`].
+pub struct ServerResponseCheck;
+impl Check for ServerResponseCheck {
+    fn id(&self) -> &str { "server-response-time" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let ttfb = ctx.page.ttfb_ms?;
+        if ttfb <= MAX_TTFB_MS { return None; }
+        Some(ctx.issue(
+            "Slow Server Response",
+            IssueSeverity::Warning,
+            &format!("Server took {}ms to send the first byte (threshold: {}ms)", ttfb, MAX_TTFB_MS),
+        ))
+    }
+}
+
+/// A 200 page that is really the site's error page.
+pub struct Soft404Check;
+impl Check for Soft404Check {
+    fn id(&self) -> &str { "soft-404" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let reason = ctx.soft_404?;
+        Some(ctx.issue("Soft 404", IssueSeverity::Warning, reason))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,6 +92,9 @@ mod tests {
             response_size_bytes: None, has_viewport: false,
             has_structured_data: false, crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            ttfb_ms: None, download_ms: None,
+            weight: None,
         }
     }
 
@@ -111,6 +143,44 @@ mod tests {
         assert!(issue.message.contains("5000"));
     }
 
+    #[test]
+    fn slow_server_is_judged_by_ttfb() {
+        let mut page = make_page(None, Some(1000));
+        let details = all_pass();
+        assert!(ServerResponseCheck.check(&ctx(&page, &details)).is_none(), "untimed fetch");
+
+        page.ttfb_ms = Some(200);
+        assert!(ServerResponseCheck.check(&ctx(&page, &details)).is_none());
+
+        page.ttfb_ms = Some(1500);
+        let issue = ServerResponseCheck.check(&ctx(&page, &details)).unwrap();
+        assert_eq!(issue.issue_type, "Slow Server Response");
+        assert!(issue.message.contains("1500"));
+    }
+
+    #[test]
+    fn fast_first_byte_does_not_hide_a_slow_load() {
+        let mut page = make_page(None, Some(5000));
+        page.ttfb_ms = Some(200);
+        let details = all_pass();
+        let issue = LoadTimeCheck.check(&ctx(&page, &details)).unwrap();
+        assert_eq!(issue.issue_type, "Slow Page Load");
+    }
+
+    #[test]
+    fn soft_404_reports_the_probe_verdict() {
+        let page = make_page(Some(20), None);
+        let details = all_pass();
+        assert!(Soft404Check.check(&ctx(&page, &details)).is_none());
+
+        let reason = "Page returns 200 but reads like an error page";
+        let issue = Soft404Check
+            .check(&ctx(&page, &details).with_soft_404(Some(reason)))
+            .unwrap();
+        assert_eq!(issue.issue_type, "Soft 404");
+        assert_eq!(issue.message, reason);
+    }
+
     #[test]
     fn load_time_skipped_when_none() {
         let page = make_page(None, None);
@@ -0,0 +1,286 @@
+use crate::checker::{Check, CheckContext};
+use crate::contexts::analysis::{IssueSeverity, NewIssue, ResponseHeaders};
+
+/// HSTS max-age below six months is too short to be preloaded and lapses
+/// between visits.
+const MIN_HSTS_MAX_AGE_SECS: u64 = 15_552_000;
+
+/// Vary tokens that split a shared cache into one copy per visitor.
+const CACHE_BUSTING_VARY: &[&str] = &["*", "cookie", "user-agent"];
+
+/// Headers that only advertise the server's software.
+const DISCLOSURE_HEADERS: &[&str] = &["x-powered-by", "x-aspnet-version", "x-aspnetmvc-version"];
+
+/// The headers of a page that loaded. Pages crawled before headers were
+/// captured, or by the deep auditor, have none and are skipped.
+fn served_headers<'a>(ctx: &'a CheckContext) -> Option<&'a ResponseHeaders> {
+    let headers = &ctx.page.response_headers;
+    if headers.is_empty() || ctx.page.status_code.is_some_and(|s| s >= 400) {
+        return None;
+    }
+    Some(headers)
+}
+
+pub struct HstsCheck;
+impl Check for HstsCheck {
+    fn id(&self) -> &str { "hsts" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headers = served_headers(ctx)?;
+        // Browsers ignore HSTS sent over plain http.
+        if !ctx.page.url.starts_with("https://") { return None; }
+        let Some(value) = headers.get("strict-transport-security") else {
+            return Some(ctx.issue(
+                "Missing HSTS",
+                IssueSeverity::Warning,
+                "No Strict-Transport-Security header; browsers may still try http first",
+            ));
+        };
+        let max_age = value
+            .split(';')
+            .find_map(|d| d.trim().to_ascii_lowercase().strip_prefix("max-age=").map(str::to_string))
+            .and_then(|secs| secs.trim_matches('"').parse::<u64>().ok())
+            .unwrap_or(0);
+        if max_age >= MIN_HSTS_MAX_AGE_SECS { return None; }
+        Some(ctx.issue(
+            "Short HSTS Max-Age",
+            IssueSeverity::Info,
+            &format!("Strict-Transport-Security max-age is {}s (recommend {}+)", max_age, MIN_HSTS_MAX_AGE_SECS),
+        ))
+    }
+}
+
+pub struct ContentSecurityPolicyCheck;
+impl Check for ContentSecurityPolicyCheck {
+    fn id(&self) -> &str { "content-security-policy" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headers = served_headers(ctx)?;
+        if headers.contains("content-security-policy") { return None; }
+        let message = if headers.contains("content-security-policy-report-only") {
+            "Content-Security-Policy is only in report-only mode"
+        } else {
+            "No Content-Security-Policy header"
+        };
+        Some(ctx.issue("Missing Content-Security-Policy", IssueSeverity::Info, message))
+    }
+}
+
+pub struct ContentTypeOptionsCheck;
+impl Check for ContentTypeOptionsCheck {
+    fn id(&self) -> &str { "x-content-type-options" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headers = served_headers(ctx)?;
+        if headers.tokens("x-content-type-options").iter().any(|t| t == "nosniff") { return None; }
+        Some(ctx.issue(
+            "Missing X-Content-Type-Options",
+            IssueSeverity::Info,
+            "No X-Content-Type-Options: nosniff header",
+        ))
+    }
+}
+
+pub struct ReferrerPolicyCheck;
+impl Check for ReferrerPolicyCheck {
+    fn id(&self) -> &str { "referrer-policy" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headers = served_headers(ctx)?;
+        let policy = headers.tokens("referrer-policy");
+        // The last policy the browser supports wins.
+        match policy.last().map(String::as_str) {
+            Some("unsafe-url") => Some(ctx.issue(
+                "Unsafe Referrer-Policy",
+                IssueSeverity::Info,
+                "Referrer-Policy unsafe-url sends full URLs to every site linked to",
+            )),
+            Some(_) => None,
+            None => Some(ctx.issue("Missing Referrer-Policy", IssueSeverity::Info, "No Referrer-Policy header")),
+        }
+    }
+}
+
+pub struct HtmlCachingCheck;
+impl Check for HtmlCachingCheck {
+    fn id(&self) -> &str { "html-caching" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headers = served_headers(ctx)?;
+        if ["cache-control", "etag", "last-modified"].iter().any(|h| headers.contains(h)) {
+            return None;
+        }
+        Some(ctx.issue(
+            "Missing Cache Headers",
+            IssueSeverity::Info,
+            "No Cache-Control, ETag or Last-Modified header; the page can't be revalidated cheaply",
+        ))
+    }
+}
+
+pub struct VaryCheck;
+impl Check for VaryCheck {
+    fn id(&self) -> &str { "vary" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headers = served_headers(ctx)?;
+        let busting: Vec<String> = headers
+            .tokens("vary")
+            .into_iter()
+            .filter(|t| CACHE_BUSTING_VARY.contains(&t.as_str()))
+            .collect();
+        if busting.is_empty() { return None; }
+        Some(ctx.issue(
+            "Vary Header Misuse",
+            IssueSeverity::Warning,
+            &format!("Vary: {} keeps shared caches from reusing the page", busting.join(", ")),
+        ))
+    }
+}
+
+pub struct ServerDisclosureCheck;
+impl Check for ServerDisclosureCheck {
+    fn id(&self) -> &str { "server-disclosure" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headers = served_headers(ctx)?;
+        let mut leaked: Vec<String> = headers
+            .get("server")
+            .filter(|server| server.chars().any(|c| c.is_ascii_digit()))
+            .map(|server| format!("Server: {server}"))
+            .into_iter()
+            .collect();
+        for name in DISCLOSURE_HEADERS {
+            if let Some(value) = headers.get(name) {
+                leaked.push(format!("{name}: {value}"));
+            }
+        }
+        if leaked.is_empty() { return None; }
+        Some(ctx.issue(
+            "Server Version Disclosure",
+            IssueSeverity::Info,
+            &format!("Response headers reveal server software ({})", leaked.join("; ")),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::CheckContext;
+    use crate::contexts::analysis::Page;
+    use crate::service::auditor::SeoAuditDetails;
+    use chrono::Utc;
+
+    fn make_page(url: &str, headers: &[(&str, &str)]) -> Page {
+        Page {
+            id: "p1".into(), job_id: "j1".into(),
+            url: url.into(), depth: crate::contexts::analysis::Depth::root(),
+            status_code: Some(200), content_type: Some("text/html".into()),
+            title: None, meta_description: None, canonical_url: None,
+            robots_meta: None, word_count: None, load_time_ms: None,
+            response_size_bytes: None, has_viewport: false,
+            has_structured_data: false, crawled_at: Utc::now(),
+            extracted_data: std::collections::HashMap::new(),
+            response_headers: ResponseHeaders::new(headers.iter().copied()),
+            ttfb_ms: None, download_ms: None,
+            weight: None,
+        }
+    }
+
+    fn run(check: &dyn Check, page: &Page) -> Option<NewIssue> {
+        let details = SeoAuditDetails::default();
+        check.check(&CheckContext::new(page, &details, "j1", "p1"))
+    }
+
+    const HARDENED: &[(&str, &str)] = &[
+        ("Strict-Transport-Security", "max-age=31536000; includeSubDomains"),
+        ("Content-Security-Policy", "default-src 'self'"),
+        ("X-Content-Type-Options", "nosniff"),
+        ("Referrer-Policy", "strict-origin-when-cross-origin"),
+        ("Cache-Control", "no-cache"),
+        ("ETag", "\"abc\""),
+        ("Vary", "Accept-Encoding"),
+        ("Server", "nginx"),
+    ];
+
+    fn all() -> Vec<Box<dyn Check>> {
+        vec![
+            Box::new(HstsCheck), Box::new(ContentSecurityPolicyCheck),
+            Box::new(ContentTypeOptionsCheck), Box::new(ReferrerPolicyCheck),
+            Box::new(HtmlCachingCheck), Box::new(VaryCheck), Box::new(ServerDisclosureCheck),
+        ]
+    }
+
+    #[test]
+    fn hardened_page_passes_every_check() {
+        let page = make_page("https://example.com", HARDENED);
+        for check in all() {
+            assert!(run(check.as_ref(), &page).is_none(), "{} fired", check.id());
+        }
+    }
+
+    #[test]
+    fn pages_without_captured_headers_are_skipped() {
+        let page = make_page("https://example.com", &[]);
+        for check in all() {
+            assert!(run(check.as_ref(), &page).is_none(), "{} fired", check.id());
+        }
+    }
+
+    #[test]
+    fn bare_page_fails_security_and_caching_checks() {
+        let page = make_page("https://example.com", &[("Content-Type", "text/html")]);
+        let fired: Vec<String> = all()
+            .iter()
+            .filter_map(|c| run(c.as_ref(), &page))
+            .map(|i| i.issue_type)
+            .collect();
+        assert_eq!(fired, [
+            "Missing HSTS", "Missing Content-Security-Policy", "Missing X-Content-Type-Options",
+            "Missing Referrer-Policy", "Missing Cache Headers",
+        ]);
+    }
+
+    #[test]
+    fn hsts_ignores_plain_http_and_flags_short_max_age() {
+        let page = make_page("http://example.com", &[("Content-Type", "text/html")]);
+        assert!(run(&HstsCheck, &page).is_none());
+
+        let page = make_page("https://example.com", &[("Strict-Transport-Security", "max-age=300")]);
+        let issue = run(&HstsCheck, &page).unwrap();
+        assert_eq!(issue.issue_type, "Short HSTS Max-Age");
+        assert_eq!(issue.severity, IssueSeverity::Info);
+        assert!(issue.message.contains("300s"));
+    }
+
+    #[test]
+    fn unsafe_referrer_policy_is_not_reported_as_missing() {
+        let page = make_page("https://example.com", &[("Referrer-Policy", "no-referrer, unsafe-url")]);
+        let issue = run(&ReferrerPolicyCheck, &page).unwrap();
+        assert_eq!(issue.issue_type, "Unsafe Referrer-Policy");
+
+        let page = make_page("https://example.com", &[("Content-Type", "text/html")]);
+        assert_eq!(run(&ReferrerPolicyCheck, &page).unwrap().issue_type, "Missing Referrer-Policy");
+    }
+
+    #[test]
+    fn caching_accepts_any_validator() {
+        for header in [("ETag", "\"abc\""), ("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT")] {
+            let page = make_page("https://example.com", &[header]);
+            assert!(run(&HtmlCachingCheck, &page).is_none(), "{} alone should do", header.0);
+        }
+    }
+
+    #[test]
+    fn vary_flags_cache_busting_tokens() {
+        let page = make_page("https://example.com", &[("Vary", "Accept-Encoding, Cookie")]);
+        let issue = run(&VaryCheck, &page).unwrap();
+        assert_eq!(issue.issue_type, "Vary Header Misuse");
+        assert!(issue.message.contains("cookie"));
+    }
+
+    #[test]
+    fn server_disclosure_needs_a_version_or_framework_header() {
+        let page = make_page("https://example.com", &[("Server", "Apache/2.4.41 (Ubuntu)")]);
+        assert!(run(&ServerDisclosureCheck, &page).unwrap().message.contains("Apache/2.4.41"));
+
+        let page = make_page("https://example.com", &[("Server", "cloudflare"), ("X-Powered-By", "PHP/8.1")]);
+        let issue = run(&ServerDisclosureCheck, &page).unwrap();
+        assert!(issue.message.contains("x-powered-by: PHP/8.1"));
+        assert!(!issue.message.contains("cloudflare"));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::checker::{Check, CheckContext};
+use crate::contexts::analysis::{IssueSeverity, NewIssue};
+use crate::extractor::page_extractor::ExtractedHeading;
+
+/// Longer headings get truncated in results and stop reading as headings.
+const MAX_HEADING_CHARS: usize = 70;
+
+/// How many headings to quote in an issue message; the rest go in the details.
+const MAX_LISTED: usize = 3;
+
+/// The headings of a page that loaded. Pages whose outline wasn't
+/// extracted, and error pages, are skipped.
+fn served_headings<'a>(ctx: &CheckContext<'a>) -> Option<&'a [ExtractedHeading]> {
+    if ctx.page.status_code.is_some_and(|s| s >= 400) {
+        return None;
+    }
+    ctx.headings
+}
+
+/// The page's H1s. An empty `<h1>` names no topic, so it doesn't count;
+/// [`EmptyHeadingCheck`] reports it.
+fn h1s<'a>(headings: &'a [ExtractedHeading]) -> impl Iterator<Item = &'a ExtractedHeading> {
+    headings.iter().filter(|h| h.level == 1 && !h.text.is_empty())
+}
+
+fn quoted(h: &ExtractedHeading) -> String {
+    format!("H{} \"{}\"", h.level, h.text)
+}
+
+/// An issue naming the first few `offending` headings, with all of them in
+/// the details.
+fn listing_issue(
+    ctx: &CheckContext,
+    offending: &[String],
+    issue_type: &str,
+    severity: IssueSeverity,
+    summary: &str,
+) -> Option<NewIssue> {
+    if offending.is_empty() { return None; }
+    let more = match offending.len().saturating_sub(MAX_LISTED) {
+        0 => String::new(),
+        n => format!(" and {n} more"),
+    };
+    let listed = offending.iter().take(MAX_LISTED).cloned().collect::<Vec<_>>().join(", ");
+    let mut issue = ctx.issue(issue_type, severity, &format!("{summary}: {listed}{more}"));
+    issue.details = Some(offending.join("\n"));
+    Some(issue)
+}
+
+pub struct MissingH1Check;
+impl Check for MissingH1Check {
+    fn id(&self) -> &str { "missing-h1" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headings = served_headings(ctx)?;
+        if h1s(headings).next().is_some() { return None; }
+        Some(ctx.issue(
+            "Missing H1",
+            IssueSeverity::Warning,
+            "Page has no H1 heading describing its topic",
+        ))
+    }
+}
+
+pub struct MultipleH1Check;
+impl Check for MultipleH1Check {
+    fn id(&self) -> &str { "multiple-h1" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headings = served_headings(ctx)?;
+        let h1s: Vec<String> = h1s(headings).map(|h| format!("\"{}\"", h.text)).collect();
+        if h1s.len() < 2 { return None; }
+        listing_issue(
+            ctx,
+            &h1s,
+            "Multiple H1",
+            IssueSeverity::Info,
+            &format!("Page has {} H1 headings, so its main topic is unclear", h1s.len()),
+        )
+    }
+}
+
+/// A heading more than one level below the one before it (H2 → H4).
+/// Going back up any number of levels is fine.
+pub struct SkippedHeadingLevelCheck;
+impl Check for SkippedHeadingLevelCheck {
+    fn id(&self) -> &str { "skipped-heading-level" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headings = served_headings(ctx)?;
+        let skips: Vec<String> = headings
+            .windows(2)
+            .filter(|pair| pair[1].level > pair[0].level + 1)
+            .map(|pair| format!("H{} → {}", pair[0].level, quoted(&pair[1])))
+            .collect();
+        listing_issue(
+            ctx,
+            &skips,
+            "Skipped Heading Level",
+            IssueSeverity::Info,
+            "Heading levels skip, breaking the outline screen readers navigate by",
+        )
+    }
+}
+
+pub struct EmptyHeadingCheck;
+impl Check for EmptyHeadingCheck {
+    fn id(&self) -> &str { "empty-heading" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headings = served_headings(ctx)?;
+        let empty: Vec<String> = headings
+            .iter()
+            .filter(|h| h.text.is_empty())
+            .map(|h| format!("H{} (heading #{})", h.level, h.position + 1))
+            .collect();
+        listing_issue(ctx, &empty, "Empty Heading", IssueSeverity::Warning, "Headings without text")
+    }
+}
+
+/// The H1 repeats the title word for word, wasting a chance to cover
+/// another phrasing of the topic.
+pub struct H1MatchesTitleCheck;
+impl Check for H1MatchesTitleCheck {
+    fn id(&self) -> &str { "h1-matches-title" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headings = served_headings(ctx)?;
+        let title = normalize(ctx.page.title.as_deref()?);
+        if title.is_empty() { return None; }
+        let h1 = h1s(headings).find(|h| normalize(&h.text) == title)?;
+        Some(ctx.issue(
+            "H1 Same As Title",
+            IssueSeverity::Info,
+            &format!("H1 \"{}\" repeats the page title", h1.text),
+        ))
+    }
+}
+
+pub struct LongHeadingCheck;
+impl Check for LongHeadingCheck {
+    fn id(&self) -> &str { "long-heading" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let headings = served_headings(ctx)?;
+        let long: Vec<String> = headings
+            .iter()
+            .filter(|h| h.text.chars().count() > MAX_HEADING_CHARS)
+            .map(|h| format!("H{} ({} characters)", h.level, h.text.chars().count()))
+            .collect();
+        listing_issue(
+            ctx,
+            &long,
+            "Long Heading",
+            IssueSeverity::Info,
+            &format!("Headings over {MAX_HEADING_CHARS} characters"),
+        )
+    }
+}
+
+/// Case- and whitespace-insensitive form for comparing a heading to the title.
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::analysis::Page;
+    use crate::extractor::page_extractor::PageExtractor;
+    use crate::service::auditor::SeoAuditDetails;
+    use chrono::Utc;
+    use scraper::Html;
+
+    fn make_page(title: Option<&str>, status_code: i64) -> Page {
+        Page {
+            id: "p1".into(), job_id: "j1".into(),
+            url: "https://example.com/".into(), depth: crate::contexts::analysis::Depth::root(),
+            status_code: Some(status_code), content_type: Some("text/html".into()),
+            title: title.map(str::to_string), meta_description: None, canonical_url: None,
+            robots_meta: None, word_count: None, load_time_ms: None,
+            response_size_bytes: None, has_viewport: false,
+            has_structured_data: false, crawled_at: Utc::now(),
+            extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            ttfb_ms: None, download_ms: None,
+            weight: None,
+        }
+    }
+
+    fn run(check: &dyn Check, title: Option<&str>, html: &str) -> Option<NewIssue> {
+        let headings = PageExtractor::extract_headings(&Html::parse_document(html));
+        let page = make_page(title, 200);
+        let details = SeoAuditDetails::default();
+        check.check(&CheckContext::new(&page, &details, "j1", "p1").with_headings(&headings))
+    }
+
+    #[test]
+    fn missing_h1_needs_extracted_headings() {
+        let issue = run(&MissingH1Check, None, "<h2>Only a subheading</h2>").unwrap();
+        assert_eq!(issue.issue_type, "Missing H1");
+        assert!(run(&MissingH1Check, None, "<h1>Welcome</h1>").is_none());
+        assert!(run(&MissingH1Check, None, "<h1> </h1><h2>Subheading</h2>").is_some(), "empty H1");
+
+        // Without an outline there's nothing to judge.
+        let page = make_page(None, 200);
+        let details = SeoAuditDetails::default();
+        assert!(MissingH1Check.check(&CheckContext::new(&page, &details, "j1", "p1")).is_none());
+    }
+
+    #[test]
+    fn error_pages_are_skipped() {
+        let page = make_page(None, 404);
+        let details = SeoAuditDetails::default();
+        let ctx = CheckContext::new(&page, &details, "j1", "p1").with_headings(&[]);
+        assert!(MissingH1Check.check(&ctx).is_none());
+    }
+
+    #[test]
+    fn multiple_h1s_are_listed() {
+        let issue = run(&MultipleH1Check, None, "<h1>One</h1><h2>x</h2><h1>Two</h1>").unwrap();
+        assert_eq!(issue.message, "Page has 2 H1 headings, so its main topic is unclear: \"One\", \"Two\"");
+        assert!(run(&MultipleH1Check, None, "<h1>One</h1><h2>Two</h2>").is_none());
+        assert!(run(&MultipleH1Check, None, "<h1>One</h1><h1></h1>").is_none());
+    }
+
+    #[test]
+    fn skipped_levels_only_count_going_down() {
+        let issue = run(&SkippedHeadingLevelCheck, None, "<h1>A</h1><h3>B</h3><h4>C</h4><h2>D</h2><h5>E</h5>").unwrap();
+        assert_eq!(issue.details.as_deref(), Some("H1 → H3 \"B\"\nH2 → H5 \"E\""));
+        assert!(run(&SkippedHeadingLevelCheck, None, "<h1>A</h1><h2>B</h2><h3>C</h3><h2>D</h2>").is_none());
+    }
+
+    #[test]
+    fn empty_headings_are_flagged() {
+        let issue = run(&EmptyHeadingCheck, None, "<h1>A</h1><h2> </h2><h3></h3>").unwrap();
+        assert_eq!(issue.message, "Headings without text: H2 (heading #2), H3 (heading #3)");
+        assert!(run(&EmptyHeadingCheck, None, r#"<h1><img src="logo.png" alt="Acme"></h1>"#).is_none());
+    }
+
+    #[test]
+    fn h1_matching_title_ignores_case_and_spacing() {
+        let html = "<h1>Blue  Widgets</h1>";
+        assert!(run(&H1MatchesTitleCheck, Some("blue widgets"), html).is_some());
+        assert!(run(&H1MatchesTitleCheck, Some("Blue Widgets | Shop"), html).is_none());
+        assert!(run(&H1MatchesTitleCheck, None, html).is_none());
+    }
+
+    #[test]
+    fn long_headings_are_flagged() {
+        let long = "x".repeat(MAX_HEADING_CHARS + 1);
+        let issue = run(&LongHeadingCheck, None, &format!("<h1>Short</h1><h2>{long}</h2>")).unwrap();
+        assert_eq!(issue.details.as_deref(), Some("H2 (71 characters)"));
+        assert!(run(&LongHeadingCheck, None, "<h1>Short</h1>").is_none());
+    }
+}
@@ -0,0 +1,254 @@
+use crate::checker::{Check, CheckContext};
+use crate::contexts::analysis::{IssueSeverity, LinkType, NewIssue};
+use crate::extractor::page_extractor::{ExtractedResource, ResourceKind};
+
+/// How many URLs to name in an issue message; the rest go in the details.
+const MAX_LISTED: usize = 3;
+
+/// Mixed content only exists on pages served over https. A page that was
+/// redirected is judged by where it ended up.
+fn is_secure_page(ctx: &CheckContext) -> bool {
+    ctx.final_url.starts_with("https://")
+}
+
+/// Subresources loaded over http. Form targets aren't loaded with the
+/// page and are left to [`InsecureFormCheck`].
+fn insecure_resources<'a>(ctx: &CheckContext<'a>, active: bool) -> Vec<&'a ExtractedResource> {
+    let mut seen = std::collections::HashSet::new();
+    ctx.resources
+        .iter()
+        .filter(|r| r.kind != ResourceKind::FormAction)
+        .filter(|r| r.active == active && r.url.starts_with("http://"))
+        .filter(|r| seen.insert(r.url.as_str()))
+        .collect()
+}
+
+/// Message summary plus the full list for the issue details.
+fn summarize(urls: impl Iterator<Item = String> + Clone) -> (String, String) {
+    let listed: Vec<String> = urls.clone().take(MAX_LISTED).collect();
+    let total = urls.clone().count();
+    let more = if total > MAX_LISTED { format!(" and {} more", total - MAX_LISTED) } else { String::new() };
+    (format!("{}{}", listed.join(", "), more), urls.collect::<Vec<_>>().join("\n"))
+}
+
+fn mixed_content_issue(
+    ctx: &CheckContext,
+    resources: &[&ExtractedResource],
+    issue_type: &str,
+    severity: IssueSeverity,
+    consequence: &str,
+) -> Option<NewIssue> {
+    if resources.is_empty() { return None; }
+    let (listed, details) = summarize(resources.iter().map(|r| format!("{} ({})", r.url, r.kind.as_str())));
+    let mut issue = ctx.issue(
+        issue_type,
+        severity,
+        &format!("{} http:// resource(s) on an https page {}: {}", resources.len(), consequence, listed),
+    );
+    issue.details = Some(details);
+    Some(issue)
+}
+
+/// Scripts, stylesheets, frames and plugins loaded over http.
+pub struct ActiveMixedContentCheck;
+impl Check for ActiveMixedContentCheck {
+    fn id(&self) -> &str { "active-mixed-content" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        if !is_secure_page(ctx) { return None; }
+        mixed_content_issue(
+            ctx,
+            &insecure_resources(ctx, true),
+            "Active Mixed Content",
+            IssueSeverity::Critical,
+            "are blocked by browsers",
+        )
+    }
+}
+
+/// Images and media loaded over http.
+pub struct PassiveMixedContentCheck;
+impl Check for PassiveMixedContentCheck {
+    fn id(&self) -> &str { "passive-mixed-content" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        if !is_secure_page(ctx) { return None; }
+        mixed_content_issue(
+            ctx,
+            &insecure_resources(ctx, false),
+            "Passive Mixed Content",
+            IssueSeverity::Warning,
+            "mark the page as not fully secure",
+        )
+    }
+}
+
+/// Forms that submit over http. Browsers still load the page but warn
+/// before sending what was typed into the form unencrypted.
+pub struct InsecureFormCheck;
+impl Check for InsecureFormCheck {
+    fn id(&self) -> &str { "insecure-form-action" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        if !is_secure_page(ctx) { return None; }
+        let mut seen = std::collections::HashSet::new();
+        let insecure: Vec<&str> = ctx
+            .resources
+            .iter()
+            .filter(|r| r.kind == ResourceKind::FormAction && r.url.starts_with("http://"))
+            .map(|r| r.url.as_str())
+            .filter(|url| seen.insert(*url))
+            .collect();
+        if insecure.is_empty() { return None; }
+        let (listed, details) = summarize(insecure.iter().map(|u| u.to_string()));
+        let mut issue = ctx.issue(
+            "Insecure Form Actions",
+            IssueSeverity::Warning,
+            &format!(
+                "{} form(s) on an https page submit to http:// URLs; browsers warn before sending the data: {}",
+                insecure.len(),
+                listed,
+            ),
+        );
+        issue.details = Some(details);
+        Some(issue)
+    }
+}
+
+/// Internal links that send visitors from https back to http.
+pub struct InsecureInternalLinkCheck;
+impl Check for InsecureInternalLinkCheck {
+    fn id(&self) -> &str { "insecure-internal-links" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        if !is_secure_page(ctx) { return None; }
+        let mut seen = std::collections::HashSet::new();
+        let insecure: Vec<&str> = ctx
+            .links
+            .iter()
+            .filter(|l| matches!(l.link_type, LinkType::Internal | LinkType::Subdomain))
+            .filter(|l| l.href.starts_with("http://"))
+            .map(|l| l.href.as_str())
+            .filter(|href| seen.insert(*href))
+            .collect();
+        if insecure.is_empty() { return None; }
+        let (listed, details) = summarize(insecure.iter().map(|h| h.to_string()));
+        let mut issue = ctx.issue(
+            "Insecure Internal Links",
+            IssueSeverity::Warning,
+            &format!("{} internal link(s) point to http:// URLs: {}", insecure.len(), listed),
+        );
+        issue.details = Some(details);
+        Some(issue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::analysis::Page;
+    use crate::extractor::page_extractor::{ExtractedLink, PageExtractor};
+    use crate::service::auditor::SeoAuditDetails;
+    use chrono::Utc;
+    use scraper::Html;
+
+    fn make_page(url: &str) -> Page {
+        Page {
+            id: "p1".into(), job_id: "j1".into(),
+            url: url.into(), depth: crate::contexts::analysis::Depth::root(),
+            status_code: Some(200), content_type: Some("text/html".into()),
+            title: None, meta_description: None, canonical_url: None,
+            robots_meta: None, word_count: None, load_time_ms: None,
+            response_size_bytes: None, has_viewport: false,
+            has_structured_data: false, crawled_at: Utc::now(),
+            extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            ttfb_ms: None, download_ms: None,
+            weight: None,
+        }
+    }
+
+    fn run(check: &dyn Check, url: &str, html: &str) -> Option<NewIssue> {
+        let doc = Html::parse_document(html);
+        let resources = PageExtractor::extract_resources(&doc, url);
+        let (_, _, links): (_, _, Vec<ExtractedLink>) = PageExtractor::extract_links(&doc, url);
+        let page = make_page(url);
+        let details = SeoAuditDetails::default();
+        check.check(&CheckContext::new(&page, &details, "j1", "p1").with_references(&resources, &links))
+    }
+
+    /// Like [`run`] for a page requested at `url` that redirected to `final_url`.
+    fn run_redirected(check: &dyn Check, url: &str, final_url: &str, html: &str) -> Option<NewIssue> {
+        let doc = Html::parse_document(html);
+        let resources = PageExtractor::extract_resources(&doc, final_url);
+        let (_, _, links): (_, _, Vec<ExtractedLink>) = PageExtractor::extract_links(&doc, final_url);
+        let page = make_page(url);
+        let details = SeoAuditDetails::default();
+        let ctx = CheckContext::new(&page, &details, "j1", "p1")
+            .with_references(&resources, &links)
+            .with_final_url(final_url);
+        check.check(&ctx)
+    }
+
+    const MIXED: &str = r##"<html><head>
+        <script src="http://cdn.example.net/lib.js"></script>
+        <script src="http://cdn.example.net/lib.js"></script>
+        <link rel="stylesheet" href="https://example.com/ok.css">
+    </head><body>
+        <img src="http://img.example.net/a.png">
+        <form action="http://example.com/subscribe"></form>
+        <a href="http://example.com/about">About</a>
+        <a href="https://example.com/contact">Contact</a>
+        <a href="http://other.org/">Elsewhere</a>
+    </body></html>"##;
+
+    #[test]
+    fn active_mixed_content_is_critical_and_deduplicated() {
+        let issue = run(&ActiveMixedContentCheck, "https://example.com/", MIXED).unwrap();
+        assert_eq!(issue.severity, IssueSeverity::Critical);
+        assert!(issue.message.starts_with("1 http:// resource(s)"));
+        let details = issue.details.unwrap();
+        assert!(details.contains("http://cdn.example.net/lib.js (script)"));
+        assert!(!details.contains("subscribe"), "form targets aren't loaded with the page");
+    }
+
+    #[test]
+    fn insecure_form_action_is_a_warning_not_a_block() {
+        let issue = run(&InsecureFormCheck, "https://example.com/", MIXED).unwrap();
+        assert_eq!(issue.severity, IssueSeverity::Warning);
+        assert!(issue.message.contains("browsers warn before sending the data"));
+        assert_eq!(issue.details.as_deref(), Some("http://example.com/subscribe"));
+    }
+
+    #[test]
+    fn redirected_pages_are_judged_by_their_final_url() {
+        let issue = run_redirected(&ActiveMixedContentCheck, "http://example.com/", "https://example.com/", MIXED);
+        assert!(issue.is_some(), "http page that redirected to https has mixed content");
+
+        let issue = run_redirected(&ActiveMixedContentCheck, "https://example.com/", "http://example.com/", MIXED);
+        assert!(issue.is_none(), "https page that was downgraded to http has none");
+    }
+
+    #[test]
+    fn passive_mixed_content_is_a_warning() {
+        let issue = run(&PassiveMixedContentCheck, "https://example.com/", MIXED).unwrap();
+        assert_eq!(issue.severity, IssueSeverity::Warning);
+        assert!(issue.message.contains("http://img.example.net/a.png"));
+    }
+
+    #[test]
+    fn insecure_internal_links_ignore_external_and_https_links() {
+        let issue = run(&InsecureInternalLinkCheck, "https://example.com/", MIXED).unwrap();
+        assert_eq!(issue.details.as_deref(), Some("http://example.com/about"));
+    }
+
+    #[test]
+    fn http_pages_are_not_mixed_content() {
+        for check in [&ActiveMixedContentCheck as &dyn Check, &PassiveMixedContentCheck, &InsecureFormCheck, &InsecureInternalLinkCheck] {
+            assert!(run(check, "http://example.com/", MIXED).is_none(), "{} fired", check.id());
+        }
+    }
+
+    #[test]
+    fn summary_truncates_long_lists() {
+        let (listed, details) = summarize((0..5).map(|i| format!("http://x/{i}")));
+        assert_eq!(listed, "http://x/0, http://x/1, http://x/2 and 2 more");
+        assert_eq!(details.lines().count(), 5);
+    }
+}
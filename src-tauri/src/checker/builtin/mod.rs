@@ -1,5 +1,9 @@
 mod content;
+mod headers;
+mod headings;
+mod mixed_content;
 mod seo;
+mod weight;
 
 use crate::checker::Check;
 
@@ -19,5 +23,33 @@ pub fn all() -> Vec<Box<dyn Check>> {
         // Content checks derived from Page fields
         Box::new(content::WordCountCheck),
         Box::new(content::LoadTimeCheck),
+        Box::new(content::ServerResponseCheck),
+        Box::new(content::Soft404Check),
+        // Heading outline checks derived from the page's headings
+        Box::new(headings::MissingH1Check),
+        Box::new(headings::MultipleH1Check),
+        Box::new(headings::SkippedHeadingLevelCheck),
+        Box::new(headings::EmptyHeadingCheck),
+        Box::new(headings::H1MatchesTitleCheck),
+        Box::new(headings::LongHeadingCheck),
+        // Security & delivery checks derived from the response headers
+        Box::new(headers::HstsCheck),
+        Box::new(headers::ContentSecurityPolicyCheck),
+        Box::new(headers::ContentTypeOptionsCheck),
+        Box::new(headers::ReferrerPolicyCheck),
+        Box::new(headers::HtmlCachingCheck),
+        Box::new(headers::VaryCheck),
+        Box::new(headers::ServerDisclosureCheck),
+        // Mixed content checks derived from the page's references
+        Box::new(mixed_content::ActiveMixedContentCheck),
+        Box::new(mixed_content::PassiveMixedContentCheck),
+        Box::new(mixed_content::InsecureFormCheck),
+        Box::new(mixed_content::InsecureInternalLinkCheck),
+        // Performance checks derived from the optional resource pass
+        Box::new(weight::PageWeightCheck),
+        Box::new(weight::RequestCountCheck),
+        Box::new(weight::RenderBlockingCheck),
+        Box::new(weight::MinificationCheck),
+        Box::new(weight::ThirdPartyCheck),
     ]
 }
@@ -173,6 +173,9 @@ mod tests {
             response_size_bytes: Some(1000), has_viewport: true,
             has_structured_data: false, crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            ttfb_ms: None, download_ms: None,
+            weight: None,
         }
     }
 
@@ -0,0 +1,171 @@
+use crate::checker::{Check, CheckContext};
+use crate::contexts::analysis::{IssueSeverity, NewIssue};
+
+/// HTML, stylesheets, scripts and fonts together; images aren't weighed.
+const MAX_PAGE_BYTES: i64 = 2 * 1024 * 1024;
+const MAX_REQUESTS: i64 = 80;
+const MAX_THIRD_PARTY_SHARE: f64 = 0.5;
+/// A page with a handful of requests is not "dominated" by one widget.
+const MIN_THIRD_PARTY_REQUESTS: i64 = 5;
+
+fn with_details(mut issue: NewIssue, urls: &[String]) -> NewIssue {
+    issue.details = Some(urls.join("\n"));
+    issue
+}
+
+pub struct PageWeightCheck;
+impl Check for PageWeightCheck {
+    fn id(&self) -> &str { "page-weight" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let weight = ctx.page.weight.as_ref()?;
+        if weight.total_bytes <= MAX_PAGE_BYTES { return None; }
+        Some(ctx.issue(
+            "Heavy Page",
+            IssueSeverity::Warning,
+            &format!(
+                "Page weighs {} KB in HTML, CSS, JS and fonts (threshold: {} KB)",
+                weight.total_bytes / 1024,
+                MAX_PAGE_BYTES / 1024
+            ),
+        ))
+    }
+}
+
+pub struct RequestCountCheck;
+impl Check for RequestCountCheck {
+    fn id(&self) -> &str { "request-count" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let weight = ctx.page.weight.as_ref()?;
+        if weight.request_count <= MAX_REQUESTS { return None; }
+        Some(ctx.issue(
+            "Too Many Requests",
+            IssueSeverity::Info,
+            &format!("Page makes {} requests (threshold: {})", weight.request_count, MAX_REQUESTS),
+        ))
+    }
+}
+
+pub struct RenderBlockingCheck;
+impl Check for RenderBlockingCheck {
+    fn id(&self) -> &str { "render-blocking" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let weight = ctx.page.weight.as_ref()?;
+        if weight.render_blocking.is_empty() { return None; }
+        let issue = ctx.issue(
+            "Render-Blocking Resources",
+            IssueSeverity::Warning,
+            &format!(
+                "{} script(s) or stylesheet(s) in <head> delay the first paint; add async/defer or a media query",
+                weight.render_blocking.len()
+            ),
+        );
+        Some(with_details(issue, &weight.render_blocking))
+    }
+}
+
+pub struct MinificationCheck;
+impl Check for MinificationCheck {
+    fn id(&self) -> &str { "minification" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let weight = ctx.page.weight.as_ref()?;
+        if weight.unminified.is_empty() { return None; }
+        let issue = ctx.issue(
+            "Unminified Assets",
+            IssueSeverity::Info,
+            &format!("{} stylesheet(s) or script(s) are not minified", weight.unminified.len()),
+        );
+        Some(with_details(issue, &weight.unminified))
+    }
+}
+
+pub struct ThirdPartyCheck;
+impl Check for ThirdPartyCheck {
+    fn id(&self) -> &str { "third-party-share" }
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        let weight = ctx.page.weight.as_ref()?;
+        let share = weight.third_party_share();
+        if share <= MAX_THIRD_PARTY_SHARE || weight.third_party_requests < MIN_THIRD_PARTY_REQUESTS {
+            return None;
+        }
+        Some(ctx.issue(
+            "Third-Party Heavy",
+            IssueSeverity::Info,
+            &format!(
+                "{} of {} requests ({:.0}%) go to third-party domains",
+                weight.third_party_requests,
+                weight.request_count,
+                share * 100.0
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::analysis::{Page, PageWeight};
+    use crate::service::auditor::SeoAuditDetails;
+    use chrono::Utc;
+
+    fn make_page(weight: Option<PageWeight>) -> Page {
+        Page {
+            id: "p1".into(), job_id: "j1".into(),
+            url: "https://example.com".into(), depth: crate::contexts::analysis::Depth::root(),
+            status_code: Some(200), content_type: Some("text/html".into()),
+            title: None, meta_description: None, canonical_url: None,
+            robots_meta: None, word_count: None, load_time_ms: None,
+            response_size_bytes: None, has_viewport: false,
+            has_structured_data: false, crawled_at: Utc::now(),
+            extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            ttfb_ms: None, download_ms: None,
+            weight,
+        }
+    }
+
+    fn fired(weight: Option<PageWeight>) -> Vec<String> {
+        let page = make_page(weight);
+        let details = SeoAuditDetails::default();
+        let ctx = CheckContext::new(&page, &details, "j1", "p1");
+        let checks: [&dyn Check; 5] = [
+            &PageWeightCheck, &RequestCountCheck, &RenderBlockingCheck,
+            &MinificationCheck, &ThirdPartyCheck,
+        ];
+        checks.iter().filter_map(|c| c.check(&ctx)).map(|i| i.issue_type).collect()
+    }
+
+    #[test]
+    fn pages_without_a_resource_pass_are_skipped() {
+        assert!(fired(None).is_empty());
+    }
+
+    #[test]
+    fn lean_page_passes() {
+        let weight = PageWeight {
+            total_bytes: 200_000, html_bytes: 30_000,
+            request_count: 20, third_party_requests: 3,
+            ..Default::default()
+        };
+        assert!(fired(Some(weight)).is_empty());
+    }
+
+    #[test]
+    fn bloated_page_fires_every_check() {
+        let weight = PageWeight {
+            total_bytes: 5 * 1024 * 1024, html_bytes: 90_000,
+            request_count: 120, third_party_requests: 70,
+            render_blocking: vec!["https://example.com/app.js".into()],
+            unminified: vec!["https://example.com/site.css".into()],
+        };
+        assert_eq!(fired(Some(weight)), [
+            "Heavy Page", "Too Many Requests", "Render-Blocking Resources",
+            "Unminified Assets", "Third-Party Heavy",
+        ]);
+    }
+
+    #[test]
+    fn third_party_share_needs_enough_requests() {
+        let weight = PageWeight { request_count: 4, third_party_requests: 3, ..Default::default() };
+        assert!(fired(Some(weight)).is_empty());
+    }
+}
@@ -3,33 +3,108 @@
 //! page analysis.
 //!
 //! Evaluation reuses `pattern_engine::resolve_field` and
-//! `evaluate_condition` — the condition logic is identical to report
+//! `CompiledCondition` — the condition logic is identical to report
 //! patterns so a user referencing `tag:og_image` in a custom check
 //! sees the same semantics as a report pattern referencing the same tag.
 //!
 //! Tag substitution in `message_template`: after a check fires,
 //! `{tag.X}` placeholders in the message are replaced with the
 //! page's extracted value for tag `X`. This is the chunk-3 deliverable.
+//!
+//! Site-scoped checks don't run per page: [`SiteAggregateCheck`] runs
+//! once per job, aggregating over every page the condition matches.
 
 use std::collections::HashMap;
 
-use crate::checker::{Check, CheckContext};
-use crate::contexts::analysis::NewIssue;
-use crate::contexts::extension::CustomCheck;
+use crate::checker::script::ScriptCheck;
+use crate::checker::{Check, CheckContext, CheckerRegistry};
+use crate::contexts::analysis::{NewIssue, Page};
+use crate::contexts::extension::{
+    AggregateFunction, CheckScope, ConditionError, CustomCheck, CustomCheckParams, Operator,
+    SiteAggregate,
+};
 use crate::contexts::report::services::pattern_engine::{
-    evaluate_condition, resolve_field, FieldValue,
+    evaluate_condition, resolve_field, validate_threshold, CompiledCondition, FieldValue,
+    HeadingIndex,
 };
 
+/// Check that a custom check's rule or script compiles and, for a site
+/// check, that its aggregate can be compared. Run before a check is saved.
+pub(crate) fn validate(params: &CustomCheckParams) -> Result<(), ConditionError> {
+    if let Some(script) = &params.script {
+        if params.scope == CheckScope::Site {
+            return Err(ConditionError::ScriptOnSiteCheck);
+        }
+        return super::script::validate(script);
+    }
+    CompiledCondition::for_rule(
+        &params.field,
+        &params.operator,
+        params.threshold.as_deref(),
+        params.condition.as_ref(),
+    )?;
+    if params.scope == CheckScope::Site {
+        let aggregate = params.aggregate.as_ref().ok_or(ConditionError::MissingAggregate)?;
+        validate_aggregate(aggregate)?;
+    }
+    Ok(())
+}
+
+/// Aggregates are numbers, so only the numeric comparisons apply.
+fn validate_aggregate(aggregate: &SiteAggregate) -> Result<(), ConditionError> {
+    if !matches!(
+        aggregate.operator,
+        Operator::Eq | Operator::Lt | Operator::Gt | Operator::Between
+    ) {
+        return Err(ConditionError::UnsupportedAggregateOperator(aggregate.operator.clone()));
+    }
+    validate_threshold(&aggregate.operator, aggregate.threshold.as_deref())
+}
+
+/// Register the enabled page checks among `checks` on `registry` and
+/// return the enabled site checks, which run once per job instead. Rule
+/// checks are wrapped in a [`CustomCheckAdapter`], checks with a script
+/// in a [`ScriptCheck`].
+pub fn register(
+    registry: &mut CheckerRegistry,
+    checks: Vec<CustomCheck>,
+) -> Vec<SiteAggregateCheck> {
+    let mut site_checks = Vec::new();
+    for check in checks.into_iter().filter(|c| c.enabled) {
+        match check.scope {
+            CheckScope::Page if check.script.is_some() => {
+                if let Some(script) = ScriptCheck::new(check) {
+                    registry.register(Box::new(script));
+                }
+            }
+            CheckScope::Page => registry.register(Box::new(CustomCheckAdapter::new(check))),
+            CheckScope::Site => site_checks.extend(SiteAggregateCheck::new(check)),
+        }
+    }
+    site_checks
+}
+
 /// Wraps a user-defined [`CustomCheck`] and evaluates it per-page using
 /// the same field resolver and condition evaluator as the report
 /// pattern engine.
 pub struct CustomCheckAdapter {
     check: CustomCheck,
+    /// `None` when the stored condition doesn't compile; the check then
+    /// never fires.
+    condition: Option<CompiledCondition>,
 }
 
 impl CustomCheckAdapter {
     pub fn new(check: CustomCheck) -> Self {
-        Self { check }
+        let condition = CompiledCondition::for_rule(
+            &check.field,
+            &check.operator,
+            check.threshold.as_deref(),
+            check.condition.as_ref(),
+        )
+        .inspect_err(|e| tracing::warn!("custom check '{}' is disabled: {e}", check.name))
+        .ok();
+        Self { check, condition }
     }
 }
 
@@ -39,19 +114,22 @@ impl Check for CustomCheckAdapter {
     }
 
     fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
-        // resolve_field needs an h1_counts map. Custom checks rarely
-        // reference h1_count (they use tag: fields), and headings
-        // aren't in CheckContext today, so we pass an empty map.
-        // h1_count will resolve to 0, which is documented as a known
-        // limitation until CheckContext carries heading info.
-        let empty_h1: HashMap<String, usize> = HashMap::new();
-
-        let value = resolve_field(ctx.page, &empty_h1, &self.check.field);
-        let threshold = self.check.threshold.as_deref();
+        // `h1_count` and `headings` read the page's extracted outline;
+        // without one they resolve as for a page with no headings.
+        let headings = ctx
+            .headings
+            .map(|headings| {
+                HeadingIndex::for_page(
+                    &ctx.page.id,
+                    headings.iter().map(|h| (h.level, h.text.as_str())),
+                )
+            })
+            .unwrap_or_default();
 
-        if !evaluate_condition(&value, &self.check.operator, threshold) {
+        if !self.condition.as_ref()?.matches(ctx.page, &headings) {
             return None;
         }
+        let value = resolve_field(ctx.page, &headings, &self.check.field);
 
         // The check fired — build the issue message by substituting
         // {tag.X} placeholders against the page's extracted_data, and
@@ -69,20 +147,186 @@ impl Check for CustomCheckAdapter {
             severity: self.check.severity,
             message,
             details: None,
+            custom_check_id: Some(self.check.id.clone()),
         })
     }
 }
 
-/// Replace `{value}` with the resolved field value and `{tag.X}` with
-/// the page's extracted value for tag X.
-fn substitute_message(
-    template: &str,
-    value: &FieldValue,
-    extracted_data: &HashMap<String, serde_json::Value>,
-) -> String {
-    // First pass: replace {value} with the evaluated field's display form.
-    let value_str = match value {
-        FieldValue::Null => String::new(),
+/// Pages listed in a site-level issue's details; the rest are counted.
+const MAX_DETAIL_PAGES: usize = 50;
+
+/// A `scope: site` [`CustomCheck`], run once per job over every page.
+///
+/// The check's condition picks the pages to aggregate over and its
+/// `field` supplies the values for `distinct`, `duplicates`, `min`,
+/// `max` and `avg`. When the aggregate satisfies the check's
+/// [`SiteAggregate`] comparison, one site-level issue is raised with
+/// the contributing pages listed in its details. A `percent` that's too
+/// low lists the pages outside the share instead, as those are the ones
+/// to fix.
+pub struct SiteAggregateCheck {
+    check: CustomCheck,
+    condition: CompiledCondition,
+    aggregate: SiteAggregate,
+}
+
+impl SiteAggregateCheck {
+    /// `None` for page checks, and for site checks whose rule or
+    /// aggregate doesn't compile — those never fire.
+    pub fn new(check: CustomCheck) -> Option<Self> {
+        if check.scope != CheckScope::Site {
+            return None;
+        }
+        let compiled = check
+            .aggregate
+            .clone()
+            .ok_or(ConditionError::MissingAggregate)
+            .and_then(|aggregate| {
+                validate_aggregate(&aggregate)?;
+                let condition = CompiledCondition::for_rule(
+                    &check.field,
+                    &check.operator,
+                    check.threshold.as_deref(),
+                    check.condition.as_ref(),
+                )?;
+                Ok((condition, aggregate))
+            });
+        match compiled {
+            Ok((condition, aggregate)) => Some(Self { check, condition, aggregate }),
+            Err(e) => {
+                tracing::warn!("custom check '{}' is disabled: {e}", check.name);
+                None
+            }
+        }
+    }
+
+    /// Aggregate over `pages` and raise the check's issue if the result
+    /// satisfies its comparison. `None` also when there's nothing to
+    /// aggregate: no pages, or no numeric values for `min`/`max`/`avg`.
+    pub fn run(&self, job_id: &str, pages: &[Page], headings: &HeadingIndex) -> Option<NewIssue> {
+        let (matching, unmatched): (Vec<&Page>, Vec<&Page>) =
+            pages.iter().partition(|page| self.condition.matches(page, headings));
+        let matching: Vec<(&Page, FieldValue)> = matching
+            .into_iter()
+            .map(|page| (page, resolve_field(page, headings, &self.check.field)))
+            .collect();
+
+        let (value, mut contributing) = aggregate(self.aggregate.function, pages.len(), &matching)?;
+        let value = FieldValue::Number(value);
+        if !evaluate_condition(&value, &self.aggregate.operator, self.aggregate.threshold.as_deref()) {
+            return None;
+        }
+        if self.aggregate.function == AggregateFunction::Percent && self.aggregate.operator == Operator::Lt {
+            contributing = unmatched.iter().map(|page| page.url.clone()).collect();
+        }
+
+        Some(NewIssue {
+            job_id: job_id.to_string(),
+            page_id: None,
+            issue_type: self.check.name.clone(),
+            severity: self.check.severity,
+            message: substitute_message(&self.check.message_template, &value, &HashMap::new()),
+            details: detail_lines(contributing),
+            custom_check_id: Some(self.check.id.clone()),
+        })
+    }
+}
+
+/// The first [`MAX_DETAIL_PAGES`] lines, and how many more there are.
+fn detail_lines(mut lines: Vec<String>) -> Option<String> {
+    if lines.is_empty() {
+        return None;
+    }
+    let more = lines.len().saturating_sub(MAX_DETAIL_PAGES);
+    lines.truncate(MAX_DETAIL_PAGES);
+    if more > 0 {
+        lines.push(format!("+{more} more"));
+    }
+    Some(lines.join("\n"))
+}
+
+/// Compute `function` over the matching pages, returning the aggregate
+/// and one details line per contributing page: its URL, followed by its
+/// value when the function reads one.
+fn aggregate(
+    function: AggregateFunction,
+    total: usize,
+    matching: &[(&Page, FieldValue)],
+) -> Option<(f64, Vec<String>)> {
+    let urls = || matching.iter().map(|(page, _)| page.url.clone()).collect::<Vec<_>>();
+    let with_value = |page: &Page, value: &str| format!("{}: {value}", page.url);
+    let texts = || {
+        matching.iter().filter_map(|(page, value)| match value {
+            FieldValue::Null | FieldValue::Unknown => None,
+            value => Some((*page, display_value(value))),
+        })
+    };
+    let numbers = || {
+        matching.iter().filter_map(|(page, value)| match value {
+            FieldValue::Number(n) => Some((*page, *n)),
+            _ => None,
+        })
+    };
+
+    match function {
+        AggregateFunction::Count => Some((matching.len() as f64, urls())),
+        AggregateFunction::Percent => {
+            if total == 0 {
+                return None;
+            }
+            Some((matching.len() as f64 * 100.0 / total as f64, urls()))
+        }
+        AggregateFunction::Distinct => {
+            let mut seen = std::collections::HashSet::new();
+            let lines: Vec<String> = texts()
+                .map(|(page, text)| {
+                    let line = with_value(page, &text);
+                    seen.insert(text);
+                    line
+                })
+                .collect();
+            Some((seen.len() as f64, lines))
+        }
+        AggregateFunction::Duplicates => {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for (_, text) in texts() {
+                *counts.entry(text).or_insert(0) += 1;
+            }
+            let lines: Vec<String> = texts()
+                .filter(|(_, text)| counts[text] > 1)
+                .map(|(page, text)| with_value(page, &text))
+                .collect();
+            Some((lines.len() as f64, lines))
+        }
+        AggregateFunction::Min | AggregateFunction::Max => {
+            let pick: fn(f64, f64) -> f64 = if function == AggregateFunction::Min { f64::min } else { f64::max };
+            let extreme = numbers().map(|(_, n)| n).reduce(pick)?;
+            let lines = numbers()
+                .filter(|(_, n)| *n == extreme)
+                .map(|(page, n)| with_value(page, &display_value(&FieldValue::Number(n))))
+                .collect();
+            Some((extreme, lines))
+        }
+        AggregateFunction::Avg => {
+            let values: Vec<(&Page, f64)> = numbers().collect();
+            if values.is_empty() {
+                return None;
+            }
+            let avg = values.iter().map(|(_, n)| n).sum::<f64>() / values.len() as f64;
+            let lines = values
+                .into_iter()
+                .map(|(page, n)| with_value(page, &display_value(&FieldValue::Number(n))))
+                .collect();
+            Some((avg, lines))
+        }
+    }
+}
+
+/// A field value as it reads in a message: whole numbers without a
+/// fraction, others to two places.
+fn display_value(value: &FieldValue) -> String {
+    match value {
+        FieldValue::Null | FieldValue::Unknown => String::new(),
         FieldValue::Text(s) => s.clone(),
         FieldValue::Number(n) => {
             if n.fract() == 0.0 {
@@ -92,9 +336,18 @@ fn substitute_message(
             }
         }
         FieldValue::Bool(b) => b.to_string(),
-    };
+    }
+}
 
-    let mut result = template.replace("{value}", &value_str);
+/// Replace `{value}` with the resolved field value and `{tag.X}` with
+/// the page's extracted value for tag X.
+fn substitute_message(
+    template: &str,
+    value: &FieldValue,
+    extracted_data: &HashMap<String, serde_json::Value>,
+) -> String {
+    // First pass: replace {value} with the evaluated field's display form.
+    let mut result = template.replace("{value}", &display_value(value));
 
     // Second pass: replace every {tag.X} with extracted_data[X].
     // Scan for `{tag.` prefix, find the closing `}`, look up the key.
@@ -167,11 +420,15 @@ mod tests {
             robots_meta: None,
             word_count: Some(500),
             load_time_ms: Some(1200),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: Some(10000),
             has_viewport: true,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data,
+            response_headers: Default::default(),
+            weight: None,
         }
     }
 
@@ -183,6 +440,10 @@ mod tests {
             field: field.into(),
             operator: op,
             threshold: threshold.map(|s| s.into()),
+            condition: None,
+            scope: CheckScope::Page,
+            aggregate: None,
+            script: None,
             message_template: template.into(),
             enabled: true,
         }
@@ -291,4 +552,239 @@ mod tests {
 
         assert_eq!(issue.message, "Title found: Test Title");
     }
+
+    #[test]
+    fn heading_fields_read_the_extracted_outline() {
+        use crate::extractor::page_extractor::ExtractedHeading;
+
+        let page = make_page_with_tags(vec![]);
+        let details = passing_details();
+        let headings = vec![
+            ExtractedHeading { level: 1, text: "Welcome".into(), position: 0 },
+            ExtractedHeading { level: 1, text: "Again".into(), position: 1 },
+        ];
+        let ctx = CheckContext::new(&page, &details, "j1", "p1").with_headings(&headings);
+
+        let check = make_check("h1_count", Operator::Gt, Some("1"), "{value} H1s");
+        let issue = CustomCheckAdapter::new(check).check(&ctx).expect("two H1s");
+        assert_eq!(issue.message, "2 H1s");
+
+        let check = make_check("headings", Operator::Contains, Some("H1: Again"), "Outline:\n{value}");
+        let issue = CustomCheckAdapter::new(check).check(&ctx).expect("outline contains the H1");
+        assert_eq!(issue.message, "Outline:\nH1: Welcome\nH1: Again");
+    }
+
+    #[test]
+    fn compound_condition_replaces_the_single_rule() {
+        use crate::contexts::extension::RuleCondition;
+
+        let page = make_page_with_tags(vec![("og_title", serde_json::json!("Test Title"))]);
+        let details = passing_details();
+        let ctx = CheckContext::new(&page, &details, "j1", "p1");
+
+        // title contains "Test" AND word_count < 1000 AND title == tag:og_title
+        let mut check = make_check("title", Operator::Missing, None, "Thin page: {value}");
+        check.condition = Some(RuleCondition::All {
+            children: vec![
+                RuleCondition::compare("title", &Operator::Contains, Some("Test")),
+                RuleCondition::compare("word_count", &Operator::Lt, Some("1000")),
+                RuleCondition::Compare {
+                    field: "title".into(),
+                    operator: Operator::Eq,
+                    threshold: None,
+                    compare_field: Some("tag:og_title".into()),
+                },
+            ],
+        });
+        let issue = CustomCheckAdapter::new(check.clone()).check(&ctx).expect("all hold");
+        assert_eq!(issue.message, "Thin page: Test Title");
+
+        check.condition = Some(RuleCondition::Not {
+            inner: Box::new(RuleCondition::compare("canonical_url", &Operator::Matches, Some("^https://www\\."))),
+        });
+        assert!(CustomCheckAdapter::new(check).check(&ctx).is_some(), "page has no canonical");
+    }
+
+    #[test]
+    fn invalid_condition_never_fires() {
+        let page = make_page_with_tags(vec![]);
+        let details = passing_details();
+        let ctx = CheckContext::new(&page, &details, "j1", "p1");
+
+        let check = make_check("title", Operator::Matches, Some("(unclosed"), "m");
+        assert!(CustomCheckAdapter::new(check).check(&ctx).is_none());
+    }
+
+    fn site_check(
+        field: &str,
+        op: Operator,
+        threshold: Option<&str>,
+        function: AggregateFunction,
+        aggregate_op: Operator,
+        aggregate_threshold: &str,
+    ) -> CustomCheck {
+        CustomCheck {
+            scope: CheckScope::Site,
+            aggregate: Some(SiteAggregate {
+                function,
+                operator: aggregate_op,
+                threshold: Some(aggregate_threshold.into()),
+            }),
+            ..make_check(field, op, threshold, "{value}")
+        }
+    }
+
+    fn site_pages() -> Vec<Page> {
+        let page = |url: &str, title: &str, words: i64, og: bool| {
+            let tags = if og { vec![("og_image", serde_json::json!("https://img.jpg"))] } else { vec![] };
+            Page {
+                id: url.into(),
+                url: url.into(),
+                title: Some(title.into()),
+                word_count: Some(words),
+                ..make_page_with_tags(tags)
+            }
+        };
+        vec![
+            page("https://example.com/a", "Home", 100, true),
+            page("https://example.com/b", "Home", 300, false),
+            page("https://example.com/c", "Home", 200, true),
+            page("https://example.com/d", "About", 400, false),
+        ]
+    }
+
+    fn run_site(check: CustomCheck) -> Option<NewIssue> {
+        SiteAggregateCheck::new(check)
+            .expect("valid site check")
+            .run("j1", &site_pages(), &HeadingIndex::default())
+    }
+
+    #[test]
+    fn site_check_percent_lists_the_offending_pages() {
+        let check = site_check("tag:og_image", Operator::Present, None, AggregateFunction::Percent, Operator::Lt, "80");
+        let issue = run_site(check).expect("only half the pages have og:image");
+        assert_eq!(issue.page_id, None);
+        assert_eq!(issue.message, "50");
+        assert_eq!(issue.details.as_deref(), Some("https://example.com/b\nhttps://example.com/d"));
+
+        let check = site_check("tag:og_image", Operator::Present, None, AggregateFunction::Percent, Operator::Lt, "50");
+        assert!(run_site(check).is_none());
+
+        // Too high a share of missing images: the matching pages are the offenders.
+        let check = site_check("tag:og_image", Operator::Missing, None, AggregateFunction::Percent, Operator::Gt, "25");
+        let issue = run_site(check).unwrap();
+        assert_eq!(issue.details.as_deref(), Some("https://example.com/b\nhttps://example.com/d"));
+    }
+
+    #[test]
+    fn site_check_details_are_capped() {
+        let pages: Vec<Page> = (0..MAX_DETAIL_PAGES + 7)
+            .map(|i| Page { id: i.to_string(), url: format!("https://example.com/{i}"), ..make_page_with_tags(vec![]) })
+            .collect();
+        let check = site_check("tag:og_image", Operator::Missing, None, AggregateFunction::Count, Operator::Gt, "0");
+        let issue = SiteAggregateCheck::new(check).unwrap().run("j1", &pages, &HeadingIndex::default()).unwrap();
+        let details = issue.details.unwrap();
+        assert_eq!(details.lines().count(), MAX_DETAIL_PAGES + 1);
+        assert!(details.ends_with("\n+7 more"));
+    }
+
+    #[test]
+    fn site_check_duplicates_and_distinct_read_the_field() {
+        let check = site_check("title", Operator::Present, None, AggregateFunction::Duplicates, Operator::Gt, "2");
+        let issue = run_site(check).expect("three pages share a title");
+        assert_eq!(issue.message, "3");
+        assert_eq!(
+            issue.details.as_deref(),
+            Some("https://example.com/a: Home\nhttps://example.com/b: Home\nhttps://example.com/c: Home"),
+        );
+
+        let check = site_check("title", Operator::Present, None, AggregateFunction::Distinct, Operator::Eq, "2");
+        assert_eq!(run_site(check).unwrap().message, "2");
+    }
+
+    #[test]
+    fn site_check_numeric_aggregates_skip_non_numbers() {
+        let check = site_check("word_count", Operator::Gt, Some("150"), AggregateFunction::Min, Operator::Between, "100,250");
+        let issue = run_site(check).expect("smallest matching count is 200");
+        assert_eq!(issue.details.as_deref(), Some("https://example.com/c: 200"));
+
+        let check = site_check("word_count", Operator::Present, None, AggregateFunction::Avg, Operator::Gt, "200");
+        assert_eq!(run_site(check).unwrap().message, "250");
+
+        // `title` is text, so there's nothing to average.
+        let check = site_check("title", Operator::Present, None, AggregateFunction::Max, Operator::Gt, "0");
+        assert!(run_site(check).is_none());
+    }
+
+    #[test]
+    fn site_checks_need_a_comparable_aggregate() {
+        let page_check = make_check("title", Operator::Present, None, "m");
+        assert!(SiteAggregateCheck::new(page_check.clone()).is_none());
+
+        let params = |check: CustomCheck| CustomCheckParams {
+            name: check.name,
+            severity: check.severity,
+            field: check.field,
+            operator: check.operator,
+            threshold: check.threshold,
+            condition: check.condition,
+            scope: check.scope,
+            aggregate: check.aggregate,
+            script: check.script,
+            message_template: check.message_template,
+            enabled: check.enabled,
+        };
+        assert!(validate(&params(page_check.clone())).is_ok());
+
+        let missing = CustomCheck { scope: CheckScope::Site, ..page_check };
+        assert_eq!(validate(&params(missing)), Err(ConditionError::MissingAggregate));
+
+        let contains = site_check("title", Operator::Present, None, AggregateFunction::Count, Operator::Contains, "1");
+        assert_eq!(
+            validate(&params(contains.clone())),
+            Err(ConditionError::UnsupportedAggregateOperator(Operator::Contains)),
+        );
+        assert!(SiteAggregateCheck::new(contains).is_none());
+
+        let typo = site_check("title", Operator::Present, None, AggregateFunction::Count, Operator::Gt, "1o");
+        assert!(matches!(validate(&params(typo)), Err(ConditionError::InvalidNumber { .. })));
+    }
+
+    #[test]
+    fn script_checks_validate_the_script_instead_of_the_rule() {
+        // A script check has no rule, so its empty field doesn't matter.
+        let script = |source: &str| CustomCheck {
+            script: Some(source.into()),
+            ..make_check("", Operator::Present, None, "")
+        };
+        let params = |check: CustomCheck| CustomCheckParams::from(&check);
+        assert!(validate(&params(script("\"no title\""))).is_ok());
+        assert!(matches!(validate(&params(script("let"))), Err(ConditionError::InvalidScript(_))));
+
+        let site = CustomCheck { scope: CheckScope::Site, ..script("()") };
+        assert_eq!(validate(&params(site)), Err(ConditionError::ScriptOnSiteCheck));
+    }
+
+    #[test]
+    fn register_skips_disabled_checks_and_keeps_site_checks_aside() {
+        let page = make_page_with_tags(vec![]);
+        let details = passing_details();
+        let ctx = CheckContext::new(&page, &details, "j1", "p1");
+
+        let rule = make_check("tag:og_image", Operator::Missing, None, "Missing OG image");
+        let script = CustomCheck {
+            name: "Script".into(),
+            script: Some(r#"["one", "two"]"#.into()),
+            ..make_check("", Operator::Present, None, "")
+        };
+        let disabled = CustomCheck { enabled: false, ..rule.clone() };
+        let site = site_check("title", Operator::Present, None, AggregateFunction::Count, Operator::Gt, "0");
+
+        let mut registry = CheckerRegistry::default();
+        let site_checks = register(&mut registry, vec![rule, script, disabled, site]);
+
+        assert_eq!(site_checks.len(), 1);
+        let messages: Vec<String> = registry.run(&ctx).into_iter().map(|i| i.message).collect();
+        assert_eq!(messages, ["Missing OG image", "one", "two"]);
+    }
 }
@@ -1,7 +1,12 @@
 pub mod builtin;
 pub(crate) mod custom;
+pub(crate) mod script;
+pub mod site;
 
 use crate::contexts::analysis::{IssueSeverity, NewIssue, Page};
+use crate::extractor::page_extractor::{
+    ExtractedHeading, ExtractedImage, ExtractedLink, ExtractedResource,
+};
 use crate::service::auditor::SeoAuditDetails;
 
 /// All the data a `Check` needs to evaluate a page.
@@ -10,6 +15,25 @@ pub struct CheckContext<'a> {
     pub seo_details: &'a SeoAuditDetails,
     pub job_id: &'a str,
     pub page_id: &'a str,
+    /// Where the page ended up after redirects; the page's own URL unless
+    /// set with [`with_final_url`](Self::with_final_url).
+    pub final_url: &'a str,
+    /// Subresources the page loads. Empty unless set with
+    /// [`with_references`](Self::with_references).
+    pub resources: &'a [ExtractedResource],
+    /// Anchors the page links to. Empty unless set with
+    /// [`with_references`](Self::with_references).
+    pub links: &'a [ExtractedLink],
+    /// Images the page embeds. Empty unless set with
+    /// [`with_images`](Self::with_images).
+    pub images: &'a [ExtractedImage],
+    /// The page's headings in document order; `None` unless set with
+    /// [`with_headings`](Self::with_headings), so heading checks skip
+    /// pages whose outline wasn't extracted.
+    pub headings: Option<&'a [ExtractedHeading]>,
+    /// Why the page looks like a soft 404, when the job's not-found probe
+    /// says it does.
+    pub soft_404: Option<&'a str>,
 }
 
 impl<'a> CheckContext<'a> {
@@ -19,7 +43,53 @@ impl<'a> CheckContext<'a> {
         job_id: &'a str,
         page_id: &'a str,
     ) -> Self {
-        Self { page, seo_details, job_id, page_id }
+        Self {
+            page,
+            seo_details,
+            job_id,
+            page_id,
+            final_url: &page.url,
+            resources: &[],
+            links: &[],
+            images: &[],
+            headings: None,
+            soft_404: None,
+        }
+    }
+
+    /// Attach the references extracted from the page's HTML.
+    pub fn with_references(
+        mut self,
+        resources: &'a [ExtractedResource],
+        links: &'a [ExtractedLink],
+    ) -> Self {
+        self.resources = resources;
+        self.links = links;
+        self
+    }
+
+    /// Attach the URL the page was served from after redirects.
+    pub fn with_final_url(mut self, final_url: &'a str) -> Self {
+        self.final_url = final_url;
+        self
+    }
+
+    /// Attach the headings extracted from the page's HTML.
+    pub fn with_headings(mut self, headings: &'a [ExtractedHeading]) -> Self {
+        self.headings = Some(headings);
+        self
+    }
+
+    /// Attach the images extracted from the page's HTML.
+    pub fn with_images(mut self, images: &'a [ExtractedImage]) -> Self {
+        self.images = images;
+        self
+    }
+
+    /// Attach the soft-404 verdict for the page.
+    pub fn with_soft_404(mut self, reason: Option<&'a str>) -> Self {
+        self.soft_404 = reason;
+        self
     }
 
     /// Helper to build a `NewIssue` scoped to this context.
@@ -36,6 +106,7 @@ impl<'a> CheckContext<'a> {
             severity,
             message: message.to_string(),
             details: None,
+            custom_check_id: None,
         }
     }
 }
@@ -44,6 +115,12 @@ impl<'a> CheckContext<'a> {
 pub trait Check: Send + Sync {
     fn id(&self) -> &str;
     fn check(&self, ctx: &CheckContext) -> Option<NewIssue>;
+
+    /// Every issue the check raises on the page. Checks that can fail
+    /// more than once per page, like scripts, override this.
+    fn issues(&self, ctx: &CheckContext) -> Vec<NewIssue> {
+        self.check(ctx).into_iter().collect()
+    }
 }
 
 /// Runs all registered checks against a page and collects the resulting issues.
@@ -73,7 +150,7 @@ impl CheckerRegistry {
     pub fn run(&self, ctx: &CheckContext) -> Vec<NewIssue> {
         self.checks
             .iter()
-            .filter_map(|c| c.check(ctx))
+            .flat_map(|c| c.issues(ctx))
             .collect()
     }
 
@@ -122,11 +199,15 @@ mod tests {
             robots_meta: None,
             word_count: Some(500),
             load_time_ms: Some(1000),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: Some(10000),
             has_viewport: true,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            weight: None,
         }
     }
 
@@ -0,0 +1,627 @@
+//! Scriptable custom checks. A [`CustomCheck`] with a `script` runs it
+//! through a sandboxed [Rhai](https://rhai.rs) engine on every page
+//! instead of evaluating a rule.
+//!
+//! The script sees the page as the constant `page`: its fields (`url`,
+//! `status_code`, `title`, `word_count`, ...), `tags` (the extracted
+//! data), `headers`, `headings`, `links` and `images`. Whatever it
+//! returns becomes the page's issues:
+//!
+//! - `()` — no issues;
+//! - a string — one issue with that message;
+//! - a map — one issue: `#{ message: "...", severity: "critical", details: "..." }`,
+//!   with `severity` and `details` optional;
+//! - an array of those — one issue each.
+//!
+//! ```rhai
+//! let missing = page.images.filter(|img| img.alt == ());
+//! if missing.len() > 3 {
+//!     #{ message: `${missing.len()} images have no alt text`,
+//!        details: missing.map(|img| img.src).reduce(|a, b| a + "\n" + b) }
+//! }
+//! ```
+//!
+//! The sandbox has no file, module or `eval` access, and caps the
+//! operations, call depth, variables, and string, array and map sizes a
+//! script can reach, so a runaway script fails instead of stalling the
+//! crawl or filling memory.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, Scope, AST};
+use serde::{Deserialize, Serialize};
+
+use crate::checker::{Check, CheckContext};
+use crate::contexts::analysis::{Heading, Image, IssueSeverity, Link, NewIssue, Page};
+use crate::contexts::extension::{ConditionError, CustomCheck, ScriptDryRun, ScriptIssue};
+
+/// Operations a script may run per page — a CPU limit that doesn't
+/// depend on the machine.
+const MAX_OPERATIONS: u64 = 1_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+/// The size caps count everything nested in a value, so no value holds
+/// more than about 2 MB, and a script keeps at most `MAX_VARIABLES` of
+/// them at once — together a run stays within some 64 MB.
+const MAX_STRING_SIZE: usize = 512 * 1024;
+const MAX_ARRAY_SIZE: usize = 50_000;
+const MAX_MAP_SIZE: usize = 10_000;
+const MAX_VARIABLES: usize = 32;
+/// Issues kept from one script run; the rest are dropped.
+const MAX_ISSUES: usize = 100;
+
+/// A Rhai engine without access to anything outside the script, limited
+/// as described in the module docs. `print` and `debug` go to the log.
+fn sandbox() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_ARRAY_SIZE)
+        .set_max_map_size(MAX_MAP_SIZE)
+        .set_max_variables(MAX_VARIABLES)
+        .on_print(|text| tracing::debug!("[SCRIPT] {text}"))
+        .on_debug(|text, _, pos| tracing::debug!("[SCRIPT] {pos}: {text}"));
+    engine.disable_symbol("eval");
+    engine
+}
+
+/// Check that a script compiles. Run before a check is saved.
+pub(crate) fn validate(script: &str) -> Result<(), ConditionError> {
+    sandbox()
+        .compile(script)
+        .map(drop)
+        .map_err(|e| ConditionError::InvalidScript(e.to_string()))
+}
+
+/// The page as a script sees it.
+#[derive(Debug, Serialize)]
+pub(crate) struct ScriptPage<'a> {
+    url: &'a str,
+    depth: i64,
+    status_code: Option<i64>,
+    content_type: Option<&'a str>,
+    title: Option<&'a str>,
+    meta_description: Option<&'a str>,
+    canonical_url: Option<&'a str>,
+    robots_meta: Option<&'a str>,
+    word_count: Option<i64>,
+    load_time_ms: Option<i64>,
+    response_size_bytes: Option<i64>,
+    has_viewport: bool,
+    has_structured_data: bool,
+    tags: &'a HashMap<String, serde_json::Value>,
+    /// Lowercased header names; repeated headers are joined with `, `.
+    headers: BTreeMap<&'a str, String>,
+    headings: Vec<ScriptHeading<'a>>,
+    links: Vec<ScriptLink<'a>>,
+    images: Vec<ScriptImage<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ScriptHeading<'a> {
+    level: i64,
+    text: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct ScriptLink<'a> {
+    href: &'a str,
+    text: Option<&'a str>,
+    /// `internal`, `subdomain`, `external` or `resource`.
+    #[serde(rename = "type")]
+    link_type: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct ScriptImage<'a> {
+    src: &'a str,
+    alt: Option<&'a str>,
+    width: Option<i64>,
+    height: Option<i64>,
+    loading: Option<&'a str>,
+    decorative: bool,
+}
+
+impl<'a> ScriptPage<'a> {
+    fn new(page: &'a Page) -> Self {
+        let mut headers: BTreeMap<&str, String> = BTreeMap::new();
+        for (name, value) in page.response_headers.iter() {
+            headers
+                .entry(name)
+                .and_modify(|joined| {
+                    joined.push_str(", ");
+                    joined.push_str(value);
+                })
+                .or_insert_with(|| value.to_string());
+        }
+        Self {
+            url: &page.url,
+            depth: page.depth.as_i64(),
+            status_code: page.status_code,
+            content_type: page.content_type.as_deref(),
+            title: page.title.as_deref(),
+            meta_description: page.meta_description.as_deref(),
+            canonical_url: page.canonical_url.as_deref(),
+            robots_meta: page.robots_meta.as_deref(),
+            word_count: page.word_count,
+            load_time_ms: page.load_time_ms,
+            response_size_bytes: page.response_size_bytes,
+            has_viewport: page.has_viewport,
+            has_structured_data: page.has_structured_data,
+            tags: &page.extracted_data,
+            headers,
+            headings: Vec::new(),
+            links: Vec::new(),
+            images: Vec::new(),
+        }
+    }
+
+    /// The page being analysed, with what was extracted from its HTML.
+    pub(crate) fn from_context(ctx: &CheckContext<'a>) -> Self {
+        let mut page = Self::new(ctx.page);
+        page.headings = ctx
+            .headings
+            .unwrap_or_default()
+            .iter()
+            .map(|h| ScriptHeading { level: h.level, text: &h.text })
+            .collect();
+        page.links = ctx
+            .links
+            .iter()
+            .map(|l| ScriptLink {
+                href: &l.href,
+                text: l.text.as_deref(),
+                link_type: l.link_type.as_str(),
+            })
+            .collect();
+        page.images = ctx
+            .images
+            .iter()
+            .map(|img| ScriptImage {
+                src: &img.src,
+                alt: img.alt.as_deref(),
+                width: img.width,
+                height: img.height,
+                loading: img.loading.as_deref(),
+                decorative: img.is_decorative,
+            })
+            .collect();
+        page
+    }
+
+    /// A page of a finished job, with its stored headings, images and
+    /// outgoing links.
+    pub(crate) fn from_stored(
+        page: &'a Page,
+        headings: &'a [Heading],
+        images: &'a [Image],
+        links: &'a [Link],
+    ) -> Self {
+        let mut script_page = Self::new(page);
+        let mut headings: Vec<&Heading> = headings.iter().collect();
+        headings.sort_by_key(|h| h.position);
+        script_page.headings = headings
+            .into_iter()
+            .map(|h| ScriptHeading { level: h.level, text: &h.text })
+            .collect();
+        script_page.links = links
+            .iter()
+            .map(|l| ScriptLink {
+                href: &l.target_url,
+                text: l.link_text.as_deref(),
+                link_type: l.link_type.as_str(),
+            })
+            .collect();
+        script_page.images = images
+            .iter()
+            .map(|img| ScriptImage {
+                src: &img.src,
+                alt: img.alt.as_deref(),
+                width: img.width,
+                height: img.height,
+                loading: img.loading.as_deref(),
+                decorative: img.is_decorative,
+            })
+            .collect();
+        script_page
+    }
+}
+
+/// An issue as a script returns it, before the check's defaults apply.
+#[derive(Debug, Deserialize)]
+struct ReturnedIssue {
+    message: String,
+    #[serde(default)]
+    severity: Option<IssueSeverity>,
+    #[serde(default)]
+    details: Option<String>,
+}
+
+/// Run a compiled script against `page` and collect the issues it returns.
+fn run(engine: &Engine, ast: &AST, page: &ScriptPage) -> Result<Vec<ReturnedIssue>, String> {
+    let page = rhai::serde::to_dynamic(page).map_err(|e| e.to_string())?;
+    let mut scope = Scope::new();
+    scope.push_constant("page", page);
+    let returned = engine
+        .eval_ast_with_scope::<Dynamic>(&mut scope, ast)
+        .map_err(|e| e.to_string())?;
+
+    let mut issues = if returned.is_array() {
+        returned
+            .into_array()?
+            .into_iter()
+            .filter(|value| !value.is_unit())
+            .map(returned_issue)
+            .collect::<Result<Vec<_>, _>>()?
+    } else if returned.is_unit() {
+        Vec::new()
+    } else {
+        vec![returned_issue(returned)?]
+    };
+    issues.truncate(MAX_ISSUES);
+    Ok(issues)
+}
+
+fn returned_issue(value: Dynamic) -> Result<ReturnedIssue, String> {
+    if value.is_string() {
+        let message = value.into_string()?;
+        return Ok(ReturnedIssue { message, severity: None, details: None });
+    }
+    if value.is_map() {
+        return rhai::serde::from_dynamic(&value).map_err(|e| format!("invalid issue: {e}"));
+    }
+    Err(format!(
+        "a script returns issues as strings or maps, not {}",
+        value.type_name()
+    ))
+}
+
+/// Wraps a [`CustomCheck`] whose `script` compiled, running it on every
+/// page. Fails silently per page: a script error is logged and the page
+/// gets no issues from it.
+pub struct ScriptCheck {
+    check: CustomCheck,
+    engine: Engine,
+    ast: AST,
+}
+
+impl ScriptCheck {
+    /// `None` for checks without a script and for scripts that don't
+    /// compile — those never fire.
+    pub fn new(check: CustomCheck) -> Option<Self> {
+        let engine = sandbox();
+        let ast = match engine.compile(check.script.as_deref()?) {
+            Ok(ast) => ast,
+            Err(e) => {
+                tracing::warn!("custom check '{}' is disabled: {e}", check.name);
+                return None;
+            }
+        };
+        Some(Self { check, engine, ast })
+    }
+}
+
+impl Check for ScriptCheck {
+    fn id(&self) -> &str {
+        &self.check.id
+    }
+
+    fn check(&self, ctx: &CheckContext) -> Option<NewIssue> {
+        self.issues(ctx).into_iter().next()
+    }
+
+    fn issues(&self, ctx: &CheckContext) -> Vec<NewIssue> {
+        let returned = match run(&self.engine, &self.ast, &ScriptPage::from_context(ctx)) {
+            Ok(returned) => returned,
+            Err(e) => {
+                tracing::warn!(
+                    "custom check '{}' failed on {}: {e}",
+                    self.check.name,
+                    ctx.page.url
+                );
+                return Vec::new();
+            }
+        };
+        returned
+            .into_iter()
+            .map(|issue| NewIssue {
+                job_id: ctx.job_id.to_string(),
+                page_id: Some(ctx.page_id.to_string()),
+                issue_type: self.check.name.clone(),
+                severity: issue.severity.unwrap_or(self.check.severity),
+                message: issue.message,
+                details: issue.details,
+                custom_check_id: Some(self.check.id.clone()),
+            })
+            .collect()
+    }
+}
+
+/// Run `script` once against `page`, keeping what it prints, for
+/// debugging a script before it's saved. Compile and runtime errors are
+/// reported in the result rather than returned.
+pub(crate) fn dry_run(script: &str, severity: IssueSeverity, page: &ScriptPage) -> ScriptDryRun {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let mut engine = sandbox();
+    let printed = output.clone();
+    engine.on_print(move |text| {
+        if let Ok(mut lines) = printed.lock() {
+            lines.push(text.to_string());
+        }
+    });
+    let debugged = output.clone();
+    engine.on_debug(move |text, _, pos| {
+        if let Ok(mut lines) = debugged.lock() {
+            lines.push(format!("{pos}: {text}"));
+        }
+    });
+
+    let result = engine
+        .compile(script)
+        .map_err(|e| e.to_string())
+        .and_then(|ast| run(&engine, &ast, page));
+    let output = output.lock().map(|mut lines| std::mem::take(&mut *lines)).unwrap_or_default();
+
+    match result {
+        Ok(returned) => ScriptDryRun {
+            issues: returned
+                .into_iter()
+                .map(|issue| ScriptIssue {
+                    severity: issue.severity.unwrap_or(severity),
+                    message: issue.message,
+                    details: issue.details,
+                })
+                .collect(),
+            output,
+            error: None,
+        },
+        Err(error) => ScriptDryRun { issues: Vec::new(), output, error: Some(error) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::CheckerRegistry;
+    use crate::contexts::analysis::{Depth, LinkType, ResponseHeaders};
+    use crate::contexts::extension::{CheckScope, Operator};
+    use crate::extractor::page_extractor::{ExtractedHeading, ExtractedImage, ExtractedLink};
+    use crate::service::auditor::{CheckResult, Score, SeoAuditDetails};
+    use chrono::Utc;
+
+    fn passing_details() -> SeoAuditDetails {
+        let pass = CheckResult { passed: true, score: Score::from(1.0), value: None, description: None };
+        SeoAuditDetails {
+            document_title: pass.clone(),
+            meta_description: pass.clone(),
+            viewport: pass.clone(),
+            canonical: pass.clone(),
+            hreflang: pass.clone(),
+            crawlable_anchors: pass.clone(),
+            link_text: pass.clone(),
+            image_alt: pass.clone(),
+            http_status_code: pass.clone(),
+            is_crawlable: pass,
+        }
+    }
+
+    fn make_page() -> Page {
+        Page {
+            id: "p1".into(),
+            job_id: "j1".into(),
+            url: "https://example.com/shop".into(),
+            depth: Depth::root(),
+            status_code: Some(200),
+            content_type: Some("text/html".into()),
+            title: Some("Shop".into()),
+            meta_description: None,
+            canonical_url: None,
+            robots_meta: None,
+            word_count: Some(120),
+            load_time_ms: Some(800),
+            ttfb_ms: None,
+            download_ms: None,
+            response_size_bytes: Some(10000),
+            has_viewport: true,
+            has_structured_data: false,
+            crawled_at: Utc::now(),
+            extracted_data: HashMap::from([("price".to_string(), serde_json::json!("19.99"))]),
+            response_headers: ResponseHeaders::new([("Cache-Control", "no-cache"), ("Vary", "Accept"), ("Vary", "Cookie")]),
+            weight: None,
+        }
+    }
+
+    fn script_check(script: &str) -> CustomCheck {
+        CustomCheck {
+            id: "chk-1".into(),
+            name: "Script".into(),
+            severity: IssueSeverity::Warning,
+            field: String::new(),
+            operator: Operator::Present,
+            threshold: None,
+            condition: None,
+            scope: CheckScope::Page,
+            aggregate: None,
+            script: Some(script.into()),
+            message_template: String::new(),
+            enabled: true,
+        }
+    }
+
+    fn dry_run_on_page(script: &str) -> ScriptDryRun {
+        let page = make_page();
+        dry_run(script, IssueSeverity::Warning, &ScriptPage::new(&page))
+    }
+
+    #[test]
+    fn script_reads_page_fields_tags_and_headers() {
+        let run = dry_run_on_page(
+            r#"`${page.title} ${page.word_count} ${page.tags.price} ${page.headers["vary"]} ${page.meta_description == ()}`"#,
+        );
+        assert_eq!(run.error, None);
+        assert_eq!(run.issues[0].message, "Shop 120 19.99 Accept, Cookie true");
+    }
+
+    #[test]
+    fn script_returns_strings_maps_and_arrays() {
+        assert!(dry_run_on_page("()").issues.is_empty());
+        assert!(dry_run_on_page("if page.word_count > 500 { \"thin\" }").issues.is_empty());
+
+        let run = dry_run_on_page(
+            r#"["plain", #{ message: "severe", severity: "critical", details: "why" }, ()]"#,
+        );
+        assert_eq!(run.error, None);
+        assert_eq!(
+            run.issues,
+            vec![
+                ScriptIssue { severity: IssueSeverity::Warning, message: "plain".into(), details: None },
+                ScriptIssue { severity: IssueSeverity::Critical, message: "severe".into(), details: Some("why".into()) },
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_return_values_are_errors() {
+        assert!(dry_run_on_page("42").error.unwrap().contains("strings or maps"));
+        assert!(dry_run_on_page("#{ severity: \"warning\" }").error.unwrap().contains("invalid issue"));
+        assert!(dry_run_on_page("#{ message: \"m\", severity: \"fatal\" }").error.is_some());
+    }
+
+    #[test]
+    fn dry_run_keeps_printed_output() {
+        let run = dry_run_on_page("print(`title is ${page.title}`); debug(1 + 1); ()");
+        assert_eq!(run.output[0], "title is Shop");
+        assert!(run.output[1].ends_with("2"), "{:?}", run.output);
+        assert!(run.issues.is_empty());
+    }
+
+    #[test]
+    fn sandbox_stops_runaway_scripts() {
+        let run = dry_run_on_page("loop { }");
+        assert!(run.error.unwrap().to_lowercase().contains("too many operations"));
+
+        let run = dry_run_on_page("let s = \"x\"; loop { s += s; }");
+        assert!(run.error.is_some());
+
+        let run = dry_run_on_page("fn f(n) { f(n + 1) } f(0)");
+        assert!(run.error.is_some());
+    }
+
+    #[test]
+    fn sandbox_stops_scripts_holding_too_much_memory() {
+        let grow = "let s = \"x\"; while s.len() < 200000 { s += s; }\n";
+
+        // Each copy is within the string cap, but there are too many of them.
+        let mut script = String::from(grow);
+        for i in 0..MAX_VARIABLES {
+            script.push_str(&format!("let copy{i} = s + \"{i}\";\n"));
+        }
+        script.push_str("()");
+        let run = dry_run_on_page(&script);
+        assert!(run.error.unwrap().contains("Too many variables"));
+
+        // Copies nested in one value count against the string cap together.
+        let run = dry_run_on_page(&format!("{grow} let copies = [s, s, s]; ()"));
+        assert!(run.error.unwrap().contains("Length of string"));
+
+        // Values dropped along the way don't count.
+        let run = dry_run_on_page(&format!("{grow} for i in 0..200 {{ let copy = s + i; }} ()"));
+        assert!(run.error.is_none(), "{:?}", run.error);
+    }
+
+    #[test]
+    fn sandbox_has_no_eval_or_modules() {
+        assert!(matches!(validate("eval(\"1\")"), Err(ConditionError::InvalidScript(_))));
+        assert!(dry_run_on_page("import \"/etc/passwd\" as m; ()").error.is_some());
+    }
+
+    #[test]
+    fn validate_rejects_scripts_that_do_not_compile() {
+        assert!(validate("page.title ==").is_err());
+        assert!(validate("if page.title == () { \"no title\" }").is_ok());
+    }
+
+    #[test]
+    fn script_check_raises_every_returned_issue_from_the_registry() {
+        let page = make_page();
+        let details = passing_details();
+        let headings = vec![
+            ExtractedHeading { level: 2, text: "Intro".into(), position: 0 },
+            ExtractedHeading { level: 2, text: "More".into(), position: 1 },
+        ];
+        let images = vec![
+            ExtractedImage { src: "/a.png".into(), alt: None, width: None, height: None, loading: None, is_decorative: false },
+            ExtractedImage { src: "/b.png".into(), alt: Some("B".into()), width: None, height: None, loading: None, is_decorative: false },
+            ExtractedImage { src: "/c.png".into(), alt: None, width: None, height: None, loading: None, is_decorative: false },
+        ];
+        let links = vec![ExtractedLink {
+            href: "https://other.example/".into(),
+            link_type: LinkType::External,
+            text: None,
+        }];
+        let ctx = CheckContext::new(&page, &details, "j1", "p1")
+            .with_references(&[], &links)
+            .with_headings(&headings)
+            .with_images(&images);
+
+        let mut registry = CheckerRegistry::new();
+        registry.register(Box::new(
+            ScriptCheck::new(script_check(
+                r#"
+                let issues = page.images.filter(|img| img.alt == ()).map(|img| `No alt on ${img.src}`);
+                if !page.headings.some(|h| h.level == 1) { issues.push(#{ message: "No h1", severity: "critical" }); }
+                if page.links[0].type == "external" { issues.push("Links out first"); }
+                issues
+                "#,
+            ))
+            .unwrap(),
+        ));
+        let issues = registry.run(&ctx);
+        let messages: Vec<&str> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, ["No alt on /a.png", "No alt on /c.png", "No h1", "Links out first"]);
+        assert_eq!(issues[0].issue_type, "Script");
+        assert_eq!(issues[0].severity, IssueSeverity::Warning);
+        assert_eq!(issues[2].severity, IssueSeverity::Critical);
+        assert_eq!(issues[0].page_id.as_deref(), Some("p1"));
+    }
+
+    #[test]
+    fn failing_or_uncompilable_scripts_raise_nothing() {
+        assert!(ScriptCheck::new(script_check("let")).is_none());
+        assert!(ScriptCheck::new(CustomCheck { script: None, ..script_check("") }).is_none());
+
+        let page = make_page();
+        let details = passing_details();
+        let ctx = CheckContext::new(&page, &details, "j1", "p1");
+        let check = ScriptCheck::new(script_check("page.missing.field")).unwrap();
+        assert!(check.issues(&ctx).is_empty());
+        assert!(check.check(&ctx).is_none());
+    }
+
+    #[test]
+    fn stored_pages_order_headings_by_position() {
+        let page = make_page();
+        let heading = |position, text: &str| Heading {
+            id: position,
+            page_id: "p1".into(),
+            level: 2,
+            text: text.into(),
+            position,
+        };
+        let headings = vec![heading(1, "Second"), heading(0, "First")];
+        let run = dry_run(
+            "page.headings.map(|h| h.text)",
+            IssueSeverity::Info,
+            &ScriptPage::from_stored(&page, &headings, &[], &[]),
+        );
+        let messages: Vec<&str> = run.issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, ["First", "Second"]);
+        assert_eq!(run.issues[0].severity, IssueSeverity::Info);
+    }
+}
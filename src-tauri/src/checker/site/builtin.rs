@@ -0,0 +1,445 @@
+use chrono::{DateTime, Utc};
+use scraper::{Html, Selector};
+use serde_json::Value;
+use url::Url;
+
+use crate::checker::site::{SiteCheck, SiteFetch};
+
+/// Variables an ads.txt file may set instead of listing a seller.
+const ADS_TXT_VARIABLES: &[&str] = &[
+    "contact",
+    "subdomain",
+    "inventorypartnerdomain",
+    "ownerdomain",
+    "managerdomain",
+];
+
+/// Returns all built-in site checks, in report order.
+pub fn all() -> Vec<Box<dyn SiteCheck>> {
+    vec![
+        // Crawl control
+        Box::new(RobotsTxtCheck),
+        Box::new(SitemapCheck),
+        // Browser and app integration
+        Box::new(FaviconCheck),
+        Box::new(WebManifestCheck),
+        Box::new(WellKnownJsonCheck::ASSET_LINKS),
+        Box::new(WellKnownJsonCheck::APPLE_APP_SITE_ASSOCIATION),
+        // Published policies and notes
+        Box::new(SecurityTxtCheck),
+        Box::new(AdsTxtCheck::ADS_TXT),
+        Box::new(AdsTxtCheck::APP_ADS_TXT),
+        Box::new(LlmsTxtCheck),
+        Box::new(HumansTxtCheck),
+    ]
+}
+
+/// `paths` resolved against the site root.
+fn at(home: &Url, paths: &[&str]) -> Vec<Url> {
+    paths.iter().filter_map(|path| home.join(path).ok()).collect()
+}
+
+/// `href`s of the home page's `<link>` elements matching `selector`.
+fn declared(home: &Url, document: &Html, selector: &str) -> Vec<Url> {
+    let Ok(selector) = Selector::parse(selector) else {
+        return Vec::new();
+    };
+    document
+        .select(&selector)
+        .filter_map(|link| link.value().attr("href"))
+        .filter_map(|href| home.join(href.trim()).ok())
+        .collect()
+}
+
+/// Non-empty lines with `#` comments removed, numbered from 1.
+fn content_lines(body: &str) -> impl Iterator<Item = (usize, &str)> {
+    body.lines().enumerate().filter_map(|(i, line)| {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        (!line.is_empty()).then_some((i + 1, line))
+    })
+}
+
+/// `"3 lines aren't …(first on line 7)"`, or nothing when `bad` is empty.
+fn bad_lines(bad: &[usize], what: &str) -> Option<String> {
+    let first = bad.first()?;
+    Some(match bad.len() {
+        1 => format!("Line {first} isn't {what}"),
+        n => format!("{n} lines aren't {what} (first on line {first})"),
+    })
+}
+
+pub struct RobotsTxtCheck;
+impl SiteCheck for RobotsTxtCheck {
+    fn id(&self) -> &str { "robots_txt" }
+    fn label(&self) -> &str { "robots.txt" }
+    fn locate(&self, home: &Url, _: &Html) -> Vec<Url> { at(home, &["/robots.txt"]) }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        let mut groups = false;
+        let mut bad = Vec::new();
+        for (number, line) in content_lines(fetch.body) {
+            match line.split_once(':') {
+                Some((field, _)) if field.trim().eq_ignore_ascii_case("user-agent") => groups = true,
+                Some(_) => {}
+                None => bad.push(number),
+            }
+        }
+        let mut problems = Vec::new();
+        if !groups {
+            problems.push("No User-agent group, so crawlers ignore every rule".to_string());
+        }
+        problems.extend(bad_lines(&bad, "a `field: value` rule"));
+        problems
+    }
+}
+
+pub struct SitemapCheck;
+impl SiteCheck for SitemapCheck {
+    fn id(&self) -> &str { "sitemap_xml" }
+    fn label(&self) -> &str { "sitemap.xml" }
+    fn locate(&self, home: &Url, _: &Html) -> Vec<Url> {
+        at(home, &["/sitemap.xml", "/sitemap_index.xml"])
+    }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        let body = fetch.body.to_ascii_lowercase();
+        if !body.contains("<urlset") && !body.contains("<sitemapindex") {
+            return vec!["No <urlset> or <sitemapindex> element".to_string()];
+        }
+        if !body.contains("<loc") {
+            return vec!["Lists no URLs".to_string()];
+        }
+        Vec::new()
+    }
+}
+
+pub struct FaviconCheck;
+impl SiteCheck for FaviconCheck {
+    fn id(&self) -> &str { "favicon" }
+    fn label(&self) -> &str { "Favicon" }
+    fn locate(&self, home: &Url, document: &Html) -> Vec<Url> {
+        let mut urls = declared(home, document, "link[rel~='icon'][href]");
+        urls.extend(at(home, &["/favicon.ico"]));
+        urls.dedup();
+        urls
+    }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        if fetch.body.is_empty() {
+            return vec!["Empty response".to_string()];
+        }
+        match fetch.headers.media_type() {
+            Some(media) if !media.starts_with("image/") && media != "application/octet-stream" => {
+                vec![format!("Served as {media}, not an image")]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+pub struct WebManifestCheck;
+impl SiteCheck for WebManifestCheck {
+    fn id(&self) -> &str { "web_manifest" }
+    fn label(&self) -> &str { "Web app manifest" }
+    fn locate(&self, home: &Url, document: &Html) -> Vec<Url> {
+        declared(home, document, "link[rel~='manifest'][href]").into_iter().take(1).collect()
+    }
+    fn not_declared(&self) -> Option<String> {
+        Some("The home page has no <link rel=\"manifest\">".to_string())
+    }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        let manifest = match serde_json::from_str::<Value>(fetch.body) {
+            Ok(Value::Object(manifest)) => manifest,
+            Ok(_) => return vec!["Not a JSON object".to_string()],
+            Err(e) => return vec![format!("Not valid JSON: {e}")],
+        };
+        let mut problems = Vec::new();
+        if !["name", "short_name"].iter().any(|key| manifest.get(*key).is_some_and(Value::is_string)) {
+            problems.push("No name or short_name".to_string());
+        }
+        if !manifest.get("icons").and_then(Value::as_array).is_some_and(|icons| !icons.is_empty()) {
+            problems.push("No icons, so the app can't be installed".to_string());
+        }
+        if !manifest.contains_key("start_url") {
+            problems.push("No start_url".to_string());
+        }
+        problems
+    }
+}
+
+/// A JSON file under `/.well-known/` that app stores read to tie apps to
+/// the site.
+pub struct WellKnownJsonCheck {
+    id: &'static str,
+    label: &'static str,
+    paths: &'static [&'static str],
+    /// Android's file is a list of statements, Apple's an object keyed by
+    /// service.
+    array: bool,
+}
+
+impl WellKnownJsonCheck {
+    pub const ASSET_LINKS: Self = Self {
+        id: "assetlinks",
+        label: "Android asset links",
+        paths: &["/.well-known/assetlinks.json"],
+        array: true,
+    };
+    pub const APPLE_APP_SITE_ASSOCIATION: Self = Self {
+        id: "apple_app_site_association",
+        label: "Apple app site association",
+        paths: &["/.well-known/apple-app-site-association", "/apple-app-site-association"],
+        array: false,
+    };
+}
+
+impl SiteCheck for WellKnownJsonCheck {
+    fn id(&self) -> &str { self.id }
+    fn label(&self) -> &str { self.label }
+    fn locate(&self, home: &Url, _: &Html) -> Vec<Url> { at(home, self.paths) }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        match serde_json::from_str::<Value>(fetch.body) {
+            Ok(Value::Array(statements)) if self.array => {
+                if statements.is_empty() { vec!["Lists no statements".to_string()] } else { Vec::new() }
+            }
+            Ok(Value::Object(services)) if !self.array => {
+                let known = ["applinks", "webcredentials", "appclips", "activitycontinuation"];
+                if services.keys().any(|key| known.contains(&key.as_str())) {
+                    Vec::new()
+                } else {
+                    vec!["Declares none of applinks, webcredentials or appclips".to_string()]
+                }
+            }
+            Ok(_) if self.array => vec!["Not a JSON array".to_string()],
+            Ok(_) => vec!["Not a JSON object".to_string()],
+            Err(e) => vec![format!("Not valid JSON: {e}")],
+        }
+    }
+}
+
+/// RFC 9116: where to report vulnerabilities, and until when that's true.
+pub struct SecurityTxtCheck;
+impl SiteCheck for SecurityTxtCheck {
+    fn id(&self) -> &str { "security_txt" }
+    fn label(&self) -> &str { "security.txt" }
+    fn locate(&self, home: &Url, _: &Html) -> Vec<Url> {
+        at(home, &["/.well-known/security.txt", "/security.txt"])
+    }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        let mut contact = false;
+        let mut expires = None;
+        for (_, line) in content_lines(fetch.body) {
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            match field.trim().to_ascii_lowercase().as_str() {
+                "contact" => contact = true,
+                "expires" => expires = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+
+        let mut problems = Vec::new();
+        if !fetch.url.contains("/.well-known/") {
+            problems.push("Served only at the legacy /security.txt; move it to /.well-known/".to_string());
+        }
+        if !contact {
+            problems.push("No Contact field".to_string());
+        }
+        match expires.as_deref().map(DateTime::parse_from_rfc3339) {
+            None => problems.push("No Expires field".to_string()),
+            Some(Err(_)) => problems.push("Expires isn't an RFC 3339 date".to_string()),
+            Some(Ok(date)) if date < Utc::now() => {
+                problems.push(format!("Expired on {}", date.format("%Y-%m-%d")))
+            }
+            Some(Ok(_)) => {}
+        }
+        problems
+    }
+}
+
+/// The IAB's list of who may sell the site's (or its apps') ad inventory.
+pub struct AdsTxtCheck {
+    id: &'static str,
+    label: &'static str,
+    path: &'static str,
+}
+
+impl AdsTxtCheck {
+    pub const ADS_TXT: Self = Self { id: "ads_txt", label: "ads.txt", path: "/ads.txt" };
+    pub const APP_ADS_TXT: Self = Self { id: "app_ads_txt", label: "app-ads.txt", path: "/app-ads.txt" };
+}
+
+impl SiteCheck for AdsTxtCheck {
+    fn id(&self) -> &str { self.id }
+    fn label(&self) -> &str { self.label }
+    fn locate(&self, home: &Url, _: &Html) -> Vec<Url> { at(home, &[self.path]) }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        let mut records = 0;
+        let mut bad = Vec::new();
+        for (number, line) in content_lines(fetch.body) {
+            if let Some((name, _)) = line.split_once('=') {
+                if ADS_TXT_VARIABLES.contains(&name.trim().to_ascii_lowercase().as_str()) {
+                    continue;
+                }
+            }
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let valid = matches!(fields.len(), 3 | 4)
+                && fields[0].contains('.')
+                && !fields[1].is_empty()
+                && ["DIRECT", "RESELLER"].iter().any(|kind| fields[2].eq_ignore_ascii_case(kind));
+            if valid {
+                records += 1;
+            } else {
+                bad.push(number);
+            }
+        }
+
+        let mut problems = Vec::new();
+        if records == 0 {
+            problems.push("Lists no seller records".to_string());
+        }
+        problems.extend(bad_lines(&bad, "a `domain, account, DIRECT|RESELLER` record"));
+        problems
+    }
+}
+
+/// llmstxt.org: a Markdown file for language models, titled with an H1.
+pub struct LlmsTxtCheck;
+impl SiteCheck for LlmsTxtCheck {
+    fn id(&self) -> &str { "llms_txt" }
+    fn label(&self) -> &str { "llms.txt" }
+    fn locate(&self, home: &Url, _: &Html) -> Vec<Url> { at(home, &["/llms.txt"]) }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        let first = fetch.body.lines().map(str::trim).find(|line| !line.is_empty());
+        if first.is_some_and(|line| line.starts_with("# ")) {
+            Vec::new()
+        } else {
+            vec!["Doesn't start with a `# Title` heading".to_string()]
+        }
+    }
+}
+
+pub struct HumansTxtCheck;
+impl SiteCheck for HumansTxtCheck {
+    fn id(&self) -> &str { "humans_txt" }
+    fn label(&self) -> &str { "humans.txt" }
+    fn locate(&self, home: &Url, _: &Html) -> Vec<Url> { at(home, &["/humans.txt"]) }
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String> {
+        if fetch.body.trim().is_empty() {
+            vec!["Empty file".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::analysis::ResponseHeaders;
+
+    fn validate(check: &dyn SiteCheck, url: &str, content_type: &str, body: &str) -> Vec<String> {
+        let headers = ResponseHeaders::new([("Content-Type", content_type)]);
+        check.validate(&SiteFetch { url, headers: &headers, body })
+    }
+
+    fn home() -> Url {
+        Url::parse("https://example.com/").unwrap()
+    }
+
+    #[test]
+    fn robots_txt_needs_a_user_agent_group() {
+        let url = "https://example.com/robots.txt";
+        assert!(validate(&RobotsTxtCheck, url, "text/plain", "User-agent: *\nDisallow: /admin # private\n").is_empty());
+
+        let problems = validate(&RobotsTxtCheck, url, "text/plain", "Disallow: /admin\nnonsense\n");
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[1], "Line 2 isn't a `field: value` rule");
+    }
+
+    #[test]
+    fn sitemap_must_be_a_sitemap() {
+        let url = "https://example.com/sitemap.xml";
+        let urlset = r#"<?xml version="1.0"?><urlset><url><loc>https://example.com/</loc></url></urlset>"#;
+        assert!(validate(&SitemapCheck, url, "application/xml", urlset).is_empty());
+        assert_eq!(validate(&SitemapCheck, url, "application/xml", "<urlset></urlset>"), ["Lists no URLs"]);
+        assert_eq!(validate(&SitemapCheck, url, "text/plain", "hello").len(), 1);
+    }
+
+    #[test]
+    fn favicon_prefers_the_declared_icon() {
+        let document = Html::parse_document(r#"<link rel="shortcut icon" href="/img/fav.png">"#);
+        let urls: Vec<String> = FaviconCheck.locate(&home(), &document).iter().map(Url::to_string).collect();
+        assert_eq!(urls, ["https://example.com/img/fav.png", "https://example.com/favicon.ico"]);
+
+        let url = "https://example.com/favicon.ico";
+        assert!(validate(&FaviconCheck, url, "image/x-icon", "\0\0\x01").is_empty());
+        assert_eq!(validate(&FaviconCheck, url, "text/plain", "x"), ["Served as text/plain, not an image"]);
+    }
+
+    #[test]
+    fn manifest_is_only_looked_for_when_declared() {
+        assert!(WebManifestCheck.locate(&home(), &Html::parse_document("<p>hi</p>")).is_empty());
+        assert!(WebManifestCheck.not_declared().is_some());
+
+        let document = Html::parse_document(r#"<link rel="manifest" href="/site.webmanifest">"#);
+        assert_eq!(WebManifestCheck.locate(&home(), &document)[0].path(), "/site.webmanifest");
+
+        let url = "https://example.com/site.webmanifest";
+        let good = r#"{"name": "Example", "start_url": "/", "icons": [{"src": "/i.png"}]}"#;
+        assert!(validate(&WebManifestCheck, url, "application/manifest+json", good).is_empty());
+        assert_eq!(validate(&WebManifestCheck, url, "application/json", r#"{"name": "Example"}"#).len(), 2);
+        assert!(validate(&WebManifestCheck, url, "application/json", "{")[0].starts_with("Not valid JSON"));
+    }
+
+    #[test]
+    fn well_known_json_files_have_the_expected_shape() {
+        let links = "https://example.com/.well-known/assetlinks.json";
+        assert!(validate(&WellKnownJsonCheck::ASSET_LINKS, links, "application/json", r#"[{"relation": []}]"#).is_empty());
+        assert_eq!(validate(&WellKnownJsonCheck::ASSET_LINKS, links, "application/json", "{}"), ["Not a JSON array"]);
+
+        let aasa = "https://example.com/.well-known/apple-app-site-association";
+        let check = WellKnownJsonCheck::APPLE_APP_SITE_ASSOCIATION;
+        assert!(validate(&check, aasa, "application/json", r#"{"applinks": {"details": []}}"#).is_empty());
+        assert_eq!(validate(&check, aasa, "application/json", r#"{"other": 1}"#).len(), 1);
+    }
+
+    #[test]
+    fn security_txt_needs_contact_and_a_future_expiry() {
+        let url = "https://example.com/.well-known/security.txt";
+        let good = "Contact: mailto:security@example.com\nExpires: 2999-01-01T00:00:00Z\n";
+        assert!(validate(&SecurityTxtCheck, url, "text/plain", good).is_empty());
+
+        let expired = "Contact: mailto:security@example.com\nExpires: 2020-01-01T00:00:00Z\n";
+        assert_eq!(validate(&SecurityTxtCheck, url, "text/plain", expired), ["Expired on 2020-01-01"]);
+
+        let problems = validate(&SecurityTxtCheck, "https://example.com/security.txt", "text/plain", "Policy: x");
+        assert_eq!(problems.len(), 3);
+    }
+
+    #[test]
+    fn ads_txt_records_are_validated_line_by_line() {
+        let url = "https://example.com/ads.txt";
+        let good = "# ads.txt\ncontact=ads@example.com\ngoogle.com, pub-123, DIRECT, f08c47fec0942fa0\nexchange.com, 42, reseller\n";
+        assert!(validate(&AdsTxtCheck::ADS_TXT, url, "text/plain", good).is_empty());
+
+        let bad = "google.com, pub-123, DIRECT\ngoogle.com pub-123 DIRECT\nexchange.com, 42, SOMETIMES\n";
+        assert_eq!(
+            validate(&AdsTxtCheck::ADS_TXT, url, "text/plain", bad),
+            ["2 lines aren't a `domain, account, DIRECT|RESELLER` record (first on line 2)"]
+        );
+        assert_eq!(validate(&AdsTxtCheck::APP_ADS_TXT, url, "text/plain", "# empty\n"), ["Lists no seller records"]);
+    }
+
+    #[test]
+    fn llms_txt_starts_with_a_title() {
+        let url = "https://example.com/llms.txt";
+        assert!(validate(&LlmsTxtCheck, url, "text/markdown", "\n# Example\n\n> A site.\n").is_empty());
+        assert_eq!(validate(&LlmsTxtCheck, url, "text/plain", "Example\n").len(), 1);
+    }
+
+    #[test]
+    fn humans_txt_must_say_something() {
+        let url = "https://example.com/humans.txt";
+        assert!(validate(&HumansTxtCheck, url, "text/plain", "/* TEAM */\nDeveloper: Jo").is_empty());
+        assert_eq!(validate(&HumansTxtCheck, url, "text/plain", "  \n"), ["Empty file"]);
+    }
+}
@@ -0,0 +1,132 @@
+//! Site-level file checks: files a site serves once, at a fixed or
+//! declared location, rather than per page.
+//!
+//! A [`SiteCheck`] says where to look and how to validate what it finds;
+//! the resource checker in `service::discovery` does the fetching, once
+//! per job, for every check in a [`SiteCheckRegistry`].
+
+pub mod builtin;
+
+use scraper::Html;
+use url::Url;
+
+use crate::contexts::analysis::ResponseHeaders;
+
+/// A 2xx answer for a site file, as a check sees it.
+pub struct SiteFetch<'a> {
+    /// Where the answer came from, after redirects.
+    pub url: &'a str,
+    pub headers: &'a ResponseHeaders,
+    pub body: &'a str,
+}
+
+impl SiteFetch<'_> {
+    /// An HTML page where a file was expected: usually the site's error
+    /// page or an app shell served for every path. The runner counts it
+    /// as the file missing.
+    pub fn looks_like_html(&self) -> bool {
+        if self
+            .headers
+            .media_type()
+            .is_some_and(|media| media == "text/html" || media == "application/xhtml+xml")
+        {
+            return true;
+        }
+        let start: String = self.body.trim_start().chars().take(15).collect();
+        let start = start.to_ascii_lowercase();
+        start.starts_with("<!doctype html") || start.starts_with("<html")
+    }
+}
+
+/// One site-level file. The runner fetches what [`locate`](Self::locate)
+/// returns, in order, and validates the first answer that's the file.
+pub trait SiteCheck: Send + Sync {
+    /// Stable id, stored with the result and used by template conditions
+    /// and `{site.<id>}` tags.
+    fn id(&self) -> &str;
+
+    /// Name shown in reports.
+    fn label(&self) -> &str;
+
+    /// Where the file may be, most authoritative first. `home` is the
+    /// site root and `document` the parsed home page (empty when it
+    /// couldn't be fetched).
+    fn locate(&self, home: &Url, document: &Html) -> Vec<Url>;
+
+    /// Why there was nowhere to look, for checks whose file has to be
+    /// declared by the home page.
+    fn not_declared(&self) -> Option<String> {
+        None
+    }
+
+    /// Problems with the served file; empty when it's valid.
+    fn validate(&self, fetch: &SiteFetch) -> Vec<String>;
+}
+
+/// The site checks a job runs, in report order.
+#[derive(Default)]
+pub struct SiteCheckRegistry {
+    checks: Vec<Box<dyn SiteCheck>>,
+}
+
+impl SiteCheckRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry pre-loaded with all built-in site checks.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        for check in builtin::all() {
+            registry.register(check);
+        }
+        registry
+    }
+
+    pub fn register(&mut self, check: Box<dyn SiteCheck>) {
+        self.checks.push(check);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn SiteCheck> {
+        self.checks.iter().map(Box::as_ref)
+    }
+
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fetch<'a>(headers: &'a ResponseHeaders, body: &'a str) -> SiteFetch<'a> {
+        SiteFetch { url: "https://example.com/robots.txt", headers, body }
+    }
+
+    #[test]
+    fn html_answers_are_recognised_by_type_or_content() {
+        let html = ResponseHeaders::new([("Content-Type", "text/html; charset=utf-8")]);
+        let text = ResponseHeaders::new([("Content-Type", "text/plain")]);
+        let none = ResponseHeaders::default();
+
+        assert!(fetch(&html, "User-agent: *").looks_like_html());
+        assert!(fetch(&none, "\n  <!DOCTYPE html><html></html>").looks_like_html());
+        assert!(!fetch(&text, "User-agent: *").looks_like_html());
+    }
+
+    #[test]
+    fn default_ids_are_unique() {
+        let registry = SiteCheckRegistry::with_defaults();
+        let mut ids: Vec<&str> = registry.iter().map(|check| check.id()).collect();
+        let total = ids.len();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), total);
+        assert!(ids.contains(&"robots_txt") && ids.contains(&"sitemap_xml"));
+    }
+}
@@ -2,7 +2,7 @@ use tauri::{command, State};
 
 use crate::{
     lifecycle::app_state::AppState,
-    service::GeminiRequest,
+    service::{GeminiRequest, OllamaSettings, OpenAiCompatibleSettings},
 };
 
 use super::ResultExt;
@@ -35,6 +35,38 @@ pub async fn set_ai_source(source: String, app_state: State<'_, AppState>) -> Re
     app_state.ai_context.set_ai_source(&source).await.context("Failed to set AI source")
 }
 
+#[command]
+#[specta::specta]
+pub async fn get_openai_settings(
+    app_state: State<'_, AppState>,
+) -> Result<OpenAiCompatibleSettings, String> {
+    app_state.ai_context.get_openai_settings().await.context("Failed to get OpenAI settings")
+}
+
+#[command]
+#[specta::specta]
+pub async fn set_openai_settings(
+    settings: OpenAiCompatibleSettings,
+    app_state: State<'_, AppState>,
+) -> Result<(), String> {
+    app_state.ai_context.set_openai_settings(&settings).await.context("Failed to set OpenAI settings")
+}
+
+#[command]
+#[specta::specta]
+pub async fn get_ollama_settings(app_state: State<'_, AppState>) -> Result<OllamaSettings, String> {
+    app_state.ai_context.get_ollama_settings().await.context("Failed to get Ollama settings")
+}
+
+#[command]
+#[specta::specta]
+pub async fn set_ollama_settings(
+    settings: OllamaSettings,
+    app_state: State<'_, AppState>,
+) -> Result<(), String> {
+    app_state.ai_context.set_ollama_settings(&settings).await.context("Failed to set Ollama settings")
+}
+
 #[command]
 #[specta::specta]
 pub async fn set_gemini_enabled(
@@ -151,6 +183,8 @@ mod tests {
                 status: 200,
                 body: String::new(),
                 url: String::new(),
+                headers: Default::default(),
+                timing: Default::default(),
             })
         }
         async fn post_json(
@@ -162,11 +196,13 @@ mod tests {
                 status: 200,
                 body: String::new(),
                 url: String::new(),
+                headers: Default::default(),
+                timing: Default::default(),
             })
         }
         async fn stream_get(&self, _url: &str) -> anyhow::Result<crate::service::spider::StreamResponse> {
             use crate::service::spider::MockSpider as Ms;
-            let ms = Ms { html_response: String::new(), generic_response: crate::service::spider::SpiderResponse { status: 200, body: String::new(), url: String::new() } };
+            let ms = Ms { html_response: String::new(), generic_response: crate::service::spider::SpiderResponse { status: 200, body: String::new(), url: String::new(), headers: Default::default(), timing: Default::default() } };
             ms.stream_get(_url).await
         }
         async fn stream_get_range(&self, _url: &str, _start_byte: u64) -> anyhow::Result<crate::service::spider::StreamResponse> {
@@ -194,10 +230,10 @@ mod tests {
             results_repo.clone(),
         );
 
-        let ai_context = crate::contexts::ai::AiServiceFactory::from_repositories(
+        let ai_context = Arc::new(crate::contexts::ai::AiServiceFactory::from_repositories(
             ai_repo.clone(),
             settings_repo.clone(),
-        );
+        ));
 
         let state = AppState {
             standard_spider: Arc::new(MockSpider),
@@ -212,7 +248,7 @@ mod tests {
                     Arc::new(MockSpider),
                     Arc::new(crate::extractor::data_extractor::ExtractorRegistry::new()),
                 ),
-                crate::service::processor::Crawler::new(Arc::new(MockSpider)),
+                Arc::new(MockSpider),
                 Arc::new(NilEmitter),
             )),
             permissions: RwLock::new(Policy::default()),
@@ -234,6 +270,8 @@ mod tests {
                                 status: 200,
                                 body: String::new(),
                                 url: String::new(),
+                                headers: Default::default(),
+                                timing: Default::default(),
                             },
                         }),
                         Arc::new(NilEmitter),
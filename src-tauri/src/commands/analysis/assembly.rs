@@ -92,6 +92,7 @@ impl From<CompleteJobResult> for CompleteAnalysisResponse {
         let lighthouse = result.lighthouse;
         let headings = result.headings;
         let images = result.images;
+        let tls = result.tls.map(TlsCertificateInfo::from);
 
         let page_url_by_id: HashMap<&str, &str> = pages
             .iter()
@@ -155,7 +156,13 @@ impl From<CompleteJobResult> for CompleteAnalysisResponse {
             completed_at: job.completed_at.map(|d| d.to_rfc3339()),
             sitemap_found: job.sitemap_found,
             robots_txt_found: job.robots_txt_found,
-            ssl_certificate: job.url.starts_with("https"),
+            // Jobs that weren't inspected (behind a proxy, or from before
+            // inspections existed) fall back to the scheme.
+            ssl_certificate: tls
+                .as_ref()
+                .filter(|tls| tls.skipped.is_none())
+                .map_or(job.url.starts_with("https"), |tls| tls.valid),
+            tls,
             created_at: job.created_at.to_rfc3339(),
         };
 
@@ -2,7 +2,8 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 
 use crate::contexts::analysis::{
-    AnalysisProgress, Job, JobSettings, JobStatus, LinkType,
+    AnalysisProgress, CrawlMode, CrawlProfile, HostOverride, HttpAuth, HttpHeader, Job, JobSettings, JobStatus, LinkType,
+    ProxyConfig, SeedCookie, TlsInspection, UrlListReport, UrlRules,
 };
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, specta::Type)]
@@ -13,6 +14,40 @@ pub struct AnalysisSettingsRequest {
     pub mobile_analysis: bool,
     pub lighthouse_analysis: bool,
     pub delay_between_requests: i64,
+    #[serde(default)]
+    pub url_rules: UrlRules,
+    #[serde(default)]
+    pub crawl_profile: CrawlProfile,
+    #[serde(default)]
+    pub resource_analysis: bool,
+    /// Extra headers, cookies and credentials. Not part of
+    /// [`JobSettings`]: it is stored apart from the job row.
+    #[serde(default)]
+    pub http: Option<HttpSettingsRequest>,
+}
+
+/// HTTP configuration as entered in the UI. Resolved into an
+/// [`HttpConfig`](crate::contexts::analysis::HttpConfig) by reading
+/// `cookies_file` and `hosts_text` and merging what they hold with the
+/// entries typed in.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Type)]
+pub struct HttpSettingsRequest {
+    #[serde(default)]
+    pub headers: Vec<HttpHeader>,
+    #[serde(default)]
+    pub cookies: Vec<SeedCookie>,
+    /// Path to a Netscape-format cookies.txt exported from a browser.
+    #[serde(default)]
+    pub cookies_file: Option<String>,
+    #[serde(default)]
+    pub auth: Option<HttpAuth>,
+    #[serde(default)]
+    pub proxy: Option<ProxyConfig>,
+    #[serde(default)]
+    pub hosts: Vec<HostOverride>,
+    /// Host overrides in `/etc/hosts` format, as pasted.
+    #[serde(default)]
+    pub hosts_text: Option<String>,
 }
 
 pub(crate) trait SettingsExt {
@@ -36,6 +71,10 @@ impl Default for AnalysisSettingsRequest {
             mobile_analysis: false,
             lighthouse_analysis: false,
             delay_between_requests: 50,
+            url_rules: UrlRules::default(),
+            crawl_profile: CrawlProfile::default(),
+            resource_analysis: false,
+            http: None,
         }
     }
 }
@@ -49,6 +88,10 @@ impl From<AnalysisSettingsRequest> for JobSettings {
             mobile_analysis: req.mobile_analysis,
             lighthouse_analysis: req.lighthouse_analysis,
             delay_between_requests: req.delay_between_requests,
+            url_rules: req.url_rules,
+            crawl_mode: CrawlMode::Spider,
+            crawl_profile: req.crawl_profile,
+            resource_analysis: req.resource_analysis,
         }
     }
 }
@@ -60,6 +103,24 @@ pub struct AnalysisJobResponse {
     pub status: JobStatus,
 }
 
+/// Where a list-mode job gets its URLs from.
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UrlListSource {
+    /// URLs pasted by the user, one per line.
+    Text { text: String },
+    /// A local text or CSV file with one URL per row.
+    File { path: String },
+    /// A sitemap (or sitemap index) whose `<loc>` entries are audited.
+    Sitemap { url: String },
+}
+
+#[derive(Debug, serde::Serialize, Type)]
+pub struct ListAnalysisResponse {
+    pub job: AnalysisJobResponse,
+    pub report: UrlListReport,
+}
+
 #[derive(Debug, serde::Serialize, Type)]
 pub struct PaginatedJobsResponse {
     pub items: Vec<AnalysisProgress>,
@@ -177,10 +238,49 @@ pub struct AnalysisResults {
     pub completed_at: Option<String>,
     pub sitemap_found: bool,
     pub robots_txt_found: bool,
+    /// The site's certificate passed a verified handshake.
     pub ssl_certificate: bool,
+    pub tls: Option<TlsCertificateInfo>,
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct TlsCertificateInfo {
+    pub host: String,
+    pub valid: bool,
+    pub protocol: Option<String>,
+    pub subject: Option<String>,
+    pub subject_alt_names: Vec<String>,
+    pub issuer: Option<String>,
+    pub not_before: Option<String>,
+    pub not_after: Option<String>,
+    pub chain_length: i64,
+    /// Why the certificate isn't trusted, or why no connection was made.
+    pub error: Option<String>,
+    /// Why the certificate wasn't inspected at all.
+    pub skipped: Option<String>,
+    pub checked_at: String,
+}
+
+impl From<TlsInspection> for TlsCertificateInfo {
+    fn from(inspection: TlsInspection) -> Self {
+        Self {
+            valid: inspection.is_valid(),
+            error: inspection.handshake_error.or(inspection.verify_error),
+            host: inspection.host,
+            protocol: inspection.protocol,
+            subject: inspection.subject,
+            subject_alt_names: inspection.subject_alt_names,
+            issuer: inspection.issuer,
+            not_before: inspection.not_before.map(|d| d.to_rfc3339()),
+            not_after: inspection.not_after.map(|d| d.to_rfc3339()),
+            chain_length: inspection.chain_length as i64,
+            skipped: inspection.skipped,
+            checked_at: inspection.checked_at.to_rfc3339(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Type)]
 pub struct CompleteAnalysisResponse {
     pub analysis: AnalysisResults,
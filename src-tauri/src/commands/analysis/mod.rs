@@ -3,17 +3,22 @@ mod dto;
 
 pub use dto::*;
 
+use std::sync::Arc;
+
 use anyhow::{Context, Result};
 use tauri::State;
 use url::Url;
 
 use crate::{
     contexts::{
-        analysis::{AnalysisProgress, JobSettings, JobStatus},
+        analysis::{AnalysisProgress, CrawlProfile, HostOverride, HttpConfig, JobSettings, JobStatus, SeedCookie, UrlListReport},
         permissions::{Feature, PermissionRequest, Policy},
     },
     error::CommandError,
+    extractor::sitemap,
     lifecycle::app_state::AppState,
+    service::cloaking::{self, CloakingProbe, CloakingReport},
+    service::spider::{Spider, SpiderAgent},
 };
 use addon_macros::addon_guard;
 
@@ -33,6 +38,37 @@ fn validate_url(url: &str) -> Result<Url> {
     Ok(parsed)
 }
 
+/// Turn the UI's HTTP settings into the job's configuration, loading the
+/// cookies.txt file if one was picked. `None` when nothing was set.
+async fn resolve_http_config(request: Option<HttpSettingsRequest>) -> Result<Option<HttpConfig>> {
+    let Some(request) = request else {
+        return Ok(None);
+    };
+
+    let mut cookies = request.cookies;
+    if let Some(path) = request.cookies_file.filter(|path| !path.trim().is_empty()) {
+        let text = tokio::fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read cookies from {}", path))?;
+        cookies.extend(SeedCookie::parse_netscape(&text)?);
+    }
+
+    let mut hosts = request.hosts;
+    if let Some(text) = &request.hosts_text {
+        hosts.extend(HostOverride::parse_hosts(text)?);
+    }
+
+    let config = HttpConfig {
+        headers: request.headers,
+        cookies,
+        auth: request.auth,
+        proxy: request.proxy.filter(|proxy| !proxy.url.trim().is_empty()),
+        hosts,
+    };
+    config.validate()?;
+    Ok((!config.is_empty()).then_some(config))
+}
+
 #[tauri::command]
 #[addon_guard(PermissionRequest::AnalyzePages(settings.requested_page_count()))]
 #[specta::specta]
@@ -45,11 +81,15 @@ pub async fn start_analysis(
     tracing::info!("Settings: {:?}", settings);
     let parsed_url = validate_url(&url).map_err(CommandError::from)?;
 
-    let analysis_settings: JobSettings = settings.unwrap_or_default().into();
+    let mut settings = settings.unwrap_or_default();
+    let http_config = resolve_http_config(settings.http.take())
+        .await
+        .map_err(CommandError::from)?;
+    let analysis_settings: JobSettings = settings.into();
 
     let job_id = app_state
         .analysis_context
-        .create_job(parsed_url.as_str(), &analysis_settings)
+        .create_job_with(parsed_url.as_str(), &analysis_settings, http_config.as_ref())
         .await
         .map_err(CommandError::from)?;
 
@@ -63,6 +103,103 @@ pub async fn start_analysis(
     })
 }
 
+/// Resolve a list source into the URLs it names, capped at `max_urls`.
+/// A sitemap is fetched the way the job will crawl: with its profile and
+/// HTTP configuration.
+async fn read_url_list(
+    source: &UrlListSource,
+    max_urls: usize,
+    profile: &CrawlProfile,
+    http_config: Option<&HttpConfig>,
+    app_state: &AppState,
+) -> Result<UrlListReport> {
+    match source {
+        UrlListSource::Text { text } => Ok(UrlListReport::parse(text, max_urls)),
+        UrlListSource::File { path } => {
+            let text = tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("Failed to read URL list from {}", path))?;
+            Ok(UrlListReport::parse(&text, max_urls))
+        }
+        UrlListSource::Sitemap { url } => {
+            let sitemap_url = validate_url(url)?;
+            let spider: Arc<dyn SpiderAgent> = if http_config.is_some() || !profile.is_default() {
+                Arc::new(Spider::for_job(profile, http_config, sitemap_url.as_str())?)
+            } else {
+                app_state.standard_spider.clone()
+            };
+            let urls = sitemap::fetch_sitemap_urls(&sitemap_url, spider)
+                .await
+                .with_context(|| format!("Failed to load sitemap {}", url))?;
+            Ok(UrlListReport::from_urls(urls, max_urls))
+        }
+    }
+}
+
+/// Start a list-mode job: analyse exactly the given URLs, with no link
+/// discovery. URLs past the page limit, duplicates and unusable lines are
+/// left out and described in the returned report.
+#[tauri::command]
+#[addon_guard(PermissionRequest::AnalyzePages(settings.requested_page_count()))]
+#[specta::specta]
+pub async fn start_list_analysis(
+    source: UrlListSource,
+    settings: Option<AnalysisSettingsRequest>,
+    #[provider] app_state: State<'_, AppState>,
+) -> Result<ListAnalysisResponse, CommandError> {
+    let mut settings = settings.unwrap_or_default();
+    let http_config = resolve_http_config(settings.http.take())
+        .await
+        .map_err(CommandError::from)?;
+    let analysis_settings: JobSettings = settings.into();
+    let max_urls = analysis_settings.max_pages.max(0) as usize;
+
+    let report = read_url_list(
+        &source,
+        max_urls,
+        &analysis_settings.crawl_profile,
+        http_config.as_ref(),
+        &app_state,
+    )
+        .await
+        .map_err(CommandError::from)?;
+    tracing::info!(
+        "Starting list analysis: {} accepted, {} duplicates, {} invalid, {} over limit",
+        report.accepted.len(),
+        report.duplicates.len(),
+        report.invalid.len(),
+        report.over_limit
+    );
+
+    let Some(first_url) = report.accepted.first() else {
+        return Err(CommandError::from(anyhow::anyhow!(
+            "The list contains no usable URLs"
+        )));
+    };
+    let label_url = match &source {
+        UrlListSource::Sitemap { url } => url.clone(),
+        _ => first_url.clone(),
+    };
+
+    let job_id = app_state
+        .analysis_context
+        .create_list_job(&label_url, &report.accepted, &analysis_settings, http_config.as_ref())
+        .await
+        .map_err(CommandError::from)?;
+
+    app_state.analysis_context.notify_new_job().await;
+    tracing::debug!("Notified job processor of new list job: {}", job_id);
+
+    Ok(ListAnalysisResponse {
+        job: AnalysisJobResponse {
+            job_id: job_id.into_string(),
+            url: label_url,
+            status: JobStatus::Pending,
+        },
+        report,
+    })
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_analysis_defaults() -> Result<AnalysisSettingsRequest, CommandError> {
@@ -160,6 +297,36 @@ pub async fn cancel_analysis(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+#[specta::specta]
+#[addon_guard(PermissionRequest::UseFeature(Feature::LinkAnalysis))]
+pub async fn pause_analysis(
+    job_id: String,
+    #[provider] state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    tracing::trace!("Pausing analysis job: {}", job_id);
+    state
+        .analysis_context
+        .pause_job(&job_id)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+#[addon_guard(PermissionRequest::UseFeature(Feature::LinkAnalysis))]
+pub async fn resume_analysis(
+    job_id: String,
+    #[provider] state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    tracing::trace!("Resuming analysis job: {}", job_id);
+    state
+        .analysis_context
+        .resume_job(&job_id)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_result(
@@ -176,6 +343,71 @@ pub async fn get_result(
         .map(|r| r.into())
 }
 
+fn cloaking_sample_size(requested: Option<u32>) -> usize {
+    requested
+        .map_or(cloaking::DEFAULT_SAMPLE_SIZE, |n| n as usize)
+        .clamp(1, cloaking::MAX_SAMPLE_SIZE)
+}
+
+/// Re-fetch a sample of a job's pages as the job's own profile and as
+/// `compare_with`, and report where the two were served different
+/// content.
+#[tauri::command]
+#[addon_guard(PermissionRequest::AnalyzePages(cloaking_sample_size(sample_size)))]
+#[specta::specta]
+pub async fn check_cloaking(
+    job_id: String,
+    compare_with: CrawlProfile,
+    sample_size: Option<u32>,
+    #[provider] app_state: State<'_, AppState>,
+) -> Result<CloakingReport, CommandError> {
+    let job = app_state
+        .analysis_context
+        .get_job(&job_id)
+        .await
+        .map_err(CommandError::from)?;
+    let baseline = job.settings.crawl_profile.clone();
+    if baseline == compare_with {
+        return Err(CommandError::from(anyhow::anyhow!(
+            "The job already crawled as {}; pick another profile to compare with",
+            baseline.label()
+        )));
+    }
+    tracing::info!(
+        "Checking job {} for cloaking: {} vs {}",
+        job_id,
+        baseline.label(),
+        compare_with.label()
+    );
+
+    let urls: Vec<String> = app_state
+        .analysis_context
+        .get_pages(&job_id)
+        .await
+        .map_err(CommandError::from)?
+        .into_iter()
+        .map(|page| page.url)
+        .collect();
+    let sample = cloaking::sample_evenly(&urls, cloaking_sample_size(sample_size));
+
+    let http_config = app_state
+        .analysis_context
+        .get_http_config(&job_id)
+        .await
+        .map_err(CommandError::from)?;
+    let probe = |profile: CrawlProfile| -> Result<CloakingProbe> {
+        let spider = Spider::for_job(&profile, http_config.as_ref(), &job.url)?;
+        Ok(CloakingProbe {
+            profile,
+            spider: Arc::new(spider),
+        })
+    };
+    let baseline = probe(baseline).map_err(CommandError::from)?;
+    let compared = probe(compare_with).map_err(CommandError::from)?;
+
+    Ok(cloaking::compare_profiles(&baseline, &compared, &sample).await)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,11 +457,15 @@ mod tests {
             robots_meta: None,
             word_count: Some(100),
             load_time_ms: Some(4000),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: Some(1024),
             has_viewport: false,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            weight: None,
         };
 
         let page_id = page_repo.insert(&page).await.unwrap();
@@ -298,11 +534,15 @@ mod tests {
             robots_meta: None,
             word_count: Some(200),
             load_time_ms: Some(1000),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: Some(512),
             has_viewport: true,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            weight: None,
         };
 
         page_repo.insert(&page).await.unwrap();
@@ -349,11 +589,15 @@ mod tests {
             robots_meta: None,
             word_count: Some(10),
             load_time_ms: Some(500),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: Some(256),
             has_viewport: false,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            weight: None,
         };
 
         let page_id = page_repo.insert(&page).await.unwrap();
@@ -440,11 +684,15 @@ mod tests {
             robots_meta: None,
             word_count: Some(100),
             load_time_ms: Some(500),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: Some(1024),
             has_viewport: true,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            weight: None,
         };
 
         let page_id = page_repo.insert(&page).await.unwrap();
@@ -502,11 +750,15 @@ mod tests {
             robots_meta: None,
             word_count: Some(100),
             load_time_ms: Some(500),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: Some(1024),
             has_viewport: true,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            weight: None,
         };
 
         page_repo.insert(&page).await.unwrap();
@@ -2,10 +2,13 @@ use tauri::{command, State};
 
 use crate::{
     contexts::extension::{
-        CustomCheck, CustomCheckParams, CustomExtractor, CustomExtractorParams,
+        AppliedChecks, CheckDryRun, CustomCheck, CustomCheckParams, CustomExtractor,
+        CustomExtractorParams, ExtractorDryRun, ScriptDryRun,
     },
+    checker::custom,
     contexts::tags::{Tag, TagRegistry, TagScope},
     error::CommandError,
+    extractor::data_extractor::{self, ExtractorConfig},
     lifecycle::app_state::AppState,
 };
 
@@ -21,12 +24,21 @@ pub async fn list_custom_checks(
         .map_err(CommandError::from)
 }
 
+/// Reject a check whose condition or site aggregate couldn't be
+/// evaluated, so a typo'd threshold or regex fails at save time instead
+/// of never firing.
+fn validate_check(params: &CustomCheckParams) -> Result<(), CommandError> {
+    custom::validate(params)?;
+    Ok(())
+}
+
 #[command]
 #[specta::specta]
 pub async fn create_custom_check(
     params: CustomCheckParams,
     app_state: State<'_, AppState>,
 ) -> Result<CustomCheck, CommandError> {
+    validate_check(&params)?;
     app_state
         .extension_repo
         .create_check(&params)
@@ -41,6 +53,7 @@ pub async fn update_custom_check(
     params: CustomCheckParams,
     app_state: State<'_, AppState>,
 ) -> Result<CustomCheck, CommandError> {
+    validate_check(&params)?;
     app_state
         .extension_repo
         .update_check(&id, &params)
@@ -61,6 +74,57 @@ pub async fn delete_custom_check(
         .map_err(CommandError::from)
 }
 
+/// Run a draft check's script against a stored page of `job_id`, for
+/// debugging it before it's saved. Script errors and printed output come
+/// back in the result.
+#[command]
+#[specta::specta]
+pub async fn dry_run_check_script(
+    params: CustomCheckParams,
+    job_id: String,
+    url: String,
+    app_state: State<'_, AppState>,
+) -> Result<ScriptDryRun, CommandError> {
+    app_state
+        .dry_run_context
+        .check_script(&params, &job_id, &url)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Run a draft check over every page of the finished job `job_id` and
+/// return the issues it would raise, without storing them.
+#[command]
+#[specta::specta]
+pub async fn dry_run_custom_check(
+    params: CustomCheckParams,
+    job_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<CheckDryRun, CommandError> {
+    validate_check(&params)?;
+    app_state
+        .dry_run_context
+        .run_check(&params, &job_id)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Re-run the saved custom checks on the finished job `job_id`,
+/// re-parsing its saved HTML with the current extractors, and replace
+/// the issues they raised during the crawl.
+#[command]
+#[specta::specta]
+pub async fn apply_custom_checks(
+    job_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<AppliedChecks, CommandError> {
+    app_state
+        .dry_run_context
+        .apply_checks(&job_id)
+        .await
+        .map_err(CommandError::from)
+}
+
 // --- Tags ---
 
 /// Return the full tag catalog so the frontend can render tag pickers
@@ -87,6 +151,13 @@ pub async fn list_tags(
 
 // --- Custom Extractors ---
 
+/// Reject an extractor whose selector, XPath, regex or JSON path doesn't
+/// compile; at crawl time it would be skipped without a trace.
+fn validate_extractor(params: &CustomExtractorParams) -> Result<(), CommandError> {
+    data_extractor::build(ExtractorConfig::from(params))?;
+    Ok(())
+}
+
 #[command]
 #[specta::specta]
 pub async fn list_custom_extractors(
@@ -105,6 +176,7 @@ pub async fn create_custom_extractor(
     params: CustomExtractorParams,
     app_state: State<'_, AppState>,
 ) -> Result<CustomExtractor, CommandError> {
+    validate_extractor(&params)?;
     app_state
         .extension_repo
         .create_extractor(&params)
@@ -119,6 +191,7 @@ pub async fn update_custom_extractor(
     params: CustomExtractorParams,
     app_state: State<'_, AppState>,
 ) -> Result<CustomExtractor, CommandError> {
+    validate_extractor(&params)?;
     app_state
         .extension_repo
         .update_extractor(&id, &params)
@@ -138,3 +211,20 @@ pub async fn delete_custom_extractor(
         .await
         .map_err(CommandError::from)
 }
+
+/// Run a draft extractor over the saved HTML of every page of the
+/// finished job `job_id` and return what it extracts from each.
+#[command]
+#[specta::specta]
+pub async fn dry_run_custom_extractor(
+    params: CustomExtractorParams,
+    job_id: String,
+    app_state: State<'_, AppState>,
+) -> Result<ExtractorDryRun, CommandError> {
+    let extractor = data_extractor::build(ExtractorConfig::from(&params))?;
+    app_state
+        .dry_run_context
+        .run_extractor(extractor.as_ref(), &job_id)
+        .await
+        .map_err(CommandError::from)
+}
@@ -80,6 +80,8 @@ mod tests {
                 status: 200,
                 body: String::new(),
                 url: String::new(),
+                headers: Default::default(),
+                timing: Default::default(),
             })
         }
         async fn post_json(
@@ -91,11 +93,13 @@ mod tests {
                 status: 200,
                 body: String::new(),
                 url: String::new(),
+                headers: Default::default(),
+                timing: Default::default(),
             })
         }
         async fn stream_get(&self, _url: &str) -> anyhow::Result<crate::service::spider::StreamResponse> {
             use crate::service::spider::MockSpider as Ms;
-            let ms = Ms { html_response: String::new(), generic_response: crate::service::spider::SpiderResponse { status: 200, body: String::new(), url: String::new() } };
+            let ms = Ms { html_response: String::new(), generic_response: crate::service::spider::SpiderResponse { status: 200, body: String::new(), url: String::new(), headers: Default::default(), timing: Default::default() } };
             ms.stream_get(_url).await
         }
         async fn stream_get_range(&self, _url: &str, _start_byte: u64) -> anyhow::Result<crate::service::spider::StreamResponse> {
@@ -123,10 +127,10 @@ mod tests {
         );
 
         // Create the new context-based AI service
-        let ai_context = crate::contexts::ai::AiServiceFactory::from_repositories(
+        let ai_context = Arc::new(crate::contexts::ai::AiServiceFactory::from_repositories(
             ai_repo.clone(),
             settings_repo.clone(),
-        );
+        ));
 
         let state = AppState {
             standard_spider: Arc::new(MockSpider),
@@ -141,7 +145,7 @@ mod tests {
                     Arc::new(MockSpider),
                     Arc::new(crate::extractor::data_extractor::ExtractorRegistry::new()),
                 ),
-                crate::service::processor::Crawler::new(Arc::new(MockSpider)),
+                Arc::new(MockSpider),
                 Arc::new(NilEmitter),
             )),
             permissions: RwLock::new(Policy::default()),
@@ -163,6 +167,8 @@ mod tests {
                                 status: 200,
                                 body: String::new(),
                                 url: String::new(),
+                                headers: Default::default(),
+                                timing: Default::default(),
                             },
                         }),
                         Arc::new(NilEmitter),
@@ -16,6 +16,8 @@ mod extension;
 mod licensing;
 mod local_model;
 mod report;
+mod rule_pack;
+mod snapshot;
 
 pub fn register_commands() -> Commands<tauri::Wry> {
     collect_commands![
@@ -35,13 +37,21 @@ pub fn register_commands() -> Commands<tauri::Wry> {
         ai::set_gemini_enabled,
         ai::get_ai_source,
         ai::set_ai_source,
+        ai::get_openai_settings,
+        ai::set_openai_settings,
+        ai::get_ollama_settings,
+        ai::set_ollama_settings,
         // Analysis commands
         analysis::start_analysis,
+        analysis::start_list_analysis,
         analysis::get_analysis_progress,
         analysis::get_all_jobs,
         analysis::get_paginated_jobs,
         analysis::cancel_analysis,
+        analysis::pause_analysis,
+        analysis::resume_analysis,
         analysis::get_result,
+        analysis::check_cloaking,
         analysis::get_analysis_defaults,
         analysis::get_free_tier_defaults,
         // Licensing commands
@@ -63,10 +73,14 @@ pub fn register_commands() -> Commands<tauri::Wry> {
         extension::create_custom_check,
         extension::update_custom_check,
         extension::delete_custom_check,
+        extension::dry_run_check_script,
+        extension::dry_run_custom_check,
+        extension::apply_custom_checks,
         extension::list_custom_extractors,
         extension::create_custom_extractor,
         extension::update_custom_extractor,
         extension::delete_custom_extractor,
+        extension::dry_run_custom_extractor,
         extension::list_tags,
         // Report commands
         report::list_report_patterns,
@@ -82,6 +96,16 @@ pub fn register_commands() -> Commands<tauri::Wry> {
         report::update_report_template,
         report::set_active_report_template,
         report::delete_report_template,
+        // Rule pack commands
+        rule_pack::export_rule_pack,
+        rule_pack::preview_rule_pack,
+        rule_pack::import_rule_pack,
+        // Page snapshot commands
+        snapshot::get_page_source,
+        snapshot::diff_page_source,
+        snapshot::get_snapshot_settings,
+        snapshot::set_snapshot_settings,
+        snapshot::get_snapshot_storage,
     ]
 }
 
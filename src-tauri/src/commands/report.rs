@@ -1,3 +1,4 @@
+use crate::contexts::report::services::pattern_engine::CompiledCondition;
 use crate::contexts::report::{ReportData, ReportPattern, ReportPatternParams, ReportTemplate};
 use crate::error::CommandError;
 use crate::lifecycle::app_state::AppState;
@@ -15,12 +16,25 @@ pub async fn list_report_patterns(
         .map_err(CommandError::from)
 }
 
+/// Reject a pattern whose condition couldn't be evaluated, so a typo'd
+/// threshold or regex fails at save time instead of matching nothing.
+fn validate_pattern(params: &ReportPatternParams) -> Result<(), CommandError> {
+    CompiledCondition::for_rule(
+        &params.field,
+        &params.operator,
+        params.threshold.as_deref(),
+        params.condition.as_ref(),
+    )?;
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn create_report_pattern(
     params: ReportPatternParams,
     state: State<'_, AppState>,
 ) -> Result<ReportPattern, CommandError> {
+    validate_pattern(&params)?;
     state
         .report_pattern_repo
         .create_pattern(&params)
@@ -35,6 +49,7 @@ pub async fn update_report_pattern(
     params: ReportPatternParams,
     state: State<'_, AppState>,
 ) -> Result<ReportPattern, CommandError> {
+    validate_pattern(&params)?;
     state
         .report_pattern_repo
         .update_pattern(&id, &params)
@@ -0,0 +1,52 @@
+use tauri::State;
+
+use crate::contexts::rule_pack::{ImportStrategy, RulePackExport, RulePackItem, RulePackPreview};
+use crate::error::CommandError;
+use crate::lifecycle::app_state::AppState;
+
+/// Bundle the selected checks, extractors, patterns and templates into a
+/// rule pack and return its contents for the frontend to save.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_rule_pack(
+    request: RulePackExport,
+    state: State<'_, AppState>,
+) -> Result<String, CommandError> {
+    state
+        .rule_pack_context
+        .export(&request)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Describe a rule pack file's contents and what importing it with
+/// `strategy` would do, without importing anything.
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_rule_pack(
+    content: String,
+    strategy: ImportStrategy,
+    state: State<'_, AppState>,
+) -> Result<RulePackPreview, CommandError> {
+    state
+        .rule_pack_context
+        .preview(&content, strategy)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Import a rule pack. New checks and extractors take effect from the
+/// next app start, like ones created in the editors.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_rule_pack(
+    content: String,
+    strategy: ImportStrategy,
+    state: State<'_, AppState>,
+) -> Result<Vec<RulePackItem>, CommandError> {
+    state
+        .rule_pack_context
+        .import(&content, strategy)
+        .await
+        .map_err(CommandError::from)
+}
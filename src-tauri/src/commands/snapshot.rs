@@ -0,0 +1,70 @@
+use tauri::State;
+
+use crate::contexts::snapshot::{PageSnapshot, SnapshotSettings, SnapshotStorage, SourceDiff};
+use crate::error::CommandError;
+use crate::lifecycle::app_state::AppState;
+
+/// The HTML and response headers the page of `job_id` crawled at `url`
+/// was served with.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_page_source(
+    job_id: String,
+    url: String,
+    state: State<'_, AppState>,
+) -> Result<PageSnapshot, CommandError> {
+    state
+        .snapshot_context
+        .page_source(&job_id, &url)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Compare the source of the page of `job_id` crawled at `url` with the
+/// same URL in `against_job_id`, or in the latest earlier crawl when
+/// `None`.
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_page_source(
+    job_id: String,
+    url: String,
+    against_job_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SourceDiff, CommandError> {
+    state
+        .snapshot_context
+        .diff(&job_id, &url, against_job_id.as_deref())
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_snapshot_settings(
+    state: State<'_, AppState>,
+) -> Result<SnapshotSettings, CommandError> {
+    Ok(state.snapshot_context.settings())
+}
+
+/// Save the snapshot settings, drop snapshots past the new retention
+/// period and return what the rest take up.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_snapshot_settings(
+    settings: SnapshotSettings,
+    state: State<'_, AppState>,
+) -> Result<SnapshotStorage, CommandError> {
+    state
+        .snapshot_context
+        .set_settings(settings)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_snapshot_storage(
+    state: State<'_, AppState>,
+) -> Result<SnapshotStorage, CommandError> {
+    state.snapshot_context.storage().await.map_err(CommandError::from)
+}
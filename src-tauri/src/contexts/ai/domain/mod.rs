@@ -22,6 +22,10 @@ pub enum AiSource {
     #[default]
     Gemini,
     Local,
+    /// Any server speaking the OpenAI chat completions API.
+    OpenAiCompatible,
+    /// An Ollama server, usually on the user's own machine.
+    Ollama,
 }
 
 /// Returned by [`AiSource::from_str`] when the input doesn't map to a
@@ -36,6 +40,8 @@ impl AiSource {
         match self {
             Self::Gemini => "gemini",
             Self::Local => "local",
+            Self::OpenAiCompatible => "openai",
+            Self::Ollama => "ollama",
         }
     }
 }
@@ -46,6 +52,8 @@ impl std::str::FromStr for AiSource {
         match s {
             "gemini" => Ok(Self::Gemini),
             "local" => Ok(Self::Local),
+            "openai" => Ok(Self::OpenAiCompatible),
+            "ollama" => Ok(Self::Ollama),
             other => Err(ParseAiSourceError(other.to_string())),
         }
     }
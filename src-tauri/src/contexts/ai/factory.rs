@@ -4,6 +4,7 @@
 use std::sync::Arc;
 use super::services::AiService;
 use crate::repository::{AiRepository, SettingsRepository};
+use crate::service::spider::SpiderAgent;
 
 /// Factory for creating AiService instances
 pub struct AiServiceFactory;
@@ -16,4 +17,14 @@ impl AiServiceFactory {
     ) -> AiService {
         AiService::new(ai_repo, settings_repo)
     }
+
+    /// Create an AiService that can reach the HTTP AI backends through
+    /// `spider`
+    pub fn with_spider(
+        ai_repo: Arc<dyn AiRepository>,
+        settings_repo: Arc<dyn SettingsRepository>,
+        spider: Arc<dyn SpiderAgent>,
+    ) -> AiService {
+        AiService::with_spider(ai_repo, settings_repo, spider)
+    }
 }
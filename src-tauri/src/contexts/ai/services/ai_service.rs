@@ -3,10 +3,19 @@
 
 use std::sync::Arc;
 use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use super::super::domain::*;
+use crate::contexts::local_model::LocalModelService;
 use crate::repository::{AiRepository, SettingsRepository};
+use crate::service::ai_backend::generate_analysis;
 use crate::service::prompt::{PERSONA_SETTING_KEY, PROMPT_BLOCKS_SETTING_KEY};
+use crate::service::spider::SpiderAgent;
+use crate::service::{
+    AiBackend, GeminiBackend, GeminiRequest, OllamaBackend, OllamaSettings,
+    OpenAiCompatibleBackend, OpenAiCompatibleSettings,
+};
 
 /// Setting key for the active AI backend selector.
 const AI_SOURCE_SETTING_KEY: &str = "ai_source";
@@ -18,8 +27,10 @@ const GEMINI_REQUIREMENTS_SETTING_KEY: &str = "gemini_requirements";
 const GEMINI_CONTEXT_OPTIONS_SETTING_KEY: &str = "gemini_context_options";
 /// Setting key for the AI-enabled feature flag.
 const GEMINI_ENABLED_SETTING_KEY: &str = "gemini_enabled";
-use crate::service::spider::SpiderAgent;
-use crate::service::{generate_gemini_analysis, GeminiRequest};
+/// Setting key for the OpenAI-compatible backend's settings, as JSON.
+const OPENAI_SETTINGS_KEY: &str = "ai_openai_settings";
+/// Setting key for the Ollama backend's settings, as JSON.
+const OLLAMA_SETTINGS_KEY: &str = "ai_ollama_settings";
 
 /// Service layer for the AI bounded context.
 /// Single point of coupling - external modules interact only through this service.
@@ -27,6 +38,7 @@ pub struct AiService {
     ai_repo: Arc<dyn AiRepository>,
     settings_repo: Arc<dyn SettingsRepository>,
     spider: Option<Arc<dyn SpiderAgent>>,
+    local_model: Option<Arc<LocalModelService>>,
 }
 
 impl AiService {
@@ -35,7 +47,7 @@ impl AiService {
         ai_repo: Arc<dyn AiRepository>,
         settings_repo: Arc<dyn SettingsRepository>,
     ) -> Self {
-        Self { ai_repo, settings_repo, spider: None }
+        Self { ai_repo, settings_repo, spider: None, local_model: None }
     }
 
     /// Create a new AiService with spider for AI generation
@@ -44,7 +56,13 @@ impl AiService {
         settings_repo: Arc<dyn SettingsRepository>,
         spider: Arc<dyn SpiderAgent>,
     ) -> Self {
-        Self { ai_repo, settings_repo, spider: Some(spider) }
+        Self { ai_repo, settings_repo, spider: Some(spider), local_model: None }
+    }
+
+    /// Serve [`AiSource::Local`] from the active model of `local_model`.
+    pub fn with_local_model(mut self, local_model: Arc<LocalModelService>) -> Self {
+        self.local_model = Some(local_model);
+        self
     }
 
     // === Insight Generation ===
@@ -58,21 +76,48 @@ impl AiService {
             return Ok(String::new());
         }
 
-        // Get spider or return error
-        let spider = self.spider.as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Spider not configured for AI service"))?;
-
-        // Use the existing generate_gemini_analysis function
-        generate_gemini_analysis(
+        let backend = self.backend().await?;
+        tracing::info!("Generating AI insights with {}", backend.name());
+        generate_analysis(
             self.ai_repo.clone(),
             self.settings_repo.clone(),
-            request,
-            spider.clone(),
-            None,
+            &request,
+            backend.as_ref(),
         )
         .await
     }
 
+    /// The backend for the selected [`AiSource`], set up from its saved
+    /// settings. Insights and the AI sections of reports both go through
+    /// it.
+    pub async fn backend(&self) -> Result<Box<dyn AiBackend>> {
+        let spider = || {
+            self.spider
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("Spider not configured for AI service"))
+        };
+
+        let backend: Box<dyn AiBackend> = match self.get_ai_source_typed().await? {
+            AiSource::Gemini => Box::new(
+                GeminiBackend::from_settings(self.settings_repo.as_ref(), spider()?, None).await?,
+            ),
+            AiSource::OpenAiCompatible => {
+                Box::new(OpenAiCompatibleBackend::new(spider()?, &self.get_openai_settings().await?)?)
+            }
+            AiSource::Ollama => {
+                Box::new(OllamaBackend::new(spider()?, &self.get_ollama_settings().await?)?)
+            }
+            AiSource::Local => {
+                let local_model = self
+                    .local_model
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("Local model not configured for AI service"))?;
+                Box::new(local_model.backend().await?)
+            }
+        };
+        Ok(backend)
+    }
+
     /// Get stored AI insights for a job
     pub async fn get_insights(&self, job_id: &str) -> Result<Option<AiInsight>> {
         let insights = self.ai_repo.get_ai_insights(job_id).await?;
@@ -155,6 +200,24 @@ impl AiService {
         Ok(val.map(|v| v != "false").unwrap_or(true))
     }
 
+    /// Settings for the OpenAI-compatible backend; defaults until saved.
+    pub async fn get_openai_settings(&self) -> Result<OpenAiCompatibleSettings> {
+        self.get_json_setting(OPENAI_SETTINGS_KEY).await
+    }
+
+    pub async fn set_openai_settings(&self, settings: &OpenAiCompatibleSettings) -> Result<()> {
+        self.set_json_setting(OPENAI_SETTINGS_KEY, settings).await
+    }
+
+    /// Settings for the Ollama backend; defaults until saved.
+    pub async fn get_ollama_settings(&self) -> Result<OllamaSettings> {
+        self.get_json_setting(OLLAMA_SETTINGS_KEY).await
+    }
+
+    pub async fn set_ollama_settings(&self, settings: &OllamaSettings) -> Result<()> {
+        self.set_json_setting(OLLAMA_SETTINGS_KEY, settings).await
+    }
+
     /// Get the active AI source. Defaults to [`AiSource::Gemini`] if unset
     /// or if a corrupted/legacy value is in the settings row (logs a
     /// warning in that case so the drift is visible).
@@ -195,6 +258,21 @@ impl AiService {
         self.set_ai_source_typed(parsed).await
     }
 
+    /// A JSON setting, or `T::default()` when it's unset or unreadable
+    /// (logged, like the prompt blocks).
+    async fn get_json_setting<T: DeserializeOwned + Default>(&self, key: &str) -> Result<T> {
+        Ok(match self.settings_repo.get_setting(key).await? {
+            None => T::default(),
+            Some(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                tracing::warn!("ai: invalid {key} setting ({e}); using defaults");
+                T::default()
+            }),
+        })
+    }
+
+    async fn set_json_setting<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        Ok(self.settings_repo.set_setting(key, &serde_json::to_string(value)?).await?)
+    }
 }
 
 #[cfg(test)]
@@ -10,6 +10,8 @@ use crate::contexts::ai::{
     AiService, PromptConfig, PromptBlock,
 };
 use crate::repository::{AiRepository, SettingsRepository};
+use crate::service::spider::{ClientType, Spider};
+use crate::service::{OllamaSettings, OpenAiCompatibleSettings};
 
 // ============================================================================
 // Mock Repositories for Testing
@@ -17,7 +19,8 @@ use crate::repository::{AiRepository, SettingsRepository};
 
 /// Mock AiRepository for testing
 struct MockAiRepository {
-    insights: RwLock<HashMap<String, String>>,
+    /// Job id → (backend, insights).
+    insights: RwLock<HashMap<String, (String, String)>>,
 }
 
 impl MockAiRepository {
@@ -34,15 +37,28 @@ impl AiRepository for MockAiRepository {
         &self,
         job_id: &str,
     ) -> crate::repository::RepositoryResult<Option<String>> {
-        Ok(self.insights.read().unwrap().get(job_id).cloned())
+        Ok(self.insights.read().unwrap().get(job_id).map(|(_, text)| text.clone()))
+    }
+
+    async fn get_ai_insights_from(
+        &self,
+        job_id: &str,
+        backend: &str,
+    ) -> crate::repository::RepositoryResult<Option<String>> {
+        let insights = self.insights.read().unwrap();
+        Ok(insights.get(job_id).filter(|(by, _)| by == backend).map(|(_, text)| text.clone()))
     }
 
     async fn save_ai_insights(
         &self,
         job_id: &str,
+        backend: &str,
         insights: &str,
     ) -> crate::repository::RepositoryResult<()> {
-        self.insights.write().unwrap().insert(job_id.to_string(), insights.to_string());
+        self.insights
+            .write()
+            .unwrap()
+            .insert(job_id.to_string(), (backend.to_string(), insights.to_string()));
         Ok(())
     }
 }
@@ -179,7 +195,7 @@ async fn test_ai_service_enabled_by_default() {
 async fn test_ai_service_get_insights() {
     // Arrange
     let ai_repo = Arc::new(MockAiRepository::new());
-    ai_repo.save_ai_insights("job-123", "Test insights").await.unwrap();
+    ai_repo.save_ai_insights("job-123", "Gemini", "Test insights").await.unwrap();
     let settings_repo = Arc::new(MockSettingsRepository::new());
     let service = AiService::new(ai_repo, settings_repo);
     
@@ -249,6 +265,121 @@ async fn test_ai_service_generate_insights_disabled() {
     assert_eq!(result.unwrap(), "");
 }
 
+/// Test: the HTTP backends' settings default until saved, then round-trip
+#[tokio::test]
+async fn test_ai_service_backend_settings() {
+    let ai_repo = Arc::new(MockAiRepository::new());
+    let settings_repo = Arc::new(MockSettingsRepository::new());
+    let service = AiService::new(ai_repo, settings_repo);
+
+    let ollama = service.get_ollama_settings().await.unwrap();
+    assert_eq!(ollama.base_url, "http://localhost:11434");
+    assert!(ollama.model.is_empty());
+
+    let openai = OpenAiCompatibleSettings {
+        base_url: "http://localhost:1234/v1".to_string(),
+        model: "qwen2.5-7b-instruct".to_string(),
+        api_key: String::new(),
+    };
+    service.set_openai_settings(&openai).await.unwrap();
+    assert_eq!(service.get_openai_settings().await.unwrap(), openai);
+
+    service.set_ai_source("ollama").await.unwrap();
+    assert_eq!(service.get_ai_source().await.unwrap(), "ollama");
+    service.set_ai_source("openai").await.unwrap();
+    assert_eq!(service.get_ai_source().await.unwrap(), "openai");
+}
+
+/// Test: generate_insights sends the prompt to the selected backend and
+/// caches the answer
+#[tokio::test]
+async fn test_ai_service_generate_insights_with_ollama() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/api/generate")
+        .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+            "model": "llama3.1:8b",
+            "stream": false,
+        })))
+        .with_status(200)
+        .with_body(serde_json::json!({ "response": "Ollama insights", "done": true }).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let ai_repo = Arc::new(MockAiRepository::new());
+    let settings_repo = Arc::new(MockSettingsRepository::new());
+    let spider = Spider::new_agent(ClientType::Standard).unwrap();
+    let service = AiService::with_spider(ai_repo.clone(), settings_repo, spider);
+    service.set_ai_source("ollama").await.unwrap();
+    service
+        .set_ollama_settings(&OllamaSettings {
+            base_url: server.url(),
+            model: "llama3.1:8b".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // Insights another backend wrote aren't reused
+    let request = crate::test_utils::fixtures::minimal_gemini_request();
+    ai_repo.save_ai_insights(&request.analysis_id, "Gemini", "Gemini insights").await.unwrap();
+    let text = service.generate_insights(request.clone()).await.unwrap();
+    assert_eq!(text, "Ollama insights");
+    assert_eq!(
+        ai_repo.get_ai_insights(&request.analysis_id).await.unwrap(),
+        Some("Ollama insights".to_string())
+    );
+
+    // Served from the cache the second time
+    service.generate_insights(request).await.unwrap();
+    mock.assert_async().await;
+}
+
+/// Test: a backend without a model reports that it isn't configured
+#[tokio::test]
+async fn test_ai_service_generate_insights_backend_not_configured() {
+    let ai_repo = Arc::new(MockAiRepository::new());
+    let settings_repo = Arc::new(MockSettingsRepository::new());
+    let spider = Spider::new_agent(ClientType::Standard).unwrap();
+    let service = AiService::with_spider(ai_repo, settings_repo, spider);
+    service.set_ai_source("openai").await.unwrap();
+
+    let err = service
+        .generate_insights(crate::test_utils::fixtures::minimal_gemini_request())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("AI_BACKEND_NOT_CONFIGURED"), "{err}");
+}
+
+/// Test: Gemini without a saved key is picked but reports it isn't set up
+#[tokio::test]
+async fn test_ai_service_backend_gemini_without_key() {
+    let ai_repo = Arc::new(MockAiRepository::new());
+    let settings_repo = Arc::new(MockSettingsRepository::new());
+    let spider = Spider::new_agent(ClientType::Standard).unwrap();
+    let service = AiService::with_spider(ai_repo, settings_repo, spider);
+
+    let backend = service.backend().await.unwrap();
+    assert_eq!(backend.name(), "Gemini");
+    assert!(!backend.is_configured());
+
+    service.set_api_key("test-api-key").await.unwrap();
+    assert!(service.backend().await.unwrap().is_configured());
+}
+
+/// Test: the local source needs the local model service
+#[tokio::test]
+async fn test_ai_service_backend_local_without_local_model() {
+    let ai_repo = Arc::new(MockAiRepository::new());
+    let settings_repo = Arc::new(MockSettingsRepository::new());
+    let spider = Spider::new_agent(ClientType::Standard).unwrap();
+    let service = AiService::with_spider(ai_repo, settings_repo, spider);
+    service.set_ai_source("local").await.unwrap();
+
+    let err = service.backend().await.err().expect("no local model service");
+    assert!(err.to_string().contains("Local model not configured"), "{err}");
+}
+
 // ============================================================================
 // Tests for PromptConfig
 // ============================================================================
@@ -0,0 +1,108 @@
+//! Who the crawler claims to be: a browser emulation (TLS and header
+//! fingerprint included) or a fixed user-agent string such as a search
+//! engine bot's.
+//!
+//! The profile is stored on the job as JSON. A stored profile this build
+//! can't read falls back to [`CrawlProfile::Default`] instead of making
+//! the job unreadable, and a custom user agent that isn't a valid header
+//! value falls back the same way when the spider is built.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Googlebot's Chrome version moves with stable Chrome; this is the one
+/// the bot user agents below advertise.
+const BOT_CHROME_VERSION: &str = "134.0.6998.165";
+
+/// Browser emulation and user agent for a job's requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CrawlProfile {
+    /// Firefox desktop emulation, the crawler's long-standing default.
+    #[default]
+    Default,
+    Chrome,
+    ChromeAndroid,
+    Safari,
+    SafariIos,
+    Edge,
+    GooglebotSmartphone,
+    GooglebotDesktop,
+    Bingbot,
+    /// Any user-agent string, sent without browser emulation.
+    Custom {
+        user_agent: String,
+    },
+}
+
+impl CrawlProfile {
+    /// The user agent this profile pins, or `None` when the browser
+    /// emulation supplies its own.
+    pub fn user_agent(&self) -> Option<String> {
+        match self {
+            Self::GooglebotSmartphone => Some(format!(
+                "Mozilla/5.0 (Linux; Android 6.0.1; Nexus 5X Build/MMB29P) AppleWebKit/537.36 \
+                 (KHTML, like Gecko) Chrome/{BOT_CHROME_VERSION} Mobile Safari/537.36 \
+                 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+            )),
+            Self::GooglebotDesktop => Some(format!(
+                "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; Googlebot/2.1; \
+                 +http://www.google.com/bot.html) Chrome/{BOT_CHROME_VERSION} Safari/537.36"
+            )),
+            Self::Bingbot => Some(format!(
+                "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; bingbot/2.0; \
+                 +http://www.bing.com/bingbot.htm) Chrome/{BOT_CHROME_VERSION} Safari/537.36"
+            )),
+            Self::Custom { user_agent } => Some(user_agent.trim().to_string()),
+            _ => None,
+        }
+    }
+
+    /// Short name for logs and reports.
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Default => "Firefox (default)",
+            Self::Chrome => "Chrome",
+            Self::ChromeAndroid => "Chrome Android",
+            Self::Safari => "Safari",
+            Self::SafariIos => "Safari iOS",
+            Self::Edge => "Edge",
+            Self::GooglebotSmartphone => "Googlebot Smartphone",
+            Self::GooglebotDesktop => "Googlebot Desktop",
+            Self::Bingbot => "Bingbot",
+            Self::Custom { .. } => "Custom user agent",
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::Default
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bot_profiles_pin_their_user_agent() {
+        let ua = CrawlProfile::GooglebotSmartphone.user_agent().unwrap();
+        assert!(ua.contains("Googlebot/2.1") && ua.contains("Mobile"));
+        assert!(CrawlProfile::Bingbot
+            .user_agent()
+            .unwrap()
+            .contains("bingbot/2.0"));
+        assert!(CrawlProfile::Chrome.user_agent().is_none());
+    }
+
+    #[test]
+    fn serializes_with_kind_tag() {
+        let json = serde_json::to_string(&CrawlProfile::Custom {
+            user_agent: "MyBot/1.0".into(),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"kind":"custom","user_agent":"MyBot/1.0"}"#);
+
+        let parsed: CrawlProfile = serde_json::from_str(r#"{"kind":"googlebot_desktop"}"#).unwrap();
+        assert_eq!(parsed, CrawlProfile::GooglebotDesktop);
+    }
+}
@@ -0,0 +1,43 @@
+//! Where the time of one page fetch went.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::Duration;
+
+/// Time to first byte and body download for the request that got a page's
+/// response. Redirect hops and a Digest challenge before it aren't
+/// counted. The crawl's HTTP client doesn't expose connection phases, so
+/// DNS lookup, connect and the TLS handshake are all part of `ttfb_ms`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct FetchTiming {
+    /// From sending the request to receiving the response headers.
+    pub ttfb_ms: f64,
+    /// From the response headers to the last byte of the body.
+    pub download_ms: f64,
+}
+
+impl FetchTiming {
+    pub fn new(ttfb: Duration, total: Duration) -> Self {
+        Self {
+            ttfb_ms: ttfb.as_secs_f64() * 1000.0,
+            download_ms: total.saturating_sub(ttfb).as_secs_f64() * 1000.0,
+        }
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.ttfb_ms + self.download_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn download_is_what_follows_the_first_byte() {
+        let timing = FetchTiming::new(Duration::from_millis(300), Duration::from_millis(1200));
+        assert_eq!(timing.ttfb_ms, 300.0);
+        assert_eq!(timing.download_ms, 900.0);
+        assert_eq!(timing.total_ms(), 1200.0);
+    }
+}
@@ -0,0 +1,546 @@
+//! Per-job HTTP configuration: extra request headers, seed cookies and
+//! credentials for sites that need them (staging behind basic auth, pages
+//! behind a session cookie), and how to reach the site — through a proxy,
+//! or at an address DNS doesn't point to yet.
+//!
+//! Unlike [`JobSettings`](super::JobSettings), an [`HttpConfig`] holds
+//! secrets. It is stored in its own table rather than on the job row, is
+//! never part of a job or report payload, and its `Debug` output redacts
+//! every value so it can't leak through logs.
+
+use std::fmt;
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+const REDACTED: &str = "<redacted>";
+
+/// An extra header sent with every request to the job's site.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct HttpHeader {
+    pub name: String,
+    pub value: String,
+}
+
+impl fmt::Debug for HttpHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpHeader")
+            .field("name", &self.name)
+            .field("value", &REDACTED)
+            .finish()
+    }
+}
+
+/// A cookie placed in the job's cookie jar before the first request.
+/// Cookies the site sets while it is crawled are kept in the same jar.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct SeedCookie {
+    pub name: String,
+    pub value: String,
+    /// Cookie domain; `None` scopes it to the job's start host.
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// Also send the cookie to subdomains of `domain`.
+    #[serde(default)]
+    pub include_subdomains: bool,
+    #[serde(default = "default_cookie_path")]
+    pub path: String,
+    /// Only send the cookie over https.
+    #[serde(default)]
+    pub secure: bool,
+}
+
+fn default_cookie_path() -> String {
+    "/".to_string()
+}
+
+impl fmt::Debug for SeedCookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeedCookie")
+            .field("name", &self.name)
+            .field("value", &REDACTED)
+            .field("domain", &self.domain)
+            .field("include_subdomains", &self.include_subdomains)
+            .field("path", &self.path)
+            .field("secure", &self.secure)
+            .finish()
+    }
+}
+
+impl SeedCookie {
+    /// Render as a `Set-Cookie` value for seeding a cookie jar.
+    /// `default_host` stands in for a missing `domain`.
+    pub fn to_set_cookie(&self, default_host: &str) -> String {
+        let mut cookie = format!("{}={}; Path={}", self.name, self.value, self.path);
+        if self.include_subdomains {
+            let domain = self.domain.as_deref().unwrap_or(default_host);
+            cookie.push_str("; Domain=");
+            cookie.push_str(domain.trim_start_matches('.'));
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+
+    /// The host a jar should associate this cookie with.
+    pub fn host<'a>(&'a self, default_host: &'a str) -> &'a str {
+        self.domain
+            .as_deref()
+            .unwrap_or(default_host)
+            .trim_start_matches('.')
+    }
+
+    /// Parse a Netscape-format `cookies.txt`, as exported by browser
+    /// extensions and `curl -c`. Comment lines are skipped (except the
+    /// `#HttpOnly_` domain prefix) and so are cookies that have already
+    /// expired; session cookies (expiry `0`) are kept.
+    pub fn parse_netscape(text: &str) -> Result<Vec<Self>, HttpConfigError> {
+        let now = chrono::Utc::now().timestamp();
+        let mut cookies = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            let line = match line.strip_prefix("#HttpOnly_") {
+                Some(rest) => rest,
+                None if line.trim().is_empty() || line.starts_with('#') => continue,
+                None => line,
+            };
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [domain, include_subdomains, path, secure, expires, name, value] = fields[..]
+            else {
+                return Err(HttpConfigError::InvalidCookieLine { line: index + 1 });
+            };
+            let expires: i64 = expires
+                .trim()
+                .parse()
+                .map_err(|_| HttpConfigError::InvalidCookieLine { line: index + 1 })?;
+            if expires != 0 && expires < now {
+                continue;
+            }
+
+            cookies.push(Self {
+                name: name.to_string(),
+                value: value.to_string(),
+                domain: Some(domain.trim_start_matches('.').to_string()),
+                include_subdomains: include_subdomains.eq_ignore_ascii_case("TRUE"),
+                path: if path.is_empty() {
+                    default_cookie_path()
+                } else {
+                    path.to_string()
+                },
+                secure: secure.eq_ignore_ascii_case("TRUE"),
+            });
+        }
+
+        Ok(cookies)
+    }
+}
+
+/// Credentials presented to the job's start URL origin (same scheme,
+/// host and port), never to the rest of its site.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "scheme", rename_all = "snake_case")]
+pub enum HttpAuth {
+    Basic {
+        username: String,
+        password: String,
+    },
+    /// Answered after the server's first `401` challenge; later requests
+    /// reuse the challenge until the server issues a new one.
+    Digest {
+        username: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+}
+
+impl fmt::Debug for HttpAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &REDACTED)
+                .finish(),
+            Self::Digest { username, .. } => f
+                .debug_struct("Digest")
+                .field("username", username)
+                .field("password", &REDACTED)
+                .finish(),
+            Self::Bearer { .. } => f.debug_struct("Bearer").field("token", &REDACTED).finish(),
+        }
+    }
+}
+
+/// An HTTP(S) or SOCKS5 proxy all of the job's traffic goes through.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct ProxyConfig {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` (the proxy
+    /// resolves host names) followed by the proxy's host and port.
+    pub url: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+impl fmt::Debug for ProxyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("url", &self.url)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| REDACTED))
+            .finish()
+    }
+}
+
+const PROXY_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+
+/// A hosts-file entry: connect to `address` whenever the job requests
+/// `host`, e.g. to audit a site on its new server before DNS cutover.
+/// The URL's port is kept. Behind an HTTP or `socks5h` proxy, the proxy
+/// resolves names itself and these entries don't apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct HostOverride {
+    pub host: String,
+    pub address: String,
+}
+
+impl HostOverride {
+    /// Parse `/etc/hosts`-style text: an address followed by one or more
+    /// host names per line, `#` starting a comment.
+    pub fn parse_hosts(text: &str) -> Result<Vec<Self>, HttpConfigError> {
+        let mut overrides = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut fields = line.split_whitespace();
+            let Some(address) = fields.next() else {
+                continue;
+            };
+            let hosts: Vec<&str> = fields.collect();
+            if hosts.is_empty() || address.parse::<IpAddr>().is_err() {
+                return Err(HttpConfigError::InvalidHostsLine { line: index + 1 });
+            }
+            overrides.extend(hosts.into_iter().map(|host| Self {
+                host: host.to_ascii_lowercase(),
+                address: address.to_string(),
+            }));
+        }
+
+        Ok(overrides)
+    }
+
+    pub fn ip(&self) -> Option<IpAddr> {
+        self.address.trim().parse().ok()
+    }
+}
+
+/// Everything a job sends beyond the spider's defaults.
+///
+/// Headers and credentials only go to the job's own site (its start host
+/// and subdomains); cookies follow their own domain scoping. The proxy and
+/// host overrides apply to every request the job makes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct HttpConfig {
+    #[serde(default)]
+    pub headers: Vec<HttpHeader>,
+    #[serde(default)]
+    pub cookies: Vec<SeedCookie>,
+    #[serde(default)]
+    pub auth: Option<HttpAuth>,
+    #[serde(default)]
+    pub proxy: Option<ProxyConfig>,
+    #[serde(default)]
+    pub hosts: Vec<HostOverride>,
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum HttpConfigError {
+    #[error("invalid header '{name}': {reason}")]
+    InvalidHeader { name: String, reason: &'static str },
+    #[error("invalid cookie '{name}': {reason}")]
+    InvalidCookie { name: String, reason: &'static str },
+    #[error("malformed cookies.txt line {line}")]
+    InvalidCookieLine { line: usize },
+    #[error("invalid credentials: {0}")]
+    InvalidAuth(&'static str),
+    #[error("invalid proxy: {0}")]
+    InvalidProxy(&'static str),
+    #[error("invalid host override '{host}': {reason}")]
+    InvalidHostOverride { host: String, reason: &'static str },
+    #[error("malformed hosts line {line}")]
+    InvalidHostsLine { line: usize },
+}
+
+impl HttpConfig {
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+            && self.cookies.is_empty()
+            && self.auth.is_none()
+            && self.proxy.is_none()
+            && self.hosts.is_empty()
+    }
+
+    /// Reject values that can't be sent as-is, before the job is created
+    /// rather than on its first request.
+    pub fn validate(&self) -> Result<(), HttpConfigError> {
+        for header in &self.headers {
+            let invalid = |reason| HttpConfigError::InvalidHeader {
+                name: header.name.clone(),
+                reason,
+            };
+            if header.name.is_empty() || !header.name.bytes().all(is_token_byte) {
+                return Err(invalid("not a valid header name"));
+            }
+            if header.value.contains(['\r', '\n']) {
+                return Err(invalid("value contains a line break"));
+            }
+        }
+
+        for cookie in &self.cookies {
+            let invalid = |reason| HttpConfigError::InvalidCookie {
+                name: cookie.name.clone(),
+                reason,
+            };
+            if cookie.name.is_empty() || !cookie.name.bytes().all(is_token_byte) {
+                return Err(invalid("not a valid cookie name"));
+            }
+            if cookie.value.contains([';', '\r', '\n']) {
+                return Err(invalid("value contains ';' or a line break"));
+            }
+        }
+
+        match &self.auth {
+            Some(HttpAuth::Basic { username, .. } | HttpAuth::Digest { username, .. }) => {
+                if username.is_empty() {
+                    return Err(HttpConfigError::InvalidAuth("username is empty"));
+                }
+                if username.contains(':') {
+                    return Err(HttpConfigError::InvalidAuth("username contains ':'"));
+                }
+            }
+            Some(HttpAuth::Bearer { token }) if token.trim().is_empty() => {
+                return Err(HttpConfigError::InvalidAuth("bearer token is empty"));
+            }
+            _ => {}
+        }
+
+        if let Some(proxy) = &self.proxy {
+            let url = url::Url::parse(proxy.url.trim())
+                .map_err(|_| HttpConfigError::InvalidProxy("not a valid URL"))?;
+            if !PROXY_SCHEMES.contains(&url.scheme()) {
+                return Err(HttpConfigError::InvalidProxy(
+                    "scheme must be http, https, socks5 or socks5h",
+                ));
+            }
+            if url.host_str().is_none_or(str::is_empty) {
+                return Err(HttpConfigError::InvalidProxy("URL has no host"));
+            }
+            if proxy.password.is_some() && proxy.username.as_deref().is_none_or(str::is_empty) {
+                return Err(HttpConfigError::InvalidProxy(
+                    "password given without a username",
+                ));
+            }
+        }
+
+        for entry in &self.hosts {
+            let invalid = |reason| HttpConfigError::InvalidHostOverride {
+                host: entry.host.clone(),
+                reason,
+            };
+            let host = entry.host.trim();
+            if host.is_empty()
+                || !host
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"-._".contains(&b))
+            {
+                return Err(invalid("not a valid host name"));
+            }
+            if entry.ip().is_none() {
+                return Err(invalid("address is not an IP address"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// RFC 9110 `tchar`, the characters allowed in header and cookie names.
+fn is_token_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_output_redacts_secrets() {
+        let config = HttpConfig {
+            headers: vec![HttpHeader {
+                name: "X-Api-Key".into(),
+                value: "header-secret".into(),
+            }],
+            cookies: vec![SeedCookie {
+                name: "session".into(),
+                value: "cookie-secret".into(),
+                domain: None,
+                include_subdomains: false,
+                path: "/".into(),
+                secure: false,
+            }],
+            auth: Some(HttpAuth::Basic {
+                username: "staging".into(),
+                password: "password-secret".into(),
+            }),
+            proxy: Some(ProxyConfig {
+                url: "http://proxy.local:3128".into(),
+                username: Some("crawler".into()),
+                password: Some("proxy-secret".into()),
+            }),
+            hosts: Vec::new(),
+        };
+
+        let debug = format!("{config:?}");
+        assert!(debug.contains("X-Api-Key"));
+        assert!(debug.contains("staging"));
+        assert!(!debug.contains("secret"));
+    }
+
+    #[test]
+    fn validate_rejects_header_injection_and_bad_names() {
+        let header = |name: &str, value: &str| HttpConfig {
+            headers: vec![HttpHeader {
+                name: name.into(),
+                value: value.into(),
+            }],
+            ..Default::default()
+        };
+
+        assert!(header("X-Env", "staging").validate().is_ok());
+        assert!(header("X Env", "staging").validate().is_err());
+        assert!(header("X-Env", "a\r\nHost: evil").validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_unusable_credentials() {
+        let auth = |auth| HttpConfig {
+            auth: Some(auth),
+            ..Default::default()
+        };
+
+        assert!(auth(HttpAuth::Bearer { token: " ".into() })
+            .validate()
+            .is_err());
+        assert!(auth(HttpAuth::Digest {
+            username: "a:b".into(),
+            password: "x".into(),
+        })
+        .validate()
+        .is_err());
+        assert!(auth(HttpAuth::Basic {
+            username: "user".into(),
+            password: String::new(),
+        })
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn validate_checks_proxy_and_host_overrides() {
+        let proxy = |url: &str| HttpConfig {
+            proxy: Some(ProxyConfig {
+                url: url.into(),
+                username: None,
+                password: None,
+            }),
+            ..Default::default()
+        };
+        assert!(proxy("socks5h://127.0.0.1:1080").validate().is_ok());
+        assert!(proxy("ftp://proxy.local").validate().is_err());
+        assert!(proxy("proxy.local:3128").validate().is_err());
+
+        let hosts = |host: &str, address: &str| HttpConfig {
+            hosts: vec![HostOverride {
+                host: host.into(),
+                address: address.into(),
+            }],
+            ..Default::default()
+        };
+        assert!(hosts("www.example.com", "203.0.113.7").validate().is_ok());
+        assert!(hosts("www.example.com", "staging-box").validate().is_err());
+        assert!(hosts("https://www.example.com", "::1").validate().is_err());
+    }
+
+    #[test]
+    fn parse_hosts_file() {
+        let text = "# staging\n\
+                    203.0.113.7  example.com www.example.com  # new box\n\
+                    \n\
+                    ::1 local.test\n";
+
+        let hosts = HostOverride::parse_hosts(text).unwrap();
+
+        assert_eq!(hosts.len(), 3);
+        assert_eq!(hosts[1].host, "www.example.com");
+        assert_eq!(hosts[1].address, "203.0.113.7");
+        assert_eq!(hosts[2].ip(), Some("::1".parse().unwrap()));
+        assert_eq!(
+            HostOverride::parse_hosts("example.com 203.0.113.7").unwrap_err(),
+            HttpConfigError::InvalidHostsLine { line: 1 }
+        );
+    }
+
+    #[test]
+    fn parse_netscape_cookies_txt() {
+        let text = "# Netscape HTTP Cookie File\n\
+                    .example.com\tTRUE\t/\tTRUE\t0\tsession\tabc123\n\
+                    #HttpOnly_staging.example.com\tFALSE\t/app\tFALSE\t4102444800\tcsrf\txyz\n\
+                    example.com\tFALSE\t/\tFALSE\t1000\told\tgone\n";
+
+        let cookies = SeedCookie::parse_netscape(text).unwrap();
+
+        assert_eq!(cookies.len(), 2, "expired cookie is dropped");
+        assert_eq!(cookies[0].domain.as_deref(), Some("example.com"));
+        assert!(cookies[0].include_subdomains && cookies[0].secure);
+        assert_eq!(cookies[1].name, "csrf");
+        assert_eq!(cookies[1].path, "/app");
+        assert!(!cookies[1].include_subdomains);
+    }
+
+    #[test]
+    fn parse_netscape_reports_malformed_line() {
+        let err = SeedCookie::parse_netscape("example.com\tFALSE\t/\n").unwrap_err();
+        assert_eq!(err, HttpConfigError::InvalidCookieLine { line: 1 });
+    }
+
+    #[test]
+    fn set_cookie_rendering_respects_host_only_cookies() {
+        let mut cookie = SeedCookie {
+            name: "session".into(),
+            value: "abc".into(),
+            domain: None,
+            include_subdomains: false,
+            path: "/".into(),
+            secure: true,
+        };
+        assert_eq!(
+            cookie.to_set_cookie("example.com"),
+            "session=abc; Path=/; Secure"
+        );
+        assert_eq!(cookie.host("example.com"), "example.com");
+
+        cookie.include_subdomains = true;
+        cookie.domain = Some(".example.com".into());
+        assert_eq!(
+            cookie.to_set_cookie("www.example.com"),
+            "session=abc; Path=/; Domain=example.com; Secure"
+        );
+    }
+}
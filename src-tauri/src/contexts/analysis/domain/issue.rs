@@ -97,6 +97,7 @@ impl IssueBuilder {
             severity: self.severity,
             message: self.message,
             details: self.details,
+            custom_check_id: None,
         }
     }
 }
@@ -109,6 +110,9 @@ pub struct NewIssue {
     pub severity: IssueSeverity,
     pub message: String,
     pub details: Option<String>,
+    /// The custom check that raised the issue; `None` for built-in checks.
+    #[serde(default)]
+    pub custom_check_id: Option<String>,
 }
 
 #[cfg(test)]
@@ -217,6 +221,7 @@ mod tests {
             severity: IssueSeverity::Critical,
             message: "404".into(),
             details: None,
+            custom_check_id: None,
         };
         let json = serde_json::to_value(&issue).unwrap();
         assert_eq!(json["severity"], "critical");
@@ -1,4 +1,7 @@
+use super::crawl_profile::CrawlProfile;
 use super::ids::JobId;
+use super::url_list::CrawlMode;
+use super::url_rules::UrlRules;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -11,6 +14,19 @@ pub struct JobSettings {
     pub mobile_analysis: bool,
     pub lighthouse_analysis: bool,
     pub delay_between_requests: i64,
+    /// Include/exclude patterns, URL normalization and trap limits.
+    #[serde(default)]
+    pub url_rules: UrlRules,
+    /// Spider from the start URL, or analyse a fixed URL list.
+    #[serde(default)]
+    pub crawl_mode: CrawlMode,
+    /// Browser emulation / user agent the crawler presents.
+    #[serde(default)]
+    pub crawl_profile: CrawlProfile,
+    /// Fetch each page's stylesheets, scripts and fonts to measure page
+    /// weight and render-blocking assets without Lighthouse.
+    #[serde(default)]
+    pub resource_analysis: bool,
 }
 
 impl Default for JobSettings {
@@ -22,6 +38,10 @@ impl Default for JobSettings {
             mobile_analysis: false,
             lighthouse_analysis: false,
             delay_between_requests: 500,
+            url_rules: UrlRules::default(),
+            crawl_mode: CrawlMode::default(),
+            crawl_profile: CrawlProfile::default(),
+            resource_analysis: false,
         }
     }
 }
@@ -270,6 +290,7 @@ mod tests {
             JobStatus::Completed,
             JobStatus::Failed,
             JobStatus::Cancelled,
+            JobStatus::Paused,
         ] {
             // No status is both active and terminal.
             assert!(!(s.is_active() && s.is_terminal()), "{s:?}");
@@ -281,6 +302,8 @@ mod tests {
         assert!(JobStatus::Processing.is_active());
         assert!(!JobStatus::Pending.is_active());
         assert!(!JobStatus::Pending.is_terminal());
+        assert!(!JobStatus::Paused.is_active());
+        assert!(!JobStatus::Paused.is_terminal());
     }
 
     #[test]
@@ -293,6 +316,7 @@ mod tests {
             JobStatus::Completed,
             JobStatus::Failed,
             JobStatus::Cancelled,
+            JobStatus::Paused,
         ] {
             let parsed = JobStatus::from_str(s.as_str()).expect("known status");
             assert_eq!(parsed, s);
@@ -462,6 +486,10 @@ pub enum JobStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Stopped by the user with its discovery frontier and page queue
+    /// checkpointed, so it can resume where it left off. Neither active
+    /// (no worker holds it) nor terminal (it is expected to continue).
+    Paused,
 }
 
 #[derive(Debug, Clone)]
@@ -484,6 +512,7 @@ impl JobStatus {
             Self::Completed => "completed",
             Self::Failed => "failed",
             Self::Cancelled => "cancelled",
+            Self::Paused => "paused",
         }
     }
 
@@ -506,6 +535,7 @@ impl std::str::FromStr for JobStatus {
             "completed" => Ok(Self::Completed),
             "failed" | "error" => Ok(Self::Failed),
             "cancelled" => Ok(Self::Cancelled),
+            "paused" => Ok(Self::Paused),
             other => Err(ParseJobStatusError(other.to_string())),
         }
     }
@@ -523,6 +553,11 @@ pub struct CompleteJobResult {
     pub headings: Vec<super::Heading>,
     pub images: Vec<super::Image>,
     pub ai_insights: Option<crate::contexts::ai::AiInsight>,
+    /// Verified handshake with the site; `None` when it wasn't inspected.
+    pub tls: Option<super::TlsInspection>,
+    /// Site-level file checks, in report order; empty for jobs that didn't
+    /// run them.
+    pub site_checks: Vec<super::SiteCheckResult>,
     /// Extracted data from custom extractors (keyed by page_id)
     pub extracted_data: std::collections::HashMap<String, std::collections::HashMap<String, serde_json::Value>>,
 }
@@ -50,6 +50,11 @@ pub struct Failed;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Cancelled;
 
+/// A job that the user paused mid-run. Its discovery frontier and page
+/// queue are checkpointed so it can pick up where it stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paused;
+
 // ── JobState wrapper ─────────────────────────────────────────────────────────
 
 /// A `Job` whose lifecycle stage is tracked at the type level.
@@ -122,6 +127,16 @@ impl JobState<Discovery> {
         }
     }
 
+    /// Pause during discovery. The unvisited frontier is checkpointed
+    /// by the processor; the typestate only records the transition.
+    pub fn pause(mut self) -> JobState<Paused> {
+        self.job.status = JobStatus::Paused;
+        JobState {
+            job: self.job,
+            _state: PhantomData,
+        }
+    }
+
     /// Discovery failed (e.g. seed URL unreachable).
     pub fn fail(mut self, message: impl Into<String>) -> JobState<Failed> {
         self.job.status = JobStatus::Failed;
@@ -165,12 +180,45 @@ impl JobState<Processing> {
         }
     }
 
+    /// Pause during processing. Pages already analysed stay analysed;
+    /// the rest remain `pending` in the page queue.
+    pub fn pause(mut self) -> JobState<Paused> {
+        self.job.status = JobStatus::Paused;
+        JobState {
+            job: self.job,
+            _state: PhantomData,
+        }
+    }
+
     /// Update progress as a percentage [0, 100].
     pub fn update_progress(&mut self, pct: f64) {
         self.job.progress = pct.clamp(0.0, 100.0);
     }
 }
 
+impl JobState<Paused> {
+    /// Hand the job back to the queue. It re-enters as `Pending`; the
+    /// processor notices the saved checkpoint and skips the work that
+    /// already happened.
+    pub fn resume(mut self) -> JobState<Pending> {
+        self.job.status = JobStatus::Pending;
+        JobState {
+            job: self.job,
+            _state: PhantomData,
+        }
+    }
+
+    /// Give up on a paused job.
+    pub fn cancel(mut self) -> JobState<Cancelled> {
+        self.job.status = JobStatus::Cancelled;
+        self.job.completed_at = Some(chrono::Utc::now());
+        JobState {
+            job: self.job,
+            _state: PhantomData,
+        }
+    }
+}
+
 impl JobState<Completed> {
     /// Final SEO score for a completed job.
     pub fn seo_score(&self) -> i64 {
@@ -198,6 +246,7 @@ pub enum AnyJob {
     Completed(JobState<Completed>),
     Failed(JobState<Failed>),
     Cancelled(JobState<Cancelled>),
+    Paused(JobState<Paused>),
 }
 
 impl AnyJob {
@@ -210,6 +259,7 @@ impl AnyJob {
             Self::Completed(s) => s.job(),
             Self::Failed(s) => s.job(),
             Self::Cancelled(s) => s.job(),
+            Self::Paused(s) => s.job(),
         }
     }
 
@@ -222,6 +272,7 @@ impl AnyJob {
             Self::Completed(s) => s.into_inner(),
             Self::Failed(s) => s.into_inner(),
             Self::Cancelled(s) => s.into_inner(),
+            Self::Paused(s) => s.into_inner(),
         }
     }
 
@@ -237,8 +288,8 @@ impl AnyJob {
     }
 
     /// Whether the job is actively running (Discovery or Processing).
-    /// `Pending` is intentionally excluded — the job is queued but not
-    /// yet doing work, matching `JobStatus::is_active`.
+    /// `Pending` and `Paused` are intentionally excluded — neither is
+    /// doing work right now, matching `JobStatus::is_active`.
     pub fn is_active(&self) -> bool {
         matches!(self, Self::Discovery(_) | Self::Processing(_))
     }
@@ -254,6 +305,7 @@ impl AnyJob {
             Self::Completed(_) => "completed",
             Self::Failed(_) => "failed",
             Self::Cancelled(_) => "cancelled",
+            Self::Paused(_) => "paused",
         }
     }
 
@@ -303,6 +355,13 @@ impl AnyJob {
             _ => None,
         }
     }
+
+    pub fn try_into_paused(self) -> Option<JobState<Paused>> {
+        match self {
+            Self::Paused(s) => Some(s),
+            _ => None,
+        }
+    }
 }
 
 impl From<Job> for AnyJob {
@@ -332,6 +391,10 @@ impl From<Job> for AnyJob {
                 job,
                 _state: PhantomData,
             }),
+            JobStatus::Paused => Self::Paused(JobState {
+                job,
+                _state: PhantomData,
+            }),
         }
     }
 }
@@ -428,7 +491,33 @@ mod tests {
         assert!(matches!(AnyJob::from(job.clone()), AnyJob::Failed(_)));
 
         job.status = JobStatus::Cancelled;
-        assert!(matches!(AnyJob::from(job), AnyJob::Cancelled(_)));
+        assert!(matches!(AnyJob::from(job.clone()), AnyJob::Cancelled(_)));
+
+        job.status = JobStatus::Paused;
+        assert!(matches!(AnyJob::from(job), AnyJob::Paused(_)));
+    }
+
+    #[test]
+    fn discovery_and_processing_can_pause_and_resume() {
+        let pending: JobState<Pending> = AnyJob::from(fresh_job()).try_into_pending().unwrap();
+        let paused = pending.start_discovery().pause();
+        assert_eq!(paused.job().status, JobStatus::Paused);
+
+        let resumed = paused.resume();
+        assert_eq!(resumed.job().status, JobStatus::Pending);
+
+        let paused = resumed.start_discovery().start_processing().pause();
+        assert_eq!(paused.job().status, JobStatus::Paused);
+        assert!(paused.job().completed_at.is_none());
+    }
+
+    #[test]
+    fn paused_can_be_cancelled() {
+        let mut job = fresh_job();
+        job.status = JobStatus::Paused;
+        let cancelled = AnyJob::from(job).try_into_paused().unwrap().cancel();
+        assert_eq!(cancelled.job().status, JobStatus::Cancelled);
+        assert!(cancelled.job().completed_at.is_some());
     }
 
     #[test]
@@ -486,6 +575,9 @@ mod tests {
         assert!(!AnyJob::from(job.clone()).is_terminal());
 
         job.status = JobStatus::Processing;
+        assert!(!AnyJob::from(job.clone()).is_terminal());
+
+        job.status = JobStatus::Paused;
         assert!(!AnyJob::from(job).is_terminal());
     }
 
@@ -508,6 +600,9 @@ mod tests {
         job.status = JobStatus::Failed;
         assert!(!AnyJob::from(job.clone()).is_active());
         job.status = JobStatus::Cancelled;
+        assert!(!AnyJob::from(job.clone()).is_active());
+        // Paused jobs hold no worker.
+        job.status = JobStatus::Paused;
         assert!(!AnyJob::from(job).is_active());
     }
 
@@ -522,6 +617,7 @@ mod tests {
             JobStatus::Completed,
             JobStatus::Failed,
             JobStatus::Cancelled,
+            JobStatus::Paused,
         ] {
             let mut job = fresh_job();
             job.status = status.clone();
@@ -545,6 +641,7 @@ mod tests {
             JobStatus::Completed,
             JobStatus::Failed,
             JobStatus::Cancelled,
+            JobStatus::Paused,
         ] {
             let mut job = fresh_job();
             job.status = status.clone();
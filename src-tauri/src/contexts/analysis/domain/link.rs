@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use super::url_rules::CompiledUrlRules;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "lowercase")]
 pub enum LinkType {
@@ -126,6 +128,15 @@ impl NewLink {
         }
     }
 
+    /// Rewrite `target_url` with the job's URL normalization so stored
+    /// links line up with the page URLs the crawler queued. Targets that
+    /// do not parse are left untouched.
+    pub fn normalize_target(&mut self, rules: &CompiledUrlRules) {
+        if let Ok(target) = Url::parse(&self.target_url) {
+            self.target_url = rules.normalize(&target).to_string();
+        }
+    }
+
     pub fn classify(target_url: &str, base_url: &str) -> LinkType {
         let (Ok(target), Ok(base)) = (Url::parse(target_url), Url::parse(base_url)) else {
             return LinkType::External;
@@ -167,6 +178,29 @@ impl NewLink {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_normalize_target_applies_url_rules() {
+        use super::super::url_rules::{QueryParamPolicy, UrlRules};
+
+        let rules = UrlRules {
+            query_params: QueryParamPolicy::StripListed(vec!["utm_source".into()]),
+            ..Default::default()
+        }
+        .compile()
+        .unwrap();
+        let mut link = NewLink::create(
+            "job",
+            "page",
+            "https://example.com/a?utm_source=x&id=1",
+            None,
+            None,
+            "https://example.com/",
+        );
+        link.normalize_target(&rules);
+        assert_eq!(link.target_url, "https://example.com/a?id=1");
+        assert_eq!(link.link_type, LinkType::Internal);
+    }
+
     #[test]
     fn test_classify_subdomain() {
         assert_eq!(
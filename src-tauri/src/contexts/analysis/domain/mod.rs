@@ -1,7 +1,10 @@
 // Analysis Context Domain Models
 // These are the core domain types for the Analysis bounded context.
 
+mod crawl_profile;
 mod depth;
+mod fetch_timing;
+mod http_config;
 mod ids;
 mod issue;
 mod job;
@@ -9,10 +12,16 @@ mod job_state;
 mod link;
 mod lighthouse;
 mod page;
+mod page_weight;
 mod pagination;
 mod progress;
 mod resource;
+mod response_headers;
 mod retry_count;
+mod site_check;
+mod tls;
+mod url_list;
+mod url_rules;
 mod url_utils;
 
 // ============================================================================
@@ -30,7 +39,7 @@ pub use retry_count::{RetryCount, RetryCountError, MAX_RETRY_COUNT};
 
 pub use job::{Job, JobSettings, JobStatus, JobInfo, JobSummary, CompleteJobResult};
 pub use job_state::{
-    AnyJob, Cancelled, Completed, Discovery, Failed, JobState, Pending, Processing,
+    AnyJob, Cancelled, Completed, Discovery, Failed, JobState, Paused, Pending, Processing,
 };
 
 /// Filter for listing jobs
@@ -47,7 +56,8 @@ pub struct JobFilter {
 // ============================================================================
 
 pub use page::{
-    NewPageQueueItem, Page, PageInfo, PageQueueItem, PageQueueStatus, ParsePageQueueStatusError,
+    DiscoveryFrontier, NewPageQueueItem, Page, PageInfo, PageQueueItem, PageQueueStatus,
+    ParsePageQueueStatusError,
 };
 
 /// Detailed page information for display
@@ -95,6 +105,20 @@ pub use resource::{ResourceStatus, Heading, Image, NewHeading, NewImage};
 // ============================================================================
 
 pub use url_utils::{extract_root_domain, extract_host, same_root_domain};
+pub use http_config::{
+    HostOverride, HttpAuth, HttpConfig, HttpConfigError, HttpHeader, ProxyConfig, SeedCookie,
+};
+pub use crawl_profile::CrawlProfile;
+pub use fetch_timing::FetchTiming;
+pub use page_weight::PageWeight;
+pub use response_headers::ResponseHeaders;
+pub use site_check::{ParseSiteCheckStatusError, SiteCheckResult, SiteCheckStatus};
+pub use tls::TlsInspection;
+pub use url_list::{CrawlMode, InvalidUrlEntry, ParseCrawlModeError, UrlListReport};
+pub use url_rules::{
+    CompiledUrlRules, CrawlTrapGuard, QueryParamPolicy, TrailingSlash, UrlPattern, UrlRules,
+    UrlRulesError,
+};
 
 // ============================================================================
 // Analysis Result
@@ -1,4 +1,7 @@
 use super::depth::Depth;
+use super::fetch_timing::FetchTiming;
+use super::page_weight::PageWeight;
+use super::response_headers::ResponseHeaders;
 use super::retry_count::RetryCount;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
@@ -20,6 +23,13 @@ pub struct Page {
     pub robots_meta: Option<String>,
     pub word_count: Option<i64>,
     pub load_time_ms: Option<i64>,
+    /// Time to first byte of the page fetch; `None` when the auditor
+    /// doesn't time it or for pages crawled before it was recorded.
+    #[serde(default)]
+    pub ttfb_ms: Option<i64>,
+    /// Body download time after the first byte.
+    #[serde(default)]
+    pub download_ms: Option<i64>,
     pub response_size_bytes: Option<i64>,
     pub has_viewport: bool,
     pub has_structured_data: bool,
@@ -27,6 +37,14 @@ pub struct Page {
     /// Extracted data from custom extractors (key-value pairs)
     #[serde(default)]
     pub extracted_data: std::collections::HashMap<String, serde_json::Value>,
+    /// Headers the page was served with; empty for pages crawled before
+    /// they were captured.
+    #[serde(default)]
+    pub response_headers: ResponseHeaders,
+    /// Transfer weight and render-blocking assets; `None` unless the job
+    /// ran the resource pass.
+    #[serde(default)]
+    pub weight: Option<PageWeight>,
 }
 
 impl Page {
@@ -114,6 +132,8 @@ pub struct PageQueueItem {
     pub http_status: Option<u16>,
     pub cached_load_time_ms: Option<f64>,
     pub final_url: Option<String>,
+    pub cached_headers: ResponseHeaders,
+    pub cached_timing: Option<FetchTiming>,
 }
 
 impl PageQueueItem {
@@ -139,6 +159,8 @@ impl PageQueueItem {
             http_status: None,
             cached_load_time_ms: None,
             final_url: None,
+            cached_headers: ResponseHeaders::default(),
+            cached_timing: None,
         }
     }
 
@@ -186,6 +208,8 @@ pub struct NewPageQueueItem {
     pub http_status: Option<u16>,
     pub cached_load_time_ms: Option<f64>,
     pub final_url: Option<String>,
+    pub cached_headers: ResponseHeaders,
+    pub cached_timing: Option<FetchTiming>,
 }
 
 impl NewPageQueueItem {
@@ -198,6 +222,8 @@ impl NewPageQueueItem {
             http_status: None,
             cached_load_time_ms: None,
             final_url: None,
+            cached_headers: ResponseHeaders::default(),
+            cached_timing: None,
         }
     }
 
@@ -215,6 +241,33 @@ impl NewPageQueueItem {
             http_status: if page.status_code == 0 { None } else { Some(page.status_code) },
             cached_load_time_ms: if page.load_time_ms == 0.0 { None } else { Some(page.load_time_ms) },
             final_url: Some(page.final_url.clone()),
+            cached_headers: page.headers.clone(),
+            cached_timing: page.timing,
+        }
+    }
+}
+
+/// Checkpoint of a paused job's discovery crawl.
+///
+/// Pages fetched before the pause are already durable in the page queue
+/// (with their cached HTML); this carries the rest of the crawl state —
+/// URLs still waiting to be fetched and the ones already visited — so a
+/// resumed job neither re-fetches nor skips anything.
+/// `discovery_complete` is set when the pause landed after discovery had
+/// finished, in which case resume goes straight to analysis.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryFrontier {
+    pub to_visit: Vec<String>,
+    pub visited: Vec<String>,
+    pub discovery_complete: bool,
+}
+
+impl DiscoveryFrontier {
+    /// Checkpoint for a job paused during analysis: nothing left to crawl.
+    pub fn completed() -> Self {
+        Self {
+            discovery_complete: true,
+            ..Default::default()
         }
     }
 }
@@ -237,11 +290,15 @@ mod tests {
             robots_meta: None,
             word_count: Some(100),
             load_time_ms: Some(1000),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: Some(512),
             has_viewport: true,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            weight: None,
         };
         overrides(&mut page);
         page
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+/// What a page costs to load, measured without a browser: the HTML plus
+/// the stylesheets, scripts and fonts it references, each fetched once per
+/// job. Sizes are decoded bytes, since the crawl's client decompresses.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PageWeight {
+    /// HTML plus every asset that could be fetched.
+    pub total_bytes: i64,
+    pub html_bytes: i64,
+    /// Unique subresources the page references, including images and
+    /// media that aren't fetched.
+    pub request_count: i64,
+    /// Requests to a different root domain than the page's.
+    pub third_party_requests: i64,
+    /// Scripts and stylesheets in `<head>` that hold up the first render.
+    #[serde(default)]
+    pub render_blocking: Vec<String>,
+    /// Stylesheets and scripts that look like they were never minified.
+    #[serde(default)]
+    pub unminified: Vec<String>,
+}
+
+impl PageWeight {
+    /// Share of the page's requests that go to third parties, 0.0–1.0.
+    pub fn third_party_share(&self) -> f64 {
+        if self.request_count == 0 {
+            return 0.0;
+        }
+        self.third_party_requests as f64 / self.request_count as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn third_party_share_handles_pages_without_requests() {
+        assert_eq!(PageWeight::default().third_party_share(), 0.0);
+        let weight = PageWeight { request_count: 8, third_party_requests: 2, ..Default::default() };
+        assert_eq!(weight.third_party_share(), 0.25);
+    }
+
+    #[test]
+    fn older_rows_without_lists_still_decode() {
+        let weight: PageWeight = serde_json::from_str(
+            r#"{"total_bytes":10,"html_bytes":10,"request_count":0,"third_party_requests":0}"#,
+        )
+        .unwrap();
+        assert!(weight.render_blocking.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+//! HTTP response headers kept from a page fetch, for the security and
+//! delivery checks.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Response headers in the order the server sent them, names lower-cased.
+/// Empty when the page was fetched before headers were captured or by the
+/// deep auditor, which doesn't expose them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(transparent)]
+pub struct ResponseHeaders(Vec<(String, String)>);
+
+impl ResponseHeaders {
+    pub fn new<N, V>(headers: impl IntoIterator<Item = (N, V)>) -> Self
+    where
+        N: AsRef<str>,
+        V: Into<String>,
+    {
+        Self(
+            headers
+                .into_iter()
+                .map(|(name, value)| (name.as_ref().to_ascii_lowercase(), value.into()))
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// First value of `name` (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).next()
+    }
+
+    /// Every value of `name`, for headers a server may repeat.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Media type without parameters, lower-cased: `text/html` for
+    /// `text/html; charset=utf-8`.
+    pub fn media_type(&self) -> Option<String> {
+        self.get("content-type")
+            .and_then(|value| value.split(';').next())
+            .map(|media| media.trim().to_ascii_lowercase())
+            .filter(|media| !media.is_empty())
+    }
+
+    /// Comma-separated tokens of a list header, lower-cased, across all of
+    /// its occurrences (`Vary: Accept-Encoding, Cookie`).
+    pub fn tokens(&self, name: &str) -> Vec<String> {
+        self.get_all(name)
+            .flat_map(|value| value.split(','))
+            .map(|token| token.trim().to_ascii_lowercase())
+            .filter(|token| !token.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> ResponseHeaders {
+        ResponseHeaders::new([
+            ("Content-Type", "text/html; charset=UTF-8"),
+            ("Vary", "Accept-Encoding"),
+            ("vary", "Cookie, User-Agent"),
+        ])
+    }
+
+    #[test]
+    fn lookups_ignore_case() {
+        let headers = headers();
+
+        assert_eq!(headers.get("content-type"), Some("text/html; charset=UTF-8"));
+        assert_eq!(headers.get("CONTENT-TYPE"), Some("text/html; charset=UTF-8"));
+        assert!(!headers.contains("etag"));
+    }
+
+    #[test]
+    fn media_type_drops_parameters() {
+        assert_eq!(headers().media_type().as_deref(), Some("text/html"));
+        assert_eq!(ResponseHeaders::default().media_type(), None);
+    }
+
+    #[test]
+    fn tokens_span_repeated_headers() {
+        assert_eq!(headers().tokens("vary"), ["accept-encoding", "cookie", "user-agent"]);
+    }
+
+    #[test]
+    fn serializes_as_name_value_pairs() {
+        let headers = ResponseHeaders::new([("ETag", "\"abc\"")]);
+        let json = serde_json::to_string(&headers).unwrap();
+
+        assert_eq!(json, r#"[["etag","\"abc\""]]"#);
+        assert_eq!(serde_json::from_str::<ResponseHeaders>(&json).unwrap(), headers);
+    }
+}
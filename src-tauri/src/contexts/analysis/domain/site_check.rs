@@ -0,0 +1,154 @@
+//! Outcome of one site-level file check (robots.txt, sitemap, favicon,
+//! security.txt and the like), run once per job.
+//!
+//! Results are stored per job, one row per check, and read back by the
+//! report: each check is a template condition and a `{site.<id>}` tag.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::{IssueBuilder, IssueSeverity, NewIssue};
+
+/// What a site check found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SiteCheckStatus {
+    /// Served, and its content is what the check expects.
+    Found,
+    /// Served, but the content has problems.
+    Invalid,
+    /// Behind a 401 or 403; it exists but can't be read.
+    Unauthorized,
+    Missing,
+    /// Every request for it failed.
+    Error,
+}
+
+impl SiteCheckStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Found => "found",
+            Self::Invalid => "invalid",
+            Self::Unauthorized => "unauthorized",
+            Self::Missing => "missing",
+            Self::Error => "error",
+        }
+    }
+
+    /// How the status reads in a report.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Found => "Found",
+            Self::Invalid => "Invalid",
+            Self::Unauthorized => "Unauthorized",
+            Self::Missing => "Missing",
+            Self::Error => "Error",
+        }
+    }
+
+    /// The file is there, whether or not it could be read or validated.
+    pub fn exists(&self) -> bool {
+        matches!(self, Self::Found | Self::Invalid | Self::Unauthorized)
+    }
+}
+
+/// Returned by [`SiteCheckStatus::from_str`] for an unknown status.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+#[error("invalid site check status: '{0}'")]
+pub struct ParseSiteCheckStatusError(pub String);
+
+impl std::str::FromStr for SiteCheckStatus {
+    type Err = ParseSiteCheckStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "found" => Ok(Self::Found),
+            "invalid" => Ok(Self::Invalid),
+            "unauthorized" => Ok(Self::Unauthorized),
+            "missing" => Ok(Self::Missing),
+            "error" => Ok(Self::Error),
+            other => Err(ParseSiteCheckStatusError(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct SiteCheckResult {
+    /// Stable id of the check, e.g. `security_txt`.
+    pub check_id: String,
+    /// Name shown in reports, e.g. `security.txt`.
+    pub label: String,
+    pub status: SiteCheckStatus,
+    /// Where the file was found, or the first place it was looked for.
+    /// `None` when there was nowhere to look (a manifest the home page
+    /// doesn't declare).
+    pub url: Option<String>,
+    /// HTTP status of the answer the result is based on.
+    pub status_code: Option<i64>,
+    /// What's wrong with the content, or why the file wasn't looked for.
+    pub problems: Vec<String>,
+}
+
+impl SiteCheckResult {
+    /// A site-level issue for a file that's served with content problems.
+    /// Missing files aren't issues on their own: most are optional, and
+    /// the report says which are absent.
+    pub fn issue(&self, job_id: &str) -> Option<NewIssue> {
+        if self.status != SiteCheckStatus::Invalid {
+            return None;
+        }
+        let url = self.url.as_deref().unwrap_or(&self.label);
+        Some(
+            IssueBuilder::new(
+                job_id.to_string(),
+                "invalid_site_file".to_string(),
+                IssueSeverity::Warning,
+                format!("{} at {url} has problems", self.label),
+            )
+            .details(self.problems.join("; "))
+            .build(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(status: SiteCheckStatus, problems: &[&str]) -> SiteCheckResult {
+        SiteCheckResult {
+            check_id: "security_txt".into(),
+            label: "security.txt".into(),
+            status,
+            url: Some("https://example.com/.well-known/security.txt".into()),
+            status_code: Some(200),
+            problems: problems.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn only_invalid_files_are_issues() {
+        let invalid = result(SiteCheckStatus::Invalid, &["No Contact field", "No Expires field"]);
+        let issue = invalid.issue("j1").unwrap();
+        assert_eq!(issue.issue_type, "invalid_site_file");
+        assert_eq!(issue.page_id, None);
+        assert_eq!(issue.details.as_deref(), Some("No Contact field; No Expires field"));
+
+        assert!(result(SiteCheckStatus::Missing, &[]).issue("j1").is_none());
+        assert!(result(SiteCheckStatus::Found, &[]).issue("j1").is_none());
+    }
+
+    #[test]
+    fn status_round_trips_through_its_string() {
+        for status in [
+            SiteCheckStatus::Found,
+            SiteCheckStatus::Invalid,
+            SiteCheckStatus::Unauthorized,
+            SiteCheckStatus::Missing,
+            SiteCheckStatus::Error,
+        ] {
+            assert_eq!(status.as_str().parse::<SiteCheckStatus>(), Ok(status));
+        }
+        assert!("gone".parse::<SiteCheckStatus>().is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! What a verified TLS handshake with the job's site found.
+//!
+//! The crawler itself connects with certificate verification off, so a
+//! broken certificate can't stop an audit. The inspection is the one
+//! connection that does verify, and it is what the report's "SSL" verdict
+//! and the site-level certificate issues are based on.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{IssueBuilder, IssueSeverity, NewIssue};
+
+/// Certificates expiring within this many days get a warning.
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+
+/// Protocol versions browsers no longer negotiate.
+const OUTDATED_PROTOCOLS: [&str; 3] = ["SSLv3", "TLSv1", "TLSv1.1"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsInspection {
+    pub host: String,
+    pub checked_at: DateTime<Utc>,
+    /// Negotiated protocol, e.g. `TLSv1.3`.
+    pub protocol: Option<String>,
+    /// Common name of the leaf certificate.
+    pub subject: Option<String>,
+    pub subject_alt_names: Vec<String>,
+    /// Organisation (or common name) of the issuing CA.
+    pub issuer: Option<String>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub not_after: Option<DateTime<Utc>>,
+    /// Certificates the server sent, leaf included.
+    pub chain_length: usize,
+    /// Why the chain didn't verify against the trusted roots for this
+    /// host (expired, self-signed, wrong host, missing intermediate), or
+    /// `None` when it did.
+    pub verify_error: Option<String>,
+    /// Set when no TLS session could be established at all; the
+    /// certificate fields are then empty.
+    pub handshake_error: Option<String>,
+    /// Why the site wasn't inspected, e.g. the job goes through a proxy;
+    /// the certificate fields are then empty.
+    #[serde(default)]
+    pub skipped: Option<String>,
+}
+
+impl TlsInspection {
+    pub fn handshake_failed(host: &str, error: String, checked_at: DateTime<Utc>) -> Self {
+        Self {
+            host: host.to_string(),
+            checked_at,
+            protocol: None,
+            subject: None,
+            subject_alt_names: Vec::new(),
+            issuer: None,
+            not_before: None,
+            not_after: None,
+            chain_length: 0,
+            verify_error: None,
+            handshake_error: Some(error),
+            skipped: None,
+        }
+    }
+
+    /// Record that `host` wasn't inspected and why.
+    pub fn skipped(host: &str, reason: String, checked_at: DateTime<Utc>) -> Self {
+        Self {
+            handshake_error: None,
+            skipped: Some(reason),
+            ..Self::handshake_failed(host, String::new(), checked_at)
+        }
+    }
+
+    /// A browser would accept this certificate. A skipped inspection
+    /// vouches for nothing.
+    pub fn is_valid(&self) -> bool {
+        self.skipped.is_none() && self.handshake_error.is_none() && self.verify_error.is_none()
+    }
+
+    /// Whole days from `now` until the certificate expires; negative once
+    /// it has.
+    pub fn days_until_expiry(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.not_after.map(|not_after| (not_after - now).num_days())
+    }
+
+    /// Site-level issues for the job (no page attached).
+    pub fn issues(&self, job_id: &str, now: DateTime<Utc>) -> Vec<NewIssue> {
+        let issue = |issue_type: &str, severity, message: String| {
+            IssueBuilder::new(
+                job_id.to_string(),
+                issue_type.to_string(),
+                severity,
+                message,
+            )
+        };
+        let mut issues = Vec::new();
+
+        if let Some(reason) = &self.skipped {
+            issues.push(
+                issue(
+                    "tls_not_inspected",
+                    IssueSeverity::Info,
+                    format!("The TLS certificate for {} was not checked", self.host),
+                )
+                .details(reason.clone())
+                .build(),
+            );
+            return issues;
+        }
+
+        if let Some(error) = &self.handshake_error {
+            issues.push(
+                issue(
+                    "tls_handshake_failed",
+                    IssueSeverity::Critical,
+                    format!("No TLS connection could be made to {}", self.host),
+                )
+                .details(error.clone())
+                .build(),
+            );
+            return issues;
+        }
+
+        if let Some(error) = &self.verify_error {
+            issues.push(
+                issue(
+                    "invalid_tls_certificate",
+                    IssueSeverity::Critical,
+                    format!("The TLS certificate for {} is not trusted", self.host),
+                )
+                .details(format!(
+                    "{error}. Browsers show a security warning instead of the page."
+                ))
+                .build(),
+            );
+        } else if let Some(days) = self
+            .days_until_expiry(now)
+            .filter(|days| *days <= EXPIRY_WARNING_DAYS)
+        {
+            let expires = self.not_after.unwrap_or(now).format("%Y-%m-%d");
+            issues.push(
+                issue(
+                    "tls_certificate_expiring",
+                    IssueSeverity::Warning,
+                    format!(
+                        "The TLS certificate for {} expires in {days} days",
+                        self.host
+                    ),
+                )
+                .details(format!("Renew it before {expires}."))
+                .build(),
+            );
+        }
+
+        if let Some(protocol) = self
+            .protocol
+            .as_deref()
+            .filter(|protocol| OUTDATED_PROTOCOLS.contains(protocol))
+        {
+            issues.push(
+                issue(
+                    "outdated_tls_protocol",
+                    IssueSeverity::Warning,
+                    format!("{} negotiated {protocol}", self.host),
+                )
+                .details("Enable TLS 1.2 or 1.3; browsers have dropped older versions.".into())
+                .build(),
+            );
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn valid(now: DateTime<Utc>, expires_in_days: i64) -> TlsInspection {
+        TlsInspection {
+            host: "example.com".into(),
+            checked_at: now,
+            protocol: Some("TLSv1.3".into()),
+            subject: Some("example.com".into()),
+            subject_alt_names: vec!["example.com".into(), "www.example.com".into()],
+            issuer: Some("Let's Encrypt".into()),
+            not_before: Some(now - Duration::days(60)),
+            not_after: Some(now + Duration::days(expires_in_days)),
+            chain_length: 2,
+            verify_error: None,
+            handshake_error: None,
+            skipped: None,
+        }
+    }
+
+    #[test]
+    fn healthy_certificate_raises_nothing() {
+        let now = Utc::now();
+        let inspection = valid(now, 80);
+
+        assert!(inspection.is_valid());
+        assert!(inspection.issues("job", now).is_empty());
+    }
+
+    #[test]
+    fn soon_to_expire_certificate_is_a_warning() {
+        let now = Utc::now();
+        let issues = valid(now, 10).issues("job", now);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, "tls_certificate_expiring");
+        assert_eq!(issues[0].severity, IssueSeverity::Warning);
+        assert_eq!(issues[0].page_id, None);
+    }
+
+    #[test]
+    fn untrusted_certificate_is_critical() {
+        let now = Utc::now();
+        let inspection = TlsInspection {
+            verify_error: Some("certificate has expired".into()),
+            protocol: Some("TLSv1".into()),
+            ..valid(now, -3)
+        };
+
+        let issues = inspection.issues("job", now);
+
+        assert!(!inspection.is_valid());
+        let types: Vec<_> = issues.iter().map(|i| i.issue_type.as_str()).collect();
+        assert_eq!(types, ["invalid_tls_certificate", "outdated_tls_protocol"]);
+        assert_eq!(issues[0].severity, IssueSeverity::Critical);
+    }
+
+    #[test]
+    fn failed_handshake_is_reported_alone() {
+        let now = Utc::now();
+        let inspection =
+            TlsInspection::handshake_failed("example.com", "unsupported protocol".into(), now);
+
+        let issues = inspection.issues("job", now);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, "tls_handshake_failed");
+    }
+
+    #[test]
+    fn skipped_inspection_is_not_valid_and_says_why() {
+        let now = Utc::now();
+        let inspection =
+            TlsInspection::skipped("example.com", "The job goes through a proxy.".into(), now);
+
+        let issues = inspection.issues("job", now);
+
+        assert!(!inspection.is_valid());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, "tls_not_inspected");
+        assert_eq!(issues[0].severity, IssueSeverity::Info);
+        assert_eq!(issues[0].details.as_deref(), Some("The job goes through a proxy."));
+    }
+
+    #[test]
+    fn inspections_stored_before_skipping_existed_still_load() {
+        let now = Utc::now();
+        let mut json = serde_json::to_value(valid(now, 80)).unwrap();
+        json.as_object_mut().unwrap().remove("skipped");
+
+        let inspection: TlsInspection = serde_json::from_value(json).unwrap();
+
+        assert_eq!(inspection.skipped, None);
+        assert!(inspection.is_valid());
+    }
+}
@@ -0,0 +1,211 @@
+//! List-mode input: an explicit set of URLs to audit without discovery.
+//!
+//! The user pastes URLs, picks a text/CSV file or points at a sitemap; all
+//! three end up as text or a URL list that [`UrlListReport::parse`] /
+//! [`UrlListReport::from_urls`] turn into the accepted URLs plus a report
+//! of what was dropped and why, so the UI can show it next to the job.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use url::Url;
+
+/// How a job finds the pages it analyses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CrawlMode {
+    /// Discover pages by following links from the start URL.
+    #[default]
+    Spider,
+    /// Analyse exactly the URLs queued at job creation; no discovery.
+    List,
+}
+
+impl CrawlMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Spider => "spider",
+            Self::List => "list",
+        }
+    }
+}
+
+/// Returned by [`CrawlMode::from_str`] for an unknown mode string.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+#[error("invalid crawl mode: '{0}'")]
+pub struct ParseCrawlModeError(pub String);
+
+impl std::str::FromStr for CrawlMode {
+    type Err = ParseCrawlModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spider" => Ok(Self::Spider),
+            "list" => Ok(Self::List),
+            other => Err(ParseCrawlModeError(other.to_string())),
+        }
+    }
+}
+
+/// A line of list input that could not be used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct InvalidUrlEntry {
+    /// 1-based line (or position, for sitemap input).
+    pub line: usize,
+    pub value: String,
+    pub reason: String,
+}
+
+/// Outcome of parsing a URL list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct UrlListReport {
+    /// URLs that will be queued, in input order.
+    pub accepted: Vec<String>,
+    /// Repeats of an already accepted URL.
+    pub duplicates: Vec<String>,
+    pub invalid: Vec<InvalidUrlEntry>,
+    /// Valid, unique URLs dropped because the job's page limit was reached.
+    pub over_limit: usize,
+}
+
+impl UrlListReport {
+    /// Parse pasted text or the contents of a text/CSV file.
+    ///
+    /// Each non-empty line contributes its first cell (split on `,`, `;`
+    /// or tab, surrounding quotes removed) that looks like a URL. A first
+    /// line with no `://` anywhere is taken as a CSV header and skipped.
+    pub fn parse(text: &str, max_urls: usize) -> Self {
+        let mut entries = Vec::new();
+        let mut invalid = Vec::new();
+        let mut seen_content = false;
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let is_first = !seen_content;
+            seen_content = true;
+
+            let cells: Vec<&str> = line
+                .split([',', ';', '\t'])
+                .map(|cell| cell.trim().trim_matches('"').trim())
+                .filter(|cell| !cell.is_empty())
+                .collect();
+            match cells.iter().find(|cell| cell.contains("://")) {
+                Some(cell) => entries.push((index + 1, cell.to_string())),
+                None if is_first => continue,
+                None => invalid.push(InvalidUrlEntry {
+                    line: index + 1,
+                    value: line.to_string(),
+                    reason: "no URL found on this line".to_string(),
+                }),
+            }
+        }
+
+        let mut report = Self::collect(entries, max_urls);
+        invalid.append(&mut report.invalid);
+        invalid.sort_by_key(|entry| entry.line);
+        report.invalid = invalid;
+        report
+    }
+
+    /// Build a report from URLs that are already one per entry, e.g. the
+    /// `<loc>` values of a sitemap.
+    pub fn from_urls(urls: impl IntoIterator<Item = String>, max_urls: usize) -> Self {
+        let entries = urls
+            .into_iter()
+            .enumerate()
+            .map(|(index, url)| (index + 1, url.trim().to_string()))
+            .collect();
+        Self::collect(entries, max_urls)
+    }
+
+    fn collect(entries: Vec<(usize, String)>, max_urls: usize) -> Self {
+        let mut report = Self::default();
+        let mut seen = HashSet::new();
+
+        for (line, raw) in entries {
+            let url = match Url::parse(&raw) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") && url.host().is_some() => url,
+                Ok(url) => {
+                    report.invalid.push(InvalidUrlEntry {
+                        line,
+                        value: raw,
+                        reason: format!("unsupported scheme '{}'", url.scheme()),
+                    });
+                    continue;
+                }
+                Err(e) => {
+                    report.invalid.push(InvalidUrlEntry {
+                        line,
+                        value: raw,
+                        reason: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let url = url.to_string();
+            if !seen.insert(url.clone()) {
+                report.duplicates.push(url);
+            } else if report.accepted.len() >= max_urls {
+                report.over_limit += 1;
+            } else {
+                report.accepted.push(url);
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crawl_mode_round_trips_through_str() {
+        for mode in [CrawlMode::Spider, CrawlMode::List] {
+            assert_eq!(mode.as_str().parse::<CrawlMode>().unwrap(), mode);
+        }
+        assert!("sitemap".parse::<CrawlMode>().is_err());
+    }
+
+    #[test]
+    fn parse_pasted_urls_reports_duplicates_and_invalid_lines() {
+        let text = "https://example.com/a\n\nhttps://example.com/a\nnot a url\nftp://example.com/file\nhttps://other.org/b\n";
+        let report = UrlListReport::parse(text, 100);
+
+        assert_eq!(
+            report.accepted,
+            vec!["https://example.com/a", "https://other.org/b"]
+        );
+        assert_eq!(report.duplicates, vec!["https://example.com/a"]);
+        let lines: Vec<usize> = report.invalid.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![4, 5]);
+        assert!(report.invalid[1].reason.contains("ftp"));
+    }
+
+    #[test]
+    fn parse_csv_skips_header_and_takes_the_url_cell() {
+        let text = "title,url,notes\n\"Home\",\"https://example.com/\",top\nPricing;https://example.com/pricing;x\n";
+        let report = UrlListReport::parse(text, 100);
+
+        assert_eq!(
+            report.accepted,
+            vec!["https://example.com/", "https://example.com/pricing"]
+        );
+        assert!(report.invalid.is_empty());
+    }
+
+    #[test]
+    fn urls_beyond_the_limit_are_counted_not_queued() {
+        let urls = (0..5).map(|i| format!("https://example.com/{i}"));
+        let report = UrlListReport::from_urls(urls, 3);
+
+        assert_eq!(report.accepted.len(), 3);
+        assert_eq!(report.over_limit, 2);
+    }
+}
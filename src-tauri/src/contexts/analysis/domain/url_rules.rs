@@ -0,0 +1,456 @@
+//! Per-job URL scoping rules for the crawler.
+//!
+//! [`UrlRules`] is the persisted, user-facing form: include/exclude
+//! patterns, a query-parameter policy, path normalization switches and
+//! crawler-trap limits. It is stored on the job as JSON and compiled once
+//! per crawl into [`CompiledUrlRules`], which owns the built regexes.
+//!
+//! Every URL is normalized *before* it is matched, deduplicated or
+//! stored, so `/Shop/?utm_source=x` and `/shop` collapse to the same key
+//! when the rules say they should. The default rules are the identity:
+//! nothing is excluded and no URL is rewritten.
+//!
+//! ## Pattern syntax
+//!
+//! - **Glob**: `*` matches any run of characters (including `/`); every
+//!   other character is literal, so `?` is the query separator rather
+//!   than a wildcard. The glob must match the whole path-and-query
+//!   (`/wp-admin/*`, `*?color=*`), or the whole URL when the pattern
+//!   contains `://`.
+//! - **Regex**: unanchored search over the full URL string.
+
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use url::Url;
+
+/// A single include or exclude pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", content = "pattern", rename_all = "snake_case")]
+pub enum UrlPattern {
+    Glob(String),
+    Regex(String),
+}
+
+/// What to do with query parameters before a URL is queued or stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "mode", content = "params", rename_all = "snake_case")]
+pub enum QueryParamPolicy {
+    #[default]
+    KeepAll,
+    StripAll,
+    /// Drop the listed parameter names and keep the rest.
+    StripListed(Vec<String>),
+    /// Keep only the listed parameter names.
+    KeepListed(Vec<String>),
+}
+
+/// Trailing-slash normalization for the URL path. The root path `/` is
+/// never changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlash {
+    #[default]
+    Keep,
+    Strip,
+    /// Add a slash to paths whose last segment has no file extension.
+    Add,
+}
+
+/// Crawl scoping rules attached to a job.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct UrlRules {
+    /// When non-empty, only URLs matching at least one pattern are crawled.
+    #[serde(default)]
+    pub include: Vec<UrlPattern>,
+    /// URLs matching any of these are never crawled. Wins over `include`.
+    #[serde(default)]
+    pub exclude: Vec<UrlPattern>,
+    #[serde(default)]
+    pub query_params: QueryParamPolicy,
+    #[serde(default)]
+    pub trailing_slash: TrailingSlash,
+    /// Lowercase the path (the host is always lowercased by the URL parser).
+    #[serde(default)]
+    pub lowercase_path: bool,
+    /// Cap on distinct URLs sharing one path shape, where numeric
+    /// segments such as `/2024/05` count as the same shape.
+    #[serde(default)]
+    pub max_urls_per_pattern: Option<u32>,
+    /// Cap on distinct query strings crawled for a single path.
+    #[serde(default)]
+    pub max_query_combinations: Option<u32>,
+}
+
+/// Returned by [`UrlRules::compile`] when a pattern cannot be built.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum UrlRulesError {
+    #[error("invalid URL pattern '{pattern}': {message}")]
+    InvalidPattern { pattern: String, message: String },
+}
+
+impl UrlRules {
+    /// Build the regexes for every pattern. Call this when the job is
+    /// created so a bad pattern is rejected up front rather than at
+    /// crawl time.
+    pub fn compile(&self) -> Result<CompiledUrlRules, UrlRulesError> {
+        let build = |patterns: &[UrlPattern]| {
+            patterns
+                .iter()
+                .map(Matcher::new)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(CompiledUrlRules {
+            include: build(&self.include)?,
+            exclude: build(&self.exclude)?,
+            rules: self.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Matcher {
+    regex: Regex,
+    /// Globs without a scheme match the path-and-query only.
+    path_only: bool,
+}
+
+impl Matcher {
+    fn new(pattern: &UrlPattern) -> Result<Self, UrlRulesError> {
+        let (source, path_only) = match pattern {
+            UrlPattern::Glob(glob) => {
+                let body = glob
+                    .split('*')
+                    .map(regex::escape)
+                    .collect::<Vec<_>>()
+                    .join(".*");
+                (format!("^{body}$"), !glob.contains("://"))
+            }
+            UrlPattern::Regex(re) => (re.clone(), false),
+        };
+        let regex = Regex::new(&source).map_err(|e| UrlRulesError::InvalidPattern {
+            pattern: match pattern {
+                UrlPattern::Glob(p) | UrlPattern::Regex(p) => p.clone(),
+            },
+            message: e.to_string(),
+        })?;
+        Ok(Self { regex, path_only })
+    }
+
+    fn is_match(&self, url: &Url) -> bool {
+        if self.path_only {
+            match url.query() {
+                Some(query) => self.regex.is_match(&format!("{}?{}", url.path(), query)),
+                None => self.regex.is_match(url.path()),
+            }
+        } else {
+            self.regex.is_match(url.as_str())
+        }
+    }
+}
+
+/// [`UrlRules`] with its patterns built, ready to apply during a crawl.
+/// The default value applies no rules.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledUrlRules {
+    rules: UrlRules,
+    include: Vec<Matcher>,
+    exclude: Vec<Matcher>,
+}
+
+impl CompiledUrlRules {
+    /// Apply the query-parameter policy and path normalization options.
+    pub fn normalize(&self, url: &Url) -> Url {
+        let mut url = url.clone();
+
+        if self.rules.lowercase_path {
+            let lowered = url.path().to_lowercase();
+            url.set_path(&lowered);
+        }
+
+        let path = url.path();
+        if path != "/" {
+            match self.rules.trailing_slash {
+                TrailingSlash::Keep => {}
+                TrailingSlash::Strip => {
+                    let trimmed = path.trim_end_matches('/');
+                    let trimmed = if trimmed.is_empty() { "/" } else { trimmed }.to_string();
+                    url.set_path(&trimmed);
+                }
+                TrailingSlash::Add => {
+                    let last = path.rsplit('/').next().unwrap_or_default();
+                    if !path.ends_with('/') && !last.contains('.') {
+                        let with_slash = format!("{path}/");
+                        url.set_path(&with_slash);
+                    }
+                }
+            }
+        }
+
+        if url.query().is_some() {
+            let keep = |name: &str| match &self.rules.query_params {
+                QueryParamPolicy::KeepAll => true,
+                QueryParamPolicy::StripAll => false,
+                QueryParamPolicy::StripListed(names) => !names.iter().any(|n| n == name),
+                QueryParamPolicy::KeepListed(names) => names.iter().any(|n| n == name),
+            };
+            if !matches!(self.rules.query_params, QueryParamPolicy::KeepAll) {
+                let kept: Vec<(String, String)> = url
+                    .query_pairs()
+                    .filter(|(name, _)| keep(name))
+                    .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                    .collect();
+                if kept.is_empty() {
+                    url.set_query(None);
+                } else {
+                    url.query_pairs_mut().clear().extend_pairs(kept);
+                }
+            }
+        }
+
+        url
+    }
+
+    /// Whether the include/exclude patterns let this (normalized) URL
+    /// be crawled.
+    pub fn is_allowed(&self, url: &Url) -> bool {
+        if self.exclude.iter().any(|m| m.is_match(url)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|m| m.is_match(url))
+    }
+
+    /// Fresh trap-guard state for one crawl.
+    pub fn trap_guard(&self) -> CrawlTrapGuard {
+        CrawlTrapGuard {
+            max_urls_per_pattern: self.rules.max_urls_per_pattern,
+            max_query_combinations: self.rules.max_query_combinations,
+            per_pattern: HashMap::new(),
+            queries_per_path: HashMap::new(),
+        }
+    }
+}
+
+/// Counts URLs per path shape and query strings per path during a single
+/// crawl, refusing new URLs once a configured limit is reached. Guards
+/// against calendars, faceted navigation and other generated URL spaces.
+#[derive(Debug)]
+pub struct CrawlTrapGuard {
+    max_urls_per_pattern: Option<u32>,
+    max_query_combinations: Option<u32>,
+    per_pattern: HashMap<String, HashSet<String>>,
+    queries_per_path: HashMap<String, HashSet<String>>,
+}
+
+impl CrawlTrapGuard {
+    /// Record `url` and return whether it is still within the limits.
+    /// Re-admitting a URL that was already admitted always succeeds.
+    pub fn admit(&mut self, url: &Url) -> bool {
+        let key = url.as_str().to_string();
+        let shape = path_shape(url.path());
+
+        if let Some(max) = self.max_urls_per_pattern {
+            let seen = self.per_pattern.entry(shape.clone()).or_default();
+            if !seen.contains(&key) && seen.len() >= max as usize {
+                return false;
+            }
+        }
+        if let (Some(max), Some(query)) = (self.max_query_combinations, url.query()) {
+            let seen = self.queries_per_path.entry(url.path().to_string()).or_default();
+            if !seen.contains(query) && seen.len() >= max as usize {
+                return false;
+            }
+        }
+
+        if self.max_urls_per_pattern.is_some() {
+            self.per_pattern.entry(shape).or_default().insert(key);
+        }
+        if let (Some(_), Some(query)) = (self.max_query_combinations, url.query()) {
+            self.queries_per_path
+                .entry(url.path().to_string())
+                .or_default()
+                .insert(query.to_string());
+        }
+        true
+    }
+}
+
+/// Collapse path segments made only of digits and `-`/`_`/`.` separators
+/// (years, dates, page numbers, numeric ids) to `*`.
+fn path_shape(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            let numeric = segment.chars().any(|c| c.is_ascii_digit())
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || matches!(c, '-' | '_' | '.'));
+            if numeric { "*" } else { segment }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    fn compile(rules: UrlRules) -> CompiledUrlRules {
+        rules.compile().unwrap()
+    }
+
+    #[test]
+    fn default_rules_are_the_identity() {
+        let rules = CompiledUrlRules::default();
+        let u = url("https://example.com/Shop/?color=red#top");
+        assert_eq!(rules.normalize(&u), u);
+        assert!(rules.is_allowed(&u));
+    }
+
+    #[test]
+    fn exclude_glob_matches_path_and_wins_over_include() {
+        let rules = compile(UrlRules {
+            include: vec![UrlPattern::Glob("/blog/*".into())],
+            exclude: vec![UrlPattern::Glob("/blog/drafts/*".into())],
+            ..Default::default()
+        });
+        assert!(rules.is_allowed(&url("https://example.com/blog/post")));
+        assert!(!rules.is_allowed(&url("https://example.com/blog/drafts/x")));
+        assert!(!rules.is_allowed(&url("https://example.com/about")));
+    }
+
+    #[test]
+    fn glob_question_mark_is_the_literal_query_separator() {
+        let rules = compile(UrlRules {
+            exclude: vec![UrlPattern::Glob("*?color=*".into())],
+            ..Default::default()
+        });
+        assert!(!rules.is_allowed(&url("https://example.com/shoes?color=red")));
+        assert!(rules.is_allowed(&url("https://example.com/shoes")));
+    }
+
+    #[test]
+    fn regex_searches_the_full_url() {
+        let rules = compile(UrlRules {
+            exclude: vec![UrlPattern::Regex(r"/wp-(admin|login)".into())],
+            ..Default::default()
+        });
+        assert!(!rules.is_allowed(&url("https://example.com/wp-admin/edit.php")));
+        assert!(rules.is_allowed(&url("https://example.com/wp-content/a.png")));
+    }
+
+    #[test]
+    fn invalid_regex_is_rejected_at_compile_time() {
+        let err = UrlRules {
+            include: vec![UrlPattern::Regex("(".into())],
+            ..Default::default()
+        }
+        .compile()
+        .unwrap_err();
+        assert!(matches!(err, UrlRulesError::InvalidPattern { pattern, .. } if pattern == "("));
+    }
+
+    #[test]
+    fn query_param_policies() {
+        let u = url("https://example.com/p?utm_source=x&id=7&color=red");
+        let strip_all = compile(UrlRules {
+            query_params: QueryParamPolicy::StripAll,
+            ..Default::default()
+        });
+        assert_eq!(strip_all.normalize(&u).as_str(), "https://example.com/p");
+
+        let strip_listed = compile(UrlRules {
+            query_params: QueryParamPolicy::StripListed(vec!["utm_source".into()]),
+            ..Default::default()
+        });
+        assert_eq!(
+            strip_listed.normalize(&u).as_str(),
+            "https://example.com/p?id=7&color=red"
+        );
+
+        let keep_listed = compile(UrlRules {
+            query_params: QueryParamPolicy::KeepListed(vec!["id".into()]),
+            ..Default::default()
+        });
+        assert_eq!(keep_listed.normalize(&u).as_str(), "https://example.com/p?id=7");
+    }
+
+    #[test]
+    fn trailing_slash_and_case_normalization() {
+        let strip = compile(UrlRules {
+            trailing_slash: TrailingSlash::Strip,
+            lowercase_path: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            strip.normalize(&url("https://example.com/Blog/Post/")).as_str(),
+            "https://example.com/blog/post"
+        );
+        assert_eq!(
+            strip.normalize(&url("https://example.com/")).as_str(),
+            "https://example.com/"
+        );
+
+        let add = compile(UrlRules {
+            trailing_slash: TrailingSlash::Add,
+            ..Default::default()
+        });
+        assert_eq!(
+            add.normalize(&url("https://example.com/blog")).as_str(),
+            "https://example.com/blog/"
+        );
+        assert_eq!(
+            add.normalize(&url("https://example.com/feed.xml")).as_str(),
+            "https://example.com/feed.xml"
+        );
+    }
+
+    #[test]
+    fn trap_guard_caps_urls_per_path_shape() {
+        let rules = compile(UrlRules {
+            max_urls_per_pattern: Some(2),
+            ..Default::default()
+        });
+        let mut guard = rules.trap_guard();
+        assert!(guard.admit(&url("https://example.com/calendar/2024/01")));
+        assert!(guard.admit(&url("https://example.com/calendar/2024/02")));
+        assert!(!guard.admit(&url("https://example.com/calendar/2024/03")));
+        // Already-admitted URLs and other shapes are unaffected.
+        assert!(guard.admit(&url("https://example.com/calendar/2024/01")));
+        assert!(guard.admit(&url("https://example.com/about")));
+    }
+
+    #[test]
+    fn trap_guard_caps_query_combinations_per_path() {
+        let rules = compile(UrlRules {
+            max_query_combinations: Some(1),
+            ..Default::default()
+        });
+        let mut guard = rules.trap_guard();
+        assert!(guard.admit(&url("https://example.com/shoes")));
+        assert!(guard.admit(&url("https://example.com/shoes?color=red")));
+        assert!(!guard.admit(&url("https://example.com/shoes?color=blue")));
+        assert!(guard.admit(&url("https://example.com/hats?color=blue")));
+    }
+
+    #[test]
+    fn rules_round_trip_through_json() {
+        let rules = UrlRules {
+            include: vec![UrlPattern::Glob("/docs/*".into())],
+            exclude: vec![UrlPattern::Regex("sessionid=".into())],
+            query_params: QueryParamPolicy::KeepListed(vec!["page".into()]),
+            trailing_slash: TrailingSlash::Strip,
+            lowercase_path: true,
+            max_urls_per_pattern: Some(50),
+            max_query_combinations: None,
+        };
+        let json = serde_json::to_string(&rules).unwrap();
+        assert_eq!(serde_json::from_str::<UrlRules>(&json).unwrap(), rules);
+        assert_eq!(serde_json::from_str::<UrlRules>("{}").unwrap(), UrlRules::default());
+    }
+}
@@ -19,10 +19,10 @@ pub use domain::{JobPageQuery, Pagination, PaginationError, MAX_LIMIT};
 pub use domain::{RetryCount, RetryCountError, MAX_RETRY_COUNT};
 pub use domain::{
     AnyJob, Cancelled, Completed, Discovery, Failed, Job, JobFilter, JobInfo, JobSettings,
-    JobState, JobStatus, JobSummary, Pending, Processing, CompleteJobResult,
+    JobState, JobStatus, JobSummary, Paused, Pending, Processing, CompleteJobResult,
 };
 pub use domain::{
-    NewPageQueueItem, Page, PageDetails, PageInfo, PageQueueItem, PageQueueStatus,
+    DiscoveryFrontier, NewPageQueueItem, Page, PageDetails, PageInfo, PageQueueItem, PageQueueStatus,
     ParsePageQueueStatusError,
 };
 pub use domain::{Issue, NewIssue, IssueBuilder, IssueSeverity};
@@ -31,3 +31,17 @@ pub use domain::LighthouseData;
 pub use domain::{Heading, Image, NewHeading, NewImage, ResourceStatus};
 pub use domain::{AnalysisProgress, AnalysisResult};
 pub use domain::{extract_root_domain, extract_host, same_root_domain};
+pub use domain::{
+    HostOverride, HttpAuth, HttpConfig, HttpConfigError, HttpHeader, ProxyConfig, SeedCookie,
+};
+pub use domain::CrawlProfile;
+pub use domain::FetchTiming;
+pub use domain::PageWeight;
+pub use domain::ResponseHeaders;
+pub use domain::{ParseSiteCheckStatusError, SiteCheckResult, SiteCheckStatus};
+pub use domain::TlsInspection;
+pub use domain::{CrawlMode, InvalidUrlEntry, ParseCrawlModeError, UrlListReport};
+pub use domain::{
+    CompiledUrlRules, CrawlTrapGuard, QueryParamPolicy, TrailingSlash, UrlPattern, UrlRules,
+    UrlRulesError,
+};
@@ -54,9 +54,63 @@ impl AnalysisService {
 
     // === Job Lifecycle ===
 
-    /// Create a new analysis job
+    /// Create a new analysis job. Invalid URL rule patterns are rejected
+    /// here so the crawl never starts with rules it cannot apply.
     pub async fn create_job(&self, url: &str, settings: &JobSettings) -> Result<JobId> {
-        let id = self.job_repo.create(url, settings).await?;
+        self.create_job_with(url, settings, None).await
+    }
+
+    /// Create a new analysis job that sends the extra headers, cookies or
+    /// credentials of `http_config`. The configuration is stored with the
+    /// job, so it is in place before any worker can pick the job up; an
+    /// empty one isn't stored.
+    pub async fn create_job_with(
+        &self,
+        url: &str,
+        settings: &JobSettings,
+        http_config: Option<&HttpConfig>,
+    ) -> Result<JobId> {
+        let http_config = Self::validate_new_job(settings, http_config)?;
+        let id = self.job_repo.create_with(url, settings, http_config, &[]).await?;
+        Ok(JobId::from(id))
+    }
+
+    /// Validate what a new job is created with, dropping an empty HTTP
+    /// configuration.
+    fn validate_new_job<'a>(
+        settings: &JobSettings,
+        http_config: Option<&'a HttpConfig>,
+    ) -> Result<Option<&'a HttpConfig>> {
+        settings.url_rules.compile()?;
+        match http_config {
+            Some(config) => {
+                config.validate()?;
+                Ok((!config.is_empty()).then_some(config))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_http_config(&self, job_id: &str) -> Result<Option<HttpConfig>> {
+        Ok(self.job_repo.get_http_config(job_id).await?)
+    }
+
+    /// Create a list-mode job for an explicit set of URLs with them
+    /// already queued, skipping discovery. `label_url` is what the job
+    /// list shows — the sitemap URL or the first listed page.
+    pub async fn create_list_job(
+        &self,
+        label_url: &str,
+        urls: &[String],
+        settings: &JobSettings,
+        http_config: Option<&HttpConfig>,
+    ) -> Result<JobId> {
+        let settings = JobSettings {
+            crawl_mode: CrawlMode::List,
+            ..settings.clone()
+        };
+        let http_config = Self::validate_new_job(&settings, http_config)?;
+        let id = self.job_repo.create_with(label_url, &settings, http_config, urls).await?;
         Ok(JobId::from(id))
     }
 
@@ -105,6 +159,44 @@ impl AnalysisService {
         self.cancel_job(id).await
     }
 
+    /// Pause a queued or running job, keeping the pages it has already
+    /// discovered and analysed. Only `Pending`, `Discovery` and
+    /// `Processing` jobs can be paused; one that finishes or is
+    /// cancelled before the pause is stored stays as it is, and this fails.
+    pub async fn pause_job(&self, id: &str) -> Result<()> {
+        let any = self.get_job_state(id).await?;
+        if !matches!(any, AnyJob::Pending(_)) && !any.is_active() {
+            return Err(anyhow::anyhow!(
+                "cannot pause job {id}: it is {}",
+                any.stage_name()
+            ));
+        }
+        if let Some(processor) = &self.job_processor {
+            processor.pause(id).await?;
+        } else {
+            self.job_repo.pause(id).await?;
+        }
+        Ok(())
+    }
+
+    /// Put a paused job back in the queue. Its crawl continues from the
+    /// saved frontier rather than starting over.
+    pub async fn resume_job(&self, id: &str) -> Result<()> {
+        let any = self.get_job_state(id).await?;
+        if !matches!(any, AnyJob::Paused(_)) {
+            return Err(anyhow::anyhow!(
+                "cannot resume job {id}: it is {}",
+                any.stage_name()
+            ));
+        }
+        if let Some(processor) = &self.job_processor {
+            processor.resume(id).await?;
+        } else {
+            self.job_repo.update_status(id, JobStatus::Pending).await?;
+        }
+        Ok(())
+    }
+
     /// List jobs with optional filtering
     pub async fn list_jobs(&self, filter: JobFilter) -> Result<Vec<JobInfo>> {
         let limit = filter.limit.unwrap_or(100);
@@ -7,7 +7,8 @@ use async_trait::async_trait;
 use tokio::sync::RwLock;
 
 use crate::contexts::analysis::{
-    AnalysisService, Job, JobFilter, JobId, JobInfo, JobSettings, JobStatus, JobSummary,
+    AnalysisService, HttpConfig, Job, JobFilter, JobId, JobInfo, JobSettings, JobStatus,
+    JobSummary,
 };
 use crate::repository::JobRepository;
 
@@ -18,12 +19,14 @@ use crate::repository::JobRepository;
 /// Mock JobRepository for testing
 struct MockJobRepository {
     jobs: RwLock<HashMap<String, Job>>,
+    http_configs: RwLock<HashMap<String, HttpConfig>>,
 }
 
 impl MockJobRepository {
     fn new() -> Self {
         Self {
             jobs: RwLock::new(HashMap::new()),
+            http_configs: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -55,6 +58,20 @@ impl JobRepository for MockJobRepository {
         Ok(id_str)
     }
 
+    async fn create_with(
+        &self,
+        url: &str,
+        settings: &JobSettings,
+        http_config: Option<&HttpConfig>,
+        _page_urls: &[String],
+    ) -> crate::repository::RepositoryResult<String> {
+        let id = self.create(url, settings).await?;
+        if let Some(config) = http_config {
+            self.http_configs.write().await.insert(id.clone(), config.clone());
+        }
+        Ok(id)
+    }
+
     async fn get_by_id(&self, id: &str) -> crate::repository::RepositoryResult<Job> {
         self.jobs
             .read()
@@ -110,6 +127,21 @@ impl JobRepository for MockJobRepository {
         Ok(())
     }
 
+    async fn pause(&self, job_id: &str) -> crate::repository::RepositoryResult<()> {
+        match self.jobs.write().await.get_mut(job_id) {
+            Some(job)
+                if matches!(
+                    job.status,
+                    JobStatus::Pending | JobStatus::Discovery | JobStatus::Processing
+                ) =>
+            {
+                job.status = JobStatus::Paused;
+                Ok(())
+            }
+            _ => Err(crate::repository::RepositoryError::not_found("pausable job", job_id)),
+        }
+    }
+
     async fn update_progress(
         &self,
         id: &str,
@@ -121,19 +153,26 @@ impl JobRepository for MockJobRepository {
         Ok(())
     }
 
-    async fn update_resources(
+    async fn set_site_checks(
         &self,
-        id: &str,
-        sitemap_found: bool,
-        robots_txt_found: bool,
+        job_id: &str,
+        checks: &[crate::contexts::analysis::SiteCheckResult],
     ) -> crate::repository::RepositoryResult<()> {
-        if let Some(job) = self.jobs.write().await.get_mut(id) {
-            job.sitemap_found = sitemap_found;
-            job.robots_txt_found = robots_txt_found;
+        if let Some(job) = self.jobs.write().await.get_mut(job_id) {
+            let exists = |id: &str| checks.iter().any(|c| c.check_id == id && c.status.exists());
+            job.sitemap_found = exists("sitemap_xml");
+            job.robots_txt_found = exists("robots_txt");
         }
         Ok(())
     }
 
+    async fn get_site_checks(
+        &self,
+        _job_id: &str,
+    ) -> crate::repository::RepositoryResult<Vec<crate::contexts::analysis::SiteCheckResult>> {
+        Ok(Vec::new())
+    }
+
     async fn set_error(
         &self,
         job_id: &str,
@@ -154,6 +193,40 @@ impl JobRepository for MockJobRepository {
         self.jobs.write().await.remove(job_id);
         Ok(())
     }
+
+    async fn set_http_config(
+        &self,
+        job_id: &str,
+        config: &HttpConfig,
+    ) -> crate::repository::RepositoryResult<()> {
+        self.http_configs
+            .write()
+            .await
+            .insert(job_id.to_string(), config.clone());
+        Ok(())
+    }
+
+    async fn get_http_config(
+        &self,
+        job_id: &str,
+    ) -> crate::repository::RepositoryResult<Option<HttpConfig>> {
+        Ok(self.http_configs.read().await.get(job_id).cloned())
+    }
+
+    async fn set_tls_inspection(
+        &self,
+        _job_id: &str,
+        _inspection: &crate::contexts::analysis::TlsInspection,
+    ) -> crate::repository::RepositoryResult<()> {
+        Ok(())
+    }
+
+    async fn get_tls_inspection(
+        &self,
+        _job_id: &str,
+    ) -> crate::repository::RepositoryResult<Option<crate::contexts::analysis::TlsInspection>> {
+        Ok(None)
+    }
 }
 
 // ============================================================================
@@ -370,6 +443,166 @@ async fn test_cancel_job_typed_rejects_failed() {
     assert!(msg.contains("already failed"), "got: {msg}");
 }
 
+/// A job whose URL rules cannot be compiled is never created.
+#[tokio::test]
+async fn test_create_job_rejects_invalid_url_rules() {
+    let job_repo = Arc::new(MockJobRepository::new());
+    let service = AnalysisService::new(job_repo);
+    let settings = JobSettings {
+        url_rules: crate::contexts::analysis::UrlRules {
+            exclude: vec![crate::contexts::analysis::UrlPattern::Regex("[".into())],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let err = service
+        .create_job("https://example.com", &settings)
+        .await
+        .expect_err("invalid regex should be rejected");
+    assert!(format!("{err:#}").contains("invalid URL pattern"), "got: {err:#}");
+}
+
+/// HTTP configuration is validated, and an empty one isn't stored.
+#[tokio::test]
+async fn test_create_job_with_http_config() {
+    use crate::contexts::analysis::{HttpAuth, HttpHeader};
+
+    let job_repo = Arc::new(MockJobRepository::new());
+    let service = AnalysisService::new(job_repo.clone());
+    let settings = JobSettings::default();
+
+    let job_id = service
+        .create_job_with("https://example.com", &settings, Some(&HttpConfig::default()))
+        .await
+        .expect("Failed to create job");
+    assert!(job_repo.get_http_config(job_id.as_str()).await.unwrap().is_none());
+
+    let invalid = HttpConfig {
+        headers: vec![HttpHeader {
+            name: "Bad Header".into(),
+            value: "x".into(),
+        }],
+        ..Default::default()
+    };
+    assert!(service
+        .create_job_with("https://example.com", &settings, Some(&invalid))
+        .await
+        .is_err());
+    assert_eq!(job_repo.count().await.unwrap(), 1, "an invalid configuration creates no job");
+
+    let config = HttpConfig {
+        auth: Some(HttpAuth::Bearer { token: "t".into() }),
+        ..Default::default()
+    };
+    let job_id = service
+        .create_job_with("https://example.com", &settings, Some(&config))
+        .await
+        .unwrap();
+    assert_eq!(
+        job_repo.get_http_config(job_id.as_str()).await.unwrap(),
+        Some(config)
+    );
+}
+
+/// Pausing a running job and resuming it round-trips through `Paused`
+/// back to `Pending`, where the dispatcher picks it up again.
+#[tokio::test]
+async fn test_pause_and_resume_job() {
+    let job_repo = Arc::new(MockJobRepository::new());
+    let service = AnalysisService::new(job_repo.clone());
+    let settings = JobSettings::default();
+
+    let job_id = service
+        .create_job("https://example.com", &settings)
+        .await
+        .expect("Failed to create job");
+    job_repo
+        .update_status(job_id.as_str(), JobStatus::Processing)
+        .await
+        .unwrap();
+
+    service
+        .pause_job(job_id.as_str())
+        .await
+        .expect("processing job should pause");
+    let job = job_repo.get_by_id(job_id.as_str()).await.unwrap();
+    assert_eq!(job.status, JobStatus::Paused);
+
+    service
+        .resume_job(job_id.as_str())
+        .await
+        .expect("paused job should resume");
+    let job = job_repo.get_by_id(job_id.as_str()).await.unwrap();
+    assert_eq!(job.status, JobStatus::Pending);
+}
+
+/// Terminal jobs have nothing left to pause.
+#[tokio::test]
+async fn test_pause_job_rejects_completed() {
+    let job_repo = Arc::new(MockJobRepository::new());
+    let service = AnalysisService::new(job_repo.clone());
+    let settings = JobSettings::default();
+
+    let job_id = service
+        .create_job("https://example.com", &settings)
+        .await
+        .expect("Failed to create job");
+    job_repo
+        .update_status(job_id.as_str(), JobStatus::Completed)
+        .await
+        .unwrap();
+
+    let err = service
+        .pause_job(job_id.as_str())
+        .await
+        .expect_err("pause should reject completed job");
+    let msg = format!("{err:#}");
+    assert!(msg.contains("it is completed"), "got: {msg}");
+}
+
+/// Only paused jobs can be resumed.
+#[tokio::test]
+async fn test_resume_job_rejects_non_paused() {
+    let job_repo = Arc::new(MockJobRepository::new());
+    let service = AnalysisService::new(job_repo.clone());
+    let settings = JobSettings::default();
+
+    let job_id = service
+        .create_job("https://example.com", &settings)
+        .await
+        .expect("Failed to create job");
+
+    let err = service
+        .resume_job(job_id.as_str())
+        .await
+        .expect_err("resume should reject pending job");
+    let msg = format!("{err:#}");
+    assert!(msg.contains("it is pending"), "got: {msg}");
+}
+
+/// Cancelling a paused job is allowed — paused is not terminal.
+#[tokio::test]
+async fn test_cancel_job_typed_succeeds_for_paused() {
+    let job_repo = Arc::new(MockJobRepository::new());
+    let service = AnalysisService::new(job_repo.clone());
+    let settings = JobSettings::default();
+
+    let job_id = service
+        .create_job("https://example.com", &settings)
+        .await
+        .expect("Failed to create job");
+    service.pause_job(job_id.as_str()).await.unwrap();
+
+    service
+        .cancel_job_typed(job_id.as_str())
+        .await
+        .expect("typed cancel should succeed for paused job");
+
+    let job = job_repo.get_by_id(job_id.as_str()).await.unwrap();
+    assert_eq!(job.status, JobStatus::Cancelled);
+}
+
 /// Test: AnalysisService returns error for non-existent job
 #[tokio::test]
 async fn test_analysis_service_get_nonexistent_job() {
@@ -431,6 +664,10 @@ fn test_job_settings_custom() {
         mobile_analysis: true,
         lighthouse_analysis: true,
         delay_between_requests: 1000,
+        url_rules: Default::default(),
+        crawl_mode: Default::default(),
+        crawl_profile: Default::default(),
+        resource_analysis: false,
     };
     
     assert_eq!(settings.max_pages, 50);
@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::checker::custom::{self, SiteAggregateCheck};
+use crate::checker::script::{self, ScriptPage};
+use crate::checker::{CheckContext, CheckerRegistry};
+use crate::contexts::analysis::{Heading, Image, JobStatus, Link, NewIssue, Page};
+use crate::contexts::report::services::pattern_engine::HeadingIndex;
+use crate::extractor::data_extractor::{DataExtractor, ExtractorRegistry};
+use crate::extractor::page_extractor::{ExtractedHeading, ExtractedImage, ExtractedLink};
+use crate::repository::{
+    ExtensionRepository, IssueRepository, LinkRepository, PageRepository, RepositoryResult,
+    ResultsRepository, SnapshotRepository,
+};
+use crate::service::auditor::SeoAuditDetails;
+use crate::service::{ServiceError, ServiceResult};
+
+use super::{
+    AppliedChecks, CheckDryRun, CustomCheck, CustomCheckParams, DryRunIssue, ExtractedValue,
+    ExtractorDryRun, ScriptDryRun,
+};
+
+/// Runs draft checks and extractors against the stored pages of
+/// finished jobs, so they can be debugged without a new crawl, and
+/// re-runs the saved custom checks on a finished job.
+///
+/// Extractors need the pages' HTML, which is read back from the job's
+/// page snapshots; pages without one are reported, not re-parsed.
+pub struct DryRunService {
+    results_repo: Arc<dyn ResultsRepository>,
+    snapshot_repo: Arc<dyn SnapshotRepository>,
+    page_repo: Arc<dyn PageRepository>,
+    link_repo: Arc<dyn LinkRepository>,
+    issue_repo: Arc<dyn IssueRepository>,
+    extension_repo: Arc<dyn ExtensionRepository>,
+}
+
+/// What analysis stored about a finished job's pages.
+struct StoredJob {
+    pages: Vec<Page>,
+    headings: Vec<Heading>,
+    images: Vec<Image>,
+    links: Vec<Link>,
+}
+
+impl DryRunService {
+    pub fn new(
+        results_repo: Arc<dyn ResultsRepository>,
+        snapshot_repo: Arc<dyn SnapshotRepository>,
+        page_repo: Arc<dyn PageRepository>,
+        link_repo: Arc<dyn LinkRepository>,
+        issue_repo: Arc<dyn IssueRepository>,
+        extension_repo: Arc<dyn ExtensionRepository>,
+    ) -> Self {
+        Self { results_repo, snapshot_repo, page_repo, link_repo, issue_repo, extension_repo }
+    }
+
+    /// Run a draft check's script against the page of `job_id` crawled
+    /// at `url`, with the page's stored headings, images and outgoing
+    /// links.
+    pub async fn check_script(
+        &self,
+        params: &CustomCheckParams,
+        job_id: &str,
+        url: &str,
+    ) -> RepositoryResult<ScriptDryRun> {
+        let Some(source) = params.script.as_deref() else {
+            return Ok(ScriptDryRun {
+                error: Some("the check has no script".to_string()),
+                ..ScriptDryRun::default()
+            });
+        };
+
+        let page = self.page_repo.get_by_url(job_id, url).await?;
+        let headings = self.page_repo.get_headings(&page.id).await?;
+        let images = self.page_repo.get_images(&page.id).await?;
+        let links = self.link_repo.get_outgoing(&page.id).await?;
+
+        let script_page = ScriptPage::from_stored(&page, &headings, &images, &links);
+        Ok(script::dry_run(source, params.severity, &script_page))
+    }
+
+    /// Run a draft check, page or site scoped, over every stored page of
+    /// a finished job and return the issues it would raise. Nothing is
+    /// stored.
+    pub async fn run_check(
+        &self,
+        params: &CustomCheckParams,
+        job_id: &str,
+    ) -> ServiceResult<CheckDryRun> {
+        let job = self.finished_job(job_id).await?;
+        let check = CustomCheck { enabled: true, ..CustomCheck::from_params(String::new(), params) };
+        let issues = evaluate(job_id, &job, vec![check]);
+
+        let urls: HashMap<&str, &str> =
+            job.pages.iter().map(|page| (page.id.as_str(), page.url.as_str())).collect();
+        let issues = issues
+            .into_iter()
+            .map(|issue| DryRunIssue {
+                url: issue
+                    .page_id
+                    .as_deref()
+                    .and_then(|id| urls.get(id))
+                    .map(|url| url.to_string()),
+                severity: issue.severity,
+                message: issue.message,
+                details: issue.details,
+            })
+            .collect();
+        Ok(CheckDryRun { pages_checked: job.pages.len() as u32, issues })
+    }
+
+    /// Run a draft extractor over the saved HTML of every page of a
+    /// finished job and return what it extracts from each.
+    pub async fn run_extractor(
+        &self,
+        extractor: &dyn DataExtractor,
+        job_id: &str,
+    ) -> ServiceResult<ExtractorDryRun> {
+        let pages = self.finished_job_pages(job_id).await?;
+
+        let mut result = ExtractorDryRun::default();
+        for page in pages {
+            match self.snapshot_repo.get_by_page_id(&page.id).await? {
+                Some(snapshot) => {
+                    let extracted = extractor.extract(&snapshot.html, &snapshot.headers);
+                    result.pages.push(ExtractedValue {
+                        value: extracted.into_values().next(),
+                        url: page.url,
+                    });
+                }
+                None => result.without_html.push(page.url),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Re-run the saved custom checks on a finished job without
+    /// re-crawling it. Pages whose HTML was saved are first re-parsed with
+    /// the enabled extractors, then every custom check's issues are
+    /// replaced with what the enabled checks raise now. The new data and
+    /// issues are stored together or not at all.
+    pub async fn apply_checks(&self, job_id: &str) -> ServiceResult<AppliedChecks> {
+        let mut job = self.finished_job(job_id).await?;
+        let extractors =
+            ExtractorRegistry::from_extractors(self.extension_repo.list_enabled_extractors().await?);
+
+        let mut reextracted = HashSet::new();
+        for page in &mut job.pages {
+            let Some(snapshot) = self.snapshot_repo.get_by_page_id(&page.id).await? else {
+                continue;
+            };
+            page.extracted_data = extractors.run(&snapshot.html, &snapshot.headers);
+            reextracted.insert(page.id.clone());
+        }
+
+        let checks = self.extension_repo.list_checks().await?;
+        let issues = evaluate(job_id, &job, checks);
+        let extracted_data: Vec<_> = job
+            .pages
+            .iter()
+            .filter(|page| reextracted.contains(&page.id))
+            .map(|page| (page.id.as_str(), &page.extracted_data))
+            .collect();
+        let removed =
+            self.issue_repo.replace_custom_check_issues(job_id, &extracted_data, &issues).await?;
+
+        Ok(AppliedChecks {
+            pages_checked: job.pages.len() as u32,
+            pages_reextracted: extracted_data.len() as u32,
+            issues_removed: removed as u32,
+            issues_added: issues.len() as u32,
+        })
+    }
+
+    async fn finished_job_pages(&self, job_id: &str) -> ServiceResult<Vec<Page>> {
+        let job = self.results_repo.get_job(job_id).await?;
+        if job.status != JobStatus::Completed {
+            return Err(ServiceError::invalid_state(format!(
+                "job {job_id} hasn't finished, so its pages can't be re-checked yet"
+            )));
+        }
+        Ok(self.results_repo.get_pages(job_id).await?)
+    }
+
+    async fn finished_job(&self, job_id: &str) -> ServiceResult<StoredJob> {
+        Ok(StoredJob {
+            pages: self.finished_job_pages(job_id).await?,
+            headings: self.results_repo.get_headings(job_id).await?,
+            images: self.results_repo.get_images(job_id).await?,
+            links: self.results_repo.get_links(job_id).await?,
+        })
+    }
+}
+
+/// Run the enabled ones of `checks` over the job's pages: page checks on
+/// each page with its stored headings, images and links, site checks
+/// once over all of them.
+fn evaluate(job_id: &str, job: &StoredJob, checks: Vec<CustomCheck>) -> Vec<NewIssue> {
+    let mut registry = CheckerRegistry::default();
+    let site_checks: Vec<SiteAggregateCheck> = custom::register(&mut registry, checks);
+
+    let mut headings: HashMap<&str, Vec<&Heading>> = HashMap::new();
+    for heading in &job.headings {
+        headings.entry(heading.page_id.as_str()).or_default().push(heading);
+    }
+    let mut images: HashMap<&str, Vec<ExtractedImage>> = HashMap::new();
+    for image in &job.images {
+        images.entry(image.page_id.as_str()).or_default().push(ExtractedImage {
+            src: image.src.clone(),
+            alt: image.alt.clone(),
+            width: image.width,
+            height: image.height,
+            loading: image.loading.clone(),
+            is_decorative: image.is_decorative,
+        });
+    }
+    let mut links: HashMap<&str, Vec<ExtractedLink>> = HashMap::new();
+    for link in &job.links {
+        links.entry(link.source_page_id.as_str()).or_default().push(ExtractedLink {
+            href: link.target_url.clone(),
+            link_type: link.link_type.clone(),
+            text: link.link_text.clone(),
+        });
+    }
+
+    // Built-in checks aren't re-run, and custom checks don't read the
+    // audit details.
+    let seo_details = SeoAuditDetails::default();
+    let mut issues = Vec::new();
+    for page in &job.pages {
+        let id = page.id.as_str();
+        let mut page_headings: Vec<ExtractedHeading> = headings
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(|h| ExtractedHeading { level: h.level, text: h.text.clone(), position: h.position })
+            .collect();
+        page_headings.sort_by_key(|h| h.position);
+        let ctx = CheckContext::new(page, &seo_details, job_id, id)
+            .with_references(&[], links.get(id).map(Vec::as_slice).unwrap_or_default())
+            .with_headings(&page_headings)
+            .with_images(images.get(id).map(Vec::as_slice).unwrap_or_default());
+        issues.extend(registry.run(&ctx));
+    }
+
+    if !site_checks.is_empty() {
+        let index = HeadingIndex::from_headings(&job.headings);
+        issues.extend(site_checks.iter().filter_map(|check| check.run(job_id, &job.pages, &index)));
+    }
+    issues
+}
@@ -1,7 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+mod dry_run;
+
+pub use dry_run::DryRunService;
+
 // Reuse the canonical IssueSeverity from the analysis domain.
 pub use crate::contexts::analysis::IssueSeverity;
+use crate::contexts::tags::TagDataType;
 
 /// Condition operator for a custom check.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
@@ -14,6 +19,16 @@ pub enum Operator {
     Gt,
     Contains,
     NotContains,
+    /// Text matches the regular expression in the threshold.
+    Matches,
+    /// Number within the inclusive `min,max` range in the threshold.
+    Between,
+    /// Text has fewer characters than the threshold.
+    LengthLt,
+    /// Text has more characters than the threshold.
+    LengthGt,
+    /// Value equals one of the comma-separated values in the threshold.
+    InList,
 }
 
 impl std::fmt::Display for Operator {
@@ -26,6 +41,11 @@ impl std::fmt::Display for Operator {
             Self::Gt => write!(f, "gt"),
             Self::Contains => write!(f, "contains"),
             Self::NotContains => write!(f, "not_contains"),
+            Self::Matches => write!(f, "matches"),
+            Self::Between => write!(f, "between"),
+            Self::LengthLt => write!(f, "length_lt"),
+            Self::LengthGt => write!(f, "length_gt"),
+            Self::InList => write!(f, "in_list"),
         }
     }
 }
@@ -50,11 +70,151 @@ impl std::str::FromStr for Operator {
             "gt" => Ok(Self::Gt),
             "contains" => Ok(Self::Contains),
             "not_contains" => Ok(Self::NotContains),
+            "matches" => Ok(Self::Matches),
+            "between" => Ok(Self::Between),
+            "length_lt" => Ok(Self::LengthLt),
+            "length_gt" => Ok(Self::LengthGt),
+            "in_list" => Ok(Self::InList),
             other => Err(ParseOperatorError(other.to_string())),
         }
     }
 }
 
+/// A condition over page fields: one comparison, or a combination of
+/// them. Shared by custom checks and report patterns.
+///
+/// Serialized with a `kind` tag, e.g.
+/// `{"kind":"all","children":[{"kind":"compare","field":"title","operator":"contains","threshold":"Acme"}, ...]}`.
+/// Mirrors the report template `Condition` tree.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleCondition {
+    /// `field <operator> threshold`, or `field <operator> compare_field`
+    /// when comparing two fields (e.g. `title` eq `tag:og_title`).
+    Compare {
+        field: String,
+        operator: Operator,
+        #[serde(default)]
+        threshold: Option<String>,
+        /// Another field whose value stands in for `threshold`.
+        #[serde(default)]
+        compare_field: Option<String>,
+    },
+    /// Every condition holds.
+    All { children: Vec<RuleCondition> },
+    /// At least one condition holds.
+    Any { children: Vec<RuleCondition> },
+    /// The condition doesn't hold.
+    Not { inner: Box<RuleCondition> },
+}
+
+impl RuleCondition {
+    /// The single comparison a rule's `field`/`operator`/`threshold` describe.
+    pub fn compare(field: &str, operator: &Operator, threshold: Option<&str>) -> Self {
+        Self::Compare {
+            field: field.to_string(),
+            operator: operator.clone(),
+            threshold: threshold.map(str::to_string),
+            compare_field: None,
+        }
+    }
+}
+
+/// Why a rule condition was rejected when saving a check or pattern.
+#[derive(Debug, Clone, thiserror::Error, PartialEq)]
+pub enum ConditionError {
+    #[error("every comparison needs a field")]
+    MissingField,
+    #[error("'{0}' needs a threshold")]
+    MissingThreshold(Operator),
+    #[error("'{operator}' threshold '{threshold}' is not a number")]
+    InvalidNumber { operator: Operator, threshold: String },
+    #[error("'between' threshold '{0}' must be 'min,max' with min no greater than max")]
+    InvalidRange(String),
+    #[error("'in_list' threshold needs at least one value")]
+    EmptyList,
+    #[error("invalid regular expression '{pattern}': {message}")]
+    InvalidRegex { pattern: String, message: String },
+    #[error("'{0}' can't compare against another field")]
+    UnsupportedFieldComparison(Operator),
+    #[error("'{0}' needs at least one condition")]
+    EmptyGroup(&'static str),
+    #[error("site checks need an aggregate")]
+    MissingAggregate,
+    #[error("'{0}' can't compare an aggregate; use eq, lt, gt or between")]
+    UnsupportedAggregateOperator(Operator),
+    #[error("script doesn't compile: {0}")]
+    InvalidScript(String),
+    #[error("site checks can't run a script")]
+    ScriptOnSiteCheck,
+}
+
+/// Whether a custom check runs on every page or once over the whole site.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckScope {
+    #[default]
+    Page,
+    Site,
+}
+
+impl CheckScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Page => "page",
+            Self::Site => "site",
+        }
+    }
+}
+
+/// Returned by [`CheckScope::from_str`] for an unknown scope.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+#[error("invalid check scope: '{0}'")]
+pub struct ParseCheckScopeError(pub String);
+
+impl std::str::FromStr for CheckScope {
+    type Err = ParseCheckScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "page" => Ok(Self::Page),
+            "site" => Ok(Self::Site),
+            other => Err(ParseCheckScopeError(other.to_string())),
+        }
+    }
+}
+
+/// How a site check summarises the pages its condition matches.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateFunction {
+    /// Number of matching pages.
+    Count,
+    /// Matching pages as a percentage of all pages.
+    Percent,
+    /// Number of distinct `field` values among matching pages.
+    Distinct,
+    /// Number of matching pages whose `field` value another matching
+    /// page shares.
+    Duplicates,
+    /// Smallest numeric `field` value among matching pages.
+    Min,
+    /// Largest numeric `field` value among matching pages.
+    Max,
+    /// Mean numeric `field` value among matching pages.
+    Avg,
+}
+
+/// The aggregate a site check computes and the comparison that raises
+/// its issue, e.g. `percent` `lt` `80`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct SiteAggregate {
+    pub function: AggregateFunction,
+    /// One of `eq`, `lt`, `gt` or `between`.
+    pub operator: Operator,
+    pub threshold: Option<String>,
+}
+
 /// A user-defined check that inspects extracted page data and produces an issue.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct CustomCheck {
@@ -66,11 +226,47 @@ pub struct CustomCheck {
     pub operator: Operator,
     /// Threshold value for numeric/text comparisons; unused for `Missing`.
     pub threshold: Option<String>,
-    /// Template for the issue message. `{value}` is replaced with the actual field value.
+    /// Compound condition that replaces `field`/`operator`/`threshold`
+    /// when set. `field` still supplies `{value}` for the message.
+    #[serde(default)]
+    pub condition: Option<RuleCondition>,
+    /// `page` checks run on every page; `site` checks run once per job.
+    #[serde(default)]
+    pub scope: CheckScope,
+    /// For site checks, what to compute over the pages the condition
+    /// matches. Ignored for page checks.
+    #[serde(default)]
+    pub aggregate: Option<SiteAggregate>,
+    /// A Rhai script that replaces the rule when set. It reads the page
+    /// as `page` and returns its issues; see `checker::script`.
+    /// Page checks only.
+    #[serde(default)]
+    pub script: Option<String>,
+    /// Template for the issue message. `{value}` is replaced with the
+    /// actual field value, or a site check's aggregate.
     pub message_template: String,
     pub enabled: bool,
 }
 
+impl CustomCheck {
+    pub fn from_params(id: String, p: &CustomCheckParams) -> Self {
+        Self {
+            id,
+            name: p.name.clone(),
+            severity: p.severity,
+            field: p.field.clone(),
+            operator: p.operator.clone(),
+            threshold: p.threshold.clone(),
+            condition: p.condition.clone(),
+            scope: p.scope,
+            aggregate: p.aggregate.clone(),
+            script: p.script.clone(),
+            message_template: p.message_template.clone(),
+            enabled: p.enabled,
+        }
+    }
+}
+
 /// Parameters for creating or updating a custom check.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct CustomCheckParams {
@@ -79,10 +275,193 @@ pub struct CustomCheckParams {
     pub field: String,
     pub operator: Operator,
     pub threshold: Option<String>,
+    #[serde(default)]
+    pub condition: Option<RuleCondition>,
+    #[serde(default)]
+    pub scope: CheckScope,
+    #[serde(default)]
+    pub aggregate: Option<SiteAggregate>,
+    #[serde(default)]
+    pub script: Option<String>,
     pub message_template: String,
     pub enabled: bool,
 }
 
+impl From<&CustomCheck> for CustomCheckParams {
+    fn from(check: &CustomCheck) -> Self {
+        Self {
+            name: check.name.clone(),
+            severity: check.severity,
+            field: check.field.clone(),
+            operator: check.operator.clone(),
+            threshold: check.threshold.clone(),
+            condition: check.condition.clone(),
+            scope: check.scope,
+            aggregate: check.aggregate.clone(),
+            script: check.script.clone(),
+            message_template: check.message_template.clone(),
+            enabled: check.enabled,
+        }
+    }
+}
+
+/// An issue a check script raised during a dry run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct ScriptIssue {
+    pub severity: IssueSeverity,
+    pub message: String,
+    pub details: Option<String>,
+}
+
+/// What a check script did when run against one stored page.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+pub struct ScriptDryRun {
+    pub issues: Vec<ScriptIssue>,
+    /// Lines the script wrote with `print` and `debug`.
+    pub output: Vec<String>,
+    /// Why the script didn't compile or didn't run to the end.
+    pub error: Option<String>,
+}
+
+/// An issue a draft check would raise on a finished job.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct DryRunIssue {
+    /// The page the issue is on; `None` for a site check's issue.
+    pub url: Option<String>,
+    pub severity: IssueSeverity,
+    pub message: String,
+    pub details: Option<String>,
+}
+
+/// What a draft check would raise over the stored pages of a finished
+/// job.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+pub struct CheckDryRun {
+    pub pages_checked: u32,
+    pub issues: Vec<DryRunIssue>,
+}
+
+/// A draft extractor's value on one page; `None` when nothing matched.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct ExtractedValue {
+    pub url: String,
+    pub value: Option<serde_json::Value>,
+}
+
+/// What a draft extractor extracts from the pages of a finished job.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+pub struct ExtractorDryRun {
+    pub pages: Vec<ExtractedValue>,
+    /// Pages whose HTML wasn't saved, so the extractor couldn't run on
+    /// them.
+    pub without_html: Vec<String>,
+}
+
+/// What re-running the custom checks on a finished job changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct AppliedChecks {
+    pub pages_checked: u32,
+    /// Pages whose HTML was re-parsed with the current extractors.
+    pub pages_reextracted: u32,
+    pub issues_removed: u32,
+    pub issues_added: u32,
+}
+
+/// Where a custom extractor reads its value from. `selector` holds the
+/// expression in that kind's syntax.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractorKind {
+    /// A CSS selector; `attribute` reads an attribute instead of the text.
+    #[default]
+    Css,
+    /// An XPath expression. `attribute` works as for CSS, or the path
+    /// can end in `/@attr` or `/text()`.
+    Xpath,
+    /// A regex over the raw HTML. `attribute` names the capture group
+    /// to read, by name or number; otherwise the first group, or the
+    /// whole match when there are none.
+    Regex,
+    /// A JSON path (`$.author.name`) into each JSON-LD block.
+    JsonLd,
+    /// A response header, named by `selector`.
+    Header,
+}
+
+impl ExtractorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Css => "css",
+            Self::Xpath => "xpath",
+            Self::Regex => "regex",
+            Self::JsonLd => "json_ld",
+            Self::Header => "header",
+        }
+    }
+}
+
+/// Returned by [`ExtractorKind::from_str`] for an unknown kind.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+#[error("invalid extractor kind: '{0}'")]
+pub struct ParseExtractorKindError(pub String);
+
+impl std::str::FromStr for ExtractorKind {
+    type Err = ParseExtractorKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "css" => Ok(Self::Css),
+            "xpath" => Ok(Self::Xpath),
+            "regex" => Ok(Self::Regex),
+            "json_ld" => Ok(Self::JsonLd),
+            "header" => Ok(Self::Header),
+            other => Err(ParseExtractorKindError(other.to_string())),
+        }
+    }
+}
+
+/// What an extractor publishes for its matches.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractorOutput {
+    /// The matched values themselves.
+    #[default]
+    Value,
+    /// How many matches there are; 0 rather than missing when none.
+    Count,
+    /// The length in characters of each matched value.
+    Length,
+}
+
+impl ExtractorOutput {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Value => "value",
+            Self::Count => "count",
+            Self::Length => "length",
+        }
+    }
+}
+
+/// Returned by [`ExtractorOutput::from_str`] for an unknown output.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+#[error("invalid extractor output: '{0}'")]
+pub struct ParseExtractorOutputError(pub String);
+
+impl std::str::FromStr for ExtractorOutput {
+    type Err = ParseExtractorOutputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "value" => Ok(Self::Value),
+            "count" => Ok(Self::Count),
+            "length" => Ok(Self::Length),
+            other => Err(ParseExtractorOutputError(other.to_string())),
+        }
+    }
+}
+
+
 /// A user-defined CSS-selector extractor that populates `page.extracted_data`.
 ///
 /// `tag` (formerly `key`) is the symbol the consultant references from
@@ -99,23 +478,80 @@ pub struct CustomExtractor {
     /// `page.extracted_data`. Must be unique across all extractors —
     /// the DB column has a `UNIQUE` constraint.
     pub tag: String,
+    #[serde(default)]
+    pub kind: ExtractorKind,
+    /// The expression to evaluate, in `kind`'s syntax.
     pub selector: String,
     pub attribute: Option<String>,
     pub multiple: bool,
+    #[serde(default)]
+    pub output: ExtractorOutput,
+    /// The type extracted values are converted to; values that don't
+    /// convert are dropped. `None` infers it, see
+    /// [`CustomExtractor::data_type`].
+    #[serde(default)]
+    pub data_type: Option<TagDataType>,
     pub enabled: bool,
 }
 
+impl CustomExtractor {
+    /// The data type of the tag's values: a list whenever it collects
+    /// every match (each item converted to the declared type), otherwise
+    /// the declared type, or without one a number for counts and lengths
+    /// and text for values.
+    pub fn data_type(&self) -> TagDataType {
+        resolve_data_type(self.data_type, self.output, self.multiple)
+    }
+}
+
+/// See [`CustomExtractor::data_type`].
+pub fn resolve_data_type(
+    declared: Option<TagDataType>,
+    output: ExtractorOutput,
+    multiple: bool,
+) -> TagDataType {
+    match (declared, output) {
+        (_, ExtractorOutput::Value | ExtractorOutput::Length) if multiple => TagDataType::List,
+        (Some(data_type), _) => data_type,
+        (None, ExtractorOutput::Count) => TagDataType::Number,
+        (None, ExtractorOutput::Length) => TagDataType::Number,
+        (None, ExtractorOutput::Value) => TagDataType::Text,
+    }
+}
+
 /// Parameters for creating or updating a custom extractor.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct CustomExtractorParams {
     pub name: String,
     pub tag: String,
+    #[serde(default)]
+    pub kind: ExtractorKind,
     pub selector: String,
     pub attribute: Option<String>,
     pub multiple: bool,
+    #[serde(default)]
+    pub output: ExtractorOutput,
+    #[serde(default)]
+    pub data_type: Option<TagDataType>,
     pub enabled: bool,
 }
 
+impl From<&CustomExtractor> for CustomExtractorParams {
+    fn from(extractor: &CustomExtractor) -> Self {
+        Self {
+            name: extractor.name.clone(),
+            tag: extractor.tag.clone(),
+            kind: extractor.kind,
+            selector: extractor.selector.clone(),
+            attribute: extractor.attribute.clone(),
+            multiple: extractor.multiple,
+            output: extractor.output,
+            data_type: extractor.data_type,
+            enabled: extractor.enabled,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //! Characterization tests for the extension domain types. The
@@ -137,6 +573,11 @@ mod tests {
             Operator::Gt,
             Operator::Contains,
             Operator::NotContains,
+            Operator::Matches,
+            Operator::Between,
+            Operator::LengthLt,
+            Operator::LengthGt,
+            Operator::InList,
         ] {
             let s = op.to_string();
             let parsed = Operator::from_str(&s).expect("round-trip should succeed");
@@ -187,6 +628,10 @@ mod tests {
             field: "title".into(),
             operator: Operator::Lt,
             threshold: Some("30".into()),
+            condition: None,
+            scope: CheckScope::Page,
+            aggregate: None,
+            script: None,
             message_template: "Title is too short ({value} chars)".into(),
             enabled: true,
         };
@@ -199,15 +644,88 @@ mod tests {
         assert_eq!(parsed.threshold.as_deref(), Some("30"));
     }
 
+    #[test]
+    fn rule_condition_serde_uses_kind_tags() {
+        let json = serde_json::json!({
+            "kind": "all",
+            "children": [
+                { "kind": "compare", "field": "title", "operator": "contains", "threshold": "Acme" },
+                { "kind": "not", "inner": {
+                    "kind": "compare", "field": "title", "operator": "eq", "compare_field": "tag:og_title"
+                } },
+            ],
+        });
+        let parsed: RuleCondition = serde_json::from_value(json.clone()).unwrap();
+        let RuleCondition::All { children } = &parsed else { panic!("expected all") };
+        assert_eq!(children[0], RuleCondition::compare("title", &Operator::Contains, Some("Acme")));
+        assert_eq!(serde_json::to_value(&parsed).unwrap()["children"][1]["inner"]["compare_field"], "tag:og_title");
+    }
+
+    #[test]
+    fn custom_check_without_condition_deserializes() {
+        // Checks saved before compound conditions have no `condition` key.
+        let json = serde_json::json!({
+            "id": "abc", "name": "n", "severity": "warning", "field": "title",
+            "operator": "missing", "threshold": null, "message_template": "m", "enabled": true,
+        });
+        let parsed: CustomCheck = serde_json::from_value(json).unwrap();
+        assert!(parsed.condition.is_none());
+        assert_eq!(parsed.scope, CheckScope::Page);
+        assert!(parsed.aggregate.is_none());
+        assert!(parsed.script.is_none());
+    }
+
+    #[test]
+    fn check_scope_round_trips_through_from_str() {
+        for scope in [CheckScope::Page, CheckScope::Site] {
+            assert_eq!(CheckScope::from_str(scope.as_str()).unwrap(), scope);
+        }
+        assert!(CheckScope::from_str("job").is_err());
+    }
+
+    #[test]
+    fn extractor_kind_and_output_round_trip_through_from_str() {
+        for kind in [
+            ExtractorKind::Css,
+            ExtractorKind::Xpath,
+            ExtractorKind::Regex,
+            ExtractorKind::JsonLd,
+            ExtractorKind::Header,
+        ] {
+            assert_eq!(ExtractorKind::from_str(kind.as_str()).unwrap(), kind);
+            assert_eq!(serde_json::to_value(kind).unwrap(), kind.as_str());
+        }
+        for output in [ExtractorOutput::Value, ExtractorOutput::Count, ExtractorOutput::Length] {
+            assert_eq!(ExtractorOutput::from_str(output.as_str()).unwrap(), output);
+        }
+        assert!(ExtractorKind::from_str("jquery").is_err());
+    }
+
+    #[test]
+    fn custom_extractor_without_kind_deserializes_as_css() {
+        // Extractors saved before other kinds existed have no `kind` key.
+        let json = serde_json::json!({
+            "id": "x", "name": "n", "tag": "t", "selector": "h1",
+            "attribute": null, "multiple": true, "enabled": true,
+        });
+        let parsed: CustomExtractor = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.kind, ExtractorKind::Css);
+        assert_eq!(parsed.output, ExtractorOutput::Value);
+        assert_eq!(parsed.data_type(), TagDataType::List);
+    }
+
     #[test]
     fn custom_extractor_threshold_field_optional() {
         let extractor = CustomExtractor {
             id: "x".into(),
             name: "OG image".into(),
             tag: "og_image".into(),
+            kind: ExtractorKind::Css,
             selector: "meta[property='og:image']".into(),
             attribute: Some("content".into()),
             multiple: false,
+            output: ExtractorOutput::Value,
+            data_type: None,
             enabled: true,
         };
         let json = serde_json::to_value(&extractor).unwrap();
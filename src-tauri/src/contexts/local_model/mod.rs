@@ -7,4 +7,4 @@ mod tests;
 
 pub use domain::{ModelEntry, ModelInfo};
 pub use factory::LocalModelServiceFactory;
-pub use services::LocalModelService;
+pub use services::{LocalModelBackend, LocalModelService};
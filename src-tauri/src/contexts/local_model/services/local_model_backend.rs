@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::service::local_model::{InferenceEngine, InferenceRequest};
+use crate::service::AiBackend;
+
+/// Answer length for insights, which are a single long answer.
+const INSIGHTS_MAX_TOKENS: usize = 1024;
+const INSIGHTS_TEMPERATURE: f32 = 0.7;
+/// Report sections run cooler than insights so a report reads the same
+/// from one section to the next.
+const SECTION_TEMPERATURE: f32 = 0.4;
+
+/// The active local model as an [`AiBackend`]. Built by
+/// [`LocalModelService::backend`](super::LocalModelService::backend),
+/// which checks the model is downloaded.
+pub struct LocalModelBackend {
+    engine: Arc<dyn InferenceEngine>,
+    model_path: PathBuf,
+}
+
+impl LocalModelBackend {
+    pub(super) fn new(engine: Arc<dyn InferenceEngine>, model_path: PathBuf) -> Self {
+        Self { engine, model_path }
+    }
+
+    async fn infer(&self, prompt: &str, max_tokens: usize, temperature: f32) -> Result<String> {
+        self.engine
+            .infer(InferenceRequest {
+                model_path: self.model_path.clone(),
+                prompt: prompt.to_string(),
+                max_tokens,
+                temperature,
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl AiBackend for LocalModelBackend {
+    fn name(&self) -> &'static str {
+        "Local model"
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        self.infer(prompt, INSIGHTS_MAX_TOKENS, INSIGHTS_TEMPERATURE)
+            .await
+    }
+
+    async fn generate_section(&self, prompt: &str, max_tokens: usize) -> Result<String> {
+        self.infer(prompt, max_tokens, SECTION_TEMPERATURE).await
+    }
+}
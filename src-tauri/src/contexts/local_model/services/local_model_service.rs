@@ -6,9 +6,12 @@ use anyhow::Result;
 use crate::contexts::local_model::domain::{ModelEntry, ModelInfo, MODEL_REGISTRY};
 use crate::repository::SettingsRepository;
 use crate::service::gemini::GeminiRequest;
-use crate::service::local_model::{InferenceEngine, InferenceRequest, ModelDownloader};
+use crate::service::local_model::{InferenceEngine, ModelDownloader};
+use crate::service::AiBackend;
 use crate::service::prompt::{build_prompt_from_blocks, load_persona, load_prompt_blocks};
 
+use super::LocalModelBackend;
+
 const ACTIVE_MODEL_SETTING: &str = "local_model_active_id";
 
 pub struct LocalModelService {
@@ -66,6 +69,10 @@ impl LocalModelService {
         let entry = ModelEntry::find_by_id(model_id)
             .ok_or_else(|| anyhow::anyhow!("Unknown model id: {model_id}"))?;
 
+        // Free the model first: it may be loaded, and a loaded model's
+        // file can't be removed on Windows.
+        self.inference_engine.unload().await;
+
         let path = self.model_path(entry);
         match std::fs::remove_file(&path) {
             Ok(()) => {}
@@ -102,7 +109,13 @@ impl LocalModelService {
             anyhow::bail!("Model {model_id} is not downloaded yet");
         }
 
+        let previous = self.get_active_model_id().await?;
         self.settings_repo.set_setting(ACTIVE_MODEL_SETTING, model_id).await?;
+
+        // Don't hold the old model in memory until it times out.
+        if previous.is_some_and(|id| id != model_id) {
+            self.inference_engine.unload().await;
+        }
         Ok(())
     }
 
@@ -111,17 +124,7 @@ impl LocalModelService {
     /// Uses the same persona and prompt blocks configured in Settings → AI Instructions,
     /// so Gemini and the local model produce prompts in exactly the same shape.
     pub async fn generate_insights(&self, request: &GeminiRequest) -> Result<String> {
-        let model_id = self.get_active_model_id().await?
-            .ok_or_else(|| anyhow::anyhow!("No local model selected. Download and activate a model first."))?;
-
-        let entry = ModelEntry::find_by_id(&model_id)
-            .ok_or_else(|| anyhow::anyhow!("Active model not found in registry"))?;
-
-        if !self.is_downloaded(entry) {
-            anyhow::bail!("Active model is not downloaded. Please re-download it.");
-        }
-
-        let model_path = self.model_path(entry);
+        let backend = self.backend().await?;
 
         // Load persona + blocks — same settings keys used by Gemini
         let persona = load_persona(self.settings_repo.as_ref()).await?;
@@ -129,23 +132,24 @@ impl LocalModelService {
 
         let prompt = build_prompt_from_blocks(&persona, &blocks, request);
 
-        self.inference_engine.infer(InferenceRequest {
-            model_path,
-            prompt,
-            max_tokens: 1024,
-            temperature: 0.7,
-        }).await
+        backend.generate(&prompt).await
     }
 
-    /// Expose the models directory path (needed by ReportService for direct inference).
-    pub fn models_dir(&self) -> &PathBuf {
-        &self.models_dir
-    }
+    /// The active model as an AI backend, for the AI service and the AI
+    /// sections of reports. Fails when no model is active or it isn't
+    /// downloaded.
+    pub async fn backend(&self) -> Result<LocalModelBackend> {
+        let model_id = self.get_active_model_id().await?
+            .ok_or_else(|| anyhow::anyhow!("No local model selected. Download and activate a model first."))?;
+
+        let entry = ModelEntry::find_by_id(&model_id)
+            .ok_or_else(|| anyhow::anyhow!("Active model not found in registry"))?;
+
+        if !self.is_downloaded(entry) {
+            anyhow::bail!("Active model is not downloaded. Please re-download it.");
+        }
 
-    /// Low-level inference call — bypasses GeminiRequest wrapping.
-    /// Used by ReportService for phased AI brief generation.
-    pub async fn infer_raw(&self, request: InferenceRequest) -> Result<String> {
-        self.inference_engine.infer(request).await
+        Ok(LocalModelBackend::new(self.inference_engine.clone(), self.model_path(entry)))
     }
 
     // --- private helpers ---
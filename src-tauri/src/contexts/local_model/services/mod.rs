@@ -1,3 +1,5 @@
+mod local_model_backend;
 mod local_model_service;
 
+pub use local_model_backend::LocalModelBackend;
 pub use local_model_service::LocalModelService;
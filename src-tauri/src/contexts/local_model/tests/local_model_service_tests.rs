@@ -51,7 +51,7 @@ impl DownloadEmitter for NilEmitter {
 fn nil_spider() -> Arc<MockSpider> {
     Arc::new(MockSpider {
         html_response: String::new(),
-        generic_response: SpiderResponse { status: 200, body: String::new(), url: String::new() },
+        generic_response: SpiderResponse { status: 200, body: String::new(), url: String::new(), headers: Default::default(), timing: Default::default() },
     })
 }
 
@@ -217,6 +217,46 @@ async fn set_active_model_is_reflected_in_list_models() {
     assert_eq!(active[0].entry.id, model_id);
 }
 
+/// Counts how often it's asked to unload its model.
+#[derive(Default)]
+struct UnloadCountingEngine {
+    unloads: std::sync::atomic::AtomicUsize,
+}
+
+impl UnloadCountingEngine {
+    fn unloads(&self) -> usize {
+        self.unloads.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl InferenceEngine for UnloadCountingEngine {
+    async fn infer(&self, _request: InferenceRequest) -> Result<String> {
+        Ok(String::new())
+    }
+
+    async fn unload(&self) {
+        self.unloads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn set_active_model_unloads_only_when_the_model_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Llama-3.2-1B-Instruct-Q4_K_M.gguf"), b"fake").unwrap();
+    std::fs::write(dir.path().join("Qwen2.5-7B-Instruct-Q4_K_M.gguf"), b"fake").unwrap();
+
+    let engine = Arc::new(UnloadCountingEngine::default());
+    let svc = make_service(MockSettingsRepo::new(), dir.path().to_path_buf(), engine.clone());
+
+    svc.set_active_model("llama-3.2-1b-instruct-q4").await.unwrap();
+    svc.set_active_model("llama-3.2-1b-instruct-q4").await.unwrap();
+    assert_eq!(engine.unloads(), 0);
+
+    svc.set_active_model("qwen2.5-7b-instruct-q4").await.unwrap();
+    assert_eq!(engine.unloads(), 1);
+}
+
 // ── delete_model ──────────────────────────────────────────────────────────────
 
 #[tokio::test]
@@ -239,6 +279,17 @@ async fn delete_model_removes_file() {
     assert!(!path.exists());
 }
 
+#[tokio::test]
+async fn delete_model_unloads_before_removing_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Llama-3.2-1B-Instruct-Q4_K_M.gguf"), b"fake").unwrap();
+
+    let engine = Arc::new(UnloadCountingEngine::default());
+    let svc = make_service(MockSettingsRepo::new(), dir.path().to_path_buf(), engine.clone());
+    svc.delete_model("llama-3.2-1b-instruct-q4").await.unwrap();
+    assert_eq!(engine.unloads(), 1);
+}
+
 #[tokio::test]
 async fn delete_model_clears_active_setting() {
     let dir = tempfile::tempdir().unwrap();
@@ -347,6 +398,55 @@ async fn generate_insights_propagates_engine_error() {
     assert!(err.to_string().contains("inference failed"), "unexpected error: {err}");
 }
 
+// ── backend ───────────────────────────────────────────────────────────────────
+
+/// Records the requests it's sent, so tests can check their limits.
+struct RecordingInferenceEngine {
+    requests: std::sync::Mutex<Vec<(PathBuf, usize, f32)>>,
+}
+
+#[async_trait]
+impl InferenceEngine for RecordingInferenceEngine {
+    async fn infer(&self, request: InferenceRequest) -> Result<String> {
+        self.requests.lock().unwrap().push((
+            request.model_path,
+            request.max_tokens,
+            request.temperature,
+        ));
+        Ok("section text".to_string())
+    }
+}
+
+#[tokio::test]
+async fn backend_fails_when_no_active_model() {
+    let svc = make_service(MockSettingsRepo::new(), PathBuf::from("/nonexistent"), MockInferenceEngine::returns(""));
+    let err = svc.backend().await.err().expect("no backend without an active model");
+    assert!(err.to_string().contains("No local model"), "unexpected error: {err}");
+}
+
+#[tokio::test]
+async fn backend_sections_use_the_requested_length() {
+    use crate::service::AiBackend;
+
+    let dir = tempfile::tempdir().unwrap();
+    let filename = "Llama-3.2-1B-Instruct-Q4_K_M.gguf";
+    std::fs::write(dir.path().join(filename), b"fake").unwrap();
+
+    let repo = MockSettingsRepo::new();
+    repo.set_setting("local_model_active_id", "llama-3.2-1b-instruct-q4").await.unwrap();
+
+    let engine = Arc::new(RecordingInferenceEngine { requests: Default::default() });
+    let svc = make_service(repo, dir.path().to_path_buf(), engine.clone());
+    let backend = svc.backend().await.unwrap();
+
+    assert_eq!(backend.generate_section("Summarize", 200).await.unwrap(), "section text");
+    backend.generate("Insights").await.unwrap();
+
+    let requests = engine.requests.lock().unwrap();
+    assert_eq!(requests[0], (dir.path().join(filename), 200, 0.4));
+    assert_eq!(requests[1].1, 1024);
+}
+
 // ── cancel_download ───────────────────────────────────────────────────────────
 
 #[test]
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::contexts::extension::Operator;
+use crate::contexts::extension::{Operator, RuleCondition};
 
 // ── Enums ─────────────────────────────────────────────────────────────────────
 
@@ -11,6 +11,8 @@ pub enum PatternCategory {
     Content,
     Performance,
     Accessibility,
+    /// "Security & Delivery": response headers, caching and compression.
+    Security,
 }
 
 impl PatternCategory {
@@ -20,6 +22,7 @@ impl PatternCategory {
             Self::Content => "content",
             Self::Performance => "performance",
             Self::Accessibility => "accessibility",
+            Self::Security => "security",
         }
     }
 }
@@ -40,6 +43,7 @@ impl std::str::FromStr for PatternCategory {
             "content" => Ok(Self::Content),
             "performance" => Ok(Self::Performance),
             "accessibility" => Ok(Self::Accessibility),
+            "security" => Ok(Self::Security),
             other => Err(ParsePatternCategoryError(other.to_string())),
         }
     }
@@ -203,11 +207,16 @@ pub struct ReportPattern {
     pub category: PatternCategory,
     pub severity: PatternSeverity,
     /// Page field to evaluate. Built-in fields: `meta_description`, `title`, `word_count`,
-    /// `load_time_ms`, `status_code`, `has_viewport`, `has_structured_data`, `canonical_url`,
-    /// `h1_count`. Custom extractor tags use `tag:<tag>`.
+    /// `load_time_ms`, `ttfb_ms`, `download_ms`, `status_code`, `has_viewport`,
+    /// `has_structured_data`, `canonical_url`, `h1_count`, `headings`, and from the resource pass `total_weight_bytes`, `request_count`,
+    /// `render_blocking_count`, `third_party_share` (percent). Custom extractor tags
+    /// use `tag:<tag>`; response headers use `header:<name>`.
     pub field: String,
     pub operator: Operator,
     pub threshold: Option<String>,
+    /// Compound condition that replaces `field`/`operator`/`threshold` when set.
+    #[serde(default)]
+    pub condition: Option<RuleCondition>,
     /// Minimum fraction of pages (0.0–1.0) that must match before the pattern is "detected".
     pub min_prevalence: f64,
     pub business_impact: BusinessImpact,
@@ -229,6 +238,8 @@ pub struct ReportPatternParams {
     pub field: String,
     pub operator: Operator,
     pub threshold: Option<String>,
+    #[serde(default)]
+    pub condition: Option<RuleCondition>,
     pub min_prevalence: f64,
     pub business_impact: BusinessImpact,
     pub fix_effort: FixEffort,
@@ -236,6 +247,26 @@ pub struct ReportPatternParams {
     pub enabled: bool,
 }
 
+impl From<&ReportPattern> for ReportPatternParams {
+    fn from(pattern: &ReportPattern) -> Self {
+        Self {
+            name: pattern.name.clone(),
+            description: pattern.description.clone(),
+            category: pattern.category.clone(),
+            severity: pattern.severity.clone(),
+            field: pattern.field.clone(),
+            operator: pattern.operator.clone(),
+            threshold: pattern.threshold.clone(),
+            condition: pattern.condition.clone(),
+            min_prevalence: pattern.min_prevalence,
+            business_impact: pattern.business_impact.clone(),
+            fix_effort: pattern.fix_effort.clone(),
+            recommendation: pattern.recommendation.clone(),
+            enabled: pattern.enabled,
+        }
+    }
+}
+
 /// A pattern that fired during analysis of a specific job.
 #[derive(Debug, Clone, Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
@@ -266,7 +297,11 @@ impl DetectedPattern {
 /// [`PillarScores::from_pillars`] and read via the typed accessors.
 /// The `overall` average is derived in the constructor so the
 /// invariant `overall == mean(technical, content, performance,
-/// accessibility)` is enforced once at construction time.
+/// accessibility[, security])` is enforced once at construction time.
+///
+/// `security` is `None` when no page had response headers to judge
+/// (deep-audited jobs, jobs from before headers were captured); the
+/// pillar is then left out of the average rather than scored 100.
 #[derive(Debug, Clone, Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct PillarScores {
@@ -274,17 +309,19 @@ pub struct PillarScores {
     content: f64,
     performance: f64,
     accessibility: f64,
+    security: Option<f64>,
     overall: f64,
 }
 
 impl PillarScores {
-    /// Construct from all five values explicitly. Used by repository
+    /// Construct from all six values explicitly. Used by repository
     /// decoders or when the caller has computed `overall` itself.
     pub fn new(
         technical: f64,
         content: f64,
         performance: f64,
         accessibility: f64,
+        security: Option<f64>,
         overall: f64,
     ) -> Self {
         Self {
@@ -292,27 +329,33 @@ impl PillarScores {
             content,
             performance,
             accessibility,
+            security,
             overall,
         }
     }
 
-    /// Construct from the four pillar scores; the `overall` average is
-    /// computed automatically as the arithmetic mean. This is the
-    /// preferred constructor for new code — pinning the overall
-    /// invariant in one place.
+    /// Construct from the pillar scores; the `overall` average is
+    /// computed automatically as the arithmetic mean of the scored
+    /// pillars. This is the preferred constructor for new code —
+    /// pinning the overall invariant in one place.
     pub fn from_pillars(
         technical: f64,
         content: f64,
         performance: f64,
         accessibility: f64,
+        security: Option<f64>,
     ) -> Self {
-        let overall = (technical + content + performance + accessibility) / 4.0;
+        let scored = [technical, content, performance, accessibility]
+            .into_iter()
+            .chain(security);
+        let (sum, count) = scored.fold((0.0, 0.0), |(sum, count), score| (sum + score, count + 1.0));
         Self {
             technical,
             content,
             performance,
             accessibility,
-            overall,
+            security,
+            overall: sum / count,
         }
     }
 
@@ -328,6 +371,10 @@ impl PillarScores {
     pub fn accessibility(&self) -> f64 {
         self.accessibility
     }
+    /// `None` when the job had nothing to judge the pillar by.
+    pub fn security(&self) -> Option<f64> {
+        self.security
+    }
     pub fn overall(&self) -> f64 {
         self.overall
     }
@@ -379,6 +426,7 @@ mod tests {
             PatternCategory::Content,
             PatternCategory::Performance,
             PatternCategory::Accessibility,
+            PatternCategory::Security,
         ] {
             assert_eq!(PatternCategory::from_str(c.as_str()).unwrap(), c);
         }
@@ -481,6 +529,7 @@ mod tests {
             field: "title".into(),
             operator: Operator::Missing,
             threshold: None,
+            condition: None,
             min_prevalence: 0.0,
             business_impact: impact,
             fix_effort: effort,
@@ -64,7 +64,13 @@ pub fn phase1_diagnosis_prompt(
     let sitemap = if sitemap { "present" } else { "missing" };
     let robots  = if robots  { "present" } else { "missing" };
     let top_list = format_top_issue_list(top_issue_names);
-    let (t, c, p, a) = (pillars.technical(), pillars.content(), pillars.performance(), pillars.accessibility());
+    let (t, c, p, a) = (
+        pillars.technical(),
+        pillars.content(),
+        pillars.performance(),
+        pillars.accessibility(),
+    );
+    let s = pillars.security().map_or_else(|| "N/A".to_string(), |s| format!("{s:.0}"));
     format!(
         "{system_prompt}\n\n\
         {GROUND_RULES}\n\
@@ -76,7 +82,8 @@ pub fn phase1_diagnosis_prompt(
         Warning issues: {warnings}\n\
         Sitemap: {sitemap}\n\
         Robots.txt: {robots}\n\
-        Pillar scores — Technical {t:.0}, Content {c:.0}, Performance {p:.0}, Accessibility {a:.0} (out of 100)\n\
+        Pillar scores — Technical {t:.0}, Content {c:.0}, Performance {p:.0}, Accessibility {a:.0}, \
+        Security & Delivery {s} (out of 100)\n\
         Top detected patterns:\n{top_list}\n\n\
         TASK — DIAGNOSIS (3 to 4 sentences, ~110 words):\n\
         Write a grounded read on this site's SEO health. Open by naming where it stands \
@@ -144,14 +151,20 @@ pub fn phase3_roadmap_prompt(
     weakest: &str,
     top_issue_names: &[String],
 ) -> String {
-    let (t, c, p, a) = (pillars.technical(), pillars.content(), pillars.performance(), pillars.accessibility());
+    let (t, c, p, a) = (
+        pillars.technical(),
+        pillars.content(),
+        pillars.performance(),
+        pillars.accessibility(),
+    );
+    let s = out_of_100(pillars.security());
     let top_list = format_top_issue_list(top_issue_names);
     format!(
         "{system_prompt}\n\n\
         {GROUND_RULES}\n\
         DATA:\n\
         Pillar scores — Technical {t:.0}/100, Content {c:.0}/100, \
-        Performance {p:.0}/100, Accessibility {a:.0}/100\n\
+        Performance {p:.0}/100, Accessibility {a:.0}/100, Security & Delivery {s}\n\
         Weakest pillar: {weakest}\n\
         Top detected patterns to resolve:\n{top_list}\n\n\
         TASK — ROADMAP (4 to 5 sentences, ~130 words):\n\
@@ -211,8 +224,10 @@ pub fn build_static_brief(
     out.push_str("## Pillar Health\n\n");
     out.push_str(&format!(
         "- Technical: {:.0}/100\n- Content: {:.0}/100\n\
-        - Performance: {:.0}/100\n- Accessibility: {:.0}/100\n\n",
+        - Performance: {:.0}/100\n- Accessibility: {:.0}/100\n\
+        - Security & Delivery: {}\n\n",
         pillars.technical(), pillars.content(), pillars.performance(), pillars.accessibility(),
+        out_of_100(pillars.security()),
     ));
 
     let weakest = weakest_pillar(pillars);
@@ -244,16 +259,23 @@ pub fn score_grade(n: i64) -> &'static str {
     else            { "Critical" }
 }
 
-/// Returns the name of the pillar with the lowest score.
+/// A pillar score as `85/100`, or `N/A` for an unscored pillar.
+pub fn out_of_100(score: Option<f64>) -> String {
+    score.map_or_else(|| "N/A".to_string(), |s| format!("{s:.0}/100"))
+}
+
+/// Returns the name of the scored pillar with the lowest score.
 pub fn weakest_pillar(p: &PillarScores) -> &'static str {
     let scores = [
-        ("Technical",     p.technical()),
-        ("Content",       p.content()),
-        ("Performance",   p.performance()),
-        ("Accessibility", p.accessibility()),
+        ("Technical",     Some(p.technical())),
+        ("Content",       Some(p.content())),
+        ("Performance",   Some(p.performance())),
+        ("Accessibility", Some(p.accessibility())),
+        ("Security & Delivery", p.security()),
     ];
     scores
         .iter()
+        .filter_map(|(name, score)| score.map(|score| (*name, score)))
         .min_by(|a, b| a.1.total_cmp(&b.1))
         .map(|(name, _)| *name)
         .unwrap_or("Technical")
@@ -277,8 +299,9 @@ mod tests {
 
     fn make_pillars(t: f64, c: f64, p: f64, a: f64) -> PillarScores {
         // Use from_pillars so the test fixture exercises the same
-        // overall-derivation path as production code.
-        PillarScores::from_pillars(t, c, p, a)
+        // overall-derivation path as production code. Security sits at
+        // 100 unless a test sets it, so it never wins a tie by accident.
+        PillarScores::from_pillars(t, c, p, a, Some(100.0))
     }
 
     fn make_pattern(severity: PatternSeverity, name: &str, recommendation: &str) -> ReportPattern {
@@ -291,6 +314,7 @@ mod tests {
             field: "title".into(),
             operator: Operator::Missing,
             threshold: None,
+            condition: None,
             min_prevalence: 0.0,
             business_impact: BusinessImpact::Medium,
             fix_effort: FixEffort::Medium,
@@ -380,6 +404,21 @@ mod tests {
         assert_eq!(weakest_pillar(&p), "Accessibility");
     }
 
+    #[test]
+    fn weakest_pillar_handles_security_lowest() {
+        let p = PillarScores::from_pillars(80.0, 80.0, 80.0, 80.0, Some(40.0));
+        assert_eq!(weakest_pillar(&p), "Security & Delivery");
+    }
+
+    #[test]
+    fn unscored_security_pillar_is_shown_as_na_and_never_weakest() {
+        let job = make_job(0, 0, 10, true);
+        let pillars = PillarScores::from_pillars(90.0, 90.0, 90.0, 90.0, None);
+        assert_eq!(weakest_pillar(&pillars), "Technical");
+        let brief = build_static_brief(&job, &[], &pillars);
+        assert!(brief.contains("Security & Delivery: N/A"));
+    }
+
     // ── build_static_brief ───────────────────────────────────────────────
 
     #[test]
@@ -406,6 +445,7 @@ mod tests {
         assert!(brief.contains("Content: 70/100"));
         assert!(brief.contains("Performance: 60/100"));
         assert!(brief.contains("Accessibility: 90/100"));
+        assert!(brief.contains("Security & Delivery: 100/100"));
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::contexts::analysis::{CompleteJobResult, Heading, Page};
-use crate::contexts::extension::Operator;
+use crate::contexts::extension::{ConditionError, Operator, RuleCondition};
 use crate::contexts::report::domain::{DetectedPattern, PatternCategory, PillarScores, ReportPattern};
 
 // ── Field value ───────────────────────────────────────────────────────────────
@@ -11,6 +11,10 @@ pub(crate) enum FieldValue {
     Text(String),
     Number(f64),
     Bool(bool),
+    /// The page carries no data for the field at all (headers of a page
+    /// crawled before they were captured). Matches no operator, so such
+    /// pages count as unaffected rather than as missing the value.
+    Unknown,
 }
 
 impl FieldValue {
@@ -46,17 +50,25 @@ impl FieldValue {
 /// only supported prefix going forward.
 pub const TAG_FIELD_PREFIX: &str = "tag:";
 
+/// Prefix marker for fields that read a response header: `header:vary`
+/// resolves to the page's first `Vary` value. Names are case-insensitive.
+pub const HEADER_FIELD_PREFIX: &str = "header:";
+
 /// Resolve a named field from page data.
 ///
 /// Supported built-in fields:
 /// - `meta_description`, `title`, `canonical_url` — `Option<String>` → Null or Text
 /// - `word_count`, `load_time_ms`, `status_code` — `Option<i64>` → Null or Number
+/// - `ttfb_ms`, `download_ms` — Number, Unknown when the fetch wasn't timed
 /// - `has_viewport`, `has_structured_data` — `bool` → Bool
-/// - `h1_count` — derived from pre-computed `h1_counts` map
+/// - `h1_count` — number of H1s, from the [`HeadingIndex`]
+/// - `headings` — the page's outline, one `H2: Text` line per heading;
+///   Null when it has none
 /// - `tag:<extractor_tag>` — value from `page.extracted_data`
+/// - `header:<name>` — response header value, Null when not sent
 pub(crate) fn resolve_field(
     page: &Page,
-    h1_counts: &HashMap<String, usize>,
+    headings: &HeadingIndex,
     field: &str,
 ) -> FieldValue {
     match field {
@@ -66,12 +78,34 @@ pub(crate) fn resolve_field(
         "word_count" => opt_number_field(page.word_count),
         "load_time_ms" => opt_number_field(page.load_time_ms),
         "status_code" => opt_number_field(page.status_code),
+        "ttfb_ms" | "download_ms" => {
+            // The deep auditor and older crawls don't time the fetch.
+            let ms = if field == "ttfb_ms" { page.ttfb_ms } else { page.download_ms };
+            ms.map_or(FieldValue::Unknown, |ms| FieldValue::Number(ms as f64))
+        }
         "has_viewport" => FieldValue::Bool(page.has_viewport),
         "has_structured_data" => FieldValue::Bool(page.has_structured_data),
         "h1_count" => {
-            let count = h1_counts.get(&page.id).copied().unwrap_or(0);
+            let count = headings.h1_counts.get(&page.id).copied().unwrap_or(0);
             FieldValue::Number(count as f64)
         }
+        "headings" => headings
+            .outlines
+            .get(&page.id)
+            .map_or(FieldValue::Null, |outline| FieldValue::Text(outline.clone())),
+        "total_weight_bytes" | "request_count" | "render_blocking_count"
+        | "third_party_share" => {
+            // Only jobs that ran the resource pass have a weight.
+            let Some(weight) = &page.weight else {
+                return FieldValue::Unknown;
+            };
+            FieldValue::Number(match field {
+                "total_weight_bytes" => weight.total_bytes as f64,
+                "request_count" => weight.request_count as f64,
+                "render_blocking_count" => weight.render_blocking.len() as f64,
+                _ => weight.third_party_share() * 100.0,
+            })
+        }
         other if other.starts_with(TAG_FIELD_PREFIX) => {
             let tag = &other[TAG_FIELD_PREFIX.len()..];
             match page.extracted_data.get(tag) {
@@ -87,6 +121,13 @@ pub(crate) fn resolve_field(
                 },
             }
         }
+        other if other.starts_with(HEADER_FIELD_PREFIX) => {
+            if page.response_headers.is_empty() {
+                return FieldValue::Unknown;
+            }
+            let name = &other[HEADER_FIELD_PREFIX.len()..];
+            opt_string_field(page.response_headers.get(name))
+        }
         _ => FieldValue::Null,
     }
 }
@@ -107,69 +148,359 @@ fn opt_number_field(v: Option<i64>) -> FieldValue {
 
 // ── Evaluator ─────────────────────────────────────────────────────────────────
 
+/// A comparison's threshold, parsed for its operator.
+enum Operand {
+    /// `missing` and `present` take none.
+    None,
+    Text(String),
+    Number(f64),
+    Range(f64, f64),
+    List(Vec<String>),
+    Regex(regex::Regex),
+}
+
+impl Operand {
+    fn parse(op: &Operator, threshold: Option<&str>) -> Result<Self, ConditionError> {
+        let required = || threshold.ok_or_else(|| ConditionError::MissingThreshold(op.clone()));
+        match op {
+            Operator::Missing | Operator::Present => Ok(Self::None),
+            // A missing text threshold compares against the empty string.
+            Operator::Eq | Operator::Contains | Operator::NotContains => {
+                Ok(Self::Text(threshold.unwrap_or("").to_string()))
+            }
+            Operator::Lt | Operator::Gt | Operator::LengthLt | Operator::LengthGt => {
+                let raw = required()?;
+                raw.trim().parse().map(Self::Number).map_err(|_| ConditionError::InvalidNumber {
+                    operator: op.clone(),
+                    threshold: raw.to_string(),
+                })
+            }
+            Operator::Between => {
+                let raw = required()?;
+                let bounds = raw.split_once(',').and_then(|(lo, hi)| {
+                    Some((lo.trim().parse::<f64>().ok()?, hi.trim().parse::<f64>().ok()?))
+                });
+                match bounds {
+                    Some((lo, hi)) if lo <= hi => Ok(Self::Range(lo, hi)),
+                    _ => Err(ConditionError::InvalidRange(raw.to_string())),
+                }
+            }
+            Operator::InList => {
+                let items: Vec<String> = threshold
+                    .unwrap_or("")
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                if items.is_empty() {
+                    return Err(ConditionError::EmptyList);
+                }
+                Ok(Self::List(items))
+            }
+            Operator::Matches => {
+                let raw = required()?;
+                regex::Regex::new(raw).map(Self::Regex).map_err(|e| {
+                    ConditionError::InvalidRegex { pattern: raw.to_string(), message: e.to_string() }
+                })
+            }
+        }
+    }
+}
+
+/// Evaluate one comparison of `value` against a raw threshold.
 pub(crate) fn evaluate_condition(value: &FieldValue, op: &Operator, threshold: Option<&str>) -> bool {
-    match op {
-        Operator::Missing => value.is_null(),
-        Operator::Present => !value.is_null(),
-        Operator::Eq => {
-            let t = threshold.unwrap_or("");
-            match value {
-                FieldValue::Null => false,
-                FieldValue::Bool(b) => {
-                    let bstr = if *b { "true" } else { "false" };
-                    bstr == t
+    if matches!(value, FieldValue::Unknown) {
+        return false;
+    }
+    // A missing or malformed threshold previously fell back to 0.0
+    // — turning a typo'd rule (threshold `"1oo"`) silently into
+    // "< 0" / "> 0", which is almost never what the author meant.
+    // Now an unparseable threshold causes the condition to not
+    // match and a warning is logged so the pattern author can fix
+    // the rule. Saved thresholds are validated, so this is left for
+    // comparisons against another field's value.
+    match Operand::parse(op, threshold) {
+        Ok(operand) => compare(value, op, &operand),
+        Err(e) => {
+            if threshold.is_some() {
+                tracing::warn!("pattern_engine: {e}; rule will not match");
+            }
+            false
+        }
+    }
+}
+
+/// Check that `threshold` parses for `op`, as it must before a rule that
+/// compares against it is saved.
+pub(crate) fn validate_threshold(op: &Operator, threshold: Option<&str>) -> Result<(), ConditionError> {
+    Operand::parse(op, threshold).map(drop)
+}
+
+fn compare(value: &FieldValue, op: &Operator, operand: &Operand) -> bool {
+    if matches!(value, FieldValue::Unknown) {
+        return false;
+    }
+    let text = value.as_comparable_string();
+    let length = text.map(|s| s.chars().count() as f64);
+    match (op, operand) {
+        (Operator::Missing, _) => value.is_null(),
+        (Operator::Present, _) => !value.is_null(),
+        (Operator::Eq, Operand::Text(t)) => equals(value, t),
+        (Operator::InList, Operand::List(items)) => items.iter().any(|t| equals(value, t)),
+        (Operator::Lt, Operand::Number(t)) => value.as_number().is_some_and(|n| n < *t),
+        (Operator::Gt, Operand::Number(t)) => value.as_number().is_some_and(|n| n > *t),
+        (Operator::Between, Operand::Range(lo, hi)) => {
+            value.as_number().is_some_and(|n| (*lo..=*hi).contains(&n))
+        }
+        (Operator::LengthLt, Operand::Number(t)) => length.is_some_and(|n| n < *t),
+        (Operator::LengthGt, Operand::Number(t)) => length.is_some_and(|n| n > *t),
+        (Operator::Contains, Operand::Text(t)) => text.is_some_and(|s| s.contains(t.as_str())),
+        (Operator::NotContains, Operand::Text(t)) => text.is_some_and(|s| !s.contains(t.as_str())),
+        (Operator::Matches, Operand::Regex(re)) => text.is_some_and(|s| re.is_match(s)),
+        // `Operand::parse` always pairs an operator with its own operand.
+        _ => false,
+    }
+}
+
+/// `eq` semantics: bools by their `true`/`false` form, numbers numerically,
+/// text exactly.
+fn equals(value: &FieldValue, t: &str) -> bool {
+    match value {
+        FieldValue::Null | FieldValue::Unknown => false,
+        FieldValue::Bool(b) => b.to_string() == t,
+        FieldValue::Number(n) => t.trim().parse::<f64>().is_ok_and(|tn| (n - tn).abs() < f64::EPSILON),
+        FieldValue::Text(s) => s.as_str() == t,
+    }
+}
+
+/// A [`RuleCondition`] with its thresholds parsed and regexes compiled,
+/// ready to run against every page of a job. Compiling is also how
+/// conditions are validated when a check or pattern is saved.
+pub(crate) struct CompiledCondition(Node);
+
+enum Node {
+    Compare { field: String, op: Operator, against: Against },
+    All(Vec<Node>),
+    Any(Vec<Node>),
+    Not(Box<Node>),
+}
+
+enum Against {
+    Threshold(Operand),
+    /// Another field of the same page, read per page and compared with
+    /// the comparison picked when the condition was compiled.
+    Field { field: String, compare: FieldComparison },
+}
+
+/// How a field compares against another field. Values are compared as
+/// they are, never round-tripped through a threshold string.
+#[derive(Clone, Copy)]
+enum FieldComparison {
+    Eq,
+    Lt,
+    Gt,
+    Contains,
+    NotContains,
+}
+
+impl FieldComparison {
+    fn for_operator(op: &Operator) -> Option<Self> {
+        match op {
+            Operator::Eq => Some(Self::Eq),
+            Operator::Lt => Some(Self::Lt),
+            Operator::Gt => Some(Self::Gt),
+            Operator::Contains => Some(Self::Contains),
+            Operator::NotContains => Some(Self::NotContains),
+            _ => None,
+        }
+    }
+
+    /// `other` is never Null or Unknown; the caller settles those.
+    fn apply(self, value: &FieldValue, other: &FieldValue) -> bool {
+        let other_text = match other {
+            FieldValue::Text(s) => s.clone(),
+            FieldValue::Number(n) => n.to_string(),
+            FieldValue::Bool(b) => b.to_string(),
+            FieldValue::Null | FieldValue::Unknown => return false,
+        };
+        let other_number = || match other {
+            FieldValue::Text(s) => s.trim().parse::<f64>().ok(),
+            other => other.as_number(),
+        };
+        match self {
+            Self::Eq => match (value, other) {
+                (FieldValue::Number(a), FieldValue::Number(b)) => (a - b).abs() < f64::EPSILON,
+                _ => equals(value, &other_text),
+            },
+            Self::Lt => value.as_number().zip(other_number()).is_some_and(|(a, b)| a < b),
+            Self::Gt => value.as_number().zip(other_number()).is_some_and(|(a, b)| a > b),
+            Self::Contains => value.as_comparable_string().is_some_and(|s| s.contains(&other_text)),
+            Self::NotContains => {
+                value.as_comparable_string().is_some_and(|s| !s.contains(&other_text))
+            }
+        }
+    }
+}
+
+impl CompiledCondition {
+    /// Compile a rule: its compound `condition` when it has one,
+    /// otherwise its single `field`/`operator`/`threshold` comparison.
+    pub(crate) fn for_rule(
+        field: &str,
+        operator: &Operator,
+        threshold: Option<&str>,
+        condition: Option<&RuleCondition>,
+    ) -> Result<Self, ConditionError> {
+        let node = match condition {
+            Some(condition) => Node::compile(condition)?,
+            None => Node::compile(&RuleCondition::compare(field, operator, threshold))?,
+        };
+        Ok(Self(node))
+    }
+
+    pub(crate) fn matches(&self, page: &Page, headings: &HeadingIndex) -> bool {
+        self.0.eval(page, headings) == Some(true)
+    }
+}
+
+impl Node {
+    fn compile(condition: &RuleCondition) -> Result<Self, ConditionError> {
+        match condition {
+            RuleCondition::Compare { field, operator, threshold, compare_field } => {
+                if field.trim().is_empty() {
+                    return Err(ConditionError::MissingField);
                 }
-                FieldValue::Number(n) => {
-                    if let Ok(tn) = t.parse::<f64>() {
-                        (n - tn).abs() < f64::EPSILON
-                    } else {
-                        false
+                let against = match compare_field {
+                    Some(other) => {
+                        let compare = FieldComparison::for_operator(operator).ok_or_else(|| {
+                            ConditionError::UnsupportedFieldComparison(operator.clone())
+                        })?;
+                        if other.trim().is_empty() {
+                            return Err(ConditionError::MissingField);
+                        }
+                        Against::Field { field: other.clone(), compare }
                     }
-                }
-                FieldValue::Text(s) => s.as_str() == t,
+                    None => Against::Threshold(Operand::parse(operator, threshold.as_deref())?),
+                };
+                Ok(Self::Compare { field: field.clone(), op: operator.clone(), against })
             }
+            RuleCondition::All { children } => Ok(Self::All(Self::compile_group("all", children)?)),
+            RuleCondition::Any { children } => Ok(Self::Any(Self::compile_group("any", children)?)),
+            RuleCondition::Not { inner } => Ok(Self::Not(Box::new(Self::compile(inner)?))),
         }
-        // A missing or malformed threshold previously fell back to 0.0
-        // — turning a typo'd rule (threshold `"1oo"`) silently into
-        // "< 0" / "> 0", which is almost never what the author meant.
-        // Now an unparseable threshold causes the condition to not
-        // match (same as Eq) and a warning is logged so the pattern
-        // author can fix the rule.
-        Operator::Lt | Operator::Gt => {
-            let Some(t) = threshold.and_then(|s| s.parse::<f64>().ok()) else {
-                if let Some(raw) = threshold {
-                    tracing::warn!(
-                        "pattern_engine: unparseable {op:?} threshold '{raw}'; rule will not match",
-                        op = op
-                    );
+    }
+
+    fn compile_group(kind: &'static str, children: &[RuleCondition]) -> Result<Vec<Self>, ConditionError> {
+        if children.is_empty() {
+            return Err(ConditionError::EmptyGroup(kind));
+        }
+        children.iter().map(Self::compile).collect()
+    }
+
+    /// `None` when the outcome hinges on a field the page has no data
+    /// for, so `not` over an unknown header doesn't match either.
+    fn eval(&self, page: &Page, headings: &HeadingIndex) -> Option<bool> {
+        match self {
+            Self::Compare { field, op, against } => {
+                let value = resolve_field(page, headings, field);
+                if matches!(value, FieldValue::Unknown) {
+                    return None;
+                }
+                match against {
+                    Against::Threshold(operand) => Some(compare(&value, op, operand)),
+                    Against::Field { field: other, compare } => {
+                        match resolve_field(page, headings, other) {
+                            FieldValue::Unknown => None,
+                            FieldValue::Null => Some(false),
+                            other => Some(compare.apply(&value, &other)),
+                        }
+                    }
                 }
-                return false;
-            };
-            let Some(n) = value.as_number() else { return false };
-            match op {
-                Operator::Lt => n < t,
-                Operator::Gt => n > t,
-                _ => unreachable!("outer match arm guarantees Lt|Gt"),
             }
+            Self::All(nodes) => {
+                let mut unknown = false;
+                for node in nodes {
+                    match node.eval(page, headings) {
+                        Some(false) => return Some(false),
+                        None => unknown = true,
+                        Some(true) => {}
+                    }
+                }
+                (!unknown).then_some(true)
+            }
+            Self::Any(nodes) => {
+                let mut unknown = false;
+                for node in nodes {
+                    match node.eval(page, headings) {
+                        Some(true) => return Some(true),
+                        None => unknown = true,
+                        Some(false) => {}
+                    }
+                }
+                (!unknown).then_some(false)
+            }
+            Self::Not(node) => node.eval(page, headings).map(|matched| !matched),
         }
-        Operator::Contains => {
-            let t = threshold.unwrap_or("");
-            value.as_comparable_string().map(|s| s.contains(t)).unwrap_or(false)
+    }
+}
+
+// ── Heading index ─────────────────────────────────────────────────────────────
+
+/// Per-page heading data read by the `h1_count` and `headings` fields,
+/// keyed by page id. Empty for pages without headings.
+#[derive(Debug, Default)]
+pub(crate) struct HeadingIndex {
+    h1_counts: HashMap<String, usize>,
+    outlines: HashMap<String, String>,
+}
+
+impl HeadingIndex {
+    /// Index the stored headings of every page in a job.
+    pub(crate) fn from_headings(headings: &[Heading]) -> Self {
+        let mut ordered: Vec<&Heading> = headings.iter().collect();
+        ordered.sort_by_key(|h| h.position);
+
+        let mut outlines: HashMap<String, String> = HashMap::new();
+        for h in ordered {
+            push_outline_line(outlines.entry(h.page_id.clone()).or_default(), h.level, &h.text);
         }
-        Operator::NotContains => {
-            let t = threshold.unwrap_or("");
-            value.as_comparable_string().map(|s| !s.contains(t)).unwrap_or(false)
+        Self { h1_counts: build_h1_counts(headings), outlines }
+    }
+
+    /// Index one page's headings, in document order, as extracted during
+    /// analysis.
+    pub(crate) fn for_page<'a>(
+        page_id: &str,
+        headings: impl IntoIterator<Item = (i64, &'a str)>,
+    ) -> Self {
+        let mut index = Self::default();
+        for (level, text) in headings {
+            if level == 1 && !text.is_empty() {
+                *index.h1_counts.entry(page_id.to_string()).or_insert(0) += 1;
+            }
+            push_outline_line(index.outlines.entry(page_id.to_string()).or_default(), level, text);
         }
+        index
     }
 }
 
+fn push_outline_line(outline: &mut String, level: i64, text: &str) {
+    if !outline.is_empty() {
+        outline.push('\n');
+    }
+    outline.push_str(&format!("H{level}: {text}"));
+}
+
 // ── Pattern engine ────────────────────────────────────────────────────────────
 
 /// Pre-compute H1 heading counts per page from the full headings list.
+/// H1s per page. Empty ones are stored for the outline but name no topic,
+/// so they aren't counted.
 fn build_h1_counts(headings: &[Heading]) -> HashMap<String, usize> {
     let mut map: HashMap<String, usize> = HashMap::new();
     for h in headings {
-        if h.level == 1 {
+        if h.level == 1 && !h.text.is_empty() {
             *map.entry(h.page_id.clone()).or_insert(0) += 1;
         }
     }
@@ -189,20 +520,28 @@ pub fn evaluate_all(
         return vec![];
     }
 
-    let h1_counts = build_h1_counts(&result.headings);
+    let headings = HeadingIndex::from_headings(&result.headings);
 
     let mut detected: Vec<DetectedPattern> = patterns
         .iter()
         .filter_map(|pattern| {
-            let threshold = pattern.threshold.as_deref();
-            let mut affected_urls: Vec<String> = Vec::new();
-
-            for page in pages {
-                let value = resolve_field(page, &h1_counts, &pattern.field);
-                if evaluate_condition(&value, &pattern.operator, threshold) {
-                    affected_urls.push(page.url.clone());
+            let condition = match CompiledCondition::for_rule(
+                &pattern.field,
+                &pattern.operator,
+                pattern.threshold.as_deref(),
+                pattern.condition.as_ref(),
+            ) {
+                Ok(condition) => condition,
+                Err(e) => {
+                    tracing::warn!("pattern_engine: skipping pattern '{}': {e}", pattern.name);
+                    return None;
                 }
-            }
+            };
+            let affected_urls: Vec<String> = pages
+                .iter()
+                .filter(|page| condition.matches(page, &headings))
+                .map(|page| page.url.clone())
+                .collect();
 
             let affected = affected_urls.len();
             let prevalence = affected as f64 / total as f64;
@@ -236,11 +575,14 @@ pub fn evaluate_all(
 
 /// Compute pillar health scores (0–100) from the set of detected patterns.
 /// Each detected pattern deducts `severity_weight × prevalence × 15` from its pillar.
-pub fn compute_pillar_scores(detected: &[DetectedPattern]) -> PillarScores {
+/// `headers_captured` says whether any page had response headers; without
+/// them the Security & Delivery pillar is unscored rather than a free 100.
+pub fn compute_pillar_scores(detected: &[DetectedPattern], headers_captured: bool) -> PillarScores {
     let mut technical: f64 = 100.0;
     let mut content: f64 = 100.0;
     let mut performance: f64 = 100.0;
     let mut accessibility: f64 = 100.0;
+    let mut security: f64 = 100.0;
 
     for d in detected {
         let deduction = d.pattern.severity.weight() * d.prevalence * 15.0;
@@ -249,6 +591,7 @@ pub fn compute_pillar_scores(detected: &[DetectedPattern]) -> PillarScores {
             PatternCategory::Content => content -= deduction,
             PatternCategory::Performance => performance -= deduction,
             PatternCategory::Accessibility => accessibility -= deduction,
+            PatternCategory::Security => security -= deduction,
         }
     }
 
@@ -257,9 +600,9 @@ pub fn compute_pillar_scores(detected: &[DetectedPattern]) -> PillarScores {
     let content       = clamp(content);
     let performance   = clamp(performance);
     let accessibility = clamp(accessibility);
-    let overall = (technical + content + performance + accessibility) / 4.0;
+    let security      = headers_captured.then(|| clamp(security));
 
-    PillarScores::new(technical, content, performance, accessibility, overall)
+    PillarScores::from_pillars(technical, content, performance, accessibility, security)
 }
 
 #[cfg(test)]
@@ -294,6 +637,7 @@ mod tests {
             field: field.to_string(),
             operator,
             threshold: threshold.map(String::from),
+            condition: None,
             min_prevalence,
             business_impact: BusinessImpact::Medium,
             fix_effort: FixEffort::Medium,
@@ -317,11 +661,15 @@ mod tests {
             robots_meta: None,
             word_count,
             load_time_ms: Some(500),
+            ttfb_ms: None,
+            download_ms: None,
             response_size_bytes: None,
             has_viewport: true,
             has_structured_data: false,
             crawled_at: Utc::now(),
             extracted_data: std::collections::HashMap::new(),
+            response_headers: Default::default(),
+            weight: None,
         }
     }
 
@@ -349,6 +697,8 @@ mod tests {
             headings,
             images: Vec::new(),
             ai_insights: None,
+            tls: None,
+            site_checks: Vec::new(),
             extracted_data: std::collections::HashMap::new(),
         }
     }
@@ -485,6 +835,186 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn evaluate_extended_operators() {
+        let title = FieldValue::Text("Blue Widgets".into());
+        assert!(evaluate_condition(&title, &Operator::Matches, Some("^Blue\\s")));
+        assert!(!evaluate_condition(&title, &Operator::Matches, Some("^Red")));
+        assert!(evaluate_condition(&title, &Operator::LengthLt, Some("13")));
+        assert!(evaluate_condition(&title, &Operator::LengthGt, Some("11")));
+        assert!(evaluate_condition(&title, &Operator::InList, Some("Red Widgets, Blue Widgets")));
+
+        let words = FieldValue::Number(300.0);
+        assert!(evaluate_condition(&words, &Operator::Between, Some("300, 500")));
+        assert!(!evaluate_condition(&words, &Operator::Between, Some("301,500")));
+        assert!(evaluate_condition(&words, &Operator::InList, Some("200,300")));
+        assert!(!evaluate_condition(&words, &Operator::LengthGt, Some("1")), "length is for text");
+    }
+
+    #[test]
+    fn invalid_thresholds_fail_to_compile() {
+        let compile = |op: Operator, threshold: Option<&str>| {
+            CompiledCondition::for_rule("title", &op, threshold, None).err()
+        };
+        assert!(matches!(compile(Operator::Matches, Some("(")), Some(ConditionError::InvalidRegex { .. })));
+        assert!(matches!(compile(Operator::Lt, Some("1oo")), Some(ConditionError::InvalidNumber { .. })));
+        assert!(matches!(compile(Operator::Between, Some("9,1")), Some(ConditionError::InvalidRange(_))));
+        assert_eq!(compile(Operator::InList, Some(" , ")), Some(ConditionError::EmptyList));
+        assert_eq!(compile(Operator::Gt, None), Some(ConditionError::MissingThreshold(Operator::Gt)));
+        assert!(compile(Operator::Missing, None).is_none());
+
+        let empty = RuleCondition::Any { children: vec![] };
+        assert_eq!(
+            CompiledCondition::for_rule("title", &Operator::Missing, None, Some(&empty)).err(),
+            Some(ConditionError::EmptyGroup("any")),
+        );
+        let cross = RuleCondition::Compare {
+            field: "title".into(),
+            operator: Operator::Matches,
+            threshold: None,
+            compare_field: Some("tag:og_title".into()),
+        };
+        assert_eq!(
+            CompiledCondition::for_rule("title", &Operator::Missing, None, Some(&cross)).err(),
+            Some(ConditionError::UnsupportedFieldComparison(Operator::Matches)),
+        );
+    }
+
+    #[test]
+    fn condition_trees_combine_comparisons() {
+        let mut page = make_page("p1", "https://a.test/", Some("Acme Widgets"), Some(120));
+        page.extracted_data.insert("og_title".into(), serde_json::json!("Acme Widgets"));
+        let headings = HeadingIndex::default();
+        let matches = |condition: RuleCondition| {
+            CompiledCondition::for_rule("title", &Operator::Missing, None, Some(&condition))
+                .unwrap()
+                .matches(&page, &headings)
+        };
+
+        let brand = RuleCondition::compare("title", &Operator::Contains, Some("Acme"));
+        let thin = RuleCondition::compare("word_count", &Operator::Lt, Some("300"));
+        let long = RuleCondition::compare("word_count", &Operator::Gt, Some("1000"));
+        assert!(matches(RuleCondition::All { children: vec![brand.clone(), thin.clone()] }));
+        assert!(!matches(RuleCondition::All { children: vec![brand.clone(), long.clone()] }));
+        assert!(matches(RuleCondition::Any { children: vec![long.clone(), thin] }));
+        assert!(matches(RuleCondition::Not { inner: Box::new(long) }));
+        assert!(matches(RuleCondition::Compare {
+            field: "title".into(),
+            operator: Operator::Eq,
+            threshold: None,
+            compare_field: Some("tag:og_title".into()),
+        }));
+    }
+
+    #[test]
+    fn field_comparisons_use_the_other_fields_value_as_is() {
+        let mut page = make_page("p1", "https://a.test/", Some("Acme Widgets"), Some(120));
+        page.extracted_data.insert("min_words".into(), serde_json::json!(300));
+        page.extracted_data.insert("brand".into(), serde_json::json!("Acme"));
+        page.extracted_data.insert("label".into(), serde_json::json!("not a number"));
+        let headings = HeadingIndex::default();
+        let matches = |field: &str, operator: Operator, other: &str| {
+            let condition = RuleCondition::Compare {
+                field: field.into(),
+                operator,
+                threshold: None,
+                compare_field: Some(other.into()),
+            };
+            CompiledCondition::for_rule("title", &Operator::Missing, None, Some(&condition))
+                .unwrap()
+                .matches(&page, &headings)
+        };
+
+        assert!(matches("word_count", Operator::Lt, "tag:min_words"));
+        assert!(!matches("word_count", Operator::Gt, "tag:min_words"));
+        assert!(matches("title", Operator::Contains, "tag:brand"));
+        assert!(!matches("title", Operator::NotContains, "tag:brand"));
+        // Text that isn't a number just doesn't compare numerically.
+        assert!(!matches("word_count", Operator::Lt, "tag:label"));
+        assert!(!matches("word_count", Operator::Lt, "tag:absent"));
+    }
+
+    #[test]
+    fn negating_an_unknown_field_matches_nothing() {
+        // No headers were captured, so the header's value is unknown
+        // rather than absent.
+        let page = make_page("p1", "https://a.test/", None, None);
+        let condition = RuleCondition::Not {
+            inner: Box::new(RuleCondition::compare("header:server", &Operator::Contains, Some("nginx"))),
+        };
+        let compiled = CompiledCondition::for_rule("title", &Operator::Missing, None, Some(&condition)).unwrap();
+        assert!(!compiled.matches(&page, &HeadingIndex::default()));
+    }
+
+    // ── header fields ────────────────────────────────────────────────────
+
+    #[test]
+    fn header_field_reads_response_header_case_insensitively() {
+        let mut page = make_page("p1", "https://a.test/", None, None);
+        page.response_headers = crate::contexts::analysis::ResponseHeaders::new([
+            ("Server", "nginx/1.25.3"),
+        ]);
+        let h1 = HeadingIndex::default();
+
+        let server = resolve_field(&page, &h1, "header:Server");
+        assert!(evaluate_condition(&server, &Operator::Contains, Some("/")));
+        let hsts = resolve_field(&page, &h1, "header:strict-transport-security");
+        assert!(evaluate_condition(&hsts, &Operator::Missing, None));
+    }
+
+    #[test]
+    fn header_field_matches_nothing_on_pages_without_headers() {
+        let page = make_page("p1", "https://a.test/", None, None);
+        let value = resolve_field(&page, &HeadingIndex::default(), "header:strict-transport-security");
+
+        assert!(!evaluate_condition(&value, &Operator::Missing, None));
+        assert!(!evaluate_condition(&value, &Operator::Present, None));
+    }
+
+    // ── page weight fields ───────────────────────────────────────────────
+
+    #[test]
+    fn weight_fields_read_the_resource_pass() {
+        let mut page = make_page("p1", "https://a.test/", None, None);
+        page.weight = Some(crate::contexts::analysis::PageWeight {
+            total_bytes: 3_000_000,
+            request_count: 40,
+            third_party_requests: 10,
+            render_blocking: vec!["https://a.test/app.js".into()],
+            ..Default::default()
+        });
+        let h1 = HeadingIndex::default();
+
+        let bytes = resolve_field(&page, &h1, "total_weight_bytes");
+        assert!(evaluate_condition(&bytes, &Operator::Gt, Some("2097152")));
+        let share = resolve_field(&page, &h1, "third_party_share");
+        assert!(evaluate_condition(&share, &Operator::Eq, Some("25")));
+        let blocking = resolve_field(&page, &h1, "render_blocking_count");
+        assert!(evaluate_condition(&blocking, &Operator::Gt, Some("0")));
+    }
+
+    #[test]
+    fn weight_fields_match_nothing_without_a_resource_pass() {
+        let page = make_page("p1", "https://a.test/", None, None);
+        let value = resolve_field(&page, &HeadingIndex::default(), "request_count");
+
+        assert!(!evaluate_condition(&value, &Operator::Gt, Some("0")));
+        assert!(!evaluate_condition(&value, &Operator::Missing, None));
+    }
+
+    // ── fetch timing fields ──────────────────────────────────────────────
+
+    #[test]
+    fn ttfb_field_reads_the_fetch_timing() {
+        let mut page = make_page("p1", "https://a.test/", None, None);
+        page.ttfb_ms = Some(1200);
+        let value = resolve_field(&page, &HeadingIndex::default(), "ttfb_ms");
+        assert!(evaluate_condition(&value, &Operator::Gt, Some("800")));
+
+        let untimed = resolve_field(&page, &HeadingIndex::default(), "download_ms");
+        assert!(!evaluate_condition(&untimed, &Operator::Missing, None));
+    }
+
     // ── build_h1_counts ──────────────────────────────────────────────────
 
     #[test]
@@ -524,6 +1054,43 @@ mod tests {
         assert_eq!(counts.get("p2").copied(), Some(1));
     }
 
+    #[test]
+    fn heading_fields_read_the_page_outline() {
+        let heading = |id, page_id: &str, level, text: &str, position| Heading {
+            id,
+            page_id: page_id.into(),
+            level,
+            text: text.into(),
+            position,
+        };
+        let index = HeadingIndex::from_headings(&[
+            heading(2, "p1", 2, "Pricing", 1),
+            heading(1, "p1", 1, "Widgets", 0),
+            heading(3, "p2", 2, "Orphan", 0),
+        ]);
+        let p1 = make_page("p1", "https://a.test/", None, None);
+        let p3 = make_page("p3", "https://a.test/3", None, None);
+
+        let outline = resolve_field(&p1, &index, "headings");
+        assert!(matches!(&outline, FieldValue::Text(t) if t == "H1: Widgets\nH2: Pricing"));
+        assert!(evaluate_condition(&outline, &Operator::Contains, Some("H2: Pricing")));
+        assert!(evaluate_condition(&resolve_field(&p1, &index, "h1_count"), &Operator::Eq, Some("1")));
+        assert!(evaluate_condition(&resolve_field(&p3, &index, "headings"), &Operator::Missing, None));
+    }
+
+    #[test]
+    fn build_h1_counts_skips_empty_h1s() {
+        let headings = vec![Heading {
+            id: 1,
+            page_id: "p1".into(),
+            level: 1,
+            text: String::new(),
+            position: 0,
+        }];
+        assert!(build_h1_counts(&headings).is_empty());
+        assert!(HeadingIndex::for_page("p1", [(1, "")]).h1_counts.is_empty());
+    }
+
     #[test]
     fn build_h1_counts_returns_empty_for_no_h1s() {
         let headings = vec![Heading {
@@ -578,6 +1145,33 @@ mod tests {
         assert_eq!(detected[0].prevalence, 0.5);
     }
 
+    #[test]
+    fn evaluate_all_uses_the_compound_condition_and_skips_invalid_patterns() {
+        let pages = vec![
+            make_page("p1", "https://a.test/1", Some("Acme"), Some(100)),
+            make_page("p2", "https://a.test/2", Some("Acme"), Some(900)),
+            make_page("p3", "https://a.test/3", Some("Other"), Some(100)),
+        ];
+        let result = make_complete_result(pages, vec![]);
+
+        let mut compound = make_pattern(
+            PatternCategory::Content, PatternSeverity::Warning, "title", Operator::Missing, None, 0.0,
+        );
+        compound.condition = Some(RuleCondition::All {
+            children: vec![
+                RuleCondition::compare("title", &Operator::Eq, Some("Acme")),
+                RuleCondition::compare("word_count", &Operator::Lt, Some("300")),
+            ],
+        });
+        let invalid = make_pattern(
+            PatternCategory::Content, PatternSeverity::Warning, "title", Operator::Matches, Some("("), 0.0,
+        );
+
+        let detected = evaluate_all(&[compound, invalid], &result);
+        assert_eq!(detected.len(), 1);
+        assert_eq!(detected[0].sample_urls, vec!["https://a.test/1".to_string()]);
+    }
+
     #[test]
     fn evaluate_all_filters_below_min_prevalence() {
         let pattern = make_pattern(
@@ -674,14 +1268,28 @@ mod tests {
 
     #[test]
     fn pillar_scores_default_to_100_with_no_detected_patterns() {
-        let scores = compute_pillar_scores(&[]);
+        let scores = compute_pillar_scores(&[], true);
         assert_eq!(scores.technical(), 100.0);
         assert_eq!(scores.content(), 100.0);
         assert_eq!(scores.performance(), 100.0);
         assert_eq!(scores.accessibility(), 100.0);
+        assert_eq!(scores.security(), Some(100.0));
         assert_eq!(scores.overall(), 100.0);
     }
 
+    #[test]
+    fn security_pillar_is_unscored_without_captured_headers() {
+        let detected = vec![detected_with(
+            PatternCategory::Technical,
+            PatternSeverity::Warning,
+            1.0,
+        )];
+        // Technical 70, the other three 100; security left out of the mean.
+        let scores = compute_pillar_scores(&detected, false);
+        assert_eq!(scores.security(), None);
+        assert_eq!(scores.overall(), 92.5);
+    }
+
     #[test]
     fn pillar_scores_deduct_per_pattern_using_severity_weight() {
         // Critical(3) × prevalence(0.5) × 15 = 22.5 deduction.
@@ -690,7 +1298,7 @@ mod tests {
             PatternSeverity::Critical,
             0.5,
         )];
-        let scores = compute_pillar_scores(&detected);
+        let scores = compute_pillar_scores(&detected, true);
         assert_eq!(scores.technical(), 77.5);
         // Other pillars unaffected.
         assert_eq!(scores.content(), 100.0);
@@ -705,13 +1313,15 @@ mod tests {
             detected_with(PatternCategory::Content, PatternSeverity::Warning, 0.5),
             detected_with(PatternCategory::Performance, PatternSeverity::Warning, 0.5),
             detected_with(PatternCategory::Accessibility, PatternSeverity::Warning, 0.5),
+            detected_with(PatternCategory::Security, PatternSeverity::Warning, 0.5),
         ];
         // Warning(2) × 0.5 × 15 = 15 deduction per pillar
-        let scores = compute_pillar_scores(&detected);
+        let scores = compute_pillar_scores(&detected, true);
         assert_eq!(scores.technical(), 85.0);
         assert_eq!(scores.content(), 85.0);
         assert_eq!(scores.performance(), 85.0);
         assert_eq!(scores.accessibility(), 85.0);
+        assert_eq!(scores.security(), Some(85.0));
         assert_eq!(scores.overall(), 85.0);
     }
 
@@ -725,13 +1335,13 @@ mod tests {
             detected_with(PatternCategory::Technical, PatternSeverity::Critical, 1.0),
             detected_with(PatternCategory::Technical, PatternSeverity::Critical, 1.0),
         ];
-        let scores = compute_pillar_scores(&detected);
+        let scores = compute_pillar_scores(&detected, true);
         assert_eq!(scores.technical(), 0.0);
         assert!(scores.technical() >= 0.0);
     }
 
     #[test]
-    fn pillar_scores_overall_is_simple_average_of_four() {
+    fn pillar_scores_overall_is_simple_average_of_five() {
         let detected = vec![detected_with(
             PatternCategory::Technical,
             PatternSeverity::Warning,
@@ -739,10 +1349,10 @@ mod tests {
         )];
         // Technical: 100 - (2 × 1 × 15) = 70
         // Others: 100 each
-        // Overall: (70 + 100 + 100 + 100) / 4 = 92.5
-        let scores = compute_pillar_scores(&detected);
+        // Overall: (70 + 100 + 100 + 100 + 100) / 5 = 94
+        let scores = compute_pillar_scores(&detected, true);
         assert_eq!(scores.technical(), 70.0);
-        assert_eq!(scores.overall(), 92.5);
+        assert_eq!(scores.overall(), 94.0);
     }
 
     // Suppress unused-import warning when the test module is the only
@@ -1,8 +1,9 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 
-use crate::contexts::local_model::LocalModelService;
+use crate::contexts::ai::AiService;
 use crate::contexts::report::domain::ReportData;
 use crate::contexts::report::template::{
     render_template, RenderContext, RenderedFragment,
@@ -10,8 +11,8 @@ use crate::contexts::report::template::{
 use crate::repository::{
     ReportPatternRepository, ReportTemplateRepository, ResultsRepository, SettingsRepository,
 };
-use crate::service::local_model::InferenceRequest;
 use crate::service::prompt::load_persona;
+use crate::service::AiBackend;
 
 use super::{brief_builder, pattern_engine};
 
@@ -20,10 +21,15 @@ pub struct ReportService {
     results_repo:  Arc<dyn ResultsRepository>,
     settings_repo: Arc<dyn SettingsRepository>,
     template_repo: Arc<dyn ReportTemplateRepository>,
-    /// Local model for AI-generated narrative.  `None` when no model is active.
-    local_model:   Option<Arc<LocalModelService>>,
+    /// Picks the backend for AI-generated narrative.  `None` leaves the
+    /// report without AI sections.
+    ai:            Option<Arc<AiService>>,
 }
 
+/// How long one AI section may take before the report moves on without
+/// it. Generous enough for a local model on a laptop CPU.
+const AI_SECTION_TIMEOUT: Duration = Duration::from_secs(180);
+
 impl ReportService {
     pub fn new(
         pattern_repo:  Arc<dyn ReportPatternRepository>,
@@ -31,17 +37,18 @@ impl ReportService {
         settings_repo: Arc<dyn SettingsRepository>,
         template_repo: Arc<dyn ReportTemplateRepository>,
     ) -> Self {
-        Self { pattern_repo, results_repo, settings_repo, template_repo, local_model: None }
+        Self { pattern_repo, results_repo, settings_repo, template_repo, ai: None }
     }
 
-    pub fn with_local_model(
+    /// Expand AI sections through whichever backend is selected in `ai`.
+    pub fn with_ai(
         pattern_repo:  Arc<dyn ReportPatternRepository>,
         results_repo:  Arc<dyn ResultsRepository>,
         settings_repo: Arc<dyn SettingsRepository>,
         template_repo: Arc<dyn ReportTemplateRepository>,
-        local_model:   Arc<LocalModelService>,
+        ai:            Arc<AiService>,
     ) -> Self {
-        Self { pattern_repo, results_repo, settings_repo, template_repo, local_model: Some(local_model) }
+        Self { pattern_repo, results_repo, settings_repo, template_repo, ai: Some(ai) }
     }
 
     /// Generate a full report for the given job.
@@ -64,7 +71,8 @@ impl ReportService {
         let (result, patterns) = tokio::try_join!(result_fut, patterns_fut)?;
 
         let detected      = pattern_engine::evaluate_all(&patterns, &result);
-        let pillar_scores = pattern_engine::compute_pillar_scores(&detected);
+        let headers_captured = result.pages.iter().any(|page| !page.response_headers.is_empty());
+        let pillar_scores = pattern_engine::compute_pillar_scores(&detected, headers_captured);
 
         // Use average lighthouse SEO score — identical to what the app UI displays.
         // Falls back to pillar_scores.overall when lighthouse data is absent.
@@ -110,7 +118,7 @@ impl ReportService {
         seo_score: i64,
     ) -> String {
         // Try the template engine first. If an active template exists,
-        // render it — expanding AI sections via the local model. This
+        // render it — expanding AI sections via the AI backend. This
         // is the unified path that replaces the hardcoded phase1/2/3.
         if let Ok(Some(template)) = self.template_repo.get_active_template().await {
             match self.render_from_template(&template, result, detected, pillars, seo_score).await {
@@ -129,8 +137,8 @@ impl ReportService {
 
     /// Render a report from a user-authored template. Text, Heading,
     /// PatternSummary, Conditional, and Divider sections are resolved
-    /// synchronously. AiPrompt sections are expanded through the local
-    /// model (or left as placeholders when no model is active).
+    /// synchronously. AiPrompt sections are expanded through the selected
+    /// AI backend, or left out when none is available.
     async fn render_from_template(
         &self,
         template:  &crate::contexts::report::ReportTemplate,
@@ -243,13 +251,14 @@ impl ReportService {
             avg_load_time_ms,
             total_words,
             tag_values,
+            site_checks: &result.site_checks,
         };
 
         let fragments = render_template(template, &ctx)
             .map_err(|e| anyhow::anyhow!("template render: {e}"))?;
 
-        // Resolve the model path once — shared across all AI sections.
-        let model_path = self.resolve_model_path().await;
+        // Resolve the backend once — shared across all AI sections.
+        let backend = self.ai_backend().await;
 
         // Load persona once — prepended to every AI prompt.
         let persona = load_persona(self.settings_repo.as_ref())
@@ -262,6 +271,11 @@ impl ReportService {
             match fragment {
                 RenderedFragment::Text(s) => out.push_str(s),
                 RenderedFragment::AiPrompt { label, prompt } => {
+                    let Some(backend) = &backend else {
+                        // No AI backend — skip AI sections silently.
+                        // The static template sections still render.
+                        continue;
+                    };
                     tracing::info!(
                         "[Report] Expanding AI section {}/{}: {}",
                         i + 1,
@@ -269,30 +283,21 @@ impl ReportService {
                         label
                     );
                     let full_prompt = format!("{persona}\n\n{prompt}");
-                    match &model_path {
-                        Some(path) => {
-                            match self.infer(path, full_prompt, 280).await {
-                                Ok(text) if !text.trim().is_empty() => {
-                                    out.push_str(text.trim());
-                                    out.push_str("\n\n");
-                                }
-                                Ok(_) => {
-                                    tracing::debug!(
-                                        "[Report] AI section '{label}' returned empty"
-                                    );
-                                }
-                                Err(e) => {
-                                    tracing::warn!(
-                                        "[Report] AI section '{label}' failed: {e}"
-                                    );
-                                }
-                            }
-                        }
-                        None => {
-                            // No local model — skip AI sections silently.
-                            // The static template sections still render.
-                        }
+                    let text = generate_section(
+                        backend.as_ref(),
+                        label,
+                        &full_prompt,
+                        280,
+                        AI_SECTION_TIMEOUT,
+                    )
+                    .await;
+                    match text {
+                        Some(text) => out.push_str(&text),
+                        None => out.push_str(&format!(
+                            "_The \"{label}\" section could not be generated for this report._"
+                        )),
                     }
+                    out.push_str("\n\n");
                 }
             }
         }
@@ -300,15 +305,22 @@ impl ReportService {
         Ok(out)
     }
 
-    /// Resolve the active local model's file path. Returns `None` if
-    /// no model is configured/downloaded — callers should skip AI
-    /// sections in that case.
-    async fn resolve_model_path(&self) -> Option<std::path::PathBuf> {
-        let lm = self.local_model.as_ref()?;
-        let model_id = lm.get_active_model_id().await.ok()??;
-        let entry = crate::contexts::local_model::domain::ModelEntry::find_by_id(&model_id)?;
-        let path = lm.models_dir().join(&entry.filename);
-        if path.exists() { Some(path) } else { None }
+    /// The selected AI backend. Returns `None` when there's no AI service
+    /// or the backend isn't set up — callers should skip AI sections in
+    /// that case.
+    async fn ai_backend(&self) -> Option<Box<dyn AiBackend>> {
+        let ai = self.ai.as_ref()?;
+        match ai.backend().await {
+            Ok(backend) if backend.is_configured() => Some(backend),
+            Ok(backend) => {
+                tracing::info!("[Report] {} isn't set up, skipping AI sections", backend.name());
+                None
+            }
+            Err(e) => {
+                tracing::info!("[Report] No AI backend available, skipping AI sections: {e}");
+                None
+            }
+        }
     }
 
     /// Legacy brief generation — the hardcoded phase1/2/3 approach.
@@ -325,26 +337,11 @@ impl ReportService {
             .await
             .unwrap_or_else(|_| crate::service::prompt::DEFAULT_PERSONA.to_string());
 
-        let Some(lm) = &self.local_model else {
+        let Some(backend) = self.ai_backend().await else {
             return brief_builder::build_static_brief(job, detected, pillars);
         };
 
-        let model_id = match lm.get_active_model_id().await {
-            Ok(Some(id)) => id,
-            _ => return brief_builder::build_static_brief(job, detected, pillars),
-        };
-
-        let model_entry = match crate::contexts::local_model::domain::ModelEntry::find_by_id(&model_id) {
-            Some(e) => e,
-            None    => return brief_builder::build_static_brief(job, detected, pillars),
-        };
-
-        let model_path = lm.models_dir().join(&model_entry.filename);
-        if !model_path.exists() {
-            return brief_builder::build_static_brief(job, detected, pillars);
-        }
-
-        tracing::info!("[Report] Legacy phased AI brief with model {}", model_id);
+        tracing::info!("[Report] Legacy phased AI brief with {}", backend.name());
 
         let grade   = brief_builder::score_grade(seo_score);
         let weakest = brief_builder::weakest_pillar(pillars);
@@ -375,10 +372,10 @@ impl ReportService {
             pillars,
             &top_issue_names,
         );
-        let diagnosis = self.infer(&model_path, p1, 280).await.unwrap_or_else(|e| {
-            tracing::warn!("[Report] Phase 1 failed: {e}");
-            String::new()
-        });
+        let diagnosis =
+            generate_section(backend.as_ref(), "Diagnosis", &p1, 280, AI_SECTION_TIMEOUT)
+                .await
+                .unwrap_or_default();
 
         let mut priority_sections = String::new();
         for dp in &priority_patterns {
@@ -396,43 +393,55 @@ impl ReportService {
                 fix_effort: &fix_effort,
                 recommendation: &dp.pattern.recommendation,
             });
-            match self.infer(&model_path, p2, 200).await {
-                Ok(text) if !text.trim().is_empty() => {
-                    priority_sections.push_str(&format!(
-                        "**{}** ({}% of pages)\n{}\n\n",
-                        dp.pattern.name, pct, text.trim()
-                    ));
-                }
-                Err(e) => tracing::warn!("[Report] Phase 2 failed for {}: {e}", dp.pattern.name),
-                _ => {}
+            let text =
+                generate_section(backend.as_ref(), &dp.pattern.name, &p2, 200, AI_SECTION_TIMEOUT)
+                    .await;
+            if let Some(text) = text {
+                priority_sections.push_str(&format!(
+                    "**{}** ({}% of pages)\n{}\n\n",
+                    dp.pattern.name, pct, text
+                ));
             }
         }
 
         let p3 = brief_builder::phase3_roadmap_prompt(&system_prompt, pillars, weakest, &top_issue_names);
-        let roadmap = self.infer(&model_path, p3, 320).await.unwrap_or_else(|e| {
-            tracing::warn!("[Report] Phase 3 failed: {e}");
-            String::new()
-        });
+        let roadmap =
+            generate_section(backend.as_ref(), "Next Steps", &p3, 320, AI_SECTION_TIMEOUT)
+                .await
+                .unwrap_or_default();
 
         assemble_brief(&diagnosis, &priority_sections, &roadmap, job, pillars)
     }
+}
 
-    async fn infer(
-        &self,
-        model_path: &std::path::Path,
-        prompt:     String,
-        max_tokens: usize,
-    ) -> Result<String> {
-        let lm = self.local_model.as_ref()
-            .ok_or_else(|| anyhow::anyhow!("No local model service"))?;
-
-        lm.infer_raw(InferenceRequest {
-            model_path:  model_path.to_path_buf(),
-            prompt,
-            max_tokens,
-            temperature: 0.4,
-        })
-        .await
+/// Generate one AI section with `backend`, giving up after `timeout`.
+/// Returns `None` when the section failed, timed out or came back empty,
+/// so the caller can put fallback text in its place.
+async fn generate_section(
+    backend:    &dyn AiBackend,
+    label:      &str,
+    prompt:     &str,
+    max_tokens: usize,
+    timeout:    Duration,
+) -> Option<String> {
+    match tokio::time::timeout(timeout, backend.generate_section(prompt, max_tokens)).await {
+        Ok(Ok(text)) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Ok(Ok(_)) => {
+            tracing::debug!("[Report] AI section '{label}' returned empty");
+            None
+        }
+        Ok(Err(e)) => {
+            tracing::warn!("[Report] AI section '{label}' failed with {}: {e}", backend.name());
+            None
+        }
+        Err(_) => {
+            tracing::warn!(
+                "[Report] AI section '{label}' timed out after {}s with {}",
+                timeout.as_secs(),
+                backend.name()
+            );
+            None
+        }
     }
 }
 
@@ -470,8 +479,10 @@ fn assemble_brief(
     out.push_str("## Pillar Health\n\n");
     out.push_str(&format!(
         "- Technical: {:.0}/100\n- Content: {:.0}/100\n\
-        - Performance: {:.0}/100\n- Accessibility: {:.0}/100\n\n",
+        - Performance: {:.0}/100\n- Accessibility: {:.0}/100\n\
+        - Security & Delivery: {}\n\n",
         pillars.technical(), pillars.content(), pillars.performance(), pillars.accessibility(),
+        brief_builder::out_of_100(pillars.security()),
     ));
 
     out.push_str("## Next Steps\n\n");
@@ -493,3 +504,54 @@ fn assemble_brief(
 
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    /// Answers every section with `answer`, after `delay`.
+    struct StubBackend {
+        answer: Result<&'static str, &'static str>,
+        delay:  Duration,
+    }
+
+    #[async_trait]
+    impl AiBackend for StubBackend {
+        fn name(&self) -> &'static str {
+            "Stub"
+        }
+
+        async fn generate(&self, _prompt: &str) -> Result<String> {
+            tokio::time::sleep(self.delay).await;
+            self.answer.map(str::to_string).map_err(|e| anyhow::anyhow!(e))
+        }
+    }
+
+    fn stub(answer: Result<&'static str, &'static str>, delay: Duration) -> StubBackend {
+        StubBackend { answer, delay }
+    }
+
+    #[tokio::test]
+    async fn generate_section_trims_the_answer() {
+        let backend = stub(Ok("  Fix the titles first.\n"), Duration::ZERO);
+        let text = generate_section(&backend, "Summary", "prompt", 280, AI_SECTION_TIMEOUT).await;
+        assert_eq!(text.as_deref(), Some("Fix the titles first."));
+    }
+
+    #[tokio::test]
+    async fn generate_section_gives_up_on_errors_and_empty_answers() {
+        let failing = stub(Err("API error 500"), Duration::ZERO);
+        assert_eq!(generate_section(&failing, "Summary", "prompt", 280, AI_SECTION_TIMEOUT).await, None);
+
+        let empty = stub(Ok("   "), Duration::ZERO);
+        assert_eq!(generate_section(&empty, "Summary", "prompt", 280, AI_SECTION_TIMEOUT).await, None);
+    }
+
+    #[tokio::test]
+    async fn generate_section_times_out() {
+        let slow = stub(Ok("too late"), Duration::from_secs(5));
+        let text = generate_section(&slow, "Summary", "prompt", 280, Duration::from_millis(20)).await;
+        assert_eq!(text, None);
+    }
+}
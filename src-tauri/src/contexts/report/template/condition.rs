@@ -8,6 +8,7 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use crate::contexts::analysis::SiteCheckStatus;
 use crate::contexts::report::domain::{PatternCategory, PatternSeverity};
 
 /// Runtime condition evaluated against the render context.
@@ -33,6 +34,11 @@ pub enum Condition {
     /// True if the robots.txt was NOT found during discovery.
     RobotsMissing,
 
+    /// True if the site-level check with id `check` (e.g.
+    /// `"security_txt"`) ended with `status`. A check the job didn't run
+    /// matches nothing.
+    SiteCheck { check: String, status: SiteCheckStatus },
+
     /// True if the named tag has a non-empty aggregated value across
     /// the crawled pages. The `tag` field is the bare extractor name
     /// (e.g. `"og_image"`, not `"tag:og_image"`).
@@ -57,7 +57,8 @@ pub fn default_template() -> ReportTemplate {
                     Sitemap: {{sitemap_found}}\n\
                     Robots.txt: {{robots_txt_found}}\n\
                     Pillar scores — Technical {{pillar.technical}}, Content {{pillar.content}}, \
-                    Performance {{pillar.performance}}, Accessibility {{pillar.accessibility}} (out of 100)\n\
+                    Performance {{pillar.performance}}, Accessibility {{pillar.accessibility}}, \
+                    Security & Delivery {{pillar.security}} (out of 100)\n\
                     Top detected patterns:\n{{top_patterns}}\n\n\
                     Issue details (severity | type | page | message):\n{{issue_details}}\n\n\
                     Pages by issue count (url | title | status | load_ms | issues):\n{{page_summaries}}\n\n\
@@ -91,10 +92,12 @@ pub fn default_template() -> ReportTemplate {
                 text: "Pillar Health".into(),
             },
             TemplateSection::Text {
-                template: "- Technical: {pillar.technical}/100\n\
-                           - Content: {pillar.content}/100\n\
-                           - Performance: {pillar.performance}/100\n\
-                           - Accessibility: {pillar.accessibility}/100"
+                template: "Scores out of 100.\n\n\
+                           - Technical: {pillar.technical}\n\
+                           - Content: {pillar.content}\n\
+                           - Performance: {pillar.performance}\n\
+                           - Accessibility: {pillar.accessibility}\n\
+                           - Security & Delivery: {pillar.security}"
                     .into(),
             },
 
@@ -111,8 +114,9 @@ pub fn default_template() -> ReportTemplate {
                     Site: {{url}}\n\
                     SEO score: {{score}}/100\n\
                     Pages: {{pages_count}} | Avg load: {{avg_load_time}}ms | Total words: {{total_words}}\n\
-                    Pillar scores — Technical {{pillar.technical}}/100, Content {{pillar.content}}/100, \
-                    Performance {{pillar.performance}}/100, Accessibility {{pillar.accessibility}}/100\n\
+                    Pillar scores (out of 100) — Technical {{pillar.technical}}, Content {{pillar.content}}, \
+                    Performance {{pillar.performance}}, Accessibility {{pillar.accessibility}}, \
+                    Security & Delivery {{pillar.security}}\n\
                     Critical: {{critical_issues}} | Warnings: {{warning_issues}} | Missing meta: {{missing_meta_count}} | Slow: {{slow_pages_count}} | Errors: {{error_pages_count}}\n\
                     Top detected patterns to resolve:\n{{top_patterns}}\n\n\
                     Issue details:\n{{issue_details}}\n\n\
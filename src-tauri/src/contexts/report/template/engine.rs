@@ -9,7 +9,8 @@
 
 use super::condition::Condition;
 use super::model::{ReportTemplate, TemplateSection};
-use crate::contexts::analysis::Job;
+use crate::checker::site::SiteCheckRegistry;
+use crate::contexts::analysis::{Job, SiteCheckResult};
 use crate::contexts::report::domain::{DetectedPattern, PillarScores};
 
 /// Everything the renderer needs to resolve variables and evaluate
@@ -46,6 +47,10 @@ pub struct RenderContext<'a> {
     /// of distinct values across all pages (capped at 5). Resolves
     /// `{tag.og_image}` in template text and AI prompts.
     pub tag_values: std::collections::HashMap<String, String>,
+
+    /// Site-level file checks of the job, in report order. Resolves
+    /// `{site.<check_id>}` and the `SiteCheck` condition.
+    pub site_checks: &'a [SiteCheckResult],
 }
 
 /// Errors that can surface during rendering. Kept narrow — the renderer
@@ -240,7 +245,7 @@ fn context_variables(ctx: &RenderContext<'_>) -> Vec<(String, String)> {
         .collect::<Vec<_>>()
         .join("\n");
 
-    vec![
+    let mut variables = vec![
         ("{url}".to_string(), job.url.clone()),
         ("{score}".to_string(), ctx.seo_score.to_string()),
         ("{pages_count}".to_string(), job.summary.total_pages().to_string()),
@@ -280,6 +285,12 @@ fn context_variables(ctx: &RenderContext<'_>) -> Vec<(String, String)> {
             "{pillar.accessibility}".to_string(),
             format!("{:.0}", ctx.pillars.accessibility()),
         ),
+        (
+            "{pillar.security}".to_string(),
+            ctx.pillars
+                .security()
+                .map_or_else(|| "N/A".to_string(), |s| format!("{s:.0}")),
+        ),
         (
             "{pillar.overall}".to_string(),
             format!("{:.0}", ctx.pillars.overall()),
@@ -324,7 +335,27 @@ fn context_variables(ctx: &RenderContext<'_>) -> Vec<(String, String)> {
                     .join("\n")
             }
         }),
-    ]
+    ];
+    variables.extend(site_check_variables(ctx.site_checks));
+    variables
+}
+
+/// `{site.<check_id>}` → the check's status label. Every built-in check
+/// resolves, to "Not checked" when the job didn't run it, so a template
+/// never shows a raw placeholder.
+fn site_check_variables(checks: &[SiteCheckResult]) -> Vec<(String, String)> {
+    let registry = SiteCheckRegistry::with_defaults();
+    let mut variables: Vec<(String, String)> = registry
+        .iter()
+        .filter(|check| !checks.iter().any(|c| c.check_id == check.id()))
+        .map(|check| (format!("{{site.{}}}", check.id()), "Not checked".to_string()))
+        .collect();
+    variables.extend(
+        checks
+            .iter()
+            .map(|c| (format!("{{site.{}}}", c.check_id), c.status.label().to_string())),
+    );
+    variables
 }
 
 fn bool_yn(v: bool) -> &'static str {
@@ -348,6 +379,10 @@ fn evaluate_condition(condition: &Condition, ctx: &RenderContext<'_>) -> bool {
         Condition::CriticalIssuesGt { value } => ctx.job.summary.critical_issues() > *value,
         Condition::SitemapMissing => !ctx.job.sitemap_found,
         Condition::RobotsMissing => !ctx.job.robots_txt_found,
+        Condition::SiteCheck { check, status } => ctx
+            .site_checks
+            .iter()
+            .any(|c| c.check_id == *check && c.status == *status),
         Condition::TagPresent { tag } => ctx
             .tag_values
             .get(tag.as_str())
@@ -4,7 +4,7 @@
 //! output.
 
 use super::*;
-use crate::contexts::analysis::{Job, JobId, JobSettings, JobSummary};
+use crate::contexts::analysis::{Job, JobId, JobSettings, JobSummary, SiteCheckResult, SiteCheckStatus};
 use crate::contexts::report::domain::{
     DetectedPattern, PatternCategory, PatternSeverity, PillarScores, ReportPattern,
     BusinessImpact, FixEffort,
@@ -32,7 +32,7 @@ fn test_job() -> Job {
 }
 
 fn test_pillars() -> PillarScores {
-    PillarScores::from_pillars(80.0, 65.0, 90.0, 75.0)
+    PillarScores::from_pillars(80.0, 65.0, 90.0, 75.0, Some(80.0))
 }
 
 fn test_pattern(name: &str, severity: PatternSeverity) -> ReportPattern {
@@ -45,6 +45,7 @@ fn test_pattern(name: &str, severity: PatternSeverity) -> ReportPattern {
         field: "title".into(),
         operator: Operator::Missing,
         threshold: None,
+        condition: None,
         min_prevalence: 0.0,
         business_impact: BusinessImpact::High,
         fix_effort: FixEffort::Low,
@@ -87,6 +88,7 @@ fn test_ctx<'a>(job: &'a Job, detected: &'a [DetectedPattern], pillars: &'a Pill
         avg_load_time_ms: 1500.0,
         total_words: 8500,
         tag_values: std::collections::HashMap::new(),
+        site_checks: &[],
     }
 }
 
@@ -395,6 +397,44 @@ fn conditional_pattern_fired() {
     assert!(result.contains("Title pattern fired!"));
 }
 
+#[test]
+fn site_check_condition_and_variables() {
+    let checks = vec![SiteCheckResult {
+        check_id: "security_txt".into(),
+        label: "security.txt".into(),
+        status: SiteCheckStatus::Invalid,
+        url: Some("https://example.com/.well-known/security.txt".into()),
+        status_code: Some(200),
+        problems: vec!["No Expires field".into()],
+    }];
+    let section = |status| TemplateSection::Conditional {
+        when: Condition::SiteCheck { check: "security_txt".into(), status },
+        children: vec![TemplateSection::Text {
+            template: format!("security.txt is {}", status.as_str()),
+        }],
+    };
+    let template = ReportTemplate {
+        id: "t".into(),
+        name: "t".into(),
+        is_builtin: false,
+        selected_tags: vec![],
+        sections: vec![
+            section(SiteCheckStatus::Invalid),
+            section(SiteCheckStatus::Found),
+            TemplateSection::Text {
+                template: "security={site.security_txt} llms={site.llms_txt}".into(),
+            },
+        ],
+    };
+    let job = test_job();
+    let pillars = test_pillars();
+    let ctx = RenderContext { site_checks: &checks, ..test_ctx(&job, &[], &pillars) };
+    let result = render_template_to_string(&template, &ctx).unwrap();
+    assert!(result.contains("security.txt is invalid"));
+    assert!(!result.contains("security.txt is found"));
+    assert!(result.contains("security=Invalid llms=Not checked"));
+}
+
 // ── Pillar variables ─────────────────────────────────────────────────────────
 
 #[test]
@@ -405,14 +445,19 @@ fn pillar_variables_resolve() {
         is_builtin: false,
         selected_tags: vec![],
         sections: vec![TemplateSection::Text {
-            template: "Tech={pillar.technical} Content={pillar.content} Overall={pillar.overall}".into(),
+            template: "Tech={pillar.technical} Content={pillar.content} Security={pillar.security} Overall={pillar.overall}".into(),
         }],
     };
     let job = test_job();
     let pillars = test_pillars();
     let ctx = test_ctx(&job, &[], &pillars);
     let result = render_template_to_string(&template, &ctx).unwrap();
-    assert!(result.contains("Tech=80 Content=65 Overall=78"));
+    assert!(result.contains("Tech=80 Content=65 Security=80 Overall=78"));
+
+    let unscored = PillarScores::from_pillars(80.0, 65.0, 90.0, 75.0, None);
+    let ctx = test_ctx(&job, &[], &unscored);
+    let result = render_template_to_string(&template, &ctx).unwrap();
+    assert!(result.contains("Security=N/A Overall=78"));
 }
 
 // ── Full template render ─────────────────────────────────────────────────────
@@ -0,0 +1,43 @@
+use crate::repository::RepositoryError;
+
+use super::model::{RulePackItemKind, RULE_PACK_VERSION};
+
+/// Why a rule pack couldn't be exported, previewed or imported.
+#[derive(Debug, thiserror::Error)]
+pub enum RulePackError {
+    #[error("couldn't read the rule pack: {0}")]
+    Parse(String),
+    #[error("the file is not a rule pack")]
+    NotARulePack,
+    #[error("rule pack version {0} isn't supported; this app reads up to version {RULE_PACK_VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("couldn't write the rule pack: {0}")]
+    Encode(String),
+    #[error("nothing was selected to export")]
+    EmptySelection,
+    #[error("the rule pack's signature doesn't match its contents")]
+    InvalidSignature,
+    #[error("couldn't use the rule pack signing key: {0}")]
+    SigningKey(String),
+    #[error("{kind} '{name}' is invalid: {message}")]
+    InvalidItem {
+        kind: RulePackItemKind,
+        name: String,
+        message: String,
+    },
+    #[error("the rule pack refers to {} which neither it nor this app provides", .0.join(", "))]
+    UnresolvedReferences(Vec<String>),
+    #[error(transparent)]
+    Repository(#[from] RepositoryError),
+}
+
+impl RulePackError {
+    /// Stable code for the frontend, in the `ServiceError::code` scheme.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Repository(inner) => inner.code(),
+            Self::Encode(_) | Self::SigningKey(_) => "SVC_INVALID_STATE",
+            _ => "SVC_INVALID_QUERY",
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Rule packs: custom checks, extractors, report patterns and report
+//! templates bundled into one file a consultant can share.
+//!
+//! A pack is versioned JSON or TOML ([`RulePack`]). Exporting a
+//! selection brings along the extractors and patterns it refers to, so
+//! the pack works on its own. Importing first plans every item against
+//! what's already here ([`plan`]) — matching ids and extractor tags
+//! collide and are merged, overwritten or renamed, built-in items never
+//! being overwritten — and writes nothing unless every item is valid and
+//! every reference resolves.
+//!
+//! Packs can be signed with a key this app generates on first use, so a
+//! recipient can tell packs from the same author apart from edited ones.
+//! The key is kept unencrypted in the settings database; see
+//! `signature::SIGNING_KEY_SETTING` for what that means for trust.
+
+mod error;
+mod model;
+pub(crate) mod plan;
+pub(crate) mod references;
+mod service;
+mod signature;
+
+pub use error::RulePackError;
+pub use model::{
+    ImportOutcome, ImportStrategy, PackFormat, PackSigner, RulePack, RulePackCollision,
+    RulePackExport, RulePackItem, RulePackItemKind, RulePackPreview, RULE_PACK_FORMAT,
+    RULE_PACK_VERSION,
+};
+pub use plan::{PlannedWrite, RulePackWrite};
+pub use service::RulePackService;
+pub use signature::PackSignature;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+
+use crate::contexts::extension::{CustomCheck, CustomExtractor};
+use crate::contexts::report::{ReportPattern, ReportTemplate};
+
+use super::error::RulePackError;
+use super::signature::PackSignature;
+
+/// The `format` every rule pack declares, so an unrelated JSON or TOML
+/// file is turned away instead of importing as an empty pack.
+pub const RULE_PACK_FORMAT: &str = "seo-insikt-rule-pack";
+
+/// The newest pack layout this build reads and the one it writes. Bump
+/// it when a change would make older builds misread a pack.
+pub const RULE_PACK_VERSION: u32 = 1;
+
+/// A shareable bundle of checks, extractors, patterns and templates.
+///
+/// Items keep their ids so importing the same pack again is recognised
+/// as a collision rather than duplicating everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulePack {
+    #[serde(default)]
+    pub format: String,
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// RFC 3339 timestamp of the export.
+    pub exported_at: String,
+    /// Absent on unsigned packs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<PackSignature>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extractors: Vec<CustomExtractor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<CustomCheck>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<ReportPattern>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<ReportTemplate>,
+}
+
+impl RulePack {
+    /// An unsigned pack stamped with the current format, version and time.
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            format: RULE_PACK_FORMAT.to_string(),
+            version: RULE_PACK_VERSION,
+            name: name.into(),
+            description: description.into(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            signature: None,
+            extractors: Vec::new(),
+            checks: Vec::new(),
+            patterns: Vec::new(),
+            templates: Vec::new(),
+        }
+    }
+
+    /// Read a pack from JSON or TOML; JSON is recognised by its leading `{`.
+    pub fn parse(content: &str) -> Result<Self, RulePackError> {
+        let pack: Self = if content.trim_start().starts_with('{') {
+            serde_json::from_str(content).map_err(|e| RulePackError::Parse(e.to_string()))?
+        } else {
+            toml::from_str(content).map_err(|e| RulePackError::Parse(e.to_string()))?
+        };
+        if pack.format != RULE_PACK_FORMAT {
+            return Err(RulePackError::NotARulePack);
+        }
+        if pack.version == 0 || pack.version > RULE_PACK_VERSION {
+            return Err(RulePackError::UnsupportedVersion(pack.version));
+        }
+        Ok(pack)
+    }
+
+    /// Write the pack out in `format`.
+    pub fn encode(&self, format: PackFormat) -> Result<String, RulePackError> {
+        match format {
+            PackFormat::Json => {
+                serde_json::to_string_pretty(self).map_err(|e| RulePackError::Encode(e.to_string()))
+            }
+            PackFormat::Toml => {
+                toml::to_string_pretty(self).map_err(|e| RulePackError::Encode(e.to_string()))
+            }
+        }
+    }
+
+    /// How many items the pack holds, across all four kinds.
+    pub fn len(&self) -> usize {
+        self.extractors.len() + self.checks.len() + self.patterns.len() + self.templates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// File format a pack is written in.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PackFormat {
+    #[default]
+    Json,
+    Toml,
+}
+
+/// Which local items to export. Extractors and patterns the selection
+/// refers to are added even when not listed.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct RulePackExport {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub check_ids: Vec<String>,
+    #[serde(default)]
+    pub extractor_ids: Vec<String>,
+    #[serde(default)]
+    pub pattern_ids: Vec<String>,
+    #[serde(default)]
+    pub template_ids: Vec<String>,
+    #[serde(default)]
+    pub format: PackFormat,
+    /// Sign the pack with this app's rule pack key.
+    #[serde(default)]
+    pub sign: bool,
+}
+
+/// What to do with a pack item whose id — or, for an extractor, whose
+/// tag — already exists here.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStrategy {
+    /// Keep the local item and skip the pack's.
+    #[default]
+    Merge,
+    /// Replace the local item with the pack's.
+    Overwrite,
+    /// Import the pack's item alongside under a new id, name or tag,
+    /// updating the pack's references to it.
+    Rename,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RulePackItemKind {
+    Extractor,
+    Check,
+    Pattern,
+    Template,
+}
+
+impl RulePackItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Extractor => "extractor",
+            Self::Check => "check",
+            Self::Pattern => "pattern",
+            Self::Template => "template",
+        }
+    }
+}
+
+crate::impl_display_via_as_str!(RulePackItemKind);
+
+/// How a pack item clashes with what's already here.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RulePackCollision {
+    /// An item of the same kind has the same id.
+    Id,
+    /// An extractor already publishes the same tag.
+    Tag,
+}
+
+/// Who signed a pack.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+pub struct PackSigner {
+    /// Short hex digest of the signer's public key, for comparing by eye.
+    pub fingerprint: String,
+    /// Signed with this app's own key.
+    pub is_local: bool,
+}
+
+/// What happened — or, in a preview, would happen — to a pack item on import.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportOutcome {
+    Created,
+    Updated,
+    Renamed,
+    Skipped,
+}
+
+/// One item of a pack and where it lands on import.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct RulePackItem {
+    pub kind: RulePackItemKind,
+    /// The item's id in the pack.
+    pub id: String,
+    /// The item's name in the pack.
+    pub name: String,
+    pub collision: Option<RulePackCollision>,
+    pub outcome: ImportOutcome,
+    /// The name — or, for an extractor, the tag — it's imported under
+    /// when renamed.
+    pub renamed_to: Option<String>,
+}
+
+/// What a pack holds and how importing it with a given strategy would
+/// go, shown before importing.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct RulePackPreview {
+    pub name: String,
+    pub description: String,
+    pub version: u32,
+    pub exported_at: String,
+    /// `None` for an unsigned pack. A pack whose signature doesn't match
+    /// fails to preview at all.
+    pub signer: Option<PackSigner>,
+    pub items: Vec<RulePackItem>,
+    /// `tag:<tag>` and pattern references nothing would provide after
+    /// the import; importing fails while there are any.
+    pub unresolved: Vec<String>,
+}
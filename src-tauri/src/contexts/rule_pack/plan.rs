@@ -0,0 +1,384 @@
+//! Working out what importing a pack would do, without touching storage.
+//!
+//! Extractors are planned first since their tags may be renamed, then
+//! patterns, whose ids templates may refer to, then checks and
+//! templates — each with the renames so far applied to its references.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::checker::custom;
+use crate::contexts::extension::{CustomCheck, CustomCheckParams, CustomExtractor};
+use crate::contexts::report::services::pattern_engine::CompiledCondition;
+use crate::contexts::report::{ReportPattern, ReportTemplate};
+use crate::extractor::data_extractor::{self, ExtractorConfig};
+
+use super::error::RulePackError;
+use super::model::{
+    ImportOutcome, ImportStrategy, RulePack, RulePackCollision, RulePackItem, RulePackItemKind,
+};
+use super::references::{self, References};
+
+/// Everything already stored that a pack could collide with or rely on.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LocalItems {
+    pub extractors: Vec<CustomExtractor>,
+    pub checks: Vec<CustomCheck>,
+    pub patterns: Vec<ReportPattern>,
+    pub templates: Vec<ReportTemplate>,
+}
+
+/// The item as it would be written, under the id it would be written as.
+#[derive(Debug, Clone)]
+pub enum PlannedWrite {
+    Extractor(CustomExtractor),
+    Check(CustomCheck),
+    Pattern(ReportPattern),
+    Template(ReportTemplate),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PlannedItem {
+    pub item: RulePackItem,
+    pub write: PlannedWrite,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ImportPlan {
+    pub items: Vec<PlannedItem>,
+    /// References nothing provides once the plan is carried out.
+    pub unresolved: Vec<String>,
+}
+
+impl ImportPlan {
+    /// Reject the plan if any item it writes wouldn't save through the
+    /// regular editors, or any reference is left dangling.
+    pub(crate) fn validate(&self) -> Result<(), RulePackError> {
+        if !self.unresolved.is_empty() {
+            return Err(RulePackError::UnresolvedReferences(self.unresolved.clone()));
+        }
+        for planned in &self.items {
+            if planned.item.outcome != ImportOutcome::Skipped {
+                validate_write(&planned.write).map_err(|message| RulePackError::InvalidItem {
+                    kind: planned.item.kind,
+                    name: planned.item.name.clone(),
+                    message,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn summary(&self) -> Vec<RulePackItem> {
+        self.items.iter().map(|planned| planned.item.clone()).collect()
+    }
+
+    /// What carrying out the plan writes: every item it doesn't skip.
+    pub(crate) fn writes(&self) -> Vec<RulePackWrite> {
+        self.items
+            .iter()
+            .filter_map(|planned| {
+                let write = planned.write.clone();
+                match planned.item.outcome {
+                    ImportOutcome::Skipped => None,
+                    ImportOutcome::Updated => Some(RulePackWrite::Update(write)),
+                    ImportOutcome::Created | ImportOutcome::Renamed => {
+                        Some(RulePackWrite::Create(write))
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// One write of an import: a new item, or the stored item with the same
+/// id replaced.
+#[derive(Debug, Clone)]
+pub enum RulePackWrite {
+    Create(PlannedWrite),
+    Update(PlannedWrite),
+}
+
+fn validate_write(write: &PlannedWrite) -> Result<(), String> {
+    match write {
+        PlannedWrite::Extractor(extractor) => {
+            data_extractor::build(ExtractorConfig::from(extractor.clone()))
+                .map(drop)
+                .map_err(|e| e.to_string())
+        }
+        PlannedWrite::Check(check) => {
+            custom::validate(&CustomCheckParams::from(check)).map_err(|e| e.to_string())
+        }
+        PlannedWrite::Pattern(pattern) => CompiledCondition::for_rule(
+            &pattern.field,
+            &pattern.operator,
+            pattern.threshold.as_deref(),
+            pattern.condition.as_ref(),
+        )
+        .map(drop)
+        .map_err(|e| e.to_string()),
+        PlannedWrite::Template(_) => Ok(()),
+    }
+}
+
+/// Plan importing `pack` into `local` with `strategy`.
+pub(crate) fn plan(pack: &RulePack, local: &LocalItems, strategy: ImportStrategy) -> ImportPlan {
+    let mut planner = Planner::new(local, strategy);
+    for extractor in &pack.extractors {
+        planner.extractor(extractor.clone());
+    }
+    for pattern in &pack.patterns {
+        planner.pattern(pattern.clone());
+    }
+    for check in &pack.checks {
+        planner.check(check.clone());
+    }
+    for template in &pack.templates {
+        planner.template(template.clone());
+    }
+    planner.finish()
+}
+
+struct Planner<'a> {
+    local: &'a LocalItems,
+    strategy: ImportStrategy,
+    /// Extractor tags in use, here or earlier in the pack.
+    taken_tags: HashSet<String>,
+    /// Pack tag → the tag it's imported under.
+    tag_renames: Vec<(String, String)>,
+    /// Pack pattern id → the id it's imported under.
+    pattern_renames: Vec<(String, String)>,
+    items: Vec<PlannedItem>,
+}
+
+impl<'a> Planner<'a> {
+    fn new(local: &'a LocalItems, strategy: ImportStrategy) -> Self {
+        Self {
+            local,
+            strategy,
+            taken_tags: local.extractors.iter().map(|e| e.tag.clone()).collect(),
+            tag_renames: Vec::new(),
+            pattern_renames: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+
+    fn extractor(&mut self, mut extractor: CustomExtractor) {
+        let by_tag = self.local.extractors.iter().find(|e| e.tag == extractor.tag);
+        let by_id = self.local.extractors.iter().find(|e| e.id == extractor.id);
+        let collision = match (by_tag, by_id) {
+            (Some(_), _) => Some(RulePackCollision::Tag),
+            (None, Some(_)) => Some(RulePackCollision::Id),
+            (None, None) => None,
+        };
+        let mut item = item(RulePackItemKind::Extractor, &extractor.id, &extractor.name, collision);
+
+        match (by_tag.or(by_id), self.strategy) {
+            (None, _) => {}
+            (Some(_), ImportStrategy::Merge) => item.outcome = ImportOutcome::Skipped,
+            (Some(existing), ImportStrategy::Overwrite) => {
+                extractor.id = existing.id.clone();
+                item.outcome = ImportOutcome::Updated;
+            }
+            (Some(_), ImportStrategy::Rename) => {
+                if by_id.is_some() {
+                    extractor.id = new_id();
+                }
+                if by_tag.is_some() {
+                    let tag = unique_tag(&extractor.tag, &self.taken_tags);
+                    self.tag_renames.push((extractor.tag.clone(), tag.clone()));
+                    extractor.tag = tag;
+                }
+                item.outcome = ImportOutcome::Renamed;
+                item.renamed_to = Some(extractor.tag.clone());
+            }
+        }
+        self.taken_tags.insert(extractor.tag.clone());
+        self.items.push(PlannedItem { item, write: PlannedWrite::Extractor(extractor) });
+    }
+
+    fn pattern(&mut self, mut pattern: ReportPattern) {
+        self.apply_tag_renames(&mut pattern);
+        let names = self.local.patterns.iter().map(|p| p.name.as_str());
+        let existing = self.local.patterns.iter().find(|p| p.id == pattern.id);
+        let (item, renamed) = self.resolve_id(
+            RulePackItemKind::Pattern,
+            &mut pattern.id,
+            &mut pattern.name,
+            existing.map(|p| p.is_builtin),
+            names,
+        );
+        if let Some((from, to)) = renamed {
+            self.pattern_renames.push((from, to));
+        }
+        // Built-in only means "seeded here"; a pack's copy is the user's.
+        pattern.is_builtin = false;
+        self.items.push(PlannedItem { item, write: PlannedWrite::Pattern(pattern) });
+    }
+
+    fn check(&mut self, mut check: CustomCheck) {
+        self.apply_tag_renames(&mut check);
+        let names = self.local.checks.iter().map(|c| c.name.as_str());
+        let existing = self.local.checks.iter().any(|c| c.id == check.id).then_some(false);
+        let (item, _) =
+            self.resolve_id(RulePackItemKind::Check, &mut check.id, &mut check.name, existing, names);
+        self.items.push(PlannedItem { item, write: PlannedWrite::Check(check) });
+    }
+
+    fn template(&mut self, mut template: ReportTemplate) {
+        self.apply_tag_renames(&mut template);
+        for (from, to) in &self.pattern_renames {
+            references::rename_pattern(&mut template, from, to);
+        }
+        let names = self.local.templates.iter().map(|t| t.name.as_str());
+        let existing = self.local.templates.iter().find(|t| t.id == template.id);
+        let (item, _) = self.resolve_id(
+            RulePackItemKind::Template,
+            &mut template.id,
+            &mut template.name,
+            existing.map(|t| t.is_builtin),
+            names,
+        );
+        template.is_builtin = false;
+        self.items.push(PlannedItem { item, write: PlannedWrite::Template(template) });
+    }
+
+    fn apply_tag_renames<T: References>(&self, item: &mut T) {
+        for (from, to) in &self.tag_renames {
+            references::rename_tag(item, from, to);
+        }
+    }
+
+    /// Settle an item that collides by id only; `existing` says whether
+    /// the local item with that id is built in. On rename it gets a new
+    /// id and name, returned as the `(old, new)` id pair.
+    ///
+    /// Built-in items are never overwritten: they can't be edited, and
+    /// the default template is reset to its seeded sections at startup,
+    /// so an overwrite would fail or be lost. The pack's copy is imported
+    /// alongside under a new name instead.
+    fn resolve_id<'n>(
+        &self,
+        kind: RulePackItemKind,
+        id: &mut String,
+        name: &mut String,
+        existing: Option<bool>,
+        local_names: impl Iterator<Item = &'n str>,
+    ) -> (RulePackItem, Option<(String, String)>) {
+        let collision = existing.map(|_| RulePackCollision::Id);
+        let mut item = item(kind, id, name, collision);
+        let Some(builtin) = existing else {
+            return (item, None);
+        };
+        let strategy = match self.strategy {
+            ImportStrategy::Overwrite if builtin => ImportStrategy::Rename,
+            strategy => strategy,
+        };
+        match strategy {
+            ImportStrategy::Merge => {
+                item.outcome = ImportOutcome::Skipped;
+                (item, None)
+            }
+            ImportStrategy::Overwrite => {
+                item.outcome = ImportOutcome::Updated;
+                (item, None)
+            }
+            ImportStrategy::Rename => {
+                let renamed_id = new_id();
+                let old_id = std::mem::replace(id, renamed_id.clone());
+                *name = unique_name(name, &local_names.collect());
+                item.outcome = ImportOutcome::Renamed;
+                item.renamed_to = Some(name.clone());
+                (item, Some((old_id, renamed_id)))
+            }
+        }
+    }
+
+    fn finish(self) -> ImportPlan {
+        let unresolved = unresolved(self.local, &self.items);
+        ImportPlan { items: self.items, unresolved }
+    }
+}
+
+fn item(
+    kind: RulePackItemKind,
+    id: &str,
+    name: &str,
+    collision: Option<RulePackCollision>,
+) -> RulePackItem {
+    RulePackItem {
+        kind,
+        id: id.to_string(),
+        name: name.to_string(),
+        collision,
+        outcome: ImportOutcome::Created,
+        renamed_to: None,
+    }
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// `base_2`, `base_3`, … — the first not in `taken`.
+fn unique_tag(base: &str, taken: &HashSet<String>) -> String {
+    (2..)
+        .map(|n| format!("{base}_{n}"))
+        .find(|tag| !taken.contains(tag))
+        .expect("unbounded range always yields a free tag")
+}
+
+/// `name (imported)`, then `name (imported 2)`, … — the first not in `taken`.
+fn unique_name(name: &str, taken: &HashSet<&str>) -> String {
+    let first = format!("{name} (imported)");
+    if !taken.contains(first.as_str()) {
+        return first;
+    }
+    (2..)
+        .map(|n| format!("{name} (imported {n})"))
+        .find(|candidate| !taken.contains(candidate.as_str()))
+        .expect("unbounded range always yields a free name")
+}
+
+/// References in the items a plan writes that neither the local items,
+/// as the plan leaves them, nor the written items provide.
+fn unresolved(local: &LocalItems, items: &[PlannedItem]) -> Vec<String> {
+    let mut tags: HashMap<&str, &str> =
+        local.extractors.iter().map(|e| (e.id.as_str(), e.tag.as_str())).collect();
+    let mut patterns: HashSet<&str> = local.patterns.iter().map(|p| p.id.as_str()).collect();
+    let written = items.iter().filter(|p| p.item.outcome != ImportOutcome::Skipped);
+    for planned in written.clone() {
+        match &planned.write {
+            PlannedWrite::Extractor(e) => {
+                tags.insert(&e.id, &e.tag);
+            }
+            PlannedWrite::Pattern(p) => {
+                patterns.insert(&p.id);
+            }
+            PlannedWrite::Check(_) | PlannedWrite::Template(_) => {}
+        }
+    }
+    let tags: HashSet<&str> = tags.into_values().collect();
+
+    let mut missing = BTreeSet::new();
+    for planned in written {
+        let (used_tags, used_patterns) = match &planned.write {
+            PlannedWrite::Extractor(_) => continue,
+            PlannedWrite::Check(c) => (references::tags(c), BTreeSet::new()),
+            PlannedWrite::Pattern(p) => (references::tags(p), BTreeSet::new()),
+            PlannedWrite::Template(t) => (references::tags(t), references::patterns(t)),
+        };
+        missing.extend(
+            used_tags
+                .into_iter()
+                .filter(|tag| !tags.contains(tag.as_str()))
+                .map(|tag| format!("tag:{tag}")),
+        );
+        missing.extend(
+            used_patterns
+                .into_iter()
+                .filter(|id| !patterns.contains(id.as_str()))
+                .map(|id| format!("pattern {id}")),
+        );
+    }
+    missing.into_iter().collect()
+}
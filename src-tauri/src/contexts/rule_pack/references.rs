@@ -0,0 +1,226 @@
+//! Where pack items refer to extractor tags and report patterns, so an
+//! export can bring its dependencies along and an import can check and
+//! rewrite them.
+//!
+//! A tag is referenced as `tag:<tag>` in a check or pattern field, as a
+//! `{tag.<tag>}` placeholder in message and template text, and bare in a
+//! template's `selected_tags` and tag conditions. A check script reads
+//! one as `tags.<tag>` or `tags["<tag>"]`. A pattern is referenced by id
+//! from a template's `PatternFired` condition.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use crate::contexts::extension::{CustomCheck, RuleCondition};
+use crate::contexts::report::template::Condition;
+use crate::contexts::report::{ReportPattern, ReportTemplate, TemplateSection};
+
+/// One string of an item that can hold a reference.
+pub(crate) enum Reference<'a> {
+    /// A page field, a tag reference when it reads `tag:<tag>`.
+    Field(&'a mut String),
+    /// Text with `{tag.<tag>}` placeholders.
+    Text(&'a mut String),
+    /// A bare tag name.
+    Tag(&'a mut String),
+    /// A check script, reading tags as `tags.<tag>` or `tags["<tag>"]`.
+    Script(&'a mut String),
+    /// A report pattern id.
+    Pattern(&'a mut String),
+}
+
+/// An item whose references can be listed and rewritten.
+pub(crate) trait References {
+    fn visit_references(&mut self, visit: &mut dyn FnMut(Reference<'_>));
+}
+
+impl References for CustomCheck {
+    fn visit_references(&mut self, visit: &mut dyn FnMut(Reference<'_>)) {
+        visit(Reference::Field(&mut self.field));
+        if let Some(condition) = &mut self.condition {
+            visit_rule_condition(condition, visit);
+        }
+        visit(Reference::Text(&mut self.message_template));
+        if let Some(script) = &mut self.script {
+            visit(Reference::Script(script));
+        }
+    }
+}
+
+impl References for ReportPattern {
+    fn visit_references(&mut self, visit: &mut dyn FnMut(Reference<'_>)) {
+        visit(Reference::Field(&mut self.field));
+        if let Some(condition) = &mut self.condition {
+            visit_rule_condition(condition, visit);
+        }
+    }
+}
+
+impl References for ReportTemplate {
+    fn visit_references(&mut self, visit: &mut dyn FnMut(Reference<'_>)) {
+        for tag in &mut self.selected_tags {
+            visit(Reference::Tag(tag));
+        }
+        for section in &mut self.sections {
+            visit_section(section, visit);
+        }
+    }
+}
+
+fn visit_rule_condition(condition: &mut RuleCondition, visit: &mut dyn FnMut(Reference<'_>)) {
+    match condition {
+        RuleCondition::Compare { field, compare_field, .. } => {
+            visit(Reference::Field(field));
+            if let Some(other) = compare_field {
+                visit(Reference::Field(other));
+            }
+        }
+        RuleCondition::All { children } | RuleCondition::Any { children } => {
+            for child in children {
+                visit_rule_condition(child, visit);
+            }
+        }
+        RuleCondition::Not { inner } => visit_rule_condition(inner, visit),
+    }
+}
+
+fn visit_section(section: &mut TemplateSection, visit: &mut dyn FnMut(Reference<'_>)) {
+    match section {
+        TemplateSection::Heading { text, .. } => visit(Reference::Text(text)),
+        TemplateSection::Text { template } => visit(Reference::Text(template)),
+        TemplateSection::Ai { prompt, .. } => visit(Reference::Text(prompt)),
+        TemplateSection::PatternSummary { per_pattern_template, empty_template, .. } => {
+            visit(Reference::Text(per_pattern_template));
+            if let Some(empty) = empty_template {
+                visit(Reference::Text(empty));
+            }
+        }
+        TemplateSection::Conditional { when, children } => {
+            visit_template_condition(when, visit);
+            for child in children {
+                visit_section(child, visit);
+            }
+        }
+        TemplateSection::Divider => {}
+    }
+}
+
+fn visit_template_condition(condition: &mut Condition, visit: &mut dyn FnMut(Reference<'_>)) {
+    match condition {
+        Condition::PatternFired { pattern_id } => visit(Reference::Pattern(pattern_id)),
+        Condition::TagPresent { tag }
+        | Condition::TagMissing { tag }
+        | Condition::TagContains { tag, .. } => visit(Reference::Tag(tag)),
+        Condition::All { children } | Condition::Any { children } => {
+            for child in children {
+                visit_template_condition(child, visit);
+            }
+        }
+        Condition::Not { inner } => visit_template_condition(inner, visit),
+        Condition::AnyPatternMatches { .. }
+        | Condition::ScoreLt { .. }
+        | Condition::CriticalIssuesGt { .. }
+        | Condition::SitemapMissing
+        | Condition::RobotsMissing
+        | Condition::SiteCheck { .. } => {}
+    }
+}
+
+/// The `{tag.<tag>}` placeholders in `text`.
+fn placeholder_tags(text: &str) -> impl Iterator<Item = &str> {
+    text.split("{tag.")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(tag, _)| tag))
+        .filter(|tag| !tag.is_empty())
+}
+
+/// Where a script names a tag: the `<tag>` of each `tags.<tag>` and
+/// `tags["<tag>"]`. A tag reached another way, say through a variable
+/// holding `page.tags`, isn't found.
+fn script_tag_spans(script: &str) -> Vec<Range<usize>> {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut spans = Vec::new();
+    for (at, _) in script.match_indices("tags") {
+        if script[..at].chars().next_back().is_some_and(is_ident) {
+            continue;
+        }
+        let rest = &script[at + "tags".len()..];
+        if let Some(name) = rest.strip_prefix('.') {
+            let start = at + "tags.".len();
+            let len = name.find(|c: char| !is_ident(c)).unwrap_or(name.len());
+            if len > 0 {
+                spans.push(start..start + len);
+            }
+        } else if let Some(name) = rest.strip_prefix("[\"") {
+            let start = at + "tags[\"".len();
+            if let Some(len) = name.find('"').filter(|&len| len > 0) {
+                spans.push(start..start + len);
+            }
+        }
+    }
+    spans
+}
+
+/// Every extractor tag `item` refers to.
+pub(crate) fn tags<T: References + Clone>(item: &T) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+    item.clone().visit_references(&mut |reference| match reference {
+        Reference::Field(field) => {
+            if let Some(tag) = field.strip_prefix("tag:") {
+                tags.insert(tag.to_string());
+            }
+        }
+        Reference::Text(text) => tags.extend(placeholder_tags(text).map(str::to_string)),
+        Reference::Tag(tag) => {
+            tags.insert(tag.clone());
+        }
+        Reference::Script(script) => {
+            let spans = script_tag_spans(script);
+            tags.extend(spans.into_iter().map(|span| script[span].to_string()));
+        }
+        Reference::Pattern(_) => {}
+    });
+    tags
+}
+
+/// Every report pattern id `item` refers to.
+pub(crate) fn patterns<T: References + Clone>(item: &T) -> BTreeSet<String> {
+    let mut patterns = BTreeSet::new();
+    item.clone().visit_references(&mut |reference| {
+        if let Reference::Pattern(id) = reference {
+            patterns.insert(id.clone());
+        }
+    });
+    patterns
+}
+
+/// Point every reference to tag `from` at tag `to`.
+pub(crate) fn rename_tag<T: References>(item: &mut T, from: &str, to: &str) {
+    let field_from = format!("tag:{from}");
+    let placeholder_from = format!("{{tag.{from}}}");
+    let placeholder_to = format!("{{tag.{to}}}");
+    item.visit_references(&mut |reference| match reference {
+        Reference::Field(field) if *field == field_from => *field = format!("tag:{to}"),
+        Reference::Text(text) => *text = text.replace(&placeholder_from, &placeholder_to),
+        Reference::Tag(tag) if *tag == from => *tag = to.to_string(),
+        Reference::Script(script) => {
+            for span in script_tag_spans(script).into_iter().rev() {
+                if script[span.clone()] == *from {
+                    script.replace_range(span, to);
+                }
+            }
+        }
+        _ => {}
+    });
+}
+
+/// Point every reference to pattern `from` at pattern `to`.
+pub(crate) fn rename_pattern<T: References>(item: &mut T, from: &str, to: &str) {
+    item.visit_references(&mut |reference| {
+        if let Reference::Pattern(id) = reference {
+            if *id == from {
+                *id = to.to_string();
+            }
+        }
+    });
+}
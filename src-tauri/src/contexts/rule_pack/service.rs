@@ -0,0 +1,144 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use ed25519_dalek::SigningKey;
+
+use crate::repository::{
+    ExtensionRepository, ReportPatternRepository, ReportTemplateRepository, RulePackRepository,
+    SettingsRepository,
+};
+
+use super::error::RulePackError;
+use super::model::{ImportStrategy, RulePack, RulePackExport, RulePackItem, RulePackPreview};
+use super::plan::{self, LocalItems};
+use super::references;
+use super::signature::{self, SIGNING_KEY_SETTING};
+
+pub struct RulePackService {
+    extension_repo: Arc<dyn ExtensionRepository>,
+    pattern_repo: Arc<dyn ReportPatternRepository>,
+    template_repo: Arc<dyn ReportTemplateRepository>,
+    rule_pack_repo: Arc<dyn RulePackRepository>,
+    settings_repo: Arc<dyn SettingsRepository>,
+}
+
+impl RulePackService {
+    pub fn new(
+        extension_repo: Arc<dyn ExtensionRepository>,
+        pattern_repo: Arc<dyn ReportPatternRepository>,
+        template_repo: Arc<dyn ReportTemplateRepository>,
+        rule_pack_repo: Arc<dyn RulePackRepository>,
+        settings_repo: Arc<dyn SettingsRepository>,
+    ) -> Self {
+        Self { extension_repo, pattern_repo, template_repo, rule_pack_repo, settings_repo }
+    }
+
+    /// Bundle the selected items, plus the extractors and patterns they
+    /// refer to, into a pack written in the requested format.
+    pub async fn export(&self, request: &RulePackExport) -> Result<String, RulePackError> {
+        let local = self.local_items().await?;
+        let selected = |ids: &[String], id: &str| ids.iter().any(|wanted| wanted == id);
+        let mut pack = RulePack::new(request.name.trim(), request.description.trim());
+
+        pack.checks =
+            local.checks.iter().filter(|c| selected(&request.check_ids, &c.id)).cloned().collect();
+        pack.templates = local
+            .templates
+            .iter()
+            .filter(|t| selected(&request.template_ids, &t.id))
+            .cloned()
+            .collect();
+
+        let mut pattern_ids: BTreeSet<String> = request.pattern_ids.iter().cloned().collect();
+        for template in &pack.templates {
+            pattern_ids.extend(references::patterns(template));
+        }
+        pack.patterns =
+            local.patterns.iter().filter(|p| pattern_ids.contains(&p.id)).cloned().collect();
+
+        let mut tags = BTreeSet::new();
+        pack.checks.iter().for_each(|c| tags.extend(references::tags(c)));
+        pack.patterns.iter().for_each(|p| tags.extend(references::tags(p)));
+        pack.templates.iter().for_each(|t| tags.extend(references::tags(t)));
+        pack.extractors = local
+            .extractors
+            .iter()
+            .filter(|e| selected(&request.extractor_ids, &e.id) || tags.contains(&e.tag))
+            .cloned()
+            .collect();
+
+        if pack.is_empty() {
+            return Err(RulePackError::EmptySelection);
+        }
+        if request.sign {
+            signature::sign(&mut pack, &self.signing_key().await?)?;
+        }
+        pack.encode(request.format)
+    }
+
+    /// Read a pack, check its signature and work out what importing it
+    /// with `strategy` would do, without writing anything.
+    pub async fn preview(
+        &self,
+        content: &str,
+        strategy: ImportStrategy,
+    ) -> Result<RulePackPreview, RulePackError> {
+        let pack = RulePack::parse(content)?;
+        let signer = signature::verify(&pack, self.local_public_key().await?.as_ref())?;
+        let plan = plan::plan(&pack, &self.local_items().await?, strategy);
+        Ok(RulePackPreview {
+            name: pack.name,
+            description: pack.description,
+            version: pack.version,
+            exported_at: pack.exported_at,
+            signer,
+            items: plan.summary(),
+            unresolved: plan.unresolved,
+        })
+    }
+
+    /// Import a pack with `strategy`. Nothing is written unless every
+    /// item is valid and every reference resolves, and the writes go in
+    /// one transaction so a failing one leaves nothing half-imported.
+    pub async fn import(
+        &self,
+        content: &str,
+        strategy: ImportStrategy,
+    ) -> Result<Vec<RulePackItem>, RulePackError> {
+        let pack = RulePack::parse(content)?;
+        signature::verify(&pack, None)?;
+        let plan = plan::plan(&pack, &self.local_items().await?, strategy);
+        plan.validate()?;
+
+        self.rule_pack_repo.import(&plan.writes()).await?;
+        Ok(plan.summary())
+    }
+
+    async fn local_items(&self) -> Result<LocalItems, RulePackError> {
+        Ok(LocalItems {
+            extractors: self.extension_repo.list_extractors().await?,
+            checks: self.extension_repo.list_checks().await?,
+            patterns: self.pattern_repo.list_patterns().await?,
+            templates: self.template_repo.list_templates().await?,
+        })
+    }
+
+    /// This app's rule pack key, generated and stored on first use.
+    async fn signing_key(&self) -> Result<SigningKey, RulePackError> {
+        if let Some(stored) = self.settings_repo.get_setting(SIGNING_KEY_SETTING).await? {
+            return signature::decode_signing_key(&stored);
+        }
+        let key = SigningKey::generate(&mut rand::rngs::OsRng);
+        self.settings_repo
+            .set_setting(SIGNING_KEY_SETTING, &signature::encode_signing_key(&key))
+            .await?;
+        Ok(key)
+    }
+
+    async fn local_public_key(&self) -> Result<Option<ed25519_dalek::VerifyingKey>, RulePackError> {
+        match self.settings_repo.get_setting(SIGNING_KEY_SETTING).await? {
+            Some(stored) => Ok(Some(signature::decode_signing_key(&stored)?.verifying_key())),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Ed25519 signatures over a rule pack's contents.
+//!
+//! The signed bytes are the pack's JSON with `signature` left out, so a
+//! pack verifies the same whether it travelled as JSON or TOML.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::error::RulePackError;
+use super::model::{PackSigner, RulePack};
+
+/// Setting holding this app's hex-encoded rule pack signing key.
+///
+/// The private key is stored unencrypted in the settings table, as the
+/// AI provider keys are, not in the OS keychain. Anyone who can read the
+/// app's database can sign packs that verify as this install's, so a
+/// signature tells packs from the same install apart from edited ones;
+/// it doesn't prove who made a pack.
+pub(crate) const SIGNING_KEY_SETTING: &str = "rule_pack_signing_key";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackSignature {
+    /// Hex-encoded Ed25519 public key of the exporter.
+    pub public_key: String,
+    /// Hex-encoded Ed25519 signature over the pack's JSON without this field.
+    pub signature: String,
+}
+
+fn signed_bytes(pack: &RulePack) -> Result<Vec<u8>, RulePackError> {
+    let unsigned = RulePack {
+        signature: None,
+        ..pack.clone()
+    };
+    serde_json::to_vec(&unsigned).map_err(|e| RulePackError::Encode(e.to_string()))
+}
+
+/// Sign `pack` with `key`, replacing any earlier signature.
+pub(crate) fn sign(pack: &mut RulePack, key: &SigningKey) -> Result<(), RulePackError> {
+    let signature = key.sign(&signed_bytes(pack)?);
+    pack.signature = Some(PackSignature {
+        public_key: hex::encode(key.verifying_key().to_bytes()),
+        signature: hex::encode(signature.to_bytes()),
+    });
+    Ok(())
+}
+
+/// Check a pack's signature. `Ok(None)` for an unsigned pack; an error
+/// when the signature is malformed or doesn't match the contents.
+pub(crate) fn verify(
+    pack: &RulePack,
+    local_key: Option<&VerifyingKey>,
+) -> Result<Option<PackSigner>, RulePackError> {
+    let Some(signed) = &pack.signature else {
+        return Ok(None);
+    };
+    let public_key = decode_public_key(&signed.public_key)?;
+    let signature_bytes =
+        hex::decode(&signed.signature).map_err(|_| RulePackError::InvalidSignature)?;
+    let signature =
+        Signature::from_slice(&signature_bytes).map_err(|_| RulePackError::InvalidSignature)?;
+    public_key
+        .verify(&signed_bytes(pack)?, &signature)
+        .map_err(|_| RulePackError::InvalidSignature)?;
+
+    Ok(Some(PackSigner {
+        fingerprint: fingerprint(&public_key),
+        is_local: local_key == Some(&public_key),
+    }))
+}
+
+fn decode_public_key(hex_key: &str) -> Result<VerifyingKey, RulePackError> {
+    let bytes: [u8; 32] = hex::decode(hex_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(RulePackError::InvalidSignature)?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| RulePackError::InvalidSignature)
+}
+
+/// The first 8 bytes of the key's SHA-256, as hex in groups of four.
+pub(crate) fn fingerprint(key: &VerifyingKey) -> String {
+    let digest = Sha256::digest(key.to_bytes());
+    let hex = hex::encode(&digest[..8]);
+    hex.as_bytes()
+        .chunks(4)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Read a signing key stored by [`encode_signing_key`].
+pub(crate) fn decode_signing_key(hex_key: &str) -> Result<SigningKey, RulePackError> {
+    let bytes: [u8; 32] = hex::decode(hex_key.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| RulePackError::SigningKey("stored key is malformed".into()))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+pub(crate) fn encode_signing_key(key: &SigningKey) -> String {
+    hex::encode(key.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn pack() -> RulePack {
+        RulePack::new("Ecommerce basics", "Product page checks")
+    }
+
+    #[test]
+    fn signed_pack_verifies_and_reports_its_signer() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut pack = pack();
+        sign(&mut pack, &key).unwrap();
+
+        let signer = verify(&pack, Some(&key.verifying_key())).unwrap().unwrap();
+        assert!(signer.is_local);
+        assert_eq!(signer.fingerprint, fingerprint(&key.verifying_key()));
+
+        let other = SigningKey::generate(&mut OsRng);
+        let signer = verify(&pack, Some(&other.verifying_key())).unwrap().unwrap();
+        assert!(!signer.is_local);
+    }
+
+    #[test]
+    fn edited_pack_fails_verification() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut pack = pack();
+        sign(&mut pack, &key).unwrap();
+        pack.description = "Tampered".into();
+
+        assert!(matches!(verify(&pack, None), Err(RulePackError::InvalidSignature)));
+    }
+
+    #[test]
+    fn unsigned_pack_has_no_signer() {
+        assert_eq!(verify(&pack(), None).unwrap(), None);
+    }
+
+    #[test]
+    fn fingerprint_is_four_groups_of_four_hex_digits() {
+        let key = SigningKey::generate(&mut OsRng);
+        let fingerprint = fingerprint(&key.verifying_key());
+        let groups: Vec<&str> = fingerprint.split(' ').collect();
+        assert_eq!(groups.len(), 4);
+        assert!(groups.iter().all(|g| g.len() == 4 && g.chars().all(|c| c.is_ascii_hexdigit())));
+    }
+
+    #[test]
+    fn signing_key_round_trips_through_its_setting_encoding() {
+        let key = SigningKey::generate(&mut OsRng);
+        let decoded = decode_signing_key(&encode_signing_key(&key)).unwrap();
+        assert_eq!(decoded.to_bytes(), key.to_bytes());
+        assert!(decode_signing_key("not hex").is_err());
+    }
+}
@@ -0,0 +1,406 @@
+use std::sync::Arc;
+
+use super::plan::{self, LocalItems, PlannedWrite};
+use super::*;
+use crate::contexts::extension::{
+    CheckScope, CustomCheck, CustomCheckParams, CustomExtractor, CustomExtractorParams,
+    ExtractorKind, ExtractorOutput, IssueSeverity, Operator, RuleCondition,
+};
+use crate::contexts::report::template::Condition;
+use crate::contexts::report::{
+    BusinessImpact, FixEffort, PatternCategory, PatternSeverity, ReportPattern,
+    ReportPatternParams, ReportTemplate, TemplateSection,
+};
+use crate::repository::{
+    sqlite_extension_repo, sqlite_report_pattern_repo, sqlite_report_template_repo,
+    sqlite_rule_pack_repo, sqlite_settings_repo, ExtensionRepository, ReportPatternRepository, ReportTemplateRepository,
+};
+use crate::test_utils::fixtures;
+
+fn extractor(id: &str, tag: &str) -> CustomExtractor {
+    CustomExtractor {
+        id: id.into(),
+        name: format!("{tag} extractor"),
+        tag: tag.into(),
+        kind: ExtractorKind::Css,
+        selector: "meta[property='og:image']".into(),
+        attribute: Some("content".into()),
+        multiple: false,
+        output: ExtractorOutput::Value,
+        data_type: None,
+        enabled: true,
+    }
+}
+
+fn check(id: &str, tag: &str) -> CustomCheck {
+    CustomCheck {
+        id: id.into(),
+        name: format!("{tag} missing"),
+        severity: IssueSeverity::Warning,
+        field: format!("tag:{tag}"),
+        operator: Operator::Missing,
+        threshold: None,
+        condition: None,
+        scope: CheckScope::Page,
+        aggregate: None,
+        script: None,
+        message_template: format!("No {tag}: {{tag.{tag}}}"),
+        enabled: true,
+    }
+}
+
+fn pattern(id: &str, tag: &str) -> ReportPattern {
+    ReportPattern {
+        id: id.into(),
+        name: format!("{tag} missing sitewide"),
+        description: "test".into(),
+        category: PatternCategory::Technical,
+        severity: PatternSeverity::Warning,
+        field: format!("tag:{tag}"),
+        operator: Operator::Missing,
+        threshold: None,
+        condition: None,
+        min_prevalence: 0.2,
+        business_impact: BusinessImpact::Medium,
+        fix_effort: FixEffort::Low,
+        recommendation: "fix".into(),
+        is_builtin: false,
+        enabled: true,
+    }
+}
+
+fn template(id: &str, pattern_id: &str, tag: &str) -> ReportTemplate {
+    ReportTemplate {
+        id: id.into(),
+        name: "Client report".into(),
+        is_builtin: false,
+        sections: vec![TemplateSection::Conditional {
+            when: Condition::PatternFired { pattern_id: pattern_id.into() },
+            children: vec![TemplateSection::Text { template: format!("Tag: {{tag.{tag}}}") }],
+        }],
+        selected_tags: vec![tag.into()],
+    }
+}
+
+fn sample_pack() -> RulePack {
+    let mut pack = RulePack::new("Social", "Open Graph checks");
+    pack.extractors = vec![extractor("e1", "og_image")];
+    pack.checks = vec![check("c1", "og_image")];
+    pack.patterns = vec![pattern("p1", "og_image")];
+    pack.templates = vec![template("t1", "p1", "og_image")];
+    pack
+}
+
+fn outcomes(plan: &plan::ImportPlan) -> Vec<ImportOutcome> {
+    plan.items.iter().map(|p| p.item.outcome).collect()
+}
+
+// ── Format ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn pack_round_trips_through_json_and_toml() {
+    let pack = sample_pack();
+    let expected = serde_json::to_value(&pack).unwrap();
+    for format in [PackFormat::Json, PackFormat::Toml] {
+        let encoded = pack.encode(format).unwrap();
+        let parsed = RulePack::parse(&encoded).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), expected, "{format:?}");
+    }
+}
+
+#[test]
+fn parse_rejects_other_files_and_newer_versions() {
+    let other = r#"{"version":1,"name":"x","exported_at":""}"#;
+    assert!(matches!(RulePack::parse(other), Err(RulePackError::NotARulePack)));
+    assert!(matches!(RulePack::parse("not = [valid"), Err(RulePackError::Parse(_))));
+
+    let mut pack = sample_pack();
+    pack.version = RULE_PACK_VERSION + 1;
+    let encoded = pack.encode(PackFormat::Json).unwrap();
+    assert!(matches!(RulePack::parse(&encoded), Err(RulePackError::UnsupportedVersion(_))));
+}
+
+// ── Planning ────────────────────────────────────────────────────────────────
+
+#[test]
+fn fresh_import_creates_everything_under_pack_ids() {
+    let plan = plan::plan(&sample_pack(), &LocalItems::default(), ImportStrategy::Merge);
+    assert_eq!(outcomes(&plan), vec![ImportOutcome::Created; 4]);
+    assert!(plan.unresolved.is_empty());
+    assert!(plan.items.iter().all(|p| p.item.collision.is_none()));
+    plan.validate().unwrap();
+}
+
+#[test]
+fn merge_skips_items_that_already_exist() {
+    let local = LocalItems {
+        extractors: vec![extractor("local", "og_image")],
+        checks: vec![check("c1", "og_image")],
+        ..Default::default()
+    };
+    let plan = plan::plan(&sample_pack(), &local, ImportStrategy::Merge);
+
+    assert_eq!(plan.items[0].item.collision, Some(RulePackCollision::Tag));
+    assert_eq!(
+        outcomes(&plan),
+        vec![ImportOutcome::Skipped, ImportOutcome::Created, ImportOutcome::Skipped, ImportOutcome::Created]
+    );
+    // The local extractor still provides the tag the pack relies on.
+    assert!(plan.unresolved.is_empty());
+}
+
+#[test]
+fn overwrite_updates_the_local_extractor_with_the_same_tag() {
+    let local = LocalItems { extractors: vec![extractor("local", "og_image")], ..Default::default() };
+    let plan = plan::plan(&sample_pack(), &local, ImportStrategy::Overwrite);
+
+    assert_eq!(plan.items[0].item.outcome, ImportOutcome::Updated);
+    let PlannedWrite::Extractor(written) = &plan.items[0].write else { panic!("not an extractor") };
+    assert_eq!(written.id, "local");
+}
+
+#[test]
+fn overwrite_renames_instead_of_replacing_builtin_items() {
+    let local = LocalItems {
+        patterns: vec![ReportPattern { is_builtin: true, ..pattern("p1", "og_image") }],
+        templates: vec![ReportTemplate { is_builtin: true, ..template("t1", "p1", "og_image") }],
+        ..Default::default()
+    };
+    let plan = plan::plan(&sample_pack(), &local, ImportStrategy::Overwrite);
+
+    assert_eq!(
+        outcomes(&plan),
+        vec![ImportOutcome::Created, ImportOutcome::Renamed, ImportOutcome::Created, ImportOutcome::Renamed]
+    );
+    let PlannedWrite::Pattern(p) = &plan.items[1].write else { panic!("not a pattern") };
+    assert_ne!(p.id, "p1");
+    let PlannedWrite::Template(t) = &plan.items[3].write else { panic!("not a template") };
+    assert_ne!(t.id, "t1");
+    let TemplateSection::Conditional { when, .. } = &t.sections[0] else { panic!() };
+    assert_eq!(when, &Condition::PatternFired { pattern_id: p.id.clone() });
+}
+
+#[test]
+fn rename_gives_a_new_tag_and_rewrites_references_to_it() {
+    let local = LocalItems {
+        extractors: vec![extractor("e1", "og_image")],
+        patterns: vec![pattern("p1", "og_image")],
+        ..Default::default()
+    };
+    let plan = plan::plan(&sample_pack(), &local, ImportStrategy::Rename);
+
+    assert_eq!(plan.items[0].item.renamed_to.as_deref(), Some("og_image_2"));
+    let PlannedWrite::Extractor(e) = &plan.items[0].write else { panic!("not an extractor") };
+    assert_ne!(e.id, "e1");
+
+    let PlannedWrite::Pattern(p) = &plan.items[1].write else { panic!("not a pattern") };
+    assert_eq!(p.field, "tag:og_image_2");
+    assert_ne!(p.id, "p1");
+    assert_eq!(p.name, "og_image missing sitewide (imported)");
+
+    let PlannedWrite::Check(c) = &plan.items[2].write else { panic!("not a check") };
+    assert_eq!(c.field, "tag:og_image_2");
+    assert_eq!(c.message_template, "No og_image: {tag.og_image_2}");
+
+    let PlannedWrite::Template(t) = &plan.items[3].write else { panic!("not a template") };
+    assert_eq!(t.selected_tags, vec!["og_image_2".to_string()]);
+    let TemplateSection::Conditional { when, children } = &t.sections[0] else { panic!() };
+    assert_eq!(when, &Condition::PatternFired { pattern_id: p.id.clone() });
+    assert_eq!(children[0], TemplateSection::Text { template: "Tag: {tag.og_image_2}".into() });
+
+    assert!(plan.unresolved.is_empty());
+}
+
+#[test]
+fn rename_rewrites_the_tags_a_check_script_reads() {
+    let mut pack = sample_pack();
+    pack.checks[0].script = Some(
+        r#"if page.tags.og_image == () || page.tags["og_image"] == page.tags.og_image_alt { "x" }"#
+            .into(),
+    );
+    let local = LocalItems { extractors: vec![extractor("e1", "og_image")], ..Default::default() };
+    let plan = plan::plan(&pack, &local, ImportStrategy::Rename);
+
+    let PlannedWrite::Check(c) = &plan.items[2].write else { panic!("not a check") };
+    assert_eq!(
+        c.script.as_deref(),
+        Some(r#"if page.tags.og_image_2 == () || page.tags["og_image_2"] == page.tags.og_image_alt { "x" }"#)
+    );
+    // The script's other tag is one nothing in the pack or here provides.
+    assert_eq!(plan.unresolved, vec!["tag:og_image_alt"]);
+}
+
+#[test]
+fn references_nothing_provides_are_reported() {
+    let mut pack = sample_pack();
+    pack.extractors.clear();
+    pack.patterns.clear();
+    pack.checks[0].condition = Some(RuleCondition::All {
+        children: vec![RuleCondition::Compare {
+            field: "title".into(),
+            operator: Operator::Eq,
+            threshold: None,
+            compare_field: Some("tag:og_title".into()),
+        }],
+    });
+
+    let plan = plan::plan(&pack, &LocalItems::default(), ImportStrategy::Merge);
+    assert_eq!(plan.unresolved, vec!["pattern p1", "tag:og_image", "tag:og_title"]);
+    assert!(matches!(plan.validate(), Err(RulePackError::UnresolvedReferences(_))));
+}
+
+#[test]
+fn invalid_items_fail_validation() {
+    let mut pack = sample_pack();
+    pack.extractors[0].selector = "[[[".into();
+    let plan = plan::plan(&pack, &LocalItems::default(), ImportStrategy::Merge);
+    assert!(matches!(
+        plan.validate(),
+        Err(RulePackError::InvalidItem { kind: RulePackItemKind::Extractor, .. })
+    ));
+}
+
+// ── Service ─────────────────────────────────────────────────────────────────
+
+struct Install {
+    pool: sqlx::SqlitePool,
+    service: RulePackService,
+    extension_repo: Arc<dyn ExtensionRepository>,
+    pattern_repo: Arc<dyn ReportPatternRepository>,
+    template_repo: Arc<dyn ReportTemplateRepository>,
+}
+
+async fn install() -> Install {
+    let pool = fixtures::setup_test_db().await;
+    let extension_repo = sqlite_extension_repo(pool.clone());
+    let pattern_repo = sqlite_report_pattern_repo(pool.clone());
+    let template_repo = sqlite_report_template_repo(pool.clone());
+    let service = RulePackService::new(
+        extension_repo.clone(),
+        pattern_repo.clone(),
+        template_repo.clone(),
+        sqlite_rule_pack_repo(pool.clone()),
+        sqlite_settings_repo(pool.clone()),
+    );
+    Install { pool, service, extension_repo, pattern_repo, template_repo }
+}
+
+async fn seed(install: &Install) -> (CustomCheck, ReportTemplate) {
+    let pack = sample_pack();
+    install
+        .extension_repo
+        .create_extractor(&CustomExtractorParams::from(&pack.extractors[0]))
+        .await
+        .unwrap();
+    let check = install
+        .extension_repo
+        .create_check(&CustomCheckParams::from(&pack.checks[0]))
+        .await
+        .unwrap();
+    let pattern = install
+        .pattern_repo
+        .create_pattern(&ReportPatternParams::from(&pack.patterns[0]))
+        .await
+        .unwrap();
+    let template = template("t-seeded", &pattern.id, "og_image");
+    install.template_repo.create_template(&template).await.unwrap();
+    (check, template)
+}
+
+#[tokio::test]
+async fn export_brings_dependencies_and_imports_into_another_install() {
+    let source = install().await;
+    let (check, template) = seed(&source).await;
+
+    let exported = source
+        .service
+        .export(&RulePackExport {
+            name: "Social".into(),
+            description: String::new(),
+            check_ids: vec![check.id.clone()],
+            extractor_ids: vec![],
+            pattern_ids: vec![],
+            template_ids: vec![template.id.clone()],
+            format: PackFormat::Toml,
+            sign: true,
+        })
+        .await
+        .unwrap();
+    let pack = RulePack::parse(&exported).unwrap();
+    assert_eq!(pack.extractors.len(), 1, "the checks' extractor comes along");
+    assert_eq!(pack.patterns.len(), 1, "the template's pattern comes along");
+
+    let preview = source.service.preview(&exported, ImportStrategy::Merge).await.unwrap();
+    assert!(preview.signer.unwrap().is_local);
+    assert!(preview.items.iter().all(|i| i.outcome == ImportOutcome::Skipped));
+
+    let target = install().await;
+    let preview = target.service.preview(&exported, ImportStrategy::Merge).await.unwrap();
+    assert!(!preview.signer.unwrap().is_local);
+
+    let imported = target.service.import(&exported, ImportStrategy::Merge).await.unwrap();
+    assert!(imported.iter().all(|i| i.outcome == ImportOutcome::Created));
+    let checks = target.extension_repo.list_checks().await.unwrap();
+    assert_eq!(checks[0].id, check.id);
+
+    // Importing the same pack again is recognised item by item.
+    let again = target.service.import(&exported, ImportStrategy::Merge).await.unwrap();
+    assert!(again.iter().all(|i| i.outcome == ImportOutcome::Skipped));
+    assert_eq!(target.extension_repo.list_checks().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn failed_import_writes_nothing() {
+    let target = install().await;
+    let mut pack = sample_pack();
+    pack.extractors.clear();
+    let content = pack.encode(PackFormat::Json).unwrap();
+
+    assert!(matches!(
+        target.service.import(&content, ImportStrategy::Merge).await,
+        Err(RulePackError::UnresolvedReferences(_))
+    ));
+    assert!(target.extension_repo.list_checks().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn import_failing_mid_write_leaves_nothing_behind() {
+    let target = install().await;
+    // Templates are written last, after the extractor, check and pattern.
+    sqlx::query(
+        "CREATE TRIGGER fail_template_insert BEFORE INSERT ON report_templates
+         BEGIN SELECT RAISE(ABORT, 'disk full'); END",
+    )
+    .execute(&target.pool)
+    .await
+    .unwrap();
+    let content = sample_pack().encode(PackFormat::Json).unwrap();
+
+    assert!(matches!(
+        target.service.import(&content, ImportStrategy::Merge).await,
+        Err(RulePackError::Repository(_))
+    ));
+    assert!(target.extension_repo.list_extractors().await.unwrap().is_empty());
+    assert!(target.extension_repo.list_checks().await.unwrap().is_empty());
+    let patterns = target.pattern_repo.list_patterns().await.unwrap();
+    assert!(patterns.iter().all(|p| p.id != "p1"));
+}
+
+#[tokio::test]
+async fn export_of_nothing_is_refused() {
+    let source = install().await;
+    let result = source
+        .service
+        .export(&RulePackExport {
+            name: "Empty".into(),
+            description: String::new(),
+            check_ids: vec!["missing".into()],
+            extractor_ids: vec![],
+            pattern_ids: vec![],
+            template_ids: vec![],
+            format: PackFormat::Json,
+            sign: false,
+        })
+        .await;
+    assert!(matches!(result, Err(RulePackError::EmptySelection)));
+}
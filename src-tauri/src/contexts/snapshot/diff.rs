@@ -0,0 +1,46 @@
+use similar::{ChangeTag, TextDiff};
+
+use super::model::{DiffHunk, DiffLine, DiffLineKind, PageSnapshot, SourceDiff};
+
+/// Lines of unchanged source shown around each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Compare the source of `from`, the older snapshot, with `to`.
+pub fn diff_source(from: &PageSnapshot, to: &PageSnapshot) -> SourceDiff {
+    let hunks = if from.hash == to.hash { Vec::new() } else { hunks(&from.html, &to.html) };
+    SourceDiff {
+        from_job_id: from.job_id.clone(),
+        from_captured_at: from.captured_at.clone(),
+        to_job_id: to.job_id.clone(),
+        to_captured_at: to.captured_at.clone(),
+        url: to.url.clone(),
+        hunks,
+    }
+}
+
+fn hunks(from: &str, to: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(from, to);
+    diff.grouped_ops(CONTEXT_LINES)
+        .into_iter()
+        .filter_map(|group| {
+            let first = group.first()?;
+            let lines = group
+                .iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| DiffLine {
+                    kind: match change.tag() {
+                        ChangeTag::Equal => DiffLineKind::Context,
+                        ChangeTag::Insert => DiffLineKind::Added,
+                        ChangeTag::Delete => DiffLineKind::Removed,
+                    },
+                    text: change.value().trim_end_matches(['\r', '\n']).to_string(),
+                })
+                .collect();
+            Some(DiffHunk {
+                from_line: first.old_range().start as u32 + 1,
+                to_line: first.new_range().start as u32 + 1,
+                lines,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,22 @@
+//! Page snapshots: the raw HTML and response headers each crawled page
+//! was served with.
+//!
+//! Analysis keeps only what it extracted from a page; the snapshot keeps
+//! the source, so checks, extractors and reports can be re-run on old
+//! jobs, the source shown in the UI and compared between crawls.
+//! Bodies are compressed and stored once per distinct HTML, and
+//! snapshots older than the retention setting are dropped.
+
+mod diff;
+mod model;
+mod service;
+
+pub use diff::diff_source;
+pub use model::{
+    DiffHunk, DiffLine, DiffLineKind, NewPageSnapshot, PageSnapshot, SnapshotSettings,
+    SnapshotStorage, SourceDiff, DEFAULT_RETENTION_DAYS, SNAPSHOT_SETTINGS_KEY,
+};
+pub use service::SnapshotService;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use crate::contexts::analysis::ResponseHeaders;
+
+/// Settings key the [`SnapshotSettings`] are stored under, as JSON.
+pub const SNAPSHOT_SETTINGS_KEY: &str = "page_snapshot_settings";
+
+/// How long snapshots are kept unless the user chooses otherwise.
+pub const DEFAULT_RETENTION_DAYS: u32 = 90;
+
+/// Whether page source is saved during analysis, and for how long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct SnapshotSettings {
+    /// Save each analysed page's HTML and headers.
+    pub enabled: bool,
+    /// Days a snapshot is kept after its page was crawled. `None` keeps
+    /// them until the job is deleted.
+    pub retention_days: Option<u32>,
+}
+
+impl Default for SnapshotSettings {
+    fn default() -> Self {
+        Self { enabled: true, retention_days: Some(DEFAULT_RETENTION_DAYS) }
+    }
+}
+
+/// A page's source as analysis fetched it, to be saved.
+#[derive(Debug, Clone, Copy)]
+pub struct NewPageSnapshot<'a> {
+    pub page_id: &'a str,
+    pub job_id: &'a str,
+    pub url: &'a str,
+    pub html: &'a str,
+    pub headers: &'a ResponseHeaders,
+}
+
+/// The HTML and response headers one page of a job was served with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct PageSnapshot {
+    pub job_id: String,
+    pub url: String,
+    pub html: String,
+    pub headers: ResponseHeaders,
+    /// Hex SHA-256 of the HTML; snapshots with the same hash have the
+    /// same source.
+    pub hash: String,
+    /// When the page was crawled, RFC 3339.
+    pub captured_at: String,
+}
+
+/// What the saved snapshots take up.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct SnapshotStorage {
+    pub snapshots: i64,
+    /// Distinct page sources among them.
+    pub distinct_sources: i64,
+    /// Bytes of HTML the snapshots hold, counting repeated sources each
+    /// time.
+    pub html_bytes: i64,
+    /// Bytes actually stored, after deduplication and compression.
+    pub stored_bytes: i64,
+}
+
+/// Line-by-line changes to a page's source between two crawls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct SourceDiff {
+    /// The older job.
+    pub from_job_id: String,
+    pub from_captured_at: String,
+    pub to_job_id: String,
+    pub to_captured_at: String,
+    pub url: String,
+    /// Changed regions with a few lines of context; empty when the
+    /// source is unchanged.
+    pub hunks: Vec<DiffHunk>,
+}
+
+/// One changed region of a [`SourceDiff`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct DiffHunk {
+    /// First line of the region in the older source, 1-based.
+    pub from_line: u32,
+    /// First line of the region in the newer source, 1-based.
+    pub to_line: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// The line without its line break.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
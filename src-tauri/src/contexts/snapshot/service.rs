@@ -0,0 +1,122 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{Duration, Utc};
+
+use crate::repository::{
+    RepositoryError, RepositoryResult, SettingsRepository, SnapshotRepository,
+};
+use crate::service::{ServiceError, ServiceResult};
+
+use super::diff::diff_source;
+use super::model::{
+    NewPageSnapshot, PageSnapshot, SnapshotSettings, SnapshotStorage, SourceDiff,
+    SNAPSHOT_SETTINGS_KEY,
+};
+
+/// Saves page source during analysis and serves it back for viewing and
+/// comparison. The settings are read once and cached, since analysis
+/// asks for them on every page.
+pub struct SnapshotService {
+    snapshot_repo: Arc<dyn SnapshotRepository>,
+    settings_repo: Arc<dyn SettingsRepository>,
+    settings: RwLock<SnapshotSettings>,
+}
+
+impl SnapshotService {
+    /// Build the service with the stored settings, or the defaults when
+    /// none were saved or they can't be read.
+    pub async fn load(
+        snapshot_repo: Arc<dyn SnapshotRepository>,
+        settings_repo: Arc<dyn SettingsRepository>,
+    ) -> Self {
+        let settings = match settings_repo.get_setting(SNAPSHOT_SETTINGS_KEY).await {
+            Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                tracing::warn!("Invalid page snapshot settings, using defaults: {e}");
+                SnapshotSettings::default()
+            }),
+            Ok(None) => SnapshotSettings::default(),
+            Err(e) => {
+                tracing::warn!("Failed to load page snapshot settings, using defaults: {e}");
+                SnapshotSettings::default()
+            }
+        };
+        Self { snapshot_repo, settings_repo, settings: RwLock::new(settings) }
+    }
+
+    pub fn settings(&self) -> SnapshotSettings {
+        *self.settings.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Store new settings and apply the retention period straight away.
+    pub async fn set_settings(&self, settings: SnapshotSettings) -> ServiceResult<SnapshotStorage> {
+        if settings.retention_days == Some(0) {
+            return Err(ServiceError::invalid_query(
+                "keep snapshots for at least a day, or turn saving them off",
+            ));
+        }
+        let json = serde_json::to_string(&settings)
+            .map_err(|e| RepositoryError::decode("page snapshot settings", e.to_string()))?;
+        self.settings_repo.set_setting(SNAPSHOT_SETTINGS_KEY, &json).await?;
+        *self.settings.write().unwrap_or_else(|e| e.into_inner()) = settings;
+
+        self.prune().await?;
+        Ok(self.snapshot_repo.storage().await?)
+    }
+
+    /// Save a page's source, unless saving is turned off or the page had
+    /// no HTML.
+    pub async fn keep(&self, snapshot: &NewPageSnapshot<'_>) -> RepositoryResult<()> {
+        if !self.settings().enabled || snapshot.html.is_empty() {
+            return Ok(());
+        }
+        self.snapshot_repo.save(snapshot).await
+    }
+
+    /// Drop snapshots past the retention period, and sources no snapshot
+    /// uses any more. Returns how many snapshots were dropped.
+    pub async fn prune(&self) -> RepositoryResult<u64> {
+        let cutoff = self
+            .settings()
+            .retention_days
+            .map(|days| Utc::now() - Duration::days(i64::from(days)));
+        let removed = self.snapshot_repo.prune(cutoff).await?;
+        if removed > 0 {
+            tracing::info!("Dropped {removed} page snapshot(s) past the retention period");
+        }
+        Ok(removed)
+    }
+
+    pub async fn storage(&self) -> ServiceResult<SnapshotStorage> {
+        Ok(self.snapshot_repo.storage().await?)
+    }
+
+    /// The source of the page of `job_id` crawled at `url`.
+    pub async fn page_source(&self, job_id: &str, url: &str) -> ServiceResult<PageSnapshot> {
+        Ok(self.snapshot_repo.get(job_id, url).await?)
+    }
+
+    /// Compare the source of the page of `job_id` crawled at `url` with
+    /// the same URL in `against_job_id`, or, without one, in the latest
+    /// earlier crawl that saved it.
+    pub async fn diff(
+        &self,
+        job_id: &str,
+        url: &str,
+        against_job_id: Option<&str>,
+    ) -> ServiceResult<SourceDiff> {
+        let current = self.snapshot_repo.get(job_id, url).await?;
+        let other = match against_job_id {
+            Some(other_job) => self.snapshot_repo.get(other_job, url).await?,
+            None => self.snapshot_repo.get_previous(job_id, url).await?.ok_or_else(|| {
+                ServiceError::invalid_state(format!("no earlier crawl saved the source of {url}"))
+            })?,
+        };
+
+        // Always diff from the older crawl to the newer one.
+        Ok(if other.captured_at <= current.captured_at {
+            diff_source(&other, &current)
+        } else {
+            diff_source(&current, &other)
+        })
+    }
+}
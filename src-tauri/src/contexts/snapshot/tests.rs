@@ -0,0 +1,65 @@
+use super::*;
+
+fn snapshot(job_id: &str, html: &str) -> PageSnapshot {
+    PageSnapshot {
+        job_id: job_id.into(),
+        url: "https://example.com/".into(),
+        html: html.into(),
+        headers: Default::default(),
+        hash: format!("hash-of-{html}"),
+        captured_at: format!("2026-01-0{}T00:00:00+00:00", if job_id == "old" { 1 } else { 2 }),
+    }
+}
+
+fn lines(hunk: &DiffHunk) -> Vec<(DiffLineKind, &str)> {
+    hunk.lines.iter().map(|line| (line.kind, line.text.as_str())).collect()
+}
+
+#[test]
+fn unchanged_source_has_no_hunks() {
+    let html = "<p>same</p>\n";
+    let diff = diff_source(&snapshot("old", html), &snapshot("new", html));
+    assert!(diff.hunks.is_empty());
+    assert_eq!((diff.from_job_id.as_str(), diff.to_job_id.as_str()), ("old", "new"));
+}
+
+#[test]
+fn changes_come_with_surrounding_context() {
+    let old: String = (1..=20).map(|n| format!("line {n}\n")).collect();
+    let new = old.replace("line 10\n", "line ten\n").replace("line 20\n", "");
+    let diff = diff_source(&snapshot("old", &old), &snapshot("new", &new));
+
+    assert_eq!(diff.hunks.len(), 2);
+    let first = &diff.hunks[0];
+    assert_eq!((first.from_line, first.to_line), (7, 7));
+    assert_eq!(
+        lines(first),
+        [
+            (DiffLineKind::Context, "line 7"),
+            (DiffLineKind::Context, "line 8"),
+            (DiffLineKind::Context, "line 9"),
+            (DiffLineKind::Removed, "line 10"),
+            (DiffLineKind::Added, "line ten"),
+            (DiffLineKind::Context, "line 11"),
+            (DiffLineKind::Context, "line 12"),
+            (DiffLineKind::Context, "line 13"),
+        ]
+    );
+    assert_eq!(lines(&diff.hunks[1]).last(), Some(&(DiffLineKind::Removed, "line 20")));
+}
+
+#[test]
+fn line_endings_are_not_part_of_the_text() {
+    let diff = diff_source(&snapshot("old", "a\r\nb\r\n"), &snapshot("new", "a\r\nc\r\n"));
+    let texts: Vec<_> = diff.hunks[0].lines.iter().map(|line| line.text.as_str()).collect();
+    assert_eq!(texts, ["a", "b", "c"]);
+}
+
+#[test]
+fn settings_default_to_saving_with_retention() {
+    let settings = SnapshotSettings::default();
+    assert!(settings.enabled);
+    assert_eq!(settings.retention_days, Some(DEFAULT_RETENTION_DAYS));
+    let json = serde_json::to_string(&settings).unwrap();
+    assert_eq!(serde_json::from_str::<SnapshotSettings>(&json).unwrap(), settings);
+}
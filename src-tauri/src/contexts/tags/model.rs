@@ -80,6 +80,36 @@ pub enum TagDataType {
     List,
 }
 
+impl TagDataType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Number => "number",
+            Self::Bool => "bool",
+            Self::List => "list",
+        }
+    }
+}
+
+/// Returned by [`TagDataType::from_str`] for an unknown type.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+#[error("invalid tag data type: '{0}'")]
+pub struct ParseTagDataTypeError(pub String);
+
+impl std::str::FromStr for TagDataType {
+    type Err = ParseTagDataTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "number" => Ok(Self::Number),
+            "bool" => Ok(Self::Bool),
+            "list" => Ok(Self::List),
+            other => Err(ParseTagDataTypeError(other.to_string())),
+        }
+    }
+}
+
 /// An authoring surface where a tag can be referenced. The editor
 /// filters the tag picker to the scopes that match the current field.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq, Hash)]
@@ -1,7 +1,8 @@
 //! Tag registry — runtime catalog assembled from the built-in field
 //! list + live custom extractors.
 
-use crate::contexts::extension::CustomExtractor;
+use crate::checker::site::SiteCheckRegistry;
+use crate::contexts::extension::{CustomExtractor, ExtractorKind, ExtractorOutput};
 use crate::repository::ExtensionRepository;
 
 use super::model::{Tag, TagDataType, TagScope, TagSource};
@@ -50,23 +51,30 @@ impl TagRegistry {
 // ── Extractor → Tag projection ───────────────────────────────────────────────
 
 fn extractor_to_tag(ext: CustomExtractor) -> Tag {
-    let data_type = if ext.multiple {
-        TagDataType::List
-    } else {
-        TagDataType::Text
+    let data_type = ext.data_type();
+    let reads = match (ext.kind, ext.attribute.as_deref()) {
+        (ExtractorKind::Css | ExtractorKind::Xpath, Some(attr)) => format!(" @{attr}"),
+        (ExtractorKind::Css, None) => " text content".into(),
+        (ExtractorKind::Regex, Some(group)) => format!(" group {group}"),
+        _ => String::new(),
+    };
+    let output = match ext.output {
+        ExtractorOutput::Value => "",
+        ExtractorOutput::Count => " (count)",
+        ExtractorOutput::Length => " (length)",
+    };
+    let kind = match ext.kind {
+        ExtractorKind::Css => "CSS",
+        ExtractorKind::Xpath => "XPath",
+        ExtractorKind::Regex => "Regex",
+        ExtractorKind::JsonLd => "JSON-LD",
+        ExtractorKind::Header => "Header",
     };
 
     Tag {
         name: format!("tag:{}", ext.tag),
         label: ext.name.clone(),
-        description: format!(
-            "Custom extractor — CSS: {} {}",
-            ext.selector,
-            ext.attribute
-                .as_deref()
-                .map(|a| format!("@{a}"))
-                .unwrap_or_else(|| "text content".into())
-        ),
+        description: format!("Custom extractor — {kind}: {}{reads}{output}", ext.selector),
         data_type,
         source: TagSource::Extractor {
             extractor_id: ext.id,
@@ -91,6 +99,7 @@ fn extractor_to_tag(ext: CustomExtractor) -> Tag {
 fn builtin_tags() -> Vec<Tag> {
     let mut tags = Vec::with_capacity(24);
     tags.extend(site_level_tags());
+    tags.extend(site_check_tags());
     tags.extend(page_level_tags());
     tags
 }
@@ -219,10 +228,19 @@ fn site_level_tags() -> Vec<Tag> {
             scopes: tpl_cond.clone(),
             example: Some("85".into()),
         },
+        Tag {
+            name: "pillar.security".into(),
+            label: "Security & Delivery Pillar".into(),
+            description: "Security & Delivery pillar score (0–100): response headers, caching and compression. N/A when no page had response headers.".into(),
+            data_type: TagDataType::Number,
+            source: TagSource::Builtin,
+            scopes: tpl_cond.clone(),
+            example: Some("70".into()),
+        },
         Tag {
             name: "pillar.overall".into(),
             label: "Overall Pillar Average".into(),
-            description: "Arithmetic mean of the four pillar scores.".into(),
+            description: "Arithmetic mean of the five pillar scores.".into(),
             data_type: TagDataType::Number,
             source: TagSource::Builtin,
             scopes: tpl_cond,
@@ -231,6 +249,30 @@ fn site_level_tags() -> Vec<Tag> {
     ]
 }
 
+/// One `site.<id>` tag per built-in site check, resolving to the check's
+/// status ("Found", "Missing", ...).
+fn site_check_tags() -> Vec<Tag> {
+    SiteCheckRegistry::with_defaults()
+        .iter()
+        .map(|check| Tag {
+            name: format!("site.{}", check.id()),
+            label: check.label().to_string(),
+            description: format!(
+                "Whether the site serves a valid {}: Found, Invalid, Unauthorized, Missing, Error or Not checked.",
+                check.label()
+            ),
+            data_type: TagDataType::Text,
+            source: TagSource::Builtin,
+            scopes: vec![
+                TagScope::TemplateText,
+                TagScope::AiPrompt,
+                TagScope::TemplateCondition,
+            ],
+            example: Some("Found".into()),
+        })
+        .collect()
+}
+
 fn page_level_tags() -> Vec<Tag> {
     let check = vec![TagScope::CheckField, TagScope::CheckMessage];
 
@@ -280,6 +322,24 @@ fn page_level_tags() -> Vec<Tag> {
             scopes: check.clone(),
             example: Some("850".into()),
         },
+        Tag {
+            name: "ttfb_ms".into(),
+            label: "Time to First Byte (ms)".into(),
+            description: "Time until the server sent the first byte of the page.".into(),
+            data_type: TagDataType::Number,
+            source: TagSource::Builtin,
+            scopes: check.clone(),
+            example: Some("320".into()),
+        },
+        Tag {
+            name: "download_ms".into(),
+            label: "Download Time (ms)".into(),
+            description: "Time spent downloading the page body after the first byte.".into(),
+            data_type: TagDataType::Number,
+            source: TagSource::Builtin,
+            scopes: check.clone(),
+            example: Some("140".into()),
+        },
         Tag {
             name: "status_code".into(),
             label: "HTTP Status Code".into(),
@@ -313,8 +373,17 @@ fn page_level_tags() -> Vec<Tag> {
             description: "Number of <h1> headings on the page.".into(),
             data_type: TagDataType::Number,
             source: TagSource::Builtin,
-            scopes: check,
+            scopes: check.clone(),
             example: Some("1".into()),
         },
+        Tag {
+            name: "headings".into(),
+            label: "Heading Outline".into(),
+            description: "The page's headings in document order, one \"H<level>: <text>\" line each.".into(),
+            data_type: TagDataType::Text,
+            source: TagSource::Builtin,
+            scopes: check,
+            example: Some("H1: Blue Widgets\nH2: Pricing".into()),
+        },
     ]
 }
@@ -3,16 +3,19 @@
 //! field or mis-scope a tag.
 
 use super::*;
-use crate::contexts::extension::CustomExtractor;
+use crate::contexts::extension::{CustomExtractor, ExtractorKind, ExtractorOutput};
 
 fn single_extractor_text() -> CustomExtractor {
     CustomExtractor {
         id: "ext-1".into(),
         name: "OG Image".into(),
         tag: "og_image".into(),
+        kind: ExtractorKind::Css,
         selector: "meta[property='og:image']".into(),
         attribute: Some("content".into()),
         multiple: false,
+        output: ExtractorOutput::Value,
+        data_type: None,
         enabled: true,
     }
 }
@@ -22,9 +25,12 @@ fn single_extractor_multi() -> CustomExtractor {
         id: "ext-2".into(),
         name: "Hreflang".into(),
         tag: "hreflang".into(),
+        kind: ExtractorKind::Css,
         selector: "link[rel='alternate'][hreflang]".into(),
         attribute: Some("hreflang".into()),
         multiple: true,
+        output: ExtractorOutput::Value,
+        data_type: None,
         enabled: true,
     }
 }
@@ -47,10 +53,14 @@ fn builtin_catalog_contains_the_site_level_variables() {
         "warning_issues",
         "sitemap_found",
         "robots_txt_found",
+        "site.robots_txt",
+        "site.sitemap_xml",
+        "site.security_txt",
         "pillar.technical",
         "pillar.content",
         "pillar.performance",
         "pillar.accessibility",
+        "pillar.security",
         "pillar.overall",
     ] {
         assert!(
@@ -74,10 +84,13 @@ fn builtin_catalog_contains_page_level_fields_used_by_resolve_field() {
         "canonical_url",
         "word_count",
         "load_time_ms",
+        "ttfb_ms",
+        "download_ms",
         "status_code",
         "has_viewport",
         "has_structured_data",
         "h1_count",
+        "headings",
     ] {
         let tag = registry
             .lookup(expected)
@@ -115,6 +128,44 @@ fn multi_valued_extractor_produces_list_typed_tag() {
     );
     let tag = registry.lookup("tag:hreflang").expect("extractor tag should exist");
     assert_eq!(tag.data_type, TagDataType::List);
+
+    // A declared type applies to each item; the tag still holds a list.
+    let prices = CustomExtractor { data_type: Some(TagDataType::Number), ..single_extractor_multi() };
+    let registry = TagRegistry::from_parts(vec![], vec![prices]);
+    assert_eq!(registry.lookup("tag:hreflang").unwrap().data_type, TagDataType::List);
+}
+
+#[test]
+fn extractor_tag_uses_the_declared_or_output_type() {
+    let price = CustomExtractor {
+        id: "ext-3".into(),
+        tag: "price".into(),
+        kind: ExtractorKind::JsonLd,
+        selector: "$.offers.price".into(),
+        attribute: None,
+        data_type: Some(TagDataType::Number),
+        ..single_extractor_text()
+    };
+    let count = CustomExtractor {
+        id: "ext-4".into(),
+        tag: "link_count".into(),
+        kind: ExtractorKind::Xpath,
+        selector: "//a".into(),
+        attribute: None,
+        multiple: true,
+        output: ExtractorOutput::Count,
+        ..single_extractor_text()
+    };
+    let registry = TagRegistry::from_parts(vec![], vec![price, count]);
+
+    let tag = registry.lookup("tag:price").unwrap();
+    assert_eq!(tag.data_type, TagDataType::Number);
+    assert_eq!(tag.description, "Custom extractor — JSON-LD: $.offers.price");
+
+    // A count is a single number even when the extractor collects every match.
+    let tag = registry.lookup("tag:link_count").unwrap();
+    assert_eq!(tag.data_type, TagDataType::Number);
+    assert_eq!(tag.description, "Custom extractor — XPath: //a (count)");
 }
 
 #[test]
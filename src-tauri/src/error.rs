@@ -58,6 +58,24 @@ impl From<crate::contexts::analysis::PaginationError> for CommandError {
     }
 }
 
+impl From<crate::contexts::extension::ConditionError> for CommandError {
+    fn from(error: crate::contexts::extension::ConditionError) -> Self {
+        Self(format!("[SVC_INVALID_QUERY] invalid condition: {}", error))
+    }
+}
+
+impl From<crate::extractor::data_extractor::ExtractorError> for CommandError {
+    fn from(error: crate::extractor::data_extractor::ExtractorError) -> Self {
+        Self(format!("[SVC_INVALID_QUERY] invalid extractor: {}", error))
+    }
+}
+
+impl From<crate::contexts::rule_pack::RulePackError> for CommandError {
+    fn from(error: crate::contexts::rule_pack::RulePackError) -> Self {
+        Self(format!("[{}] {}", error.code(), error))
+    }
+}
+
 impl std::error::Error for CommandError {}
 
 impl fmt::Display for CommandError {
@@ -179,6 +197,24 @@ mod tests {
         assert!(s.starts_with("[SVC_INVALID_QUERY]"));
     }
 
+    #[test]
+    fn from_condition_error_prefixes_with_svc_invalid_query_code() {
+        use crate::contexts::extension::ConditionError;
+        let cmd: CommandError = ConditionError::EmptyList.into();
+        let s = format!("{cmd}");
+        assert!(s.starts_with("[SVC_INVALID_QUERY]"));
+        assert!(s.contains("in_list"));
+    }
+
+    #[test]
+    fn from_extractor_error_prefixes_with_svc_invalid_query_code() {
+        use crate::extractor::data_extractor::ExtractorError;
+        let cmd: CommandError = ExtractorError::MissingHeaderName.into();
+        let s = format!("{cmd}");
+        assert!(s.starts_with("[SVC_INVALID_QUERY] invalid extractor:"));
+        assert!(s.contains("header name"));
+    }
+
     #[test]
     fn from_anyhow_uses_alternate_format_for_full_chain() {
         // The `{:#}` Display format on anyhow::Error renders the full
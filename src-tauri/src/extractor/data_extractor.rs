@@ -1,57 +1,271 @@
 use std::collections::HashMap;
 use serde_json::Value;
 
+use crate::contexts::analysis::ResponseHeaders;
+use crate::contexts::extension::{
+    resolve_data_type, CustomExtractor, CustomExtractorParams, ExtractorKind, ExtractorOutput,
+};
+use crate::contexts::tags::TagDataType;
+
+pub mod header;
+pub mod html_regex;
+pub mod json_ld;
 pub mod selector;
+pub mod xpath;
 
-/// Configuration for a single CSS-selector-based extractor.
+/// Configuration for a single extractor.
 #[derive(Debug, Clone)]
 pub struct ExtractorConfig {
     /// The tag under which the result is stored in `extracted_data`.
     /// Matches `CustomExtractor.tag` for user-defined extractors.
     pub tag: String,
-    /// CSS selector to match elements.
+    /// How `selector` is evaluated.
+    pub kind: ExtractorKind,
+    /// CSS selector to match elements, or the expression for `kind`.
     pub selector: String,
     /// HTML attribute to read. `None` means use the element's text content.
+    /// For regex extractors, the capture group to read.
     pub attribute: Option<String>,
     /// Collect all matches (`true`) or only the first (`false`).
     pub multiple: bool,
+    /// Publish the values, their count or their lengths.
+    pub output: ExtractorOutput,
+    /// Convert values to this type; `None` infers it.
+    pub data_type: Option<TagDataType>,
 }
 
 impl ExtractorConfig {
-    pub fn text(tag: &str, selector: &str) -> Self {
-        Self { tag: tag.into(), selector: selector.into(), attribute: None, multiple: false }
-    }
-
-    pub fn attr(tag: &str, selector: &str, attribute: &str) -> Self {
+    fn css(tag: &str, selector: &str, attribute: Option<&str>, multiple: bool) -> Self {
         Self {
             tag: tag.into(),
+            kind: ExtractorKind::Css,
             selector: selector.into(),
-            attribute: Some(attribute.into()),
-            multiple: false,
+            attribute: attribute.map(Into::into),
+            multiple,
+            output: ExtractorOutput::Value,
+            data_type: None,
         }
     }
 
+    pub fn text(tag: &str, selector: &str) -> Self {
+        Self::css(tag, selector, None, false)
+    }
+
+    pub fn attr(tag: &str, selector: &str, attribute: &str) -> Self {
+        Self::css(tag, selector, Some(attribute), false)
+    }
+
     pub fn multi_text(tag: &str, selector: &str) -> Self {
-        Self { tag: tag.into(), selector: selector.into(), attribute: None, multiple: true }
+        Self::css(tag, selector, None, true)
     }
 
     pub fn multi_attr(tag: &str, selector: &str, attribute: &str) -> Self {
+        Self::css(tag, selector, Some(attribute), true)
+    }
+
+    /// Turn an extractor's matches into its `extracted_data` entry.
+    ///
+    /// `matches` holds one item per matched node, `None` for a node that
+    /// yielded no value (an element with no text); those count as
+    /// matches but are otherwise skipped. Values are converted to the
+    /// declared data type and dropped if they don't convert; collecting
+    /// every match converts each item, so a `multiple` number extractor
+    /// stores a list of numbers. Nothing is stored when no value remains,
+    /// except for counts.
+    pub(crate) fn finish(&self, matches: Vec<Option<Value>>) -> HashMap<String, Value> {
+        let data_type = resolve_data_type(self.data_type, self.output, self.multiple);
+        let item_type = match self.data_type {
+            Some(declared) if self.multiple => declared,
+            _ => data_type,
+        };
+        let value = match self.output {
+            ExtractorOutput::Count => coerce(Value::from(matches.len()), data_type),
+            ExtractorOutput::Value | ExtractorOutput::Length => {
+                let mut values = matches
+                    .into_iter()
+                    .flatten()
+                    .map(|value| match self.output {
+                        ExtractorOutput::Length => Value::from(display(&value).chars().count()),
+                        _ => value,
+                    })
+                    .filter_map(|value| coerce(value, item_type));
+                if self.multiple || data_type == TagDataType::List {
+                    let values: Vec<Value> = values.collect();
+                    (!values.is_empty()).then_some(Value::Array(values))
+                } else {
+                    values.next()
+                }
+            }
+        };
+        value.map(|v| HashMap::from([(self.tag.clone(), v)])).unwrap_or_default()
+    }
+}
+
+impl From<CustomExtractor> for ExtractorConfig {
+    fn from(ext: CustomExtractor) -> Self {
         Self {
-            tag: tag.into(),
-            selector: selector.into(),
-            attribute: Some(attribute.into()),
-            multiple: true,
+            tag: ext.tag,
+            kind: ext.kind,
+            selector: ext.selector,
+            attribute: ext.attribute,
+            multiple: ext.multiple,
+            output: ext.output,
+            data_type: ext.data_type,
+        }
+    }
+}
+
+impl From<&CustomExtractorParams> for ExtractorConfig {
+    fn from(params: &CustomExtractorParams) -> Self {
+        Self {
+            tag: params.tag.clone(),
+            kind: params.kind,
+            selector: params.selector.clone(),
+            attribute: params.attribute.clone(),
+            multiple: params.multiple,
+            output: params.output,
+            data_type: params.data_type,
         }
     }
 }
 
-/// Extracts structured data from raw HTML.
+/// A value as text: strings as they are, anything else as JSON.
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Convert one extracted value to `data_type`, or `None` if it doesn't
+/// convert. Lists keep their items as extracted.
+fn coerce(value: Value, data_type: TagDataType) -> Option<Value> {
+    match data_type {
+        TagDataType::List => Some(value),
+        TagDataType::Text => Some(Value::String(display(&value))),
+        TagDataType::Number => match value {
+            Value::Number(n) => Some(Value::Number(n)),
+            Value::String(s) => parse_number(&s),
+            _ => None,
+        },
+        TagDataType::Bool => match value {
+            Value::Bool(b) => Some(Value::Bool(b)),
+            Value::Number(n) => n.as_f64().map(|n| Value::Bool(n != 0.0)),
+            Value::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" => Some(Value::Bool(true)),
+                "false" | "no" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            _ => None,
+        },
+    }
+}
+
+/// Parse extracted text as a number, ignoring surrounding whitespace and
+/// thousands separators. Whole numbers stay integers so they read back
+/// as `1299` rather than `1299.0`.
+///
+/// Both `.` and `,` can be the decimal separator: with both present the
+/// last one is (`"1.299,50"`, `"1,299.50"`), a lone comma followed by
+/// anything but three digits is (`"19,99"`), and repeated or three-digit
+/// groups are thousands (`"1,299"`, `"1.299.000"`). Separators that don't
+/// group in threes make the text ambiguous, and it isn't a number.
+fn parse_number(text: &str) -> Option<Value> {
+    let cleaned = normalize_separators(text.trim())?;
+    let n: f64 = cleaned.parse().ok().filter(|n: &f64| n.is_finite())?;
+    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        Some(Value::from(n as i64))
+    } else {
+        serde_json::Number::from_f64(n).map(Value::Number)
+    }
+}
+
+/// `text` with its thousands separators removed and its decimal
+/// separator as `.`, or `None` if the separators are ambiguous.
+fn normalize_separators(text: &str) -> Option<String> {
+    let (thousands, decimal) = match (text.rfind(','), text.rfind('.')) {
+        (Some(comma), Some(dot)) if comma > dot => (Some('.'), Some(',')),
+        (Some(_), Some(_)) => (Some(','), Some('.')),
+        (Some(comma), None) => {
+            let lone = text.matches(',').count() == 1;
+            if lone && text[comma + 1..].len() != 3 { (None, Some(',')) } else { (Some(','), None) }
+        }
+        (None, Some(_)) if text.matches('.').count() > 1 => (Some('.'), None),
+        (None, _) => (None, Some('.')),
+    };
+    let (integer, fraction) = match decimal {
+        Some(decimal) => {
+            let mut parts = text.splitn(2, decimal);
+            let integer = parts.next().unwrap_or_default();
+            let fraction = parts.next();
+            if fraction.is_some_and(|f| f.contains([',', '.'])) {
+                return None;
+            }
+            (integer, fraction)
+        }
+        None => (text, None),
+    };
+    let integer = match thousands.filter(|sep| integer.contains(*sep)) {
+        Some(sep) => {
+            let digits = integer.trim_start_matches(['-', '+']);
+            let mut groups = digits.split(sep);
+            let first = groups.next().unwrap_or_default();
+            let grouped = (1..=3).contains(&first.len())
+                && groups.all(|group| group.len() == 3 && group.chars().all(|c| c.is_ascii_digit()));
+            if !grouped {
+                return None;
+            }
+            integer.replace(sep, "")
+        }
+        None => integer.to_string(),
+    };
+    Some(match fraction {
+        Some(fraction) => format!("{integer}.{fraction}"),
+        None => integer,
+    })
+}
+
+/// Why an extractor's expression couldn't be compiled.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum ExtractorError {
+    #[error("invalid CSS selector '{0}'")]
+    InvalidSelector(String),
+    #[error("invalid XPath '{expression}': {message}")]
+    InvalidXPath { expression: String, message: String },
+    #[error("invalid regex '{pattern}': {message}")]
+    InvalidRegex { pattern: String, message: String },
+    #[error("regex '{pattern}' has no capture group '{group}'")]
+    UnknownCaptureGroup { pattern: String, group: String },
+    #[error("invalid JSON path '{path}': {message}")]
+    InvalidJsonPath { path: String, message: String },
+    #[error("header extractors need a header name")]
+    MissingHeaderName,
+}
+
+/// Build the extractor for `config`'s kind, compiling its expression.
+/// Compiling is also how extractors are validated when they're saved.
+pub fn build(config: ExtractorConfig) -> Result<Box<dyn DataExtractor>, ExtractorError> {
+    Ok(match config.kind {
+        ExtractorKind::Css => {
+            if scraper::Selector::parse(&config.selector).is_err() {
+                return Err(ExtractorError::InvalidSelector(config.selector));
+            }
+            Box::new(selector::SelectorExtractor::new(config))
+        }
+        ExtractorKind::Xpath => Box::new(xpath::XPathExtractor::new(config)?),
+        ExtractorKind::Regex => Box::new(html_regex::RegexExtractor::new(config)?),
+        ExtractorKind::JsonLd => Box::new(json_ld::JsonLdExtractor::new(config)?),
+        ExtractorKind::Header => Box::new(header::HeaderExtractor::new(config)?),
+    })
+}
+
+/// Extracts structured data from a fetched page.
 pub trait DataExtractor: Send + Sync {
     fn id(&self) -> &str;
-    fn extract(&self, html: &str) -> HashMap<String, Value>;
+    fn extract(&self, html: &str, headers: &ResponseHeaders) -> HashMap<String, Value>;
 }
 
-/// Runs all registered extractors against raw HTML and merges their results.
+/// Runs all registered extractors against a page and merges their results.
 #[derive(Default)]
 pub struct ExtractorRegistry {
     extractors: Vec<Box<dyn DataExtractor>>,
@@ -66,11 +280,25 @@ impl ExtractorRegistry {
         self.extractors.push(extractor);
     }
 
+    /// A registry of `extractors`, skipping those whose expression
+    /// doesn't compile.
+    pub fn from_extractors(extractors: Vec<CustomExtractor>) -> Self {
+        let mut registry = Self::new();
+        for ext in extractors {
+            let tag = ext.tag.clone();
+            match build(ExtractorConfig::from(ext)) {
+                Ok(extractor) => registry.register(extractor),
+                Err(e) => tracing::warn!("Skipping custom extractor '{}': {}", tag, e),
+            }
+        }
+        registry
+    }
+
     /// Run all extractors and merge results. Later extractors win on tag conflicts.
-    pub fn run(&self, html: &str) -> HashMap<String, Value> {
+    pub fn run(&self, html: &str, headers: &ResponseHeaders) -> HashMap<String, Value> {
         let mut result = HashMap::new();
         for extractor in &self.extractors {
-            result.extend(extractor.extract(html));
+            result.extend(extractor.extract(html, headers));
         }
         result
     }
@@ -109,7 +337,7 @@ mod tests {
     #[test]
     fn empty_registry_returns_empty_map() {
         let registry = ExtractorRegistry::new();
-        let result = registry.run(HTML);
+        let result = registry.run(HTML, &ResponseHeaders::default());
         assert!(result.is_empty());
     }
 
@@ -123,7 +351,7 @@ mod tests {
             ExtractorConfig::text("h1", "h1"),
         )));
 
-        let result = registry.run(HTML);
+        let result = registry.run(HTML, &ResponseHeaders::default());
         assert!(result.contains_key("title"));
         assert!(result.contains_key("h1"));
     }
@@ -138,7 +366,7 @@ mod tests {
             ExtractorConfig::text("heading", "h2"),
         )));
 
-        let result = registry.run(HTML);
+        let result = registry.run(HTML, &ResponseHeaders::default());
         // h2 extractor registered last — its value wins
         let val = result["heading"].as_str().unwrap();
         assert_eq!(val, "Sub Heading One");
@@ -168,4 +396,66 @@ mod tests {
         assert!(cfg.multiple);
         assert_eq!(cfg.attribute, Some("href".into()));
     }
+
+    #[test]
+    fn finish_counts_every_match_and_measures_lengths() {
+        let count = ExtractorConfig { output: ExtractorOutput::Count, ..ExtractorConfig::text("n", "h2") };
+        assert_eq!(count.finish(vec![Some("a".into()), None])["n"], 2);
+        // A count is stored even when nothing matched.
+        assert_eq!(count.finish(vec![])["n"], 0);
+
+        let length = ExtractorConfig { output: ExtractorOutput::Length, ..ExtractorConfig::multi_text("n", "h2") };
+        assert_eq!(length.finish(vec![Some("four".into()), None, Some("é".into())])["n"], serde_json::json!([4, 1]));
+    }
+
+    #[test]
+    fn finish_converts_to_the_declared_type() {
+        let number = ExtractorConfig { data_type: Some(TagDataType::Number), ..ExtractorConfig::text("n", "p") };
+        // Values that don't convert are skipped, so the first number wins.
+        assert_eq!(number.finish(vec![Some("n/a".into()), Some(" 2,499.50 ".into())])["n"], 2499.5);
+        assert!(number.finish(vec![Some("n/a".into())]).is_empty());
+
+        let flag = ExtractorConfig { data_type: Some(TagDataType::Bool), ..ExtractorConfig::text("n", "p") };
+        assert_eq!(flag.finish(vec![Some("Yes".into())])["n"], true);
+
+        // A declared list collects every value even in single mode.
+        let list = ExtractorConfig { data_type: Some(TagDataType::List), ..ExtractorConfig::text("n", "p") };
+        assert_eq!(list.finish(vec![Some("a".into()), Some("b".into())])["n"], serde_json::json!(["a", "b"]));
+    }
+
+    #[test]
+    fn finish_converts_each_item_when_collecting_every_match() {
+        let numbers = ExtractorConfig { data_type: Some(TagDataType::Number), ..ExtractorConfig::multi_text("n", "p") };
+        let result = numbers.finish(vec![Some("19,99".into()), Some("n/a".into()), Some("1.299,50".into())]);
+        assert_eq!(result["n"], serde_json::json!([19.99, 1299.5]));
+    }
+
+    #[test]
+    fn parse_number_respects_the_decimal_separator() {
+        for (text, expected) in [
+            ("19,99", serde_json::json!(19.99)),
+            ("19.99", serde_json::json!(19.99)),
+            ("1,299", serde_json::json!(1299)),
+            ("1.299.000", serde_json::json!(1299000)),
+            ("1.299,50", serde_json::json!(1299.5)),
+            ("-2,499.50", serde_json::json!(-2499.5)),
+        ] {
+            assert_eq!(parse_number(text), Some(expected), "{text}");
+        }
+        // Separators that don't group in threes are ambiguous.
+        for text in ["1,2,3", "12,34.5", "1.2.3", "1,5.6,7"] {
+            assert_eq!(parse_number(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn build_rejects_invalid_expressions() {
+        assert!(matches!(
+            build(ExtractorConfig::text("k", "h1[")),
+            Err(ExtractorError::InvalidSelector(_))
+        ));
+        let xpath = ExtractorConfig { kind: ExtractorKind::Xpath, ..ExtractorConfig::text("k", "h1") };
+        assert!(matches!(build(xpath), Err(ExtractorError::InvalidXPath { .. })));
+        assert!(build(ExtractorConfig::text("k", "h1")).is_ok());
+    }
 }
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use serde_json::Value;
+
+use super::{DataExtractor, ExtractorConfig, ExtractorError};
+use crate::contexts::analysis::ResponseHeaders;
+
+/// Reads a response header, named by the config's `selector`
+/// (case-insensitive). A header sent more than once yields one match
+/// per occurrence. Pages fetched without their headers yield nothing.
+pub struct HeaderExtractor {
+    config: ExtractorConfig,
+}
+
+impl HeaderExtractor {
+    pub fn new(mut config: ExtractorConfig) -> Result<Self, ExtractorError> {
+        config.selector = config.selector.trim().to_string();
+        if config.selector.is_empty() {
+            return Err(ExtractorError::MissingHeaderName);
+        }
+        Ok(Self { config })
+    }
+}
+
+impl DataExtractor for HeaderExtractor {
+    fn id(&self) -> &str {
+        &self.config.tag
+    }
+
+    fn extract(&self, _html: &str, headers: &ResponseHeaders) -> HashMap<String, Value> {
+        let matches = headers
+            .get_all(&self.config.selector)
+            .map(|value| Some(Value::String(value.trim().to_string())))
+            .collect();
+        self.config.finish(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::extension::{ExtractorKind, ExtractorOutput};
+    use crate::contexts::tags::TagDataType;
+
+    fn config(selector: &str) -> ExtractorConfig {
+        ExtractorConfig { kind: ExtractorKind::Header, ..ExtractorConfig::text("h", selector) }
+    }
+
+    fn headers() -> ResponseHeaders {
+        ResponseHeaders::new([
+            ("Content-Length", " 5120 "),
+            ("Set-Cookie", "a=1"),
+            ("Set-Cookie", "b=2"),
+        ])
+    }
+
+    #[test]
+    fn reads_a_header_case_insensitively() {
+        let extractor = HeaderExtractor::new(config("content-length")).unwrap();
+        assert_eq!(extractor.extract("", &headers())["h"], "5120");
+
+        let numeric = ExtractorConfig { data_type: Some(TagDataType::Number), ..config("Content-Length") };
+        let extractor = HeaderExtractor::new(numeric).unwrap();
+        assert_eq!(extractor.extract("", &headers())["h"], 5120);
+    }
+
+    #[test]
+    fn repeated_headers_are_separate_matches() {
+        let count = ExtractorConfig { output: ExtractorOutput::Count, ..config("set-cookie") };
+        let extractor = HeaderExtractor::new(count).unwrap();
+        assert_eq!(extractor.extract("", &headers())["h"], 2);
+
+        let extractor = HeaderExtractor::new(config("x-missing")).unwrap();
+        assert!(extractor.extract("", &headers()).is_empty());
+    }
+
+    #[test]
+    fn needs_a_header_name() {
+        assert_eq!(HeaderExtractor::new(config("  ")).err(), Some(ExtractorError::MissingHeaderName));
+    }
+}
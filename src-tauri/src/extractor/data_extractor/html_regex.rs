@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use regex::Regex;
+use serde_json::Value;
+
+use super::{DataExtractor, ExtractorConfig, ExtractorError};
+use crate::contexts::analysis::ResponseHeaders;
+
+/// Matches a regex against the raw HTML, for values no selector can
+/// reach: inline script variables, comments, text split across tags.
+///
+/// The config's `attribute` names the capture group to read, by name or
+/// number. Without one, the first group is read, or the whole match when
+/// the pattern has no groups.
+pub struct RegexExtractor {
+    config: ExtractorConfig,
+    regex: Regex,
+    group: Group,
+}
+
+enum Group {
+    Index(usize),
+    Name(String),
+}
+
+impl RegexExtractor {
+    pub fn new(config: ExtractorConfig) -> Result<Self, ExtractorError> {
+        let regex = Regex::new(&config.selector).map_err(|e| ExtractorError::InvalidRegex {
+            pattern: config.selector.clone(),
+            message: e.to_string(),
+        })?;
+        let unknown = |group: &str| ExtractorError::UnknownCaptureGroup {
+            pattern: config.selector.clone(),
+            group: group.to_string(),
+        };
+        let group = match config.attribute.as_deref().map(str::trim) {
+            None | Some("") => Group::Index(usize::from(regex.captures_len() > 1)),
+            Some(raw) => match raw.parse::<usize>() {
+                Ok(index) if index < regex.captures_len() => Group::Index(index),
+                Ok(_) => return Err(unknown(raw)),
+                Err(_) if regex.capture_names().flatten().any(|name| name == raw) => {
+                    Group::Name(raw.to_string())
+                }
+                Err(_) => return Err(unknown(raw)),
+            },
+        };
+        Ok(Self { config, regex, group })
+    }
+}
+
+impl DataExtractor for RegexExtractor {
+    fn id(&self) -> &str {
+        &self.config.tag
+    }
+
+    fn extract(&self, html: &str, _headers: &ResponseHeaders) -> HashMap<String, Value> {
+        let matches = self
+            .regex
+            .captures_iter(html)
+            .map(|captures| {
+                let group = match &self.group {
+                    Group::Index(index) => captures.get(*index),
+                    Group::Name(name) => captures.name(name),
+                };
+                // A group that didn't take part in the match, or matched
+                // only whitespace, still counts as a match.
+                group
+                    .map(|m| m.as_str().trim())
+                    .filter(|text| !text.is_empty())
+                    .map(|text| Value::String(text.to_string()))
+            })
+            .collect();
+        self.config.finish(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::extension::{ExtractorKind, ExtractorOutput};
+    use crate::contexts::tags::TagDataType;
+
+    const HTML: &str = r#"
+        <script>window.dataLayer = [{"pageType": "product", "price": "1,299.00"}];</script>
+        <p>SKU: AB-12</p><p>SKU: CD-34</p>
+    "#;
+
+    fn config(pattern: &str, group: Option<&str>) -> ExtractorConfig {
+        ExtractorConfig {
+            kind: ExtractorKind::Regex,
+            attribute: group.map(str::to_string),
+            ..ExtractorConfig::text("r", pattern)
+        }
+    }
+
+    fn extract(config: ExtractorConfig) -> HashMap<String, Value> {
+        RegexExtractor::new(config).unwrap().extract(HTML, &ResponseHeaders::default())
+    }
+
+    #[test]
+    fn reads_the_first_group_by_default() {
+        let result = extract(config(r#""pageType":\s*"(\w+)""#, None));
+        assert_eq!(result["r"], "product");
+
+        // Without groups, the whole match.
+        let result = extract(config(r"SKU: [A-Z]{2}-\d+", None));
+        assert_eq!(result["r"], "SKU: AB-12");
+    }
+
+    #[test]
+    fn reads_a_named_or_numbered_group() {
+        let pattern = r"SKU: (?P<prefix>[A-Z]{2})-(?P<number>\d+)";
+        let multiple = ExtractorConfig { multiple: true, ..config(pattern, Some("number")) };
+        assert_eq!(extract(multiple)["r"], serde_json::json!(["12", "34"]));
+        assert_eq!(extract(config(pattern, Some("1")))["r"], "AB");
+    }
+
+    #[test]
+    fn converts_captures_to_the_declared_type() {
+        let price = ExtractorConfig {
+            data_type: Some(TagDataType::Number),
+            ..config(r#""price":\s*"([^"]+)""#, None)
+        };
+        assert_eq!(extract(price)["r"], 1299);
+
+        let count = ExtractorConfig { output: ExtractorOutput::Count, ..config(r"SKU:", None) };
+        assert_eq!(extract(count)["r"], 2);
+    }
+
+    #[test]
+    fn rejects_bad_patterns_and_groups() {
+        assert!(matches!(
+            RegexExtractor::new(config("(unclosed", None)),
+            Err(ExtractorError::InvalidRegex { .. })
+        ));
+        assert!(matches!(
+            RegexExtractor::new(config(r"(\d+)", Some("2"))),
+            Err(ExtractorError::UnknownCaptureGroup { .. })
+        ));
+        assert!(matches!(
+            RegexExtractor::new(config(r"(\d+)", Some("price"))),
+            Err(ExtractorError::UnknownCaptureGroup { .. })
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use scraper::Html;
+use serde_json::Value;
+
+use super::{DataExtractor, ExtractorConfig, ExtractorError};
+use crate::contexts::analysis::ResponseHeaders;
+
+/// Queries the page's JSON-LD blocks with a JSON path, e.g.
+/// `$.author.name` or `$..@type`.
+///
+/// The path runs against every block, and against each item of a
+/// block's `@graph` or top-level array rather than the wrapper, so
+/// `$.@type` reads each entity's type however the site nests them.
+/// A result that is an array counts as one match per item.
+pub struct JsonLdExtractor {
+    config: ExtractorConfig,
+    path: JsonPath,
+}
+
+impl JsonLdExtractor {
+    pub fn new(config: ExtractorConfig) -> Result<Self, ExtractorError> {
+        let path = JsonPath::parse(&config.selector).map_err(|message| {
+            ExtractorError::InvalidJsonPath { path: config.selector.clone(), message }
+        })?;
+        Ok(Self { config, path })
+    }
+}
+
+impl DataExtractor for JsonLdExtractor {
+    fn id(&self) -> &str {
+        &self.config.tag
+    }
+
+    fn extract(&self, html: &str, _headers: &ResponseHeaders) -> HashMap<String, Value> {
+        let document = Html::parse_document(html);
+        let blocks: Vec<Value> = document
+            .select(cached_selector!("script[type='application/ld+json']"))
+            .filter_map(|el| serde_json::from_str(&el.text().collect::<String>()).ok())
+            .collect();
+
+        let matches = blocks
+            .iter()
+            .flat_map(entities)
+            .flat_map(|entity| self.path.select(entity))
+            .flat_map(|value| -> Vec<&Value> {
+                match value {
+                    Value::Array(items) => items.iter().collect(),
+                    other => vec![other],
+                }
+            })
+            .map(|value| (!value.is_null()).then(|| value.clone()))
+            .collect();
+        self.config.finish(matches)
+    }
+}
+
+/// The entities of a JSON-LD block: the items of its `@graph` or of a
+/// top-level array, otherwise the block itself.
+fn entities(block: &Value) -> Vec<&Value> {
+    match block {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(map) => match map.get("@graph") {
+            Some(Value::Array(items)) => items.iter().collect(),
+            _ => vec![block],
+        },
+        _ => vec![],
+    }
+}
+
+/// A JSON path: `$` followed by `.key`, `['key']`, `[index]` (negative
+/// from the end), `.*` / `[*]`, or `..` before any of them to search at
+/// every depth. Keys may contain `@` and `:` (`$.@type`, `$.schema:name`),
+/// and the leading `$.` can be left out.
+///
+/// That's the whole grammar: filters (`[?(@.price)]`), slices (`[0:2]`)
+/// and unions (`['a','b']`, `[0,1]`) are rejected when the path is
+/// parsed.
+///
+/// JSON-LD wraps values in arrays whenever there's more than one, so a
+/// key is also looked up in each object of an array: `$.author.name`
+/// reads the name of every author.
+pub(crate) struct JsonPath(Vec<Segment>);
+
+enum Segment {
+    Child(Step),
+    Descendant(Step),
+}
+
+enum Step {
+    Key(String),
+    Index(i64),
+    Wildcard,
+}
+
+impl JsonPath {
+    pub(crate) fn parse(path: &str) -> Result<Self, String> {
+        let path = path.trim();
+        if path.is_empty() {
+            return Err("path is empty".into());
+        }
+        let rest = match path.strip_prefix('$') {
+            Some(rest) => rest.to_string(),
+            None if path.starts_with(['.', '[']) => path.to_string(),
+            None => format!(".{path}"),
+        };
+        Self::parse_segments(&rest).map(Self)
+    }
+
+    fn parse_segments(mut rest: &str) -> Result<Vec<Segment>, String> {
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            let (descendant, after) = match rest.strip_prefix("..") {
+                Some(after) => (true, after),
+                None => match rest.strip_prefix('.') {
+                    Some(after) => (false, after),
+                    None if rest.starts_with('[') => (false, rest),
+                    None => return Err(format!("expected '.' or '[' at '{rest}'")),
+                },
+            };
+            let (step, after) = if let Some(inner) = after.strip_prefix('[') {
+                let end = inner.find(']').ok_or("unclosed '['")?;
+                (Self::parse_bracket(inner[..end].trim())?, &inner[end + 1..])
+            } else {
+                let end = after.find(['.', '[']).unwrap_or(after.len());
+                let name = after[..end].trim();
+                let step = match name {
+                    "" => return Err(format!("missing key at '{rest}'")),
+                    "*" => Step::Wildcard,
+                    name => Step::Key(name.to_string()),
+                };
+                (step, &after[end..])
+            };
+            segments.push(if descendant { Segment::Descendant(step) } else { Segment::Child(step) });
+            rest = after;
+        }
+        Ok(segments)
+    }
+
+    fn parse_bracket(inner: &str) -> Result<Step, String> {
+        if inner == "*" {
+            return Ok(Step::Wildcard);
+        }
+        for quote in ['\'', '"'] {
+            if let Some(key) = inner.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+                if key.contains(quote) {
+                    return Err(format!("unions aren't supported in '[{inner}]'"));
+                }
+                return Ok(Step::Key(key.to_string()));
+            }
+        }
+        inner
+            .parse()
+            .map(Step::Index)
+            .map_err(|_| format!("expected an index, '*' or a quoted key in '[{inner}]'"))
+    }
+
+    /// Every value the path selects from `root`, in document order.
+    pub(crate) fn select<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![root];
+        for segment in &self.0 {
+            let mut next = Vec::new();
+            for value in current {
+                match segment {
+                    Segment::Child(step) => step.apply(value, true, &mut next),
+                    Segment::Descendant(step) => {
+                        // Every array is itself visited, so looking through
+                        // them as well would count values twice.
+                        let mut nodes = Vec::new();
+                        descendants_or_self(value, &mut nodes);
+                        for node in nodes {
+                            step.apply(node, false, &mut next);
+                        }
+                    }
+                }
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+impl Step {
+    fn apply<'a>(&self, value: &'a Value, through_arrays: bool, out: &mut Vec<&'a Value>) {
+        match (self, value) {
+            (Self::Key(key), Value::Object(map)) => out.extend(map.get(key)),
+            (Self::Key(_), Value::Array(items)) if through_arrays => {
+                for item in items.iter().filter(|item| item.is_object()) {
+                    self.apply(item, false, out);
+                }
+            }
+            (Self::Index(index), Value::Array(items)) => {
+                let index = if *index < 0 { items.len() as i64 + index } else { *index };
+                out.extend(usize::try_from(index).ok().and_then(|i| items.get(i)));
+            }
+            (Self::Wildcard, Value::Object(map)) => out.extend(map.values()),
+            (Self::Wildcard, Value::Array(items)) => out.extend(items),
+            _ => {}
+        }
+    }
+}
+
+fn descendants_or_self<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(value);
+    match value {
+        Value::Object(map) => map.values().for_each(|v| descendants_or_self(v, out)),
+        Value::Array(items) => items.iter().for_each(|v| descendants_or_self(v, out)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::extension::{ExtractorKind, ExtractorOutput};
+    use crate::contexts::tags::TagDataType;
+    use serde_json::json;
+
+    const HTML: &str = r#"
+        <script type="application/ld+json">
+          {"@context": "https://schema.org", "@graph": [
+            {"@type": "Product", "name": "Widget",
+             "offers": {"@type": "Offer", "price": "19.99"},
+             "aggregateRating": {"ratingValue": 4.5, "reviewCount": "1,024"}},
+            {"@type": "BreadcrumbList", "itemListElement": [{"name": "Home"}, {"name": "Widgets"}]}
+          ]}
+        </script>
+        <script type="application/ld+json">
+          {"@type": "Article", "author": [{"name": "Ada"}, {"name": "Grace"}]}
+        </script>
+        <script type="application/ld+json">{ not json</script>
+    "#;
+
+    fn extract(config: ExtractorConfig) -> HashMap<String, Value> {
+        JsonLdExtractor::new(config).unwrap().extract(HTML, &ResponseHeaders::default())
+    }
+
+    fn config(path: &str) -> ExtractorConfig {
+        ExtractorConfig { kind: ExtractorKind::JsonLd, ..ExtractorConfig::multi_text("j", path) }
+    }
+
+    #[test]
+    fn reads_each_entity_of_every_block() {
+        assert_eq!(extract(config("$.@type"))["j"], json!(["Product", "BreadcrumbList", "Article"]));
+        assert_eq!(extract(config("author.name"))["j"], json!(["Ada", "Grace"]));
+        assert_eq!(extract(config("$['offers'].price"))["j"], json!(["19.99"]));
+    }
+
+    #[test]
+    fn searches_every_depth_and_indexes_arrays() {
+        let names = extract(config("$..name"))["j"].clone();
+        assert_eq!(names, json!(["Widget", "Home", "Widgets", "Ada", "Grace"]));
+        assert_eq!(extract(config("$.itemListElement[-1].name"))["j"], json!(["Widgets"]));
+    }
+
+    #[test]
+    fn counts_and_converts_values() {
+        let count = ExtractorConfig { output: ExtractorOutput::Count, ..config("$.itemListElement") };
+        assert_eq!(extract(count)["j"], 2);
+
+        let reviews = ExtractorConfig {
+            multiple: false,
+            data_type: Some(TagDataType::Number),
+            ..config("$.aggregateRating.reviewCount")
+        };
+        assert_eq!(extract(reviews)["j"], 1024);
+    }
+
+    #[test]
+    fn rejects_malformed_paths() {
+        for path in ["", "$.", "$[unclosed", "$[abc]", "$x"] {
+            assert!(JsonPath::parse(path).is_err(), "{path} should not parse");
+        }
+    }
+
+    #[test]
+    fn rejects_syntax_outside_the_supported_subset() {
+        for path in ["$[?(@.price)]", "$.offers[0:2]", "$['name','url']", "$.author[0,1]"] {
+            assert!(JsonPath::parse(path).is_err(), "{path} should not parse");
+        }
+    }
+}
@@ -3,6 +3,7 @@ use scraper::{Html, Selector};
 use serde_json::Value;
 
 use super::{DataExtractor, ExtractorConfig};
+use crate::contexts::analysis::ResponseHeaders;
 
 /// Extracts data from HTML using a CSS selector.
 ///
@@ -10,6 +11,9 @@ use super::{DataExtractor, ExtractorConfig};
 /// - Multiple mode: returns all matching elements as a JSON array of strings.
 /// - If no element matches, the key is omitted from the result (not inserted as null).
 ///
+/// Counts, lengths and type conversion are applied by
+/// [`ExtractorConfig::finish`], as for every extractor kind.
+///
 /// The CSS selector is parsed once at construction time and reused for
 /// every `extract` call. Previously the selector was re-parsed per page,
 /// which is wasted work in a multi-page crawl. An invalid selector is
@@ -46,7 +50,7 @@ impl DataExtractor for SelectorExtractor {
         &self.config.tag
     }
 
-    fn extract(&self, html: &str) -> HashMap<String, Value> {
+    fn extract(&self, html: &str, _headers: &ResponseHeaders) -> HashMap<String, Value> {
         let Some(selector) = self.parsed_selector.as_ref() else {
             return HashMap::new();
         };
@@ -54,34 +58,11 @@ impl DataExtractor for SelectorExtractor {
         let document = Html::parse_document(html);
         let attribute = self.config.attribute.as_deref();
 
-        if self.config.multiple {
-            let values: Vec<Value> = document
-                .select(selector)
-                .filter_map(|el| Self::read_element(el, attribute))
-                .map(Value::String)
-                .collect();
-
-            if values.is_empty() {
-                return HashMap::new();
-            }
-
-            let mut result = HashMap::new();
-            result.insert(self.config.tag.clone(), Value::Array(values));
-            result
-        } else {
-            let value = document
-                .select(selector)
-                .find_map(|el| Self::read_element(el, attribute));
-
-            match value {
-                Some(v) => {
-                    let mut result = HashMap::new();
-                    result.insert(self.config.tag.clone(), Value::String(v));
-                    result
-                }
-                None => HashMap::new(),
-            }
-        }
+        let matches = document
+            .select(selector)
+            .map(|el| Self::read_element(el, attribute).map(Value::String))
+            .collect();
+        self.config.finish(matches)
     }
 }
 
@@ -109,7 +90,7 @@ mod tests {
     "#;
 
     fn extract(config: ExtractorConfig) -> HashMap<String, Value> {
-        SelectorExtractor::new(config).extract(HTML)
+        SelectorExtractor::new(config).extract(HTML, &ResponseHeaders::default())
     }
 
     // --- Single text extraction ---
@@ -229,14 +210,14 @@ mod tests {
     #[test]
     fn empty_text_element_skipped_in_single_mode() {
         let html = "<html><body><p></p></body></html>";
-        let result = SelectorExtractor::new(ExtractorConfig::text("p", "p")).extract(html);
+        let result = SelectorExtractor::new(ExtractorConfig::text("p", "p")).extract(html, &ResponseHeaders::default());
         assert!(result.is_empty());
     }
 
     #[test]
     fn empty_text_elements_filtered_in_multi_mode() {
         let html = "<html><body><p>has text</p><p></p><p>more text</p></body></html>";
-        let result = SelectorExtractor::new(ExtractorConfig::multi_text("ps", "p")).extract(html);
+        let result = SelectorExtractor::new(ExtractorConfig::multi_text("ps", "p")).extract(html, &ResponseHeaders::default());
         let arr = result["ps"].as_array().unwrap();
         assert_eq!(arr.len(), 2);
     }
@@ -0,0 +1,498 @@
+use std::collections::{HashMap, HashSet};
+use scraper::{ElementRef, Html};
+use serde_json::Value;
+
+use super::{DataExtractor, ExtractorConfig, ExtractorError};
+use crate::contexts::analysis::ResponseHeaders;
+
+/// Extracts data with an XPath expression, for structure CSS can't
+/// express: parents, positions among siblings, matching on text.
+///
+/// Supports the subset of XPath 1.0 that extraction needs: absolute
+/// paths of `/` and `//` steps over element names (case-insensitive),
+/// `*`, `.` and `..`, ending optionally in `@attr` or `text()`.
+/// Predicates are `[n]`, `[last()]`, `[@attr]`, `[@attr='v']` and
+/// `[@attr!='v']`, and `contains(…, 'v')` / `starts-with(…, 'v')`,
+/// where `@attr` may also be `text()` or `.`.
+///
+/// Anything outside that subset — other axes (`following-sibling::`),
+/// functions such as `normalize-space()`, `and` / `or`, unions with `|`,
+/// relative paths — fails to parse, so the extractor is refused when
+/// it's saved rather than read some other way.
+///
+/// An expression that selects elements reads them like a CSS extractor:
+/// their text, or the config's `attribute` if set.
+pub struct XPathExtractor {
+    config: ExtractorConfig,
+    path: XPath,
+}
+
+impl XPathExtractor {
+    pub fn new(config: ExtractorConfig) -> Result<Self, ExtractorError> {
+        let path = XPath::parse(&config.selector).map_err(|message| ExtractorError::InvalidXPath {
+            expression: config.selector.clone(),
+            message,
+        })?;
+        Ok(Self { config, path })
+    }
+
+    fn read_element(&self, el: ElementRef) -> Option<Value> {
+        let value = match self.config.attribute.as_deref() {
+            Some(attr) => el.value().attr(attr)?.trim().to_string(),
+            None => el.text().collect::<String>().trim().to_string(),
+        };
+        (!value.is_empty()).then_some(Value::String(value))
+    }
+}
+
+impl DataExtractor for XPathExtractor {
+    fn id(&self) -> &str {
+        &self.config.tag
+    }
+
+    fn extract(&self, html: &str, _headers: &ResponseHeaders) -> HashMap<String, Value> {
+        let document = Html::parse_document(html);
+        let root = document.root_element();
+        let elements: Vec<ElementRef> = self
+            .path
+            .select(root)
+            .into_iter()
+            .map(|node| match node {
+                Node::Document => root,
+                Node::Element(el) => el,
+            })
+            .collect();
+
+        let matches = match &self.path.terminal {
+            None => elements.into_iter().map(|el| self.read_element(el)).collect(),
+            Some((descendant, terminal)) => {
+                let scope: Vec<ElementRef> = if *descendant {
+                    elements.iter().flat_map(|el| el.descendent_elements()).collect()
+                } else {
+                    elements
+                };
+                let mut values = Vec::new();
+                for el in scope {
+                    match terminal {
+                        Terminal::Attribute(name) => {
+                            values.extend(el.value().attr(name).map(str::trim).map(str::to_string));
+                        }
+                        Terminal::Text => values.extend(
+                            el.children()
+                                .filter_map(|node| node.value().as_text())
+                                .map(|text| text.trim().to_string())
+                                .filter(|text| !text.is_empty()),
+                        ),
+                    }
+                }
+                values.into_iter().map(|v| Some(Value::String(v))).collect()
+            }
+        };
+        self.config.finish(matches)
+    }
+}
+
+/// A node an XPath step can stand on: the document itself or an element.
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Document,
+    Element(ElementRef<'a>),
+}
+
+struct XPath {
+    steps: Vec<Step>,
+    /// A trailing `@attr` or `text()` step, and whether it followed `//`.
+    terminal: Option<(bool, Terminal)>,
+}
+
+struct Step {
+    /// The step followed `//` and so applies below every descendant.
+    descendant: bool,
+    axis: Axis,
+    predicates: Vec<Predicate>,
+}
+
+enum Axis {
+    /// Child elements with this (lowercase) name, or any name for `*`.
+    Child(Option<String>),
+    Parent,
+    SelfNode,
+}
+
+enum Terminal {
+    Attribute(String),
+    Text,
+}
+
+enum Predicate {
+    Position(usize),
+    Last,
+    Exists(Operand),
+    Equals { operand: Operand, value: String, negated: bool },
+    Contains(Operand, String),
+    StartsWith(Operand, String),
+}
+
+enum Operand {
+    Attribute(String),
+    /// `text()`: the element's own text, without its descendants'.
+    Text,
+    /// `.`: all of the element's text.
+    Context,
+}
+
+impl XPath {
+    fn parse(expression: &str) -> Result<Self, String> {
+        let raw_steps = split_steps(expression.trim())?;
+        let last = raw_steps.len() - 1;
+        let mut steps = Vec::new();
+        let mut terminal = None;
+        for (i, (descendant, raw)) in raw_steps.into_iter().enumerate() {
+            let end = if let Some(name) = raw.strip_prefix('@') {
+                Some(Terminal::Attribute(parse_name(name)?))
+            } else if raw == "text()" {
+                Some(Terminal::Text)
+            } else {
+                None
+            };
+            match end {
+                Some(_) if i != last => return Err(format!("'{raw}' must be the last step")),
+                Some(end) => terminal = Some((descendant, end)),
+                None => steps.push(Step::parse(descendant, raw)?),
+            }
+        }
+        Ok(Self { steps, terminal })
+    }
+
+    /// The nodes the element steps select, from the document down.
+    fn select<'a>(&self, root: ElementRef<'a>) -> Vec<Node<'a>> {
+        let mut current = vec![Node::Document];
+        for step in &self.steps {
+            let mut next = Vec::new();
+            for node in current {
+                if step.descendant {
+                    for context in descendants_or_self(node, root) {
+                        next.extend(step.apply(context, root));
+                    }
+                } else {
+                    next.extend(step.apply(node, root));
+                }
+            }
+            let mut seen = HashSet::new();
+            next.retain(|node| {
+                seen.insert(match node {
+                    Node::Document => None,
+                    Node::Element(el) => Some(el.id()),
+                })
+            });
+            current = next;
+        }
+        current
+    }
+}
+
+/// Split an expression into its steps, noting which followed `//`.
+/// Slashes inside predicates and quotes don't split.
+fn split_steps(expression: &str) -> Result<Vec<(bool, &str)>, String> {
+    if !expression.starts_with('/') {
+        return Err("must start with '/' or '//'".into());
+    }
+    let mut steps = Vec::new();
+    let mut rest = expression;
+    while !rest.is_empty() {
+        let (descendant, after) = match rest.strip_prefix("//") {
+            Some(after) => (true, after),
+            None => (false, &rest[1..]),
+        };
+        let mut depth = 0usize;
+        let mut quote = None;
+        let mut end = after.len();
+        for (i, c) in after.char_indices() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"') => quote = Some(c),
+                (None, '[') => depth += 1,
+                (None, ']') => depth = depth.checked_sub(1).ok_or("unbalanced ']'")?,
+                (None, '/') if depth == 0 => {
+                    end = i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        if quote.is_some() || depth > 0 {
+            return Err("unclosed quote or '['".into());
+        }
+        let step = after[..end].trim();
+        if step.is_empty() {
+            return Err("empty step".into());
+        }
+        steps.push((descendant, step));
+        rest = &after[end..];
+    }
+    Ok(steps)
+}
+
+fn parse_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    // A namespace prefix has one colon; `axis::` is an unsupported axis.
+    let valid = !name.is_empty()
+        && !name.contains("::")
+        && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'));
+    if valid { Ok(name.to_ascii_lowercase()) } else { Err(format!("invalid name '{name}'")) }
+}
+
+impl Step {
+    fn parse(descendant: bool, raw: &str) -> Result<Self, String> {
+        let (test, mut rest) = raw.split_at(raw.find('[').unwrap_or(raw.len()));
+        let axis = match test.trim() {
+            "." => Axis::SelfNode,
+            ".." => Axis::Parent,
+            "*" => Axis::Child(None),
+            name => Axis::Child(Some(parse_name(name)?)),
+        };
+        let mut predicates = Vec::new();
+        while let Some(inner) = rest.trim_start().strip_prefix('[') {
+            let end = closing_bracket(inner).ok_or("unclosed '['")?;
+            predicates.push(Predicate::parse(inner[..end].trim())?);
+            rest = &inner[end + 1..];
+        }
+        if !rest.trim().is_empty() {
+            return Err(format!("unexpected '{}'", rest.trim()));
+        }
+        Ok(Self { descendant, axis, predicates })
+    }
+
+    /// The nodes this step selects from `node`, with its predicates
+    /// applied in turn so positions count among what's left.
+    fn apply<'a>(&self, node: Node<'a>, root: ElementRef<'a>) -> Vec<Node<'a>> {
+        let mut candidates: Vec<ElementRef<'a>> = match (&self.axis, node) {
+            (Axis::SelfNode, Node::Document) => return vec![node],
+            (Axis::Parent, Node::Document) => return vec![],
+            (Axis::SelfNode, Node::Element(el)) => vec![el],
+            (Axis::Parent, Node::Element(el)) => match el.parent().and_then(ElementRef::wrap) {
+                Some(parent) => vec![parent],
+                None => return vec![Node::Document],
+            },
+            (Axis::Child(name), node) => {
+                let children: Vec<ElementRef<'a>> = match node {
+                    Node::Document => vec![root],
+                    Node::Element(el) => el.child_elements().collect(),
+                };
+                children
+                    .into_iter()
+                    .filter(|el| {
+                        name.as_deref().is_none_or(|name| el.value().name().eq_ignore_ascii_case(name))
+                    })
+                    .collect()
+            }
+        };
+        for predicate in &self.predicates {
+            let len = candidates.len();
+            candidates = candidates
+                .into_iter()
+                .enumerate()
+                .filter(|(i, el)| predicate.matches(*el, i + 1, len))
+                .map(|(_, el)| el)
+                .collect();
+        }
+        candidates.into_iter().map(Node::Element).collect()
+    }
+}
+
+fn descendants_or_self<'a>(node: Node<'a>, root: ElementRef<'a>) -> Vec<Node<'a>> {
+    match node {
+        Node::Document => std::iter::once(Node::Document)
+            .chain(root.descendent_elements().map(Node::Element))
+            .collect(),
+        Node::Element(el) => el.descendent_elements().map(Node::Element).collect(),
+    }
+}
+
+/// Index of the `]` closing a predicate, skipping quoted text.
+fn closing_bracket(inner: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in inner.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, ']') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+impl Predicate {
+    fn parse(inner: &str) -> Result<Self, String> {
+        if let Ok(position) = inner.parse::<usize>() {
+            return match position {
+                0 => Err("positions start at 1".into()),
+                n => Ok(Self::Position(n)),
+            };
+        }
+        if inner == "last()" {
+            return Ok(Self::Last);
+        }
+        for (function, contains) in [("contains(", true), ("starts-with(", false)] {
+            if let Some(args) = inner.strip_prefix(function).and_then(|s| s.strip_suffix(')')) {
+                let (operand, literal) =
+                    args.split_once(',').ok_or_else(|| format!("{function}) takes two arguments"))?;
+                let operand = Operand::parse(operand)?;
+                let literal = parse_literal(literal)?;
+                return Ok(if contains {
+                    Self::Contains(operand, literal)
+                } else {
+                    Self::StartsWith(operand, literal)
+                });
+            }
+        }
+        // Operands never contain quotes, so the first `=` is the operator.
+        if let Some(eq) = inner.find('=') {
+            let negated = inner[..eq].ends_with('!');
+            let operand = inner[..eq].strip_suffix('!').unwrap_or(&inner[..eq]);
+            return Ok(Self::Equals {
+                operand: Operand::parse(operand)?,
+                value: parse_literal(&inner[eq + 1..])?,
+                negated,
+            });
+        }
+        Operand::parse(inner).map(Self::Exists)
+    }
+
+    fn matches(&self, el: ElementRef, position: usize, len: usize) -> bool {
+        match self {
+            Self::Position(n) => position == *n,
+            Self::Last => position == len,
+            Self::Exists(Operand::Attribute(name)) => el.value().attr(name).is_some(),
+            Self::Exists(operand) => operand.value(el).is_some_and(|v| !v.is_empty()),
+            Self::Equals { operand, value, negated } => {
+                operand.value(el).is_some_and(|v| (v == *value) != *negated)
+            }
+            Self::Contains(operand, value) => operand.value(el).is_some_and(|v| v.contains(value.as_str())),
+            Self::StartsWith(operand, value) => operand.value(el).is_some_and(|v| v.starts_with(value.as_str())),
+        }
+    }
+}
+
+impl Operand {
+    fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim() {
+            "." => Ok(Self::Context),
+            "text()" => Ok(Self::Text),
+            raw => match raw.strip_prefix('@') {
+                Some(name) => parse_name(name).map(Self::Attribute),
+                None => Err(format!("unsupported predicate operand '{raw}'")),
+            },
+        }
+    }
+
+    /// The operand's value on `el`, trimmed; `None` for a missing attribute.
+    fn value(&self, el: ElementRef) -> Option<String> {
+        let value = match self {
+            Self::Attribute(name) => el.value().attr(name)?.to_string(),
+            Self::Text => el.children().filter_map(|node| node.value().as_text()).map(|text| &**text).collect(),
+            Self::Context => el.text().collect(),
+        };
+        Some(value.trim().to_string())
+    }
+}
+
+/// A quoted string, or a bare number.
+fn parse_literal(raw: &str) -> Result<String, String> {
+    let raw = raw.trim();
+    for quote in ['\'', '"'] {
+        if let Some(value) = raw.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+            return Ok(value.to_string());
+        }
+    }
+    match raw.parse::<f64>() {
+        Ok(_) => Ok(raw.to_string()),
+        Err(_) => Err(format!("expected a quoted string, got '{raw}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::extension::{ExtractorKind, ExtractorOutput};
+    use serde_json::json;
+
+    const HTML: &str = r#"
+        <html><body>
+          <nav><ul><li><a href="/">Home</a></li><li><a href="/shop">Shop</a></li></ul></nav>
+          <dl class="specs">
+            <dt>Weight</dt><dd>1.2 kg</dd>
+            <dt>Colour</dt><dd>Red</dd>
+          </dl>
+          <ul id="features"><li>Fast</li><li data-new="1">Quiet</li><li>Cheap</li></ul>
+          <p>Price: <b>£20</b></p>
+        </body></html>
+    "#;
+
+    fn config(expression: &str) -> ExtractorConfig {
+        ExtractorConfig { kind: ExtractorKind::Xpath, ..ExtractorConfig::multi_text("x", expression) }
+    }
+
+    fn extract(config: ExtractorConfig) -> Value {
+        let result = XPathExtractor::new(config).unwrap().extract(HTML, &ResponseHeaders::default());
+        result.get("x").cloned().unwrap_or(Value::Null)
+    }
+
+    #[test]
+    fn selects_elements_attributes_and_text() {
+        assert_eq!(extract(config("//nav//a/@href")), json!(["/", "/shop"]));
+        assert_eq!(extract(config("/html/body/ul/li")), json!(["Fast", "Quiet", "Cheap"]));
+        // text() reads the element's own text, not its children's.
+        assert_eq!(extract(config("//p/text()")), json!(["Price:"]));
+        assert_eq!(extract(config("//P/B")), json!(["£20"]));
+    }
+
+    #[test]
+    fn positions_count_per_parent() {
+        assert_eq!(extract(config("//li[1]")), json!(["Home", "Fast"]));
+        assert_eq!(extract(config("//ul[@id='features']/li[last()]")), json!(["Cheap"]));
+    }
+
+    #[test]
+    fn predicates_match_attributes_and_text() {
+        assert_eq!(extract(config("//li[@data-new]")), json!(["Quiet"]));
+        assert_eq!(extract(config("//dt[text()='Weight']/../dd[1]")), json!(["1.2 kg"]));
+        assert_eq!(extract(config("//a[starts-with(@href, '/s')]")), json!(["Shop"]));
+        assert_eq!(extract(config("//ul[@id!='nav']/li[1]")), json!(["Fast"]));
+        // Predicates filter in turn, so the position counts what's left.
+        assert_eq!(extract(config("//li[contains(., 'e')][2]")), json!(["Cheap"]));
+    }
+
+    #[test]
+    fn reads_the_configured_attribute_and_counts() {
+        let href = ExtractorConfig { attribute: Some("href".into()), multiple: false, ..config("//nav//li[2]/a") };
+        assert_eq!(extract(href), "/shop");
+
+        let count = ExtractorConfig { output: ExtractorOutput::Count, ..config("//li") };
+        assert_eq!(extract(count), 5);
+    }
+
+    #[test]
+    fn rejects_unsupported_expressions() {
+        for expression in ["li", "//", "//li[", "//li[0]", "//@href/a", "//li[position() > 1]", "//a[@x=y]"] {
+            assert!(XPath::parse(expression).is_err(), "{expression} should not parse");
+        }
+    }
+
+    #[test]
+    fn rejects_syntax_outside_the_supported_subset() {
+        let unsupported = [
+            "//dt/following-sibling::dd",
+            "//a | //b",
+            "//a[@x='1' and @y]",
+            "//a[normalize-space(.)='Shop']",
+            "nav/a",
+        ];
+        for expression in unsupported {
+            assert!(XPath::parse(expression).is_err(), "{expression} should not parse");
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use scraper::Html;
+use regex::Regex;
+use scraper::{ElementRef, Html};
+use std::sync::OnceLock;
 use url::Url;
 
 use crate::contexts::analysis::LinkType;
@@ -29,6 +31,51 @@ pub struct ExtractedLink {
 
 pub type LinkLists = (Vec<String>, Vec<String>, Vec<ExtractedLink>);
 
+/// What kind of element pulled a subresource into the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    Script,
+    Stylesheet,
+    /// `<link rel=preload|modulepreload>`; `as` decides whether it is active.
+    Preload,
+    Image,
+    /// `<video>`, `<audio>` and their `<source>`/`<track>` children.
+    Media,
+    Iframe,
+    /// `<object>`/`<embed>` plugin content.
+    Embed,
+    FormAction,
+    /// `url()` in a `style` attribute or `<style>` block.
+    CssUrl,
+}
+
+impl ResourceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceKind::Script => "script",
+            ResourceKind::Stylesheet => "stylesheet",
+            ResourceKind::Preload => "preload",
+            ResourceKind::Image => "image",
+            ResourceKind::Media => "media",
+            ResourceKind::Iframe => "iframe",
+            ResourceKind::Embed => "embed",
+            ResourceKind::FormAction => "form action",
+            ResourceKind::CssUrl => "css url()",
+        }
+    }
+}
+
+/// A URL the page loads (or submits to) rather than links to.
+#[derive(Debug, Clone)]
+pub struct ExtractedResource {
+    pub url: String,
+    pub kind: ResourceKind,
+    /// Whether the resource can read or rewrite the page. Browsers block
+    /// active mixed content outright; passive (images, media) is loaded
+    /// with a warning or upgraded.
+    pub active: bool,
+}
+
 pub struct PageExtractor;
 
 impl PageExtractor {
@@ -62,15 +109,44 @@ impl PageExtractor {
             .unwrap_or(0)
     }
 
+    /// Text a visitor sees in `<body>`, leaving out scripts, styles and
+    /// templates, with whitespace collapsed.
+    pub fn extract_body_text(html: &Html) -> String {
+        let Some(body) = html.select(cached_selector!("body")).next() else {
+            return String::new();
+        };
+        let mut words = Vec::new();
+        for node in body.descendants() {
+            let Some(text) = node.value().as_text() else { continue };
+            let hidden = node
+                .ancestors()
+                .filter_map(|a| a.value().as_element())
+                .any(|el| matches!(el.name(), "script" | "style" | "noscript" | "template"));
+            if !hidden {
+                words.extend(text.split_whitespace());
+            }
+        }
+        words.join(" ")
+    }
+
+    /// Every `<h1>`–`<h6>` in document order. Empty headings are kept,
+    /// with empty text, so checks can see the outline as the page has it;
+    /// a heading holding only an image reads as the image's alt text.
     pub fn extract_headings(html: &Html) -> Vec<ExtractedHeading> {
         html.select(cached_selector!("h1, h2, h3, h4, h5, h6"))
             .enumerate()
             .filter_map(|(idx, element)| {
                 let tag = element.value().name();
                 let level = tag.trim_start_matches('h').parse::<i64>().ok()?;
-                let text = element.text().collect::<String>().trim().to_string();
+                let mut text = element.text().collect::<String>().trim().to_string();
                 if text.is_empty() {
-                    return None;
+                    text = element
+                        .select(cached_selector!("img[alt]"))
+                        .filter_map(|img| img.value().attr("alt"))
+                        .map(str::trim)
+                        .filter(|alt| !alt.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ");
                 }
 
                 Some(ExtractedHeading {
@@ -205,6 +281,134 @@ impl PageExtractor {
 
         (internal, external, all)
     }
+
+    /// Every subresource reference in the page, resolved against
+    /// `base_url`. Inline (`data:`, `blob:`) and unresolvable references
+    /// are skipped.
+    pub fn extract_resources(html: &Html, base_url: &str) -> Vec<ExtractedResource> {
+        let base = Url::parse(base_url).ok();
+        let mut resources = Vec::new();
+        let mut push = |raw: &str, kind: ResourceKind, active: bool| {
+            if let Some(url) = resolve_resource(base.as_ref(), raw) {
+                resources.push(ExtractedResource { url, kind, active });
+            }
+        };
+
+        for el in html.select(cached_selector!("script[src]")) {
+            push(attr(&el, "src"), ResourceKind::Script, true);
+        }
+
+        for el in html.select(cached_selector!("link[href][rel]")) {
+            let rel = attr(&el, "rel").to_ascii_lowercase();
+            let rels: Vec<&str> = rel.split_whitespace().collect();
+            let href = attr(&el, "href");
+            if rels.contains(&"stylesheet") {
+                push(href, ResourceKind::Stylesheet, true);
+            } else if rels.contains(&"modulepreload") {
+                push(href, ResourceKind::Preload, true);
+            } else if rels.contains(&"preload") {
+                let passive = matches!(
+                    attr(&el, "as").to_ascii_lowercase().as_str(),
+                    "image" | "audio" | "video" | "track"
+                );
+                push(href, ResourceKind::Preload, !passive);
+            } else if rels.contains(&"icon") {
+                push(href, ResourceKind::Image, false);
+            }
+        }
+
+        for el in html.select(cached_selector!("img, source, video, audio, track, input[type='image']")) {
+            let tag = el.value().name();
+            let kind = match tag {
+                "img" | "input" => ResourceKind::Image,
+                "source" if el.parent().and_then(ElementRef::wrap).is_some_and(|p| p.value().name() == "picture") => {
+                    ResourceKind::Image
+                }
+                _ => ResourceKind::Media,
+            };
+            push(attr(&el, "src"), kind, false);
+            if tag == "video" {
+                push(attr(&el, "poster"), ResourceKind::Image, false);
+            }
+            for candidate in srcset_urls(attr(&el, "srcset")) {
+                push(candidate, kind, false);
+            }
+        }
+
+        for el in html.select(cached_selector!("iframe[src], frame[src]")) {
+            push(attr(&el, "src"), ResourceKind::Iframe, true);
+        }
+
+        for el in html.select(cached_selector!("object[data], embed[src]")) {
+            let raw = el.value().attr("data").or_else(|| el.value().attr("src")).unwrap_or_default();
+            push(raw, ResourceKind::Embed, true);
+        }
+
+        for el in html.select(cached_selector!("form[action]")) {
+            push(attr(&el, "action"), ResourceKind::FormAction, true);
+        }
+
+        // A style attribute can only pull in images, but a <style> block
+        // can @import stylesheets and load fonts, so treat it as active.
+        for el in html.select(cached_selector!("[style]")) {
+            for raw in css_urls(attr(&el, "style")) {
+                push(raw, ResourceKind::CssUrl, false);
+            }
+        }
+        for el in html.select(cached_selector!("style")) {
+            let css = el.text().collect::<String>();
+            for raw in css_urls(&css) {
+                push(raw, ResourceKind::CssUrl, true);
+            }
+        }
+
+        resources
+    }
+
+    /// Scripts and stylesheets in `<head>` that the browser must fetch
+    /// before it can paint: scripts without `async`, `defer` or
+    /// `type=module`, and stylesheets whose `media` matches every screen.
+    pub fn extract_render_blocking(html: &Html, base_url: &str) -> Vec<String> {
+        let base = Url::parse(base_url).ok();
+        let mut blocking = Vec::new();
+
+        for el in html.select(cached_selector!("head script[src], head link[href][rel]")) {
+            let element = el.value();
+            let raw = if element.name() == "script" {
+                let deferred = element.attr("async").is_some()
+                    || element.attr("defer").is_some()
+                    || attr(&el, "type").eq_ignore_ascii_case("module");
+                if deferred {
+                    continue;
+                }
+                attr(&el, "src")
+            } else {
+                let is_stylesheet = attr(&el, "rel")
+                    .split_whitespace()
+                    .any(|r| r.eq_ignore_ascii_case("stylesheet"));
+                let media = attr(&el, "media").to_ascii_lowercase();
+                let all_media = media.is_empty() || media == "all" || media == "screen";
+                if !is_stylesheet || !all_media || element.attr("disabled").is_some() {
+                    continue;
+                }
+                attr(&el, "href")
+            };
+            if let Some(url) = resolve_resource(base.as_ref(), raw) {
+                blocking.push(url);
+            }
+        }
+
+        blocking
+    }
+
+    /// `url()` references in a stylesheet, resolved against its URL.
+    pub fn extract_css_urls(css: &str, base_url: &str) -> Vec<String> {
+        let base = Url::parse(base_url).ok();
+        css_urls(css)
+            .filter_map(|raw| resolve_resource(base.as_ref(), raw))
+            .collect()
+    }
+
     pub fn extract_has_viewport(html: &Html) -> bool {
         html.select(cached_selector!("meta[name='viewport']"))
             .next()
@@ -220,10 +424,50 @@ impl PageExtractor {
     }
 }
 
+fn attr<'a>(el: &ElementRef<'a>, name: &str) -> &'a str {
+    el.value().attr(name).map(str::trim).unwrap_or_default()
+}
+
+fn resolve_resource(base: Option<&Url>, raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() || raw.starts_with('#') {
+        return None;
+    }
+    let url = match base {
+        Some(base) => base.join(raw).ok()?,
+        None => Url::parse(raw).ok()?,
+    };
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+/// The URLs of a `srcset`, dropping the width/density descriptors.
+fn srcset_urls(srcset: &str) -> impl Iterator<Item = &str> {
+    srcset
+        .split(',')
+        .filter_map(|candidate| candidate.split_whitespace().next())
+}
+
+fn css_urls(css: &str) -> impl Iterator<Item = &str> {
+    static URL_FN: OnceLock<Regex> = OnceLock::new();
+    URL_FN
+        .get_or_init(|| Regex::new(r#"url\(\s*['"]?([^'")\s]+)['"]?\s*\)"#).expect("invalid url() regex"))
+        .captures_iter(css)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn body_text_skips_scripts_and_styles() {
+        let html = Html::parse_document(
+            "<html><body><h1>Not  found</h1><script>var x = 1;</script>\
+             <style>p { color: red }</style><p>Try the <a href='/'>home page</a>.</p></body></html>",
+        );
+        assert_eq!(PageExtractor::extract_body_text(&html), "Not found Try the home page .");
+    }
+
     #[test]
     fn test_extract_has_viewport() {
         let html_content = r#"
@@ -456,14 +700,23 @@ mod tests {
     }
 
     #[test]
-    fn extract_headings_skips_empty_text() {
+    fn extract_headings_keeps_empty_headings() {
         let html = Html::parse_document(
             "<html><body><h1></h1><h2>Real</h2><h3>   </h3></body></html>",
         );
         let headings = PageExtractor::extract_headings(&html);
-        // Empty and whitespace-only headings are skipped.
-        assert_eq!(headings.len(), 1);
-        assert_eq!(headings[0].text, "Real");
+        // Empty and whitespace-only headings are kept with empty text.
+        let texts: Vec<&str> = headings.iter().map(|h| h.text.as_str()).collect();
+        assert_eq!(texts, vec!["", "Real", ""]);
+        assert_eq!(headings[2].level, 3);
+    }
+
+    #[test]
+    fn extract_headings_reads_image_alt_in_image_only_headings() {
+        let html = Html::parse_document(
+            r#"<html><body><h1><a href="/"><img src="logo.png" alt=" Acme "></a></h1></body></html>"#,
+        );
+        assert_eq!(PageExtractor::extract_headings(&html)[0].text, "Acme");
     }
 
     #[test]
@@ -562,4 +815,71 @@ mod tests {
         assert!(images[0].height.is_none());
         assert!(images[0].loading.is_none());
     }
+
+    // ── extract_resources ────────────────────────────────────────────────
+
+    fn resources(html: &str) -> Vec<ExtractedResource> {
+        PageExtractor::extract_resources(&Html::parse_document(html), "https://example.com/blog/")
+    }
+
+    #[test]
+    fn extract_resources_covers_every_subresource_kind() {
+        let found = resources(r##"<html><head>
+            <script src="/app.js"></script>
+            <link rel="stylesheet" href="style.css">
+            <link rel="preload" href="/hero.webp" as="image">
+            <link rel="preload" href="/font.woff2" as="font">
+            <style>@font-face { src: url("/fonts/a.woff2"); }</style>
+        </head><body style="background: url(/bg.png)">
+            <img src="/a.png" srcset="/a-2x.png 2x, /a-3x.png 3x">
+            <picture><source srcset="/b.avif"></picture>
+            <video src="/v.mp4" poster="/poster.jpg"><source src="/v.webm"></video>
+            <iframe src="https://maps.example.org/embed"></iframe>
+            <form action="/search"></form>
+            <img src="data:image/png;base64,AAAA">
+        </body></html>"##);
+
+        let summary: Vec<(&str, ResourceKind, bool)> =
+            found.iter().map(|r| (r.url.as_str(), r.kind, r.active)).collect();
+        assert!(summary.contains(&("https://example.com/app.js", ResourceKind::Script, true)));
+        assert!(summary.contains(&("https://example.com/blog/style.css", ResourceKind::Stylesheet, true)));
+        assert!(summary.contains(&("https://example.com/hero.webp", ResourceKind::Preload, false)));
+        assert!(summary.contains(&("https://example.com/font.woff2", ResourceKind::Preload, true)));
+        assert!(summary.contains(&("https://example.com/fonts/a.woff2", ResourceKind::CssUrl, true)));
+        assert!(summary.contains(&("https://example.com/bg.png", ResourceKind::CssUrl, false)));
+        assert!(summary.contains(&("https://example.com/a-3x.png", ResourceKind::Image, false)));
+        assert!(summary.contains(&("https://example.com/b.avif", ResourceKind::Image, false)));
+        assert!(summary.contains(&("https://example.com/v.webm", ResourceKind::Media, false)));
+        assert!(summary.contains(&("https://example.com/poster.jpg", ResourceKind::Image, false)));
+        assert!(summary.contains(&("https://maps.example.org/embed", ResourceKind::Iframe, true)));
+        assert!(summary.contains(&("https://example.com/search", ResourceKind::FormAction, true)));
+        assert!(!found.iter().any(|r| r.url.starts_with("data:")));
+    }
+
+    #[test]
+    fn extract_resources_keeps_explicit_http_scheme() {
+        let found = resources(r##"<html><body>
+            <script src="http://cdn.example.net/lib.js"></script>
+            <img src="//example.com/protocol-relative.png">
+        </body></html>"##);
+        assert_eq!(found[0].url, "http://cdn.example.net/lib.js");
+        assert_eq!(found[1].url, "https://example.com/protocol-relative.png");
+    }
+
+    #[test]
+    fn extract_render_blocking_skips_deferred_scripts_and_print_styles() {
+        let html = Html::parse_document(r##"<html><head>
+            <link rel="stylesheet" href="/main.css">
+            <link rel="stylesheet" href="/print.css" media="print">
+            <link rel="preload" href="/font.woff2" as="font">
+            <script src="/blocking.js"></script>
+            <script src="/async.js" async></script>
+            <script src="/defer.js" defer></script>
+            <script src="/module.js" type="module"></script>
+        </head><body><script src="/footer.js"></script></body></html>"##);
+        assert_eq!(
+            PageExtractor::extract_render_blocking(&html, "https://example.com/"),
+            ["https://example.com/main.css", "https://example.com/blocking.js"]
+        );
+    }
 }
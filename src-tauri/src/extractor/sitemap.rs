@@ -84,6 +84,58 @@ pub async fn extract_sitemap_urls(
     extract_url_from_sitemap(&text)
 }
 
+/// Upper bound on child sitemaps followed from one sitemap index, so a
+/// huge index cannot turn list-mode setup into a crawl of its own.
+const MAX_CHILD_SITEMAPS: usize = 50;
+
+/// Fetch the page URLs listed by the sitemap at `sitemap_url`. A sitemap
+/// index is followed one level deep; children that fail to load are
+/// logged and skipped so one broken file doesn't sink the whole list.
+pub async fn fetch_sitemap_urls(
+    sitemap_url: &Url,
+    spider: Arc<dyn SpiderAgent>,
+) -> Result<Vec<String>, Error> {
+    let text = fetch_sitemap(spider.as_ref(), sitemap_url.as_str())
+        .await
+        .with_context(|| format!("Unable to fetch sitemap {sitemap_url}"))?;
+
+    if !text.contains("<sitemapindex") {
+        return extract_url_from_sitemap(&text);
+    }
+
+    let children = extract_url_from_sitemap(&text)?;
+    if children.len() > MAX_CHILD_SITEMAPS {
+        tracing::warn!(
+            "Sitemap index {} lists {} sitemaps; reading the first {}",
+            sitemap_url,
+            children.len(),
+            MAX_CHILD_SITEMAPS
+        );
+    }
+
+    let mut urls = Vec::new();
+    for child in children.into_iter().take(MAX_CHILD_SITEMAPS) {
+        let child_urls = fetch_sitemap(spider.as_ref(), &child)
+            .await
+            .and_then(|body| extract_url_from_sitemap(&body));
+        match child_urls {
+            Ok(child_urls) => urls.extend(child_urls),
+            Err(e) => tracing::warn!("Skipping child sitemap {}: {:#}", child, e),
+        }
+    }
+    Ok(urls)
+}
+
+/// The body of the sitemap at `url`. An error status fails, so an error
+/// page is never read as a list of URLs.
+async fn fetch_sitemap(spider: &dyn SpiderAgent, url: &str) -> Result<String> {
+    let response = spider.get(url).await?;
+    if !(200..300).contains(&response.status) {
+        anyhow::bail!("HTTP {}", response.status);
+    }
+    Ok(response.body)
+}
+
 fn extract_url_from_sitemap(text: &str) -> Result<Vec<String>, Error> {
     let format: SitemapFormat = SitemapFormat::detect(text);
     let urls = format.extract_urls(text);
@@ -244,4 +296,68 @@ https://www.google.com/intl/am/gmail/about/policy/"#;
             SitemapFormat::PlainText
         ));
     }
+
+    #[tokio::test]
+    async fn fetch_sitemap_urls_follows_a_sitemap_index() {
+        use crate::service::spider::{ClientType, Spider};
+
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+        let _index = server
+            .mock("GET", "/sitemap_index.xml")
+            .with_body(format!(
+                "<sitemapindex><sitemap><loc>{base}/a.xml</loc></sitemap>\
+                 <sitemap><loc>{base}/missing.xml</loc></sitemap>\
+                 <sitemap><loc>http://127.0.0.1:1/down.xml</loc></sitemap>\
+                 <sitemap><loc>{base}/b.xml</loc></sitemap></sitemapindex>"
+            ))
+            .create_async()
+            .await;
+        // An error page is skipped, not read as a plain-text URL list
+        let _missing = server
+            .mock("GET", "/missing.xml")
+            .with_status(404)
+            .with_body("Not found: https://example.com/error-page")
+            .create_async()
+            .await;
+        let _second = server
+            .mock("GET", "/b.xml")
+            .with_body("<urlset><url><loc>https://example.com/three</loc></url></urlset>")
+            .create_async()
+            .await;
+        let _child = server
+            .mock("GET", "/a.xml")
+            .with_body(
+                "<urlset><url><loc>https://example.com/one</loc></url>\
+                 <url><loc>https://example.com/two</loc></url></urlset>",
+            )
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let index = Url::parse(&format!("{base}/sitemap_index.xml")).unwrap();
+        let urls = fetch_sitemap_urls(&index, spider).await.unwrap();
+
+        assert_eq!(
+            urls,
+            vec!["https://example.com/one", "https://example.com/two", "https://example.com/three"]
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_sitemap_urls_fails_when_the_sitemap_itself_is_missing() {
+        use crate::service::spider::{ClientType, Spider};
+
+        let mut server = mockito::Server::new_async().await;
+        let _missing = server
+            .mock("GET", "/sitemap.xml")
+            .with_status(404)
+            .with_body("https://example.com/error-page")
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let url = Url::parse(&format!("{}/sitemap.xml", server.url())).unwrap();
+        assert!(fetch_sitemap_urls(&url, spider).await.is_err());
+    }
 }
@@ -2,22 +2,24 @@ use crate::{
     contexts::{
         ai::{AiService, AiServiceFactory},
         analysis::{AnalysisService, AnalysisServiceFactory},
+        extension::DryRunService,
         licensing::{LicensingAgent, PermissionRequest, Policy},
         local_model::{LocalModelService, LocalModelServiceFactory},
         report::ReportService,
+        rule_pack::RulePackService,
+        snapshot::SnapshotService,
     },
-    extractor::data_extractor::{ExtractorConfig, ExtractorRegistry},
-    extractor::data_extractor::selector::SelectorExtractor,
+    extractor::data_extractor::ExtractorRegistry,
     repository::{
         sqlite_ai_repo, sqlite_extension_repo, sqlite_issue_repo, sqlite_job_repo,
         sqlite_link_repo, sqlite_page_queue_repo, sqlite_page_repo, sqlite_report_pattern_repo,
-        sqlite_report_template_repo, sqlite_results_repo, sqlite_settings_repo,
-        ExtensionRepository, ReportPatternRepository, ReportTemplateRepository,
+        sqlite_report_template_repo, sqlite_results_repo, sqlite_rule_pack_repo,
+        sqlite_settings_repo, sqlite_snapshot_repo, ExtensionRepository, ReportPatternRepository, ReportTemplateRepository,
     },
     service::{
         JobProcessor, ProgressReporter,
         licensing::{LicensingService, MockLicensingService},
-        processor::{AnalyzerService, Crawler, reporter::ProgressEmitter},
+        processor::{AnalyzerService, reporter::ProgressEmitter},
         spider::{ClientType, Spider, SpiderAgent},
     },
 };
@@ -35,12 +37,15 @@ pub struct AppState {
     pub permissions: RwLock<Policy>,
     pub licensing_context: Arc<dyn LicensingAgent>,
     pub analysis_context: AnalysisService,
-    pub ai_context: AiService,
+    pub ai_context: Arc<AiService>,
     pub local_model_context: Arc<LocalModelService>,
     pub extension_repo: Arc<dyn ExtensionRepository>,
+    pub dry_run_context: DryRunService,
     pub report_pattern_repo: Arc<dyn ReportPatternRepository>,
     pub report_template_repo: Arc<dyn ReportTemplateRepository>,
     pub report_context: ReportService,
+    pub rule_pack_context: RulePackService,
+    pub snapshot_context: Arc<SnapshotService>,
 }
 
 impl AppState {
@@ -61,6 +66,8 @@ impl AppState {
         let extension_repo = sqlite_extension_repo(pool.clone());
         let report_pattern_repo = sqlite_report_pattern_repo(pool.clone());
         let report_template_repo = sqlite_report_template_repo(pool.clone());
+        let snapshot_repo = sqlite_snapshot_repo(pool.clone());
+        let rule_pack_repo = sqlite_rule_pack_repo(pool.clone());
 
         // Ensure the default report template has its sections populated.
         // The migration seeds an empty shell; this backfills the JSON on
@@ -76,24 +83,17 @@ impl AppState {
 
         // Build extractor registry from persisted custom extractors
         let extractor_registry = {
-            let mut registry = ExtractorRegistry::new();
-            match extension_repo.list_enabled_extractors().await {
+            let registry = match extension_repo.list_enabled_extractors().await {
                 Ok(extractors) => {
-                    for ext in extractors {
-                        let config = ExtractorConfig {
-                            tag: ext.tag,
-                            selector: ext.selector,
-                            attribute: ext.attribute,
-                            multiple: ext.multiple,
-                        };
-                        registry.register(Box::new(SelectorExtractor::new(config)));
-                    }
+                    let registry = ExtractorRegistry::from_extractors(extractors);
                     tracing::info!("[INIT] Loaded {} custom extractor(s)", registry.len());
+                    registry
                 }
                 Err(e) => {
                     tracing::warn!("[INIT] Failed to load custom extractors: {}", e);
+                    ExtractorRegistry::new()
                 }
-            }
+            };
             Arc::new(registry)
         };
 
@@ -112,21 +112,30 @@ impl AppState {
             }
         };
 
+        let snapshot_context =
+            Arc::new(SnapshotService::load(snapshot_repo.clone(), settings_repo.clone()).await);
+        let prune_snapshots = snapshot_context.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = prune_snapshots.prune().await {
+                tracing::warn!("[INIT] Failed to prune page snapshots: {e}");
+            }
+        });
+
         let analyzer = AnalyzerService::with_custom_checks(
-            pages_repo,
-            issues_repo,
+            pages_repo.clone(),
+            issues_repo.clone(),
             heavy_spider.clone(),
             extractor_registry,
             custom_checks,
-        );
-        let crawler = Crawler::new(heavy_spider.clone());
+        )
+        .with_snapshots(snapshot_context.clone());
 
         let job_processor = Arc::new(JobProcessor::new(
             job_repo.clone(),
-            link_repo,
+            link_repo.clone(),
             page_queue_repo.clone(),
             analyzer,
-            crawler,
+            heavy_spider.clone(),
             progress_reporter.clone(),
         ));
 
@@ -162,11 +171,6 @@ impl AppState {
             job_processor.clone(),
         );
 
-        let ai_context = AiServiceFactory::from_repositories(
-            ai_repo.clone(),
-            settings_repo.clone(),
-        );
-
         let models_dir = app_handle
             .path()
             .app_data_dir()
@@ -178,12 +182,38 @@ impl AppState {
             app_handle.clone(),
         ));
 
-        let report_context = ReportService::with_local_model(
+        let ai_context = Arc::new(
+            AiServiceFactory::with_spider(
+                ai_repo.clone(),
+                settings_repo.clone(),
+                Spider::new_agent(ClientType::Inference)?,
+            )
+            .with_local_model(local_model_context.clone()),
+        );
+
+        let report_context = ReportService::with_ai(
             report_pattern_repo.clone(),
             results_repo.clone(),
             settings_repo.clone(),
             report_template_repo.clone(),
-            local_model_context.clone(),
+            ai_context.clone(),
+        );
+
+        let dry_run_context = DryRunService::new(
+            results_repo.clone(),
+            snapshot_repo,
+            pages_repo,
+            link_repo,
+            issues_repo,
+            extension_repo.clone(),
+        );
+
+        let rule_pack_context = RulePackService::new(
+            extension_repo.clone(),
+            report_pattern_repo.clone(),
+            report_template_repo.clone(),
+            rule_pack_repo,
+            settings_repo.clone(),
         );
 
         Ok(AppState {
@@ -196,9 +226,12 @@ impl AppState {
             ai_context,
             local_model_context,
             extension_repo,
+            dry_run_context,
             report_pattern_repo,
             report_template_repo,
             report_context,
+            rule_pack_context,
+            snapshot_context,
         })
     }
 
@@ -1,14 +1,19 @@
 use crate::contexts::{
     ai::AiInsight,
     analysis::{
-        CompleteJobResult, Heading, Image, Issue, IssueSeverity, Job, JobInfo, JobPageQuery,
+        CompleteJobResult, DiscoveryFrontier, Heading, HttpConfig, Image, Issue, IssueSeverity, Job, JobInfo, JobPageQuery,
         JobSettings, JobStatus, LighthouseData, Link, NewHeading, NewImage, NewIssue, NewLink,
-        NewPageQueueItem, Page, PageInfo, PageQueueItem, PageQueueStatus,
+        NewPageQueueItem, Page, PageInfo, PageQueueItem, PageQueueStatus, SiteCheckResult,
+        TlsInspection,
     },
     extension::{CustomCheck, CustomCheckParams, CustomExtractor, CustomExtractorParams},
     report::{ReportPattern, ReportPatternParams, ReportTemplate},
+    rule_pack::RulePackWrite,
+    snapshot::{NewPageSnapshot, PageSnapshot, SnapshotStorage},
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use std::sync::Arc;
 pub mod error;
 pub mod sqlite;
@@ -60,11 +65,30 @@ pub fn sqlite_report_template_repo(pool: sqlx::SqlitePool) -> Arc<dyn ReportTemp
     Arc::new(sqlite::ReportTemplateRepository::new(pool))
 }
 
+pub fn sqlite_rule_pack_repo(pool: sqlx::SqlitePool) -> Arc<dyn RulePackRepository> {
+    Arc::new(sqlite::SqliteRulePackRepository::new(pool))
+}
+
+pub fn sqlite_snapshot_repo(pool: sqlx::SqlitePool) -> Arc<dyn SnapshotRepository> {
+    Arc::new(sqlite::SnapshotRepository::new(pool))
+}
+
 pub use sqlite::{ExternalDomain, IssueCounts, IssueGroup, LinkCounts};
 
 #[async_trait]
 pub trait JobRepository: Send + Sync {
     async fn create(&self, url: &str, settings: &JobSettings) -> RepositoryResult<String>;
+    /// Create a job together with its HTTP configuration and, for list
+    /// mode, `page_urls` already in its page queue. All of it is written
+    /// in one transaction, so no worker can pick the job up before it is
+    /// complete.
+    async fn create_with(
+        &self,
+        url: &str,
+        settings: &JobSettings,
+        http_config: Option<&HttpConfig>,
+        page_urls: &[String],
+    ) -> RepositoryResult<String>;
     async fn get_by_id(&self, id: &str) -> RepositoryResult<Job>;
     async fn get_all(&self) -> RepositoryResult<Vec<JobInfo>>;
     async fn get_paginated(&self, limit: i64, offset: i64) -> RepositoryResult<Vec<JobInfo>>;
@@ -75,19 +99,38 @@ pub trait JobRepository: Send + Sync {
     async fn get_pending(&self) -> RepositoryResult<Vec<Job>>;
     async fn get_running_jobs_id(&self) -> RepositoryResult<Vec<String>>;
     async fn update_status(&self, job_id: &str, status: JobStatus) -> RepositoryResult<()>;
+    /// Mark the job paused if it is still pending, discovering or
+    /// processing. Fails with `NotFound` when it has moved on since it
+    /// was looked up, e.g. finished or been cancelled.
+    async fn pause(&self, job_id: &str) -> RepositoryResult<()>;
     async fn update_progress(&self, id: &str, progress: f64) -> RepositoryResult<()>;
-    async fn update_resources(
+    /// Store the job's site-level check results, replacing any previous
+    /// ones. Their order is kept.
+    async fn set_site_checks(
         &self,
-        id: &str,
-        sitemap_found: bool,
-        robots_txt_found: bool,
+        job_id: &str,
+        checks: &[SiteCheckResult],
     ) -> RepositoryResult<()>;
+    /// Empty when the job hasn't checked its site files yet.
+    async fn get_site_checks(&self, job_id: &str) -> RepositoryResult<Vec<SiteCheckResult>>;
     async fn set_error(&self, job_id: &str, error: &str) -> RepositoryResult<()>;
     /// Total number of jobs in the database. Migrated to the per-layer
     /// `RepositoryResult` so callers can match on `RepositoryError::Database`
     /// for retry logic instead of stringly-typed `anyhow` matching.
     async fn count(&self) -> RepositoryResult<i64>;
     async fn delete(&self, job_id: &str) -> RepositoryResult<()>;
+    /// Store the job's HTTP configuration, replacing any previous one.
+    /// Kept apart from the job row because it holds credentials.
+    async fn set_http_config(&self, job_id: &str, config: &HttpConfig) -> RepositoryResult<()>;
+    /// `None` when the job sends plain requests.
+    async fn get_http_config(&self, job_id: &str) -> RepositoryResult<Option<HttpConfig>>;
+    async fn set_tls_inspection(
+        &self,
+        job_id: &str,
+        inspection: &TlsInspection,
+    ) -> RepositoryResult<()>;
+    /// `None` when the site wasn't inspected (plain http, or behind a proxy).
+    async fn get_tls_inspection(&self, job_id: &str) -> RepositoryResult<Option<TlsInspection>>;
 }
 
 #[async_trait]
@@ -97,6 +140,8 @@ pub trait PageRepository: Send + Sync {
     async fn get_by_job_id(&self, job_id: &str) -> RepositoryResult<Vec<Page>>;
     async fn get_info_by_job_id(&self, job_id: &str) -> RepositoryResult<Vec<PageInfo>>;
     async fn get_by_id(&self, page_id: &str) -> RepositoryResult<Page>;
+    /// The page of `job_id` crawled at `url`.
+    async fn get_by_url(&self, job_id: &str, url: &str) -> RepositoryResult<Page>;
     async fn replace_headings(
         &self,
         page_id: &str,
@@ -109,6 +154,9 @@ pub trait PageRepository: Send + Sync {
         &self,
         job_id: &str,
     ) -> RepositoryResult<Vec<LighthouseData>>;
+    async fn get_headings_by_job_id(&self, job_id: &str) -> RepositoryResult<Vec<Heading>>;
+    async fn get_headings(&self, page_id: &str) -> RepositoryResult<Vec<Heading>>;
+    async fn get_images(&self, page_id: &str) -> RepositoryResult<Vec<Image>>;
 }
 
 #[async_trait]
@@ -142,6 +190,15 @@ pub trait IssueRepository: Send + Sync {
     async fn count_by_severity(&self, job_id: &str) -> RepositoryResult<IssueCounts>;
     async fn count_by_job_id(&self, job_id: &str) -> RepositoryResult<i64>;
     async fn get_grouped_by_type(&self, job_id: &str) -> RepositoryResult<Vec<IssueGroup>>;
+    /// Atomically store the pages' re-extracted data, delete every issue
+    /// a custom check raised on the job and insert `issues` in their
+    /// place. Returns how many issues were deleted.
+    async fn replace_custom_check_issues(
+        &self,
+        job_id: &str,
+        extracted_data: &[(&str, &HashMap<String, serde_json::Value>)],
+        issues: &[NewIssue],
+    ) -> RepositoryResult<u64>;
 }
 
 #[async_trait]
@@ -168,7 +225,21 @@ pub trait ResultsRepository: Send + Sync {
 #[async_trait]
 pub trait AiRepository: Send + Sync {
     async fn get_ai_insights(&self, job_id: &str) -> RepositoryResult<Option<String>>;
-    async fn save_ai_insights(&self, job_id: &str, insights: &str) -> RepositoryResult<()>;
+    /// The job's insights if `backend` (an [`AiBackend::name`]) wrote
+    /// them.
+    ///
+    /// [`AiBackend::name`]: crate::service::AiBackend::name
+    async fn get_ai_insights_from(
+        &self,
+        job_id: &str,
+        backend: &str,
+    ) -> RepositoryResult<Option<String>>;
+    async fn save_ai_insights(
+        &self,
+        job_id: &str,
+        backend: &str,
+        insights: &str,
+    ) -> RepositoryResult<()>;
 }
 
 /// Repository for managing the page analysis queue.
@@ -225,11 +296,33 @@ pub trait PageQueueRepository: Send + Sync {
 
     /// Check if all pages for a job are complete (no pending or processing).
     async fn is_job_complete(&self, job_id: &str) -> RepositoryResult<bool>;
+
+    /// Store (or replace) the discovery checkpoint of a paused job.
+    async fn save_frontier(
+        &self,
+        job_id: &str,
+        frontier: &DiscoveryFrontier,
+    ) -> RepositoryResult<()>;
+
+    /// Load the discovery checkpoint for a job, if it was paused.
+    async fn load_frontier(&self, job_id: &str) -> RepositoryResult<Option<DiscoveryFrontier>>;
+
+    /// Drop the discovery checkpoint once it has been consumed.
+    async fn delete_frontier(&self, job_id: &str) -> RepositoryResult<()>;
 }
 
 #[async_trait]
 pub trait ExtensionRepository: Send + Sync {
-    async fn create_check(&self, params: &CustomCheckParams) -> RepositoryResult<CustomCheck>;
+    async fn create_check(&self, params: &CustomCheckParams) -> RepositoryResult<CustomCheck> {
+        self.create_check_with_id(&uuid::Uuid::new_v4().to_string(), params).await
+    }
+    /// Create a check under a given id, e.g. one kept from an imported
+    /// rule pack so importing it again is recognised.
+    async fn create_check_with_id(
+        &self,
+        id: &str,
+        params: &CustomCheckParams,
+    ) -> RepositoryResult<CustomCheck>;
     async fn list_checks(&self) -> RepositoryResult<Vec<CustomCheck>>;
     async fn get_check(&self, id: &str) -> RepositoryResult<CustomCheck>;
     async fn update_check(
@@ -243,6 +336,14 @@ pub trait ExtensionRepository: Send + Sync {
     async fn create_extractor(
         &self,
         params: &CustomExtractorParams,
+    ) -> RepositoryResult<CustomExtractor> {
+        self.create_extractor_with_id(&uuid::Uuid::new_v4().to_string(), params).await
+    }
+    /// See [`ExtensionRepository::create_check_with_id`].
+    async fn create_extractor_with_id(
+        &self,
+        id: &str,
+        params: &CustomExtractorParams,
     ) -> RepositoryResult<CustomExtractor>;
     async fn list_extractors(&self) -> RepositoryResult<Vec<CustomExtractor>>;
     async fn get_extractor(&self, id: &str) -> RepositoryResult<CustomExtractor>;
@@ -260,7 +361,15 @@ pub trait ReportPatternRepository: Send + Sync {
     async fn list_patterns(&self) -> RepositoryResult<Vec<ReportPattern>>;
     async fn list_enabled_patterns(&self) -> RepositoryResult<Vec<ReportPattern>>;
     async fn get_pattern(&self, id: &str) -> RepositoryResult<ReportPattern>;
-    async fn create_pattern(&self, params: &ReportPatternParams) -> RepositoryResult<ReportPattern>;
+    async fn create_pattern(&self, params: &ReportPatternParams) -> RepositoryResult<ReportPattern> {
+        self.create_pattern_with_id(&uuid::Uuid::new_v4().to_string(), params).await
+    }
+    /// See [`ExtensionRepository::create_check_with_id`].
+    async fn create_pattern_with_id(
+        &self,
+        id: &str,
+        params: &ReportPatternParams,
+    ) -> RepositoryResult<ReportPattern>;
     async fn update_pattern(
         &self,
         id: &str,
@@ -280,3 +389,34 @@ pub trait ReportTemplateRepository: Send + Sync {
     async fn set_active_template(&self, id: &str) -> RepositoryResult<()>;
     async fn delete_template(&self, id: &str) -> RepositoryResult<()>;
 }
+
+#[async_trait]
+pub trait RulePackRepository: Send + Sync {
+    /// Carry out a rule pack import's writes in one transaction, so a
+    /// write that fails leaves none of the others behind.
+    async fn import(&self, writes: &[RulePackWrite]) -> RepositoryResult<()>;
+}
+
+/// Page source saved per analysed page, with identical bodies stored once.
+#[async_trait]
+pub trait SnapshotRepository: Send + Sync {
+    /// Save a page's HTML and headers, replacing any earlier snapshot of
+    /// the same page.
+    async fn save(&self, snapshot: &NewPageSnapshot<'_>) -> RepositoryResult<()>;
+
+    /// The snapshot of the page of `job_id` crawled at `url`.
+    async fn get(&self, job_id: &str, url: &str) -> RepositoryResult<PageSnapshot>;
+
+    /// The snapshot of one page, if its HTML was saved.
+    async fn get_by_page_id(&self, page_id: &str) -> RepositoryResult<Option<PageSnapshot>>;
+
+    /// The latest snapshot of `url` from a job started before `job_id`.
+    async fn get_previous(&self, job_id: &str, url: &str)
+        -> RepositoryResult<Option<PageSnapshot>>;
+
+    /// Delete snapshots taken before `older_than`, then any stored source
+    /// no snapshot uses. Returns how many snapshots were deleted.
+    async fn prune(&self, older_than: Option<DateTime<Utc>>) -> RepositoryResult<u64>;
+
+    async fn storage(&self) -> RepositoryResult<SnapshotStorage>;
+}
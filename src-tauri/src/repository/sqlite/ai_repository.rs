@@ -20,17 +20,33 @@ impl AiRepository {
         Ok(result)
     }
 
+    pub async fn get_ai_insights_from(
+        &self,
+        job_id: &str,
+        backend: &str,
+    ) -> RepositoryResult<Option<String>> {
+        let result = sqlx::query_scalar::<_, String>(
+            "SELECT summary FROM ai_insights WHERE job_id = ? AND model = ?",
+        )
+        .bind(job_id)
+        .bind(backend)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(result)
+    }
+
     pub async fn save_ai_insights(
         &self,
         job_id: &str,
+        backend: &str,
         insights: &str,
     ) -> RepositoryResult<()> {
         sqlx::query!(
-            "INSERT INTO ai_insights (job_id, summary, created_at, updated_at) VALUES (?, ?, datetime('now'), datetime('now'))
-             ON CONFLICT(job_id) DO UPDATE SET summary = ?, updated_at = datetime('now')",
+            "INSERT INTO ai_insights (job_id, summary, model, created_at, updated_at) VALUES (?, ?, ?, datetime('now'), datetime('now'))
+             ON CONFLICT(job_id) DO UPDATE SET summary = excluded.summary, model = excluded.model, updated_at = datetime('now')",
             job_id,
             insights,
-            insights
+            backend
         )
         .execute(&self.pool)
         .await?;
@@ -44,8 +60,21 @@ impl crate::repository::AiRepository for AiRepository {
         AiRepository::get_ai_insights(self, job_id).await
     }
 
-    async fn save_ai_insights(&self, job_id: &str, insights: &str) -> RepositoryResult<()> {
-        AiRepository::save_ai_insights(self, job_id, insights).await
+    async fn get_ai_insights_from(
+        &self,
+        job_id: &str,
+        backend: &str,
+    ) -> RepositoryResult<Option<String>> {
+        AiRepository::get_ai_insights_from(self, job_id, backend).await
+    }
+
+    async fn save_ai_insights(
+        &self,
+        job_id: &str,
+        backend: &str,
+        insights: &str,
+    ) -> RepositoryResult<()> {
+        AiRepository::save_ai_insights(self, job_id, backend, insights).await
     }
 }
 
@@ -82,12 +111,16 @@ mod tests {
         // Create the job record first to satisfy FK constraint
         create_test_job(&pool, "job_123").await;
 
-        repo.save_ai_insights("job_123", "These are AI insights")
+        repo.save_ai_insights("job_123", "Gemini", "These are AI insights")
             .await
             .unwrap();
 
         let result = repo.get_ai_insights("job_123").await.unwrap();
         assert_eq!(result, Some("These are AI insights".to_string()));
+        let result = repo.get_ai_insights_from("job_123", "Gemini").await.unwrap();
+        assert_eq!(result, Some("These are AI insights".to_string()));
+        let result = repo.get_ai_insights_from("job_123", "Ollama").await.unwrap();
+        assert!(result.is_none(), "Another backend's insights aren't its own");
     }
 
     #[tokio::test]
@@ -98,10 +131,10 @@ mod tests {
         // Create the job record first to satisfy FK constraint
         create_test_job(&pool, "job_456").await;
 
-        repo.save_ai_insights("job_456", "Original insights")
+        repo.save_ai_insights("job_456", "Gemini", "Original insights")
             .await
             .unwrap();
-        repo.save_ai_insights("job_456", "Updated insights")
+        repo.save_ai_insights("job_456", "Ollama", "Updated insights")
             .await
             .unwrap();
 
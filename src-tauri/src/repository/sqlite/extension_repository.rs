@@ -1,11 +1,13 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use std::str::FromStr;
 
 use crate::contexts::analysis::IssueSeverity;
 use crate::contexts::extension::{
-    CustomCheck, CustomCheckParams, CustomExtractor, CustomExtractorParams, Operator,
+    CheckScope, CustomCheck, CustomCheckParams, CustomExtractor, CustomExtractorParams,
+    ExtractorKind, ExtractorOutput, Operator,
 };
+use crate::contexts::tags::TagDataType;
 use crate::repository::{ExtensionRepository, RepositoryError, RepositoryResult};
 
 pub struct SqliteExtensionRepository {
@@ -18,29 +20,19 @@ impl SqliteExtensionRepository {
     }
 }
 
-use super::require_affected;
-
-fn check_from_params(id: String, p: &CustomCheckParams) -> CustomCheck {
-    CustomCheck {
-        id,
-        name: p.name.clone(),
-        severity: p.severity,
-        field: p.field.clone(),
-        operator: p.operator.clone(),
-        threshold: p.threshold.clone(),
-        message_template: p.message_template.clone(),
-        enabled: p.enabled,
-    }
-}
+use super::{decode_json_column, encode_json_column, require_affected};
 
 fn extractor_from_params(id: String, p: &CustomExtractorParams) -> CustomExtractor {
     CustomExtractor {
         id,
         name: p.name.clone(),
         tag: p.tag.clone(),
+        kind: p.kind,
         selector: p.selector.clone(),
         attribute: p.attribute.clone(),
         multiple: p.multiple,
+        output: p.output,
+        data_type: p.data_type,
         enabled: p.enabled,
     }
 }
@@ -49,33 +41,20 @@ fn extractor_from_params(id: String, p: &CustomExtractorParams) -> CustomExtract
 impl ExtensionRepository for SqliteExtensionRepository {
     // --- CustomCheck CRUD ---
 
-    async fn create_check(&self, params: &CustomCheckParams) -> RepositoryResult<CustomCheck> {
-        let id = uuid::Uuid::new_v4().to_string();
-        let severity = params.severity.as_str();
-        let operator = params.operator.to_string();
-        let enabled = i64::from(params.enabled);
-
-        sqlx::query(
-            "INSERT INTO custom_checks (id, name, severity, field, operator, threshold, message_template, enabled)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&id)
-        .bind(&params.name)
-        .bind(severity)
-        .bind(&params.field)
-        .bind(&operator)
-        .bind(&params.threshold)
-        .bind(&params.message_template)
-        .bind(enabled)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(check_from_params(id, params))
+    async fn create_check_with_id(
+        &self,
+        id: &str,
+        params: &CustomCheckParams,
+    ) -> RepositoryResult<CustomCheck> {
+        let mut conn = self.pool.acquire().await?;
+        insert_check(&mut conn, id, params).await?;
+        Ok(CustomCheck::from_params(id.to_string(), params))
     }
 
     async fn list_checks(&self) -> RepositoryResult<Vec<CustomCheck>> {
         let rows = sqlx::query_as::<_, CheckRow>(
-            "SELECT id, name, severity, field, operator, threshold, message_template, enabled
+            "SELECT id, name, severity, field, operator, threshold, condition, scope, aggregate,
+                    script, message_template, enabled
              FROM custom_checks ORDER BY created_at ASC",
         )
         .fetch_all(&self.pool)
@@ -86,7 +65,8 @@ impl ExtensionRepository for SqliteExtensionRepository {
 
     async fn get_check(&self, id: &str) -> RepositoryResult<CustomCheck> {
         let row = sqlx::query_as::<_, CheckRow>(
-            "SELECT id, name, severity, field, operator, threshold, message_template, enabled
+            "SELECT id, name, severity, field, operator, threshold, condition, scope, aggregate,
+                    script, message_template, enabled
              FROM custom_checks WHERE id = ?",
         )
         .bind(id)
@@ -105,30 +85,9 @@ impl ExtensionRepository for SqliteExtensionRepository {
         id: &str,
         params: &CustomCheckParams,
     ) -> RepositoryResult<CustomCheck> {
-        let severity = params.severity.as_str();
-        let operator = params.operator.to_string();
-        let enabled = i64::from(params.enabled);
-
-        let rows_affected = sqlx::query(
-            "UPDATE custom_checks
-             SET name = ?, severity = ?, field = ?, operator = ?, threshold = ?,
-                 message_template = ?, enabled = ?, updated_at = datetime('now')
-             WHERE id = ?",
-        )
-        .bind(&params.name)
-        .bind(severity)
-        .bind(&params.field)
-        .bind(&operator)
-        .bind(&params.threshold)
-        .bind(&params.message_template)
-        .bind(enabled)
-        .bind(id)
-        .execute(&self.pool)
-        .await?
-        .rows_affected();
-
-        require_affected(rows_affected, "custom_check", id)?;
-        Ok(check_from_params(id.to_string(), params))
+        let mut conn = self.pool.acquire().await?;
+        update_check(&mut conn, id, params).await?;
+        Ok(CustomCheck::from_params(id.to_string(), params))
     }
 
     async fn delete_check(&self, id: &str) -> RepositoryResult<()> {
@@ -143,7 +102,8 @@ impl ExtensionRepository for SqliteExtensionRepository {
 
     async fn list_enabled_checks(&self) -> RepositoryResult<Vec<CustomCheck>> {
         let rows = sqlx::query_as::<_, CheckRow>(
-            "SELECT id, name, severity, field, operator, threshold, message_template, enabled
+            "SELECT id, name, severity, field, operator, threshold, condition, scope, aggregate,
+                    script, message_template, enabled
              FROM custom_checks WHERE enabled = 1 ORDER BY created_at ASC",
         )
         .fetch_all(&self.pool)
@@ -154,45 +114,30 @@ impl ExtensionRepository for SqliteExtensionRepository {
 
     // --- CustomExtractor CRUD ---
 
-    async fn create_extractor(
+    async fn create_extractor_with_id(
         &self,
+        id: &str,
         params: &CustomExtractorParams,
     ) -> RepositoryResult<CustomExtractor> {
-        let id = uuid::Uuid::new_v4().to_string();
-        let multiple = i64::from(params.multiple);
-        let enabled = i64::from(params.enabled);
-
-        sqlx::query(
-            "INSERT INTO custom_extractors (id, name, tag, selector, attribute, multiple, enabled)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
-        )
-        .bind(&id)
-        .bind(&params.name)
-        .bind(&params.tag)
-        .bind(&params.selector)
-        .bind(&params.attribute)
-        .bind(multiple)
-        .bind(enabled)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(extractor_from_params(id, params))
+        let mut conn = self.pool.acquire().await?;
+        insert_extractor(&mut conn, id, params).await?;
+        Ok(extractor_from_params(id.to_string(), params))
     }
 
     async fn list_extractors(&self) -> RepositoryResult<Vec<CustomExtractor>> {
         let rows = sqlx::query_as::<_, ExtractorRow>(
-            "SELECT id, name, tag, selector, attribute, multiple, enabled
+            "SELECT id, name, tag, kind, selector, attribute, multiple, output, data_type, enabled
              FROM custom_extractors ORDER BY created_at ASC",
         )
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(ExtractorRow::into_domain).collect())
+        rows.into_iter().map(ExtractorRow::into_domain).collect()
     }
 
     async fn get_extractor(&self, id: &str) -> RepositoryResult<CustomExtractor> {
         let row = sqlx::query_as::<_, ExtractorRow>(
-            "SELECT id, name, tag, selector, attribute, multiple, enabled
+            "SELECT id, name, tag, kind, selector, attribute, multiple, output, data_type, enabled
              FROM custom_extractors WHERE id = ?",
         )
         .bind(id)
@@ -203,7 +148,7 @@ impl ExtensionRepository for SqliteExtensionRepository {
             other => RepositoryError::from(other),
         })?;
 
-        Ok(row.into_domain())
+        row.into_domain()
     }
 
     async fn update_extractor(
@@ -211,27 +156,8 @@ impl ExtensionRepository for SqliteExtensionRepository {
         id: &str,
         params: &CustomExtractorParams,
     ) -> RepositoryResult<CustomExtractor> {
-        let multiple = i64::from(params.multiple);
-        let enabled = i64::from(params.enabled);
-
-        let rows_affected = sqlx::query(
-            "UPDATE custom_extractors
-             SET name = ?, tag = ?, selector = ?, attribute = ?, multiple = ?, enabled = ?,
-                 updated_at = datetime('now')
-             WHERE id = ?",
-        )
-        .bind(&params.name)
-        .bind(&params.tag)
-        .bind(&params.selector)
-        .bind(&params.attribute)
-        .bind(multiple)
-        .bind(enabled)
-        .bind(id)
-        .execute(&self.pool)
-        .await?
-        .rows_affected();
-
-        require_affected(rows_affected, "custom_extractor", id)?;
+        let mut conn = self.pool.acquire().await?;
+        update_extractor(&mut conn, id, params).await?;
         Ok(extractor_from_params(id.to_string(), params))
     }
 
@@ -247,16 +173,150 @@ impl ExtensionRepository for SqliteExtensionRepository {
 
     async fn list_enabled_extractors(&self) -> RepositoryResult<Vec<CustomExtractor>> {
         let rows = sqlx::query_as::<_, ExtractorRow>(
-            "SELECT id, name, tag, selector, attribute, multiple, enabled
+            "SELECT id, name, tag, kind, selector, attribute, multiple, output, data_type, enabled
              FROM custom_extractors WHERE enabled = 1 ORDER BY created_at ASC",
         )
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(ExtractorRow::into_domain).collect())
+        rows.into_iter().map(ExtractorRow::into_domain).collect()
     }
 }
 
+// --- Writes, on a connection so a rule pack import can run them in its transaction ---
+
+pub(super) async fn insert_check(
+    conn: &mut SqliteConnection,
+    id: &str,
+    params: &CustomCheckParams,
+) -> RepositoryResult<()> {
+    let severity = params.severity.as_str();
+    let operator = params.operator.to_string();
+    let condition = encode_json_column("custom_check", params.condition.as_ref())?;
+    let aggregate = encode_json_column("custom_check", params.aggregate.as_ref())?;
+    let enabled = i64::from(params.enabled);
+
+    sqlx::query(
+        "INSERT INTO custom_checks
+            (id, name, severity, field, operator, threshold, condition, scope, aggregate, script,
+             message_template, enabled)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(id)
+    .bind(&params.name)
+    .bind(severity)
+    .bind(&params.field)
+    .bind(&operator)
+    .bind(&params.threshold)
+    .bind(&condition)
+    .bind(params.scope.as_str())
+    .bind(&aggregate)
+    .bind(&params.script)
+    .bind(&params.message_template)
+    .bind(enabled)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn update_check(
+    conn: &mut SqliteConnection,
+    id: &str,
+    params: &CustomCheckParams,
+) -> RepositoryResult<()> {
+    let severity = params.severity.as_str();
+    let operator = params.operator.to_string();
+    let condition = encode_json_column("custom_check", params.condition.as_ref())?;
+    let aggregate = encode_json_column("custom_check", params.aggregate.as_ref())?;
+    let enabled = i64::from(params.enabled);
+
+    let rows_affected = sqlx::query(
+        "UPDATE custom_checks
+         SET name = ?, severity = ?, field = ?, operator = ?, threshold = ?, condition = ?,
+             scope = ?, aggregate = ?, script = ?, message_template = ?, enabled = ?,
+             updated_at = datetime('now')
+         WHERE id = ?",
+    )
+    .bind(&params.name)
+    .bind(severity)
+    .bind(&params.field)
+    .bind(&operator)
+    .bind(&params.threshold)
+    .bind(&condition)
+    .bind(params.scope.as_str())
+    .bind(&aggregate)
+    .bind(&params.script)
+    .bind(&params.message_template)
+    .bind(enabled)
+    .bind(id)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    require_affected(rows_affected, "custom_check", id)
+}
+
+pub(super) async fn insert_extractor(
+    conn: &mut SqliteConnection,
+    id: &str,
+    params: &CustomExtractorParams,
+) -> RepositoryResult<()> {
+    let multiple = i64::from(params.multiple);
+    let enabled = i64::from(params.enabled);
+
+    sqlx::query(
+        "INSERT INTO custom_extractors
+            (id, name, tag, kind, selector, attribute, multiple, output, data_type, enabled)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(id)
+    .bind(&params.name)
+    .bind(&params.tag)
+    .bind(params.kind.as_str())
+    .bind(&params.selector)
+    .bind(&params.attribute)
+    .bind(multiple)
+    .bind(params.output.as_str())
+    .bind(params.data_type.as_ref().map(TagDataType::as_str))
+    .bind(enabled)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn update_extractor(
+    conn: &mut SqliteConnection,
+    id: &str,
+    params: &CustomExtractorParams,
+) -> RepositoryResult<()> {
+    let multiple = i64::from(params.multiple);
+    let enabled = i64::from(params.enabled);
+
+    let rows_affected = sqlx::query(
+        "UPDATE custom_extractors
+         SET name = ?, tag = ?, kind = ?, selector = ?, attribute = ?, multiple = ?,
+             output = ?, data_type = ?, enabled = ?, updated_at = datetime('now')
+         WHERE id = ?",
+    )
+    .bind(&params.name)
+    .bind(&params.tag)
+    .bind(params.kind.as_str())
+    .bind(&params.selector)
+    .bind(&params.attribute)
+    .bind(multiple)
+    .bind(params.output.as_str())
+    .bind(params.data_type.as_ref().map(TagDataType::as_str))
+    .bind(enabled)
+    .bind(id)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    require_affected(rows_affected, "custom_extractor", id)
+}
+
 // --- Row types for sqlx ---
 
 #[derive(sqlx::FromRow)]
@@ -267,6 +327,10 @@ struct CheckRow {
     field: String,
     operator: String,
     threshold: Option<String>,
+    condition: Option<String>,
+    scope: String,
+    aggregate: Option<String>,
+    script: Option<String>,
     message_template: String,
     enabled: i64,
 }
@@ -287,6 +351,12 @@ impl CheckRow {
                 RepositoryError::decode("custom_check", format!("invalid operator: {e}"))
             })?,
             threshold: self.threshold,
+            condition: decode_json_column("custom_check", "condition", self.condition.as_deref())?,
+            scope: CheckScope::from_str(&self.scope).map_err(|e| {
+                RepositoryError::decode("custom_check", format!("invalid scope: {e}"))
+            })?,
+            aggregate: decode_json_column("custom_check", "aggregate", self.aggregate.as_deref())?,
+            script: self.script,
             message_template: self.message_template,
             enabled: self.enabled != 0,
         })
@@ -298,22 +368,36 @@ struct ExtractorRow {
     id: String,
     name: String,
     tag: String,
+    kind: String,
     selector: String,
     attribute: Option<String>,
     multiple: i64,
+    output: String,
+    data_type: Option<String>,
     enabled: i64,
 }
 
 impl ExtractorRow {
-    fn into_domain(self) -> CustomExtractor {
-        CustomExtractor {
+    fn into_domain(self) -> RepositoryResult<CustomExtractor> {
+        let decode = |message: String| RepositoryError::decode("custom_extractor", message);
+        Ok(CustomExtractor {
             id: self.id,
             name: self.name,
             tag: self.tag,
+            kind: ExtractorKind::from_str(&self.kind)
+                .map_err(|e| decode(format!("invalid kind: {e}")))?,
             selector: self.selector,
             attribute: self.attribute,
             multiple: self.multiple != 0,
+            output: ExtractorOutput::from_str(&self.output)
+                .map_err(|e| decode(format!("invalid output: {e}")))?,
+            data_type: self
+                .data_type
+                .as_deref()
+                .map(TagDataType::from_str)
+                .transpose()
+                .map_err(|e| decode(format!("invalid data type: {e}")))?,
             enabled: self.enabled != 0,
-        }
+        })
     }
 }
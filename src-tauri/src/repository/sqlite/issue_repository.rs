@@ -1,7 +1,9 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 
-use super::map_severity;
+use super::{map_severity, page_repository};
 use crate::contexts::analysis::{Issue, IssueSeverity, NewIssue};
 use crate::repository::{IssueRepository as IssueRepositoryTrait, RepositoryResult};
 use async_trait::async_trait;
@@ -86,30 +88,8 @@ impl IssueRepositoryTrait for IssueRepository {
             return Ok(());
         }
 
-        const CHUNK_SIZE: usize = 100;
         let mut tx = self.pool.begin().await?;
-
-        for chunk in issues.chunks(CHUNK_SIZE) {
-            let mut qb = sqlx::QueryBuilder::new(
-                r#"
-                INSERT INTO issues (
-                    job_id, page_id, type, severity, message, details, created_at
-                ) "#,
-            );
-
-            qb.push_values(chunk, |mut b, issue| {
-                b.push_bind(&issue.job_id)
-                    .push_bind(&issue.page_id)
-                    .push_bind(&issue.issue_type)
-                    .push_bind(issue.severity.as_str())
-                    .push_bind(&issue.message)
-                    .push_bind(&issue.details)
-                    .push_bind(Utc::now().to_rfc3339());
-            });
-
-            qb.build().execute(&mut *tx).await?;
-        }
-
+        insert_issues(&mut tx, issues).await?;
         tx.commit().await?;
         tracing::debug!("Inserted {} issues", issues.len());
         Ok(())
@@ -262,6 +242,74 @@ impl IssueRepositoryTrait for IssueRepository {
             })
             .collect())
     }
+
+    async fn replace_custom_check_issues(
+        &self,
+        job_id: &str,
+        extracted_data: &[(&str, &HashMap<String, serde_json::Value>)],
+        issues: &[NewIssue],
+    ) -> RepositoryResult<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        for (page_id, data) in extracted_data {
+            page_repository::update_extracted_data(&mut tx, page_id, data).await?;
+        }
+
+        let removed =
+            sqlx::query("DELETE FROM issues WHERE job_id = ? AND custom_check_id IS NOT NULL")
+                .bind(job_id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+
+        // The insert trigger keeps the job's issue counts current, but
+        // nothing recounts them after a delete.
+        sqlx::query(
+            r#"
+            UPDATE jobs SET
+                total_issues = (SELECT COUNT(*) FROM issues WHERE job_id = ?1),
+                critical_issues = (SELECT COUNT(*) FROM issues WHERE job_id = ?1 AND severity = 'critical'),
+                warning_issues = (SELECT COUNT(*) FROM issues WHERE job_id = ?1 AND severity = 'warning'),
+                info_issues = (SELECT COUNT(*) FROM issues WHERE job_id = ?1 AND severity = 'info')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(job_id)
+        .execute(&mut *tx)
+        .await?;
+
+        insert_issues(&mut tx, issues).await?;
+
+        tx.commit().await?;
+        Ok(removed)
+    }
+}
+
+async fn insert_issues(conn: &mut SqliteConnection, issues: &[NewIssue]) -> RepositoryResult<()> {
+    const CHUNK_SIZE: usize = 100;
+
+    for chunk in issues.chunks(CHUNK_SIZE) {
+        let mut qb = sqlx::QueryBuilder::new(
+            r#"
+            INSERT INTO issues (
+                job_id, page_id, type, severity, message, details, custom_check_id, created_at
+            ) "#,
+        );
+
+        qb.push_values(chunk, |mut b, issue| {
+            b.push_bind(&issue.job_id)
+                .push_bind(&issue.page_id)
+                .push_bind(&issue.issue_type)
+                .push_bind(issue.severity.as_str())
+                .push_bind(&issue.message)
+                .push_bind(&issue.details)
+                .push_bind(&issue.custom_check_id)
+                .push_bind(Utc::now().to_rfc3339());
+        });
+
+        qb.build().execute(&mut *conn).await?;
+    }
+    Ok(())
 }
 
 use super::parse_datetime;
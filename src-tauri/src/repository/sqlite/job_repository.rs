@@ -1,8 +1,13 @@
 use chrono::Utc;
-use sqlx::SqlitePool;
+use sqlx::{Row, SqliteConnection, SqlitePool};
 
 use super::map_job_status;
-use crate::contexts::{Job, JobId, JobInfo, JobPageQuery, JobSettings, JobStatus, JobSummary};
+use crate::contexts::analysis::{
+    CrawlProfile, Depth, HttpConfig, SiteCheckResult, SiteCheckStatus, TlsInspection, UrlRules,
+};
+use crate::contexts::{
+    Job, JobId, JobInfo, JobPageQuery, JobSettings, JobStatus, JobSummary, NewPageQueueItem,
+};
 use crate::repository::JobRepository as JobRepositoryTrait;
 use async_trait::async_trait;
 
@@ -14,19 +19,21 @@ impl JobRepository {
     pub fn new(pool: SqlitePool) -> Self {
         Self { pool }
     }
-}
 
-#[async_trait]
-impl JobRepositoryTrait for JobRepository {
-    async fn create(
-        &self,
+    /// Insert the pending job row `id` on `conn`.
+    async fn insert_job(
+        conn: &mut SqliteConnection,
+        id: &str,
         url: &str,
         settings: &JobSettings,
-    ) -> crate::repository::RepositoryResult<String> {
-        let id = uuid::Uuid::new_v4().to_string();
+    ) -> crate::repository::RepositoryResult<()> {
         let now = Utc::now().to_rfc3339();
 
         let lighthouse_analysis = i32::from(settings.lighthouse_analysis);
+        let resource_analysis = i32::from(settings.resource_analysis);
+        let url_rules = encode_url_rules(&settings.url_rules)?;
+        let crawl_mode = settings.crawl_mode.as_str();
+        let crawl_profile = encode_crawl_profile(&settings.crawl_profile)?;
 
         sqlx::query!(
             r#"
@@ -34,9 +41,9 @@ impl JobRepositoryTrait for JobRepository {
                 id, url, status, created_at, updated_at,
                 max_pages, max_depth, respect_robots_txt, include_subdomains, 
                 rate_limit_ms, user_agent, lighthouse_analysis,
-                sitemap_found, robots_txt_found
+                url_rules, crawl_mode, crawl_profile, resource_analysis
             )
-            VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 0, 0)
+            VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
             "#,
             id,
             url,
@@ -49,14 +56,79 @@ impl JobRepositoryTrait for JobRepository {
             settings.delay_between_requests,
             "SEO-Insikt-Crawler/0.1", // user_agent
             lighthouse_analysis,
+            url_rules,
+            crawl_mode,
+            crawl_profile,
+            resource_analysis,
         )
-        .execute(&self.pool)
+        .execute(conn)
         .await?;
+        Ok(())
+    }
+
+    /// Store `config` as the HTTP configuration of job `job_id` on `conn`.
+    async fn upsert_http_config(
+        conn: &mut SqliteConnection,
+        job_id: &str,
+        config: &HttpConfig,
+    ) -> crate::repository::RepositoryResult<()> {
+        let json = serde_json::to_string(config).map_err(|e| {
+            crate::repository::RepositoryError::decode("job_http_config.config", e.to_string())
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO job_http_config (job_id, config)
+            VALUES (?, ?)
+            ON CONFLICT(job_id) DO UPDATE SET config = excluded.config
+            "#,
+        )
+        .bind(job_id)
+        .bind(json)
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl JobRepositoryTrait for JobRepository {
+    async fn create(
+        &self,
+        url: &str,
+        settings: &JobSettings,
+    ) -> crate::repository::RepositoryResult<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let mut conn = self.pool.acquire().await?;
+        Self::insert_job(&mut conn, &id, url, settings).await?;
 
         tracing::info!("Created job {} for URL: {}", id, url);
         Ok(id)
     }
 
+    async fn create_with(
+        &self,
+        url: &str,
+        settings: &JobSettings,
+        http_config: Option<&HttpConfig>,
+        page_urls: &[String],
+    ) -> crate::repository::RepositoryResult<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let mut tx = self.pool.begin().await?;
+        Self::insert_job(&mut tx, &id, url, settings).await?;
+        if let Some(config) = http_config {
+            Self::upsert_http_config(&mut tx, &id, config).await?;
+        }
+        for page_url in page_urls {
+            let item = NewPageQueueItem::new(&id, page_url, Depth::root());
+            super::page_queue_repository::insert_item(&mut tx, &item).await?;
+        }
+        tx.commit().await?;
+
+        tracing::info!("Created job {} for URL: {} with {} queued pages", id, url, page_urls.len());
+        Ok(id)
+    }
+
     async fn get_by_id(&self, job_id: &str) -> crate::repository::RepositoryResult<Job> {
         let row = sqlx::query!(
             r#"
@@ -66,7 +138,8 @@ impl JobRepositoryTrait for JobRepository {
                 rate_limit_ms, user_agent, lighthouse_analysis,
                 total_pages, pages_crawled, total_issues, 
                 critical_issues, warning_issues, info_issues,
-                progress, error_message, sitemap_found, robots_txt_found
+                progress, error_message,
+                url_rules, crawl_mode, crawl_profile, resource_analysis
             FROM jobs
             WHERE id = ?
             "#,
@@ -75,7 +148,8 @@ impl JobRepositoryTrait for JobRepository {
         .fetch_one(&self.pool)
         .await?;
 
-        Ok(super::job_from_row!(row))
+        let site_checks = site_checks(&self.pool, job_id).await?;
+        Ok(super::job_from_row!(row, &site_checks))
     }
 
     async fn get_all(&self) -> crate::repository::RepositoryResult<Vec<JobInfo>> {
@@ -218,7 +292,8 @@ impl JobRepositoryTrait for JobRepository {
                 rate_limit_ms, user_agent, lighthouse_analysis,
                 total_pages, pages_crawled, total_issues, 
                 critical_issues, warning_issues, info_issues,
-                progress, error_message, sitemap_found, robots_txt_found
+                progress, error_message,
+                url_rules, crawl_mode, crawl_profile, resource_analysis
             FROM jobs
             WHERE status IN ('pending', 'discovery', 'processing')
             ORDER BY created_at ASC
@@ -227,34 +302,56 @@ impl JobRepositoryTrait for JobRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(|row| super::job_from_row!(row)).collect())
+        let mut jobs = Vec::with_capacity(rows.len());
+        for row in rows {
+            let site_checks = site_checks(&self.pool, &row.id).await?;
+            jobs.push(super::job_from_row!(row, &site_checks));
+        }
+        Ok(jobs)
     }
 
-    async fn update_resources(
+    async fn set_site_checks(
         &self,
         job_id: &str,
-        sitemap_found: bool,
-        robots_txt_found: bool,
+        checks: &[SiteCheckResult],
     ) -> crate::repository::RepositoryResult<()> {
-        let sitemap = i32::from(sitemap_found);
-        let robots = i32::from(robots_txt_found);
-
-        sqlx::query!(
-            r#"
-            UPDATE jobs
-            SET sitemap_found = ?1, robots_txt_found = ?2
-            WHERE id = ?3
-            "#,
-            sitemap,
-            robots,
-            job_id,
-        )
-        .execute(&self.pool)
-        .await?;
-
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM job_site_checks WHERE job_id = ?")
+            .bind(job_id)
+            .execute(&mut *tx)
+            .await?;
+
+        if !checks.is_empty() {
+            let mut qb = sqlx::QueryBuilder::new(
+                "INSERT INTO job_site_checks \
+                 (job_id, check_id, position, label, status, url, status_code, problems) ",
+            );
+            qb.push_values(checks.iter().enumerate(), |mut b, (position, check)| {
+                let problems =
+                    serde_json::to_string(&check.problems).unwrap_or_else(|_| "[]".to_string());
+                b.push_bind(job_id)
+                    .push_bind(&check.check_id)
+                    .push_bind(position as i64)
+                    .push_bind(&check.label)
+                    .push_bind(check.status.as_str())
+                    .push_bind(&check.url)
+                    .push_bind(check.status_code)
+                    .push_bind(problems);
+            });
+            qb.build().execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 
+    async fn get_site_checks(
+        &self,
+        job_id: &str,
+    ) -> crate::repository::RepositoryResult<Vec<SiteCheckResult>> {
+        site_checks(&self.pool, job_id).await
+    }
+
     async fn update_status(
         &self,
         job_id: &str,
@@ -284,6 +381,23 @@ impl JobRepositoryTrait for JobRepository {
         Ok(())
     }
 
+    async fn pause(&self, job_id: &str) -> crate::repository::RepositoryResult<()> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE jobs
+            SET status = 'paused'
+            WHERE id = ? AND status IN ('pending', 'discovery', 'processing')
+            "#,
+            job_id,
+        )
+        .execute(&self.pool)
+        .await?;
+        super::require_affected(result.rows_affected(), "pausable job", job_id)?;
+
+        tracing::info!("Updated job {} to status: paused", job_id);
+        Ok(())
+    }
+
     async fn update_progress(
         &self,
         job_id: &str,
@@ -328,14 +442,76 @@ impl JobRepositoryTrait for JobRepository {
     }
 
     async fn delete(&self, job_id: &str) -> crate::repository::RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
         sqlx::query!("DELETE FROM jobs WHERE id = ?", job_id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?; // sqlx::Error → RepositoryError via #[from]
+        // The job's snapshots cascade, the page sources they used don't.
+        super::snapshot_repository::delete_unused_blobs(&mut tx).await?;
+        tx.commit().await?;
 
         tracing::info!("Deleted job {}", job_id);
         Ok(())
     }
 
+    async fn set_http_config(
+        &self,
+        job_id: &str,
+        config: &HttpConfig,
+    ) -> crate::repository::RepositoryResult<()> {
+        let mut conn = self.pool.acquire().await?;
+        Self::upsert_http_config(&mut conn, job_id, config).await
+    }
+
+    /// Unlike `url_rules`, an unreadable row is an error: silently
+    /// dropping credentials would crawl the site logged out.
+    async fn get_http_config(
+        &self,
+        job_id: &str,
+    ) -> crate::repository::RepositoryResult<Option<HttpConfig>> {
+        let row = sqlx::query("SELECT config FROM job_http_config WHERE job_id = ?")
+            .bind(job_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(|row| {
+            serde_json::from_str(&row.get::<String, _>("config")).map_err(|e| {
+                crate::repository::RepositoryError::decode("job_http_config.config", e.to_string())
+            })
+        })
+        .transpose()
+    }
+
+    async fn set_tls_inspection(
+        &self,
+        job_id: &str,
+        inspection: &TlsInspection,
+    ) -> crate::repository::RepositoryResult<()> {
+        let json = serde_json::to_string(inspection).map_err(|e| {
+            crate::repository::RepositoryError::decode("job_tls_inspections.inspection", e.to_string())
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO job_tls_inspections (job_id, inspection)
+            VALUES (?, ?)
+            ON CONFLICT(job_id) DO UPDATE SET inspection = excluded.inspection
+            "#,
+        )
+        .bind(job_id)
+        .bind(json)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_tls_inspection(
+        &self,
+        job_id: &str,
+    ) -> crate::repository::RepositoryResult<Option<TlsInspection>> {
+        tls_inspection(&self.pool, job_id).await
+    }
+
     async fn get_running_jobs_id(&self) -> crate::repository::RepositoryResult<Vec<String>> {
         let rows = sqlx::query!(
             r#"
@@ -405,6 +581,10 @@ pub(super) fn decode_job_settings(
     include_subdomains: i64,
     lighthouse_analysis: i64,
     rate_limit_ms: i64,
+    url_rules: Option<&str>,
+    crawl_mode: &str,
+    crawl_profile: Option<&str>,
+    resource_analysis: i64,
 ) -> JobSettings {
     JobSettings {
         max_pages,
@@ -413,6 +593,120 @@ pub(super) fn decode_job_settings(
         mobile_analysis: false,
         lighthouse_analysis: lighthouse_analysis != 0,
         delay_between_requests: rate_limit_ms,
+        url_rules: decode_url_rules(url_rules),
+        crawl_mode: super::map_crawl_mode(crawl_mode),
+        crawl_profile: decode_crawl_profile(crawl_profile),
+        resource_analysis: resource_analysis != 0,
+    }
+}
+
+/// `NULL` for the default profile, like `url_rules`.
+fn encode_crawl_profile(
+    profile: &CrawlProfile,
+) -> crate::repository::RepositoryResult<Option<String>> {
+    if profile.is_default() {
+        return Ok(None);
+    }
+    serde_json::to_string(profile).map(Some).map_err(|e| {
+        crate::repository::RepositoryError::decode("jobs.crawl_profile", e.to_string())
+    })
+}
+
+/// A profile this build doesn't know (e.g. written by a newer version)
+/// falls back to the default one.
+fn decode_crawl_profile(raw: Option<&str>) -> CrawlProfile {
+    raw.and_then(|json| {
+        serde_json::from_str(json)
+            .inspect_err(|e| {
+                tracing::warn!("Unsupported jobs.crawl_profile, using the default: {}", e)
+            })
+            .ok()
+    })
+    .unwrap_or_default()
+}
+
+/// `NULL` for the default rules so existing rows and untouched jobs
+/// stay indistinguishable.
+fn encode_url_rules(rules: &UrlRules) -> crate::repository::RepositoryResult<Option<String>> {
+    if *rules == UrlRules::default() {
+        return Ok(None);
     }
+    serde_json::to_string(rules)
+        .map(Some)
+        .map_err(|e| crate::repository::RepositoryError::decode("jobs.url_rules", e.to_string()))
+}
+
+/// A column that fails to parse falls back to the default rules rather
+/// than making the whole job unreadable; the rules were validated when
+/// the job was created, so this only trips on hand-edited rows.
+fn decode_url_rules(raw: Option<&str>) -> UrlRules {
+    raw.and_then(|json| {
+        serde_json::from_str(json)
+            .inspect_err(|e| tracing::warn!("Ignoring unreadable jobs.url_rules: {}", e))
+            .ok()
+    })
+    .unwrap_or_default()
+}
+
+
+/// Whether the site check `check_id` among a job's `checks` found its
+/// file; a job's `sitemap_found` and `robots_txt_found` are read this way.
+pub(super) fn site_file_found(checks: &[SiteCheckResult], check_id: &str) -> bool {
+    checks.iter().any(|check| check.check_id == check_id && check.status.exists())
+}
+
+/// The job's site-level file checks in the order they ran. Unreadable
+/// problems read as none; an unknown status fails, as it can only come
+/// from a newer build's checks.
+pub(super) async fn site_checks(
+    pool: &SqlitePool,
+    job_id: &str,
+) -> crate::repository::RepositoryResult<Vec<SiteCheckResult>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT check_id, label, status, url, status_code, problems
+        FROM job_site_checks
+        WHERE job_id = ?
+        ORDER BY position
+        "#,
+        job_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            let status = row.status.parse::<SiteCheckStatus>().map_err(|e| {
+                crate::repository::RepositoryError::decode("job_site_checks.status", e.to_string())
+            })?;
+            Ok(SiteCheckResult {
+                check_id: row.check_id,
+                label: row.label,
+                status,
+                url: row.url,
+                status_code: row.status_code,
+                problems: serde_json::from_str(&row.problems).unwrap_or_default(),
+            })
+        })
+        .collect()
 }
 
+/// The job's stored TLS inspection. An unreadable row reads as "not
+/// inspected" rather than failing the whole report.
+pub(super) async fn tls_inspection(
+    pool: &SqlitePool,
+    job_id: &str,
+) -> crate::repository::RepositoryResult<Option<TlsInspection>> {
+    let row = sqlx::query("SELECT inspection FROM job_tls_inspections WHERE job_id = ?")
+        .bind(job_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.and_then(|row| {
+        serde_json::from_str(&row.get::<String, _>("inspection"))
+            .inspect_err(|e| {
+                tracing::warn!("Ignoring unreadable TLS inspection for job {}: {}", job_id, e)
+            })
+            .ok()
+    }))
+}
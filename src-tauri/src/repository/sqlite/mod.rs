@@ -7,7 +7,9 @@ mod page_queue_repository;
 mod page_repository;
 mod report_repository;
 mod results_repository;
+mod rule_pack_repository;
 mod settings_repository;
+mod snapshot_repository;
 mod template_repository;
 
 pub use ai_repository::AiRepository;
@@ -20,11 +22,13 @@ pub use page_repository::PageRepository;
 pub use report_repository::SqliteReportPatternRepository;
 pub use template_repository::ReportTemplateRepository;
 pub use results_repository::ResultsRepository;
+pub use rule_pack_repository::SqliteRulePackRepository;
 pub use settings_repository::SettingsRepository;
+pub use snapshot_repository::SnapshotRepository;
 
 use chrono::{DateTime, Utc};
 
-use crate::contexts::analysis::Depth;
+use crate::contexts::analysis::{CrawlMode, Depth};
 use crate::contexts::{IssueSeverity, JobStatus, LinkType};
 
 /// Decode an RFC-3339 timestamp column. Malformed values fall back to the
@@ -68,6 +72,34 @@ pub fn require_affected(
     Ok(())
 }
 
+/// Encode an optional value (a rule condition, a site check's
+/// aggregate) for its JSON column.
+pub fn encode_json_column<T: serde::Serialize>(
+    entity: &'static str,
+    value: Option<&T>,
+) -> crate::repository::RepositoryResult<Option<String>> {
+    value
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| crate::repository::RepositoryError::decode(entity, e.to_string()))
+}
+
+/// Decode an optional JSON column written by [`encode_json_column`].
+/// Unlike `extracted_data`, a malformed value is an error: dropping a
+/// rule's condition would silently turn it back into its single
+/// comparison.
+pub fn decode_json_column<T: serde::de::DeserializeOwned>(
+    entity: &'static str,
+    column: &str,
+    raw: Option<&str>,
+) -> crate::repository::RepositoryResult<Option<T>> {
+    raw.map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| {
+            crate::repository::RepositoryError::decode(entity, format!("invalid {column}: {e}"))
+        })
+}
+
 /// Decode the JSON `extracted_data` column. Malformed JSON falls back to an
 /// empty map so a single broken row doesn't fail the whole query.
 pub fn decode_extracted_data(
@@ -79,6 +111,28 @@ pub fn decode_extracted_data(
     })
 }
 
+/// Decode a JSON response-headers column. NULL (rows from before headers
+/// were captured) and malformed JSON both decode to no headers.
+pub fn decode_response_headers(raw: Option<&str>) -> crate::contexts::analysis::ResponseHeaders {
+    raw.map(|raw| {
+        serde_json::from_str(raw).unwrap_or_else(|e| {
+            tracing::warn!("decoder: invalid response headers JSON ({e}); defaulting to empty");
+            Default::default()
+        })
+    })
+    .unwrap_or_default()
+}
+
+/// Decode a JSON page-weight column. NULL (the job skipped the resource
+/// pass) and malformed JSON both decode to `None`.
+pub fn decode_page_weight(raw: Option<&str>) -> Option<crate::contexts::analysis::PageWeight> {
+    raw.and_then(|raw| {
+        serde_json::from_str(raw)
+            .inspect_err(|e| tracing::warn!("decoder: invalid page weight JSON ({e}); ignoring"))
+            .ok()
+    })
+}
+
 /// Project a sqlx anonymous lighthouse row into a [`LighthouseData`].
 /// Implemented as a macro because each `sqlx::query!` invocation produces
 /// a distinct anonymous row type, so a generic function can't be expressed
@@ -92,8 +146,10 @@ pub fn decode_extracted_data(
 /// each call site even though every Job decoder selects the same columns.
 /// Three decoders use this: `job_repository::get_by_id`,
 /// `job_repository::get_pending`, and `results_repository::get_job`.
+/// `$site_checks` are the job's stored site checks, which give the
+/// sitemap and robots.txt flags.
 macro_rules! job_from_row {
-    ($row:expr) => {{
+    ($row:expr, $site_checks:expr) => {{
         let row = $row;
         $crate::contexts::analysis::Job {
             id: $crate::contexts::analysis::JobId::from(row.id),
@@ -107,6 +163,10 @@ macro_rules! job_from_row {
                 row.include_subdomains,
                 row.lighthouse_analysis,
                 row.rate_limit_ms,
+                row.url_rules.as_deref(),
+                &row.crawl_mode,
+                row.crawl_profile.as_deref(),
+                row.resource_analysis,
             ),
             summary: super::job_repository::decode_job_summary(
                 row.total_pages,
@@ -118,8 +178,8 @@ macro_rules! job_from_row {
             ),
             progress: row.progress,
             error_message: row.error_message,
-            sitemap_found: row.sitemap_found,
-            robots_txt_found: row.robots_txt_found,
+            sitemap_found: super::job_repository::site_file_found($site_checks, "sitemap_xml"),
+            robots_txt_found: super::job_repository::site_file_found($site_checks, "robots_txt"),
         }
     }};
 }
@@ -148,11 +208,15 @@ macro_rules! page_from_row {
             robots_meta: row.robots_meta,
             word_count: row.word_count,
             load_time_ms: row.load_time_ms,
+            ttfb_ms: row.ttfb_ms,
+            download_ms: row.download_ms,
             response_size_bytes: row.response_size_bytes,
             has_viewport: row.has_viewport != 0,
             has_structured_data: row.has_structured_data != 0,
             crawled_at: super::parse_datetime(row.crawled_at.as_str()),
             extracted_data: $extracted_data,
+            response_headers: super::decode_response_headers(row.response_headers.as_deref()),
+            weight: super::decode_page_weight(row.page_weight.as_deref()),
         }
     }};
 }
@@ -191,6 +255,13 @@ pub fn map_job_status(s: &str) -> JobStatus {
     })
 }
 
+pub fn map_crawl_mode(s: &str) -> CrawlMode {
+    s.parse().unwrap_or_else(|e| {
+        tracing::warn!("decoder: unknown crawl mode '{s}' ({e}); defaulting to Spider");
+        CrawlMode::Spider
+    })
+}
+
 pub fn map_severity(s: &str) -> IssueSeverity {
     s.parse().unwrap_or_else(|e| {
         tracing::warn!("decoder: unknown issue severity '{s}' ({e}); defaulting to Info");
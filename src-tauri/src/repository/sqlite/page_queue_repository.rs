@@ -1,22 +1,53 @@
-use crate::contexts::{NewPageQueueItem, PageQueueItem, PageQueueStatus};
+use crate::contexts::analysis::{FetchTiming, ResponseHeaders};
+use crate::contexts::{DiscoveryFrontier, NewPageQueueItem, PageQueueItem, PageQueueStatus};
 use crate::repository::{
-    PageQueueRepository as PageQueueRepositoryTrait, RepositoryResult,
+    PageQueueRepository as PageQueueRepositoryTrait, RepositoryError, RepositoryResult,
 };
 use async_trait::async_trait;
 use chrono::Utc;
-use sqlx::SqlitePool;
-use sqlx::Row;
+use sqlx::{Row, SqliteConnection, SqlitePool};
 
 /// Column lists shared across INSERT and SELECT queries. Defined once
 /// to prevent drift when the schema changes.
 const INSERT_SQL: &str = r#"
     INSERT INTO page_queue (id, job_id, url, depth, status, created_at, updated_at,
-                            cached_html, http_status, cached_load_time_ms, final_url)
-    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                            cached_html, http_status, cached_load_time_ms, final_url,
+                            cached_headers, cached_ttfb_ms, cached_download_ms)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 "#;
 
 const SELECT_COLUMNS: &str =
-    "id, job_id, url, depth, status, retry_count, error_message, created_at, updated_at, cached_html, http_status, cached_load_time_ms, final_url";
+    "id, job_id, url, depth, status, retry_count, error_message, created_at, updated_at, cached_html, http_status, cached_load_time_ms, final_url, cached_headers, cached_ttfb_ms, cached_download_ms";
+
+/// Queue `item` as a pending page on `conn`, so callers can fill a
+/// job's queue inside their own transaction.
+pub(super) async fn insert_item(
+    conn: &mut SqliteConnection,
+    item: &NewPageQueueItem,
+) -> RepositoryResult<String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+
+    sqlx::query(INSERT_SQL)
+        .bind(&id)
+        .bind(&item.job_id)
+        .bind(&item.url)
+        .bind(item.depth.as_i64())
+        .bind(PageQueueStatus::Pending.as_str())
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .bind(&item.cached_html)
+        .bind(item.http_status.map(|s| s as i64))
+        .bind(item.cached_load_time_ms)
+        .bind(&item.final_url)
+        .bind(encode_headers(&item.cached_headers))
+        .bind(item.cached_timing.map(|t| t.ttfb_ms))
+        .bind(item.cached_timing.map(|t| t.download_ms))
+        .execute(conn)
+        .await?;
+
+    Ok(id)
+}
 
 pub struct PageQueueRepository {
     pool: SqlitePool,
@@ -57,26 +88,8 @@ impl PageQueueRepository {
 #[async_trait]
 impl PageQueueRepositoryTrait for PageQueueRepository {
     async fn insert(&self, item: &NewPageQueueItem) -> RepositoryResult<String> {
-        let id = uuid::Uuid::new_v4().to_string();
-        let now = Utc::now();
-        let status = "pending";
-
-        sqlx::query(INSERT_SQL)
-        .bind(&id)
-        .bind(&item.job_id)
-        .bind(&item.url)
-        .bind(item.depth.as_i64())
-        .bind(status)
-        .bind(now.to_rfc3339())
-        .bind(now.to_rfc3339())
-        .bind(&item.cached_html)
-        .bind(item.http_status.map(|s| s as i64))
-        .bind(item.cached_load_time_ms)
-        .bind(&item.final_url)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(id)
+        let mut conn = self.pool.acquire().await?;
+        insert_item(&mut conn, item).await
     }
 
     async fn insert_batch(&self, items: &[NewPageQueueItem]) -> RepositoryResult<()> {
@@ -85,28 +98,9 @@ impl PageQueueRepositoryTrait for PageQueueRepository {
         }
 
         let mut tx = self.pool.begin().await?;
-
         for item in items {
-            let id = uuid::Uuid::new_v4().to_string();
-            let now = Utc::now();
-            let status = "pending";
-
-            sqlx::query(INSERT_SQL)
-            .bind(&id)
-            .bind(&item.job_id)
-            .bind(&item.url)
-            .bind(item.depth.as_i64())
-            .bind(status)
-            .bind(now.to_rfc3339())
-            .bind(now.to_rfc3339())
-            .bind(&item.cached_html)
-            .bind(item.http_status.map(|s| s as i64))
-            .bind(item.cached_load_time_ms)
-            .bind(&item.final_url)
-            .execute(&mut *tx)
-            .await?;
+            insert_item(&mut tx, item).await?;
         }
-
         tx.commit().await?;
         Ok(())
     }
@@ -158,14 +152,16 @@ impl PageQueueRepositoryTrait for PageQueueRepository {
         let now = Utc::now();
         let status_str = status.as_str();
 
-        // Clear cached_html when completing to free disk space —
+        // Clear the cached response when completing to free disk space —
         // the analysis has already extracted everything it needs.
         let clear_cache = status == PageQueueStatus::Completed;
 
         sqlx::query(
             r#"
             UPDATE page_queue
-            SET status = ?, updated_at = ?, cached_html = CASE WHEN ? THEN NULL ELSE cached_html END
+            SET status = ?, updated_at = ?,
+                cached_html = CASE WHEN ?3 THEN NULL ELSE cached_html END,
+                cached_headers = CASE WHEN ?3 THEN NULL ELSE cached_headers END
             WHERE id = ?
             "#,
         )
@@ -288,6 +284,79 @@ impl PageQueueRepositoryTrait for PageQueueRepository {
 
         Ok(result.get::<i64, _>("count") == 0)
     }
+
+    async fn save_frontier(
+        &self,
+        job_id: &str,
+        frontier: &DiscoveryFrontier,
+    ) -> RepositoryResult<()> {
+        let to_visit = encode_url_list(&frontier.to_visit)?;
+        let visited = encode_url_list(&frontier.visited)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO discovery_frontier (job_id, to_visit, visited, discovery_complete, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(job_id) DO UPDATE SET
+                to_visit = excluded.to_visit,
+                visited = excluded.visited,
+                discovery_complete = excluded.discovery_complete,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(job_id)
+        .bind(to_visit)
+        .bind(visited)
+        .bind(frontier.discovery_complete)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn load_frontier(&self, job_id: &str) -> RepositoryResult<Option<DiscoveryFrontier>> {
+        let row = sqlx::query(
+            r#"
+            SELECT to_visit, visited, discovery_complete
+            FROM discovery_frontier
+            WHERE job_id = ?
+            "#,
+        )
+        .bind(job_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(DiscoveryFrontier {
+                to_visit: decode_url_list(&row.get::<String, _>("to_visit"))?,
+                visited: decode_url_list(&row.get::<String, _>("visited"))?,
+                discovery_complete: row.get("discovery_complete"),
+            })
+        })
+        .transpose()
+    }
+
+    async fn delete_frontier(&self, job_id: &str) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM discovery_frontier WHERE job_id = ?")
+            .bind(job_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Frontier URL lists are stored as JSON arrays — they are only ever
+/// read back whole, so a child table would buy nothing.
+fn encode_url_list(urls: &[String]) -> RepositoryResult<String> {
+    serde_json::to_string(urls)
+        .map_err(|e| RepositoryError::decode("discovery_frontier", e.to_string()))
+}
+
+fn decode_url_list(raw: &str) -> RepositoryResult<Vec<String>> {
+    serde_json::from_str(raw)
+        .map_err(|e| RepositoryError::decode("discovery_frontier", e.to_string()))
 }
 
 fn map_row_to_item(row: &sqlx::sqlite::SqliteRow) -> PageQueueItem {
@@ -334,5 +403,84 @@ fn map_row_to_item(row: &sqlx::sqlite::SqliteRow) -> PageQueueItem {
             .map(|s| s as u16),
         cached_load_time_ms: row.try_get("cached_load_time_ms").ok().flatten(),
         final_url: row.get("final_url"),
+        cached_headers: super::decode_response_headers(
+            row.try_get::<Option<String>, _>("cached_headers")
+                .ok()
+                .flatten()
+                .as_deref(),
+        ),
+        cached_timing: match (
+            row.try_get::<Option<f64>, _>("cached_ttfb_ms").ok().flatten(),
+            row.try_get::<Option<f64>, _>("cached_download_ms").ok().flatten(),
+        ) {
+            (Some(ttfb_ms), Some(download_ms)) => Some(FetchTiming { ttfb_ms, download_ms }),
+            _ => None,
+        },
+    }
+}
+
+/// Headers are stored as NULL when there are none, like the rest of the
+/// discovery cache.
+fn encode_headers(headers: &ResponseHeaders) -> Option<String> {
+    if headers.is_empty() {
+        None
+    } else {
+        serde_json::to_string(headers).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::contexts::analysis::{JobSettings, JobStatus};
+    use crate::contexts::DiscoveryFrontier;
+    use crate::repository::PageQueueRepository as _;
+    use crate::repository::sqlite_job_repo;
+    use crate::test_utils::fixtures;
+
+    #[tokio::test]
+    async fn test_frontier_round_trip_and_overwrite() {
+        let pool = fixtures::setup_test_db().await;
+        let job_id = sqlite_job_repo(pool.clone())
+            .create("https://example.com", &JobSettings::default())
+            .await
+            .unwrap();
+        let repo = super::PageQueueRepository::new(pool.clone());
+
+        assert!(repo.load_frontier(&job_id).await.unwrap().is_none());
+
+        let frontier = DiscoveryFrontier {
+            to_visit: vec!["https://example.com/b".into(), "https://example.com/c".into()],
+            visited: vec!["https://example.com/".into()],
+            discovery_complete: false,
+        };
+        repo.save_frontier(&job_id, &frontier).await.unwrap();
+        assert_eq!(repo.load_frontier(&job_id).await.unwrap(), Some(frontier));
+
+        // A second pause replaces the checkpoint rather than conflicting.
+        repo.save_frontier(&job_id, &DiscoveryFrontier::completed())
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.load_frontier(&job_id).await.unwrap(),
+            Some(DiscoveryFrontier::completed())
+        );
+
+        repo.delete_frontier(&job_id).await.unwrap();
+        assert!(repo.load_frontier(&job_id).await.unwrap().is_none());
+    }
+
+    /// Migration 0038 widens the `jobs.status` CHECK in place; pin that
+    /// the new value is accepted and the job's children survive.
+    #[tokio::test]
+    async fn test_paused_status_is_persisted() {
+        let pool = fixtures::setup_test_db().await;
+        let jobs = sqlite_job_repo(pool.clone());
+        let job_id = jobs
+            .create("https://example.com", &JobSettings::default())
+            .await
+            .unwrap();
+
+        jobs.update_status(&job_id, JobStatus::Paused).await.unwrap();
+        assert_eq!(jobs.get_by_id(&job_id).await.unwrap().status, JobStatus::Paused);
     }
 }
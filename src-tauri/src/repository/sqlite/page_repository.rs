@@ -1,10 +1,14 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 
-use crate::contexts::analysis::{LighthouseData, NewHeading, NewImage, Page, PageInfo};
+use crate::contexts::analysis::{
+    Heading, Image, LighthouseData, NewHeading, NewImage, Page, PageInfo,
+};
 use crate::repository::{PageRepository as PageRepositoryTrait, RepositoryError, RepositoryResult};
 use async_trait::async_trait;
-use super::decode_extracted_data;
+use super::{decode_extracted_data, require_affected};
 
 /// Use the page's existing id, or mint a fresh UUID if it's empty.
 /// Both `insert` and `insert_batch` need this logic.
@@ -21,8 +25,24 @@ fn page_id_or_new(page: &Page) -> String {
 /// whole insert — the map is `HashMap<String, serde_json::Value>` so
 /// this practically can't fail, but the safety net is preserved from
 /// the original code and now lives in one place.
-fn encode_extracted_data(page: &Page) -> String {
-    serde_json::to_string(&page.extracted_data).unwrap_or_else(|_| "{}".to_string())
+fn encode_extracted_data(extracted_data: &HashMap<String, serde_json::Value>) -> String {
+    serde_json::to_string(extracted_data).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Serialize a page's response headers to JSON, or NULL when none were
+/// captured.
+fn encode_response_headers(page: &Page) -> Option<String> {
+    if page.response_headers.is_empty() {
+        None
+    } else {
+        serde_json::to_string(&page.response_headers).ok()
+    }
+}
+
+/// Serialize a page's weight to JSON, or NULL when the job skipped the
+/// resource pass.
+fn encode_page_weight(page: &Page) -> Option<String> {
+    page.weight.as_ref().and_then(|w| serde_json::to_string(w).ok())
 }
 
 pub struct PageRepository {
@@ -40,7 +60,9 @@ impl PageRepositoryTrait for PageRepository {
     async fn insert(&self, page: &Page) -> RepositoryResult<String> {
         let id = page_id_or_new(page);
         let crawled_at_str = page.crawled_at.to_rfc3339();
-        let extracted_data_json = encode_extracted_data(page);
+        let extracted_data_json = encode_extracted_data(&page.extracted_data);
+        let response_headers_json = encode_response_headers(page);
+        let page_weight_json = encode_page_weight(page);
         let depth_raw = page.depth.as_i64();
 
         let row = sqlx::query!(
@@ -48,10 +70,11 @@ impl PageRepositoryTrait for PageRepository {
             INSERT INTO pages (
                 id, job_id, url, depth, status_code, content_type,
                 title, meta_description, canonical_url, robots_meta,
-                word_count, load_time_ms, response_size_bytes,
-                has_viewport, has_structured_data, crawled_at, extracted_data
+                word_count, load_time_ms, ttfb_ms, download_ms, response_size_bytes,
+                has_viewport, has_structured_data, crawled_at, extracted_data,
+                response_headers, page_weight
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(job_id, url) DO UPDATE SET
                 depth = excluded.depth,
                 status_code = excluded.status_code,
@@ -62,11 +85,15 @@ impl PageRepositoryTrait for PageRepository {
                 robots_meta = excluded.robots_meta,
                 word_count = excluded.word_count,
                 load_time_ms = excluded.load_time_ms,
+                ttfb_ms = excluded.ttfb_ms,
+                download_ms = excluded.download_ms,
                 response_size_bytes = excluded.response_size_bytes,
                 has_viewport = excluded.has_viewport,
                 has_structured_data = excluded.has_structured_data,
                 crawled_at = excluded.crawled_at,
-                extracted_data = excluded.extracted_data
+                extracted_data = excluded.extracted_data,
+                response_headers = excluded.response_headers,
+                page_weight = excluded.page_weight
             RETURNING id
             "#,
             id,
@@ -81,11 +108,15 @@ impl PageRepositoryTrait for PageRepository {
             page.robots_meta,
             page.word_count,
             page.load_time_ms,
+            page.ttfb_ms,
+            page.download_ms,
             page.response_size_bytes,
             page.has_viewport,
             page.has_structured_data,
             crawled_at_str,
-            extracted_data_json
+            extracted_data_json,
+            response_headers_json,
+            page_weight_json
         )
         .fetch_one(&self.pool)
         .await?;
@@ -107,14 +138,17 @@ impl PageRepositoryTrait for PageRepository {
                 INSERT INTO pages (
                     id, job_id, url, depth, status_code, content_type,
                     title, meta_description, canonical_url, robots_meta,
-                    word_count, load_time_ms, response_size_bytes,
-                    has_viewport, has_structured_data, crawled_at, extracted_data
+                    word_count, load_time_ms, ttfb_ms, download_ms, response_size_bytes,
+                    has_viewport, has_structured_data, crawled_at, extracted_data,
+                    response_headers, page_weight
                 ) "#,
             );
 
             qb.push_values(chunk, |mut b, page| {
                 let id = page_id_or_new(page);
-                let extracted_data_json = encode_extracted_data(page);
+                let extracted_data_json = encode_extracted_data(&page.extracted_data);
+                let response_headers_json = encode_response_headers(page);
+                let page_weight_json = encode_page_weight(page);
 
                 b.push_bind(id)
                     .push_bind(&page.job_id)
@@ -128,11 +162,15 @@ impl PageRepositoryTrait for PageRepository {
                     .push_bind(&page.robots_meta)
                     .push_bind(page.word_count)
                     .push_bind(page.load_time_ms)
+                    .push_bind(page.ttfb_ms)
+                    .push_bind(page.download_ms)
                     .push_bind(page.response_size_bytes)
                     .push_bind(page.has_viewport)
                     .push_bind(page.has_structured_data)
                     .push_bind(page.crawled_at.to_rfc3339())
-                    .push_bind(extracted_data_json);
+                    .push_bind(extracted_data_json)
+                    .push_bind(response_headers_json)
+                    .push_bind(page_weight_json);
             });
 
             qb.build().execute(&mut *tx).await?;
@@ -149,8 +187,9 @@ impl PageRepositoryTrait for PageRepository {
             SELECT 
                 id, job_id, url, depth, status_code, content_type,
                 title, meta_description, canonical_url, robots_meta,
-                word_count, load_time_ms, response_size_bytes,
-                has_viewport, has_structured_data, crawled_at, extracted_data
+                word_count, load_time_ms, ttfb_ms, download_ms, response_size_bytes,
+                has_viewport, has_structured_data, crawled_at, extracted_data,
+                response_headers, page_weight
             FROM pages
             WHERE job_id = ?
             ORDER BY depth ASC, url ASC
@@ -205,8 +244,9 @@ impl PageRepositoryTrait for PageRepository {
             SELECT 
                 id, job_id, url, depth, status_code, content_type,
                 title, meta_description, canonical_url, robots_meta,
-                word_count, load_time_ms, response_size_bytes,
-                has_viewport, has_structured_data, crawled_at, extracted_data
+                word_count, load_time_ms, ttfb_ms, download_ms, response_size_bytes,
+                has_viewport, has_structured_data, crawled_at, extracted_data,
+                response_headers, page_weight
             FROM pages
             WHERE id = ?
             "#,
@@ -223,6 +263,19 @@ impl PageRepositoryTrait for PageRepository {
         Ok(super::page_from_row!(row, extracted_data))
     }
 
+    async fn get_by_url(&self, job_id: &str, url: &str) -> RepositoryResult<Page> {
+        let page_id: Option<String> =
+            sqlx::query_scalar("SELECT id FROM pages WHERE job_id = ? AND url = ?")
+                .bind(job_id)
+                .bind(url)
+                .fetch_optional(&self.pool)
+                .await?;
+        match page_id {
+            Some(page_id) => self.get_by_id(&page_id).await,
+            None => Err(RepositoryError::not_found("page", url)),
+        }
+    }
+
     async fn replace_headings(
         &self,
         page_id: &str,
@@ -353,5 +406,101 @@ impl PageRepositoryTrait for PageRepository {
             .map(|row| super::lighthouse_data_from_row!(row))
             .collect())
     }
+
+    async fn get_headings_by_job_id(&self, job_id: &str) -> RepositoryResult<Vec<Heading>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                ph.id as "id!", ph.page_id, ph.level, ph.text, ph.position
+            FROM page_headings ph
+            JOIN pages p ON p.id = ph.page_id
+            WHERE p.job_id = ?
+            ORDER BY ph.page_id, ph.position
+            "#,
+            job_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(RepositoryError::from)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Heading {
+                id: row.id,
+                page_id: row.page_id,
+                level: row.level,
+                text: row.text,
+                position: row.position,
+            })
+            .collect())
+    }
+
+    async fn get_headings(&self, page_id: &str) -> RepositoryResult<Vec<Heading>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id as "id!", page_id, level, text, position
+            FROM page_headings
+            WHERE page_id = ?
+            ORDER BY position
+            "#,
+            page_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Heading {
+                id: row.id,
+                page_id: row.page_id,
+                level: row.level,
+                text: row.text,
+                position: row.position,
+            })
+            .collect())
+    }
+
+    async fn get_images(&self, page_id: &str) -> RepositoryResult<Vec<Image>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                id as "id!", page_id, src, alt, width, height, loading, is_decorative
+            FROM page_images
+            WHERE page_id = ?
+            ORDER BY id
+            "#,
+            page_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Image {
+                id: row.id,
+                page_id: row.page_id,
+                src: row.src,
+                alt: row.alt,
+                width: row.width,
+                height: row.height,
+                loading: row.loading,
+                is_decorative: row.is_decorative != 0,
+            })
+            .collect())
+    }
 }
 
+/// Replace a page's `extracted_data`, after re-running the extractors
+/// on its HTML.
+pub(super) async fn update_extracted_data(
+    conn: &mut SqliteConnection,
+    page_id: &str,
+    extracted_data: &HashMap<String, serde_json::Value>,
+) -> RepositoryResult<()> {
+    let result = sqlx::query("UPDATE pages SET extracted_data = ? WHERE id = ?")
+        .bind(encode_extracted_data(extracted_data))
+        .bind(page_id)
+        .execute(conn)
+        .await?;
+    require_affected(result.rows_affected(), "page", page_id)
+}
@@ -1,6 +1,5 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
-use uuid::Uuid;
+use sqlx::{SqliteConnection, SqlitePool};
 
 use crate::contexts::extension::Operator;
 use crate::contexts::report::{
@@ -23,7 +22,7 @@ impl SqliteReportPatternRepository {
 impl ReportPatternRepository for SqliteReportPatternRepository {
     async fn list_patterns(&self) -> RepositoryResult<Vec<ReportPattern>> {
         let rows = sqlx::query(
-            "SELECT id, name, description, category, severity, field, operator, threshold,
+            "SELECT id, name, description, category, severity, field, operator, threshold, condition,
                     min_prevalence, business_impact, fix_effort, recommendation, is_builtin, enabled
              FROM report_patterns
              ORDER BY is_builtin DESC, name ASC",
@@ -36,7 +35,7 @@ impl ReportPatternRepository for SqliteReportPatternRepository {
 
     async fn list_enabled_patterns(&self) -> RepositoryResult<Vec<ReportPattern>> {
         let rows = sqlx::query(
-            "SELECT id, name, description, category, severity, field, operator, threshold,
+            "SELECT id, name, description, category, severity, field, operator, threshold, condition,
                     min_prevalence, business_impact, fix_effort, recommendation, is_builtin, enabled
              FROM report_patterns
              WHERE enabled = 1
@@ -50,7 +49,7 @@ impl ReportPatternRepository for SqliteReportPatternRepository {
 
     async fn get_pattern(&self, id: &str) -> RepositoryResult<ReportPattern> {
         let row = sqlx::query(
-            "SELECT id, name, description, category, severity, field, operator, threshold,
+            "SELECT id, name, description, category, severity, field, operator, threshold, condition,
                     min_prevalence, business_impact, fix_effort, recommendation, is_builtin, enabled
              FROM report_patterns WHERE id = ?",
         )
@@ -65,34 +64,14 @@ impl ReportPatternRepository for SqliteReportPatternRepository {
         row_to_pattern(&row)
     }
 
-    async fn create_pattern(
+    async fn create_pattern_with_id(
         &self,
+        id: &str,
         params: &ReportPatternParams,
     ) -> RepositoryResult<ReportPattern> {
-        let id = Uuid::new_v4().to_string();
-        sqlx::query(
-            "INSERT INTO report_patterns
-                (id, name, description, category, severity, field, operator, threshold,
-                 min_prevalence, business_impact, fix_effort, recommendation, is_builtin, enabled)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?)",
-        )
-        .bind(&id)
-        .bind(&params.name)
-        .bind(&params.description)
-        .bind(params.category.as_str())
-        .bind(params.severity.as_str())
-        .bind(&params.field)
-        .bind(params.operator.to_string())
-        .bind(&params.threshold)
-        .bind(params.min_prevalence)
-        .bind(params.business_impact.as_str())
-        .bind(params.fix_effort.as_str())
-        .bind(&params.recommendation)
-        .bind(params.enabled)
-        .execute(&self.pool)
-        .await?;
-
-        self.get_pattern(&id).await
+        let mut conn = self.pool.acquire().await?;
+        insert_pattern(&mut conn, id, params).await?;
+        self.get_pattern(id).await
     }
 
     async fn update_pattern(
@@ -100,30 +79,8 @@ impl ReportPatternRepository for SqliteReportPatternRepository {
         id: &str,
         params: &ReportPatternParams,
     ) -> RepositoryResult<ReportPattern> {
-        sqlx::query(
-            "UPDATE report_patterns SET
-                name = ?, description = ?, category = ?, severity = ?, field = ?,
-                operator = ?, threshold = ?, min_prevalence = ?, business_impact = ?,
-                fix_effort = ?, recommendation = ?, enabled = ?,
-                updated_at = datetime('now')
-             WHERE id = ? AND is_builtin = 0",
-        )
-        .bind(&params.name)
-        .bind(&params.description)
-        .bind(params.category.as_str())
-        .bind(params.severity.as_str())
-        .bind(&params.field)
-        .bind(params.operator.to_string())
-        .bind(&params.threshold)
-        .bind(params.min_prevalence)
-        .bind(params.business_impact.as_str())
-        .bind(params.fix_effort.as_str())
-        .bind(&params.recommendation)
-        .bind(params.enabled)
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
-
+        let mut conn = self.pool.acquire().await?;
+        update_pattern(&mut conn, id, params).await?;
         self.get_pattern(id).await
     }
 
@@ -147,6 +104,76 @@ impl ReportPatternRepository for SqliteReportPatternRepository {
     }
 }
 
+/// Insert a user pattern on `conn`, which may be a rule pack import's
+/// transaction.
+pub(super) async fn insert_pattern(
+    conn: &mut SqliteConnection,
+    id: &str,
+    params: &ReportPatternParams,
+) -> RepositoryResult<()> {
+    let condition = super::encode_json_column("report_pattern", params.condition.as_ref())?;
+    sqlx::query(
+        "INSERT INTO report_patterns
+            (id, name, description, category, severity, field, operator, threshold, condition,
+             min_prevalence, business_impact, fix_effort, recommendation, is_builtin, enabled)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?)",
+    )
+    .bind(id)
+    .bind(&params.name)
+    .bind(&params.description)
+    .bind(params.category.as_str())
+    .bind(params.severity.as_str())
+    .bind(&params.field)
+    .bind(params.operator.to_string())
+    .bind(&params.threshold)
+    .bind(&condition)
+    .bind(params.min_prevalence)
+    .bind(params.business_impact.as_str())
+    .bind(params.fix_effort.as_str())
+    .bind(&params.recommendation)
+    .bind(params.enabled)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Update a user pattern. Built-in patterns can't be edited, so one
+/// reads as not found.
+pub(super) async fn update_pattern(
+    conn: &mut SqliteConnection,
+    id: &str,
+    params: &ReportPatternParams,
+) -> RepositoryResult<()> {
+    let condition = super::encode_json_column("report_pattern", params.condition.as_ref())?;
+    let result = sqlx::query(
+        "UPDATE report_patterns SET
+            name = ?, description = ?, category = ?, severity = ?, field = ?,
+            operator = ?, threshold = ?, condition = ?, min_prevalence = ?, business_impact = ?,
+            fix_effort = ?, recommendation = ?, enabled = ?,
+            updated_at = datetime('now')
+         WHERE id = ? AND is_builtin = 0",
+    )
+    .bind(&params.name)
+    .bind(&params.description)
+    .bind(params.category.as_str())
+    .bind(params.severity.as_str())
+    .bind(&params.field)
+    .bind(params.operator.to_string())
+    .bind(&params.threshold)
+    .bind(&condition)
+    .bind(params.min_prevalence)
+    .bind(params.business_impact.as_str())
+    .bind(params.fix_effort.as_str())
+    .bind(&params.recommendation)
+    .bind(params.enabled)
+    .bind(id)
+    .execute(&mut *conn)
+    .await?;
+
+    super::require_affected(result.rows_affected(), "report_pattern", id)
+}
+
 fn row_to_pattern(row: &sqlx::sqlite::SqliteRow) -> RepositoryResult<ReportPattern> {
     use sqlx::Row;
 
@@ -157,6 +184,7 @@ fn row_to_pattern(row: &sqlx::sqlite::SqliteRow) -> RepositoryResult<ReportPatte
     let fix_effort: String = row.try_get("fix_effort")?;
     let is_builtin: i64 = row.try_get("is_builtin")?;
     let enabled: i64 = row.try_get("enabled")?;
+    let condition: Option<String> = row.try_get("condition")?;
 
     // Generic over the typed Parse*Error structs in pattern.rs / extension.
     // Each carries Display via thiserror, so a single closure handles them all
@@ -181,6 +209,7 @@ fn row_to_pattern(row: &sqlx::sqlite::SqliteRow) -> RepositoryResult<ReportPatte
             .parse::<Operator>()
             .map_err(|e| decode_err("operator", &e))?,
         threshold: row.try_get("threshold")?,
+        condition: super::decode_json_column("report_pattern", "condition", condition.as_deref())?,
         min_prevalence: row.try_get("min_prevalence")?,
         business_impact: business_impact
             .parse::<BusinessImpact>()
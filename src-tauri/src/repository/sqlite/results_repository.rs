@@ -5,7 +5,7 @@ use crate::contexts::{
     ai::AiInsight,
     analysis::{
         CompleteJobResult, Heading, Image, Issue, Job,
-        LighthouseData, Link, Page,
+        LighthouseData, Link, Page, SiteCheckResult,
     },
 };
 
@@ -64,6 +64,16 @@ impl ResultsRepositoryTrait for ResultsRepository {
         // 8. Get AI insights (optional)
         let ai_insights = self.get_ai_insights(job_id).await.ok();
 
+        // 9. Get the TLS inspection (optional)
+        let tls = super::job_repository::tls_inspection(&self.pool, job_id)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to load TLS inspection for {}: {}", job_id, e))
+            .ok()
+            .flatten();
+
+        // 10. Get the site-level file checks
+        let site_checks = super::job_repository::site_checks(&self.pool, job_id).await?;
+
         let total_time = query_start.elapsed();
         tracing::info!(
             "Loaded complete result for job {} with {} pages, {} issues, {} links in {:?}",
@@ -83,6 +93,8 @@ impl ResultsRepositoryTrait for ResultsRepository {
             headings,
             images,
             ai_insights,
+            tls,
+            site_checks,
             extracted_data: std::collections::HashMap::new(),
         })
     }
@@ -96,7 +108,8 @@ impl ResultsRepositoryTrait for ResultsRepository {
                 rate_limit_ms, user_agent, lighthouse_analysis,
                 total_pages, pages_crawled, total_issues, 
                 critical_issues, warning_issues, info_issues,
-                progress, error_message, sitemap_found, robots_txt_found
+                progress, error_message,
+                url_rules, crawl_mode, crawl_profile, resource_analysis
             FROM jobs
             WHERE id = ?
             "#,
@@ -109,7 +122,8 @@ impl ResultsRepositoryTrait for ResultsRepository {
             other => RepositoryError::from(other),
         })?;
 
-        Ok(super::job_from_row!(row))
+        let site_checks = super::job_repository::site_checks(&self.pool, job_id).await?;
+        Ok(super::job_from_row!(row, &site_checks))
     }
 
     async fn get_pages(&self, job_id: &str) -> RepositoryResult<Vec<Page>> {
@@ -118,8 +132,9 @@ impl ResultsRepositoryTrait for ResultsRepository {
             SELECT 
                 id, job_id, url, depth, status_code, content_type,
                 title, meta_description, canonical_url, robots_meta,
-                word_count, load_time_ms, response_size_bytes,
-                has_viewport, has_structured_data, crawled_at, extracted_data
+                word_count, load_time_ms, ttfb_ms, download_ms, response_size_bytes,
+                has_viewport, has_structured_data, crawled_at, extracted_data,
+                response_headers, page_weight
             FROM pages
             WHERE job_id = ?
             ORDER BY depth ASC, url ASC
@@ -0,0 +1,70 @@
+//! SQLite implementation of `RulePackRepository`, running an import's
+//! writes through the other repositories' queries in one transaction.
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+
+use crate::contexts::extension::{CustomCheckParams, CustomExtractorParams};
+use crate::contexts::report::ReportPatternParams;
+use crate::contexts::rule_pack::{PlannedWrite, RulePackWrite};
+use crate::repository::{RepositoryResult, RulePackRepository};
+
+use super::{extension_repository, report_repository, template_repository};
+
+pub struct SqliteRulePackRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRulePackRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RulePackRepository for SqliteRulePackRepository {
+    async fn import(&self, writes: &[RulePackWrite]) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+        for write in writes {
+            let (planned, update) = match write {
+                RulePackWrite::Create(planned) => (planned, false),
+                RulePackWrite::Update(planned) => (planned, true),
+            };
+            match planned {
+                PlannedWrite::Extractor(e) => {
+                    let params = CustomExtractorParams::from(e);
+                    if update {
+                        extension_repository::update_extractor(&mut tx, &e.id, &params).await?;
+                    } else {
+                        extension_repository::insert_extractor(&mut tx, &e.id, &params).await?;
+                    }
+                }
+                PlannedWrite::Check(c) => {
+                    let params = CustomCheckParams::from(c);
+                    if update {
+                        extension_repository::update_check(&mut tx, &c.id, &params).await?;
+                    } else {
+                        extension_repository::insert_check(&mut tx, &c.id, &params).await?;
+                    }
+                }
+                PlannedWrite::Pattern(p) => {
+                    let params = ReportPatternParams::from(p);
+                    if update {
+                        report_repository::update_pattern(&mut tx, &p.id, &params).await?;
+                    } else {
+                        report_repository::insert_pattern(&mut tx, &p.id, &params).await?;
+                    }
+                }
+                PlannedWrite::Template(t) => {
+                    if update {
+                        template_repository::update_template(&mut tx, t).await?;
+                    } else {
+                        template_repository::insert_template(&mut tx, t).await?;
+                    }
+                }
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
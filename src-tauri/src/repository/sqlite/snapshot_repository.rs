@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use crate::contexts::snapshot::{NewPageSnapshot, PageSnapshot, SnapshotStorage};
+use crate::repository::{
+    RepositoryError, RepositoryResult, SnapshotRepository as SnapshotRepositoryTrait,
+};
+
+/// zstd level for page source: HTML compresses well even at the fast
+/// levels, and pages are compressed while analysis is running.
+const COMPRESSION_LEVEL: i32 = 3;
+
+const SELECT_SNAPSHOT: &str = r#"
+    SELECT s.job_id, s.url, s.html_hash, s.headers, s.created_at, b.content
+    FROM page_snapshots s
+    JOIN html_blobs b ON b.hash = s.html_hash
+"#;
+
+pub struct SnapshotRepository {
+    pool: SqlitePool,
+}
+
+impl SnapshotRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+fn snapshot_from_row(row: &SqliteRow) -> RepositoryResult<PageSnapshot> {
+    let content: Vec<u8> = row.get("content");
+    let html = zstd::decode_all(content.as_slice())
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| String::from_utf8(bytes).map_err(|e| e.to_string()))
+        .map_err(|e| RepositoryError::decode("html_blobs.content", e))?;
+    Ok(PageSnapshot {
+        job_id: row.get("job_id"),
+        url: row.get("url"),
+        html,
+        headers: super::decode_response_headers(Some(row.get::<String, _>("headers").as_str())),
+        hash: row.get("html_hash"),
+        captured_at: row.get("created_at"),
+    })
+}
+
+/// Store the source under `hash` unless it already is, then point the
+/// page's snapshot at it. Identical bodies share one blob. The blob is
+/// written first, so a transaction this opens takes the write lock.
+async fn insert_snapshot(
+    conn: &mut SqliteConnection,
+    snapshot: &NewPageSnapshot<'_>,
+    hash: &str,
+    content: &[u8],
+    headers: &str,
+) -> RepositoryResult<()> {
+    sqlx::query(
+        "INSERT INTO html_blobs (hash, content, size, stored_size) VALUES (?, ?, ?, ?)
+         ON CONFLICT(hash) DO NOTHING",
+    )
+    .bind(hash)
+    .bind(content)
+    .bind(snapshot.html.len() as i64)
+    .bind(content.len() as i64)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO page_snapshots (page_id, job_id, url, html_hash, headers, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(page_id) DO UPDATE SET
+            html_hash = excluded.html_hash,
+            headers = excluded.headers,
+            created_at = excluded.created_at
+        "#,
+    )
+    .bind(snapshot.page_id)
+    .bind(snapshot.job_id)
+    .bind(snapshot.url)
+    .bind(hash)
+    .bind(headers)
+    .bind(Utc::now().to_rfc3339())
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Delete the sources no snapshot uses any more. Snapshots go with their
+/// job, but `html_blobs` has no owner to cascade from.
+pub(super) async fn delete_unused_blobs(conn: &mut SqliteConnection) -> RepositoryResult<()> {
+    sqlx::query(
+        "DELETE FROM html_blobs
+         WHERE NOT EXISTS (SELECT 1 FROM page_snapshots WHERE html_hash = html_blobs.hash)",
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl SnapshotRepositoryTrait for SnapshotRepository {
+    async fn save(&self, snapshot: &NewPageSnapshot<'_>) -> RepositoryResult<()> {
+        let hash = hex::encode(Sha256::digest(snapshot.html.as_bytes()));
+        let headers = serde_json::to_string(snapshot.headers)
+            .map_err(|e| RepositoryError::decode("page_snapshots.headers", e.to_string()))?;
+
+        let content = zstd::bulk::compress(snapshot.html.as_bytes(), COMPRESSION_LEVEL)
+            .map_err(|e| RepositoryError::decode("html_blobs.content", e.to_string()))?;
+
+        // The blob insert is the transaction's first statement, so it
+        // holds the write lock from the start and `prune` can't delete the
+        // blob before the snapshot that uses it is stored.
+        let mut tx = self.pool.begin().await?;
+        insert_snapshot(&mut tx, snapshot, &hash, &content, &headers).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get(&self, job_id: &str, url: &str) -> RepositoryResult<PageSnapshot> {
+        let row = sqlx::query(&format!("{SELECT_SNAPSHOT} WHERE s.job_id = ? AND s.url = ?"))
+            .bind(job_id)
+            .bind(url)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| RepositoryError::not_found("page snapshot", url))?;
+        snapshot_from_row(&row)
+    }
+
+    async fn get_by_page_id(&self, page_id: &str) -> RepositoryResult<Option<PageSnapshot>> {
+        let row = sqlx::query(&format!("{SELECT_SNAPSHOT} WHERE s.page_id = ?"))
+            .bind(page_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(snapshot_from_row).transpose()
+    }
+
+    async fn get_previous(
+        &self,
+        job_id: &str,
+        url: &str,
+    ) -> RepositoryResult<Option<PageSnapshot>> {
+        let row = sqlx::query(&format!(
+            r#"{SELECT_SNAPSHOT}
+            JOIN jobs j ON j.id = s.job_id
+            WHERE s.url = ?2 AND s.job_id != ?1
+              AND j.created_at < (SELECT created_at FROM jobs WHERE id = ?1)
+            ORDER BY j.created_at DESC
+            LIMIT 1"#
+        ))
+        .bind(job_id)
+        .bind(url)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(snapshot_from_row).transpose()
+    }
+
+    async fn prune(&self, older_than: Option<DateTime<Utc>>) -> RepositoryResult<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let removed = match older_than {
+            Some(cutoff) => {
+                sqlx::query("DELETE FROM page_snapshots WHERE created_at < ?")
+                    .bind(cutoff.to_rfc3339())
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected()
+            }
+            None => 0,
+        };
+        delete_unused_blobs(&mut tx).await?;
+
+        tx.commit().await?;
+        Ok(removed)
+    }
+
+    async fn storage(&self) -> RepositoryResult<SnapshotStorage> {
+        let snapshots = sqlx::query(
+            r#"
+            SELECT COUNT(*) AS snapshots, COALESCE(SUM(b.size), 0) AS html_bytes
+            FROM page_snapshots s
+            JOIN html_blobs b ON b.hash = s.html_hash
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        let blobs = sqlx::query(
+            "SELECT COUNT(*) AS blobs, COALESCE(SUM(stored_size), 0) AS stored_bytes FROM html_blobs",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(SnapshotStorage {
+            snapshots: snapshots.get("snapshots"),
+            distinct_sources: blobs.get("blobs"),
+            html_bytes: snapshots.get("html_bytes"),
+            stored_bytes: blobs.get("stored_bytes"),
+        })
+    }
+}
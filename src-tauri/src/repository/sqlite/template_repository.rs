@@ -1,7 +1,7 @@
 //! SQLite implementation of `ReportTemplateRepository`.
 
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 
 use crate::contexts::report::{ReportTemplate, TemplateSection};
 use crate::repository::{
@@ -118,47 +118,13 @@ impl ReportTemplateRepositoryTrait for ReportTemplateRepository {
     }
 
     async fn create_template(&self, template: &ReportTemplate) -> RepositoryResult<()> {
-        let json = serde_json::to_string(&template.sections)
-            .map_err(|e| RepositoryError::decode("report_template", e.to_string()))?;
-
-        let tags_json = serde_json::to_string(&template.selected_tags)
-            .map_err(|e| RepositoryError::decode("report_template", e.to_string()))?;
-
-        sqlx::query(
-            "INSERT INTO report_templates (id, name, is_builtin, sections_json, selected_tags_json, is_active)
-             VALUES (?, ?, ?, ?, ?, 0)",
-        )
-        .bind(&template.id)
-        .bind(&template.name)
-        .bind(i64::from(template.is_builtin))
-        .bind(&json)
-        .bind(&tags_json)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(())
+        let mut conn = self.pool.acquire().await?;
+        insert_template(&mut conn, template).await
     }
 
     async fn update_template(&self, template: &ReportTemplate) -> RepositoryResult<()> {
-        let json = serde_json::to_string(&template.sections)
-            .map_err(|e| RepositoryError::decode("report_template", e.to_string()))?;
-
-        let tags_json = serde_json::to_string(&template.selected_tags)
-            .map_err(|e| RepositoryError::decode("report_template", e.to_string()))?;
-
-        let result = sqlx::query(
-            "UPDATE report_templates
-             SET name = ?, sections_json = ?, selected_tags_json = ?, updated_at = datetime('now')
-             WHERE id = ?",
-        )
-        .bind(&template.name)
-        .bind(&json)
-        .bind(&tags_json)
-        .bind(&template.id)
-        .execute(&self.pool)
-        .await?;
-
-        super::require_affected(result.rows_affected(), "report_template", &template.id)
+        let mut conn = self.pool.acquire().await?;
+        update_template(&mut conn, template).await
     }
 
     async fn set_active_template(&self, id: &str) -> RepositoryResult<()> {
@@ -187,6 +153,58 @@ impl ReportTemplateRepositoryTrait for ReportTemplateRepository {
     }
 }
 
+// ── Writes, also run by rule pack imports inside their transaction ──
+
+pub(super) async fn insert_template(
+    conn: &mut SqliteConnection,
+    template: &ReportTemplate,
+) -> RepositoryResult<()> {
+    let json = serde_json::to_string(&template.sections)
+        .map_err(|e| RepositoryError::decode("report_template", e.to_string()))?;
+
+    let tags_json = serde_json::to_string(&template.selected_tags)
+        .map_err(|e| RepositoryError::decode("report_template", e.to_string()))?;
+
+    sqlx::query(
+        "INSERT INTO report_templates (id, name, is_builtin, sections_json, selected_tags_json, is_active)
+         VALUES (?, ?, ?, ?, ?, 0)",
+    )
+    .bind(&template.id)
+    .bind(&template.name)
+    .bind(i64::from(template.is_builtin))
+    .bind(&json)
+    .bind(&tags_json)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn update_template(
+    conn: &mut SqliteConnection,
+    template: &ReportTemplate,
+) -> RepositoryResult<()> {
+    let json = serde_json::to_string(&template.sections)
+        .map_err(|e| RepositoryError::decode("report_template", e.to_string()))?;
+
+    let tags_json = serde_json::to_string(&template.selected_tags)
+        .map_err(|e| RepositoryError::decode("report_template", e.to_string()))?;
+
+    let result = sqlx::query(
+        "UPDATE report_templates
+         SET name = ?, sections_json = ?, selected_tags_json = ?, updated_at = datetime('now')
+         WHERE id = ?",
+    )
+    .bind(&template.name)
+    .bind(&json)
+    .bind(&tags_json)
+    .bind(&template.id)
+    .execute(&mut *conn)
+    .await?;
+
+    super::require_affected(result.rows_affected(), "report_template", &template.id)
+}
+
 // ── Row type ─────────────────────────────────────────────────────────────────
 
 #[derive(sqlx::FromRow)]
@@ -0,0 +1,114 @@
+//! Text generation backends for AI insights and the AI sections of
+//! reports. Every backend is sent the same prompt, assembled by
+//! [`build_prompt_from_blocks`], so switching backends only changes which
+//! model answers it.
+
+mod ollama;
+mod openai;
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use crate::repository::{AiRepository, SettingsRepository};
+use crate::service::gemini::GeminiRequest;
+use crate::service::prompt::{build_prompt_from_blocks, load_persona, load_prompt_blocks};
+use crate::service::spider::SpiderAgent;
+
+pub use ollama::{OllamaBackend, OllamaSettings};
+pub use openai::{OpenAiCompatibleBackend, OpenAiCompatibleSettings};
+
+/// Prefix of the error raised when the selected backend is missing its
+/// endpoint or model, so the frontend can send the user to Settings → AI.
+pub const NOT_CONFIGURED: &str = "AI_BACKEND_NOT_CONFIGURED";
+
+/// A model that turns a prompt into text.
+#[async_trait]
+pub trait AiBackend: Send + Sync {
+    /// Shown in logs and errors, e.g. `"Ollama"`.
+    fn name(&self) -> &'static str;
+
+    /// Whether the backend has what it needs to answer, such as an API
+    /// key. Backends that check their settings when they're built are
+    /// always configured.
+    fn is_configured(&self) -> bool {
+        true
+    }
+
+    /// Send `prompt` to the model and return its answer.
+    async fn generate(&self, prompt: &str) -> Result<String>;
+
+    /// Like [`generate`](Self::generate), for one section of a longer
+    /// document: the answer is cut off after about `max_tokens` tokens.
+    /// A backend whose API can't cap its answer may ignore the limit,
+    /// which is what the default does.
+    async fn generate_section(&self, prompt: &str, max_tokens: usize) -> Result<String> {
+        let _ = max_tokens;
+        self.generate(prompt).await
+    }
+}
+
+/// AI insights for `request` from `backend`, written from the persona and
+/// prompt blocks in Settings → AI. Answers are cached per job and
+/// backend, so asking the same backend again returns the cached answer
+/// without another request.
+pub async fn generate_analysis(
+    ai_repo: Arc<dyn AiRepository>,
+    settings_repo: Arc<dyn SettingsRepository>,
+    request: &GeminiRequest,
+    backend: &dyn AiBackend,
+) -> Result<String> {
+    if let Ok(Some(cached_insights)) =
+        ai_repo.get_ai_insights_from(&request.analysis_id, backend.name()).await
+    {
+        tracing::info!(
+            "Using cached AI insights for analysis {}",
+            request.analysis_id
+        );
+        return Ok(cached_insights);
+    }
+
+    let persona = load_persona(settings_repo.as_ref()).await?;
+    let blocks = load_prompt_blocks(settings_repo.as_ref()).await?;
+    let prompt = build_prompt_from_blocks(&persona, &blocks, request);
+
+    let text = backend.generate(&prompt).await?;
+
+    if let Err(e) = ai_repo.save_ai_insights(&request.analysis_id, backend.name(), &text).await {
+        tracing::error!("Failed to save AI insights to cache: {}", e);
+    }
+
+    Ok(text)
+}
+
+/// `path` under the API root `base_url`, which must be an http(s) URL.
+fn endpoint(backend: &str, base_url: &str, path: &str) -> Result<String> {
+    let base = base_url.trim().trim_end_matches('/');
+    match url::Url::parse(base) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(format!("{base}{path}")),
+        _ => anyhow::bail!("{NOT_CONFIGURED}: '{base_url}' isn't a valid {backend} URL"),
+    }
+}
+
+/// POST `payload` and parse the answer as JSON, turning an error status
+/// into an error that names the backend.
+pub(crate) async fn post(
+    spider: &dyn SpiderAgent,
+    backend: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    payload: &serde_json::Value,
+) -> Result<serde_json::Value> {
+    let response = spider
+        .post_json_with_headers(url, headers, payload)
+        .await
+        .with_context(|| format!("Failed to send request to {backend}"))?;
+
+    if !(200..300).contains(&response.status) {
+        anyhow::bail!("{backend} API error {}: {}", response.status, response.body);
+    }
+
+    serde_json::from_str(&response.body)
+        .with_context(|| format!("Failed to parse {backend} response"))
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use specta::Type;
+
+use super::{endpoint, post, AiBackend, NOT_CONFIGURED};
+use crate::service::spider::SpiderAgent;
+
+const NAME: &str = "Ollama";
+
+/// Where to reach an Ollama server and which of its models to use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct OllamaSettings {
+    pub base_url: String,
+    /// A model the server has pulled, e.g. `llama3.1:8b`.
+    pub model: String,
+}
+
+impl Default for OllamaSettings {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:11434".to_string(),
+            model: String::new(),
+        }
+    }
+}
+
+pub struct OllamaBackend {
+    spider: Arc<dyn SpiderAgent>,
+    url: String,
+    model: String,
+}
+
+impl OllamaBackend {
+    pub fn new(spider: Arc<dyn SpiderAgent>, settings: &OllamaSettings) -> Result<Self> {
+        let url = endpoint(NAME, &settings.base_url, "/api/generate")?;
+        let model = settings.model.trim();
+        if model.is_empty() {
+            anyhow::bail!("{NOT_CONFIGURED}: choose a model for the {NAME} backend");
+        }
+        Ok(Self {
+            spider,
+            url,
+            model: model.to_string(),
+        })
+    }
+
+    /// Ollama takes the token limit as the `num_predict` option.
+    async fn complete(&self, prompt: &str, max_tokens: Option<usize>) -> Result<String> {
+        // Without `stream: false` Ollama answers with one JSON object per
+        // generated token.
+        let mut body = json!({ "model": self.model, "prompt": prompt, "stream": false });
+        if let Some(max_tokens) = max_tokens {
+            body["options"] = json!({ "num_predict": max_tokens });
+        }
+
+        let response = post(self.spider.as_ref(), NAME, &self.url, &[], &body).await?;
+        response["response"]
+            .as_str()
+            .map(str::to_string)
+            .with_context(|| format!("Failed to extract text from {NAME} response"))
+    }
+}
+
+#[async_trait]
+impl AiBackend for OllamaBackend {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        self.complete(prompt, None).await
+    }
+
+    async fn generate_section(&self, prompt: &str, max_tokens: usize) -> Result<String> {
+        self.complete(prompt, Some(max_tokens)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::spider::{ClientType, Spider};
+
+    fn settings(base_url: &str) -> OllamaSettings {
+        OllamaSettings {
+            base_url: base_url.to_string(),
+            model: "llama3.1:8b".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn asks_for_a_single_non_streamed_answer() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::Json(json!({
+                "model": "llama3.1:8b",
+                "prompt": "Audit this site",
+                "stream": false,
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({ "model": "llama3.1:8b", "response": "Fix titles", "done": true })
+                    .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let backend = OllamaBackend::new(spider, &settings(&server.url())).unwrap();
+
+        assert_eq!(
+            backend.generate("Audit this site").await.unwrap(),
+            "Fix titles"
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn caps_a_section_at_its_token_budget() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "prompt": "Summarize",
+                "options": { "num_predict": 200 },
+            })))
+            .with_status(200)
+            .with_body(json!({ "response": "Short", "done": true }).to_string())
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let backend = OllamaBackend::new(spider, &settings(&server.url())).unwrap();
+
+        assert_eq!(backend.generate_section("Summarize", 200).await.unwrap(), "Short");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn reports_a_missing_model() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/api/generate")
+            .with_status(404)
+            .with_body(r#"{"error": "model 'llama3.1:8b' not found"}"#)
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let backend = OllamaBackend::new(spider, &settings(&server.url())).unwrap();
+
+        let err = backend.generate("prompt").await.unwrap_err().to_string();
+        assert!(err.contains("Ollama API error 404"), "{err}");
+        assert!(err.contains("not found"), "{err}");
+    }
+
+    #[test]
+    fn needs_a_model() {
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let err = OllamaBackend::new(spider, &OllamaSettings::default())
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with(NOT_CONFIGURED));
+    }
+}
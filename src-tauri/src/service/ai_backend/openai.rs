@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use specta::Type;
+
+use super::{endpoint, post, AiBackend, NOT_CONFIGURED};
+use crate::service::spider::SpiderAgent;
+
+const NAME: &str = "OpenAI-compatible";
+
+/// Where to reach an OpenAI-compatible chat completions API: OpenAI
+/// itself, or a server that speaks the same protocol such as LM Studio,
+/// vLLM or OpenRouter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct OpenAiCompatibleSettings {
+    /// API root that `/chat/completions` is appended to.
+    pub base_url: String,
+    pub model: String,
+    /// Sent as a bearer token; empty for servers that don't need one.
+    #[serde(default)]
+    pub api_key: String,
+}
+
+impl Default for OpenAiCompatibleSettings {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.openai.com/v1".to_string(),
+            model: String::new(),
+            api_key: String::new(),
+        }
+    }
+}
+
+pub struct OpenAiCompatibleBackend {
+    spider: Arc<dyn SpiderAgent>,
+    url: String,
+    model: String,
+    api_key: String,
+}
+
+impl OpenAiCompatibleBackend {
+    pub fn new(spider: Arc<dyn SpiderAgent>, settings: &OpenAiCompatibleSettings) -> Result<Self> {
+        let url = endpoint(NAME, &settings.base_url, "/chat/completions")?;
+        let model = settings.model.trim();
+        if model.is_empty() {
+            anyhow::bail!("{NOT_CONFIGURED}: choose a model for the {NAME} backend");
+        }
+        Ok(Self {
+            spider,
+            url,
+            model: model.to_string(),
+            api_key: settings.api_key.trim().to_string(),
+        })
+    }
+
+    /// The token limit goes in the request's `max_tokens` field.
+    async fn complete(&self, prompt: &str, max_tokens: Option<usize>) -> Result<String> {
+        let mut body = json!({
+            "model": self.model,
+            "messages": [{ "role": "user", "content": prompt }],
+        });
+        if let Some(max_tokens) = max_tokens {
+            body["max_tokens"] = json!(max_tokens);
+        }
+        let authorization = format!("Bearer {}", self.api_key);
+        let headers = if self.api_key.is_empty() {
+            vec![]
+        } else {
+            vec![("Authorization", authorization.as_str())]
+        };
+
+        let response = post(self.spider.as_ref(), NAME, &self.url, &headers, &body).await?;
+        response["choices"][0]["message"]["content"]
+            .as_str()
+            .map(str::to_string)
+            .with_context(|| format!("Failed to extract text from {NAME} response"))
+    }
+}
+
+#[async_trait]
+impl AiBackend for OpenAiCompatibleBackend {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        self.complete(prompt, None).await
+    }
+
+    async fn generate_section(&self, prompt: &str, max_tokens: usize) -> Result<String> {
+        self.complete(prompt, Some(max_tokens)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::spider::{ClientType, Spider};
+
+    fn settings(base_url: &str, api_key: &str) -> OpenAiCompatibleSettings {
+        OpenAiCompatibleSettings {
+            base_url: base_url.to_string(),
+            model: "gpt-4o-mini".to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_the_prompt_as_a_chat_message_with_the_key() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_header("authorization", "Bearer sk-test")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "model": "gpt-4o-mini",
+                "messages": [{ "role": "user", "content": "Audit this site" }],
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({ "choices": [{ "message": { "role": "assistant", "content": "Fix titles" } }] })
+                    .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let backend = OpenAiCompatibleBackend::new(
+            spider,
+            &settings(&format!("{}/v1/", server.url()), "sk-test"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            backend.generate("Audit this site").await.unwrap(),
+            "Fix titles"
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn caps_a_section_at_its_token_budget() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "messages": [{ "role": "user", "content": "Summarize" }],
+                "max_tokens": 280,
+            })))
+            .with_status(200)
+            .with_body(
+                json!({ "choices": [{ "message": { "role": "assistant", "content": "Short" } }] })
+                    .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let backend = OpenAiCompatibleBackend::new(spider, &settings(&server.url(), "")).unwrap();
+
+        assert_eq!(backend.generate_section("Summarize", 280).await.unwrap(), "Short");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn leaves_out_the_authorization_header_without_a_key() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/chat/completions")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_body(json!({ "choices": [{ "message": { "content": "ok" } }] }).to_string())
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let backend = OpenAiCompatibleBackend::new(spider, &settings(&server.url(), "")).unwrap();
+
+        assert_eq!(backend.generate("prompt").await.unwrap(), "ok");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn reports_the_status_of_a_failed_request() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/chat/completions")
+            .with_status(401)
+            .with_body(r#"{"error": {"message": "Incorrect API key"}}"#)
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let backend =
+            OpenAiCompatibleBackend::new(spider, &settings(&server.url(), "bad")).unwrap();
+
+        let err = backend.generate("prompt").await.unwrap_err().to_string();
+        assert!(err.contains("401"), "{err}");
+    }
+
+    #[test]
+    fn needs_a_model_and_an_http_url() {
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+
+        let mut no_model = settings("https://api.openai.com/v1", "");
+        no_model.model = " ".to_string();
+        let err = OpenAiCompatibleBackend::new(spider.clone(), &no_model)
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with(NOT_CONFIGURED));
+
+        let err = OpenAiCompatibleBackend::new(spider, &settings("localhost:8080", ""))
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with(NOT_CONFIGURED));
+    }
+}
@@ -269,6 +269,9 @@ impl DeepAuditor {
             load_time_ms,
             content_size,
             scores,
+            // Lighthouse doesn't report the document's headers.
+            headers: Default::default(),
+            timing: None,
         }
     }
 
@@ -84,6 +84,8 @@ impl Auditor for LightAuditor {
         let status_code = cached.status_code;
         let html = cached.html;
         let load_time_ms = cached.load_time_ms;
+        let headers = cached.headers;
+        let timing = cached.timing;
         let content_size = html.len();
 
         let (mut scores, _details) = self.analyze_html(&html, &final_url);
@@ -111,6 +113,8 @@ impl Auditor for LightAuditor {
             load_time_ms,
             content_size,
             scores,
+            headers,
+            timing,
         })
     }
 
@@ -136,6 +140,8 @@ impl Auditor for LightAuditor {
 
         let status_code = response.status;
         let html = response.body;
+        let headers = response.headers;
+        let timing = Some(response.timing);
 
         let load_time_ms = start_time.elapsed().as_secs_f64() * 1000.0;
         let content_size = html.len();
@@ -176,6 +182,8 @@ impl Auditor for LightAuditor {
             load_time_ms,
             content_size,
             scores,
+            headers,
+            timing,
         })
     }
 
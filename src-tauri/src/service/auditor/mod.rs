@@ -11,6 +11,7 @@ pub use deep::DeepAuditor;
 pub use light::LightAuditor;
 pub use types::*;
 
+use crate::contexts::analysis::{FetchTiming, ResponseHeaders};
 use anyhow::Result;
 use async_trait::async_trait;
 
@@ -22,6 +23,8 @@ pub struct CachedHtml {
     pub final_url: String,
     pub status_code: u16,
     pub load_time_ms: f64,
+    pub headers: ResponseHeaders,
+    pub timing: Option<FetchTiming>,
 }
 
 #[async_trait]
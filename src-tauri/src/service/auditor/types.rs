@@ -1,3 +1,4 @@
+use crate::contexts::analysis::{FetchTiming, ResponseHeaders};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
@@ -32,6 +33,13 @@ pub struct AuditResult {
     pub load_time_ms: f64,
     pub content_size: usize,
     pub scores: AuditScores,
+    /// Response headers; empty when the auditor can't see them.
+    #[serde(default)]
+    pub headers: ResponseHeaders,
+    /// TTFB and download time of the fetch; `None` when the auditor
+    /// can't see them.
+    #[serde(default)]
+    pub timing: Option<FetchTiming>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -0,0 +1,289 @@
+//! Cloaking check: fetch the same pages as two different clients (say, a
+//! browser and Googlebot) and report where the responses disagree on
+//! what search engines index.
+//!
+//! Small wording or markup differences are normal — personalised blocks,
+//! rotating promos — so word and link counts only count as different past
+//! a tolerance. Titles, descriptions, canonicals, robots directives and
+//! H1s are compared exactly.
+
+use std::sync::Arc;
+
+use scraper::Html;
+use serde::Serialize;
+use specta::Type;
+
+use crate::contexts::analysis::CrawlProfile;
+use crate::extractor::page_extractor::PageExtractor;
+use crate::service::spider::{SpiderAgent, SpiderResponse};
+
+/// Relative difference above which word and link counts are reported.
+const COUNT_TOLERANCE: f64 = 0.1;
+
+pub const DEFAULT_SAMPLE_SIZE: usize = 10;
+pub const MAX_SAMPLE_SIZE: usize = 50;
+
+/// One side of the comparison: the profile a spider presents.
+pub struct CloakingProbe {
+    pub profile: CrawlProfile,
+    pub spider: Arc<dyn SpiderAgent>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct CloakingReport {
+    pub baseline: CrawlProfile,
+    pub compared: CrawlProfile,
+    pub pages: Vec<CloakingPageResult>,
+}
+
+impl CloakingReport {
+    pub fn pages_with_differences(&self) -> usize {
+        self.pages
+            .iter()
+            .filter(|page| !page.differences.is_empty())
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct CloakingPageResult {
+    pub url: String,
+    pub differences: Vec<CloakingDifference>,
+    /// Set when either fetch failed; the page is then not compared.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Type)]
+pub struct CloakingDifference {
+    pub field: String,
+    pub baseline: Option<String>,
+    pub compared: Option<String>,
+}
+
+/// The parts of a response a search engine's view of the page hinges on.
+#[derive(Debug)]
+struct PageSnapshot {
+    status: u16,
+    final_url: String,
+    title: Option<String>,
+    meta_description: Option<String>,
+    canonical: Option<String>,
+    robots: Option<String>,
+    h1: Vec<String>,
+    word_count: i64,
+    link_count: usize,
+}
+
+impl PageSnapshot {
+    fn from_response(response: &SpiderResponse) -> Self {
+        let html = Html::parse_document(&response.body);
+        let (internal, external, _) = PageExtractor::extract_links(&html, &response.url);
+        let h1 = PageExtractor::extract_headings(&html)
+            .into_iter()
+            .filter(|heading| heading.level == 1 && !heading.text.is_empty())
+            .map(|heading| heading.text)
+            .collect();
+        let robots = html
+            .select(cached_selector!("meta[name='robots']"))
+            .next()
+            .and_then(|el| el.value().attr("content"))
+            .map(|s| s.trim().to_ascii_lowercase())
+            .filter(|s| !s.is_empty());
+
+        Self {
+            status: response.status,
+            final_url: response.url.clone(),
+            title: PageExtractor::extract_title(&html),
+            meta_description: PageExtractor::extract_meta_description(&html),
+            canonical: PageExtractor::extract_canonical(&html),
+            robots,
+            h1,
+            word_count: PageExtractor::extract_word_count(&html),
+            link_count: internal.len() + external.len(),
+        }
+    }
+
+    fn differences(&self, other: &Self) -> Vec<CloakingDifference> {
+        let mut differences = Vec::new();
+        let mut compare = |field: &str, baseline: Option<String>, compared: Option<String>| {
+            if baseline != compared {
+                differences.push(CloakingDifference {
+                    field: field.to_string(),
+                    baseline,
+                    compared,
+                });
+            }
+        };
+
+        compare(
+            "status",
+            Some(self.status.to_string()),
+            Some(other.status.to_string()),
+        );
+        compare(
+            "final_url",
+            Some(self.final_url.clone()),
+            Some(other.final_url.clone()),
+        );
+        compare("title", self.title.clone(), other.title.clone());
+        compare(
+            "meta_description",
+            self.meta_description.clone(),
+            other.meta_description.clone(),
+        );
+        compare("canonical", self.canonical.clone(), other.canonical.clone());
+        compare("robots", self.robots.clone(), other.robots.clone());
+        compare("h1", join(&self.h1), join(&other.h1));
+
+        if counts_differ(self.word_count as usize, other.word_count as usize) {
+            compare(
+                "word_count",
+                Some(self.word_count.to_string()),
+                Some(other.word_count.to_string()),
+            );
+        }
+        if counts_differ(self.link_count, other.link_count) {
+            compare(
+                "link_count",
+                Some(self.link_count.to_string()),
+                Some(other.link_count.to_string()),
+            );
+        }
+
+        differences
+    }
+}
+
+fn join(values: &[String]) -> Option<String> {
+    (!values.is_empty()).then(|| values.join(" | "))
+}
+
+fn counts_differ(a: usize, b: usize) -> bool {
+    a.abs_diff(b) as f64 > a.max(b) as f64 * COUNT_TOLERANCE
+}
+
+/// Up to `size` URLs spread evenly over `urls`, so the sample covers the
+/// whole crawl rather than just the pages found first.
+pub fn sample_evenly(urls: &[String], size: usize) -> Vec<String> {
+    if urls.len() <= size {
+        return urls.to_vec();
+    }
+    (0..size)
+        .map(|i| urls[i * urls.len() / size].clone())
+        .collect()
+}
+
+/// Fetch every URL through both probes and compare what each was served.
+pub async fn compare_profiles(
+    baseline: &CloakingProbe,
+    compared: &CloakingProbe,
+    urls: &[String],
+) -> CloakingReport {
+    let mut pages = Vec::with_capacity(urls.len());
+
+    for url in urls {
+        let (left, right) = tokio::join!(baseline.spider.get(url), compared.spider.get(url));
+        let result = match (left, right) {
+            (Ok(left), Ok(right)) => CloakingPageResult {
+                url: url.clone(),
+                differences: PageSnapshot::from_response(&left)
+                    .differences(&PageSnapshot::from_response(&right)),
+                error: None,
+            },
+            (Err(e), _) | (_, Err(e)) => CloakingPageResult {
+                url: url.clone(),
+                differences: Vec::new(),
+                error: Some(format!("{e:#}")),
+            },
+        };
+        pages.push(result);
+    }
+
+    CloakingReport {
+        baseline: baseline.profile.clone(),
+        compared: compared.profile.clone(),
+        pages,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::spider::Spider;
+    use mockito::Matcher;
+
+    fn response(body: &str) -> SpiderResponse {
+        SpiderResponse {
+            status: 200,
+            body: body.to_string(),
+            url: "https://example.com/".to_string(),
+            headers: Default::default(),
+            timing: Default::default(),
+        }
+    }
+
+    #[test]
+    fn small_count_changes_are_tolerated() {
+        let words = |n: usize| format!("<html><body>{}</body></html>", "word ".repeat(n));
+        let base = PageSnapshot::from_response(&response(&words(100)));
+
+        assert!(base
+            .differences(&PageSnapshot::from_response(&response(&words(95))))
+            .is_empty());
+        let differences = base.differences(&PageSnapshot::from_response(&response(&words(40))));
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].field, "word_count");
+    }
+
+    #[test]
+    fn sample_spreads_over_the_whole_list() {
+        let urls: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+
+        assert_eq!(sample_evenly(&urls, 3), vec!["0", "3", "6"]);
+        assert_eq!(sample_evenly(&urls[..2], 3).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reports_content_served_only_to_googlebot() {
+        let mut server = mockito::Server::new_async().await;
+        let _bot = server
+            .mock("GET", "/")
+            .match_header("user-agent", Matcher::Regex("Googlebot".into()))
+            .with_body(
+                "<html><head><title>Cheap pills</title></head>\
+                 <body><h1>Buy now</h1></body></html>",
+            )
+            .create_async()
+            .await;
+        let _browser = server
+            .mock("GET", "/")
+            .match_header("user-agent", Matcher::Regex("Browser".into()))
+            .with_body(
+                "<html><head><title>Garden tips</title></head>\
+                 <body><h1>Buy now</h1></body></html>",
+            )
+            .create_async()
+            .await;
+
+        let probe = |profile: CrawlProfile| CloakingProbe {
+            spider: Arc::new(Spider::for_job(&profile, None, &server.url()).unwrap()),
+            profile,
+        };
+        let baseline = probe(CrawlProfile::Custom {
+            user_agent: "Browser/1.0".into(),
+        });
+        let compared = probe(CrawlProfile::GooglebotSmartphone);
+
+        let report = compare_profiles(&baseline, &compared, &[format!("{}/", server.url())]).await;
+
+        assert_eq!(report.pages_with_differences(), 1);
+        assert_eq!(
+            report.pages[0].differences,
+            vec![CloakingDifference {
+                field: "title".into(),
+                baseline: Some("Garden tips".into()),
+                compared: Some("Cheap pills".into()),
+            }]
+        );
+    }
+}
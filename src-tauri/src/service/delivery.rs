@@ -0,0 +1,440 @@
+//! Delivery probe: how the site's start page and a sample of the static
+//! assets it references are compressed and cached.
+//!
+//! Spiders decode compressed bodies, and decoding drops `Content-Encoding`
+//! from the response, so the probe fetches everything a second time
+//! through a spider built with
+//! [`Spider::for_probe`](crate::service::spider::Spider::for_probe) and
+//! judges the headers only. One probe runs per job; its findings are
+//! site-level issues.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use scraper::{Html, Selector};
+use url::Url;
+
+use crate::contexts::analysis::{
+    IssueBuilder, IssueSeverity, NewIssue, ResponseHeaders,
+};
+use crate::service::spider::{SpiderAgent, SpiderResponse};
+
+/// Static assets probed per job. Enough to spot a server that doesn't
+/// compress or cache a type of file without fetching the whole site twice.
+const MAX_ASSETS: usize = 12;
+
+/// Responses smaller than this gain little from compression.
+const MIN_COMPRESSIBLE_BYTES: usize = 1024;
+
+/// Assets cached for less than a day are re-downloaded on most visits.
+const MIN_ASSET_MAX_AGE_SECS: u64 = 86_400;
+
+const COMPRESSED_ENCODINGS: &[&str] = &["gzip", "br", "zstd", "deflate"];
+
+/// URLs listed in an issue's details, so one broken CDN doesn't produce a
+/// wall of text.
+const MAX_LISTED_URLS: usize = 5;
+
+pub struct DeliveryProbe {
+    /// Fetches the start page decoded, to find its assets.
+    pages: Arc<dyn SpiderAgent>,
+    /// Fetches without decoding, for the headers.
+    probe: Arc<dyn SpiderAgent>,
+}
+
+impl DeliveryProbe {
+    pub fn new(pages: Arc<dyn SpiderAgent>, probe: Arc<dyn SpiderAgent>) -> Self {
+        Self { pages, probe }
+    }
+
+    /// Probe `url` and the same-host assets it references. A start page
+    /// that can't be fetched yields no issues; the crawl reports that.
+    pub async fn audit(&self, job_id: &str, url: &str) -> Vec<NewIssue> {
+        let page = match self.pages.get(url).await {
+            Ok(page) if page.status < 400 => page,
+            Ok(page) => {
+                tracing::debug!("[DELIVERY] {} answered {}, skipping", url, page.status);
+                return Vec::new();
+            }
+            Err(e) => {
+                tracing::debug!("[DELIVERY] Could not fetch {}: {:#}", url, e);
+                return Vec::new();
+            }
+        };
+
+        let mut issues = Vec::new();
+        match self.probe.get(&page.url).await {
+            Ok(raw) => issues.extend(page_issues(job_id, &page.url, &raw)),
+            Err(e) => tracing::debug!("[DELIVERY] Probe of {} failed: {:#}", page.url, e),
+        }
+
+        let mut assets = Vec::new();
+        for asset_url in asset_urls(&page.body, &page.url) {
+            match self.probe.get(&asset_url).await {
+                Ok(raw) if raw.status < 400 => assets.push((asset_url, raw)),
+                Ok(_) => {}
+                Err(e) => tracing::debug!("[DELIVERY] Probe of {} failed: {:#}", asset_url, e),
+            }
+        }
+        issues.extend(asset_issues(job_id, &assets));
+
+        tracing::debug!(
+            "[DELIVERY] {}: {} assets probed, {} issues",
+            page.url,
+            assets.len(),
+            issues.len()
+        );
+        issues
+    }
+}
+
+/// Stylesheets, scripts and images on the page's own host, in that order
+/// (render-blocking files first), deduplicated and capped at
+/// [`MAX_ASSETS`]. Third-party files are left out: the site can't fix how
+/// someone else's server delivers them.
+fn asset_urls(html: &str, page_url: &str) -> Vec<String> {
+    let Ok(base) = Url::parse(page_url) else {
+        return Vec::new();
+    };
+    let document = Html::parse_document(html);
+    let mut seen = HashSet::new();
+    let mut urls = Vec::new();
+
+    for (selector, attr) in [
+        ("link[rel~='stylesheet'][href]", "href"),
+        ("script[src]", "src"),
+        ("img[src]", "src"),
+    ] {
+        let Ok(selector) = Selector::parse(selector) else {
+            continue;
+        };
+        for element in document.select(&selector) {
+            let Some(url) = element
+                .value()
+                .attr(attr)
+                .and_then(|href| base.join(href.trim()).ok())
+                .filter(|url| matches!(url.scheme(), "http" | "https"))
+                .filter(|url| url.host_str() == base.host_str())
+            else {
+                continue;
+            };
+            if urls.len() == MAX_ASSETS {
+                return urls;
+            }
+            if seen.insert(url.to_string()) {
+                urls.push(url.to_string());
+            }
+        }
+    }
+    urls
+}
+
+fn page_issues(job_id: &str, url: &str, raw: &SpiderResponse) -> Vec<NewIssue> {
+    let mut issues = Vec::new();
+    if wants_compression(raw) && encoding(&raw.headers).is_none() {
+        issues.push(
+            IssueBuilder::new(
+                job_id.to_string(),
+                "uncompressed_html".to_string(),
+                IssueSeverity::Warning,
+                format!("{url} is served without compression"),
+            )
+            .details(format!(
+                "{} of HTML sent as-is to a browser that accepts gzip and Brotli. \
+                 Enable compression for text responses on the server or CDN.",
+                format_size(body_size(raw))
+            ))
+            .build(),
+        );
+    }
+    if let Some(issue) = vary_issue(job_id, [(url.to_string(), raw)]) {
+        issues.push(issue);
+    }
+    issues
+}
+
+fn asset_issues(job_id: &str, assets: &[(String, SpiderResponse)]) -> Vec<NewIssue> {
+    let mut issues = Vec::new();
+
+    let uncompressed: Vec<&str> = assets
+        .iter()
+        .filter(|(_, raw)| wants_compression(raw) && encoding(&raw.headers).is_none())
+        .map(|(url, _)| url.as_str())
+        .collect();
+    if !uncompressed.is_empty() {
+        issues.push(
+            IssueBuilder::new(
+                job_id.to_string(),
+                "uncompressed_assets".to_string(),
+                IssueSeverity::Warning,
+                format!(
+                    "{} of {} text assets checked are served without compression",
+                    uncompressed.len(),
+                    assets.iter().filter(|(_, raw)| wants_compression(raw)).count()
+                ),
+            )
+            .details(format!(
+                "Enable gzip or Brotli for CSS, JavaScript and SVG. Examples: {}",
+                list_urls(&uncompressed)
+            ))
+            .build(),
+        );
+    }
+
+    let uncached: Vec<String> = assets
+        .iter()
+        .filter_map(|(url, raw)| {
+            cache_problem(&raw.headers).map(|problem| format!("{url} ({problem})"))
+        })
+        .collect();
+    if !uncached.is_empty() {
+        let listed: Vec<&str> = uncached.iter().map(String::as_str).collect();
+        issues.push(
+            IssueBuilder::new(
+                job_id.to_string(),
+                "short_asset_caching".to_string(),
+                IssueSeverity::Warning,
+                format!(
+                    "{} of {} static assets checked can't be cached for long",
+                    uncached.len(),
+                    assets.len()
+                ),
+            )
+            .details(format!(
+                "Send `Cache-Control: max-age` of a day or more (a year for \
+                 fingerprinted files) so repeat visits don't download them again. \
+                 Examples: {}",
+                list_urls(&listed)
+            ))
+            .build(),
+        );
+    }
+
+    if let Some(issue) = vary_issue(
+        job_id,
+        assets.iter().map(|(url, raw)| (url.clone(), raw)),
+    ) {
+        issues.push(issue);
+    }
+    issues
+}
+
+/// Compressed responses without `Vary: Accept-Encoding` can be served
+/// compressed from a shared cache to a client that can't decode them.
+fn vary_issue<'a>(
+    job_id: &str,
+    responses: impl IntoIterator<Item = (String, &'a SpiderResponse)>,
+) -> Option<NewIssue> {
+    let missing: Vec<String> = responses
+        .into_iter()
+        .filter(|(_, raw)| encoding(&raw.headers).is_some())
+        .filter(|(_, raw)| {
+            let vary = raw.headers.tokens("vary");
+            !vary.iter().any(|token| token == "accept-encoding" || token == "*")
+        })
+        .map(|(url, _)| url)
+        .collect();
+    if missing.is_empty() {
+        return None;
+    }
+
+    let listed: Vec<&str> = missing.iter().map(String::as_str).collect();
+    Some(
+        IssueBuilder::new(
+            job_id.to_string(),
+            "missing_vary_accept_encoding".to_string(),
+            IssueSeverity::Info,
+            format!(
+                "{} compressed responses don't send Vary: Accept-Encoding",
+                missing.len()
+            ),
+        )
+        .details(format!(
+            "Without it a shared cache may hand the compressed copy to a client \
+             that didn't ask for it. Examples: {}",
+            list_urls(&listed)
+        ))
+        .build(),
+    )
+}
+
+/// The content encoding applied, if it's a compression.
+fn encoding(headers: &ResponseHeaders) -> Option<String> {
+    headers
+        .tokens("content-encoding")
+        .into_iter()
+        .find(|token| COMPRESSED_ENCODINGS.contains(&token.as_str()))
+}
+
+/// A text response large enough for compression to matter.
+fn wants_compression(raw: &SpiderResponse) -> bool {
+    raw.headers.media_type().is_some_and(|media| is_compressible(&media))
+        && body_size(raw) >= MIN_COMPRESSIBLE_BYTES
+}
+
+fn is_compressible(media_type: &str) -> bool {
+    media_type.starts_with("text/")
+        || media_type.ends_with("+json")
+        || media_type.ends_with("+xml")
+        || matches!(
+            media_type,
+            "application/javascript"
+                | "application/x-javascript"
+                | "application/json"
+                | "application/xml"
+                | "application/wasm"
+        )
+}
+
+/// Size on the wire: `Content-Length` when sent, else the body received.
+fn body_size(raw: &SpiderResponse) -> usize {
+    raw.headers
+        .get("content-length")
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(raw.body.len())
+}
+
+/// Why a static asset can't be cached for long, or `None` if it can.
+/// An `Expires` header without `Cache-Control` is taken at its word.
+fn cache_problem(headers: &ResponseHeaders) -> Option<String> {
+    let directives = headers.tokens("cache-control");
+    if directives.is_empty() {
+        return (!headers.contains("expires")).then(|| "no Cache-Control".to_string());
+    }
+    if directives.iter().any(|d| d == "no-store") {
+        return Some("no-store".to_string());
+    }
+    if directives.iter().any(|d| d == "no-cache") {
+        return Some("no-cache".to_string());
+    }
+    let max_age = directives.iter().find_map(|d| {
+        d.strip_prefix("max-age=")
+            .and_then(|secs| secs.trim_matches('"').parse::<u64>().ok())
+    });
+    match max_age {
+        Some(secs) if secs >= MIN_ASSET_MAX_AGE_SECS => None,
+        Some(secs) => Some(format!("max-age={secs}")),
+        None if headers.contains("expires") => None,
+        None => Some("no max-age".to_string()),
+    }
+}
+
+fn list_urls(urls: &[&str]) -> String {
+    let mut listed = urls[..urls.len().min(MAX_LISTED_URLS)].join(", ");
+    if urls.len() > MAX_LISTED_URLS {
+        listed.push_str(&format!(" and {} more", urls.len() - MAX_LISTED_URLS));
+    }
+    listed
+}
+
+fn format_size(bytes: usize) -> String {
+    if bytes >= 1024 {
+        format!("{:.0} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(headers: &[(&str, &str)], size: usize) -> SpiderResponse {
+        SpiderResponse {
+            status: 200,
+            body: "x".repeat(size),
+            url: "https://example.com/".to_string(),
+            headers: ResponseHeaders::new(headers.iter().copied()),
+            timing: Default::default(),
+        }
+    }
+
+    #[test]
+    fn assets_are_same_host_and_render_blocking_first() {
+        let html = r#"<html><head>
+            <script src="/app.js"></script>
+            <link rel="preload stylesheet" href="/site.css">
+            <link rel="icon" href="/favicon.ico">
+            <script src="https://cdn.other.com/lib.js"></script>
+        </head><body><img src="logo.png"><img src="/logo.png"></body></html>"#;
+
+        assert_eq!(
+            asset_urls(html, "https://example.com/"),
+            [
+                "https://example.com/site.css",
+                "https://example.com/app.js",
+                "https://example.com/logo.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn uncompressed_html_is_flagged() {
+        let raw = response(&[("Content-Type", "text/html; charset=utf-8")], 20_000);
+        let issues = page_issues("j1", "https://example.com/", &raw);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, "uncompressed_html");
+        assert_eq!(issues[0].page_id, None);
+    }
+
+    #[test]
+    fn compressed_html_needs_vary() {
+        let raw = response(
+            &[("Content-Type", "text/html"), ("Content-Encoding", "br"), ("Content-Length", "4000")],
+            4000,
+        );
+        let issues = page_issues("j1", "https://example.com/", &raw);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, "missing_vary_accept_encoding");
+
+        let raw = response(
+            &[("Content-Type", "text/html"), ("Content-Encoding", "br"), ("Vary", "Accept-Encoding")],
+            4000,
+        );
+        assert!(page_issues("j1", "https://example.com/", &raw).is_empty());
+    }
+
+    #[test]
+    fn small_and_binary_responses_need_no_compression() {
+        assert!(!wants_compression(&response(&[("Content-Type", "text/css")], 200)));
+        assert!(!wants_compression(&response(&[("Content-Type", "image/png")], 50_000)));
+        assert!(wants_compression(&response(&[("Content-Type", "image/svg+xml")], 5_000)));
+    }
+
+    #[test]
+    fn asset_caching_needs_a_day_or_more() {
+        let headers = |pairs: &[(&str, &str)]| ResponseHeaders::new(pairs.iter().copied());
+
+        assert_eq!(cache_problem(&headers(&[])).as_deref(), Some("no Cache-Control"));
+        assert_eq!(
+            cache_problem(&headers(&[("Cache-Control", "public, max-age=600")])).as_deref(),
+            Some("max-age=600")
+        );
+        assert_eq!(
+            cache_problem(&headers(&[("Cache-Control", "no-store")])).as_deref(),
+            Some("no-store")
+        );
+        assert!(cache_problem(&headers(&[("Cache-Control", "max-age=31536000, immutable")])).is_none());
+        assert!(cache_problem(&headers(&[("Expires", "Thu, 01 Dec 2099 16:00:00 GMT")])).is_none());
+    }
+
+    #[test]
+    fn asset_findings_are_grouped_into_one_issue_each() {
+        let assets = vec![
+            (
+                "https://example.com/a.css".to_string(),
+                response(&[("Content-Type", "text/css")], 8_000),
+            ),
+            (
+                "https://example.com/b.js".to_string(),
+                response(&[("Content-Type", "application/javascript")], 8_000),
+            ),
+        ];
+        let issues = asset_issues("j1", &assets);
+        let types: Vec<&str> = issues.iter().map(|i| i.issue_type.as_str()).collect();
+
+        assert_eq!(types, ["uncompressed_assets", "short_asset_caching"]);
+        assert!(issues[0].message.starts_with("2 of 2"));
+    }
+}
@@ -6,7 +6,11 @@ use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
 use url::Url;
 
-use crate::contexts::analysis::ResourceStatus;
+use crate::checker::site::{SiteCheck, SiteCheckRegistry, SiteFetch};
+use crate::contexts::analysis::{
+    CompiledUrlRules, DiscoveryFrontier, FetchTiming, ResponseHeaders, SiteCheckResult,
+    SiteCheckStatus,
+};
 use crate::service::spider::SpiderAgent;
 use std::sync::Arc;
 
@@ -23,45 +27,19 @@ pub struct DiscoveredPage {
     pub html: String,
     pub status_code: u16,
     pub load_time_ms: f64,
+    pub headers: ResponseHeaders,
+    /// `None` for pages queued without a fetch.
+    pub timing: Option<FetchTiming>,
 }
 
-/// Result of a site-level resource check (robots.txt presence,
-/// sitemap presence, HTTPS).
-///
-/// Fields are private to enforce that any `SiteResources` value flows
-/// through the `new` constructor — that's where the (potential)
-/// validation lives. Today there's no validation, but the
-/// encapsulation gives us a single place to add it later without a
-/// cascade through every read site.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct SiteResources {
-    robots_txt: bool,
-    sitemap: bool,
-    ssl: bool,
-}
-
-impl SiteResources {
-    pub fn new(robots_txt: bool, sitemap: bool, ssl: bool) -> Self {
-        Self { robots_txt, sitemap, ssl }
-    }
-
-    pub fn robots_txt(&self) -> bool {
-        self.robots_txt
-    }
-
-    pub fn sitemap(&self) -> bool {
-        self.sitemap
-    }
-
-    pub fn ssl(&self) -> bool {
-        self.ssl
-    }
-
-    /// Whether all three site resources are present. Useful for the
-    /// "fully optimized" UI badge.
-    pub fn all_present(&self) -> bool {
-        self.robots_txt && self.sitemap && self.ssl
-    }
+/// Result of a (possibly resumed) discovery crawl.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryOutcome {
+    /// Pages fetched during this run, in crawl order.
+    pub pages: Vec<DiscoveredPage>,
+    /// The remaining crawl state when the run was interrupted by its
+    /// cancellation token; `None` when discovery ran to completion.
+    pub interrupted: Option<DiscoveryFrontier>,
 }
 
 pub struct PageDiscovery {
@@ -82,7 +60,50 @@ impl PageDiscovery {
         cancel_token: &CancellationToken,
         on_discovered: impl Fn(usize) + Send + Sync,
     ) -> Result<Vec<DiscoveredPage>> {
-        let start_url = Url::parse(start_url_str)?;
+        let outcome = self
+            .discover_from(
+                start_url_str,
+                None,
+                &CompiledUrlRules::default(),
+                max_pages,
+                delay_ms,
+                include_subdomains,
+                cancel_token,
+                on_discovered,
+            )
+            .await?;
+        Ok(outcome.pages)
+    }
+
+    /// Depth-first discovery that can start from a saved checkpoint.
+    ///
+    /// With `resume: None` the crawl starts at `start_url_str`. With a
+    /// frontier from a paused job, the visited set and the to-visit stack
+    /// are restored instead, so the crawl continues with the next URL it
+    /// would have fetched and the `max_pages` budget counts the pages
+    /// visited before the pause.
+    ///
+    /// Every link is normalized by `rules` before it is deduplicated, and
+    /// only links the rules allow (and the trap guard admits) are queued.
+    /// The start URL is normalized but always crawled.
+    ///
+    /// When `cancel_token` fires mid-crawl the returned outcome carries
+    /// the unfinished frontier; the caller decides whether that was a
+    /// cancel (drop it) or a pause (persist it).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn discover_from(
+        &self,
+        start_url_str: &str,
+        resume: Option<DiscoveryFrontier>,
+        rules: &CompiledUrlRules,
+        max_pages: i64,
+        delay_ms: i64,
+        include_subdomains: bool,
+        cancel_token: &CancellationToken,
+        on_discovered: impl Fn(usize) + Send + Sync,
+    ) -> Result<DiscoveryOutcome> {
+        let start_url = rules.normalize(&Url::parse(start_url_str)?);
+        let mut trap_guard = rules.trap_guard();
         tracing::info!("[DISCOVERY] Starting page discovery from: {}", start_url);
         tracing::debug!(
             "[DISCOVERY] Max pages: {}, Delay: {}ms",
@@ -90,9 +111,30 @@ impl PageDiscovery {
             delay_ms
         );
 
-        let mut visited: HashSet<Url> = HashSet::new();
+        let (mut visited, mut to_visit): (HashSet<Url>, Vec<Url>) = match resume {
+            Some(frontier) => {
+                let parse_all = |urls: Vec<String>| {
+                    urls.into_iter()
+                        .filter_map(|u| Url::parse(&u).ok())
+                        .collect::<Vec<_>>()
+                };
+                let visited: HashSet<Url> = parse_all(frontier.visited).into_iter().collect();
+                let to_visit = parse_all(frontier.to_visit);
+                // Re-seed the trap counters with everything the paused
+                // run had already accepted.
+                for url in visited.iter().chain(&to_visit) {
+                    trap_guard.admit(url);
+                }
+                tracing::info!(
+                    "[DISCOVERY] Resuming with {} visited and {} queued URLs",
+                    visited.len(),
+                    to_visit.len()
+                );
+                (visited, to_visit)
+            }
+            None => (HashSet::new(), vec![start_url.clone()]),
+        };
         let mut discovered_pages: Vec<DiscoveredPage> = Vec::new();
-        let mut to_visit = vec![start_url.clone()];
 
         let base_host = start_url
             .host_str()
@@ -107,10 +149,20 @@ impl PageDiscovery {
         while let Some(url) = to_visit.pop() {
             if cancel_token.is_cancelled() {
                 tracing::warn!(
-                    "[DISCOVERY] Discovery cancelled by user at {} pages",
+                    "[DISCOVERY] Discovery interrupted by user at {} pages",
                     visited.len()
                 );
-                return Ok(discovered_pages);
+                // Put the URL back so a resumed crawl fetches it first.
+                to_visit.push(url);
+                let frontier = DiscoveryFrontier {
+                    to_visit: to_visit.iter().map(Url::to_string).collect(),
+                    visited: visited.iter().map(Url::to_string).collect(),
+                    discovery_complete: false,
+                };
+                return Ok(DiscoveryOutcome {
+                    pages: discovered_pages,
+                    interrupted: Some(frontier),
+                });
             }
             if visited.contains(&url) {
                 tracing::trace!("[DISCOVERY] Skipping already visited: {}", url);
@@ -156,11 +208,14 @@ impl PageDiscovery {
                 html: body.clone(),
                 status_code,
                 load_time_ms,
+                headers: response.headers,
+                timing: Some(response.timing),
             });
 
             let links: Vec<Url> = Self::extract_links(&body, &url)
                 .into_iter()
                 .filter_map(|s| Url::parse(&s).ok())
+                .map(|link| rules.normalize(&link))
                 .collect();
 
             tracing::debug!("[DISCOVERY] Found {} links on {}", links.len(), url);
@@ -172,9 +227,14 @@ impl PageDiscovery {
                 let link_type =
                     crate::contexts::link::NewLink::classify_urls(&link, &start_url);
 
-                let should_follow = link_type.should_follow(include_subdomains);
+                let should_follow =
+                    link_type.should_follow(include_subdomains) && rules.is_allowed(&link);
 
-                if should_follow && !visited.contains(&link) && !to_visit.contains(&link) {
+                if should_follow
+                    && !visited.contains(&link)
+                    && !to_visit.contains(&link)
+                    && trap_guard.admit(&link)
+                {
                     to_visit.push(link);
                     new_links_count += 1;
                 }
@@ -190,7 +250,10 @@ impl PageDiscovery {
             "[DISCOVERY] Discovery complete - found {} pages",
             discovered_pages.len()
         );
-        Ok(discovered_pages)
+        Ok(DiscoveryOutcome {
+            pages: discovered_pages,
+            interrupted: None,
+        })
     }
 
     pub fn extract_links(html: &str, base_url: &Url) -> Vec<String> {
@@ -207,61 +270,130 @@ impl PageDiscovery {
     }
 }
 
+/// Runs the site-level file checks (robots.txt, sitemap, favicon,
+/// security.txt, ...) once per job.
 pub struct ResourceChecker {
     spider: Arc<dyn SpiderAgent>,
+    registry: SiteCheckRegistry,
 }
 
 impl ResourceChecker {
     pub fn new(spider: Arc<dyn SpiderAgent>) -> Self {
-        Self { spider }
+        Self::with_registry(spider, SiteCheckRegistry::with_defaults())
     }
 
-    pub async fn check_robots_txt(&self, base_url_str: &str) -> Result<ResourceStatus> {
-        tracing::debug!("[RESOURCE] Checking robots.txt for {}", base_url_str);
-        self.check_resource(base_url_str, "robots.txt").await
+    pub fn with_registry(spider: Arc<dyn SpiderAgent>, registry: SiteCheckRegistry) -> Self {
+        Self { spider, registry }
     }
 
-    pub async fn check_sitemap_xml(&self, base_url_str: &str) -> Result<ResourceStatus> {
-        tracing::debug!("[RESOURCE] Checking sitemap.xml for {}", base_url_str);
-        self.check_resource(base_url_str, "sitemap.xml").await
-    }
+    /// One result per registered check, in registry order. Only an
+    /// unparseable `base_url_str` is an error; failed fetches are recorded
+    /// in the results.
+    pub async fn check_all(&self, base_url_str: &str) -> Result<Vec<SiteCheckResult>> {
+        let home = Url::parse(base_url_str)?.join("/")?;
+        tracing::debug!("[RESOURCE] Checking {} site files for {}", self.registry.len(), home);
+
+        // Declared files (icons, the manifest) are linked from the home page.
+        let home_html = match self.spider.get(home.as_str()).await {
+            Ok(response) if (200..300).contains(&response.status) => response.body,
+            Ok(response) => {
+                tracing::debug!("[RESOURCE] Home page answered {}: {}", response.status, home);
+                String::new()
+            }
+            Err(e) => {
+                tracing::debug!("[RESOURCE] Failed to fetch home page {}: {}", home, e);
+                String::new()
+            }
+        };
+
+        // `Html` isn't `Send`, so every check is located before the next await.
+        let plans: Vec<Vec<Url>> = {
+            let document = Html::parse_document(&home_html);
+            self.registry.iter().map(|check| check.locate(&home, &document)).collect()
+        };
 
-    pub fn check_ssl_certificate(&self, url_str: &str) -> bool {
-        let has_ssl = url_str.starts_with("https");
-        tracing::debug!("[RESOURCE] SSL check for {}: {}", url_str, has_ssl);
-        has_ssl
+        let mut results = Vec::with_capacity(plans.len());
+        for (check, urls) in self.registry.iter().zip(plans) {
+            results.push(self.run(check, &urls).await);
+        }
+        Ok(results)
     }
 
-    async fn check_resource(&self, base_url_str: &str, path: &str) -> Result<ResourceStatus> {
-        let base_url = Url::parse(base_url_str)?;
-        let resource_url = base_url.join(path)?;
-        tracing::trace!("[RESOURCE] Fetching: {}", resource_url);
-        let response = self.spider.get(resource_url.as_str()).await?;
+    /// Tries `urls` in order and validates the first answer that's the
+    /// file. An HTML page where a file was expected counts as missing.
+    async fn run(&self, check: &dyn SiteCheck, urls: &[Url]) -> SiteCheckResult {
+        let mut result = SiteCheckResult {
+            check_id: check.id().to_string(),
+            label: check.label().to_string(),
+            status: SiteCheckStatus::Missing,
+            url: urls.first().map(Url::to_string),
+            status_code: None,
+            problems: Vec::new(),
+        };
+        if urls.is_empty() {
+            result.problems.extend(check.not_declared());
+            return result;
+        }
 
-        let status = match response.status {
-            200 => {
-                tracing::debug!("[RESOURCE] Found: {}", resource_url);
-                ResourceStatus::Found(resource_url.to_string())
-            }
-            401 | 403 => {
-                tracing::debug!("[RESOURCE] Unauthorized: {}", resource_url);
-                ResourceStatus::Unauthorized(resource_url.to_string())
-            }
-            404 => {
-                tracing::debug!("[RESOURCE] Not found: {}", resource_url);
-                ResourceStatus::NotFound
-            }
-            status => {
-                tracing::debug!(
-                    "[RESOURCE] Unexpected status {} for: {}",
-                    status,
-                    resource_url
-                );
-                ResourceStatus::NotFound
+        let mut failed = 0;
+        for url in urls {
+            tracing::trace!("[RESOURCE] Fetching: {}", url);
+            let response = match self.spider.get(url.as_str()).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::debug!("[RESOURCE] Failed to fetch {}: {}", url, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            let status_code = Some(i64::from(response.status));
+
+            match response.status {
+                200..=299 => {
+                    let fetch = SiteFetch {
+                        url: &response.url,
+                        headers: &response.headers,
+                        body: &response.body,
+                    };
+                    if fetch.looks_like_html() {
+                        tracing::debug!("[RESOURCE] HTML page instead of {}: {}", check.label(), url);
+                        result.status_code = result.status_code.or(status_code);
+                        continue;
+                    }
+                    let problems = check.validate(&fetch);
+                    tracing::debug!("[RESOURCE] Found: {} ({} problems)", url, problems.len());
+                    return SiteCheckResult {
+                        status: if problems.is_empty() {
+                            SiteCheckStatus::Found
+                        } else {
+                            SiteCheckStatus::Invalid
+                        },
+                        url: Some(response.url),
+                        status_code,
+                        problems,
+                        ..result
+                    };
+                }
+                401 | 403 => {
+                    tracing::debug!("[RESOURCE] Unauthorized: {}", url);
+                    return SiteCheckResult {
+                        status: SiteCheckStatus::Unauthorized,
+                        url: Some(url.to_string()),
+                        status_code,
+                        ..result
+                    };
+                }
+                status => {
+                    tracing::debug!("[RESOURCE] Status {} for: {}", status, url);
+                    result.status_code = result.status_code.or(status_code);
+                }
             }
-        };
+        }
 
-        Ok(status)
+        if failed == urls.len() {
+            result.status = SiteCheckStatus::Error;
+        }
+        result
     }
 }
 
@@ -370,57 +502,183 @@ mod tests {
         assert!(links.is_empty());
     }
 
-    // ── SiteResources ────────────────────────────────────────────────────
-
-    #[test]
-    fn site_resources_constructor_assigns_fields() {
-        let r = SiteResources::new(true, false, true);
-        assert!(r.robots_txt());
-        assert!(!r.sitemap());
-        assert!(r.ssl());
-    }
-
-    #[test]
-    fn site_resources_all_present_requires_every_field_true() {
-        assert!(SiteResources::new(true, true, true).all_present());
-        assert!(!SiteResources::new(false, true, true).all_present());
-        assert!(!SiteResources::new(true, false, true).all_present());
-        assert!(!SiteResources::new(true, true, false).all_present());
-        assert!(!SiteResources::new(false, false, false).all_present());
-    }
-
-    #[test]
-    fn site_resources_equality_ignores_construction_path() {
-        // Two SiteResources with the same field values are equal
-        // regardless of how they were constructed.
-        let a = SiteResources::new(true, true, false);
-        let b = SiteResources::new(true, true, false);
-        assert_eq!(a, b);
-    }
-
     #[tokio::test]
-    async fn test_check_robots_txt_found() {
+    async fn check_all_validates_found_files_and_skips_html_answers() {
         let mut server = mockito::Server::new_async().await;
-        let _mock = server
+        let _home = server
+            .mock("GET", "/")
+            .with_status(200)
+            .with_header("content-type", "text/html")
+            .with_body(r#"<link rel="icon" href="/icon.png">"#)
+            .create_async()
+            .await;
+        let _robots = server
             .mock("GET", "/robots.txt")
             .with_status(200)
+            .with_header("content-type", "text/plain")
             .with_body("User-agent: *\nDisallow:")
             .create_async()
             .await;
+        let _icon = server
+            .mock("GET", "/icon.png")
+            .with_status(200)
+            .with_header("content-type", "text/plain")
+            .with_body("not an image")
+            .create_async()
+            .await;
+        // An app shell answering every path with its home page.
+        let _sitemap = server
+            .mock("GET", "/sitemap.xml")
+            .with_status(200)
+            .with_header("content-type", "text/html")
+            .with_body("<!doctype html><html></html>")
+            .create_async()
+            .await;
+        let _humans = server
+            .mock("GET", "/humans.txt")
+            .with_status(403)
+            .create_async()
+            .await;
 
+        let mut registry = SiteCheckRegistry::new();
+        for check in crate::checker::site::builtin::all() {
+            if ["robots_txt", "sitemap_xml", "favicon", "web_manifest", "humans_txt"]
+                .contains(&check.id())
+            {
+                registry.register(check);
+            }
+        }
         let spider = Spider::new_agent(ClientType::Standard).unwrap();
-        let checker = ResourceChecker::new(spider);
-        let base_url = Url::parse(&server.url()).unwrap();
+        let checker = ResourceChecker::with_registry(spider, registry);
+
+        let results = checker.check_all(&server.url()).await.unwrap();
+        let status = |id: &str| results.iter().find(|r| r.check_id == id).unwrap();
+
+        assert_eq!(status("robots_txt").status, SiteCheckStatus::Found);
+        assert_eq!(status("sitemap_xml").status, SiteCheckStatus::Missing);
+        assert_eq!(status("sitemap_xml").status_code, Some(200));
+        assert_eq!(status("favicon").status, SiteCheckStatus::Invalid);
+        assert!(status("favicon").url.as_deref().unwrap().ends_with("/icon.png"));
+        assert_eq!(status("web_manifest").status, SiteCheckStatus::Missing);
+        assert_eq!(status("web_manifest").url, None);
+        assert_eq!(status("humans_txt").status, SiteCheckStatus::Unauthorized);
+    }
 
-        let status = checker.check_robots_txt(base_url.as_str()).await.unwrap();
-        assert!(status.exists());
+    #[tokio::test]
+    async fn discover_from_checkpoints_on_cancel_and_resumes_from_frontier() {
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+        let _root = server
+            .mock("GET", "/")
+            .with_status(200)
+            .with_body(r#"<a href="/a">A</a><a href="/b">B</a>"#)
+            .create_async()
+            .await;
+        let _a = server
+            .mock("GET", "/a")
+            .with_status(200)
+            .with_body("<p>a</p>")
+            .create_async()
+            .await;
+        let _b = server
+            .mock("GET", "/b")
+            .with_status(200)
+            .with_body("<p>b</p>")
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let discovery = PageDiscovery::new(spider);
+        let start = format!("{base}/");
+
+        // Cancel as soon as the first page is counted; the loop notices
+        // on its next iteration and hands back the unfetched links.
+        let token = CancellationToken::new();
+        let first = discovery
+            .discover_from(&start, None, &CompiledUrlRules::default(), 10, 0, false, &token, |_| {
+                token.cancel()
+            })
+            .await
+            .unwrap();
+        assert_eq!(first.pages.len(), 1);
+        let frontier = first.interrupted.expect("cancelled run should checkpoint");
+        assert!(!frontier.discovery_complete);
+        assert_eq!(frontier.visited, vec![start.clone()]);
+        assert_eq!(frontier.to_visit.len(), 2);
+
+        let resumed = discovery
+            .discover_from(
+                &start,
+                Some(frontier),
+                &CompiledUrlRules::default(),
+                10,
+                0,
+                false,
+                &CancellationToken::new(),
+                |_| {},
+            )
+            .await
+            .unwrap();
+        assert!(resumed.interrupted.is_none());
+        let mut urls: Vec<_> = resumed.pages.iter().map(|p| p.url.clone()).collect();
+        urls.sort();
+        assert_eq!(urls, vec![format!("{base}/a"), format!("{base}/b")]);
     }
 
     #[tokio::test]
-    async fn test_check_ssl_certificate() {
+    async fn discover_from_normalizes_and_filters_links_with_url_rules() {
+        use crate::contexts::analysis::{QueryParamPolicy, UrlPattern, UrlRules};
+
+        let mut server = mockito::Server::new_async().await;
+        let base = server.url();
+        let _root = server
+            .mock("GET", "/")
+            .with_status(200)
+            .with_body(
+                r#"<a href="/a?utm_source=x">A</a><a href="/a">A again</a>
+                   <a href="/wp-admin/">Admin</a>"#,
+            )
+            .create_async()
+            .await;
+        let a = server
+            .mock("GET", "/a")
+            .with_status(200)
+            .with_body("<p>a</p>")
+            .expect(1)
+            .create_async()
+            .await;
+        let admin = server
+            .mock("GET", "/wp-admin/")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let rules = UrlRules {
+            exclude: vec![UrlPattern::Glob("/wp-admin/*".into())],
+            query_params: QueryParamPolicy::StripAll,
+            ..Default::default()
+        }
+        .compile()
+        .unwrap();
+
         let spider = Spider::new_agent(ClientType::Standard).unwrap();
-        let checker = ResourceChecker::new(spider);
-        assert!(checker.check_ssl_certificate("https://google.com"));
-        assert!(!checker.check_ssl_certificate("http://google.com"));
+        let outcome = PageDiscovery::new(spider)
+            .discover_from(
+                &format!("{base}/"),
+                None,
+                &rules,
+                10,
+                0,
+                false,
+                &CancellationToken::new(),
+                |_| {},
+            )
+            .await
+            .unwrap();
+
+        let urls: Vec<_> = outcome.pages.iter().map(|p| p.url.clone()).collect();
+        assert_eq!(urls, vec![format!("{base}/"), format!("{base}/a")]);
+        a.assert_async().await;
+        admin.assert_async().await;
     }
 }
@@ -1,8 +1,12 @@
-use crate::service::prompt::{build_prompt_from_blocks, load_persona, load_prompt_blocks};
+use std::sync::Arc;
+
+use crate::repository::{AiRepository, SettingsRepository};
+use crate::service::ai_backend::{generate_analysis, post, AiBackend};
 use crate::service::spider::SpiderAgent;
 #[cfg(test)]
 use crate::service::spider::{ClientType, Spider};
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use specta::Type;
@@ -74,76 +78,96 @@ pub struct GeminiRequest {
     pub tag_values: std::collections::HashMap<String, String>,
 }
 
-pub async fn generate_gemini_analysis(
-    ai_repo: std::sync::Arc<dyn crate::repository::AiRepository>,
-    settings_repo: std::sync::Arc<dyn crate::repository::SettingsRepository>,
-    request: GeminiRequest,
-    spider: std::sync::Arc<dyn SpiderAgent>,
-    api_base_url: Option<String>,
-) -> Result<String> {
-    // 1. Check cache first
-    if let Ok(Some(cached_insights)) = ai_repo.get_ai_insights(&request.analysis_id).await {
-        tracing::info!(
-            "Using cached AI insights for analysis {}",
-            request.analysis_id
-        );
-        return Ok(cached_insights);
-    }
+/// Google's Gemini API.
+pub struct GeminiBackend {
+    spider: Arc<dyn SpiderAgent>,
+    api_key: Option<String>,
+    base_url: String,
+}
 
-    // Get API key from database
-    let api_key = match settings_repo.get_setting("gemini_api_key").await? {
-        Some(key) if !key.is_empty() => key,
-        _ => {
-            anyhow::bail!("API_KEY_MISSING: Please configure your Gemini API key");
+impl GeminiBackend {
+    /// Without a key, [`AiBackend::generate`] fails with `API_KEY_MISSING`.
+    pub fn new(
+        spider: Arc<dyn SpiderAgent>,
+        api_key: Option<String>,
+        api_base_url: Option<String>,
+    ) -> Self {
+        Self {
+            spider,
+            api_key: api_key.filter(|key| !key.is_empty()),
+            base_url: api_base_url
+                .unwrap_or_else(|| "https://generativelanguage.googleapis.com".to_string()),
         }
-    };
-
-    let persona = load_persona(settings_repo.as_ref()).await?;
+    }
 
-    let blocks = load_prompt_blocks(settings_repo.as_ref()).await?;
+    /// Gemini takes the token limit as `generationConfig.maxOutputTokens`.
+    async fn complete(&self, prompt: &str, max_tokens: Option<usize>) -> Result<String> {
+        let Some(api_key) = &self.api_key else {
+            anyhow::bail!("API_KEY_MISSING: Please configure your Gemini API key");
+        };
+        let api_url = format!("{}{}?key={}", self.base_url, GEMINI_API_PATH, api_key);
 
-    let prompt = build_prompt_from_blocks(&persona, &blocks, &request);
+        let mut request_body = json!({
+            "contents": [{
+                "parts": [{
+                    "text": prompt
+                }]
+            }]
+        });
+        if let Some(max_tokens) = max_tokens {
+            request_body["generationConfig"] = json!({ "maxOutputTokens": max_tokens });
+        }
 
-    // Prepare API request
-    let base = api_base_url
-        .as_deref()
-        .unwrap_or("https://generativelanguage.googleapis.com");
-    let api_url = format!("{}{}?key={}", base, GEMINI_API_PATH, api_key);
+        let response_json =
+            post(self.spider.as_ref(), "Gemini", &api_url, &[], &request_body).await?;
 
-    let request_body = json!({
-        "contents": [{
-            "parts": [{
-                "text": prompt
-            }]
-        }]
-    });
+        // Extract text from response
+        response_json["candidates"][0]["content"]["parts"][0]["text"]
+            .as_str()
+            .map(str::to_string)
+            .context("Failed to extract text from Gemini response")
+    }
 
-    // Make API request
-    let response = spider
-        .post_json(&api_url, &request_body)
-        .await
-        .context("Failed to send request to Gemini API")?;
+    /// The backend for the key saved in Settings → AI.
+    pub async fn from_settings(
+        settings_repo: &dyn SettingsRepository,
+        spider: Arc<dyn SpiderAgent>,
+        api_base_url: Option<String>,
+    ) -> Result<Self> {
+        let api_key = settings_repo.get_setting("gemini_api_key").await?;
+        Ok(Self::new(spider, api_key, api_base_url))
+    }
+}
 
-    if response.status != 200 {
-        anyhow::bail!("Gemini API error {}: {}", response.status, response.body);
+#[async_trait]
+impl AiBackend for GeminiBackend {
+    fn name(&self) -> &'static str {
+        "Gemini"
     }
 
-    // Parse response
-    let response_json: serde_json::Value =
-        serde_json::from_str(&response.body).context("Failed to parse Gemini API response")?;
+    fn is_configured(&self) -> bool {
+        self.api_key.is_some()
+    }
 
-    // Extract text from response
-    let text = response_json["candidates"][0]["content"]["parts"][0]["text"]
-        .as_str()
-        .context("Failed to extract text from Gemini response")?
-        .to_string();
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        self.complete(prompt, None).await
+    }
 
-    // 2. Save to cache
-    if let Err(e) = ai_repo.save_ai_insights(&request.analysis_id, &text).await {
-        tracing::error!("Failed to save AI insights to cache: {}", e);
+    async fn generate_section(&self, prompt: &str, max_tokens: usize) -> Result<String> {
+        self.complete(prompt, Some(max_tokens)).await
     }
+}
 
-    Ok(text)
+pub async fn generate_gemini_analysis(
+    ai_repo: Arc<dyn AiRepository>,
+    settings_repo: Arc<dyn SettingsRepository>,
+    request: GeminiRequest,
+    spider: Arc<dyn SpiderAgent>,
+    api_base_url: Option<String>,
+) -> Result<String> {
+    let backend =
+        GeminiBackend::from_settings(settings_repo.as_ref(), spider, api_base_url).await?;
+    generate_analysis(ai_repo, settings_repo, &request, &backend).await
 }
 
 
@@ -231,6 +255,31 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn test_gemini_caps_a_section_at_its_token_budget() {
+        use crate::test_utils::mocks;
+
+        let mut server = mockito::Server::new_async().await;
+        let api_path = format!("{}?key=test_key", GEMINI_API_PATH);
+        let mock = server
+            .mock("POST", api_path.as_str())
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "generationConfig": { "maxOutputTokens": 320 }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(mocks::gemini_response("Next steps"))
+            .create_async()
+            .await;
+
+        let spider = Spider::new_agent(ClientType::Standard).unwrap();
+        let backend = GeminiBackend::new(spider, Some("test_key".into()), Some(server.url()));
+
+        let text = backend.generate_section("Plan", 320).await.unwrap();
+        assert_eq!(text, "Next steps");
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn test_gemini_missing_api_key_returns_error() {
         use crate::test_utils::fixtures;
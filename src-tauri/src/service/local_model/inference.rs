@@ -1,9 +1,18 @@
 use std::num::NonZeroU32;
-use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use llama_cpp_2::context::LlamaContext;
+use llama_cpp_2::model::LlamaModel;
+use tokio::sync::oneshot;
+
+/// How long the loaded model stays in memory after its last request.
+/// Reports ask for several sections in a row, and reloading a model
+/// means reading gigabytes from disk.
+const MODEL_IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
 
 pub struct InferenceRequest {
     pub model_path: PathBuf,
@@ -15,14 +24,60 @@ pub struct InferenceRequest {
 #[async_trait]
 pub trait InferenceEngine: Send + Sync {
     async fn infer(&self, request: InferenceRequest) -> Result<String>;
+
+    /// Free the loaded model, for engines that keep one loaded. Returns
+    /// once it's gone, so its file can be deleted.
+    async fn unload(&self) {}
 }
 
 /// Llama.cpp inference engine backed by the `llama-cpp-2` crate.
-pub struct LlamaInferenceEngine;
+///
+/// Requests run one at a time on a worker thread, which keeps the last
+/// model loaded along with a context for it. The model is dropped after
+/// [`MODEL_IDLE_TIMEOUT`] without requests, when a request asks for a
+/// different model, or on [`unload`](InferenceEngine::unload).
+pub struct LlamaInferenceEngine {
+    idle_timeout: Duration,
+    /// Queue of the worker thread; started on the first request.
+    worker: Mutex<Option<mpsc::Sender<Command>>>,
+}
+
+enum Command {
+    Infer(InferenceRequest, oneshot::Sender<Result<String>>),
+    Unload(oneshot::Sender<()>),
+}
 
 impl LlamaInferenceEngine {
     pub fn new() -> Self {
-        Self
+        Self::with_idle_timeout(MODEL_IDLE_TIMEOUT)
+    }
+
+    pub fn with_idle_timeout(idle_timeout: Duration) -> Self {
+        Self { idle_timeout, worker: Mutex::new(None) }
+    }
+
+    /// Queue `command` on the worker, starting a new worker if there's
+    /// none yet or the last one stopped.
+    fn send(&self, mut command: Command) -> Result<()> {
+        let mut worker = self.worker.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(queue) = worker.as_ref() {
+            match queue.send(command) {
+                Ok(()) => return Ok(()),
+                Err(mpsc::SendError(unsent)) => command = unsent,
+            }
+        }
+
+        let (queue, commands) = mpsc::channel();
+        let idle_timeout = self.idle_timeout;
+        std::thread::Builder::new()
+            .name("llama-inference".to_string())
+            .spawn(move || run_worker(commands, idle_timeout))
+            .map_err(|e| anyhow::anyhow!("Failed to start inference worker: {e}"))?;
+        queue
+            .send(command)
+            .map_err(|_| anyhow::anyhow!("Inference worker stopped unexpectedly"))?;
+        *worker = Some(queue);
+        Ok(())
     }
 }
 
@@ -35,9 +90,21 @@ impl Default for LlamaInferenceEngine {
 #[async_trait]
 impl InferenceEngine for LlamaInferenceEngine {
     async fn infer(&self, request: InferenceRequest) -> Result<String> {
-        tokio::task::spawn_blocking(move || run_inference(request))
+        let (reply, answer) = oneshot::channel();
+        self.send(Command::Infer(request, reply))?;
+        answer
             .await
-            .map_err(|e| anyhow::anyhow!("Inference task panicked: {e}"))?
+            .map_err(|_| anyhow::anyhow!("Inference worker stopped unexpectedly"))?
+    }
+
+    async fn unload(&self) {
+        if self.worker.lock().unwrap_or_else(|e| e.into_inner()).is_none() {
+            return;
+        }
+        let (done, unloaded) = oneshot::channel();
+        if self.send(Command::Unload(done)).is_ok() {
+            let _ = unloaded.await;
+        }
     }
 }
 
@@ -59,9 +126,122 @@ fn backend() -> Result<&'static llama_cpp_2::llama_backend::LlamaBackend> {
         .map_err(|e| anyhow::anyhow!("{e}"))
 }
 
-// ── Synchronous inference (runs on a blocking thread) ────────────────────────
+// ── Worker thread ────────────────────────────────────────────────────────────
+
+/// Serve commands until the engine is dropped. Each pass of the loop
+/// loads one model and serves it until [`serve_model`] lets it go.
+fn run_worker(commands: mpsc::Receiver<Command>, idle_timeout: Duration) {
+    let mut next = None;
+    loop {
+        let command = match next.take() {
+            Some(command) => command,
+            None => match commands.recv() {
+                Ok(command) => command,
+                Err(_) => return,
+            },
+        };
+        match command {
+            Command::Infer(request, reply) => {
+                next = serve_model(&commands, idle_timeout, request, reply);
+            }
+            // By now the model `serve_model` held has been dropped.
+            Command::Unload(done) => {
+                let _ = done.send(());
+            }
+        }
+    }
+}
+
+/// Load the model for `request` and answer it, then keep answering
+/// requests for the same model. Returns the command that needs another
+/// model or an unload, or `None` once the model sat idle or the engine
+/// was dropped. The model is dropped on return either way.
+fn serve_model(
+    commands: &mpsc::Receiver<Command>,
+    idle_timeout: Duration,
+    request: InferenceRequest,
+    reply: oneshot::Sender<Result<String>>,
+) -> Option<Command> {
+    let model_path = request.model_path.clone();
+    let model = match load_model(&model_path) {
+        Ok(model) => model,
+        Err(e) => {
+            let _ = reply.send(Err(e));
+            return None;
+        }
+    };
+
+    // One context serves every request for this model; it's only
+    // rebuilt after a request fails part-way through.
+    let mut ctx: Option<LlamaContext<'_>> = None;
+    let mut pending = Some((request, reply));
+    loop {
+        let (request, reply) = match pending.take() {
+            Some(pending) => pending,
+            None => match commands.recv_timeout(idle_timeout) {
+                Ok(Command::Infer(request, reply)) if request.model_path == model_path => {
+                    (request, reply)
+                }
+                Ok(command) => return Some(command),
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    tracing::info!(
+                        "[LLAMA] unloading {} after {}s idle",
+                        model_path.display(),
+                        idle_timeout.as_secs()
+                    );
+                    return None;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => return None,
+            },
+        };
+
+        let mut context = match ctx.take() {
+            Some(context) => context,
+            None => match new_context(&model) {
+                Ok(context) => context,
+                Err(e) => {
+                    let _ = reply.send(Err(e));
+                    continue;
+                }
+            },
+        };
+        // Stop generating once nobody is waiting for the answer, e.g.
+        // after a report section timed out.
+        let result = run_inference(&model, &mut context, &request, &|| reply.is_closed());
+        if result.is_ok() {
+            ctx = Some(context);
+        }
+        let _ = reply.send(result);
+    }
+}
+
+fn load_model(model_path: &Path) -> Result<LlamaModel> {
+    use llama_cpp_2::model::params::LlamaModelParams;
+
+    tracing::info!("[LLAMA] loading model {}", model_path.display());
+    let model_params = LlamaModelParams::default();
+    LlamaModel::load_from_file(backend()?, model_path, &model_params)
+        .map_err(|e| anyhow::anyhow!("Failed to load model: {e}"))
+}
+
+fn new_context(model: &LlamaModel) -> Result<LlamaContext<'_>> {
+    use llama_cpp_2::context::params::LlamaContextParams;
 
-fn run_inference(req: InferenceRequest) -> Result<String> {
+    let ctx_params = LlamaContextParams::default()
+        .with_n_ctx(NonZeroU32::new(2048));
+    model
+        .new_context(backend()?, ctx_params)
+        .map_err(|e| anyhow::anyhow!("Failed to create context: {e}"))
+}
+
+// ── Synchronous inference (runs on the worker thread) ────────────────────────
+
+fn run_inference(
+    model: &LlamaModel,
+    ctx: &mut LlamaContext<'_>,
+    req: &InferenceRequest,
+    cancelled: &dyn Fn() -> bool,
+) -> Result<String> {
     tracing::info!(
         "[LLAMA] inference start — model: {}, prompt len: {} chars, max_tokens: {}, temp: {:.2}",
         req.model_path.display(),
@@ -74,23 +254,13 @@ fn run_inference(req: InferenceRequest) -> Result<String> {
         &req.prompt[..req.prompt.len().min(200)],
         if req.prompt.len() > 200 { " […]" } else { "" }
     );
-    use llama_cpp_2::context::params::LlamaContextParams;
     use llama_cpp_2::llama_batch::LlamaBatch;
-    use llama_cpp_2::model::params::LlamaModelParams;
-    use llama_cpp_2::model::{AddBos, LlamaModel};
+    use llama_cpp_2::model::AddBos;
     use llama_cpp_2::sampling::LlamaSampler;
 
-    let backend = backend()?;
-
-    let model_params = LlamaModelParams::default();
-    let model = LlamaModel::load_from_file(backend, &req.model_path, &model_params)
-        .map_err(|e| anyhow::anyhow!("Failed to load model: {e}"))?;
-
-    let ctx_params = LlamaContextParams::default()
-        .with_n_ctx(NonZeroU32::new(2048));
-    let mut ctx = model
-        .new_context(backend, ctx_params)
-        .map_err(|e| anyhow::anyhow!("Failed to create context: {e}"))?;
+    // Start from an empty cache: the context may have served an earlier
+    // request.
+    ctx.clear_kv_cache();
 
     // Tokenize the prompt
     let tokens = model
@@ -135,7 +305,12 @@ fn run_inference(req: InferenceRequest) -> Result<String> {
     let mut n_pos = n_prompt as i32;
 
     for _ in 0..req.max_tokens {
-        let token = sampler.sample(&ctx, -1);
+        if cancelled() {
+            tracing::info!("[LLAMA] request abandoned, stopping at {} chars", output.len());
+            break;
+        }
+
+        let token = sampler.sample(ctx, -1);
 
         if model.is_eog_token(token) {
             break;
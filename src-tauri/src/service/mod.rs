@@ -1,24 +1,39 @@
+pub mod ai_backend;
 pub mod auditor;
+pub mod cloaking;
+pub mod delivery;
 pub mod discovery;
 pub mod error;
 pub mod gemini;
 pub mod hardware;
 pub mod licensing;
 pub mod local_model;
+pub mod origins;
+pub mod page_weight;
 pub mod processor;
 pub mod prompt;
+pub mod soft_404;
 pub mod spider;
+pub mod tls;
 
 pub use error::{ServiceError, ServiceResult};
 
 #[cfg(test)]
 mod tests;
 
+pub use ai_backend::{
+    AiBackend, OllamaBackend, OllamaSettings, OpenAiCompatibleBackend, OpenAiCompatibleSettings,
+};
 pub use auditor::{AuditMode, Auditor, DeepAuditor, LightAuditor};
+pub use delivery::DeliveryProbe;
 pub use discovery::{PageDiscovery, ResourceChecker};
-pub use gemini::{generate_gemini_analysis, GeminiRequest};
+pub use gemini::{generate_gemini_analysis, GeminiBackend, GeminiRequest};
+pub use origins::{OriginProbe, OriginReport};
+pub use page_weight::PageWeigher;
 pub use processor::{
     AnalyzerService, Crawler, JobCanceler, JobProcessor, JobQueue, ProgressReporter,
 };
 pub use prompt::{build_prompt_from_blocks, DEFAULT_PERSONA};
+pub use soft_404::{NotFoundProbe, NotFoundProfile};
 pub use spider::Spider;
+pub use tls::TlsInspector;
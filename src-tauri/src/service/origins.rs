@@ -0,0 +1,636 @@
+//! Host and protocol consistency: whether every way of typing the site's
+//! address ends up at one canonical origin.
+//!
+//! At job start an [`OriginProbe`] requests the home page over http and
+//! https, with and without `www.`, plus one page with and without its
+//! trailing slash, following each redirect by hand. The resulting
+//! [`OriginReport`] flags variants that don't reach the canonical origin
+//! with a single permanent redirect, the site being served at more than
+//! one origin, and (once the crawl is done) internal links that point at
+//! an origin other than the canonical one.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use scraper::{Html, Selector};
+use url::{Host, Url};
+
+use crate::contexts::analysis::{
+    extract_root_domain, IssueBuilder, IssueSeverity, Link, NewIssue,
+};
+use crate::service::spider::SpiderAgent;
+
+/// Redirects followed per variant before giving up on it.
+const MAX_HOPS: usize = 5;
+
+/// URLs listed in an issue's details.
+const MAX_LISTED: usize = 5;
+
+/// One response in a redirect chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hop {
+    pub url: String,
+    pub status: u16,
+}
+
+impl Hop {
+    fn is_redirect(&self) -> bool {
+        (300..400).contains(&self.status)
+    }
+
+    fn is_permanent(&self) -> bool {
+        matches!(self.status, 301 | 308)
+    }
+}
+
+/// Every response on the way from one requested URL to where it ends up.
+#[derive(Debug, Clone)]
+pub struct Trace {
+    pub requested: String,
+    pub hops: Vec<Hop>,
+    /// Why the chain stopped early: a failed request, a loop, or too many
+    /// redirects.
+    pub failure: Option<String>,
+}
+
+impl Trace {
+    /// The response the chain ended with, when it ended on a page.
+    fn landing(&self) -> Option<&Hop> {
+        if self.failure.is_some() {
+            return None;
+        }
+        self.hops.last().filter(|hop| !hop.is_redirect())
+    }
+
+    fn landed_ok(&self) -> bool {
+        self.landing().is_some_and(|hop| (200..300).contains(&hop.status))
+    }
+
+    /// Answered 2xx at the requested URL, without redirecting.
+    fn served(&self) -> bool {
+        self.hops.len() == 1 && self.landed_ok()
+    }
+
+    fn redirects(&self) -> impl Iterator<Item = &Hop> {
+        self.hops.iter().filter(|hop| hop.is_redirect())
+    }
+
+    fn describe(&self) -> String {
+        let mut text = self.requested.clone();
+        for hop in &self.hops {
+            if hop.url != self.requested {
+                text.push_str(&format!(" → {}", hop.url));
+            }
+            text.push_str(&format!(" ({})", hop.status));
+        }
+        if let Some(failure) = &self.failure {
+            text.push_str(&format!(" → {failure}"));
+        }
+        text
+    }
+}
+
+/// Where the site's address variants end up.
+#[derive(Debug, Clone)]
+pub struct OriginReport {
+    /// Origin the site settles on, e.g. `https://www.example.com`.
+    canonical: String,
+    variants: Vec<Trace>,
+    /// One page requested with and without its trailing slash.
+    slash: Vec<Trace>,
+}
+
+impl OriginReport {
+    /// The origin the job's own start URL lands on, or failing that the
+    /// one most variants land on. `None` when no variant reached a page.
+    pub fn new(start_url: &str, variants: Vec<Trace>, slash: Vec<Trace>) -> Option<Self> {
+        let start = Url::parse(start_url).ok().map(|url| origin_of(&url));
+        let landed: Vec<(String, &Trace)> = variants
+            .iter()
+            .filter(|trace| trace.landed_ok())
+            .filter_map(|trace| Some((hop_origin(trace.hops.last()?)?, trace)))
+            .collect();
+
+        let own = landed
+            .iter()
+            .find(|(_, trace)| Url::parse(&trace.requested).ok().map(|url| origin_of(&url)) == start)
+            .map(|(origin, _)| origin.clone());
+        let canonical = own.or_else(|| {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for (origin, _) in &landed {
+                *counts.entry(origin.as_str()).or_default() += 1;
+            }
+            counts
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+                .map(|(origin, _)| origin.to_string())
+        })?;
+
+        Some(Self { canonical, variants, slash })
+    }
+
+    pub fn canonical(&self) -> &str {
+        &self.canonical
+    }
+
+    /// Site-level issues for the address variants (no page attached).
+    pub fn site_issues(&self, job_id: &str) -> Vec<NewIssue> {
+        let mut issues = Vec::new();
+
+        // Variants that answer without redirecting, one per origin.
+        let mut served: Vec<String> = self
+            .variants
+            .iter()
+            .filter(|trace| trace.served())
+            .filter_map(|trace| hop_origin(&trace.hops[0]))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if served.len() > 1 {
+            served.sort_by_key(|origin| *origin != self.canonical);
+            issues.push(
+                IssueBuilder::new(
+                    job_id.to_string(),
+                    "duplicate_origins".to_string(),
+                    IssueSeverity::Critical,
+                    format!("The site is served at {} origins without redirecting", served.len()),
+                )
+                .details(format!(
+                    "Every page exists at each of {}, splitting links and ranking signals \
+                     between copies. Redirect the others to {} with a 301.",
+                    served.join(", "),
+                    self.canonical
+                ))
+                .build(),
+            );
+        }
+
+        let mut astray = Vec::new();
+        let mut temporary = Vec::new();
+        let mut chained = Vec::new();
+        for trace in &self.variants {
+            if trace.served() {
+                continue;
+            }
+            let reaches = trace.landed_ok()
+                && trace.hops.last().and_then(hop_origin).as_deref() == Some(self.canonical.as_str());
+            if !reaches {
+                astray.push(trace.describe());
+                continue;
+            }
+            judge_redirects(trace, &mut temporary, &mut chained);
+        }
+
+        if let [first, second] = self.slash.as_slice() {
+            if first.served() && second.served() {
+                issues.push(
+                    IssueBuilder::new(
+                        job_id.to_string(),
+                        "trailing_slash_duplicate".to_string(),
+                        IssueSeverity::Warning,
+                        format!("{} is served both with and without a trailing slash", second.requested),
+                    )
+                    .details(format!(
+                        "{} and {} both answer 200. Pick one form and 301 the other to it.",
+                        first.requested, second.requested
+                    ))
+                    .build(),
+                );
+            } else {
+                for trace in [first, second] {
+                    if trace.landed_ok() && !trace.served() {
+                        judge_redirects(trace, &mut temporary, &mut chained);
+                    }
+                }
+            }
+        }
+
+        if !astray.is_empty() {
+            issues.push(
+                IssueBuilder::new(
+                    job_id.to_string(),
+                    "inconsistent_origin_variant".to_string(),
+                    IssueSeverity::Warning,
+                    format!(
+                        "{} of {} address variants don't lead to {}",
+                        astray.len(),
+                        self.variants.len(),
+                        self.canonical
+                    ),
+                )
+                .details(format!(
+                    "Visitors and crawlers typing these addresses don't reach the site. \
+                     Redirect each to {} with a 301. Requests made: {}",
+                    self.canonical,
+                    list(&astray)
+                ))
+                .build(),
+            );
+        }
+        if !temporary.is_empty() {
+            issues.push(
+                IssueBuilder::new(
+                    job_id.to_string(),
+                    "temporary_origin_redirect".to_string(),
+                    IssueSeverity::Warning,
+                    format!("{} canonicalising redirects are temporary", temporary.len()),
+                )
+                .details(format!(
+                    "A 302 or 307 tells search engines to keep the old address indexed. \
+                     Use 301 or 308 instead. Requests made: {}",
+                    list(&temporary)
+                ))
+                .build(),
+            );
+        }
+        if !chained.is_empty() {
+            issues.push(
+                IssueBuilder::new(
+                    job_id.to_string(),
+                    "origin_redirect_chain".to_string(),
+                    IssueSeverity::Info,
+                    format!("{} address variants take more than one redirect", chained.len()),
+                )
+                .details(format!(
+                    "Each extra hop costs a round trip. Redirect straight to the final \
+                     address. Requests made: {}",
+                    list(&chained)
+                ))
+                .build(),
+            );
+        }
+        issues
+    }
+
+    /// A site-level issue for internal links whose origin isn't the
+    /// canonical one, e.g. `http://` or bare-domain links on a
+    /// `https://www.` site.
+    pub fn link_issues(&self, job_id: &str, links: &[Link]) -> Vec<NewIssue> {
+        let Ok(canonical) = Url::parse(&self.canonical) else {
+            return Vec::new();
+        };
+        let site = canonical.host_str().map(bare_host);
+
+        let mut targets = BTreeSet::new();
+        let mut sources = BTreeSet::new();
+        let mut count = 0;
+        for link in links {
+            let Ok(target) = Url::parse(&link.target_url) else {
+                continue;
+            };
+            if target.host_str().map(bare_host) != site || origin_of(&target) == self.canonical {
+                continue;
+            }
+            count += 1;
+            targets.insert(link.target_url.clone());
+            sources.insert(link.source_page_id.as_str());
+        }
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let targets: Vec<String> = targets.into_iter().collect();
+        vec![IssueBuilder::new(
+            job_id.to_string(),
+            "non_canonical_internal_links".to_string(),
+            IssueSeverity::Warning,
+            format!(
+                "{count} internal links on {} pages point at an origin other than {}",
+                sources.len(),
+                self.canonical
+            ),
+        )
+        .details(format!(
+            "Each one sends visitors and crawlers through a redirect or to a \
+             duplicate. Link to {} directly. Examples: {}",
+            self.canonical,
+            list(&targets)
+        ))
+        .build()]
+    }
+
+}
+
+/// Requests the site's address variants without following redirects.
+pub struct OriginProbe {
+    spider: Arc<dyn SpiderAgent>,
+}
+
+impl OriginProbe {
+    pub fn new(spider: Arc<dyn SpiderAgent>) -> Self {
+        Self { spider }
+    }
+
+    /// Probe the site `start_url` belongs to. `None` for addresses with an
+    /// explicit port or an IP, which have no variants worth checking, and
+    /// when no variant reached a page.
+    pub async fn probe(&self, start_url: &str) -> Option<OriginReport> {
+        let start = Url::parse(start_url).ok()?;
+        let mut variants = Vec::new();
+        let mut home_body = None;
+        for url in variant_urls(&start) {
+            let own = origin_of(&url) == origin_of(&start);
+            let (trace, body) = self.trace(&url).await;
+            if own && trace.landed_ok() {
+                home_body = body;
+            }
+            variants.push(trace);
+        }
+
+        let mut report = OriginReport::new(start_url, variants, Vec::new())?;
+        let canonical = Url::parse(report.canonical()).ok()?;
+        if let Some((with, without)) = slash_pair(&start, &canonical, home_body.as_deref()) {
+            report.slash = vec![self.trace(&with).await.0, self.trace(&without).await.0];
+        }
+
+        tracing::debug!(
+            "[ORIGINS] {}: canonical {}, {} variants, {} slash probes",
+            start_url,
+            report.canonical,
+            report.variants.len(),
+            report.slash.len()
+        );
+        Some(report)
+    }
+
+    /// Follow `url` one redirect at a time. Returns the chain and the body
+    /// of the page it ended on.
+    async fn trace(&self, url: &Url) -> (Trace, Option<String>) {
+        let mut trace = Trace {
+            requested: url.to_string(),
+            hops: Vec::new(),
+            failure: None,
+        };
+        let mut current = url.clone();
+        loop {
+            let response = match self.spider.get(current.as_str()).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::debug!("[ORIGINS] Request to {} failed: {:#}", current, e);
+                    trace.failure = Some("request failed".to_string());
+                    return (trace, None);
+                }
+            };
+            trace.hops.push(Hop {
+                url: current.to_string(),
+                status: response.status,
+            });
+            if !(300..400).contains(&response.status) {
+                return (trace, Some(response.body));
+            }
+
+            let Some(next) = response
+                .headers
+                .get("location")
+                .and_then(|location| current.join(location.trim()).ok())
+            else {
+                trace.failure = Some("redirect without Location".to_string());
+                return (trace, None);
+            };
+            if trace.hops.iter().any(|hop| hop.url == next.as_str()) {
+                trace.failure = Some(format!("redirect loop at {next}"));
+                return (trace, None);
+            }
+            if trace.hops.len() > MAX_HOPS {
+                trace.failure = Some("too many redirects".to_string());
+                return (trace, None);
+            }
+            current = next;
+        }
+    }
+}
+
+/// File a redirect chain that reaches its page under temporary
+/// redirects or more than one hop.
+fn judge_redirects(trace: &Trace, temporary: &mut Vec<String>, chained: &mut Vec<String>) {
+    if trace.redirects().any(|hop| !hop.is_permanent()) {
+        temporary.push(trace.describe());
+    }
+    if trace.redirects().count() > 1 {
+        chained.push(trace.describe());
+    }
+}
+
+/// The home page over https and http, at the bare domain and at `www.`.
+/// Subdomains other than `www` only get the protocol variants.
+fn variant_urls(start: &Url) -> Vec<Url> {
+    if start.port().is_some() {
+        return Vec::new();
+    }
+    let Some(Host::Domain(host)) = start.host() else {
+        return Vec::new();
+    };
+    if !host.contains('.') {
+        return Vec::new();
+    }
+
+    let bare = bare_host(host);
+    let hosts = if extract_root_domain(start.as_str()).as_deref() == Some(bare) {
+        vec![bare.to_string(), format!("www.{bare}")]
+    } else {
+        vec![host.to_string()]
+    };
+    ["https", "http"]
+        .iter()
+        .flat_map(|scheme| hosts.iter().map(move |host| format!("{scheme}://{host}/")))
+        .filter_map(|url| Url::parse(&url).ok())
+        .collect()
+}
+
+/// A page on the canonical origin with and without its trailing slash:
+/// the start page when it isn't the home page, else the first such link
+/// on the home page. Files (paths with an extension) and query strings
+/// are left out.
+fn slash_pair(start: &Url, canonical: &Url, home: Option<&str>) -> Option<(Url, Url)> {
+    let eligible = |url: &Url| {
+        url.path() != "/"
+            && url.query().is_none()
+            && !url.path().trim_end_matches('/').rsplit('/').next().unwrap_or_default().contains('.')
+    };
+
+    let path = if eligible(start) {
+        start.path().to_string()
+    } else {
+        let document = Html::parse_document(home?);
+        let selector = Selector::parse("a[href]").ok()?;
+        document
+            .select(&selector)
+            .filter_map(|a| a.value().attr("href"))
+            .filter_map(|href| canonical.join(href.trim()).ok())
+            .find(|url| origin_of(url) == origin_of(canonical) && eligible(url))?
+            .path()
+            .to_string()
+    };
+
+    let trimmed = path.trim_end_matches('/');
+    let with = canonical.join(&format!("{trimmed}/")).ok()?;
+    let without = canonical.join(trimmed).ok()?;
+    Some((with, without))
+}
+
+/// `scheme://host[:port]`, without a trailing slash.
+fn origin_of(url: &Url) -> String {
+    url.origin().ascii_serialization()
+}
+
+fn hop_origin(hop: &Hop) -> Option<String> {
+    Url::parse(&hop.url).ok().map(|url| origin_of(&url))
+}
+
+fn bare_host(host: &str) -> &str {
+    host.strip_prefix("www.").unwrap_or(host)
+}
+
+fn list(items: &[String]) -> String {
+    let mut listed = items[..items.len().min(MAX_LISTED)].join("; ");
+    if items.len() > MAX_LISTED {
+        listed.push_str(&format!(" and {} more", items.len() - MAX_LISTED));
+    }
+    listed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::analysis::LinkType;
+
+    fn trace(requested: &str, hops: &[(&str, u16)]) -> Trace {
+        Trace {
+            requested: requested.into(),
+            hops: hops
+                .iter()
+                .map(|(url, status)| Hop { url: (*url).into(), status: *status })
+                .collect(),
+            failure: None,
+        }
+    }
+
+    fn tidy_site() -> Vec<Trace> {
+        vec![
+            trace("https://example.com/", &[("https://example.com/", 301), ("https://www.example.com/", 200)]),
+            trace("https://www.example.com/", &[("https://www.example.com/", 200)]),
+            trace("http://example.com/", &[("http://example.com/", 301), ("https://www.example.com/", 200)]),
+            trace("http://www.example.com/", &[("http://www.example.com/", 301), ("https://www.example.com/", 200)]),
+        ]
+    }
+
+    fn types(issues: &[NewIssue]) -> Vec<&str> {
+        issues.iter().map(|issue| issue.issue_type.as_str()).collect()
+    }
+
+    #[test]
+    fn one_permanent_hop_per_variant_is_clean() {
+        let report = OriginReport::new("https://example.com/", tidy_site(), Vec::new()).unwrap();
+        assert_eq!(report.canonical(), "https://www.example.com");
+        assert!(report.site_issues("j1").is_empty());
+    }
+
+    #[test]
+    fn origins_serving_the_same_site_are_duplicates() {
+        let mut variants = tidy_site();
+        variants[0] = trace("https://example.com/", &[("https://example.com/", 200)]);
+        let report = OriginReport::new("https://www.example.com/", variants, Vec::new()).unwrap();
+
+        let issues = report.site_issues("j1");
+        assert_eq!(types(&issues), ["duplicate_origins"]);
+        assert_eq!(issues[0].severity, IssueSeverity::Critical);
+        assert!(issues[0].details.as_deref().unwrap().starts_with("Every page exists at each of https://www.example.com, https://example.com"));
+    }
+
+    #[test]
+    fn temporary_chained_and_broken_variants_are_reported() {
+        let mut variants = tidy_site();
+        variants[2] = trace(
+            "http://example.com/",
+            &[("http://example.com/", 302), ("https://example.com/", 301), ("https://www.example.com/", 200)],
+        );
+        variants[3] = Trace {
+            failure: Some("request failed".into()),
+            ..trace("http://www.example.com/", &[])
+        };
+        let report = OriginReport::new("https://www.example.com/", variants, Vec::new()).unwrap();
+
+        let issues = report.site_issues("j1");
+        assert_eq!(
+            types(&issues),
+            ["inconsistent_origin_variant", "temporary_origin_redirect", "origin_redirect_chain"]
+        );
+        assert!(issues[0].details.as_deref().unwrap().contains("http://www.example.com/ → request failed"));
+        assert!(issues[1].details.as_deref().unwrap().contains("(302)"));
+    }
+
+    #[test]
+    fn both_slash_forms_answering_is_a_duplicate() {
+        let slash = vec![
+            trace("https://www.example.com/shop/", &[("https://www.example.com/shop/", 200)]),
+            trace("https://www.example.com/shop", &[("https://www.example.com/shop", 200)]),
+        ];
+        let report = OriginReport::new("https://www.example.com/", tidy_site(), slash).unwrap();
+        assert_eq!(types(&report.site_issues("j1")), ["trailing_slash_duplicate"]);
+    }
+
+    #[test]
+    fn nothing_reachable_means_no_report() {
+        let variants = vec![trace("https://example.com/", &[("https://example.com/", 503)])];
+        assert!(OriginReport::new("https://example.com/", variants, Vec::new()).is_none());
+    }
+
+    #[test]
+    fn links_to_other_origins_of_the_site_are_flagged() {
+        let report = OriginReport::new("https://www.example.com/", tidy_site(), Vec::new()).unwrap();
+        let link = |source: &str, target: &str| Link {
+            id: "l".into(),
+            job_id: "j1".into(),
+            source_page_id: source.into(),
+            target_url: target.into(),
+            link_text: None,
+            link_type: LinkType::Internal,
+            status_code: None,
+        };
+        let links = [
+            link("p1", "https://www.example.com/about"),
+            link("p1", "http://www.example.com/shop"),
+            link("p2", "https://example.com/shop"),
+            link("p2", "https://blog.example.com/"),
+            link("p2", "https://other.com/"),
+        ];
+
+        let issues = report.link_issues("j1", &links);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.starts_with("2 internal links on 2 pages"));
+    }
+
+    #[test]
+    fn variants_cover_both_hosts_and_protocols() {
+        let urls: Vec<String> = variant_urls(&Url::parse("https://www.example.co.uk/shop").unwrap())
+            .iter()
+            .map(Url::to_string)
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.co.uk/",
+                "https://www.example.co.uk/",
+                "http://example.co.uk/",
+                "http://www.example.co.uk/",
+            ]
+        );
+        assert_eq!(variant_urls(&Url::parse("https://blog.example.com/").unwrap()).len(), 2);
+        assert!(variant_urls(&Url::parse("http://localhost:3000/").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn slash_pair_uses_a_home_page_link_for_root_starts() {
+        let canonical = Url::parse("https://www.example.com/").unwrap();
+        let home = r#"<a href="/">Home</a><a href="/logo.png">x</a><a href="https://other.com/a">x</a><a href="/shop/">Shop</a>"#;
+
+        let (with, without) =
+            slash_pair(&Url::parse("https://example.com/").unwrap(), &canonical, Some(home)).unwrap();
+        assert_eq!(with.as_str(), "https://www.example.com/shop/");
+        assert_eq!(without.as_str(), "https://www.example.com/shop");
+
+        let (with, _) =
+            slash_pair(&Url::parse("https://example.com/blog").unwrap(), &canonical, None).unwrap();
+        assert_eq!(with.as_str(), "https://www.example.com/blog/");
+    }
+}
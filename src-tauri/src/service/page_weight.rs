@@ -0,0 +1,311 @@
+//! Page weight without a browser: fetch the stylesheets, scripts and fonts
+//! a page references and add them up with its HTML.
+//!
+//! A [`PageWeigher`] lives for one job and fetches each asset once, however
+//! many pages share it, so a site-wide bundle costs one request rather
+//! than one per page. Fonts are found in the page itself and in the
+//! stylesheets it loads.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use tokio::sync::OnceCell;
+
+use crate::contexts::analysis::{same_root_domain, PageWeight};
+use crate::extractor::page_extractor::{ExtractedResource, PageExtractor, ResourceKind};
+use crate::service::spider::SpiderAgent;
+
+/// Assets fetched at once for one page.
+const FETCH_CONCURRENCY: usize = 4;
+
+/// Anything bigger is counted at this size and the rest is not downloaded.
+const MAX_ASSET_BYTES: usize = 10 * 1024 * 1024;
+
+/// Small files aren't worth flagging as unminified.
+const MIN_MINIFY_BYTES: usize = 2048;
+
+/// Minified code is mostly tokens; hand-written code is around a fifth
+/// whitespace (indentation, blank lines, spacing).
+const MAX_MINIFIED_WHITESPACE: f64 = 0.15;
+
+const FONT_EXTENSIONS: &[&str] = &[".woff2", ".woff", ".ttf", ".otf", ".eot"];
+
+/// One fetched asset, shared by every page that references it.
+#[derive(Debug, Clone, Default)]
+struct Asset {
+    bytes: usize,
+    unminified: bool,
+    /// Fonts a stylesheet loads.
+    fonts: Vec<String>,
+}
+
+pub struct PageWeigher {
+    spider: Arc<dyn SpiderAgent>,
+    /// `None` once an asset failed to load, so it isn't retried per page.
+    assets: Mutex<HashMap<String, Arc<OnceCell<Option<Asset>>>>>,
+}
+
+impl PageWeigher {
+    pub fn new(spider: Arc<dyn SpiderAgent>) -> Self {
+        Self { spider, assets: Mutex::new(HashMap::new()) }
+    }
+
+    /// Weigh a page from what was extracted from its HTML. Assets that
+    /// can't be fetched still count as requests but add no bytes.
+    pub async fn weigh(
+        &self,
+        page_url: &str,
+        html_bytes: usize,
+        resources: &[ExtractedResource],
+        render_blocking: Vec<String>,
+    ) -> PageWeight {
+        let mut requests: HashSet<&str> = resources
+            .iter()
+            .filter(|r| r.kind != ResourceKind::FormAction)
+            .map(|r| r.url.as_str())
+            .collect();
+
+        let to_fetch: Vec<(String, ResourceKind)> = {
+            let mut seen = HashSet::new();
+            resources
+                .iter()
+                .filter(|r| is_weighed(r))
+                .filter(|r| seen.insert(r.url.as_str()))
+                .map(|r| (r.url.clone(), r.kind))
+                .collect()
+        };
+        let mut fetched = self.fetch_all(to_fetch).await;
+
+        let font_urls: Vec<String> = fetched
+            .iter()
+            .flat_map(|(_, asset)| asset.fonts.iter())
+            .filter(|font| !requests.contains(font.as_str()))
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let fonts = font_urls.iter().map(|url| (url.clone(), ResourceKind::CssUrl)).collect();
+        fetched.extend(self.fetch_all(fonts).await);
+        requests.extend(font_urls.iter().map(String::as_str));
+
+        let third_party_requests =
+            requests.iter().filter(|url| !same_root_domain(url, page_url)).count();
+        let mut unminified: Vec<String> = fetched
+            .iter()
+            .filter(|(_, asset)| asset.unminified)
+            .map(|(url, _)| url.clone())
+            .collect();
+        unminified.sort();
+
+        PageWeight {
+            total_bytes: (html_bytes + fetched.iter().map(|(_, a)| a.bytes).sum::<usize>()) as i64,
+            html_bytes: html_bytes as i64,
+            request_count: requests.len() as i64,
+            third_party_requests: third_party_requests as i64,
+            render_blocking,
+            unminified,
+        }
+    }
+
+    async fn fetch_all(&self, assets: Vec<(String, ResourceKind)>) -> Vec<(String, Asset)> {
+        futures::stream::iter(assets)
+            .map(|(url, kind)| async move {
+                let asset = self.asset(&url, kind).await;
+                asset.map(|asset| (url, asset))
+            })
+            .buffer_unordered(FETCH_CONCURRENCY)
+            .filter_map(|asset| async move { asset })
+            .collect()
+            .await
+    }
+
+    async fn asset(&self, url: &str, kind: ResourceKind) -> Option<Asset> {
+        let cell = {
+            let mut assets = self.assets.lock().unwrap_or_else(|e| e.into_inner());
+            assets.entry(url.to_string()).or_default().clone()
+        };
+        cell.get_or_init(|| self.fetch(url, kind)).await.clone()
+    }
+
+    async fn fetch(&self, url: &str, kind: ResourceKind) -> Option<Asset> {
+        let mut response = match self.spider.stream_get(url).await {
+            Ok(response) if response.status < 400 => response,
+            Ok(response) => {
+                tracing::debug!("[WEIGHT] {} answered {}", url, response.status);
+                return None;
+            }
+            Err(e) => {
+                tracing::debug!("[WEIGHT] Could not fetch {}: {:#}", url, e);
+                return None;
+            }
+        };
+
+        let is_text = matches!(kind, ResourceKind::Script | ResourceKind::Stylesheet) && !is_font(url);
+        let mut body = Vec::new();
+        let mut bytes = 0;
+        loop {
+            match response.next_chunk().await {
+                Ok(Some(chunk)) => {
+                    bytes += chunk.len();
+                    if is_text {
+                        body.extend_from_slice(&chunk);
+                    }
+                    if bytes >= MAX_ASSET_BYTES {
+                        break;
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::debug!("[WEIGHT] Download of {} failed: {:#}", url, e);
+                    break;
+                }
+            }
+        }
+
+        let text = String::from_utf8_lossy(&body);
+        Some(Asset {
+            bytes: bytes.min(MAX_ASSET_BYTES),
+            unminified: is_text && looks_unminified(url, &text),
+            fonts: if kind == ResourceKind::Stylesheet {
+                PageExtractor::extract_css_urls(&text, url).into_iter().filter(|u| is_font(u)).collect()
+            } else {
+                Vec::new()
+            },
+        })
+    }
+}
+
+/// Stylesheets, scripts and fonts are fetched; images and media are
+/// only counted.
+fn is_weighed(resource: &ExtractedResource) -> bool {
+    match resource.kind {
+        ResourceKind::Script | ResourceKind::Stylesheet => true,
+        ResourceKind::Preload => resource.active || is_font(&resource.url),
+        ResourceKind::CssUrl => is_font(&resource.url),
+        _ => false,
+    }
+}
+
+fn is_font(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_ascii_lowercase();
+    FONT_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+fn looks_unminified(url: &str, body: &str) -> bool {
+    if body.len() < MIN_MINIFY_BYTES || url.contains(".min.") {
+        return false;
+    }
+    let whitespace = body.bytes().filter(u8::is_ascii_whitespace).count();
+    whitespace as f64 / body.len() as f64 > MAX_MINIFIED_WHITESPACE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::spider::{SpiderResponse, StreamResponse};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serves fixed bodies by URL and counts downloads.
+    struct FakeSpider {
+        bodies: HashMap<&'static str, String>,
+        downloads: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SpiderAgent for FakeSpider {
+        async fn fetch_html(&self, _url: &str) -> Result<String> {
+            unreachable!()
+        }
+        async fn get(&self, _url: &str) -> Result<SpiderResponse> {
+            unreachable!()
+        }
+        async fn post_json(&self, _url: &str, _payload: &serde_json::Value) -> Result<SpiderResponse> {
+            unreachable!()
+        }
+        async fn stream_get(&self, url: &str) -> Result<StreamResponse> {
+            self.downloads.fetch_add(1, Ordering::SeqCst);
+            match self.bodies.get(url) {
+                Some(body) => Ok(StreamResponse::from_bytes(200, body.clone().into_bytes())),
+                None => Ok(StreamResponse::from_bytes(404, Vec::new())),
+            }
+        }
+        async fn stream_get_range(&self, url: &str, _start_byte: u64) -> Result<StreamResponse> {
+            self.stream_get(url).await
+        }
+    }
+
+    fn weigher() -> (PageWeigher, Arc<FakeSpider>) {
+        let readable_css = format!(
+            "@font-face {{\n    src: url(\"/fonts/brand.woff2\");\n}}\n{}",
+            "body {\n    margin: 0;\n    padding: 0;\n}\n".repeat(80)
+        );
+        let spider = Arc::new(FakeSpider {
+            bodies: HashMap::from([
+                ("https://example.com/site.css", readable_css),
+                ("https://example.com/app.min.js", "x".repeat(4000)),
+                ("https://example.com/fonts/brand.woff2", "f".repeat(1000)),
+                ("https://cdn.other.net/widget.js", "w".repeat(500)),
+            ]),
+            downloads: AtomicUsize::new(0),
+        });
+        (PageWeigher::new(spider.clone()), spider)
+    }
+
+    fn resource(url: &str, kind: ResourceKind) -> ExtractedResource {
+        ExtractedResource { url: url.into(), kind, active: true }
+    }
+
+    fn page_resources() -> Vec<ExtractedResource> {
+        vec![
+            resource("https://example.com/site.css", ResourceKind::Stylesheet),
+            resource("https://example.com/app.min.js", ResourceKind::Script),
+            resource("https://cdn.other.net/widget.js", ResourceKind::Script),
+            resource("https://example.com/hero.jpg", ResourceKind::Image),
+            resource("https://example.com/missing.js", ResourceKind::Script),
+            resource("https://example.com/subscribe", ResourceKind::FormAction),
+        ]
+    }
+
+    #[tokio::test]
+    async fn weighs_page_assets_and_fonts_from_stylesheets() {
+        let (weigher, _) = weigher();
+        let weight = weigher
+            .weigh("https://example.com/", 1000, &page_resources(), vec!["https://example.com/site.css".into()])
+            .await;
+
+        let css_len = weigher.asset("https://example.com/site.css", ResourceKind::Stylesheet).await.unwrap().bytes;
+        assert_eq!(weight.html_bytes, 1000);
+        assert_eq!(weight.total_bytes as usize, 1000 + css_len + 4000 + 1000 + 500);
+        // css, two scripts, image, missing script and the font
+        assert_eq!(weight.request_count, 6);
+        assert_eq!(weight.third_party_requests, 1);
+        assert_eq!(weight.unminified, ["https://example.com/site.css"]);
+        assert_eq!(weight.render_blocking, ["https://example.com/site.css"]);
+    }
+
+    #[tokio::test]
+    async fn shared_assets_are_fetched_once_per_job() {
+        let (weigher, spider) = weigher();
+        weigher.weigh("https://example.com/a", 10, &page_resources(), Vec::new()).await;
+        let first = spider.downloads.load(Ordering::SeqCst);
+        weigher.weigh("https://example.com/b", 10, &page_resources(), Vec::new()).await;
+        assert_eq!(spider.downloads.load(Ordering::SeqCst), first);
+    }
+
+    #[test]
+    fn minified_and_small_files_are_not_flagged() {
+        assert!(!looks_unminified("https://x/a.js", "a = 1;\n"));
+        assert!(!looks_unminified("https://x/a.min.js", &" \n".repeat(2000)));
+        assert!(!looks_unminified("https://x/a.js", &"function(){return 1}".repeat(200)));
+        assert!(looks_unminified("https://x/a.js", &"function () {\n    return 1;\n}\n".repeat(200)));
+    }
+
+    #[test]
+    fn fonts_are_recognised_by_extension() {
+        assert!(is_font("https://x/f/brand.WOFF2?v=3"));
+        assert!(!is_font("https://x/f/brand.css"));
+    }
+}
@@ -1,13 +1,19 @@
 use crate::checker::{CheckContext, CheckerRegistry};
-use crate::checker::custom::CustomCheckAdapter;
+use crate::checker::custom::{self, SiteAggregateCheck};
 use crate::contexts::extension::CustomCheck;
+use crate::contexts::report::services::pattern_engine::HeadingIndex;
+use crate::contexts::snapshot::{NewPageSnapshot, SnapshotService};
 use crate::contexts::analysis::{
     JobSettings, LighthouseData, LinkType, NewHeading, NewImage, NewIssue, NewLink, Page,
 };
 use crate::extractor::data_extractor::ExtractorRegistry;
-use crate::extractor::page_extractor::{ExtractedHeading, ExtractedImage, PageExtractor};
+use crate::extractor::page_extractor::{
+    ExtractedHeading, ExtractedImage, ExtractedLink, ExtractedResource, PageExtractor,
+};
 use crate::repository::{IssueRepository as IssueRepoTrait, PageRepository as PageRepoTrait};
 use crate::service::auditor::{Auditor, AuditResult, DeepAuditor, LightAuditor};
+use crate::service::page_weight::PageWeigher;
+use crate::service::soft_404::NotFoundProfile;
 use crate::service::spider::SpiderAgent;
 use anyhow::Result;
 use scraper::Html;
@@ -20,6 +26,20 @@ pub struct AnalyzerService {
     deep_auditor: Arc<DeepAuditor>,
     checker_registry: Arc<CheckerRegistry>,
     extractor_registry: Arc<ExtractorRegistry>,
+    /// Enabled `scope: site` custom checks, run once per job by
+    /// [`run_aggregate_checks`](Self::run_aggregate_checks).
+    aggregate_checks: Arc<Vec<SiteAggregateCheck>>,
+    /// Saves each page's source, when set and saving is turned on.
+    snapshots: Option<Arc<SnapshotService>>,
+}
+
+/// What a job brings to each of its pages' analysis beyond the page
+/// itself. Everything is optional: the resource pass is opt-in and the
+/// not-found probe can fail.
+#[derive(Clone, Copy, Default)]
+pub struct SiteContext<'a> {
+    pub weigher: Option<&'a PageWeigher>,
+    pub not_found: Option<&'a NotFoundProfile>,
 }
 
 pub struct PageResult {
@@ -39,6 +59,11 @@ struct ExtractedPageData {
     link_edges: Vec<ExtractedLinkEdge>,
     headings: Vec<ExtractedHeading>,
     images: Vec<ExtractedImage>,
+    resources: Vec<ExtractedResource>,
+    render_blocking: Vec<String>,
+    links: Vec<ExtractedLink>,
+    /// Why the page looks like a soft 404.
+    soft_404: Option<String>,
     final_url: String,
 }
 
@@ -59,6 +84,7 @@ fn extract_page_data(
     depth: crate::contexts::analysis::Depth,
     audit_result: &AuditResult,
     extracted_data: std::collections::HashMap<String, serde_json::Value>,
+    not_found: Option<&NotFoundProfile>,
 ) -> ExtractedPageData {
     let parsed_html = Html::parse_document(html);
 
@@ -74,6 +100,21 @@ fn extract_page_data(
 
     let headings = PageExtractor::extract_headings(&parsed_html);
     let images = PageExtractor::extract_images(&parsed_html, url);
+    let resources = PageExtractor::extract_resources(&parsed_html, &audit_result.url);
+    let render_blocking = PageExtractor::extract_render_blocking(&parsed_html, &audit_result.url);
+    let soft_404 = not_found.and_then(|profile| {
+        let h1 = headings
+            .iter()
+            .find(|h| h.level == 1 && !h.text.is_empty())
+            .map(|h| h.text.as_str());
+        profile.classify(
+            &audit_result.url,
+            Some(audit_result.status_code as i64),
+            title.as_deref(),
+            h1,
+            &PageExtractor::extract_body_text(&parsed_html),
+        )
+    });
 
     let page = Page {
         id: uuid::Uuid::new_v4().to_string(),
@@ -81,32 +122,37 @@ fn extract_page_data(
         url: url.to_string(),
         depth,
         status_code: Some(audit_result.status_code as i64),
-        content_type: None,
+        content_type: audit_result.headers.media_type(),
         title,
         meta_description,
         canonical_url,
         robots_meta: None,
         word_count: Some(word_count),
         load_time_ms: Some(audit_result.load_time_ms as i64),
+        ttfb_ms: audit_result.timing.map(|t| t.ttfb_ms as i64),
+        download_ms: audit_result.timing.map(|t| t.download_ms as i64),
         response_size_bytes: Some(audit_result.content_size as i64),
         has_viewport,
         has_structured_data,
         crawled_at: chrono::Utc::now(),
         extracted_data,
+        response_headers: audit_result.headers.clone(),
+        // Filled in by the resource pass, once the page has been parsed.
+        weight: None,
     };
 
     let link_edges: Vec<ExtractedLinkEdge> = all_links
-        .into_iter()
+        .iter()
         .map(|link| {
             ExtractedLinkEdge {
-                href: link.href,
+                href: link.href.clone(),
                 initial_status: if matches!(link.link_type, LinkType::Internal | LinkType::Subdomain)
                 {
                     200i32
                 } else {
                     0i32
                 },
-                anchor_text: link.text,
+                anchor_text: link.text.clone(),
             }
         })
         .collect();
@@ -117,6 +163,10 @@ fn extract_page_data(
         link_edges,
         headings,
         images,
+        resources,
+        render_blocking,
+        links: all_links,
+        soft_404,
         final_url: audit_result.url.clone(),
     }
 }
@@ -156,10 +206,12 @@ impl AnalyzerService {
     }
 
     /// Build an analyzer with user-defined custom checks loaded from
-    /// the extension repository. Each `CustomCheck` is wrapped in a
-    /// [`CustomCheckAdapter`] and registered alongside the built-ins,
-    /// so they fire during `analyze_page` the same way as any built-in
-    /// check — with `{tag.X}` substitution in the issue message.
+    /// the extension repository. Page checks are registered alongside
+    /// the built-ins by [`custom::register`], so they fire during
+    /// `analyze_page` the same way as any built-in check — with
+    /// `{tag.X}` substitution in the issue message.
+    /// Site-scoped checks are kept aside for
+    /// [`run_aggregate_checks`](Self::run_aggregate_checks).
     pub fn with_custom_checks(
         page_db: Arc<dyn PageRepoTrait>,
         issue_db: Arc<dyn IssueRepoTrait>,
@@ -168,11 +220,7 @@ impl AnalyzerService {
         custom_checks: Vec<CustomCheck>,
     ) -> Self {
         let mut checker_registry = CheckerRegistry::with_defaults();
-        for check in custom_checks {
-            if check.enabled {
-                checker_registry.register(Box::new(CustomCheckAdapter::new(check)));
-            }
-        }
+        let aggregate_checks = custom::register(&mut checker_registry, custom_checks);
         Self {
             page_db,
             issue_db,
@@ -180,9 +228,18 @@ impl AnalyzerService {
             deep_auditor: Arc::new(DeepAuditor::new(deep_spider.clone())),
             checker_registry: Arc::new(checker_registry),
             extractor_registry,
+            aggregate_checks: Arc::new(aggregate_checks),
+            snapshots: None,
         }
     }
 
+    /// Save the HTML and headers of every analysed page through
+    /// `snapshots`.
+    pub fn with_snapshots(mut self, snapshots: Arc<SnapshotService>) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
     pub fn select_auditor(&self, settings: &JobSettings) -> Arc<dyn Auditor + Send + Sync> {
         if settings.lighthouse_analysis {
             if self.deep_auditor.is_available() {
@@ -196,6 +253,63 @@ impl AnalyzerService {
         }
     }
 
+    /// Like [`select_auditor`](Self::select_auditor) for a job that
+    /// fetches through its own spider (a crawl profile, custom headers,
+    /// cookies or credentials). Lighthouse's browser can't present any of
+    /// those, so such jobs always get the light auditor rather than scores
+    /// for a page the job's spider would never have been served.
+    pub fn select_auditor_for(
+        &self,
+        settings: &JobSettings,
+        job_spider: Option<Arc<dyn SpiderAgent>>,
+    ) -> Arc<dyn Auditor + Send + Sync> {
+        match job_spider {
+            Some(spider) => {
+                if settings.lighthouse_analysis {
+                    tracing::warn!(
+                        "[JOB] Lighthouse can't use the job's crawl profile or HTTP configuration, using light auditor"
+                    );
+                }
+                Arc::new(LightAuditor::new(spider))
+            }
+            None => self.select_auditor(settings),
+        }
+    }
+
+    /// Store issues that belong to the whole site rather than one page.
+    pub async fn record_site_issues(&self, issues: &[NewIssue]) -> Result<()> {
+        if !issues.is_empty() {
+            self.issue_db.insert_batch(issues).await?;
+        }
+        Ok(())
+    }
+
+    /// Run the site-scoped custom checks over every analysed page of the
+    /// job and store the issues they raise.
+    pub async fn run_aggregate_checks(&self, job_id: &str) -> Result<()> {
+        if self.aggregate_checks.is_empty() {
+            return Ok(());
+        }
+        let pages = self.page_db.get_by_job_id(job_id).await?;
+        let headings = HeadingIndex::from_headings(&self.page_db.get_headings_by_job_id(job_id).await?);
+        let issues: Vec<NewIssue> = self
+            .aggregate_checks
+            .iter()
+            .filter_map(|check| check.run(job_id, &pages, &headings))
+            .collect();
+        self.record_site_issues(&issues).await
+    }
+
+    /// Drop page snapshots past the retention period. Failing to is
+    /// logged, not fatal: the job's results are already stored.
+    pub async fn prune_snapshots(&self) {
+        if let Some(snapshots) = &self.snapshots {
+            if let Err(e) = snapshots.prune().await {
+                tracing::warn!("Failed to prune page snapshots: {}", e);
+            }
+        }
+    }
+
     pub fn deep_auditor(&self) -> Arc<DeepAuditor> {
         self.deep_auditor.clone()
     }
@@ -214,10 +328,11 @@ impl AnalyzerService {
         job_id: &str,
         depth: crate::contexts::analysis::Depth,
         auditor: &Arc<dyn Auditor + Send + Sync>,
+        site: SiteContext<'_>,
         cached: crate::service::auditor::CachedHtml,
     ) -> Result<(PageResult, Vec<String>)> {
         let audit_result = auditor.analyze_from_cache(url, cached).await?;
-        self.process_audit_result(url, job_id, depth, audit_result).await
+        self.process_audit_result(url, job_id, depth, site, audit_result).await
     }
 
     pub async fn analyze_page(
@@ -226,9 +341,10 @@ impl AnalyzerService {
         job_id: &str,
         depth: crate::contexts::analysis::Depth,
         auditor: &Arc<dyn Auditor + Send + Sync>,
+        site: SiteContext<'_>,
     ) -> Result<(PageResult, Vec<String>)> {
         let audit_result = auditor.analyze(url).await?;
-        self.process_audit_result(url, job_id, depth, audit_result).await
+        self.process_audit_result(url, job_id, depth, site, audit_result).await
     }
 
     /// Shared processing for both cached and non-cached paths.
@@ -237,6 +353,7 @@ impl AnalyzerService {
         url: &str,
         job_id: &str,
         depth: crate::contexts::analysis::Depth,
+        site: SiteContext<'_>,
         audit_result: crate::service::auditor::AuditResult,
     ) -> Result<(PageResult, Vec<String>)> {
 
@@ -248,17 +365,55 @@ impl AnalyzerService {
             );
         }
 
-        let custom_data = self.extractor_registry.run(&audit_result.html);
-        let extracted = extract_page_data(&audit_result.html, url, job_id, depth, &audit_result, custom_data);
+        let custom_data = self.extractor_registry.run(&audit_result.html, &audit_result.headers);
+        let mut extracted = extract_page_data(
+            &audit_result.html,
+            url,
+            job_id,
+            depth,
+            &audit_result,
+            custom_data,
+            site.not_found,
+        );
+
+        if let Some(weigher) = site.weigher {
+            let weight = weigher
+                .weigh(
+                    &audit_result.url,
+                    audit_result.html.len(),
+                    &extracted.resources,
+                    std::mem::take(&mut extracted.render_blocking),
+                )
+                .await;
+            extracted.page.weight = Some(weight);
+        }
 
         let page_id = self.page_db.insert(&extracted.page).await?;
 
+        if let Some(snapshots) = &self.snapshots {
+            let snapshot = NewPageSnapshot {
+                page_id: &page_id,
+                job_id,
+                url: &extracted.page.url,
+                html: &audit_result.html,
+                headers: &audit_result.headers,
+            };
+            if let Err(e) = snapshots.keep(&snapshot).await {
+                tracing::warn!("Failed to save the source of {}: {}", url, e);
+            }
+        }
+
         let check_ctx = CheckContext::new(
             &extracted.page,
             &audit_result.scores.seo_details,
             job_id,
             &page_id,
-        );
+        )
+        .with_final_url(&extracted.final_url)
+        .with_references(&extracted.resources, &extracted.links)
+        .with_headings(&extracted.headings)
+        .with_images(&extracted.images)
+        .with_soft_404(extracted.soft_404.as_deref());
         let issues = self.checker_registry.run(&check_ctx);
         let lighthouse = LighthouseData::from_audit_scores(&page_id, &audit_result.scores);
 
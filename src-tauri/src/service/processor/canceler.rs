@@ -1,15 +1,22 @@
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 
 pub struct JobCanceler {
     cancel_map: Arc<DashMap<String, CancellationToken>>,
+    /// Jobs whose token was cancelled by a pause request rather than a
+    /// cancel. The worker checks this to checkpoint instead of failing.
+    paused: Arc<DashSet<String>>,
+    /// Jobs currently owned by a worker inside `process_job`.
+    running: Arc<DashSet<String>>,
 }
 
 impl JobCanceler {
     pub fn new() -> Self {
         Self {
             cancel_map: Arc::new(DashMap::with_capacity(10)),
+            paused: Arc::new(DashSet::new()),
+            running: Arc::new(DashSet::new()),
         }
     }
 
@@ -21,10 +28,9 @@ impl JobCanceler {
     }
 
     pub fn cancel(&self, job_id: &str) {
-        self.cancel_map
-            .entry(job_id.to_string())
-            .or_default()
-            .cancel();
+        // A cancel overrides an earlier pause request.
+        self.paused.remove(job_id);
+        self.stop(job_id);
     }
 
     pub fn is_cancelled(&self, job_id: &str) -> bool {
@@ -33,14 +39,47 @@ impl JobCanceler {
             .is_some_and(|token| token.is_cancelled())
     }
 
+    /// Stop the job's work the same way `cancel` does, but flag it so the
+    /// worker saves its progress and leaves the job resumable.
+    pub fn pause(&self, job_id: &str) {
+        // Flag first so a worker that observes the cancelled token
+        // always sees it as a pause.
+        self.paused.insert(job_id.to_string());
+        self.stop(job_id);
+    }
+
+    pub fn is_paused(&self, job_id: &str) -> bool {
+        self.paused.contains(job_id)
+    }
+
+    pub fn mark_running(&self, job_id: &str) {
+        self.running.insert(job_id.to_string());
+    }
+
+    pub fn mark_stopped(&self, job_id: &str) {
+        self.running.remove(job_id);
+    }
+
+    pub fn is_running(&self, job_id: &str) -> bool {
+        self.running.contains(job_id)
+    }
+
     pub fn cancel_all(&self) {
         for entry in self.cancel_map.iter() {
             entry.cancel();
         }
     }
 
+    fn stop(&self, job_id: &str) {
+        self.cancel_map
+            .entry(job_id.to_string())
+            .or_default()
+            .cancel();
+    }
+
     pub fn cleanup(&self, job_id: &str) {
         self.cancel_map.remove(job_id);
+        self.paused.remove(job_id);
     }
 }
 
@@ -151,6 +190,47 @@ mod tests {
         assert!(cancel_token.is_cancelled(), "Token should be cancelled");
     }
 
+    #[test]
+    fn test_pause_cancels_token_and_is_cleared_by_cleanup() {
+        let canceler = JobCanceler::new();
+        let job_id = "test-job-pause";
+
+        let token = canceler.get_token(job_id);
+        canceler.pause(job_id);
+
+        assert!(token.is_cancelled(), "pause should stop the running work");
+        assert!(canceler.is_paused(job_id));
+
+        canceler.cleanup(job_id);
+
+        assert!(!canceler.is_paused(job_id));
+        assert!(!canceler.get_token(job_id).is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel_overrides_pause() {
+        let canceler = JobCanceler::new();
+        let job_id = "test-job-5";
+
+        canceler.pause(job_id);
+        canceler.cancel(job_id);
+
+        assert!(canceler.is_cancelled(job_id));
+        assert!(!canceler.is_paused(job_id));
+    }
+
+    #[test]
+    fn test_running_tracking() {
+        let canceler = JobCanceler::new();
+        let job_id = "test-job-running";
+
+        assert!(!canceler.is_running(job_id));
+        canceler.mark_running(job_id);
+        assert!(canceler.is_running(job_id));
+        canceler.mark_stopped(job_id);
+        assert!(!canceler.is_running(job_id));
+    }
+
     #[test]
     fn test_token_shared_reference() {
         let canceler = JobCanceler::new();
@@ -1,11 +1,10 @@
-use crate::contexts::analysis::JobSettings;
-use crate::service::discovery::{DiscoveredPage, PageDiscovery, ResourceChecker, SiteResources};
+use crate::contexts::analysis::{CompiledUrlRules, DiscoveryFrontier, JobSettings, SiteCheckResult};
+use crate::service::discovery::{DiscoveredPage, DiscoveryOutcome, PageDiscovery, ResourceChecker};
 use crate::service::processor::reporter::{ProgressEmitter, ProgressEvent};
 use crate::service::spider::SpiderAgent;
 use anyhow::{Context, Result};
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
-use url::Url;
 
 pub struct Crawler {
     discovery: PageDiscovery,
@@ -16,7 +15,11 @@ pub struct CrawlContext {
     pub job_id: String,
     pub settings: JobSettings,
     pub start_url: String,
+    /// The job's URL rules, compiled once for the whole run.
+    pub url_rules: Arc<CompiledUrlRules>,
     pub cancel_token: CancellationToken,
+    /// Discovery checkpoint from a paused run; `None` starts a fresh crawl.
+    pub resume: Option<DiscoveryFrontier>,
 }
 
 impl Crawler {
@@ -27,53 +30,27 @@ impl Crawler {
         }
     }
 
-    pub async fn check_resources(&self, url_str: &str) -> Result<SiteResources> {
-        let url = Url::parse(url_str)?;
-
-        // Both checks silently treat errors as "resource absent", but we
-        // now log the error so a misconfigured spider or a transient
-        // network blip doesn't vanish into a negative signal. The
-        // previous code dropped the error with `.unwrap_or(false)`.
-        async fn present<E: std::fmt::Display>(
-            what: &str,
-            fut: impl std::future::Future<
-                Output = std::result::Result<crate::contexts::analysis::ResourceStatus, E>,
-            >,
-        ) -> bool {
-            match fut.await {
-                Ok(status) => status.exists(),
-                Err(e) => {
-                    tracing::debug!("resource check for {what} failed: {e}");
-                    false
-                }
-            }
-        }
-
-        let robots_txt = present("robots.txt", self.resource_checker.check_robots_txt(url.as_str())).await;
-        let sitemap = present("sitemap.xml", self.resource_checker.check_sitemap_xml(url.as_str())).await;
-
-        Ok(SiteResources::new(
-            robots_txt,
-            sitemap,
-            url.scheme() == "https",
-        ))
+    pub async fn check_resources(&self, url_str: &str) -> Result<Vec<SiteCheckResult>> {
+        self.resource_checker.check_all(url_str).await
     }
 
     pub async fn discover_pages(
         &self,
         context: &CrawlContext,
         progress_emitter: Arc<dyn ProgressEmitter>,
-    ) -> Result<Vec<DiscoveredPage>> {
+    ) -> Result<DiscoveryOutcome> {
         let job_id = context.job_id.clone();
         let max_pages = context.settings.max_pages as usize;
 
         let emitter = progress_emitter.clone();
         let job_id_clone = job_id.clone();
 
-        let mut discovered = self
+        let mut outcome = self
             .discovery
-            .discover(
+            .discover_from(
                 &context.start_url,
+                context.resume.clone(),
+                &context.url_rules,
                 context.settings.max_pages,
                 context.settings.delay_between_requests,
                 context.settings.include_subdomains,
@@ -90,17 +67,22 @@ impl Crawler {
             .await
             .context("Page discovery failed")?;
 
-        if discovered.is_empty() {
+        // A resumed crawl legitimately returns nothing new when the pause
+        // landed right at the end, and an interrupted one is checkpointed
+        // rather than analysed, so only a fresh, complete run falls back.
+        if outcome.pages.is_empty() && outcome.interrupted.is_none() && context.resume.is_none() {
             tracing::warn!("[JOB] Discovery returned no pages, falling back to start URL");
-            discovered.push(DiscoveredPage {
+            outcome.pages.push(DiscoveredPage {
                 url: context.start_url.clone(),
                 final_url: context.start_url.clone(),
                 html: String::new(),
                 status_code: 0,
                 load_time_ms: 0.0,
+                headers: Default::default(),
+                timing: None,
             });
         }
 
-        Ok(discovered)
+        Ok(outcome)
     }
 }
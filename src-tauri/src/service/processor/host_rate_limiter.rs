@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+use url::Url;
+
+/// Spaces out live fetches per host during the analysis phase.
+///
+/// Spider-mode pages arrive with cached HTML, but list-mode jobs fetch
+/// every page during analysis and may mix hosts. The job-level
+/// `DomainSemaphore` only covers the job's start host, so this keeps each
+/// host to one request start per `delay` while different hosts proceed
+/// in parallel.
+pub struct HostRateLimiter {
+    delay: Duration,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait until `url`'s host may be requested again, reserving the slot
+    /// after it for the next caller.
+    pub async fn until_ready(&self, url: &str) {
+        if self.delay.is_zero() {
+            return;
+        }
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let slot = next_slot
+                .get(&host)
+                .copied()
+                .filter(|t| *t > now)
+                .unwrap_or(now);
+            next_slot.insert(host, slot + self.delay);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn same_host_requests_are_spaced_out() {
+        let limiter = HostRateLimiter::new(Duration::from_millis(60));
+        let start = Instant::now();
+
+        limiter.until_ready("https://example.com/a").await;
+        limiter.until_ready("https://example.com/b").await;
+
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[tokio::test]
+    async fn different_hosts_do_not_wait_for_each_other() {
+        let limiter = HostRateLimiter::new(Duration::from_secs(5));
+        let start = Instant::now();
+
+        limiter.until_ready("https://example.com/a").await;
+        limiter.until_ready("https://other.org/a").await;
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn zero_delay_never_waits() {
+        let limiter = HostRateLimiter::new(Duration::ZERO);
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.until_ready("https://example.com/").await;
+        }
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -3,21 +3,29 @@ mod canceler;
 mod channel;
 mod crawler;
 mod domain_semaphore;
+mod host_rate_limiter;
 mod page_queue;
 mod queue;
 pub mod reporter;
 
-pub use crate::service::discovery::SiteResources;
-pub use analyzer::{AnalyzerService, PageResult};
+pub use analyzer::{AnalyzerService, PageResult, SiteContext};
 pub use canceler::JobCanceler;
 pub use channel::{JobChannel, JobChannelConfig, JobDispatcher, JobNotifier};
 pub use crawler::{CrawlContext, Crawler};
 pub use domain_semaphore::DomainSemaphore;
+pub use host_rate_limiter::HostRateLimiter;
 pub use page_queue::PageQueueManager;
 pub use queue::{JobQueue, JobQueueConfig};
 pub use reporter::ProgressReporter;
 
-use crate::contexts::{Job, NewLink};
+use crate::contexts::analysis::{CompiledUrlRules, CrawlMode, HttpConfig};
+use crate::service::delivery::DeliveryProbe;
+use crate::service::origins::{OriginProbe, OriginReport};
+use crate::service::page_weight::PageWeigher;
+use crate::service::soft_404::{NotFoundProbe, NotFoundProfile};
+use crate::service::spider::{Spider, SpiderAgent};
+use crate::service::tls::{self, TlsInspector};
+use crate::contexts::{DiscoveryFrontier, Job, NewLink};
 use crate::service::processor::reporter::{ProgressEmitter, ProgressEvent};
 use anyhow::Result;
 use std::sync::Arc;
@@ -38,13 +46,18 @@ impl Default for WorkerPoolConfig {
 
 pub struct JobProcessor {
     job_queue: Arc<JobQueue>,
-    crawler: Arc<Crawler>,
+    /// The spider for jobs that need none of their own. Each run builds its
+    /// crawler from the spider picked for its job.
+    spider: Arc<dyn SpiderAgent>,
     analyzer: Arc<AnalyzerService>,
     progress_emitter: Arc<dyn ProgressEmitter>,
     canceler: Arc<JobCanceler>,
     domain_semaphore: Arc<DomainSemaphore>,
     page_queue_manager: Arc<PageQueueManager>,
     link_db: Arc<dyn crate::repository::LinkRepository>,
+    /// `None` if the verifying TLS client couldn't be set up; jobs then
+    /// record that their certificate wasn't inspected.
+    tls_inspector: Option<Arc<TlsInspector>>,
     worker_config: WorkerPoolConfig,
 }
 
@@ -55,7 +68,7 @@ impl JobProcessor {
         link_repo: Arc<dyn crate::repository::LinkRepository>,
         page_queue_repo: Arc<dyn crate::repository::PageQueueRepository>,
         analyzer: AnalyzerService,
-        crawler: Crawler,
+        spider: Arc<dyn SpiderAgent>,
         progress_emitter: Arc<dyn ProgressEmitter>,
     ) -> Self {
         Self::with_config(
@@ -63,7 +76,7 @@ impl JobProcessor {
             link_repo,
             page_queue_repo,
             analyzer,
-            crawler,
+            spider,
             progress_emitter,
             WorkerPoolConfig::default(),
         )
@@ -75,19 +88,23 @@ impl JobProcessor {
         link_repo: Arc<dyn crate::repository::LinkRepository>,
         page_queue_repo: Arc<dyn crate::repository::PageQueueRepository>,
         analyzer: AnalyzerService,
-        crawler: Crawler,
+        spider: Arc<dyn SpiderAgent>,
         progress_emitter: Arc<dyn ProgressEmitter>,
         worker_config: WorkerPoolConfig,
     ) -> Self {
         Self {
             job_queue: Arc::new(JobQueue::new(job_repo)),
-            crawler: Arc::new(crawler),
+            spider,
             analyzer: Arc::new(analyzer),
             progress_emitter,
             canceler: Arc::new(JobCanceler::new()),
             domain_semaphore: Arc::new(DomainSemaphore::new()),
             page_queue_manager: Arc::new(PageQueueManager::new(page_queue_repo)),
             link_db: link_repo,
+            tls_inspector: TlsInspector::new()
+                .inspect_err(|e| tracing::warn!("TLS inspection unavailable: {e:#}"))
+                .ok()
+                .map(Arc::new),
             worker_config,
         }
     }
@@ -155,13 +172,14 @@ impl JobProcessor {
     fn worker_context(&self) -> WorkerContext {
         WorkerContext {
             job_queue: self.job_queue.clone(),
-            crawler: self.crawler.clone(),
+            spider: self.spider.clone(),
             analyzer: self.analyzer.clone(),
             progress_emitter: self.progress_emitter.clone(),
             canceler: self.canceler.clone(),
             link_db: self.link_db.clone(),
             domain_semaphore: self.domain_semaphore.clone(),
             page_queue_manager: self.page_queue_manager.clone(),
+            tls_inspector: self.tls_inspector.clone(),
         }
     }
 
@@ -215,7 +233,35 @@ impl JobProcessor {
     pub async fn cancel(&self, job_id: &str) -> Result<()> {
         tracing::info!("Cancelling job {}", job_id);
         self.canceler.cancel(job_id);
-        self.job_queue.mark_cancelled(job_id).await
+        self.job_queue.mark_cancelled(job_id).await?;
+        self.page_queue_manager.clear_frontier(job_id).await
+    }
+
+    /// Ask a job to stop at the next checkpoint and keep its progress.
+    ///
+    /// A job a worker is currently running is parked by that worker once
+    /// it has saved its frontier; a job that is only queued is marked
+    /// paused here directly.
+    pub async fn pause(&self, job_id: &str) -> Result<()> {
+        tracing::info!("Pausing job {}", job_id);
+        self.canceler.pause(job_id);
+        if !self.canceler.is_running(job_id) {
+            self.job_queue.mark_paused(job_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Requeue a paused job. Fails while the worker that was asked to
+    /// pause it is still draining its in-flight pages.
+    pub async fn resume(&self, job_id: &str) -> Result<()> {
+        if self.canceler.is_running(job_id) {
+            anyhow::bail!("Job {} is still pausing", job_id);
+        }
+        tracing::info!("Resuming job {}", job_id);
+        self.canceler.cleanup(job_id);
+        self.job_queue.mark_pending(job_id).await?;
+        self.job_queue.notify_new_job().await;
+        Ok(())
     }
 
     pub async fn process_job(&self, job: Job) -> Result<String> {
@@ -234,13 +280,14 @@ impl JobProcessor {
 
 struct WorkerContext {
     job_queue: Arc<JobQueue>,
-    crawler: Arc<Crawler>,
+    spider: Arc<dyn SpiderAgent>,
     analyzer: Arc<AnalyzerService>,
     progress_emitter: Arc<dyn ProgressEmitter>,
     canceler: Arc<JobCanceler>,
     link_db: Arc<dyn crate::repository::LinkRepository>,
     domain_semaphore: Arc<DomainSemaphore>,
     page_queue_manager: Arc<PageQueueManager>,
+    tls_inspector: Option<Arc<TlsInspector>>,
 }
 
 impl WorkerContext {
@@ -249,8 +296,11 @@ impl WorkerContext {
     /// job id as the successful exit value. Centralizes the two
     /// near-identical early-exit blocks (before crawl, before
     /// analysis) so a future stage can be added by one line.
+    ///
+    /// A paused job is not bailed out here — its token is cancelled too,
+    /// but the caller checkpoints it instead of dropping its work.
     fn bail_if_cancelled(&self, job: &Job, stage: &'static str) -> Option<String> {
-        if self.canceler.is_cancelled(&job.id) {
+        if self.canceler.is_cancelled(&job.id) && !self.canceler.is_paused(&job.id) {
             tracing::warn!("Job {} cancelled {}", job.id, stage);
             self.canceler.cleanup(&job.id);
             return Some(job.id.as_str().to_string());
@@ -258,6 +308,16 @@ impl WorkerContext {
         None
     }
 
+    /// Persist the paused status. The token is left cancelled (and the
+    /// pause flag set) until `JobProcessor::resume` clears it, so a stale
+    /// copy of the job still sitting in the dispatch channel bails
+    /// straight back here instead of running.
+    async fn park(&self, job: &Job, stage: &'static str) -> Result<String> {
+        self.job_queue.mark_paused(&job.id).await?;
+        tracing::info!("Job {} paused {}", job.id, stage);
+        Ok(job.id.as_str().to_string())
+    }
+
     async fn process_job(&self, job: Job) -> Result<String> {
         // Enforce the lifecycle invariant at the entry: `process_job`
         // must only be handed jobs that are actually in `Pending`. The
@@ -278,70 +338,163 @@ impl WorkerContext {
             }
         };
 
-        // `job` is owned and dropped at function exit. The lifecycle is
-        // tracked in the DB via `mark_*` (which call the JobRepository
-        // typed-result methods); the local `job.status` field is only
-        // read for branching here, never re-persisted, so we no longer
-        // mutate it. The previous `job.status = JobStatus::X` writes
-        // were dead code that misled readers into thinking they
+        // Registered before the first cancellation check so a concurrent
+        // `JobProcessor::pause` either sees the job as running (and leaves
+        // the status write to us) or we see its cancelled token.
+        self.canceler.mark_running(&job.id);
+        let result = self.run_job(&job).await;
+        self.canceler.mark_stopped(&job.id);
+        result
+    }
+
+    async fn run_job(&self, job: &Job) -> Result<String> {
+        // `job` is owned by the caller and dropped at function exit. The
+        // lifecycle is tracked in the DB via `mark_*` (which call the
+        // JobRepository typed-result methods); the local `job.status`
+        // field is only read for branching here, never re-persisted, so
+        // we no longer mutate it. The previous `job.status = JobStatus::X`
+        // writes were dead code that misled readers into thinking they
         // mattered.
         let timer = JobTimer::start(&job.id);
         let cancel_token = self.canceler.get_token(&job.id);
 
-        if let Some(id) = self.bail_if_cancelled(&job, "before crawl") {
+        if let Some(id) = self.bail_if_cancelled(job, "before crawl") {
             return Ok(id);
         }
+        if self.canceler.is_paused(&job.id) {
+            return self.park(job, "before crawl").await;
+        }
 
         let job_id_str = job.id.as_str().to_string();
 
-        self.job_queue.mark_discovery(&job.id).await?;
-
-        let resources = self.crawler.check_resources(&job.url).await?;
-        self.job_queue
-            .update_resources(&job.id, resources.sitemap(), resources.robots_txt())
-            .await?;
+        // Compiled once for discovery and for normalizing the links stored
+        // at the end.
+        let url_rules = match job.settings.url_rules.compile() {
+            Ok(rules) => Arc::new(rules),
+            Err(e) => {
+                self.job_queue
+                    .mark_failed(&job.id, &format!("Invalid URL rules: {e}"))
+                    .await?;
+                self.canceler.cleanup(&job.id);
+                return Err(e.into());
+            }
+        };
 
-        let crawl_context = CrawlContext {
-            job_id: job_id_str.clone(),
-            settings: job.settings.clone(),
-            start_url: job.url.clone(),
-            cancel_token: cancel_token.clone(),
+        // Every request the run makes goes through the spiders built here
+        // from the job's crawl profile and HTTP configuration.
+        let config = self.job_queue.http_config(&job.id).await?;
+        let spiders = match self.job_spiders(job, config.as_ref()) {
+            Ok(spiders) => spiders,
+            Err(e) => {
+                self.job_queue
+                    .mark_failed(&job.id, &format!("Invalid crawl configuration: {e:#}"))
+                    .await?;
+                self.canceler.cleanup(&job.id);
+                return Err(e);
+            }
         };
+        let crawler = Crawler::new(spiders.pages.clone());
+
+        // A frontier means this job was paused before and has been
+        // resumed: the site resources were already checked and part of
+        // the page queue may already be analysed.
+        let frontier = self.page_queue_manager.load_frontier(&job.id).await?;
+        if frontier.is_some() {
+            let requeued = self.page_queue_manager.reset_processing_pages(&job.id).await?;
+            tracing::info!("Job {}: resuming ({} in-flight pages requeued)", job.id, requeued);
+        }
 
-        let discovered_pages = self
-            .crawler
-            .discover_pages(&crawl_context, self.progress_emitter.clone())
-            .await?;
+        // Paces the probes' requests and the analysis's live fetches.
+        let host_limiter = Arc::new(HostRateLimiter::new(std::time::Duration::from_millis(
+            job.settings.delay_between_requests.max(0) as u64,
+        )));
 
-        if let Some(id) = self.bail_if_cancelled(&job, "before analysis") {
-            return Ok(id);
+        self.inspect_tls(job, config.as_ref()).await?;
+        if frontier.is_none() {
+            self.probe_delivery(job, &spiders).await?;
         }
+        let not_found = self
+            .probe_not_found(job, &spiders, &host_limiter, frontier.is_none())
+            .await?;
+        let origins = self.probe_origins(job, &spiders, frontier.is_none()).await?;
 
         let max_pages = job.settings.max_pages as usize;
-        let pages_to_queue: Vec<_> = discovered_pages.into_iter().take(max_pages).collect();
-        self.page_queue_manager
-            .insert_discovered_pages(
-                &job.id,
-                &pages_to_queue,
-                crate::contexts::analysis::Depth::root(),
-            )
-            .await?;
 
-        tracing::info!(
-            "Job {}: Discovery returned {} pages, queued {} (max_pages={})",
-            job.id,
-            pages_to_queue.len(),
-            pages_to_queue.len(),
-            max_pages,
-        );
-        if pages_to_queue.is_empty() {
-            tracing::error!("Job {}: No pages to analyze — discovery returned nothing!", job.id);
+        // List-mode jobs had their pages queued at creation; only spider
+        // jobs with discovery still outstanding run the crawler.
+        let needs_discovery = job.settings.crawl_mode == CrawlMode::Spider
+            && !frontier.as_ref().is_some_and(|f| f.discovery_complete);
+
+        if needs_discovery {
+            self.job_queue.mark_discovery(&job.id).await?;
+
+            if frontier.is_none() {
+                let site_checks = crawler.check_resources(&job.url).await?;
+                self.job_queue.save_site_checks(&job.id, &site_checks).await?;
+                let issues: Vec<_> =
+                    site_checks.iter().filter_map(|check| check.issue(&job_id_str)).collect();
+                self.analyzer.record_site_issues(&issues).await?;
+            }
+
+            let crawl_context = CrawlContext {
+                job_id: job_id_str.clone(),
+                settings: job.settings.clone(),
+                start_url: job.url.clone(),
+                url_rules: url_rules.clone(),
+                cancel_token: cancel_token.clone(),
+                resume: frontier,
+            };
+
+            let outcome = crawler
+                .discover_pages(&crawl_context, self.progress_emitter.clone())
+                .await?;
+
+            if let Some(id) = self.bail_if_cancelled(job, "before analysis") {
+                return Ok(id);
+            }
+
+            let already_queued = self.page_queue_manager.total_count(&job.id).await? as usize;
+            let pages_to_queue: Vec<_> = outcome
+                .pages
+                .into_iter()
+                .take(max_pages.saturating_sub(already_queued))
+                .collect();
+            self.page_queue_manager
+                .insert_discovered_pages(
+                    &job.id,
+                    &pages_to_queue,
+                    crate::contexts::analysis::Depth::root(),
+                )
+                .await?;
+
+            tracing::info!(
+                "Job {}: Discovery returned {} pages, queued {} (max_pages={})",
+                job.id,
+                pages_to_queue.len(),
+                pages_to_queue.len(),
+                max_pages,
+            );
+
+            if let Some(frontier) = outcome.interrupted {
+                self.page_queue_manager.save_frontier(&job.id, &frontier).await?;
+                return self.park(job, "during discovery").await;
+            }
+
+            if pages_to_queue.is_empty() && already_queued == 0 {
+                tracing::error!("Job {}: No pages to analyze — discovery returned nothing!", job.id);
+            }
         }
 
         self.job_queue.mark_processing(&job.id).await?;
 
-        let auditor = self.analyzer.select_auditor(&job.settings);
-        let total_pages = pages_to_queue.len();
+        let weigher = job
+            .settings
+            .resource_analysis
+            .then(|| Arc::new(PageWeigher::new(spiders.pages.clone())));
+        let auditor = self.analyzer.select_auditor_for(&job.settings, spiders.own);
+        let total_pages = self.page_queue_manager.total_count(&job.id).await? as usize;
+        let pending_pages = self.page_queue_manager.pending_count(&job.id).await? as usize;
+        let already_analyzed = total_pages.saturating_sub(pending_pages);
 
         // ── Parallel analysis ────────────────────────────────────────
         //
@@ -357,15 +510,15 @@ impl WorkerContext {
         let concurrency = num_cpus::get().clamp(2, 8);
         tracing::info!(
             "Job {}: Analyzing {} pages with concurrency {}",
-            job.id, total_pages, concurrency,
+            job.id, pending_pages, concurrency,
         );
 
         let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
-        let pages_analyzed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let pages_analyzed = Arc::new(std::sync::atomic::AtomicUsize::new(already_analyzed));
         let crawl_links = Arc::new(tokio::sync::Mutex::new(Vec::<NewLink>::new()));
         let was_cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
 
-        let mut handles: Vec<tokio::task::JoinHandle<()>> = Vec::with_capacity(total_pages);
+        let mut handles: Vec<tokio::task::JoinHandle<()>> = Vec::with_capacity(pending_pages);
 
         // Extract Arc-wrapped shared state outside the loop to avoid
         // re-cloning per iteration for fields that don't change.
@@ -374,9 +527,13 @@ impl WorkerContext {
         while let Some(mut page_item) = self.page_queue_manager.claim_next_page(&job.id).await? {
             if cancel_token.is_cancelled() {
                 tracing::info!("Job {} cancelled during analysis", job.id);
-                self.page_queue_manager
-                    .mark_failed(&page_item.id, "Job cancelled")
-                    .await?;
+                // A paused job's claimed page is put back to pending
+                // after the in-flight tasks drain; only a cancel fails it.
+                if !self.canceler.is_paused(&job.id) {
+                    self.page_queue_manager
+                        .mark_failed(&page_item.id, "Job cancelled")
+                        .await?;
+                }
                 was_cancelled.store(true, std::sync::atomic::Ordering::Relaxed);
                 break;
             }
@@ -386,25 +543,35 @@ impl WorkerContext {
 
             let analyzer = self.analyzer.clone();
             let auditor = auditor.clone();
+            let weigher = weigher.clone();
+            let not_found = not_found.clone();
             let page_queue_manager = self.page_queue_manager.clone();
             let job_queue = self.job_queue.clone();
             let progress_emitter = self.progress_emitter.clone();
+            let canceler = self.canceler.clone();
             let job_id = Arc::clone(&job_id_arc);
             let cancel = cancel_token.clone();
             let pages_analyzed = pages_analyzed.clone();
             let crawl_links = crawl_links.clone();
+            let host_limiter = host_limiter.clone();
 
             handles.push(tokio::spawn(async move {
                 let _permit = permit; // held until this task completes
 
                 if cancel.is_cancelled() {
-                    let _ = page_queue_manager
-                        .mark_failed(&page_item.id, "Job cancelled")
-                        .await;
+                    if !canceler.is_paused(&job_id) {
+                        let _ = page_queue_manager
+                            .mark_failed(&page_item.id, "Job cancelled")
+                            .await;
+                    }
                     return;
                 }
 
                 // Use cached HTML from discovery if available
+                let site = SiteContext {
+                    weigher: weigher.as_deref(),
+                    not_found: not_found.as_deref(),
+                };
                 let analysis = if let (Some(html), Some(status), Some(load_time)) = (
                     page_item.cached_html.take(),
                     page_item.http_status,
@@ -417,13 +584,23 @@ impl WorkerContext {
                         final_url,
                         status_code: status,
                         load_time_ms: load_time,
+                        headers: std::mem::take(&mut page_item.cached_headers),
+                        timing: page_item.cached_timing,
                     };
                     analyzer
-                        .analyze_page_cached(&page_item.url, &job_id, page_item.depth, &auditor, cached)
+                        .analyze_page_cached(
+                            &page_item.url,
+                            &job_id,
+                            page_item.depth,
+                            &auditor,
+                            site,
+                            cached,
+                        )
                         .await
                 } else {
+                    host_limiter.until_ready(&page_item.url).await;
                     analyzer
-                        .analyze_page(&page_item.url, &job_id, page_item.depth, &auditor)
+                        .analyze_page(&page_item.url, &job_id, page_item.depth, &auditor, site)
                         .await
                 };
 
@@ -470,15 +647,56 @@ impl WorkerContext {
         }
 
         let collected_links: Vec<NewLink> = crawl_links.lock().await.drain(..).collect();
-        self.persist_links(&collected_links).await?;
+        self.persist_links(collected_links, &url_rules).await?;
+
+        if self.canceler.is_paused(&job.id) {
+            // Pages claimed but not analysed go back to pending. If the
+            // pause arrived after the last page finished there is nothing
+            // left to resume, so the job completes normally below.
+            self.page_queue_manager.reset_processing_pages(&job.id).await?;
+            if self.page_queue_manager.pending_count(&job.id).await? > 0 {
+                self.page_queue_manager
+                    .save_frontier(&job.id, &DiscoveryFrontier::completed())
+                    .await?;
+                let id = self.park(job, "during analysis").await?;
 
-        if was_cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+                let done = pages_analyzed.load(std::sync::atomic::Ordering::Relaxed);
+                self.progress_emitter.emit(ProgressEvent::Analysis {
+                    job_id: id.clone(),
+                    progress: (done as f64 / total_pages.max(1) as f64) * 100.0,
+                    pages_analyzed: done,
+                    total_pages,
+                });
+                return Ok(id);
+            }
+        }
+
+        // Links are judged once every page's links are stored, including
+        // those from before a pause.
+        if let Some(origins) = &origins {
+            let links = self.link_db.get_by_job_id(job.id.as_str()).await?;
+            self.analyzer
+                .record_site_issues(&origins.link_issues(job.id.as_str(), &links))
+                .await?;
+        }
+
+        // Site-scoped custom checks aggregate over every analysed page,
+        // which a cancelled job never got to.
+        let cancelled = was_cancelled.load(std::sync::atomic::Ordering::Relaxed)
+            && !self.canceler.is_paused(&job.id);
+        if !cancelled {
+            self.analyzer.run_aggregate_checks(job.id.as_str()).await?;
+        }
+
+        if cancelled {
             self.job_queue.mark_cancelled(&job.id).await?;
             tracing::info!("Job {} cancelled after {}ms", job.id, timer.elapsed_ms());
         } else {
             self.job_queue.mark_completed(&job.id).await?;
             tracing::info!("Job {} completed in {}ms", job.id, timer.elapsed_ms());
         }
+        self.page_queue_manager.clear_frontier(&job.id).await?;
+        self.analyzer.prune_snapshots().await;
 
         // Emit a final progress event AFTER the job status is persisted
         // so the frontend refreshes the job list and sees the new status
@@ -495,15 +713,126 @@ impl WorkerContext {
         Ok(job.id.as_str().to_string())
     }
 
-    async fn persist_links(&self, links: &[NewLink]) -> Result<()> {
+    /// The spiders for one run of `job`. Jobs with their own crawl
+    /// profile, or with custom headers, cookies, credentials, a proxy or
+    /// host overrides, get their own; the rest share the processor's.
+    fn job_spiders(&self, job: &Job, config: Option<&HttpConfig>) -> Result<JobSpiders> {
+        let profile = &job.settings.crawl_profile;
+        let own: Option<Arc<dyn SpiderAgent>> = if config.is_none() && profile.is_default() {
+            None
+        } else {
+            Some(Arc::new(Spider::for_job(profile, config, &job.url)?))
+        };
+        Ok(JobSpiders {
+            pages: own.clone().unwrap_or_else(|| self.spider.clone()),
+            own,
+            raw: Arc::new(Spider::for_probe(profile, config, &job.url)?),
+            redirects: Arc::new(Spider::for_redirects(profile, config, &job.url)?),
+        })
+    }
+
+    /// Inspect the site's certificate once per job and record what's
+    /// wrong with it as site-level issues. Jobs behind a proxy aren't
+    /// inspected, since the inspection connects directly and would judge a
+    /// different route than the crawl takes; the skip is recorded instead.
+    async fn inspect_tls(&self, job: &Job, config: Option<&HttpConfig>) -> Result<()> {
+        if self.job_queue.tls_inspection(&job.id).await?.is_some() {
+            return Ok(());
+        }
+
+        let inspection = if config.is_some_and(|config| config.proxy.is_some()) {
+            tls::skip(
+                &job.url,
+                "The job connects through a proxy, so the certificate the site serves directly wasn't checked.",
+            )
+        } else if let Some(inspector) = &self.tls_inspector {
+            let hosts = config.map_or(&[][..], |config| config.hosts.as_slice());
+            inspector.inspect(&job.url, hosts).await
+        } else {
+            tls::skip(&job.url, "The verifying TLS client couldn't be set up.")
+        };
+        let Some(inspection) = inspection else {
+            return Ok(());
+        };
+        self.job_queue.save_tls_inspection(&job.id, &inspection).await?;
+        self.analyzer
+            .record_site_issues(&inspection.issues(job.id.as_str(), chrono::Utc::now()))
+            .await
+    }
+
+    /// Check how the start page and its assets are compressed and cached,
+    /// once per job (a resumed job already has the findings).
+    async fn probe_delivery(&self, job: &Job, spiders: &JobSpiders) -> Result<()> {
+        let probe = DeliveryProbe::new(spiders.pages.clone(), spiders.raw.clone());
+        let issues = probe.audit(job.id.as_str(), &job.url).await;
+        self.analyzer.record_site_issues(&issues).await
+    }
+
+    /// Learn how the site answers for pages that don't exist. The probe
+    /// runs on every start so a resumed job can still classify its pages,
+    /// but its site-level issues are only recorded on the first.
+    async fn probe_not_found(
+        &self,
+        job: &Job,
+        spiders: &JobSpiders,
+        host_limiter: &Arc<HostRateLimiter>,
+        record_issues: bool,
+    ) -> Result<Option<Arc<NotFoundProfile>>> {
+        let probe = NotFoundProbe::new(spiders.pages.clone(), host_limiter.clone());
+        let Some(profile) = probe.probe(&job.url).await else {
+            return Ok(None);
+        };
+        if record_issues {
+            self.analyzer.record_site_issues(&profile.site_issues(job.id.as_str())).await?;
+        }
+        Ok(Some(Arc::new(profile)))
+    }
+
+    /// Check that the site's host and protocol variants all redirect to
+    /// one origin. Like the not-found probe it runs on every start, since
+    /// the crawl's links are judged against its canonical origin at the
+    /// end, but its own issues are only recorded on the first.
+    async fn probe_origins(
+        &self,
+        job: &Job,
+        spiders: &JobSpiders,
+        record_issues: bool,
+    ) -> Result<Option<OriginReport>> {
+        let probe = OriginProbe::new(spiders.redirects.clone());
+        let Some(report) = probe.probe(&job.url).await else {
+            return Ok(None);
+        };
+        if record_issues {
+            self.analyzer.record_site_issues(&report.site_issues(job.id.as_str())).await?;
+        }
+        Ok(Some(report))
+    }
+
+    async fn persist_links(&self, mut links: Vec<NewLink>, rules: &CompiledUrlRules) -> Result<()> {
         if links.is_empty() {
             return Ok(());
         }
-        self.link_db.insert_batch(links).await?;
+        for link in &mut links {
+            link.normalize_target(rules);
+        }
+        self.link_db.insert_batch(&links).await?;
         Ok(())
     }
 }
 
+/// The clients one run of a job fetches through, built once from the job's
+/// crawl profile and HTTP configuration.
+struct JobSpiders {
+    /// The job's own spider; `None` when the shared one sends the same requests.
+    own: Option<Arc<dyn SpiderAgent>>,
+    /// Fetches pages, for the crawl, the analysis and the probes.
+    pages: Arc<dyn SpiderAgent>,
+    /// Fetches without decoding, for the delivery probe's headers.
+    raw: Arc<dyn SpiderAgent>,
+    /// Follows no redirects, for the origin probe.
+    redirects: Arc<dyn SpiderAgent>,
+}
+
 struct JobTimer {
     start: std::time::Instant,
 }
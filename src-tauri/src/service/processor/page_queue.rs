@@ -1,4 +1,4 @@
-use crate::contexts::{NewPageQueueItem, PageQueueItem, PageQueueStatus};
+use crate::contexts::{DiscoveryFrontier, NewPageQueueItem, PageQueueItem, PageQueueStatus};
 use crate::repository::PageQueueRepository;
 use anyhow::Result;
 use std::sync::Arc;
@@ -85,6 +85,23 @@ impl PageQueueManager {
         Ok(self.repo.reset_processing_to_pending(job_id).await?)
     }
 
+    /// Checkpoint the discovery state of a paused job.
+    pub async fn save_frontier(&self, job_id: &str, frontier: &DiscoveryFrontier) -> Result<()> {
+        self.repo.save_frontier(job_id, frontier).await?;
+        Ok(())
+    }
+
+    /// Load the checkpoint left by a previous pause, if any.
+    pub async fn load_frontier(&self, job_id: &str) -> Result<Option<DiscoveryFrontier>> {
+        Ok(self.repo.load_frontier(job_id).await?)
+    }
+
+    /// Drop the checkpoint once the job has finished or been cancelled.
+    pub async fn clear_frontier(&self, job_id: &str) -> Result<()> {
+        self.repo.delete_frontier(job_id).await?;
+        Ok(())
+    }
+
     /// Get progress information for a job.
     pub async fn get_progress(&self, job_id: &str) -> Result<PageQueueProgress> {
         let pending = self.repo.count_pending(job_id).await?;
@@ -220,6 +237,28 @@ mod tests {
         ) -> crate::repository::RepositoryResult<bool> {
             Ok(self.pending_count == 0)
         }
+
+        async fn save_frontier(
+            &self,
+            _job_id: &str,
+            _frontier: &DiscoveryFrontier,
+        ) -> crate::repository::RepositoryResult<()> {
+            Ok(())
+        }
+
+        async fn load_frontier(
+            &self,
+            _job_id: &str,
+        ) -> crate::repository::RepositoryResult<Option<DiscoveryFrontier>> {
+            Ok(None)
+        }
+
+        async fn delete_frontier(
+            &self,
+            _job_id: &str,
+        ) -> crate::repository::RepositoryResult<()> {
+            Ok(())
+        }
     }
 
     #[tokio::test]
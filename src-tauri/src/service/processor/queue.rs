@@ -1,3 +1,4 @@
+use crate::contexts::analysis::{HttpConfig, SiteCheckResult, TlsInspection};
 use crate::contexts::{Job, JobStatus};
 use crate::repository::JobRepository as JobRepositoryTrait;
 use crate::service::processor::channel::{JobChannel, JobChannelConfig, JobNotifier};
@@ -209,6 +210,20 @@ impl JobQueue {
         self.transition(job_id, JobStatus::Cancelled).await
     }
 
+    /// Unlike the other transitions this one is conditional: a job that
+    /// finished or was cancelled in the meantime isn't paused, and the
+    /// call fails.
+    pub async fn mark_paused(&self, job_id: &str) -> Result<()> {
+        self.repo.pause(job_id).await?;
+        Ok(())
+    }
+
+    /// Requeue a paused job. The row goes back to `pending` so the
+    /// normal dispatch path picks it up again.
+    pub async fn mark_pending(&self, job_id: &str) -> Result<()> {
+        self.transition(job_id, JobStatus::Pending).await
+    }
+
     pub async fn cancel_all_running_jobs(&self) -> Result<()> {
         let jobs = self.repo.get_running_jobs_id().await?;
         tracing::info!("Cancelling {} running jobs", jobs.len());
@@ -229,15 +244,22 @@ impl JobQueue {
         Ok(())
     }
 
-    pub async fn update_resources(
-        &self,
-        job_id: &str,
-        sitemap_found: bool,
-        robots_txt_found: bool,
-    ) -> Result<()> {
-        self.repo
-            .update_resources(job_id, sitemap_found, robots_txt_found)
-            .await?;
+    pub async fn save_site_checks(&self, job_id: &str, checks: &[SiteCheckResult]) -> Result<()> {
+        self.repo.set_site_checks(job_id, checks).await?;
+        Ok(())
+    }
+
+    /// The job's HTTP configuration, if it was created with one.
+    pub async fn http_config(&self, job_id: &str) -> Result<Option<HttpConfig>> {
+        Ok(self.repo.get_http_config(job_id).await?)
+    }
+
+    pub async fn tls_inspection(&self, job_id: &str) -> Result<Option<TlsInspection>> {
+        Ok(self.repo.get_tls_inspection(job_id).await?)
+    }
+
+    pub async fn save_tls_inspection(&self, job_id: &str, inspection: &TlsInspection) -> Result<()> {
+        self.repo.set_tls_inspection(job_id, inspection).await?;
         Ok(())
     }
 }
@@ -462,6 +462,9 @@ mod tests {
                 response_size_bytes: None, has_viewport: false,
                 has_structured_data: false, crawled_at: Utc::now(),
                 extracted_data,
+                response_headers: Default::default(),
+                ttfb_ms: None, download_ms: None,
+                weight: None,
             }
         };
 
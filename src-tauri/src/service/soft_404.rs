@@ -0,0 +1,445 @@
+//! Soft-404 detection: how the site answers for pages that don't exist,
+//! and which crawled pages are really its error page served with a 200.
+//!
+//! At job start a [`NotFoundProbe`] requests a few random URLs that can't
+//! exist on the site. Their answers make up a [`NotFoundProfile`], which
+//! reports a server that never sends a real 404 and classifies crawled
+//! pages that match the error page or read like one.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use url::Url;
+
+use crate::contexts::analysis::{IssueBuilder, IssueSeverity, NewIssue};
+use crate::extractor::page_extractor::PageExtractor;
+use crate::service::processor::HostRateLimiter;
+use crate::service::spider::SpiderAgent;
+
+/// Pages this similar to an error page's text are that error page.
+const TEMPLATE_SIMILARITY: f64 = 0.9;
+
+/// Error pages with less text than this (an empty app shell, a bare
+/// "Not Found") would match every thin page; they aren't compared.
+const MIN_TEMPLATE_WORDS: usize = 20;
+
+/// A page saying "not found" with more text than this is probably an
+/// article about something that wasn't found.
+const MAX_SIGNAL_WORDS: usize = 250;
+
+/// A bare "404" is also a product code or a price, so it only counts
+/// beside "not found" (already a phrase of its own) or one of these.
+const NOT_FOUND_PHRASES: &[&str] = &[
+    "not found",
+    "doesn't exist",
+    "does not exist",
+    "no longer exists",
+    "no longer available",
+    "cannot be found",
+    "can't be found",
+    "could not be found",
+];
+
+/// Words that make a "404" in a title or heading the error code.
+const STATUS_CODE_WORDS: &[&str] = &["error", "page", "http"];
+
+/// Words per shingle when comparing page text.
+const SHINGLE_WORDS: usize = 3;
+
+/// What the server sent for one URL that can't exist.
+#[derive(Debug, Clone)]
+pub struct ProbeAnswer {
+    pub url: String,
+    pub status: u16,
+    /// Where the request ended up after redirects.
+    pub final_url: String,
+    pub body_text: String,
+}
+
+impl ProbeAnswer {
+    fn redirected(&self) -> bool {
+        self.final_url != self.url
+    }
+
+    fn is_not_found(&self) -> bool {
+        matches!(self.status, 404 | 410)
+    }
+
+    /// Refused rather than answered: an auth wall, or a firewall or rate
+    /// limit turning the probe away. It says nothing about missing pages.
+    fn is_refused(&self) -> bool {
+        matches!(self.status, 401 | 403 | 406 | 429 | 503)
+    }
+}
+
+/// How the site answers for missing pages.
+#[derive(Debug, Clone)]
+pub struct NotFoundProfile {
+    answers: Vec<ProbeAnswer>,
+    /// Shingles of each error page served at the missing URL itself.
+    templates: Vec<HashSet<u64>>,
+}
+
+impl NotFoundProfile {
+    pub fn new(answers: Vec<ProbeAnswer>) -> Self {
+        // A missing URL redirected to the home page would make the home
+        // page its own error template.
+        let templates = answers
+            .iter()
+            .filter(|answer| !answer.redirected() && !answer.is_refused())
+            .filter(|answer| answer.body_text.split_whitespace().count() >= MIN_TEMPLATE_WORDS)
+            .map(|answer| shingles(&answer.body_text))
+            .collect();
+        Self { answers, templates }
+    }
+
+    /// At least one missing URL got a 404 or 410.
+    pub fn returns_not_found(&self) -> bool {
+        self.answers.iter().any(ProbeAnswer::is_not_found)
+    }
+
+    /// Site-level issues for the job (no page attached). A probe that was
+    /// refused leaves the question open, so nothing is reported.
+    pub fn site_issues(&self, job_id: &str) -> Vec<NewIssue> {
+        let Some(first) = self.answers.first() else {
+            return Vec::new();
+        };
+        if self.returns_not_found() {
+            return Vec::new();
+        }
+        if self.answers.iter().any(ProbeAnswer::is_refused) {
+            tracing::debug!("[SOFT404] {}: probe refused, 404 handling not judged", first.url);
+            return Vec::new();
+        }
+
+        // A 200 at the missing URL is the worst case: every broken link
+        // is an indexable duplicate of the error page.
+        let served = self
+            .answers
+            .iter()
+            .any(|answer| !answer.redirected() && (200..300).contains(&answer.status));
+        let severity = if served { IssueSeverity::Critical } else { IssueSeverity::Warning };
+        let host = Url::parse(&first.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| first.url.clone());
+        let answers: Vec<String> = self
+            .answers
+            .iter()
+            .map(|answer| {
+                if answer.redirected() {
+                    format!("{} → redirected to {} ({})", answer.url, answer.final_url, answer.status)
+                } else {
+                    format!("{} → {}", answer.url, answer.status)
+                }
+            })
+            .collect();
+
+        vec![IssueBuilder::new(
+            job_id.to_string(),
+            "missing_404_status".to_string(),
+            severity,
+            format!("{host} never answers 404 for pages that don't exist"),
+        )
+        .details(format!(
+            "Status checks can't spot broken pages and search engines may index \
+             error pages as soft 404s. Serve missing URLs with a 404 or 410 status. \
+             Requests made: {}",
+            answers.join("; ")
+        ))
+        .build()]
+    }
+
+    /// Why a crawled page looks like a soft 404, or `None`. Only 2xx pages
+    /// other than the home page are judged.
+    pub fn classify(
+        &self,
+        url: &str,
+        status: Option<i64>,
+        title: Option<&str>,
+        h1: Option<&str>,
+        body_text: &str,
+    ) -> Option<String> {
+        if !status.is_some_and(|status| (200..300).contains(&status)) {
+            return None;
+        }
+        if Url::parse(url).is_ok_and(|url| url.path() == "/") {
+            return None;
+        }
+
+        if !self.templates.is_empty() {
+            let page = shingles(body_text);
+            let similarity = self
+                .templates
+                .iter()
+                .map(|template| jaccard(template, &page))
+                .fold(0.0, f64::max);
+            if similarity >= TEMPLATE_SIMILARITY {
+                return Some(format!(
+                    "Page returns 200 but its content is {:.0}% identical to the site's error page",
+                    similarity * 100.0
+                ));
+            }
+        }
+
+        let words = body_text.split_whitespace().count();
+        if words <= MAX_SIGNAL_WORDS {
+            let signal = [title, h1]
+                .into_iter()
+                .flatten()
+                .find(|text| says_not_found(text));
+            if let Some(text) = signal {
+                return Some(format!(
+                    "Page returns 200 but reads like an error page (\"{}\", {} words)",
+                    text.trim(),
+                    words
+                ));
+            }
+        }
+        None
+    }
+}
+
+/// Requests a few URLs that can't exist on the site, paced like the
+/// job's other fetches.
+pub struct NotFoundProbe {
+    spider: Arc<dyn SpiderAgent>,
+    limiter: Arc<HostRateLimiter>,
+}
+
+impl NotFoundProbe {
+    pub fn new(spider: Arc<dyn SpiderAgent>, limiter: Arc<HostRateLimiter>) -> Self {
+        Self { spider, limiter }
+    }
+
+    /// Probe the site `start_url` belongs to. `None` when no probe got an
+    /// answer; the crawl reports an unreachable site.
+    pub async fn probe(&self, start_url: &str) -> Option<NotFoundProfile> {
+        let Ok(start) = Url::parse(start_url) else {
+            return None;
+        };
+        let mut answers = Vec::new();
+        for url in probe_urls(&start) {
+            self.limiter.until_ready(url.as_str()).await;
+            match self.spider.get(url.as_str()).await {
+                Ok(response) => answers.push(ProbeAnswer {
+                    url: url.to_string(),
+                    status: response.status,
+                    body_text: body_text(&response.body),
+                    final_url: response.url,
+                }),
+                Err(e) => tracing::debug!("[SOFT404] Probe of {} failed: {:#}", url, e),
+            }
+        }
+        tracing::debug!(
+            "[SOFT404] {}: {} probes answered, statuses {:?}",
+            start_url,
+            answers.len(),
+            answers.iter().map(|a| a.status).collect::<Vec<_>>()
+        );
+        (!answers.is_empty()).then(|| NotFoundProfile::new(answers))
+    }
+}
+
+/// A random page at the root, one with an extension, and one beside the
+/// start page — sites often route each of those differently.
+fn probe_urls(start: &Url) -> Vec<Url> {
+    let token = || uuid::Uuid::new_v4().simple().to_string();
+    [
+        format!("/{}", token()),
+        format!("/{}.html", token()),
+        token(),
+    ]
+    .iter()
+    .filter_map(|path| start.join(path).ok())
+    .collect()
+}
+
+/// Parsed documents aren't `Send`, so parsing stays out of async code.
+fn body_text(html: &str) -> String {
+    PageExtractor::extract_body_text(&scraper::Html::parse_document(html))
+}
+
+fn says_not_found(text: &str) -> bool {
+    let text = text.to_lowercase();
+    if NOT_FOUND_PHRASES.iter().any(|phrase| text.contains(phrase)) {
+        return true;
+    }
+    // "404", "Error 404", "404 - Page": the code and nothing it could be
+    // part of.
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.contains(&"404")
+        && words
+            .iter()
+            .all(|word| *word == "404" || STATUS_CODE_WORDS.contains(word))
+}
+
+fn shingles(text: &str) -> HashSet<u64> {
+    let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+    words
+        .windows(SHINGLE_WORDS.min(words.len().max(1)))
+        .map(|window| {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERROR_PAGE: &str = "Home Blog Shop About Sorry, we couldn't find that page. \
+        It may have moved or been deleted. Try searching the site or head back to \
+        the home page. Popular: guides, pricing, support, contact. © Example Ltd";
+
+    fn answer(url: &str, status: u16, final_url: &str, text: &str) -> ProbeAnswer {
+        ProbeAnswer {
+            url: url.into(),
+            status,
+            final_url: final_url.into(),
+            body_text: text.into(),
+        }
+    }
+
+    fn soft_site() -> NotFoundProfile {
+        NotFoundProfile::new(vec![
+            answer("https://example.com/abc", 200, "https://example.com/abc", ERROR_PAGE),
+            answer("https://example.com/abc.html", 200, "https://example.com/", "Welcome home"),
+        ])
+    }
+
+    #[test]
+    fn site_without_real_404s_is_reported() {
+        let issues = soft_site().site_issues("j1");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_type, "missing_404_status");
+        assert_eq!(issues[0].severity, IssueSeverity::Critical);
+        assert!(issues[0].details.as_deref().unwrap().contains("redirected to https://example.com/"));
+    }
+
+    #[test]
+    fn a_real_404_means_no_site_issue() {
+        let profile = NotFoundProfile::new(vec![
+            answer("https://example.com/abc", 404, "https://example.com/abc", ERROR_PAGE),
+            answer("https://example.com/x.html", 200, "https://example.com/x.html", ERROR_PAGE),
+        ]);
+        assert!(profile.site_issues("j1").is_empty());
+    }
+
+    #[test]
+    fn pages_matching_the_error_template_are_soft_404s() {
+        let profile = soft_site();
+        let reason = profile
+            .classify("https://example.com/old-product", Some(200), Some("Example"), None, ERROR_PAGE)
+            .unwrap();
+        assert!(reason.contains("error page"));
+
+        let range: Vec<String> = (0..60).map(|i| format!("chair-{i}")).collect();
+        let article = format!("{ERROR_PAGE} {}", range.join(" "));
+        assert!(profile.classify("https://example.com/garden", Some(200), None, None, &article).is_none());
+    }
+
+    #[test]
+    fn thin_pages_that_say_not_found_are_soft_404s() {
+        let profile = NotFoundProfile::new(Vec::new());
+        let reason = profile.classify(
+            "https://example.com/gone",
+            Some(200),
+            Some("Page Not Found | Example"),
+            None,
+            "Nothing here",
+        );
+        assert!(reason.unwrap().contains("Page Not Found"));
+
+        let long = "word ".repeat(600);
+        assert!(profile
+            .classify("https://example.com/post", Some(200), Some("Why 404 pages matter"), None, &long)
+            .is_none());
+    }
+
+    #[test]
+    fn a_bare_404_counts_only_as_an_error_code() {
+        assert!(says_not_found("404"));
+        assert!(says_not_found("Error 404"));
+        assert!(says_not_found("404 - Page"));
+        assert!(says_not_found("404: Page not found"));
+        assert!(!says_not_found("Peugeot 404 Coupé"));
+        assert!(!says_not_found("Model KX-404 only $404"));
+        assert!(!says_not_found("Error 4041"));
+    }
+
+    #[test]
+    fn refused_probes_are_inconclusive() {
+        for status in [401, 403, 429] {
+            let profile = NotFoundProfile::new(vec![
+                answer("https://example.com/abc", status, "https://example.com/abc", ERROR_PAGE),
+                answer("https://example.com/x.html", 200, "https://example.com/", "Welcome home"),
+            ]);
+            assert!(profile.site_issues("j1").is_empty(), "status {status}");
+            assert!(profile.templates.is_empty(), "status {status}");
+        }
+    }
+
+    #[tokio::test]
+    async fn probes_wait_for_the_host_rate_limiter() {
+        use crate::service::spider::{MockSpider, SpiderResponse};
+
+        let spider = Arc::new(MockSpider {
+            html_response: String::new(),
+            generic_response: SpiderResponse {
+                status: 404,
+                body: String::new(),
+                url: "https://example.com/missing".into(),
+                headers: Default::default(),
+                timing: Default::default(),
+            },
+        });
+        let delay = std::time::Duration::from_millis(50);
+        let probe = NotFoundProbe::new(spider, Arc::new(HostRateLimiter::new(delay)));
+
+        let started = std::time::Instant::now();
+        let profile = probe.probe("https://example.com/").await.unwrap();
+
+        assert!(profile.returns_not_found());
+        assert!(started.elapsed() >= delay * 2, "three probes take two delays");
+    }
+
+    #[test]
+    fn real_errors_and_the_home_page_are_not_judged() {
+        let profile = soft_site();
+        assert!(profile.classify("https://example.com/x", Some(404), Some("Not found"), None, "").is_none());
+        assert!(profile.classify("https://example.com/", Some(200), None, None, ERROR_PAGE).is_none());
+    }
+
+    #[test]
+    fn redirected_and_empty_answers_are_not_templates() {
+        let profile = NotFoundProfile::new(vec![
+            answer("https://example.com/abc", 200, "https://example.com/", ERROR_PAGE),
+            answer("https://example.com/def", 200, "https://example.com/def", "<app-root>"),
+        ]);
+        assert!(profile.templates.is_empty());
+    }
+
+    #[test]
+    fn probe_urls_cover_root_extension_and_sibling_paths() {
+        let urls = probe_urls(&Url::parse("https://example.com/blog/").unwrap());
+        assert_eq!(urls.len(), 3);
+        assert!(urls[1].path().ends_with(".html"));
+        assert!(urls[2].path().starts_with("/blog/"));
+    }
+}
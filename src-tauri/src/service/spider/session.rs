@@ -0,0 +1,421 @@
+//! Per-job request decoration for [`Spider`](super::Spider): extra
+//! headers and credentials from the job's [`HttpConfig`], plus the
+//! challenge state HTTP Digest needs between requests.
+//!
+//! Headers are only attached to URLs on the job's own site (same root
+//! domain as the start URL), so outbound requests to third-party hosts
+//! never carry them. Credentials are narrower still: they only go to the
+//! start URL's origin (scheme, host and port), so another subdomain or a
+//! plain-http link never sees them. Cookies are handled by the client's
+//! cookie jar, which does its own domain scoping.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use md5::Md5;
+use rand::Rng;
+use rquest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, WWW_AUTHENTICATE};
+use rquest::RequestBuilder;
+use sha2::{Digest as _, Sha256};
+use url::{Origin, Url};
+
+use crate::contexts::analysis::{extract_root_domain, HttpAuth, HttpConfig};
+
+pub(super) struct HttpSession {
+    site: Option<String>,
+    origin: Origin,
+    headers: HeaderMap,
+    auth: Option<HttpAuth>,
+    digest: Mutex<Option<DigestChallenge>>,
+}
+
+impl HttpSession {
+    pub(super) fn new(config: &HttpConfig, start_url: &Url) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        for header in &config.headers {
+            let name = HeaderName::from_bytes(header.name.as_bytes())
+                .with_context(|| format!("Invalid header name '{}'", header.name))?;
+            let value = HeaderValue::from_str(&header.value)
+                .with_context(|| format!("Invalid value for header '{}'", header.name))?;
+            headers.append(name, value);
+        }
+
+        Ok(Self {
+            site: extract_root_domain(start_url.as_str()),
+            origin: start_url.origin(),
+            headers,
+            auth: config.auth.clone(),
+            digest: Mutex::new(None),
+        })
+    }
+
+    fn in_scope(&self, url: &str) -> bool {
+        self.site.is_some() && extract_root_domain(url) == self.site
+    }
+
+    fn in_origin(&self, url: &str) -> bool {
+        Url::parse(url).is_ok_and(|url| url.origin() == self.origin)
+    }
+
+    /// Attach the job's headers and credentials to a request for `url`.
+    pub(super) fn authorize(
+        &self,
+        mut req: RequestBuilder,
+        method: &str,
+        url: &str,
+    ) -> RequestBuilder {
+        if !self.in_scope(url) {
+            return req;
+        }
+        if !self.headers.is_empty() {
+            req = req.headers(self.headers.clone());
+        }
+        if !self.in_origin(url) {
+            return req;
+        }
+        match &self.auth {
+            Some(HttpAuth::Basic { username, password }) => {
+                req.basic_auth(username, Some(password))
+            }
+            Some(HttpAuth::Bearer { token }) => req.bearer_auth(token),
+            Some(HttpAuth::Digest { username, password }) => {
+                let digest = self.digest.lock().unwrap_or_else(|e| e.into_inner());
+                match digest.as_ref() {
+                    Some(challenge) => req.header(
+                        AUTHORIZATION,
+                        challenge.respond(username, password, method, url),
+                    ),
+                    None => req,
+                }
+            }
+            None => req,
+        }
+    }
+
+    /// Inspect a `401` for a Digest challenge. Returns `true` when a new
+    /// challenge was stored and the request is worth retrying once.
+    pub(super) fn accept_challenge(&self, url: &str, headers: &HeaderMap) -> bool {
+        if !matches!(self.auth, Some(HttpAuth::Digest { .. })) || !self.in_origin(url) {
+            return false;
+        }
+        let Some(challenge) = headers
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(DigestChallenge::parse)
+        else {
+            return false;
+        };
+
+        let mut digest = self.digest.lock().unwrap_or_else(|e| e.into_inner());
+        // The same nonce rejected twice means wrong credentials, not a
+        // stale challenge; retrying would only repeat the 401.
+        if digest
+            .as_ref()
+            .is_some_and(|d| d.nonce == challenge.nonce && !challenge.stale)
+        {
+            return false;
+        }
+        *digest = Some(challenge);
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestAlgorithm {
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess,
+}
+
+impl DigestAlgorithm {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_uppercase().as_str() {
+            "MD5" => Some(Self::Md5),
+            "MD5-SESS" => Some(Self::Md5Sess),
+            "SHA-256" => Some(Self::Sha256),
+            "SHA-256-SESS" => Some(Self::Sha256Sess),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Md5Sess => "MD5-sess",
+            Self::Sha256 => "SHA-256",
+            Self::Sha256Sess => "SHA-256-sess",
+        }
+    }
+
+    fn hash(self, data: &str) -> String {
+        match self {
+            Self::Md5 | Self::Md5Sess => hex::encode(Md5::digest(data.as_bytes())),
+            Self::Sha256 | Self::Sha256Sess => hex::encode(Sha256::digest(data.as_bytes())),
+        }
+    }
+
+    fn is_session(self) -> bool {
+        matches!(self, Self::Md5Sess | Self::Sha256Sess)
+    }
+}
+
+/// A parsed `WWW-Authenticate: Digest …` challenge (RFC 7616).
+#[derive(Debug)]
+struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: DigestAlgorithm,
+    /// Whether the server offered `qop=auth`; `auth-int` alone is not
+    /// supported and falls back to the RFC 2069 form.
+    qop_auth: bool,
+    stale: bool,
+    nonce_count: AtomicU32,
+}
+
+impl DigestChallenge {
+    fn parse(header: &str) -> Option<Self> {
+        let (scheme, params) = header.trim().split_once(char::is_whitespace)?;
+        if !scheme.eq_ignore_ascii_case("digest") {
+            return None;
+        }
+
+        let mut realm = None;
+        let mut nonce = None;
+        let mut opaque = None;
+        let mut algorithm = DigestAlgorithm::Md5;
+        let mut qop_auth = false;
+        let mut stale = false;
+
+        for (key, value) in parse_auth_params(params) {
+            match key.to_ascii_lowercase().as_str() {
+                "realm" => realm = Some(value),
+                "nonce" => nonce = Some(value),
+                "opaque" => opaque = Some(value),
+                "algorithm" => algorithm = DigestAlgorithm::parse(&value)?,
+                "qop" => {
+                    qop_auth = value
+                        .split(',')
+                        .any(|q| q.trim().eq_ignore_ascii_case("auth"))
+                }
+                "stale" => stale = value.eq_ignore_ascii_case("true"),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            realm: realm?,
+            nonce: nonce?,
+            opaque,
+            algorithm,
+            qop_auth,
+            stale,
+            nonce_count: AtomicU32::new(0),
+        })
+    }
+
+    fn respond(&self, username: &str, password: &str, method: &str, url: &str) -> String {
+        let cnonce = format!("{:016x}", rand::thread_rng().gen::<u64>());
+        self.respond_with_cnonce(username, password, method, url, &cnonce)
+    }
+
+    fn respond_with_cnonce(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        url: &str,
+        cnonce: &str,
+    ) -> String {
+        let uri = Url::parse(url)
+            .map(|u| match u.query() {
+                Some(query) => format!("{}?{}", u.path(), query),
+                None => u.path().to_string(),
+            })
+            .unwrap_or_else(|_| "/".to_string());
+        let algorithm = self.algorithm;
+
+        let mut ha1 = algorithm.hash(&format!("{username}:{}:{password}", self.realm));
+        if algorithm.is_session() {
+            ha1 = algorithm.hash(&format!("{ha1}:{}:{cnonce}", self.nonce));
+        }
+        let ha2 = algorithm.hash(&format!("{method}:{uri}"));
+
+        let mut header = format!(
+            r#"Digest username="{username}", realm="{}", nonce="{}", uri="{uri}", algorithm={}"#,
+            self.realm,
+            self.nonce,
+            algorithm.name()
+        );
+        if self.qop_auth {
+            let nc = format!(
+                "{:08x}",
+                self.nonce_count.fetch_add(1, Ordering::Relaxed) + 1
+            );
+            let response =
+                algorithm.hash(&format!("{ha1}:{}:{nc}:{cnonce}:auth:{ha2}", self.nonce));
+            header.push_str(&format!(
+                r#", response="{response}", qop=auth, nc={nc}, cnonce="{cnonce}""#
+            ));
+        } else {
+            let response = algorithm.hash(&format!("{ha1}:{}:{ha2}", self.nonce));
+            header.push_str(&format!(r#", response="{response}""#));
+        }
+        if let Some(opaque) = &self.opaque {
+            header.push_str(&format!(r#", opaque="{opaque}""#));
+        }
+        header
+    }
+}
+
+/// Split `key=value, key="quoted, value"` auth parameters.
+fn parse_auth_params(input: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        let Some((key, after_key)) = rest.split_once('=') else {
+            break;
+        };
+        let key = key.trim().trim_start_matches(',').trim().to_string();
+        let after_key = after_key.trim_start();
+
+        let (value, remainder) = if let Some(quoted) = after_key.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            value.push(escaped);
+                        }
+                    }
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+                    _ => value.push(c),
+                }
+            }
+            (value, &quoted[end..])
+        } else {
+            let end = after_key.find(',').unwrap_or(after_key.len());
+            (after_key[..end].trim().to_string(), &after_key[end..])
+        };
+
+        params.push((key, value));
+        rest = remainder.trim_start().trim_start_matches(',').trim_start();
+    }
+
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_response_matches_rfc_2617_example() {
+        let challenge = DigestChallenge::parse(
+            r#"Digest realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#,
+        )
+        .unwrap();
+
+        let header = challenge.respond_with_cnonce(
+            "Mufasa",
+            "Circle Of Life",
+            "GET",
+            "http://www.nowhere.org/dir/index.html",
+            "0a4f113b",
+        );
+
+        assert!(header.contains(r#"response="6629fae49393a05397450978507c4ef1""#));
+        assert!(header.contains("nc=00000001"));
+        assert!(header.contains(r#"opaque="5ccc069c403ebaf9f0171e9517f40e41""#));
+    }
+
+    #[test]
+    fn nonce_count_increments_per_request() {
+        let challenge =
+            DigestChallenge::parse(r#"Digest realm="r", nonce="n", qop="auth""#).unwrap();
+        challenge.respond("u", "p", "GET", "https://example.com/");
+        let second = challenge.respond("u", "p", "GET", "https://example.com/");
+        assert!(second.contains("nc=00000002"));
+    }
+
+    #[test]
+    fn parse_challenge_rejects_other_schemes_and_unknown_algorithms() {
+        assert!(DigestChallenge::parse(r#"Basic realm="r""#).is_none());
+        assert!(
+            DigestChallenge::parse(r#"Digest realm="r", nonce="n", algorithm=SHA-512"#).is_none()
+        );
+        assert!(DigestChallenge::parse(r#"Digest realm="r""#).is_none());
+    }
+
+    #[test]
+    fn headers_only_go_to_the_jobs_site() {
+        let config = HttpConfig {
+            auth: Some(HttpAuth::Bearer { token: "t".into() }),
+            ..Default::default()
+        };
+        let session =
+            HttpSession::new(&config, &Url::parse("https://www.example.com/").unwrap()).unwrap();
+
+        assert!(session.in_scope("https://example.com/a"));
+        assert!(session.in_scope("https://staging.example.com/"));
+        assert!(!session.in_scope("https://cdn.other.net/app.js"));
+    }
+
+    #[test]
+    fn credentials_only_go_to_the_start_urls_origin() {
+        let config = HttpConfig {
+            auth: Some(HttpAuth::Basic {
+                username: "u".into(),
+                password: "p".into(),
+            }),
+            ..Default::default()
+        };
+        let session =
+            HttpSession::new(&config, &Url::parse("https://www.example.com/").unwrap()).unwrap();
+        let client = rquest::Client::new();
+        let authorization = |url: &str| {
+            let req = session
+                .authorize(client.get(url), "GET", url)
+                .build()
+                .unwrap();
+            req.headers().get(AUTHORIZATION).cloned()
+        };
+
+        assert!(authorization("https://www.example.com/a?b=c").is_some());
+        assert!(authorization("https://www.example.com:443/").is_some());
+        assert!(authorization("http://www.example.com/").is_none());
+        assert!(authorization("https://staging.example.com/").is_none());
+        assert!(authorization("https://www.example.com:8443/").is_none());
+        assert!(authorization("https://cdn.other.net/app.js").is_none());
+    }
+
+    #[test]
+    fn same_nonce_twice_is_not_retried() {
+        let config = HttpConfig {
+            auth: Some(HttpAuth::Digest {
+                username: "u".into(),
+                password: "p".into(),
+            }),
+            ..Default::default()
+        };
+        let session =
+            HttpSession::new(&config, &Url::parse("https://example.com/").unwrap()).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(r#"Digest realm="r", nonce="abc", qop="auth""#),
+        );
+
+        assert!(session.accept_challenge("https://example.com/", &headers));
+        assert!(!session.accept_challenge("https://example.com/", &headers));
+    }
+}
@@ -2,13 +2,14 @@ use async_trait::async_trait;
 use std::sync::{Arc, Mutex};
 
 use crate::contexts::analysis::{
-    Issue, IssueSeverity, LighthouseData, NewHeading, NewImage, NewIssue, Page, PageInfo,
+    Heading, Image, Issue, IssueSeverity, LighthouseData, NewHeading, NewImage, NewIssue, Page,
+    PageInfo,
 };
 use crate::extractor::data_extractor::{ExtractorConfig, ExtractorRegistry};
 use crate::extractor::data_extractor::selector::SelectorExtractor;
 use crate::repository::{IssueRepository, PageRepository};
 use crate::service::auditor::{AuditResult, AuditScores, Auditor, SeoAuditDetails};
-use crate::service::processor::AnalyzerService;
+use crate::service::processor::{AnalyzerService, SiteContext};
 use anyhow::Result;
 
 // ---------------------------------------------------------------------------
@@ -47,6 +48,9 @@ impl PageRepository for MockPageRepo {
     async fn get_by_id(&self, id: &str) -> crate::repository::RepositoryResult<Page> {
         Err(crate::repository::RepositoryError::not_found("page", id))
     }
+    async fn get_by_url(&self, _: &str, url: &str) -> crate::repository::RepositoryResult<Page> {
+        Err(crate::repository::RepositoryError::not_found("page", url))
+    }
     async fn replace_headings(
         &self,
         _: &str,
@@ -76,6 +80,18 @@ impl PageRepository for MockPageRepo {
     ) -> crate::repository::RepositoryResult<Vec<LighthouseData>> {
         Ok(vec![])
     }
+    async fn get_headings_by_job_id(
+        &self,
+        _: &str,
+    ) -> crate::repository::RepositoryResult<Vec<Heading>> {
+        Ok(vec![])
+    }
+    async fn get_headings(&self, _: &str) -> crate::repository::RepositoryResult<Vec<Heading>> {
+        Ok(vec![])
+    }
+    async fn get_images(&self, _: &str) -> crate::repository::RepositoryResult<Vec<Image>> {
+        Ok(vec![])
+    }
 }
 
 struct MockIssueRepo {
@@ -122,6 +138,15 @@ impl IssueRepository for MockIssueRepo {
     ) -> crate::repository::RepositoryResult<Vec<crate::repository::IssueGroup>> {
         Ok(vec![])
     }
+    async fn replace_custom_check_issues(
+        &self,
+        _: &str,
+        _: &[(&str, &std::collections::HashMap<String, serde_json::Value>)],
+        issues: &[NewIssue],
+    ) -> crate::repository::RepositoryResult<u64> {
+        self.inserted_issues.lock().unwrap().extend_from_slice(issues);
+        Ok(0)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -143,6 +168,8 @@ impl Auditor for MockAuditor {
             load_time_ms: 100.0,
             content_size: 1000,
             scores: AuditScores { seo_details: SeoAuditDetails::default(), ..Default::default() },
+            headers: Default::default(),
+            timing: None,
         })
     }
     fn name(&self) -> &'static str { "mock" }
@@ -172,6 +199,8 @@ impl Auditor for MockAuditorWithExtractableContent {
             load_time_ms: 80.0,
             content_size: 500,
             scores: AuditScores { seo_details: SeoAuditDetails::default(), ..Default::default() },
+            headers: Default::default(),
+            timing: None,
         })
     }
     fn name(&self) -> &'static str { "mock-with-content" }
@@ -189,13 +218,13 @@ async fn analyze_page_insert_and_issue_generation() {
     let analyzer = AnalyzerService::new(
         page_repo.clone(),
         issue_repo.clone(),
-        Arc::new(crate::service::spider::MockSpider { html_response: String::new(), generic_response: crate::service::spider::SpiderResponse { status: 200, body: String::new(), url: String::new() } }),
+        Arc::new(crate::service::spider::MockSpider { html_response: String::new(), generic_response: crate::service::spider::SpiderResponse { status: 200, body: String::new(), url: String::new(), headers: Default::default(), timing: Default::default() } }),
         Arc::new(ExtractorRegistry::new()),
     );
     let auditor: Arc<dyn Auditor + Send + Sync> = Arc::new(MockAuditor);
 
     let (result, new_urls) = analyzer
-        .analyze_page("https://example.com", "job-1", crate::contexts::analysis::Depth::root(), &auditor)
+        .analyze_page("https://example.com", "job-1", crate::contexts::analysis::Depth::root(), &auditor, SiteContext::default())
         .await
         .expect("analysis should succeed");
 
@@ -218,29 +247,27 @@ async fn analyze_page_populates_extracted_data() {
 
     // Build a registry with two extractors
     let mut registry = ExtractorRegistry::new();
-    registry.register(Box::new(SelectorExtractor::new(ExtractorConfig {
-        tag: "og_title".into(),
-        selector: "meta[property='og:title']".into(),
-        attribute: Some("content".into()),
-        multiple: false,
-    })));
-    registry.register(Box::new(SelectorExtractor::new(ExtractorConfig {
-        tag: "hreflang".into(),
-        selector: "link[rel='alternate'][hreflang]".into(),
-        attribute: Some("hreflang".into()),
-        multiple: true,
-    })));
+    registry.register(Box::new(SelectorExtractor::new(ExtractorConfig::attr(
+        "og_title",
+        "meta[property='og:title']",
+        "content",
+    ))));
+    registry.register(Box::new(SelectorExtractor::new(ExtractorConfig::multi_attr(
+        "hreflang",
+        "link[rel='alternate'][hreflang]",
+        "hreflang",
+    ))));
 
     let analyzer = AnalyzerService::new(
         page_repo.clone(),
         issue_repo.clone(),
-        Arc::new(crate::service::spider::MockSpider { html_response: String::new(), generic_response: crate::service::spider::SpiderResponse { status: 200, body: String::new(), url: String::new() } }),
+        Arc::new(crate::service::spider::MockSpider { html_response: String::new(), generic_response: crate::service::spider::SpiderResponse { status: 200, body: String::new(), url: String::new(), headers: Default::default(), timing: Default::default() } }),
         Arc::new(registry),
     );
     let auditor: Arc<dyn Auditor + Send + Sync> = Arc::new(MockAuditorWithExtractableContent);
 
     analyzer
-        .analyze_page("https://example.com", "job-extractor", crate::contexts::analysis::Depth::root(), &auditor)
+        .analyze_page("https://example.com", "job-extractor", crate::contexts::analysis::Depth::root(), &auditor, SiteContext::default())
         .await
         .expect("analysis should succeed");
 
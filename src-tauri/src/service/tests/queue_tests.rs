@@ -25,6 +25,16 @@ impl JobRepository for MockJobRepo {
         Err(crate::repository::RepositoryError::not_found("job", "mock"))
     }
 
+    async fn create_with(
+        &self,
+        _url: &str,
+        _settings: &JobSettings,
+        _http_config: Option<&crate::contexts::analysis::HttpConfig>,
+        _page_urls: &[String],
+    ) -> crate::repository::RepositoryResult<String> {
+        Err(crate::repository::RepositoryError::not_found("job", "mock"))
+    }
+
     async fn get_by_id(&self, _id: &str) -> crate::repository::RepositoryResult<Job> {
         Err(crate::repository::RepositoryError::not_found("job", "mock"))
     }
@@ -64,6 +74,10 @@ impl JobRepository for MockJobRepo {
         Ok(())
     }
 
+    async fn pause(&self, _job_id: &str) -> crate::repository::RepositoryResult<()> {
+        Ok(())
+    }
+
     async fn update_progress(
         &self,
         _id: &str,
@@ -72,15 +86,21 @@ impl JobRepository for MockJobRepo {
         Ok(())
     }
 
-    async fn update_resources(
+    async fn set_site_checks(
         &self,
-        _id: &str,
-        _sitemap: bool,
-        _robots: bool,
+        _job_id: &str,
+        _checks: &[crate::contexts::analysis::SiteCheckResult],
     ) -> crate::repository::RepositoryResult<()> {
         Ok(())
     }
 
+    async fn get_site_checks(
+        &self,
+        _job_id: &str,
+    ) -> crate::repository::RepositoryResult<Vec<crate::contexts::analysis::SiteCheckResult>> {
+        Ok(Vec::new())
+    }
+
     async fn set_error(
         &self,
         _job_id: &str,
@@ -96,6 +116,36 @@ impl JobRepository for MockJobRepo {
     async fn delete(&self, _job_id: &str) -> crate::repository::RepositoryResult<()> {
         Ok(())
     }
+
+    async fn set_http_config(
+        &self,
+        _job_id: &str,
+        _config: &crate::contexts::analysis::HttpConfig,
+    ) -> crate::repository::RepositoryResult<()> {
+        Ok(())
+    }
+
+    async fn get_http_config(
+        &self,
+        _job_id: &str,
+    ) -> crate::repository::RepositoryResult<Option<crate::contexts::analysis::HttpConfig>> {
+        Ok(None)
+    }
+
+    async fn set_tls_inspection(
+        &self,
+        _job_id: &str,
+        _inspection: &crate::contexts::analysis::TlsInspection,
+    ) -> crate::repository::RepositoryResult<()> {
+        Ok(())
+    }
+
+    async fn get_tls_inspection(
+        &self,
+        _job_id: &str,
+    ) -> crate::repository::RepositoryResult<Option<crate::contexts::analysis::TlsInspection>> {
+        Ok(None)
+    }
 }
 
 #[tokio::test]
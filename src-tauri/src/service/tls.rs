@@ -0,0 +1,283 @@
+//! Verified TLS handshake with a job's site.
+//!
+//! Spiders connect with certificate verification off so a broken
+//! certificate never stops an audit. The inspector makes one separate
+//! connection that verifies against the bundled web PKI roots and the
+//! site's host name, and records what it found instead of failing.
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use boring2::asn1::{Asn1Time, Asn1TimeRef};
+use boring2::nid::Nid;
+use boring2::ssl::{SslConnector, SslMethod, SslRef, SslVerifyMode};
+use boring2::x509::store::X509StoreBuilder;
+use boring2::x509::{X509NameRef, X509};
+use chrono::{DateTime, Utc};
+use tokio::net::TcpStream;
+use url::Url;
+
+use crate::contexts::analysis::{HostOverride, TlsInspection};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct TlsInspector {
+    connector: SslConnector,
+}
+
+impl TlsInspector {
+    pub fn new() -> Result<Self> {
+        let mut roots = X509StoreBuilder::new()?;
+        for der in webpki_root_certs::TLS_SERVER_ROOT_CERTS {
+            // A root BoringSSL can't parse is skipped rather than
+            // disabling the inspection altogether.
+            if let Ok(cert) = X509::from_der(der) {
+                roots.add_cert(cert)?;
+            }
+        }
+
+        let mut builder = SslConnector::builder(SslMethod::tls())?;
+        builder.set_cert_store(roots.build());
+        // Let the handshake finish whatever the chain looks like; the
+        // verdict is read back from the session afterwards.
+        builder.set_verify_callback(SslVerifyMode::PEER, |_, _| true);
+
+        Ok(Self {
+            connector: builder.build(),
+        })
+    }
+
+    /// Inspect the certificate `url`'s host serves. `None` for plain http
+    /// URLs. `hosts` are the job's host overrides, so the inspection
+    /// reaches the same server the crawl does.
+    pub async fn inspect(&self, url: &str, hosts: &[HostOverride]) -> Option<TlsInspection> {
+        let (host, port) = https_host(url)?;
+        let checked_at = Utc::now();
+
+        let inspection = match tokio::time::timeout(
+            CONNECT_TIMEOUT,
+            self.handshake(&host, port, hosts, checked_at),
+        )
+        .await
+        {
+            Ok(Ok(inspection)) => inspection,
+            Ok(Err(e)) => TlsInspection::handshake_failed(&host, format!("{e:#}"), checked_at),
+            Err(_) => TlsInspection::handshake_failed(
+                &host,
+                format!("timed out after {}s", CONNECT_TIMEOUT.as_secs()),
+                checked_at,
+            ),
+        };
+
+        tracing::debug!(
+            "[TLS] {}: valid={} protocol={:?} expires={:?}",
+            host,
+            inspection.is_valid(),
+            inspection.protocol,
+            inspection.not_after
+        );
+        Some(inspection)
+    }
+
+    async fn handshake(
+        &self,
+        host: &str,
+        port: u16,
+        hosts: &[HostOverride],
+        checked_at: DateTime<Utc>,
+    ) -> Result<TlsInspection> {
+        let stream = match override_address(host, hosts) {
+            Some(ip) => TcpStream::connect(SocketAddr::new(ip, port)).await,
+            None => TcpStream::connect((host, port)).await,
+        }
+        .with_context(|| format!("could not connect to {host}:{port}"))?;
+
+        let config = self.connector.configure()?;
+        let stream = tokio_boring2::connect(config, host, stream)
+            .await
+            .map_err(|e| anyhow::anyhow!("handshake failed: {e}"))?;
+
+        Ok(describe(stream.ssl(), host, checked_at))
+    }
+}
+
+/// Record why `url`'s certificate wasn't inspected. `None` for plain http
+/// URLs, which have nothing to inspect.
+pub fn skip(url: &str, reason: &str) -> Option<TlsInspection> {
+    let (host, _) = https_host(url)?;
+    Some(TlsInspection::skipped(&host, reason.to_string(), Utc::now()))
+}
+
+fn https_host(url: &str) -> Option<(String, u16)> {
+    let url = Url::parse(url).ok()?;
+    if url.scheme() != "https" {
+        return None;
+    }
+    let host = url.host_str()?.trim_matches(['[', ']']).to_string();
+    Some((host, url.port_or_known_default().unwrap_or(443)))
+}
+
+fn override_address(host: &str, hosts: &[HostOverride]) -> Option<IpAddr> {
+    hosts
+        .iter()
+        .find(|entry| entry.host.trim().eq_ignore_ascii_case(host))
+        .and_then(HostOverride::ip)
+}
+
+fn describe(ssl: &SslRef, host: &str, checked_at: DateTime<Utc>) -> TlsInspection {
+    let leaf = ssl.peer_certificate();
+
+    TlsInspection {
+        host: host.to_string(),
+        checked_at,
+        protocol: Some(ssl.version_str().to_string()),
+        subject: leaf
+            .as_ref()
+            .and_then(|cert| name_entry(cert.subject_name(), Nid::COMMONNAME)),
+        subject_alt_names: leaf
+            .as_ref()
+            .and_then(|cert| cert.subject_alt_names())
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| name.dnsname().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        issuer: leaf.as_ref().and_then(|cert| {
+            name_entry(cert.issuer_name(), Nid::ORGANIZATIONNAME)
+                .or_else(|| name_entry(cert.issuer_name(), Nid::COMMONNAME))
+        }),
+        not_before: leaf
+            .as_ref()
+            .and_then(|cert| to_datetime(cert.not_before())),
+        not_after: leaf.as_ref().and_then(|cert| to_datetime(cert.not_after())),
+        chain_length: ssl.peer_cert_chain().map_or(0, |chain| chain.len()),
+        verify_error: ssl
+            .verify_result()
+            .err()
+            .map(|e| e.error_string().to_string()),
+        handshake_error: None,
+        skipped: None,
+    }
+}
+
+fn name_entry(name: &X509NameRef, nid: Nid) -> Option<String> {
+    name.entries_by_nid(nid)
+        .next()
+        .and_then(|entry| entry.data().as_utf8().ok())
+        .map(|value| value.to_string())
+}
+
+fn to_datetime(time: &Asn1TimeRef) -> Option<DateTime<Utc>> {
+    let diff = Asn1Time::from_unix(0).ok()?.diff(time).ok()?;
+    DateTime::from_timestamp(i64::from(diff.days) * 86_400 + i64::from(diff.secs), 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boring2::bn::BigNum;
+    use boring2::ec::{EcGroup, EcKey};
+    use boring2::hash::MessageDigest;
+    use boring2::pkey::PKey;
+    use boring2::ssl::SslAcceptor;
+    use boring2::x509::{X509Builder, X509NameBuilder};
+
+    fn localhost() -> Vec<HostOverride> {
+        vec![HostOverride {
+            host: "localhost".into(),
+            address: "127.0.0.1".into(),
+        }]
+    }
+
+    /// An acceptor serving a self-signed certificate for `localhost`.
+    fn self_signed_acceptor() -> SslAcceptor {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "localhost").unwrap();
+        let name = name.build();
+
+        let mut cert = X509Builder::new().unwrap();
+        cert.set_version(2).unwrap();
+        cert.set_serial_number(&BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap())
+            .unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_issuer_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        cert.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(30).unwrap()).unwrap();
+        cert.sign(&key, MessageDigest::sha256()).unwrap();
+        let cert = cert.build();
+
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&key).unwrap();
+        acceptor.set_certificate(&cert).unwrap();
+        acceptor.build()
+    }
+
+    #[tokio::test]
+    async fn untrusted_certificate_is_described_with_its_verify_error() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let acceptor = self_signed_acceptor();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            // The client has finished its side once the accept completes.
+            let _ = tokio_boring2::accept(&acceptor, socket).await;
+        });
+
+        let inspector = TlsInspector::new().unwrap();
+        let inspection = inspector
+            .inspect(&format!("https://localhost:{port}"), &localhost())
+            .await
+            .unwrap();
+
+        assert_eq!(inspection.handshake_error, None);
+        assert!(inspection.protocol.is_some());
+        assert_eq!(inspection.subject.as_deref(), Some("localhost"));
+        assert_eq!(inspection.chain_length, 1);
+        assert!(inspection.not_after.is_some());
+        assert!(inspection.verify_error.is_some(), "self-signed chain must not verify");
+        assert!(!inspection.is_valid());
+    }
+
+    #[test]
+    fn skipping_records_the_reason_for_https_only() {
+        let inspection = skip("https://example.com/shop", "behind a proxy").unwrap();
+
+        assert_eq!(inspection.host, "example.com");
+        assert_eq!(inspection.skipped.as_deref(), Some("behind a proxy"));
+        assert!(skip("http://example.com", "behind a proxy").is_none());
+    }
+
+    #[tokio::test]
+    async fn plain_http_is_not_inspected() {
+        let inspector = TlsInspector::new().unwrap();
+
+        assert!(inspector.inspect("http://example.com", &[]).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn server_that_drops_the_connection_fails_the_handshake() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            drop(socket);
+        });
+
+        let inspector = TlsInspector::new().unwrap();
+        let inspection = inspector
+            .inspect(&format!("https://localhost:{port}"), &localhost())
+            .await
+            .unwrap();
+
+        assert!(!inspection.is_valid());
+        assert!(inspection.handshake_error.is_some());
+        assert_eq!(inspection.host, "localhost");
+    }
+}
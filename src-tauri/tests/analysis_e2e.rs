@@ -4,10 +4,14 @@
 
 use app::{
     contexts::analysis::{
-        IssueSeverity, JobPageQuery, JobSettings, JobStatus, LinkType, NewIssue, NewLink,
-        NewPageQueueItem, Page, Pagination, PageQueueStatus,
+        CrawlMode, CrawlProfile, HttpAuth, HttpConfig, HttpHeader, IssueSeverity, JobPageQuery, JobSettings, JobStatus, LinkType, NewIssue, NewLink,
+        NewPageQueueItem, Page, Pagination, PageQueueStatus, QueryParamPolicy, SiteCheckResult,
+        SiteCheckStatus, UrlPattern, UrlRules,
     },
-    repository::sqlite_job_repo,
+    contexts::extension::{
+        AggregateFunction, CheckScope, CustomCheckParams, Operator, RuleCondition, SiteAggregate,
+    },
+    repository::{sqlite_extension_repo, sqlite_job_repo, sqlite_report_pattern_repo},
 };
 use sqlx::SqlitePool;
 
@@ -91,6 +95,10 @@ async fn test_job_creation_with_custom_settings() {
         mobile_analysis: false,
         lighthouse_analysis: false,
         delay_between_requests: 100,
+        url_rules: Default::default(),
+        crawl_mode: Default::default(),
+        crawl_profile: Default::default(),
+        resource_analysis: false,
     };
 
     let repo = sqlite_job_repo(pool.clone());
@@ -103,6 +111,161 @@ async fn test_job_creation_with_custom_settings() {
 
     assert_eq!(job.settings.max_pages, 10);
     assert_eq!(job.settings.delay_between_requests, 100);
+    assert_eq!(job.settings.url_rules, UrlRules::default());
+}
+
+#[tokio::test]
+async fn test_job_url_rules_round_trip() {
+    let pool = setup_test_db().await;
+
+    let url_rules = UrlRules {
+        exclude: vec![UrlPattern::Glob("/wp-admin/*".into())],
+        query_params: QueryParamPolicy::StripListed(vec!["utm_source".into()]),
+        max_query_combinations: Some(5),
+        ..Default::default()
+    };
+    let settings = JobSettings {
+        url_rules: url_rules.clone(),
+        ..Default::default()
+    };
+
+    let repo = sqlite_job_repo(pool.clone());
+    let job_id = repo
+        .create("https://example.com/", &settings)
+        .await
+        .expect("Failed to create job with URL rules");
+
+    let job = repo.get_by_id(&job_id).await.expect("Failed to get job");
+    assert_eq!(job.settings.url_rules, url_rules);
+
+    let pending = repo.get_pending().await.expect("Failed to get pending jobs");
+    assert_eq!(pending[0].settings.url_rules, url_rules);
+}
+
+#[tokio::test]
+async fn test_job_crawl_mode_round_trip() {
+    let pool = setup_test_db().await;
+    let repo = sqlite_job_repo(pool.clone());
+
+    let spider_id = create_job(&pool, "https://example.com/").await;
+    let list_id = repo
+        .create(
+            "https://example.org/",
+            &JobSettings {
+                crawl_mode: CrawlMode::List,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create list-mode job");
+
+    let spider = repo.get_by_id(&spider_id).await.expect("Failed to get job");
+    assert_eq!(spider.settings.crawl_mode, CrawlMode::Spider);
+    let list = repo.get_by_id(&list_id).await.expect("Failed to get job");
+    assert_eq!(list.settings.crawl_mode, CrawlMode::List);
+}
+
+#[tokio::test]
+async fn test_job_crawl_profile_round_trip() {
+    let pool = setup_test_db().await;
+    let repo = sqlite_job_repo(pool.clone());
+
+    let profile = CrawlProfile::Custom {
+        user_agent: "AuditBot/2.0".into(),
+    };
+    let job_id = repo
+        .create(
+            "https://example.com/",
+            &JobSettings {
+                crawl_profile: profile.clone(),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to create job");
+
+    let job = repo.get_by_id(&job_id).await.expect("Failed to get job");
+    assert_eq!(job.settings.crawl_profile, profile);
+
+    // A profile written by a newer build reads back as the default.
+    sqlx::query("UPDATE jobs SET crawl_profile = '{\"kind\":\"future_bot\"}' WHERE id = ?")
+        .bind(&job_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let job = repo.get_by_id(&job_id).await.expect("Failed to get job");
+    assert_eq!(job.settings.crawl_profile, CrawlProfile::Default);
+}
+
+#[tokio::test]
+async fn test_job_http_config_is_stored_apart_from_the_job() {
+    let pool = setup_test_db().await;
+    let repo = sqlite_job_repo(pool.clone());
+    let job_id = create_job(&pool, "https://staging.example.com/").await;
+
+    assert_eq!(repo.get_http_config(&job_id).await.unwrap(), None);
+
+    let config = HttpConfig {
+        headers: vec![HttpHeader {
+            name: "X-Env".into(),
+            value: "staging".into(),
+        }],
+        auth: Some(HttpAuth::Bearer {
+            token: "secret-token".into(),
+        }),
+        ..Default::default()
+    };
+    repo.set_http_config(&job_id, &config)
+        .await
+        .expect("Failed to store HTTP config");
+    assert_eq!(repo.get_http_config(&job_id).await.unwrap(), Some(config));
+
+    let job = repo.get_by_id(&job_id).await.expect("Failed to get job");
+    let job_json = serde_json::to_string(&job).unwrap();
+    assert!(!job_json.contains("secret-token"));
+
+    // The app's pool enables foreign keys on every connection (db.rs).
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&pool).await.unwrap();
+    repo.delete(&job_id).await.expect("Failed to delete job");
+    let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM job_http_config")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0, "credentials are deleted with the job");
+}
+
+#[tokio::test]
+async fn test_job_is_created_with_its_http_config_and_queued_pages() {
+    let pool = setup_test_db().await;
+    let repo = sqlite_job_repo(pool.clone());
+    let settings = JobSettings { crawl_mode: CrawlMode::List, ..Default::default() };
+    let config = HttpConfig {
+        auth: Some(HttpAuth::Bearer { token: "secret-token".into() }),
+        ..Default::default()
+    };
+    let urls = vec!["https://example.com/a".to_string(), "https://example.com/b".to_string()];
+
+    let job_id = repo
+        .create_with("https://example.com/sitemap.xml", &settings, Some(&config), &urls)
+        .await
+        .expect("Failed to create list job");
+
+    let job = repo.get_by_id(&job_id).await.unwrap();
+    assert_eq!(job.status, JobStatus::Pending);
+    assert_eq!(repo.get_http_config(&job_id).await.unwrap(), Some(config));
+    let queued: Vec<(String, String)> =
+        sqlx::query_as("SELECT url, status FROM page_queue WHERE job_id = ? ORDER BY url")
+            .bind(&job_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        queued,
+        [
+            ("https://example.com/a".to_string(), "pending".to_string()),
+            ("https://example.com/b".to_string(), "pending".to_string()),
+        ]
+    );
 }
 
 #[tokio::test]
@@ -186,6 +349,22 @@ async fn test_update_job_status() {
     assert_eq!(job.status.as_str(), "completed");
 }
 
+#[tokio::test]
+async fn test_pause_only_pauses_unfinished_jobs() {
+    let pool = setup_test_db().await;
+    let repo = sqlite_job_repo(pool.clone());
+
+    let job_id = create_job(&pool, "https://example.com/").await;
+    repo.update_status(&job_id, JobStatus::Processing).await.unwrap();
+    repo.pause(&job_id).await.expect("a processing job should pause");
+    assert_eq!(repo.get_by_id(&job_id).await.unwrap().status, JobStatus::Paused);
+
+    // Cancelled after the caller looked it up: the pause doesn't undo that
+    repo.update_status(&job_id, JobStatus::Cancelled).await.unwrap();
+    assert!(repo.pause(&job_id).await.is_err());
+    assert_eq!(repo.get_by_id(&job_id).await.unwrap().status, JobStatus::Cancelled);
+}
+
 #[tokio::test]
 async fn test_update_job_progress() {
     let pool = setup_test_db().await;
@@ -276,25 +455,467 @@ async fn test_delete_job() {
 }
 
 #[tokio::test]
-async fn test_update_resources() {
+async fn test_site_checks_round_trip() {
     let pool = setup_test_db().await;
     let repo = sqlite_job_repo(pool.clone());
 
     let job_id = create_job(&pool, "https://example.com/").await;
 
-    // Initially resources should be false
+    // Nothing checked yet
     let job = repo.get_by_id(&job_id).await.expect("Failed to get job");
     assert!(!job.sitemap_found);
     assert!(!job.robots_txt_found);
+    assert!(repo.get_site_checks(&job_id).await.unwrap().is_empty());
 
-    // Update resources
-    repo.update_resources(&job_id, true, true)
+    let check = |id: &str, status: SiteCheckStatus, problems: &[&str]| SiteCheckResult {
+        check_id: id.to_string(),
+        label: id.to_string(),
+        status,
+        url: Some(format!("https://example.com/{id}")),
+        status_code: Some(200),
+        problems: problems.iter().map(|p| p.to_string()).collect(),
+    };
+    let checks = vec![
+        check("robots_txt", SiteCheckStatus::Invalid, &["No User-agent group"]),
+        check("sitemap_xml", SiteCheckStatus::Missing, &[]),
+        check("favicon", SiteCheckStatus::Found, &[]),
+    ];
+    repo.set_site_checks(&job_id, &checks)
         .await
-        .expect("Failed to update resources");
+        .expect("Failed to store site checks");
 
+    assert_eq!(repo.get_site_checks(&job_id).await.unwrap(), checks);
     let job = repo.get_by_id(&job_id).await.expect("Failed to get job");
-    assert!(job.sitemap_found);
-    assert!(job.robots_txt_found);
+    assert!(!job.sitemap_found);
+    assert!(job.robots_txt_found, "an invalid robots.txt still exists");
+
+    // Storing again replaces the previous results
+    repo.set_site_checks(&job_id, &checks[2..]).await.unwrap();
+    assert_eq!(repo.get_site_checks(&job_id).await.unwrap(), checks[2..]);
+}
+
+#[tokio::test]
+async fn test_builtin_security_patterns_match_their_page_checks() {
+    let pool = setup_test_db().await;
+    let repo = sqlite_report_pattern_repo(pool.clone());
+
+    // Uncacheable only when none of the three validators is sent.
+    let caching = repo.get_pattern("builtin-missing-cache-control").await.unwrap();
+    let Some(RuleCondition::All { children }) = caching.condition else {
+        panic!("expected an all-of condition, got {:?}", caching.condition);
+    };
+    let fields: Vec<_> = children
+        .iter()
+        .map(|child| match child {
+            RuleCondition::Compare { field, operator: Operator::Missing, .. } => field.as_str(),
+            other => panic!("unexpected condition {other:?}"),
+        })
+        .collect();
+    assert_eq!(fields, ["header:cache-control", "header:etag", "header:last-modified"]);
+
+    // A version is a digit in the Server header, as ServerDisclosureCheck has it.
+    let server = repo.get_pattern("builtin-server-version").await.unwrap();
+    assert_eq!(server.operator, Operator::Matches);
+    assert_eq!(server.threshold.as_deref(), Some("[0-9]"));
+}
+
+#[tokio::test]
+async fn test_custom_check_condition_round_trip() {
+    let pool = setup_test_db().await;
+    let repo = sqlite_extension_repo(pool.clone());
+
+    let condition = RuleCondition::All {
+        children: vec![
+            RuleCondition::compare("title", &Operator::Matches, Some("^Acme")),
+            RuleCondition::Not {
+                inner: Box::new(RuleCondition::compare("word_count", &Operator::Between, Some("300,1500"))),
+            },
+        ],
+    };
+    let mut params = CustomCheckParams {
+        name: "Thin brand page".into(),
+        severity: IssueSeverity::Warning,
+        field: "title".into(),
+        operator: Operator::Present,
+        threshold: None,
+        condition: Some(condition.clone()),
+        scope: CheckScope::Page,
+        aggregate: None,
+        script: None,
+        message_template: "{value} is thin".into(),
+        enabled: true,
+    };
+    let created = repo.create_check(&params).await.expect("Failed to create check");
+    let stored = repo.get_check(&created.id).await.expect("Failed to get check");
+    assert_eq!(stored.condition, Some(condition));
+
+    // Clearing the condition falls back to the single rule
+    params.condition = None;
+    repo.update_check(&created.id, &params).await.expect("Failed to update check");
+    assert_eq!(repo.get_check(&created.id).await.unwrap().condition, None);
+
+    // A site check stores its scope and aggregate
+    let aggregate = SiteAggregate {
+        function: AggregateFunction::Percent,
+        operator: Operator::Gt,
+        threshold: Some("20".into()),
+    };
+    params.scope = CheckScope::Site;
+    params.aggregate = Some(aggregate.clone());
+    repo.update_check(&created.id, &params).await.expect("Failed to update check");
+    let stored = repo.get_check(&created.id).await.unwrap();
+    assert_eq!(stored.scope, CheckScope::Site);
+    assert_eq!(stored.aggregate, Some(aggregate));
+
+    // A script check stores its script
+    params.scope = CheckScope::Page;
+    params.aggregate = None;
+    params.script = Some("if page.title == () { \"No title\" }".into());
+    repo.update_check(&created.id, &params).await.expect("Failed to update check");
+    assert_eq!(repo.get_check(&created.id).await.unwrap().script, params.script);
+}
+
+/// A stored page of `job_id` with a title and no extracted data.
+fn stored_page(job_id: &str, id: &str, url: &str) -> Page {
+    Page {
+        id: id.to_string(),
+        job_id: job_id.to_string(),
+        url: url.to_string(),
+        depth: app::contexts::analysis::Depth::root(),
+        status_code: Some(200),
+        content_type: Some("text/html".to_string()),
+        title: Some("Example".to_string()),
+        meta_description: None,
+        canonical_url: None,
+        robots_meta: None,
+        word_count: Some(100),
+        load_time_ms: Some(150),
+        ttfb_ms: None,
+        download_ms: None,
+        response_size_bytes: Some(5000),
+        has_viewport: true,
+        has_structured_data: false,
+        crawled_at: chrono::Utc::now(),
+        extracted_data: std::collections::HashMap::new(),
+        response_headers: Default::default(),
+        weight: None,
+    }
+}
+
+fn dry_run_service(pool: &SqlitePool) -> app::contexts::extension::DryRunService {
+    use app::repository::{
+        sqlite_issue_repo, sqlite_link_repo, sqlite_page_repo, sqlite_results_repo,
+        sqlite_snapshot_repo,
+    };
+    app::contexts::extension::DryRunService::new(
+        sqlite_results_repo(pool.clone()),
+        sqlite_snapshot_repo(pool.clone()),
+        sqlite_page_repo(pool.clone()),
+        sqlite_link_repo(pool.clone()),
+        sqlite_issue_repo(pool.clone()),
+        sqlite_extension_repo(pool.clone()),
+    )
+}
+
+#[tokio::test]
+async fn test_dry_run_check_script_against_stored_page() {
+    use app::contexts::analysis::NewHeading;
+    use app::repository::{sqlite_link_repo, sqlite_page_repo};
+
+    let pool = setup_test_db().await;
+    let job_id = create_job(&pool, "https://example.com/").await;
+    let page_repo = sqlite_page_repo(pool.clone());
+    let page = stored_page(&job_id, "page-1", "https://example.com/");
+    page_repo.insert(&page).await.expect("Failed to insert page");
+    page_repo
+        .replace_headings(
+            "page-1",
+            &[NewHeading { page_id: "page-1".into(), level: 2, text: "Intro".into(), position: 0 }],
+        )
+        .await
+        .expect("Failed to insert headings");
+    sqlite_link_repo(pool.clone())
+        .insert_batch(&[NewLink {
+            job_id: job_id.clone(),
+            source_page_id: "page-1".to_string(),
+            target_url: "https://external.com".to_string(),
+            link_text: None,
+            status_code: Some(200),
+            link_type: LinkType::External,
+        }])
+        .await
+        .expect("Failed to insert links");
+
+    let service = dry_run_service(&pool);
+    let params = CustomCheckParams {
+        name: "Outline".into(),
+        severity: IssueSeverity::Warning,
+        field: String::new(),
+        operator: Operator::Present,
+        threshold: None,
+        condition: None,
+        scope: CheckScope::Page,
+        aggregate: None,
+        script: Some(
+            r#"print(page.title);
+               [`${page.headings.len()} heading(s), first ${page.headings[0].text}`,
+                `${page.links.len()} ${page.links[0].type} link(s)`]"#
+                .into(),
+        ),
+        message_template: String::new(),
+        enabled: true,
+    };
+    let run = service
+        .check_script(&params, &job_id, "https://example.com/")
+        .await
+        .expect("Failed to dry-run script");
+    assert_eq!(run.error, None);
+    assert_eq!(run.output, ["Example"]);
+    let messages: Vec<&str> = run.issues.iter().map(|i| i.message.as_str()).collect();
+    assert_eq!(messages, ["1 heading(s), first Intro", "1 external link(s)"]);
+
+    // A runtime error is reported, not returned
+    let broken = CustomCheckParams { script: Some("page.headings[5].text".into()), ..params.clone() };
+    let run = service.check_script(&broken, &job_id, "https://example.com/").await.unwrap();
+    assert!(run.error.is_some());
+
+    // The page must exist in the job
+    assert!(service.check_script(&params, &job_id, "https://example.com/missing").await.is_err());
+}
+
+#[tokio::test]
+async fn test_dry_run_and_apply_custom_checks_on_finished_job() {
+    use app::contexts::extension::{CustomExtractorParams, ExtractorKind, ExtractorOutput};
+    use app::contexts::snapshot::NewPageSnapshot;
+    use app::repository::{sqlite_issue_repo, sqlite_page_repo, sqlite_snapshot_repo};
+
+    let pool = setup_test_db().await;
+    let job_id = create_job(&pool, "https://example.com/").await;
+    let page_repo = sqlite_page_repo(pool.clone());
+    let snapshot_repo = sqlite_snapshot_repo(pool.clone());
+    for (id, url, html) in [
+        ("page-1", "https://example.com/", Some(r#"<meta property="og:image" content="/a.png">"#)),
+        ("page-2", "https://example.com/b", Some("<p>no image</p>")),
+        ("page-3", "https://example.com/c", None),
+    ] {
+        page_repo.insert(&stored_page(&job_id, id, url)).await.expect("Failed to insert page");
+        if let Some(html) = html {
+            let headers = Default::default();
+            let snapshot =
+                NewPageSnapshot { page_id: id, job_id: &job_id, url, html, headers: &headers };
+            snapshot_repo.save(&snapshot).await.expect("Failed to save snapshot");
+        }
+    }
+    let service = dry_run_service(&pool);
+    let extractor = CustomExtractorParams {
+        name: "OG image".into(),
+        tag: "og_image".into(),
+        kind: ExtractorKind::Css,
+        selector: r#"meta[property="og:image"]"#.into(),
+        attribute: Some("content".into()),
+        multiple: false,
+        output: ExtractorOutput::Value,
+        data_type: None,
+        enabled: true,
+    };
+    let check = CustomCheckParams {
+        name: "Missing OG image".into(),
+        severity: IssueSeverity::Warning,
+        field: "tag:og_image".into(),
+        operator: Operator::Missing,
+        threshold: None,
+        condition: None,
+        scope: CheckScope::Page,
+        aggregate: None,
+        script: None,
+        message_template: "No og:image".into(),
+        enabled: false,
+    };
+
+    // Only finished jobs can be re-checked
+    assert!(service.run_check(&check, &job_id).await.is_err());
+    sqlite_job_repo(pool.clone()).update_status(&job_id, JobStatus::Completed).await.unwrap();
+
+    let extractor_config = app::extractor::data_extractor::ExtractorConfig::from(&extractor);
+    let built = app::extractor::data_extractor::build(extractor_config).unwrap();
+    let run = service.run_extractor(built.as_ref(), &job_id).await.unwrap();
+    let values: Vec<_> = run.pages.iter().map(|p| (p.url.as_str(), p.value.clone())).collect();
+    assert_eq!(
+        values,
+        [
+            ("https://example.com/", Some(serde_json::json!("/a.png"))),
+            ("https://example.com/b", None),
+        ]
+    );
+    assert_eq!(run.without_html, ["https://example.com/c"]);
+
+    // The draft check runs even though it's disabled, on the stored data
+    let run = service.run_check(&check, &job_id).await.unwrap();
+    assert_eq!(run.pages_checked, 3);
+    assert_eq!(run.issues.len(), 3);
+    assert_eq!(run.issues[0].url.as_deref(), Some("https://example.com/"));
+    assert_eq!(run.issues[0].severity, IssueSeverity::Warning);
+
+    // The crawl ran with an older version of the check, since renamed,
+    // next to a built-in check that happens to share the new name
+    let extensions = sqlite_extension_repo(pool.clone());
+    extensions.create_extractor(&extractor).await.unwrap();
+    let saved = extensions.create_check(&CustomCheckParams { enabled: true, ..check }).await.unwrap();
+    let issue_repo = sqlite_issue_repo(pool.clone());
+    let crawled = |issue_type: &str, custom_check_id: Option<String>| NewIssue {
+        job_id: job_id.clone(),
+        page_id: Some("page-1".into()),
+        issue_type: issue_type.into(),
+        severity: IssueSeverity::Info,
+        message: "from the crawl".into(),
+        details: None,
+        custom_check_id,
+    };
+    issue_repo
+        .insert_batch(&[
+            crawled("OG image (old name)", Some(saved.id.clone())),
+            crawled("Missing OG image", None),
+        ])
+        .await
+        .unwrap();
+
+    // Applying re-extracts the saved pages before re-running saved checks
+    let applied = service.apply_checks(&job_id).await.unwrap();
+    assert_eq!(applied.pages_checked, 3);
+    assert_eq!(applied.pages_reextracted, 2);
+    assert_eq!(applied.issues_removed, 1);
+    assert_eq!(applied.issues_added, 2);
+
+    let issues = issue_repo.get_by_job_id(&job_id).await.unwrap();
+    let mut pages: Vec<_> = issues.iter().filter_map(|i| i.page_id.as_deref()).collect();
+    pages.sort();
+    assert_eq!(pages, ["page-1", "page-2", "page-3"]);
+    assert!(issues.iter().all(|i| i.issue_type == "Missing OG image"));
+    let page = page_repo.get_by_id("page-1").await.unwrap();
+    assert_eq!(page.extracted_data["og_image"], "/a.png");
+    let job = sqlite_job_repo(pool.clone()).get_by_id(&job_id).await.unwrap();
+    assert_eq!(job.summary.total_issues(), 3);
+    assert_eq!(job.summary.info_issues(), 1);
+}
+
+#[tokio::test]
+async fn test_page_snapshots_are_deduplicated_diffed_and_pruned() {
+    use app::contexts::analysis::ResponseHeaders;
+    use app::contexts::snapshot::{DiffLineKind, NewPageSnapshot, SnapshotService, SnapshotSettings};
+    use app::repository::{sqlite_page_repo, sqlite_settings_repo, sqlite_snapshot_repo};
+
+    let pool = setup_test_db().await;
+    let page_repo = sqlite_page_repo(pool.clone());
+    let service =
+        SnapshotService::load(sqlite_snapshot_repo(pool.clone()), sqlite_settings_repo(pool.clone()))
+            .await;
+    assert_eq!(service.settings(), SnapshotSettings::default());
+
+    let before = "<html>\n<title>Old</title>\n<body>same</body>\n</html>\n";
+    let after = "<html>\n<title>New</title>\n<body>same</body>\n</html>\n";
+    let headers = ResponseHeaders::new([("Content-Type", "text/html")]);
+    let first_job = create_job(&pool, "https://example.com/").await;
+    let second_job = create_job(&pool, "https://example.com/").await;
+    for (id, job_id, url, html) in [
+        ("page-1", &first_job, "https://example.com/", before),
+        ("page-2", &first_job, "https://example.com/copy", before),
+        ("page-3", &second_job, "https://example.com/", after),
+    ] {
+        page_repo.insert(&stored_page(job_id, id, url)).await.expect("Failed to insert page");
+        let snapshot = NewPageSnapshot { page_id: id, job_id, url, html, headers: &headers };
+        service.keep(&snapshot).await.expect("Failed to save snapshot");
+    }
+
+    // Identical bodies are stored once
+    let storage = service.storage().await.unwrap();
+    assert_eq!(storage.snapshots, 3);
+    assert_eq!(storage.distinct_sources, 2);
+    assert_eq!(storage.html_bytes, (2 * before.len() + after.len()) as i64);
+
+    let source = service.page_source(&first_job, "https://example.com/copy").await.unwrap();
+    assert_eq!(source.html, before);
+    assert_eq!(source.headers.get("content-type"), Some("text/html"));
+    assert!(service.page_source(&first_job, "https://example.com/missing").await.is_err());
+
+    // Without a job to compare against, the previous crawl is used
+    let diff = service.diff(&second_job, "https://example.com/", None).await.unwrap();
+    assert_eq!(diff.from_job_id, first_job);
+    assert_eq!(diff.to_job_id, second_job);
+    assert_eq!(diff.hunks.len(), 1);
+    let changed: Vec<_> = diff.hunks[0]
+        .lines
+        .iter()
+        .filter(|line| line.kind != DiffLineKind::Context)
+        .map(|line| (line.kind, line.text.as_str()))
+        .collect();
+    assert_eq!(
+        changed,
+        [(DiffLineKind::Removed, "<title>Old</title>"), (DiffLineKind::Added, "<title>New</title>")]
+    );
+    assert!(service.diff(&first_job, "https://example.com/", None).await.is_err());
+
+    // Turned off, nothing more is saved
+    let off = SnapshotSettings { enabled: false, ..SnapshotSettings::default() };
+    service.set_settings(off).await.unwrap();
+    page_repo.insert(&stored_page(&second_job, "page-4", "https://example.com/d")).await.unwrap();
+    let snapshot = NewPageSnapshot {
+        page_id: "page-4",
+        job_id: &second_job,
+        url: "https://example.com/d",
+        html: after,
+        headers: &headers,
+    };
+    service.keep(&snapshot).await.unwrap();
+    assert_eq!(service.storage().await.unwrap().snapshots, 3);
+
+    // Snapshots past the retention period go, and so do sources nothing uses
+    sqlx::query("UPDATE page_snapshots SET created_at = '2020-01-01T00:00:00+00:00' WHERE job_id = ?")
+        .bind(&first_job)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let storage = service
+        .set_settings(SnapshotSettings { enabled: true, retention_days: Some(30) })
+        .await
+        .unwrap();
+    assert_eq!((storage.snapshots, storage.distinct_sources), (1, 1));
+    assert!(service
+        .set_settings(SnapshotSettings { enabled: true, retention_days: Some(0) })
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_deleting_a_job_drops_the_page_sources_only_it_used() {
+    use app::contexts::analysis::ResponseHeaders;
+    use app::contexts::snapshot::NewPageSnapshot;
+    use app::repository::{sqlite_page_repo, sqlite_snapshot_repo};
+
+    let pool = setup_test_db().await;
+    // The app's pool enables foreign keys on every connection (db.rs).
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&pool).await.unwrap();
+    let page_repo = sqlite_page_repo(pool.clone());
+    let snapshot_repo = sqlite_snapshot_repo(pool.clone());
+    let headers = ResponseHeaders::default();
+    let first_job = create_job(&pool, "https://example.com/").await;
+    let second_job = create_job(&pool, "https://example.com/").await;
+    for (id, job_id, url, html) in [
+        ("page-1", &first_job, "https://example.com/", "<p>shared</p>"),
+        ("page-2", &first_job, "https://example.com/b", "<p>first job only</p>"),
+        ("page-3", &second_job, "https://example.com/", "<p>shared</p>"),
+    ] {
+        page_repo.insert(&stored_page(job_id, id, url)).await.expect("Failed to insert page");
+        let snapshot = NewPageSnapshot { page_id: id, job_id, url, html, headers: &headers };
+        snapshot_repo.save(&snapshot).await.expect("Failed to save snapshot");
+    }
+    assert_eq!(snapshot_repo.storage().await.unwrap().distinct_sources, 2);
+
+    sqlite_job_repo(pool.clone()).delete(&first_job).await.expect("Failed to delete job");
+
+    let storage = snapshot_repo.storage().await.unwrap();
+    assert_eq!((storage.snapshots, storage.distinct_sources), (1, 1));
+    let kept = snapshot_repo.get(&second_job, "https://example.com/").await.unwrap();
+    assert_eq!(kept.html, "<p>shared</p>");
 }
 
 #[tokio::test]
@@ -376,6 +997,10 @@ async fn test_job_with_all_settings_enabled() {
         mobile_analysis: true, // This is hardcoded to false in the repo
         lighthouse_analysis: true,
         delay_between_requests: 1000,
+        url_rules: Default::default(),
+        crawl_mode: Default::default(),
+        crawl_profile: Default::default(),
+        resource_analysis: false,
     };
 
     let repo = sqlite_job_repo(pool.clone());
@@ -456,7 +1081,7 @@ async fn test_ai_repository() {
 
     // Test save and get insights
     let test_insights = r#"{"summary": "Test summary", "recommendations": ["Rec 1", "Rec 2"]}"#;
-    repo.save_ai_insights(&job_id, test_insights)
+    repo.save_ai_insights(&job_id, "Gemini", test_insights)
         .await
         .expect("Failed to save insights");
 
@@ -465,7 +1090,7 @@ async fn test_ai_repository() {
 
     // Test update existing insights
     let updated_insights = r#"{"summary": "Updated summary", "recommendations": ["New Rec"]}"#;
-    repo.save_ai_insights(&job_id, updated_insights)
+    repo.save_ai_insights(&job_id, "Gemini", updated_insights)
         .await
         .expect("Failed to update insights");
 
@@ -587,6 +1212,7 @@ async fn test_issue_repository() {
             severity: IssueSeverity::Critical,
             message: "Page has no title tag".to_string(),
             details: Some("Add a descriptive title tag".to_string()),
+            custom_check_id: None,
         },
         NewIssue {
             job_id: job_id.clone(),
@@ -595,6 +1221,7 @@ async fn test_issue_repository() {
             severity: IssueSeverity::Warning,
             message: "Page has no meta description".to_string(),
             details: None,
+            custom_check_id: None,
         },
         NewIssue {
             job_id: job_id.clone(),
@@ -603,6 +1230,7 @@ async fn test_issue_repository() {
             severity: IssueSeverity::Info,
             message: "Page has only 50 words".to_string(),
             details: Some("Consider adding more content".to_string()),
+            custom_check_id: None,
         },
     ];
 
@@ -657,11 +1285,15 @@ async fn test_link_repository() {
         robots_meta: None,
         word_count: Some(100),
         load_time_ms: Some(150),
+        ttfb_ms: None,
+        download_ms: None,
         response_size_bytes: Some(5000),
         has_viewport: true,
         has_structured_data: false,
         crawled_at: Utc::now(),
         extracted_data: std::collections::HashMap::new(),
+        response_headers: Default::default(),
+        weight: None,
     };
     page_repo.insert(&page).await.expect("Failed to insert page");
 
@@ -4,9 +4,10 @@
 //!   ExtensionRepository (DB) → ExtractorRegistry → HTML → Page.extracted_data → PageRepository (DB)
 
 use app::{
-    contexts::analysis::Page,
-    contexts::extension::CustomExtractorParams,
-    extractor::data_extractor::{ExtractorConfig, ExtractorRegistry},
+    contexts::analysis::{Page, ResponseHeaders},
+    contexts::extension::{CustomExtractorParams, ExtractorKind, ExtractorOutput},
+    contexts::tags::TagDataType,
+    extractor::data_extractor::{self, ExtractorConfig, ExtractorRegistry},
     extractor::data_extractor::selector::SelectorExtractor,
     repository::{sqlite_extension_repo, sqlite_page_repo},
 };
@@ -52,20 +53,18 @@ fn sample_html() -> &'static str {
 #[test]
 fn extractor_registry_produces_data_from_html() {
     let mut registry = ExtractorRegistry::new();
-    registry.register(Box::new(SelectorExtractor::new(ExtractorConfig {
-        tag: "og_title".into(),
-        selector: "meta[property='og:title']".into(),
-        attribute: Some("content".into()),
-        multiple: false,
-    })));
-    registry.register(Box::new(SelectorExtractor::new(ExtractorConfig {
-        tag: "hreflang".into(),
-        selector: "link[rel='alternate'][hreflang]".into(),
-        attribute: Some("hreflang".into()),
-        multiple: true,
-    })));
-
-    let result = registry.run(sample_html());
+    registry.register(Box::new(SelectorExtractor::new(ExtractorConfig::attr(
+        "og_title",
+        "meta[property='og:title']",
+        "content",
+    ))));
+    registry.register(Box::new(SelectorExtractor::new(ExtractorConfig::multi_attr(
+        "hreflang",
+        "link[rel='alternate'][hreflang]",
+        "hreflang",
+    ))));
+
+    let result = registry.run(sample_html(), &ResponseHeaders::default());
 
     assert_eq!(
         result.get("og_title"),
@@ -93,9 +92,12 @@ async fn extension_repo_persists_and_retrieves_extractors() {
     repo.create_extractor(&CustomExtractorParams {
         name: "OG Title".into(),
         tag: "og_title".into(),
+        kind: ExtractorKind::Css,
         selector: "meta[property='og:title']".into(),
         attribute: Some("content".into()),
         multiple: false,
+        output: ExtractorOutput::Value,
+        data_type: None,
         enabled: true,
     })
     .await
@@ -104,9 +106,12 @@ async fn extension_repo_persists_and_retrieves_extractors() {
     repo.create_extractor(&CustomExtractorParams {
         name: "Hreflang (disabled)".into(),
         tag: "hreflang".into(),
+        kind: ExtractorKind::Css,
         selector: "link[rel='alternate'][hreflang]".into(),
         attribute: Some("hreflang".into()),
         multiple: true,
+        output: ExtractorOutput::Value,
+        data_type: None,
         enabled: false, // disabled — should not appear in list_enabled
     })
     .await
@@ -138,9 +143,12 @@ async fn registry_built_from_db_extracts_correct_data() {
     repo.create_extractor(&CustomExtractorParams {
         name: "OG Title".into(),
         tag: "og_title".into(),
+        kind: ExtractorKind::Css,
         selector: "meta[property='og:title']".into(),
         attribute: Some("content".into()),
         multiple: false,
+        output: ExtractorOutput::Value,
+        data_type: None,
         enabled: true,
     })
     .await
@@ -149,9 +157,12 @@ async fn registry_built_from_db_extracts_correct_data() {
     repo.create_extractor(&CustomExtractorParams {
         name: "Hreflang".into(),
         tag: "hreflang".into(),
+        kind: ExtractorKind::Css,
         selector: "link[rel='alternate'][hreflang]".into(),
         attribute: Some("hreflang".into()),
         multiple: true,
+        output: ExtractorOutput::Value,
+        data_type: None,
         enabled: true,
     })
     .await
@@ -163,17 +174,11 @@ async fn registry_built_from_db_extracts_correct_data() {
     assert_eq!(extractors.len(), 2, "2 extractors should be loaded from DB");
 
     for ext in extractors {
-        let config = ExtractorConfig {
-            tag: ext.tag,
-            selector: ext.selector,
-            attribute: ext.attribute,
-            multiple: ext.multiple,
-        };
-        registry.register(Box::new(SelectorExtractor::new(config)));
+        registry.register(data_extractor::build(ExtractorConfig::from(ext)).unwrap());
     }
 
     // Run against sample HTML
-    let data = registry.run(sample_html());
+    let data = registry.run(sample_html(), &ResponseHeaders::default());
 
     assert_eq!(
         data.get("og_title"),
@@ -185,6 +190,59 @@ async fn registry_built_from_db_extracts_correct_data() {
     assert_eq!(arr.len(), 2, "hreflang should have 2 values");
 }
 
+#[tokio::test]
+async fn extractor_kinds_and_types_survive_db_and_run() {
+    let pool = setup_db().await;
+    let repo = sqlite_extension_repo(pool.clone());
+
+    repo.create_extractor(&CustomExtractorParams {
+        name: "Subheading count".into(),
+        tag: "h2_count".into(),
+        kind: ExtractorKind::Xpath,
+        selector: "//body/h2".into(),
+        attribute: None,
+        multiple: false,
+        output: ExtractorOutput::Count,
+        data_type: None,
+        enabled: true,
+    })
+    .await
+    .unwrap();
+
+    repo.create_extractor(&CustomExtractorParams {
+        name: "Page size".into(),
+        tag: "page_size".into(),
+        kind: ExtractorKind::Header,
+        selector: "Content-Length".into(),
+        attribute: None,
+        multiple: false,
+        output: ExtractorOutput::Value,
+        data_type: Some(TagDataType::Number),
+        enabled: true,
+    })
+    .await
+    .unwrap();
+
+    let extractors = repo.list_enabled_extractors().await.unwrap();
+    let size = extractors.iter().find(|e| e.tag == "page_size").unwrap();
+    assert_eq!(size.kind, ExtractorKind::Header);
+    assert_eq!(size.data_type, Some(TagDataType::Number));
+    let count = extractors.iter().find(|e| e.tag == "h2_count").unwrap();
+    assert_eq!(count.kind, ExtractorKind::Xpath);
+    assert_eq!(count.output, ExtractorOutput::Count);
+    assert_eq!(count.data_type(), TagDataType::Number);
+
+    let mut registry = ExtractorRegistry::new();
+    for ext in extractors {
+        registry.register(data_extractor::build(ExtractorConfig::from(ext)).unwrap());
+    }
+    let headers = ResponseHeaders::new([("content-length", "5120")]);
+    let data = registry.run(sample_html(), &headers);
+
+    assert_eq!(data["h2_count"], 2);
+    assert_eq!(data["page_size"], 5120);
+}
+
 // ---------------------------------------------------------------------------
 // Layer 4: extracted_data survives Page insert → retrieval round-trip
 // ---------------------------------------------------------------------------
@@ -222,11 +280,15 @@ async fn extracted_data_survives_page_db_round_trip() {
         robots_meta: None,
         word_count: Some(100),
         load_time_ms: Some(250),
+        ttfb_ms: None,
+        download_ms: None,
         response_size_bytes: Some(5000),
         has_viewport: true,
         has_structured_data: false,
         crawled_at: Utc::now(),
         extracted_data,
+        response_headers: Default::default(),
+        weight: None,
     };
 
     // Insert needs the job to exist first due to FK — insert directly into jobs table
@@ -274,9 +336,12 @@ async fn full_pipeline_extractor_config_to_stored_page() {
         .create_extractor(&CustomExtractorParams {
             name: "OG Title".into(),
             tag: "og_title".into(),
+            kind: ExtractorKind::Css,
             selector: "meta[property='og:title']".into(),
             attribute: Some("content".into()),
             multiple: false,
+            output: ExtractorOutput::Value,
+            data_type: None,
             enabled: true,
         })
         .await
@@ -285,16 +350,11 @@ async fn full_pipeline_extractor_config_to_stored_page() {
     // Step 2: app startup — build registry from DB
     let mut registry = ExtractorRegistry::new();
     for ext in ext_repo.list_enabled_extractors().await.unwrap() {
-        registry.register(Box::new(SelectorExtractor::new(ExtractorConfig {
-            tag: ext.tag,
-            selector: ext.selector,
-            attribute: ext.attribute,
-            multiple: ext.multiple,
-        })));
+        registry.register(data_extractor::build(ExtractorConfig::from(ext)).unwrap());
     }
 
     // Step 3: analyze_page runs the registry
-    let extracted_data = registry.run(sample_html());
+    let extracted_data = registry.run(sample_html(), &ResponseHeaders::default());
     assert!(
         !extracted_data.is_empty(),
         "registry must produce data — if this fails the problem is in the registry"
@@ -322,11 +382,15 @@ async fn full_pipeline_extractor_config_to_stored_page() {
         robots_meta: None,
         word_count: Some(50),
         load_time_ms: Some(100),
+        ttfb_ms: None,
+        download_ms: None,
         response_size_bytes: Some(2000),
         has_viewport: false,
         has_structured_data: false,
         crawled_at: Utc::now(),
         extracted_data,
+        response_headers: Default::default(),
+        weight: None,
     };
 
     page_repo.insert(&page).await.unwrap();
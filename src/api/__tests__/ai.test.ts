@@ -13,6 +13,8 @@ vi.mock("@/src/bindings", () => ({
     getGeminiInsights: vi.fn(),
     getAiSource: vi.fn(),
     generateLocalInsights: vi.fn(),
+    getOllamaSettings: vi.fn(),
+    setOllamaSettings: vi.fn(),
   },
 }));
 
@@ -36,7 +38,12 @@ import {
   setPersona,
   getAiEnabled,
   setAiEnabled,
+  getAiSource,
+  getOllamaSettings,
+  setOllamaSettings,
+  generateAnalysis,
 } from "../ai";
+import { toast } from "sonner";
 
 const mocked = vi.mocked(commands);
 beforeEach(() => vi.clearAllMocks());
@@ -97,3 +104,62 @@ describe("setAiEnabled", () => {
     expect(mocked.setGeminiEnabled).toHaveBeenCalledWith(false);
   });
 });
+
+describe("getAiSource", () => {
+  it("passes the HTTP backends through", async () => {
+    mocked.getAiSource.mockResolvedValue({ status: "ok", data: "ollama" } as never);
+    expect((await getAiSource()).unwrap()).toBe("ollama");
+
+    mocked.getAiSource.mockResolvedValue({ status: "ok", data: "openai" } as never);
+    expect((await getAiSource()).unwrap()).toBe("openai");
+  });
+
+  it("falls back to gemini for an unknown source", async () => {
+    mocked.getAiSource.mockResolvedValue({ status: "ok", data: "bard" } as never);
+    expect((await getAiSource()).unwrap()).toBe("gemini");
+  });
+});
+
+describe("Ollama settings", () => {
+  it("round-trips through the commands", async () => {
+    const settings = { base_url: "http://localhost:11434", model: "llama3.1:8b" };
+    mocked.getOllamaSettings.mockResolvedValue({ status: "ok", data: settings } as never);
+    mocked.setOllamaSettings.mockResolvedValue({ status: "ok", data: null } as never);
+
+    expect((await getOllamaSettings()).unwrap()).toEqual(settings);
+    expect((await setOllamaSettings(settings)).isOk()).toBe(true);
+    expect(mocked.setOllamaSettings).toHaveBeenCalledWith(settings);
+  });
+});
+
+describe("generateAnalysis", () => {
+  const result = {
+    analysis: { id: "job-1", url: "https://example.com" },
+    summary: { seo_score: 80, total_issues: 0, avg_load_time: 1, total_words: 100 },
+    issues: [],
+    pages: [],
+  } as never;
+
+  it("asks the backend for insights when an HTTP backend is selected", async () => {
+    mocked.getAiSource.mockResolvedValue({ status: "ok", data: "ollama" } as never);
+    mocked.getGeminiInsights.mockResolvedValue({ status: "ok", data: "Insights" } as never);
+
+    const res = await generateAnalysis(result);
+
+    expect(res.unwrap()).toEqual({ text: "Insights", source: "ollama" });
+    expect(mocked.generateLocalInsights).not.toHaveBeenCalled();
+  });
+
+  it("points the user to settings when the backend isn't configured", async () => {
+    mocked.getAiSource.mockResolvedValue({ status: "ok", data: "openai" } as never);
+    mocked.getGeminiInsights.mockResolvedValue({
+      status: "error",
+      error: "Failed to generate AI insights: AI_BACKEND_NOT_CONFIGURED: choose a model",
+    } as never);
+
+    const res = await generateAnalysis(result);
+
+    expect(res.isErr()).toBe(true);
+    expect(toast).toHaveBeenCalledWith("AI Backend Configuration", expect.anything());
+  });
+});
@@ -9,6 +9,10 @@ vi.mock("@/src/bindings", () => ({
     createCustomExtractor: vi.fn(),
     deleteCustomCheck: vi.fn(),
     deleteCustomExtractor: vi.fn(),
+    dryRunCheckScript: vi.fn(),
+    dryRunCustomCheck: vi.fn(),
+    applyCustomChecks: vi.fn(),
+    dryRunCustomExtractor: vi.fn(),
   },
 }));
 
@@ -18,6 +22,10 @@ import {
   listCustomChecks,
   listCustomExtractors,
   createCustomCheck,
+  dryRunCheckScript,
+  dryRunCustomCheck,
+  applyCustomChecks,
+  dryRunCustomExtractor,
 } from "../extension";
 
 const mocked = vi.mocked(commands);
@@ -92,3 +100,101 @@ describe("createCustomCheck", () => {
     expect(result).toEqual(check);
   });
 });
+
+describe("dryRunCheckScript", () => {
+  const params = {
+    name: "Alt text",
+    severity: "warning" as const,
+    field: "",
+    operator: "present" as const,
+    threshold: null,
+    script: 'page.images.filter(|img| img.alt == ()).map(|img| img.src)',
+    message_template: "",
+    enabled: true,
+  };
+
+  it("returns the run on success", async () => {
+    const run = { issues: [], output: ["hello"], error: null };
+    mocked.dryRunCheckScript.mockResolvedValue({ status: "ok", data: run } as never);
+
+    const result = await dryRunCheckScript(params, "job-1", "https://example.com/");
+    expect(result).toEqual(run);
+    expect(mocked.dryRunCheckScript).toHaveBeenCalledWith(params, "job-1", "https://example.com/");
+  });
+
+  it("throws on error", async () => {
+    mocked.dryRunCheckScript.mockResolvedValue({
+      status: "error",
+      error: "[DB_NOT_FOUND] page not found",
+    } as never);
+
+    await expect(dryRunCheckScript(params, "job-1", "https://example.com/x")).rejects.toThrow(
+      "page not found",
+    );
+  });
+});
+
+describe("dryRunCustomCheck", () => {
+  const params = {
+    name: "Missing OG image",
+    severity: "warning" as const,
+    field: "tag:og_image",
+    operator: "missing" as const,
+    threshold: null,
+    message_template: "No og:image",
+    enabled: true,
+  };
+
+  it("returns the would-be issues", async () => {
+    const run = {
+      pages_checked: 2,
+      issues: [
+        { url: "https://example.com/b", severity: "warning", message: "No og", details: null },
+      ],
+    };
+    mocked.dryRunCustomCheck.mockResolvedValue({ status: "ok", data: run } as never);
+
+    expect(await dryRunCustomCheck(params, "job-1")).toEqual(run);
+    expect(mocked.dryRunCustomCheck).toHaveBeenCalledWith(params, "job-1");
+  });
+
+  it("throws on error", async () => {
+    mocked.dryRunCustomCheck.mockResolvedValue({
+      status: "error",
+      error: "[SVC_INVALID_STATE] invalid state: job job-1 hasn't finished",
+    } as never);
+
+    await expect(dryRunCustomCheck(params, "job-1")).rejects.toThrow("hasn't finished");
+  });
+});
+
+describe("applyCustomChecks", () => {
+  it("returns what changed", async () => {
+    const applied = { pages_checked: 3, pages_reextracted: 2, issues_removed: 1, issues_added: 2 };
+    mocked.applyCustomChecks.mockResolvedValue({ status: "ok", data: applied } as never);
+
+    expect(await applyCustomChecks("job-1")).toEqual(applied);
+    expect(mocked.applyCustomChecks).toHaveBeenCalledWith("job-1");
+  });
+});
+
+describe("dryRunCustomExtractor", () => {
+  it("returns the values per page", async () => {
+    const params = {
+      name: "OG image",
+      tag: "og_image",
+      selector: 'meta[property="og:image"]',
+      attribute: "content",
+      multiple: false,
+      enabled: true,
+    };
+    const run = {
+      pages: [{ url: "https://example.com/", value: "/a.png" }],
+      without_html: ["https://example.com/c"],
+    };
+    mocked.dryRunCustomExtractor.mockResolvedValue({ status: "ok", data: run } as never);
+
+    expect(await dryRunCustomExtractor(params, "job-1")).toEqual(run);
+    expect(mocked.dryRunCustomExtractor).toHaveBeenCalledWith(params, "job-1");
+  });
+});
@@ -0,0 +1,63 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@/src/bindings", () => ({
+  commands: {
+    exportRulePack: vi.fn(),
+    previewRulePack: vi.fn(),
+    importRulePack: vi.fn(),
+  },
+}));
+
+import { commands } from "@/src/bindings";
+import { exportRulePack, previewRulePack, importRulePack } from "../rule-pack";
+
+const mocked = vi.mocked(commands);
+
+beforeEach(() => vi.clearAllMocks());
+
+describe("exportRulePack", () => {
+  it("returns the pack contents on success", async () => {
+    mocked.exportRulePack.mockResolvedValue({ status: "ok", data: "{}" } as never);
+
+    const request = { name: "Social", check_ids: ["c1"], format: "json" as const };
+    await expect(exportRulePack(request)).resolves.toBe("{}");
+    expect(mocked.exportRulePack).toHaveBeenCalledWith(request);
+  });
+
+  it("throws on error", async () => {
+    mocked.exportRulePack.mockResolvedValue({
+      status: "error",
+      error: "[SVC_INVALID_QUERY] nothing was selected to export",
+    } as never);
+
+    await expect(exportRulePack({ name: "Empty" })).rejects.toThrow("nothing was selected");
+  });
+});
+
+describe("previewRulePack", () => {
+  it("passes the content and strategy through", async () => {
+    const preview = { name: "Social", items: [], unresolved: [], signer: null };
+    mocked.previewRulePack.mockResolvedValue({ status: "ok", data: preview } as never);
+
+    await expect(previewRulePack("{}", "rename")).resolves.toEqual(preview);
+    expect(mocked.previewRulePack).toHaveBeenCalledWith("{}", "rename");
+  });
+});
+
+describe("importRulePack", () => {
+  it("returns the per-item outcomes", async () => {
+    const items = [{ kind: "check", id: "c1", name: "Check", outcome: "created" }];
+    mocked.importRulePack.mockResolvedValue({ status: "ok", data: items } as never);
+
+    await expect(importRulePack("{}", "merge")).resolves.toEqual(items);
+  });
+
+  it("throws on error", async () => {
+    mocked.importRulePack.mockResolvedValue({
+      status: "error",
+      error: "[SVC_INVALID_QUERY] the rule pack's signature doesn't match its contents",
+    } as never);
+
+    await expect(importRulePack("{}", "merge")).rejects.toThrow("signature");
+  });
+});
@@ -0,0 +1,67 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+
+vi.mock("@/src/bindings", () => ({
+  commands: {
+    getPageSource: vi.fn(),
+    diffPageSource: vi.fn(),
+    getSnapshotSettings: vi.fn(),
+    setSnapshotSettings: vi.fn(),
+    getSnapshotStorage: vi.fn(),
+  },
+}));
+
+import { commands } from "@/src/bindings";
+import { getPageSource, diffPageSource, setSnapshotSettings } from "../snapshot";
+
+const mocked = vi.mocked(commands);
+
+beforeEach(() => vi.clearAllMocks());
+
+describe("getPageSource", () => {
+  it("returns the snapshot on success", async () => {
+    const snapshot = {
+      job_id: "job-1",
+      url: "https://example.com/",
+      html: "<html></html>",
+      headers: [["content-type", "text/html"]],
+      hash: "abc",
+      captured_at: "2026-01-01T00:00:00Z",
+    };
+    mocked.getPageSource.mockResolvedValue({ status: "ok", data: snapshot } as never);
+
+    await expect(getPageSource("job-1", "https://example.com/")).resolves.toEqual(snapshot);
+    expect(mocked.getPageSource).toHaveBeenCalledWith("job-1", "https://example.com/");
+  });
+
+  it("throws on error", async () => {
+    mocked.getPageSource.mockResolvedValue({
+      status: "error",
+      error: "[REPO_NOT_FOUND] page snapshot not found",
+    } as never);
+
+    await expect(getPageSource("job-1", "https://example.com/")).rejects.toThrow("not found");
+  });
+});
+
+describe("diffPageSource", () => {
+  it("diffs against the previous crawl unless a job is given", async () => {
+    mocked.diffPageSource.mockResolvedValue({ status: "ok", data: { hunks: [] } } as never);
+
+    await diffPageSource("job-2", "https://example.com/");
+    expect(mocked.diffPageSource).toHaveBeenCalledWith("job-2", "https://example.com/", null);
+
+    await diffPageSource("job-2", "https://example.com/", "job-1");
+    expect(mocked.diffPageSource).toHaveBeenCalledWith("job-2", "https://example.com/", "job-1");
+  });
+});
+
+describe("setSnapshotSettings", () => {
+  it("returns the storage left after pruning", async () => {
+    const storage = { snapshots: 3, distinct_sources: 2, html_bytes: 900, stored_bytes: 200 };
+    mocked.setSnapshotSettings.mockResolvedValue({ status: "ok", data: storage } as never);
+
+    const settings = { enabled: true, retention_days: 30 };
+    await expect(setSnapshotSettings(settings)).resolves.toEqual(storage);
+    expect(mocked.setSnapshotSettings).toHaveBeenCalledWith(settings);
+  });
+});
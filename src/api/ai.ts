@@ -1,12 +1,21 @@
 import { toast } from "sonner";
 import { Result } from "../lib/result";
-import { commands, type CompleteAnalysisResponse, type SeoIssue } from "@/src/bindings";
+import {
+  commands,
+  type CompleteAnalysisResponse,
+  type OllamaSettings,
+  type OpenAiCompatibleSettings,
+  type SeoIssue,
+} from "@/src/bindings";
+
+export type { OllamaSettings, OpenAiCompatibleSettings };
 
 export const AiError = {
   MissingKey: "MISSING_KEY",
   InvalidKey: "INVALID_KEY",
   RateLimit: "RATE_LIMIT",
   NetworkError: "NETWORK_ERROR",
+  NotConfigured: "NOT_CONFIGURED",
   Unknown: "UNKNOWN",
 } as const;
 
@@ -29,6 +38,7 @@ function openSettingsDialog() {
 
 function mapErrorToType(error: string): AiError {
   if (error.includes("API_KEY_MISSING")) return AiError.MissingKey;
+  if (error.includes("AI_BACKEND_NOT_CONFIGURED")) return AiError.NotConfigured;
   if (error.includes("401")) return AiError.InvalidKey;
   if (error.includes("429")) return AiError.RateLimit;
   return AiError.Unknown;
@@ -115,10 +125,16 @@ export async function setContextOptions(options: string): Promise<Result<null, s
   return res.status === "ok" ? Result.Ok(res.data) : Result.Err(res.error ?? "");
 }
 
+const AI_SOURCES: readonly AiSource[] = ["gemini", "local", "openai", "ollama"];
+
+function toAiSource(value: string): AiSource {
+  return AI_SOURCES.find((source) => source === value) ?? "gemini";
+}
+
 export async function getAiSource(): Promise<Result<AiSource, string>> {
   const res = await commands.getAiSource();
   if (res.status === "ok") {
-    return Result.Ok(res.data === "local" ? "local" : "gemini");
+    return Result.Ok(toAiSource(res.data));
   }
   return Result.Err(res.error ?? "Failed to get AI source");
 }
@@ -128,6 +144,28 @@ export async function setAiSource(source: AiSource): Promise<Result<null, string
   return res.status === "ok" ? Result.Ok(res.data) : Result.Err(res.error ?? "Failed to set AI source");
 }
 
+export async function getOpenAiSettings(): Promise<Result<OpenAiCompatibleSettings, string>> {
+  const res = await commands.getOpenaiSettings();
+  return res.status === "ok" ? Result.Ok(res.data) : Result.Err(res.error ?? "");
+}
+
+export async function setOpenAiSettings(
+  settings: OpenAiCompatibleSettings,
+): Promise<Result<null, string>> {
+  const res = await commands.setOpenaiSettings(settings);
+  return res.status === "ok" ? Result.Ok(res.data) : Result.Err(res.error ?? "");
+}
+
+export async function getOllamaSettings(): Promise<Result<OllamaSettings, string>> {
+  const res = await commands.getOllamaSettings();
+  return res.status === "ok" ? Result.Ok(res.data) : Result.Err(res.error ?? "");
+}
+
+export async function setOllamaSettings(settings: OllamaSettings): Promise<Result<null, string>> {
+  const res = await commands.setOllamaSettings(settings);
+  return res.status === "ok" ? Result.Ok(res.data) : Result.Err(res.error ?? "");
+}
+
 function buildInsightsPayload(result: CompleteAnalysisResponse) {
   const { analysis, summary, issues, pages } = result;
 
@@ -185,7 +223,7 @@ function buildInsightsPayload(result: CompleteAnalysisResponse) {
   };
 }
 
-export type AiSource = "gemini" | "local";
+export type AiSource = "gemini" | "local" | "openai" | "ollama";
 
 /**
  * Generate AI insights using whichever source the user has selected in
@@ -197,13 +235,13 @@ export async function generateAnalysis(
   const payload = buildInsightsPayload(result);
 
   const sourceRes = await commands.getAiSource();
-  const source: AiSource =
-    sourceRes.status === "ok" && sourceRes.data === "local" ? "local" : "gemini";
+  const source = sourceRes.status === "ok" ? toAiSource(sourceRes.data) : "gemini";
 
-  if (source === "gemini") {
+  // Every backend but the local model is called from the Rust side.
+  if (source !== "local") {
     const res = await commands.getGeminiInsights(payload);
-    if (res.status === "ok") return Result.Ok({ text: res.data, source: "gemini" });
-    const err = res.status === "error" ? (res.error as string) : "Gemini request failed";
+    if (res.status === "ok") return Result.Ok({ text: res.data, source });
+    const err = res.status === "error" ? (res.error as string) : "AI request failed";
     handleAiUiEffects(mapErrorToType(err));
     return Result.Err(err);
   }
@@ -239,6 +277,13 @@ function handleAiUiEffects(error: AiError) {
       });
       break;
 
+    case AiError.NotConfigured:
+      toast("AI Backend Configuration", {
+        description: "Choose an endpoint and model for the selected AI source.",
+        action: { label: "Configure", onClick: () => openSettingsDialog() },
+      });
+      break;
+
     case AiError.NetworkError:
       toast.error("Connection Error", {
         description: "Could not reach Gemini services. Check your internet.",
@@ -1,14 +1,31 @@
 import { commands } from "@/src/bindings";
 import type {
+  AppliedChecks,
+  CheckDryRun,
   CustomCheck,
   CustomCheckParams,
   CustomExtractor,
   CustomExtractorParams,
+  ExtractorDryRun,
+  RuleCondition,
+  ScriptDryRun,
   Tag,
   TagScope,
 } from "@/src/bindings";
 
-export type { CustomCheck, CustomCheckParams, CustomExtractor, CustomExtractorParams, Tag, TagScope };
+export type {
+  AppliedChecks,
+  CheckDryRun,
+  CustomCheck,
+  CustomCheckParams,
+  CustomExtractor,
+  CustomExtractorParams,
+  ExtractorDryRun,
+  RuleCondition,
+  ScriptDryRun,
+  Tag,
+  TagScope,
+};
 
 // --- Tags ---
 
@@ -44,6 +61,34 @@ export async function deleteCustomCheck(id: string): Promise<void> {
   if (res.status !== "ok") throw new Error(res.error ?? "Failed to delete custom check");
 }
 
+/** Run a draft check's script against the page crawled at `url` in a job. */
+export async function dryRunCheckScript(
+  params: CustomCheckParams,
+  jobId: string,
+  url: string,
+): Promise<ScriptDryRun> {
+  const res = await commands.dryRunCheckScript(params, jobId, url);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to run check script");
+}
+
+/** Run a draft check over every page of a finished job, without storing its issues. */
+export async function dryRunCustomCheck(
+  params: CustomCheckParams,
+  jobId: string,
+): Promise<CheckDryRun> {
+  const res = await commands.dryRunCustomCheck(params, jobId);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to run custom check");
+}
+
+/** Re-run the saved custom checks on a finished job and replace their issues. */
+export async function applyCustomChecks(jobId: string): Promise<AppliedChecks> {
+  const res = await commands.applyCustomChecks(jobId);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to apply custom checks");
+}
+
 // --- Custom Extractors ---
 
 export async function listCustomExtractors(): Promise<CustomExtractor[]> {
@@ -68,3 +113,13 @@ export async function deleteCustomExtractor(id: string): Promise<void> {
   const res = await commands.deleteCustomExtractor(id);
   if (res.status !== "ok") throw new Error(res.error ?? "Failed to delete custom extractor");
 }
+
+/** Run a draft extractor over the saved HTML of every page of a finished job. */
+export async function dryRunCustomExtractor(
+  params: CustomExtractorParams,
+  jobId: string,
+): Promise<ExtractorDryRun> {
+  const res = await commands.dryRunCustomExtractor(params, jobId);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to run custom extractor");
+}
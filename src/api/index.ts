@@ -6,3 +6,5 @@ export * from "./licensing";
 export * from "./local-model";
 export * from "./permissions";
 export * from "./report";
+export * from "./rule-pack";
+export * from "./snapshot";
@@ -0,0 +1,47 @@
+import { commands } from "@/src/bindings";
+import type {
+  ImportOutcome,
+  ImportStrategy,
+  PackFormat,
+  PackSigner,
+  RulePackExport,
+  RulePackItem,
+  RulePackItemKind,
+  RulePackPreview,
+} from "@/src/bindings";
+
+export type {
+  ImportOutcome,
+  ImportStrategy,
+  PackFormat,
+  PackSigner,
+  RulePackExport,
+  RulePackItem,
+  RulePackItemKind,
+  RulePackPreview,
+};
+
+/** Build a rule pack from the selected items; resolves to the file contents. */
+export async function exportRulePack(request: RulePackExport): Promise<string> {
+  const res = await commands.exportRulePack(request);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to export rule pack");
+}
+
+export async function previewRulePack(
+  content: string,
+  strategy: ImportStrategy,
+): Promise<RulePackPreview> {
+  const res = await commands.previewRulePack(content, strategy);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to read rule pack");
+}
+
+export async function importRulePack(
+  content: string,
+  strategy: ImportStrategy,
+): Promise<RulePackItem[]> {
+  const res = await commands.importRulePack(content, strategy);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to import rule pack");
+}
@@ -0,0 +1,60 @@
+import { commands } from "@/src/bindings";
+import type {
+  DiffHunk,
+  DiffLine,
+  DiffLineKind,
+  PageSnapshot,
+  SnapshotSettings,
+  SnapshotStorage,
+  SourceDiff,
+} from "@/src/bindings";
+
+export type {
+  DiffHunk,
+  DiffLine,
+  DiffLineKind,
+  PageSnapshot,
+  SnapshotSettings,
+  SnapshotStorage,
+  SourceDiff,
+};
+
+/** The saved HTML and headers of the page crawled at `url` in `jobId`. */
+export async function getPageSource(jobId: string, url: string): Promise<PageSnapshot> {
+  const res = await commands.getPageSource(jobId, url);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to load page source");
+}
+
+/**
+ * Diff a page's source against the same URL in `againstJobId`, or in the
+ * previous crawl that saved it when omitted.
+ */
+export async function diffPageSource(
+  jobId: string,
+  url: string,
+  againstJobId?: string,
+): Promise<SourceDiff> {
+  const res = await commands.diffPageSource(jobId, url, againstJobId ?? null);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to compare page source");
+}
+
+export async function getSnapshotSettings(): Promise<SnapshotSettings> {
+  const res = await commands.getSnapshotSettings();
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to load snapshot settings");
+}
+
+/** Save the settings; resolves to the storage left after pruning. */
+export async function setSnapshotSettings(settings: SnapshotSettings): Promise<SnapshotStorage> {
+  const res = await commands.setSnapshotSettings(settings);
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to save snapshot settings");
+}
+
+export async function getSnapshotStorage(): Promise<SnapshotStorage> {
+  const res = await commands.getSnapshotStorage();
+  if (res.status === "ok") return res.data;
+  throw new Error(res.error ?? "Failed to load snapshot storage");
+}
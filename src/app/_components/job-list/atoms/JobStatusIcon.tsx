@@ -1,5 +1,5 @@
 import type { JobStatus } from "@/src/api/analysis";
-import { CheckCircle2, Clock, Loader2, Search, XCircle, Ban } from "lucide-react";
+import { CheckCircle2, Clock, Loader2, Search, XCircle, Ban, Pause } from "lucide-react";
 
 export function getStatusIcon(status: JobStatus) {
   switch (status) {
@@ -19,6 +19,8 @@ export function getStatusIcon(status: JobStatus) {
       return <XCircle className="h-4 w-4 text-destructive" />;
     case "cancelled":
       return <Ban className="h-4 w-4 text-muted-foreground/60" />;
+    case "paused":
+      return <Pause className="h-4 w-4 text-muted-foreground" />;
     default:
       return <Clock className="h-4 w-4 text-muted-foreground" />;
   }
@@ -91,6 +91,20 @@ export function AnalysisSettingsFields({ maxPages, isFreeUser }: AnalysisSetting
                                 />
                             )}
                         />
+                        <FormField
+                            control={control}
+                            name="settings.resource_analysis"
+                            render={({ field }) => (
+                                <SettingToggle
+                                    id="resource-analysis"
+                                    label="Page Weight"
+                                    description="Fetch CSS, JS & fonts to size each page"
+                                    tooltip="Downloads each stylesheet, script and font once per audit to measure page weight, render-blocking assets and third-party share without Chrome."
+                                    checked={field.value ?? false}
+                                    onCheckedChange={field.onChange}
+                                />
+                            )}
+                        />
                         <FormField
                             control={control}
                             name="settings.mobile_analysis"
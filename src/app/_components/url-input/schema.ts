@@ -19,6 +19,7 @@ export const createSchema = (maxPages: number) => z.object({
         check_images: z.boolean(),
         mobile_analysis: z.boolean(),
         lighthouse_analysis: z.boolean(),
+        resource_analysis: z.boolean().optional(),
         delay_between_requests: z.number().min(0).max(5000),
     })
 })
@@ -11,6 +11,7 @@ import HeadingsTab from "./molecules/HeadingsTab";
 import ImagesTab from "./molecules/ImagesTab";
 import LinksTab from "./molecules/LinksTab";
 import ExtractedDataTab from "./molecules/ExtractedDataTab";
+import SourceTab from "./molecules/SourceTab";
 import { ChevronLeft, ChevronRight } from "lucide-react";
 import { logger } from "@/src/lib";
 
@@ -76,6 +77,7 @@ export function PageDetailView({
             count: (page.internal_links || 0) + (page.external_links || 0),
         },
         { value: "extracted", label: "Extracted", count: extractedCount },
+        { value: "source", label: "Source" },
     ];
 
     return (
@@ -133,6 +135,9 @@ export function PageDetailView({
                 <TabsContent value="extracted" className="mt-0 focus-visible:outline-none">
                     <ExtractedDataTab extractedData={extractedData} />
                 </TabsContent>
+                <TabsContent value="source" className="mt-0 focus-visible:outline-none">
+                    <SourceTab jobId={page.analysis_id} url={page.url} />
+                </TabsContent>
             </Tabs>
 
             {/* Keyboard Shortcuts Footer */}
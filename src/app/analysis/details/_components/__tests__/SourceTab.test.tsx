@@ -0,0 +1,82 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { render, screen } from "@testing-library/react";
+import userEvent from "@testing-library/user-event";
+
+vi.mock("@/src/api/snapshot", () => ({
+  getPageSource: vi.fn(),
+  diffPageSource: vi.fn(),
+}));
+
+import { diffPageSource, getPageSource } from "@/src/api/snapshot";
+import SourceTab from "../molecules/SourceTab";
+
+const snapshot = {
+  job_id: "job-2",
+  url: "https://example.com/",
+  html: "<title>New</title>",
+  headers: [["content-type", "text/html"]] as [string, string][],
+  hash: "abc",
+  captured_at: "2026-02-01T00:00:00Z",
+};
+
+beforeEach(() => vi.clearAllMocks());
+
+describe("SourceTab", () => {
+  it("shows the saved HTML and response headers", async () => {
+    vi.mocked(getPageSource).mockResolvedValue(snapshot);
+    render(<SourceTab jobId="job-2" url="https://example.com/" />);
+
+    expect(await screen.findByText("<title>New</title>")).toBeInTheDocument();
+    expect(screen.getByText("content-type")).toBeInTheDocument();
+    expect(getPageSource).toHaveBeenCalledWith("job-2", "https://example.com/");
+  });
+
+  it("explains when the page has no saved source", async () => {
+    vi.mocked(getPageSource).mockRejectedValue(new Error("[REPO_NOT_FOUND] page snapshot"));
+    render(<SourceTab jobId="job-2" url="https://example.com/" />);
+
+    expect(await screen.findByText("No saved source for this page")).toBeInTheDocument();
+  });
+
+  it("compares the source with the previous crawl", async () => {
+    const user = userEvent.setup();
+    vi.mocked(getPageSource).mockResolvedValue(snapshot);
+    vi.mocked(diffPageSource).mockResolvedValue({
+      from_job_id: "job-1",
+      from_captured_at: "2026-01-01T00:00:00Z",
+      to_job_id: "job-2",
+      to_captured_at: "2026-02-01T00:00:00Z",
+      url: "https://example.com/",
+      hunks: [
+        {
+          from_line: 1,
+          to_line: 1,
+          lines: [
+            { kind: "removed", text: "<title>Old</title>" },
+            { kind: "added", text: "<title>New</title>" },
+          ],
+        },
+      ],
+    });
+    render(<SourceTab jobId="job-2" url="https://example.com/" />);
+
+    await user.click(await screen.findByRole("button", { name: /compare with previous crawl/i }));
+
+    expect(await screen.findByText("@@ -1 +1 @@")).toBeInTheDocument();
+    expect(screen.getByText("- <title>Old</title>")).toHaveAttribute("data-kind", "removed");
+    expect(screen.getByText("+ <title>New</title>")).toHaveAttribute("data-kind", "added");
+  });
+
+  it("shows why there is nothing to compare against", async () => {
+    const user = userEvent.setup();
+    vi.mocked(getPageSource).mockResolvedValue(snapshot);
+    vi.mocked(diffPageSource).mockRejectedValue(
+      new Error("[SVC_INVALID_STATE] no earlier crawl saved the source of https://example.com/"),
+    );
+    render(<SourceTab jobId="job-2" url="https://example.com/" />);
+
+    await user.click(await screen.findByRole("button", { name: /compare with previous crawl/i }));
+
+    expect(await screen.findByText(/no earlier crawl saved the source/)).toBeInTheDocument();
+  });
+});
@@ -0,0 +1,161 @@
+"use client";
+
+import { useEffect, useState } from "react";
+import { FileCode, GitCompare } from "lucide-react";
+
+import { Button } from "@/src/components/ui/button";
+import { Card, CardContent } from "@/src/components/ui/card";
+import { cn } from "@/src/lib/utils";
+import {
+  diffPageSource,
+  getPageSource,
+  type DiffLineKind,
+  type PageSnapshot,
+  type SourceDiff,
+} from "@/src/api/snapshot";
+
+const LINE_STYLES: Record<DiffLineKind, { prefix: string; className: string }> = {
+  context: { prefix: " ", className: "text-muted-foreground" },
+  added: { prefix: "+", className: "bg-success/10 text-success" },
+  removed: { prefix: "-", className: "bg-destructive/10 text-destructive" },
+};
+
+function formatDate(value: string) {
+  return new Date(value).toLocaleString();
+}
+
+function errorMessage(e: unknown) {
+  return e instanceof Error ? e.message : String(e);
+}
+
+function DiffView({ diff }: { diff: SourceDiff }) {
+  return (
+    <div className="space-y-3">
+      <p className="text-sm text-muted-foreground">
+        Crawled {formatDate(diff.from_captured_at)} → {formatDate(diff.to_captured_at)}
+      </p>
+      {diff.hunks.length === 0 ? (
+        <p className="text-sm">The source hasn&apos;t changed between these crawls.</p>
+      ) : (
+        diff.hunks.map((hunk, i) => (
+          <div key={i} className="overflow-x-auto rounded-md border font-mono text-xs">
+            <div className="bg-muted px-3 py-1 text-muted-foreground">
+              @@ -{hunk.from_line} +{hunk.to_line} @@
+            </div>
+            {hunk.lines.map((line, j) => (
+              <pre
+                key={j}
+                data-kind={line.kind}
+                className={cn("px-3 whitespace-pre", LINE_STYLES[line.kind].className)}
+              >
+                {LINE_STYLES[line.kind].prefix} {line.text}
+              </pre>
+            ))}
+          </div>
+        ))
+      )}
+    </div>
+  );
+}
+
+/**
+ * The HTML and response headers saved for a page when it was crawled,
+ * and what changed in them since the previous crawl of the same URL.
+ */
+export default function SourceTab({ jobId, url }: { jobId: string; url: string }) {
+  const [snapshot, setSnapshot] = useState<PageSnapshot | null>(null);
+  const [loadError, setLoadError] = useState<string | null>(null);
+  const [diff, setDiff] = useState<SourceDiff | null>(null);
+  const [diffError, setDiffError] = useState<string | null>(null);
+  const [comparing, setComparing] = useState(false);
+
+  useEffect(() => {
+    let cancelled = false;
+    setSnapshot(null);
+    setLoadError(null);
+    setDiff(null);
+    setDiffError(null);
+    getPageSource(jobId, url)
+      .then((s) => !cancelled && setSnapshot(s))
+      .catch((e) => !cancelled && setLoadError(errorMessage(e)));
+    return () => {
+      cancelled = true;
+    };
+  }, [jobId, url]);
+
+  async function handleCompare() {
+    setComparing(true);
+    try {
+      setDiff(await diffPageSource(jobId, url));
+      setDiffError(null);
+    } catch (e) {
+      setDiff(null);
+      setDiffError(errorMessage(e));
+    } finally {
+      setComparing(false);
+    }
+  }
+
+  if (loadError) {
+    return (
+      <Card>
+        <CardContent className="py-12 text-center">
+          <FileCode className="h-12 w-12 text-muted-foreground mx-auto mb-3" />
+          <p className="text-muted-foreground">No saved source for this page</p>
+          <p className="text-sm text-muted-foreground mt-1">
+            Turn on page snapshots in Settings → Page Snapshots and re-crawl the site.
+          </p>
+        </CardContent>
+      </Card>
+    );
+  }
+
+  if (!snapshot) {
+    return <p className="text-sm text-muted-foreground">Loading source…</p>;
+  }
+
+  return (
+    <div className="space-y-4">
+      <Card>
+        <CardContent className="pt-6 space-y-4">
+          <div className="flex items-center justify-between gap-2">
+            <p className="text-sm text-muted-foreground">
+              Saved {formatDate(snapshot.captured_at)}
+            </p>
+            <Button variant="outline" size="sm" onClick={handleCompare} disabled={comparing}>
+              <GitCompare className="h-4 w-4 mr-1" />
+              Compare with previous crawl
+            </Button>
+          </div>
+          {diffError && <p className="text-sm text-destructive">{diffError}</p>}
+          {diff && <DiffView diff={diff} />}
+        </CardContent>
+      </Card>
+
+      {snapshot.headers.length > 0 && (
+        <Card>
+          <CardContent className="pt-6">
+            <h3 className="text-sm font-medium mb-2">Response headers</h3>
+            <dl className="grid grid-cols-[auto_1fr] gap-x-4 gap-y-1 font-mono text-xs">
+              {snapshot.headers.map(([name, value], i) => (
+                <div key={i} className="contents">
+                  <dt className="text-muted-foreground">{name}</dt>
+                  <dd className="break-all">{value}</dd>
+                </div>
+              ))}
+            </dl>
+          </CardContent>
+        </Card>
+      )}
+
+      <Card>
+        <CardContent className="pt-6">
+          <h3 className="text-sm font-medium mb-2">HTML</h3>
+          <pre className="max-h-[32rem] overflow-auto rounded-md bg-muted p-3 font-mono text-xs whitespace-pre">
+            {snapshot.html}
+          </pre>
+        </CardContent>
+      </Card>
+    </div>
+  );
+}
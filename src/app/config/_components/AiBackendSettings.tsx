@@ -0,0 +1,155 @@
+"use client";
+
+import { useEffect, useState, type ComponentProps } from "react";
+import { Save } from "lucide-react";
+import { Button } from "@/src/components/ui/button";
+import { Input } from "@/src/components/ui/input";
+import { Label } from "@/src/components/ui/label";
+import {
+  getOllamaSettings,
+  getOpenAiSettings,
+  setOllamaSettings,
+  setOpenAiSettings,
+  type OllamaSettings,
+  type OpenAiCompatibleSettings,
+} from "@/src/api/ai";
+import { useMutation } from "@/src/hooks/use-mutation";
+
+const FIELD_CLASS =
+  "font-mono bg-background/50 focus:bg-background transition-colors border-input/50 focus-visible:ring-0 focus-visible:border-primary";
+
+function Field({
+  id,
+  label,
+  hint,
+  ...input
+}: {
+  id: string;
+  label: string;
+  hint: string;
+} & ComponentProps<typeof Input>) {
+  return (
+    <div className="space-y-2">
+      <Label htmlFor={id}>{label}</Label>
+      <Input id={id} className={FIELD_CLASS} {...input} />
+      <p className="text-xs text-muted-foreground">{hint}</p>
+    </div>
+  );
+}
+
+// ── OpenAI-compatible ─────────────────────────────────────────────────────────
+
+export function OpenAiCompatibleSettingsForm() {
+  const [settings, setSettings] = useState<OpenAiCompatibleSettings | null>(null);
+
+  useEffect(() => {
+    getOpenAiSettings().then((res) => {
+      if (res.isOk()) setSettings(res.unwrap());
+    });
+  }, []);
+
+  const save = useMutation(
+    async (next: OpenAiCompatibleSettings) => {
+      const res = await setOpenAiSettings(next);
+      if (res.isErr()) throw new Error("Failed to save OpenAI-compatible settings");
+    },
+    { successMessage: "OpenAI-compatible settings saved" },
+  );
+
+  if (!settings) return null;
+
+  return (
+    <div className="space-y-4">
+      <div className="space-y-4 p-4 border border-border/50 rounded-lg bg-card/30">
+        <Field
+          id="openai-base-url"
+          label="Base URL"
+          hint="The API root, e.g. https://api.openai.com/v1 or http://localhost:1234/v1 for LM Studio."
+          value={settings.base_url}
+          onChange={(e) => setSettings({ ...settings, base_url: e.target.value })}
+          placeholder="https://api.openai.com/v1"
+        />
+        <Field
+          id="openai-model"
+          label="Model"
+          hint="The model name the server expects."
+          value={settings.model}
+          onChange={(e) => setSettings({ ...settings, model: e.target.value })}
+          placeholder="gpt-4o-mini"
+        />
+        <Field
+          id="openai-api-key"
+          label="API Key"
+          hint="Leave empty for local servers that don't check keys."
+          type="password"
+          value={settings.api_key ?? ""}
+          onChange={(e) => setSettings({ ...settings, api_key: e.target.value })}
+          placeholder="sk-..."
+        />
+      </div>
+      <Button
+        onClick={() => save.execute(settings)}
+        disabled={save.isLoading}
+        size="sm"
+        className="gap-2"
+      >
+        <Save className="h-3.5 w-3.5" />
+        Save Settings
+      </Button>
+    </div>
+  );
+}
+
+// ── Ollama ────────────────────────────────────────────────────────────────────
+
+export function OllamaSettingsForm() {
+  const [settings, setSettings] = useState<OllamaSettings | null>(null);
+
+  useEffect(() => {
+    getOllamaSettings().then((res) => {
+      if (res.isOk()) setSettings(res.unwrap());
+    });
+  }, []);
+
+  const save = useMutation(
+    async (next: OllamaSettings) => {
+      const res = await setOllamaSettings(next);
+      if (res.isErr()) throw new Error("Failed to save Ollama settings");
+    },
+    { successMessage: "Ollama settings saved" },
+  );
+
+  if (!settings) return null;
+
+  return (
+    <div className="space-y-4">
+      <div className="space-y-4 p-4 border border-border/50 rounded-lg bg-card/30">
+        <Field
+          id="ollama-base-url"
+          label="Server URL"
+          hint="Where Ollama is listening; it runs on port 11434 by default."
+          value={settings.base_url}
+          onChange={(e) => setSettings({ ...settings, base_url: e.target.value })}
+          placeholder="http://localhost:11434"
+        />
+        <Field
+          id="ollama-model"
+          label="Model"
+          hint="A model you've pulled with `ollama pull`."
+          value={settings.model}
+          onChange={(e) => setSettings({ ...settings, model: e.target.value })}
+          placeholder="llama3.1:8b"
+        />
+      </div>
+      <Button
+        onClick={() => save.execute(settings)}
+        disabled={save.isLoading}
+        size="sm"
+        className="gap-2"
+      >
+        <Save className="h-3.5 w-3.5" />
+        Save Settings
+      </Button>
+    </div>
+  );
+}
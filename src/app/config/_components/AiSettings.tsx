@@ -1,7 +1,7 @@
 "use client";
 
 import { useCallback, useEffect, useState } from "react";
-import { Cpu, Info, Sparkles } from "lucide-react";
+import { Cpu, Info, Server, Sparkles, Workflow } from "lucide-react";
 import { Save } from "lucide-react";
 import { toast } from "sonner";
 import { Button } from "@/src/components/ui/button";
@@ -17,6 +17,7 @@ import {
 } from "@/src/api/ai";
 import { useMutation } from "@/src/hooks/use-mutation";
 import { LocalModelSettings } from "./LocalModelSettings";
+import { OllamaSettingsForm, OpenAiCompatibleSettingsForm } from "./AiBackendSettings";
 import type { AiSource } from "@/src/api/ai";
 // All Tauri command access now goes through src/api/ai — no direct
 // `commands.*` imports in this component.
@@ -133,6 +134,22 @@ export function AiSettings() {
             description="Runs entirely on your machine. No API key needed. Private by default."
             onClick={() => selectSource("local")}
           />
+          <SourceOption
+            id="openai"
+            active={source === "openai"}
+            icon={<Workflow className="h-4 w-4" />}
+            title="OpenAI-compatible API"
+            description="OpenAI, or any server with the same chat completions API such as LM Studio or vLLM."
+            onClick={() => selectSource("openai")}
+          />
+          <SourceOption
+            id="ollama"
+            active={source === "ollama"}
+            icon={<Server className="h-4 w-4" />}
+            title="Ollama"
+            description="Models served by Ollama, on this machine or elsewhere on your network."
+            onClick={() => selectSource("ollama")}
+          />
         </div>
       </div>
 
@@ -181,6 +198,8 @@ export function AiSettings() {
       )}
 
       {source === "local" && <LocalModelSettings />}
+      {source === "openai" && <OpenAiCompatibleSettingsForm />}
+      {source === "ollama" && <OllamaSettingsForm />}
     </div>
   );
 }
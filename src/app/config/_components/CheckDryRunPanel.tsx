@@ -0,0 +1,78 @@
+"use client";
+
+import { useState } from "react";
+import { Play } from "lucide-react";
+
+import { Badge } from "@/src/components/ui/badge";
+import { Button } from "@/src/components/ui/button";
+
+import { dryRunCustomCheck, type CheckDryRun, type CustomCheckParams } from "@/src/api/extension";
+import { CompletedJobSelect } from "./CompletedJobSelect";
+
+const SHOWN_ISSUES = 50;
+
+/**
+ * Runs the check being edited over every page of a finished crawl and
+ * lists the issues it would raise. Nothing is saved to the crawl.
+ */
+export function CheckDryRunPanel({ params }: { params: CustomCheckParams }) {
+  const [jobId, setJobId] = useState("");
+  const [running, setRunning] = useState(false);
+  const [run, setRun] = useState<CheckDryRun | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  async function handleRun() {
+    setRunning(true);
+    try {
+      setRun(await dryRunCustomCheck(params, jobId));
+      setError(null);
+    } catch (e) {
+      setRun(null);
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setRunning(false);
+    }
+  }
+
+  return (
+    <div className="space-y-3 rounded-md border p-3">
+      <div className="grid grid-cols-[1fr_auto] items-end gap-2">
+        <CompletedJobSelect value={jobId} onChange={(job) => setJobId(job.job_id)} />
+        <Button variant="outline" onClick={handleRun} disabled={!jobId || running}>
+          <Play className="h-4 w-4 mr-1" />
+          Run
+        </Button>
+      </div>
+
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      {run && (
+        <div className="space-y-2 text-sm">
+          <p className="text-muted-foreground">
+            {run.issues.length} issue(s) on {run.pages_checked} page(s).
+          </p>
+          <ul className="max-h-48 space-y-1 overflow-auto">
+            {run.issues.slice(0, SHOWN_ISSUES).map((issue, i) => (
+              <li key={i} className="flex items-start gap-2">
+                <Badge variant="outline" className="text-xs capitalize">
+                  {issue.severity}
+                </Badge>
+                <span className="min-w-0">
+                  <span className="block truncate font-mono text-xs">
+                    {issue.url ?? "Whole site"}
+                  </span>
+                  {issue.message}
+                </span>
+              </li>
+            ))}
+          </ul>
+          {run.issues.length > SHOWN_ISSUES && (
+            <p className="text-xs text-muted-foreground">
+              and {run.issues.length - SHOWN_ISSUES} more
+            </p>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
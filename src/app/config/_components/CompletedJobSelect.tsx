@@ -0,0 +1,54 @@
+"use client";
+
+import useSWR from "swr";
+
+import { Label } from "@/src/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/src/components/ui/select";
+
+import { getAllJobs } from "@/src/api/analysis";
+import type { AnalysisProgress } from "@/src/bindings";
+
+const fetchCompletedJobs = () =>
+  getAllJobs(50, 0).then((res) => res.unwrap().filter((job) => job.job_status === "completed"));
+
+/** Picks one of the recent finished crawls to run a draft check or extractor against. */
+export function CompletedJobSelect({
+  value,
+  onChange,
+  label = "Test against crawl",
+}: {
+  value: string;
+  onChange: (job: AnalysisProgress) => void;
+  label?: string;
+}) {
+  const { data: jobs = [] } = useSWR("completed-jobs", fetchCompletedJobs);
+
+  function handleChange(id: string) {
+    const job = jobs.find((j) => j.job_id === id);
+    if (job) onChange(job);
+  }
+
+  return (
+    <div className="space-y-1.5">
+      <Label>{label}</Label>
+      <Select value={value} onValueChange={handleChange}>
+        <SelectTrigger aria-label={label}>
+          <SelectValue placeholder="Select a crawl…" />
+        </SelectTrigger>
+        <SelectContent>
+          {jobs.map((job) => (
+            <SelectItem key={job.job_id} value={job.job_id}>
+              {job.url}
+            </SelectItem>
+          ))}
+        </SelectContent>
+      </Select>
+    </div>
+  );
+}
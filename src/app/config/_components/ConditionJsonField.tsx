@@ -0,0 +1,87 @@
+"use client";
+
+import { useEffect, useState } from "react";
+
+import { Label } from "@/src/components/ui/label";
+import { Textarea } from "@/src/components/ui/textarea";
+
+import type { RuleCondition } from "@/src/api/extension";
+
+const PLACEHOLDER = `{"kind": "all", "children": [
+  {"kind": "compare", "field": "title", "operator": "contains", "threshold": "Acme"},
+  {"kind": "compare", "field": "word_count", "operator": "lt", "threshold": "300"}
+]}`;
+
+function format(condition: RuleCondition | null | undefined): string {
+  return condition ? JSON.stringify(condition, null, 2) : "";
+}
+
+/** Parse the editor text: `null` when blank, `undefined` when not a JSON object. */
+function parse(text: string): RuleCondition | null | undefined {
+  if (!text.trim()) return null;
+  try {
+    const value: unknown = JSON.parse(text);
+    return value && typeof value === "object" && !Array.isArray(value)
+      ? (value as RuleCondition)
+      : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+interface ConditionJsonFieldProps {
+  id: string;
+  value: RuleCondition | null | undefined;
+  onChange: (condition: RuleCondition | null) => void;
+  /** Called with `true` while the text isn't a JSON object. */
+  onInvalidChange: (invalid: boolean) => void;
+}
+
+/**
+ * Optional compound condition (all / any / not over comparisons), edited
+ * as JSON. When set it replaces the single field/operator/threshold rule;
+ * the backend validates thresholds and regexes on save.
+ */
+export function ConditionJsonField({ id, value, onChange, onInvalidChange }: ConditionJsonFieldProps) {
+  const [text, setText] = useState(() => format(value));
+  const invalid = parse(text) === undefined;
+
+  // Pick up a value loaded from outside (the dialog reopening on another
+  // item) without reformatting what the user is typing.
+  useEffect(() => {
+    setText((current) =>
+      JSON.stringify(parse(current) ?? null) === JSON.stringify(value ?? null)
+        ? current
+        : format(value),
+    );
+  }, [value]);
+
+  useEffect(() => {
+    onInvalidChange(invalid);
+  }, [invalid, onInvalidChange]);
+
+  function handleChange(next: string) {
+    setText(next);
+    const parsed = parse(next);
+    if (parsed !== undefined) onChange(parsed);
+  }
+
+  return (
+    <div className="space-y-1.5">
+      <Label htmlFor={id}>Compound Condition (optional)</Label>
+      <Textarea
+        id={id}
+        rows={4}
+        className="font-mono text-xs"
+        placeholder={PLACEHOLDER}
+        value={text}
+        onChange={(e) => handleChange(e.target.value)}
+      />
+      <p className={invalid ? "text-xs text-destructive" : "text-xs text-muted-foreground"}>
+        {invalid
+          ? "Not a valid JSON object"
+          : "Replaces the field, operator, and threshold above when set."}
+      </p>
+    </div>
+  );
+}
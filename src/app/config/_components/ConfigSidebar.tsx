@@ -2,7 +2,7 @@
 import pkg from "@/package.json";
 
 import { useRouter } from "next/navigation";
-import { LayoutDashboard, Bot, CreditCard, Palette, ChevronLeft, Puzzle, Code2, FileBarChart2, Tags } from "lucide-react";
+import { LayoutDashboard, Bot, CreditCard, Palette, ChevronLeft, Puzzle, Code2, FileBarChart2, Tags, Package, FileCode } from "lucide-react";
 import { Button } from "@/src/components/ui/button";
 
 export const SIDEBAR_ITEMS = [
@@ -12,6 +12,8 @@ export const SIDEBAR_ITEMS = [
   { id: "custom-checks", label: "Custom Checks", icon: Puzzle },
   { id: "custom-extractors", label: "Custom Extractors", icon: Code2 },
   { id: "tags", label: "Tags", icon: Tags },
+  { id: "rule-packs", label: "Rule Packs", icon: Package },
+  { id: "page-snapshots", label: "Page Snapshots", icon: FileCode },
   { id: "licensing", label: "Licensing", icon: CreditCard },
   { id: "appearance", label: "Appearance", icon: Palette },
 ];
@@ -26,19 +26,37 @@ import { Switch } from "@/src/components/ui/switch";
 import { Textarea } from "@/src/components/ui/textarea";
 
 import type { CustomCheck, CustomCheckParams } from "@/src/api/extension";
+import { CheckDryRunPanel } from "./CheckDryRunPanel";
+import { ConditionJsonField } from "./ConditionJsonField";
+import { ScriptDryRunPanel } from "./ScriptDryRunPanel";
+import type { AggregateFunction, CheckScope, SiteAggregate } from "@/src/bindings";
 import {
+  AGGREGATE_OPERATOR_OPTIONS,
+  AGGREGATE_OPTIONS,
+  CHECK_KIND_OPTIONS,
   OPERATOR_OPTIONS,
+  SCOPE_OPTIONS,
+  SCRIPT_TEMPLATE,
   SEVERITY_OPTIONS,
+  THRESHOLD_PLACEHOLDERS,
+  type AggregateOperator,
+  type CheckKind,
   type CheckOperator,
   type CheckSeverity,
 } from "./custom-check-options";
 
+const DEFAULT_AGGREGATE: SiteAggregate = { function: "count", operator: "gt", threshold: null };
+
 const EMPTY_PARAMS: CustomCheckParams = {
   name: "",
   severity: "warning",
   field: "",
   operator: "missing",
   threshold: null,
+  condition: null,
+  scope: "page",
+  aggregate: null,
+  script: null,
   message_template: "",
   enabled: true,
 };
@@ -50,6 +68,10 @@ function paramsFrom(check: CustomCheck): CustomCheckParams {
     field: check.field,
     operator: check.operator,
     threshold: check.threshold,
+    condition: check.condition ?? null,
+    scope: check.scope ?? "page",
+    aggregate: check.aggregate ?? null,
+    script: check.script ?? null,
     message_template: check.message_template,
     enabled: check.enabled,
   };
@@ -74,15 +96,47 @@ export function CustomCheckDialog({
 }: CustomCheckDialogProps) {
   const [form, setForm] = useFormSync(open, editing, EMPTY_PARAMS, paramsFrom);
   const { tags } = useCheckFieldTags();
+  const [conditionInvalid, setConditionInvalid] = useState(false);
 
+  const kind: CheckKind = form.script != null ? "script" : "rule";
   const needsThreshold = form.operator !== "missing";
+  const aggregate = form.scope === "site" ? (form.aggregate ?? DEFAULT_AGGREGATE) : null;
+
+  function setKind(next: CheckKind) {
+    // Scripts run on each page, so switching to one drops any site aggregate.
+    setForm((f) =>
+      next === "script"
+        ? { ...f, script: f.script ?? SCRIPT_TEMPLATE, scope: "page", aggregate: null }
+        : { ...f, script: null },
+    );
+  }
+
+  function setAggregate(patch: Partial<SiteAggregate>) {
+    setForm((f) => ({ ...f, aggregate: { ...(f.aggregate ?? DEFAULT_AGGREGATE), ...patch } }));
+  }
 
   function handleSave() {
+    if (kind === "script") {
+      if (!form.name.trim() || !form.script?.trim()) {
+        onValidationError("Name and script are required");
+        return;
+      }
+      onSave({ ...form, aggregate: null });
+      return;
+    }
     if (!form.name.trim() || !form.field.trim() || !form.message_template.trim()) {
       onValidationError("Name, field, and message template are required");
       return;
     }
-    onSave(form);
+    if (conditionInvalid) {
+      onValidationError("Compound condition must be a JSON object");
+      return;
+    }
+    if (aggregate && !aggregate.threshold?.trim()) {
+      onValidationError("Site checks need a threshold to compare the aggregate against");
+      return;
+    }
+    onSave({ ...form, aggregate });
   }
 
   return (
@@ -122,74 +176,205 @@ export function CustomCheckDialog({
             </div>
           </div>
 
-          <div className="grid grid-cols-2 gap-4">
-            <div className="space-y-1.5">
-              <Label>Field</Label>
-              <Select
-                value={form.field}
-                onValueChange={(v) => setForm((f) => ({ ...f, field: v }))}
-              >
-                <SelectTrigger>
-                  <SelectValue placeholder="Select a field…" />
-                </SelectTrigger>
-                <SelectContent>
-                  {tags.map((t) => (
-                    <SelectItem key={t.name} value={t.name}>
-                      <span className="flex items-center gap-2">
-                        <code className="text-xs">{t.name}</code>
-                        <span className="text-xs text-muted-foreground">{t.label}</span>
-                      </span>
-                    </SelectItem>
-                  ))}
-                </SelectContent>
-              </Select>
-            </div>
-            <div className="space-y-1.5">
-              <Label>Operator</Label>
-              <Select
-                value={form.operator as CheckOperator}
-                onValueChange={(v) =>
-                  setForm((f) => ({ ...f, operator: v as CheckOperator, threshold: null }))
-                }
-              >
-                <SelectTrigger>
-                  <SelectValue />
-                </SelectTrigger>
-                <SelectContent>
-                  {OPERATOR_OPTIONS.map((o) => (
-                    <SelectItem key={o.value} value={o.value}>
-                      {o.label}
-                    </SelectItem>
-                  ))}
-                </SelectContent>
-              </Select>
-            </div>
+          <div className="space-y-1.5">
+            <Label>Check type</Label>
+            <Select value={kind} onValueChange={(v) => setKind(v as CheckKind)}>
+              <SelectTrigger aria-label="Check type">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {CHECK_KIND_OPTIONS.map((o) => (
+                  <SelectItem key={o.value} value={o.value}>
+                    {o.label}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
           </div>
 
-          {needsThreshold && (
-            <div className="space-y-1.5">
-              <Label htmlFor="chk-threshold">Threshold</Label>
-              <Input
-                id="chk-threshold"
-                placeholder={form.operator === "lt" || form.operator === "gt" ? "300" : "keyword"}
-                value={form.threshold ?? ""}
-                onChange={(e) =>
-                  setForm((f) => ({ ...f, threshold: e.target.value || null }))
-                }
-              />
-            </div>
+          {kind === "script" && (
+            <>
+              <div className="space-y-1.5">
+                <Label htmlFor="chk-script">Script</Label>
+                <Textarea
+                  id="chk-script"
+                  className="font-mono text-xs"
+                  rows={10}
+                  spellCheck={false}
+                  value={form.script ?? ""}
+                  onChange={(e) => setForm((f) => ({ ...f, script: e.target.value }))}
+                />
+                <p className="text-xs text-muted-foreground">
+                  Runs on every page. Messages come from the script; issues use the severity above
+                  unless the script sets one.
+                </p>
+              </div>
+              <ScriptDryRunPanel params={form} />
+            </>
           )}
 
-          <div className="space-y-1.5">
-            <Label htmlFor="chk-msg">Message Template</Label>
-            <Textarea
-              id="chk-msg"
-              placeholder="Page is missing an OG image. Use {value} to show the actual value."
-              rows={2}
-              value={form.message_template}
-              onChange={(e) => setForm((f) => ({ ...f, message_template: e.target.value }))}
-            />
-          </div>
+          {kind === "rule" && (
+            <>
+              <div className="grid grid-cols-2 gap-4">
+                <div className="space-y-1.5">
+                  <Label>Field</Label>
+                  <Select
+                    value={form.field}
+                    onValueChange={(v) => setForm((f) => ({ ...f, field: v }))}
+                  >
+                    <SelectTrigger>
+                      <SelectValue placeholder="Select a field…" />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {tags.map((t) => (
+                        <SelectItem key={t.name} value={t.name}>
+                          <span className="flex items-center gap-2">
+                            <code className="text-xs">{t.name}</code>
+                            <span className="text-xs text-muted-foreground">{t.label}</span>
+                          </span>
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+                <div className="space-y-1.5">
+                  <Label>Operator</Label>
+                  <Select
+                    value={form.operator as CheckOperator}
+                    onValueChange={(v) =>
+                      setForm((f) => ({ ...f, operator: v as CheckOperator, threshold: null }))
+                    }
+                  >
+                    <SelectTrigger>
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {OPERATOR_OPTIONS.map((o) => (
+                        <SelectItem key={o.value} value={o.value}>
+                          {o.label}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                </div>
+              </div>
+
+              {needsThreshold && (
+                <div className="space-y-1.5">
+                  <Label htmlFor="chk-threshold">Threshold</Label>
+                  <Input
+                    id="chk-threshold"
+                    placeholder={THRESHOLD_PLACEHOLDERS[form.operator] ?? "keyword"}
+                    value={form.threshold ?? ""}
+                    onChange={(e) =>
+                      setForm((f) => ({ ...f, threshold: e.target.value || null }))
+                    }
+                  />
+                </div>
+              )}
+
+              <div className="space-y-1.5">
+                <Label>Runs on</Label>
+                <Select
+                  value={form.scope ?? "page"}
+                  onValueChange={(v) =>
+                    setForm((f) => ({
+                      ...f,
+                      scope: v as CheckScope,
+                      aggregate: v === "site" ? (f.aggregate ?? DEFAULT_AGGREGATE) : null,
+                    }))
+                  }
+                >
+                  <SelectTrigger aria-label="Runs on">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    {SCOPE_OPTIONS.map((o) => (
+                      <SelectItem key={o.value} value={o.value}>
+                        {o.label}
+                      </SelectItem>
+                    ))}
+                  </SelectContent>
+                </Select>
+                {aggregate && (
+                  <p className="text-xs text-muted-foreground">
+                    Runs once per crawl over the pages the condition above matches.
+                  </p>
+                )}
+              </div>
+
+              {aggregate && (
+                <div className="grid grid-cols-3 gap-4">
+                  <div className="space-y-1.5">
+                    <Label>Aggregate</Label>
+                    <Select
+                      value={aggregate.function}
+                      onValueChange={(v) => setAggregate({ function: v as AggregateFunction })}
+                    >
+                      <SelectTrigger aria-label="Aggregate">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        {AGGREGATE_OPTIONS.map((o) => (
+                          <SelectItem key={o.value} value={o.value}>
+                            {o.label}
+                          </SelectItem>
+                        ))}
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-1.5">
+                    <Label>Is</Label>
+                    <Select
+                      value={aggregate.operator as AggregateOperator}
+                      onValueChange={(v) =>
+                        setAggregate({ operator: v as AggregateOperator, threshold: null })
+                      }
+                    >
+                      <SelectTrigger aria-label="Aggregate operator">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        {AGGREGATE_OPERATOR_OPTIONS.map((o) => (
+                          <SelectItem key={o.value} value={o.value}>
+                            {o.label}
+                          </SelectItem>
+                        ))}
+                      </SelectContent>
+                    </Select>
+                  </div>
+                  <div className="space-y-1.5">
+                    <Label htmlFor="chk-aggregate-threshold">Value</Label>
+                    <Input
+                      id="chk-aggregate-threshold"
+                      placeholder={aggregate.operator === "between" ? "10,50" : "80"}
+                      value={aggregate.threshold ?? ""}
+                      onChange={(e) => setAggregate({ threshold: e.target.value || null })}
+                    />
+                  </div>
+                </div>
+              )}
+
+              <ConditionJsonField
+                id="chk-condition"
+                value={form.condition}
+                onChange={(condition) => setForm((f) => ({ ...f, condition }))}
+                onInvalidChange={setConditionInvalid}
+              />
+
+              <div className="space-y-1.5">
+                <Label htmlFor="chk-msg">Message Template</Label>
+                <Textarea
+                  id="chk-msg"
+                  placeholder="Page is missing an OG image. Use {value} to show the actual value."
+                  rows={2}
+                  value={form.message_template}
+                  onChange={(e) => setForm((f) => ({ ...f, message_template: e.target.value }))}
+                />
+              </div>
+              <CheckDryRunPanel params={{ ...form, aggregate }} />
+            </>
+          )}
 
           <label className="flex items-center gap-2 text-sm cursor-pointer">
             <Switch
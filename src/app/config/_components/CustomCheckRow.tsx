@@ -8,7 +8,12 @@ import { Switch } from "@/src/components/ui/switch";
 import { TableCell, TableRow } from "@/src/components/ui/table";
 
 import type { CustomCheck } from "@/src/api/extension";
-import { OPERATOR_OPTIONS, type CheckSeverity } from "./custom-check-options";
+import {
+  AGGREGATE_OPERATOR_OPTIONS,
+  AGGREGATE_OPTIONS,
+  OPERATOR_OPTIONS,
+  type CheckSeverity,
+} from "./custom-check-options";
 
 const SEVERITY_VARIANT: Record<CheckSeverity, "destructive" | "secondary" | "outline"> = {
   critical: "destructive",
@@ -30,6 +35,7 @@ export function CustomCheckRow({
   onToggleEnabled,
 }: CustomCheckRowProps) {
   const operatorLabel = OPERATOR_OPTIONS.find((o) => o.value === check.operator)?.label;
+  const aggregate = check.scope === "site" ? check.aggregate : null;
 
   return (
     <TableRow>
@@ -43,8 +49,26 @@ export function CustomCheckRow({
         </Badge>
       </TableCell>
       <TableCell className="text-sm text-muted-foreground">
-        <code className="text-xs">{check.field}</code> {operatorLabel}
-        {check.threshold && <> {check.threshold}</>}
+        {check.script != null ? (
+          <Badge variant="outline" className="text-xs">
+            script
+          </Badge>
+        ) : (
+          <>
+            <code className="text-xs">{check.field}</code> {operatorLabel}
+            {check.threshold && <> {check.threshold}</>}
+          </>
+        )}
+        {aggregate && (
+          <div className="text-xs">
+            <Badge variant="outline" className="text-xs mr-1">
+              site
+            </Badge>
+            {AGGREGATE_OPTIONS.find((o) => o.value === aggregate.function)?.label}{" "}
+            {AGGREGATE_OPERATOR_OPTIONS.find((o) => o.value === aggregate.operator)?.label}{" "}
+            {aggregate.threshold}
+          </div>
+        )}
       </TableCell>
       <TableCell>
         <Switch checked={check.enabled} onCheckedChange={() => onToggleEnabled(check)} />
@@ -1,6 +1,7 @@
 "use client";
 
-import { Plus } from "lucide-react";
+import { useState } from "react";
+import { Plus, RefreshCw } from "lucide-react";
 import { toast } from "sonner";
 
 import { Button } from "@/src/components/ui/button";
@@ -17,10 +18,12 @@ import {
   createCustomCheck,
   updateCustomCheck,
   deleteCustomCheck,
+  applyCustomChecks,
   type CustomCheck,
   type CustomCheckParams,
 } from "@/src/api/extension";
 import { useCrudState } from "@/src/hooks/use-crud-state";
+import { CompletedJobSelect } from "./CompletedJobSelect";
 import { CustomCheckDialog } from "./CustomCheckDialog";
 import { CustomCheckRow } from "./CustomCheckRow";
 
@@ -33,6 +36,8 @@ export function CustomChecksSettings() {
     onDelete: deleteCustomCheck,
     entityName: "Check",
   });
+  const [applyJobId, setApplyJobId] = useState("");
+  const [applying, setApplying] = useState(false);
 
   async function handleToggleEnabled(check: CustomCheck) {
     try {
@@ -50,6 +55,21 @@ export function CustomChecksSettings() {
     }
   }
 
+  async function handleApply() {
+    setApplying(true);
+    try {
+      const applied = await applyCustomChecks(applyJobId);
+      toast.success(
+        `Re-checked ${applied.pages_checked} page(s): ${applied.issues_added} issue(s) raised, ` +
+          `${applied.issues_removed} replaced`,
+      );
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : "Failed to apply checks");
+    } finally {
+      setApplying(false);
+    }
+  }
+
   return (
     <div className="space-y-4">
       <div className="flex items-center justify-between">
@@ -91,6 +111,20 @@ export function CustomChecksSettings() {
         </Table>
       )}
 
+      {crud.items.length > 0 && (
+        <div className="grid grid-cols-[1fr_auto] items-end gap-2 rounded-md border p-3">
+          <CompletedJobSelect
+            label="Apply to finished crawl"
+            value={applyJobId}
+            onChange={(job) => setApplyJobId(job.job_id)}
+          />
+          <Button variant="outline" onClick={handleApply} disabled={!applyJobId || applying}>
+            <RefreshCw className="h-4 w-4 mr-1" />
+            Apply Checks
+          </Button>
+        </div>
+      )}
+
       <CustomCheckDialog
         open={crud.dialogOpen}
         editing={crud.editing}
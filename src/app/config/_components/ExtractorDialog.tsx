@@ -14,19 +14,32 @@ import {
 } from "@/src/components/ui/dialog";
 import { Input } from "@/src/components/ui/input";
 import { Label } from "@/src/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/src/components/ui/select";
 import { Separator } from "@/src/components/ui/separator";
 import { Switch } from "@/src/components/ui/switch";
 
 import type { CustomExtractor, CustomExtractorParams } from "@/src/api/extension";
+import type { ExtractorKind, ExtractorOutput, TagDataType } from "@/src/bindings";
+import { ExtractorDryRunPanel } from "./ExtractorDryRunPanel";
 import { SelectorLivePreview } from "./SelectorLivePreview";
+import { DATA_TYPE_OPTIONS, KIND_OPTIONS, OUTPUT_OPTIONS, kindOption } from "./extractor-options";
 import { PRESETS, type Preset } from "./extractor-presets";
 
 const EMPTY_PARAMS: CustomExtractorParams = {
   name: "",
   tag: "",
+  kind: "css",
   selector: "",
   attribute: null,
   multiple: false,
+  output: "value",
+  data_type: null,
   enabled: true,
 };
 
@@ -34,9 +47,12 @@ function paramsFrom(extractor: CustomExtractor): CustomExtractorParams {
   return {
     name: extractor.name,
     tag: extractor.tag,
+    kind: extractor.kind ?? "css",
     selector: extractor.selector,
     attribute: extractor.attribute,
     multiple: extractor.multiple,
+    output: extractor.output ?? "value",
+    data_type: extractor.data_type ?? null,
     enabled: extractor.enabled,
   };
 }
@@ -60,14 +76,18 @@ export function ExtractorDialog({
 }: ExtractorDialogProps) {
   const [form, setForm] = useFormSync(open, editing, EMPTY_PARAMS, paramsFrom);
   const [presetsOpen, setPresetsOpen] = useState(false);
+  const kind = kindOption(form.kind);
 
   function applyPreset(preset: Preset) {
     setForm({
       name: preset.name,
       tag: preset.tag,
+      kind: "css",
       selector: preset.selector,
       attribute: preset.attribute,
       multiple: preset.multiple,
+      output: "value",
+      data_type: null,
       enabled: true,
     });
     setPresetsOpen(false);
@@ -144,34 +164,107 @@ export function ExtractorDialog({
             </div>
           </div>
 
+          <div className="space-y-1.5">
+            <Label>Extract with</Label>
+            <Select
+              value={kind.value}
+              onValueChange={(v) =>
+                setForm((f) => ({
+                  ...f,
+                  kind: v as ExtractorKind,
+                  attribute: kindOption(v as ExtractorKind).attributeLabel ? f.attribute : null,
+                }))
+              }
+            >
+              <SelectTrigger aria-label="Extract with">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {KIND_OPTIONS.map((o) => (
+                  <SelectItem key={o.value} value={o.value}>
+                    {o.label}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </div>
+
           <div className="grid grid-cols-2 gap-4">
             <div className="space-y-1.5">
-              <Label htmlFor="ext-selector">CSS Selector</Label>
+              <Label htmlFor="ext-selector">{kind.selectorLabel}</Label>
               <Input
                 id="ext-selector"
-                placeholder="meta[property='og:image']"
+                placeholder={kind.selectorPlaceholder}
                 value={form.selector}
                 onChange={(e) => setForm((f) => ({ ...f, selector: e.target.value }))}
               />
             </div>
+            {kind.attributeLabel && (
+              <div className="space-y-1.5">
+                <Label htmlFor="ext-attr">
+                  {kind.attributeLabel}{" "}
+                  <span className="text-xs text-muted-foreground font-normal">— optional</span>
+                </Label>
+                <Input
+                  id="ext-attr"
+                  placeholder={kind.attributePlaceholder}
+                  value={form.attribute ?? ""}
+                  onChange={(e) =>
+                    setForm((f) => ({ ...f, attribute: e.target.value || null }))
+                  }
+                />
+              </div>
+            )}
+          </div>
+
+          {kind.value === "css" && (
+            <SelectorLivePreview selector={form.selector} attribute={form.attribute} />
+          )}
+
+          <div className="grid grid-cols-2 gap-4">
+            <div className="space-y-1.5">
+              <Label>Publish</Label>
+              <Select
+                value={form.output ?? "value"}
+                onValueChange={(v) => setForm((f) => ({ ...f, output: v as ExtractorOutput }))}
+              >
+                <SelectTrigger aria-label="Publish">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {OUTPUT_OPTIONS.map((o) => (
+                    <SelectItem key={o.value} value={o.value}>
+                      {o.label}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
             <div className="space-y-1.5">
-              <Label htmlFor="ext-attr">
-                Attribute{" "}
-                <span className="text-xs text-muted-foreground font-normal">— optional</span>
-              </Label>
-              <Input
-                id="ext-attr"
-                placeholder='e.g. "content" or "href" — blank = element text'
-                value={form.attribute ?? ""}
-                onChange={(e) =>
-                  setForm((f) => ({ ...f, attribute: e.target.value || null }))
+              <Label>Data type</Label>
+              <Select
+                value={form.data_type ?? "auto"}
+                onValueChange={(v) =>
+                  setForm((f) => ({ ...f, data_type: v === "auto" ? null : (v as TagDataType) }))
                 }
-              />
+              >
+                <SelectTrigger aria-label="Data type">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {DATA_TYPE_OPTIONS.map((o) => (
+                    <SelectItem key={o.value} value={o.value}>
+                      {o.label}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+              <p className="text-xs text-muted-foreground">
+                Values that don&apos;t convert are skipped.
+              </p>
             </div>
           </div>
 
-          <SelectorLivePreview selector={form.selector} attribute={form.attribute} />
-
           <Separator />
 
           <div className="flex items-center gap-6 flex-wrap">
@@ -195,6 +288,8 @@ export function ExtractorDialog({
               Enabled
             </label>
           </div>
+
+          <ExtractorDryRunPanel params={form} />
         </div>
 
         <DialogFooter>
@@ -0,0 +1,91 @@
+"use client";
+
+import { useState } from "react";
+import { Play } from "lucide-react";
+
+import { Button } from "@/src/components/ui/button";
+
+import {
+  dryRunCustomExtractor,
+  type CustomExtractorParams,
+  type ExtractorDryRun,
+} from "@/src/api/extension";
+import type { JsonValue } from "@/src/bindings";
+import { CompletedJobSelect } from "./CompletedJobSelect";
+
+const SHOWN_PAGES = 50;
+
+function display(value: JsonValue) {
+  return typeof value === "string" ? value : JSON.stringify(value);
+}
+
+/**
+ * Runs the extractor being edited over the saved HTML of a finished crawl
+ * and shows what it extracts from each page.
+ */
+export function ExtractorDryRunPanel({ params }: { params: CustomExtractorParams }) {
+  const [jobId, setJobId] = useState("");
+  const [running, setRunning] = useState(false);
+  const [run, setRun] = useState<ExtractorDryRun | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  async function handleRun() {
+    setRunning(true);
+    try {
+      setRun(await dryRunCustomExtractor(params, jobId));
+      setError(null);
+    } catch (e) {
+      setRun(null);
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setRunning(false);
+    }
+  }
+
+  const matched = run?.pages.filter((page) => page.value != null).length ?? 0;
+
+  return (
+    <div className="space-y-3 rounded-md border p-3">
+      <div className="grid grid-cols-[1fr_auto] items-end gap-2">
+        <CompletedJobSelect value={jobId} onChange={(job) => setJobId(job.job_id)} />
+        <Button
+          variant="outline"
+          onClick={handleRun}
+          disabled={!jobId || !params.selector.trim() || running}
+        >
+          <Play className="h-4 w-4 mr-1" />
+          Run
+        </Button>
+      </div>
+
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      {run && (
+        <div className="space-y-2 text-sm">
+          <p className="text-muted-foreground">
+            Matched {matched} of {run.pages.length} page(s).
+            {run.without_html.length > 0 &&
+              ` ${run.without_html.length} page(s) have no saved HTML and were skipped.`}
+          </p>
+          <ul className="max-h-48 space-y-1 overflow-auto">
+            {run.pages.slice(0, SHOWN_PAGES).map((page) => (
+              <li key={page.url} className="grid grid-cols-[1fr_1fr] gap-2">
+                <span className="truncate font-mono text-xs">{page.url}</span>
+                {page.value == null ? (
+                  <span className="text-xs text-muted-foreground">no match</span>
+                ) : (
+                  <span className="truncate text-xs">{display(page.value)}</span>
+                )}
+              </li>
+            ))}
+          </ul>
+          {run.pages.length > SHOWN_PAGES && (
+            <p className="text-xs text-muted-foreground">
+              and {run.pages.length - SHOWN_PAGES} more
+            </p>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
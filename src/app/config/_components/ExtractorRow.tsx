@@ -8,6 +8,7 @@ import { Switch } from "@/src/components/ui/switch";
 import { TableCell, TableRow } from "@/src/components/ui/table";
 
 import type { CustomExtractor } from "@/src/api/extension";
+import { kindOption } from "./extractor-options";
 
 interface ExtractorRowProps {
   extractor: CustomExtractor;
@@ -17,6 +18,7 @@ interface ExtractorRowProps {
 }
 
 export function ExtractorRow({ extractor, onEdit, onDelete, onToggleEnabled }: ExtractorRowProps) {
+  const kind = kindOption(extractor.kind);
   return (
     <TableRow>
       <TableCell className="font-medium">{extractor.name}</TableCell>
@@ -24,10 +26,15 @@ export function ExtractorRow({ extractor, onEdit, onDelete, onToggleEnabled }: E
         <code className="text-xs bg-muted px-1 py-0.5 rounded">tag:{extractor.tag}</code>
       </TableCell>
       <TableCell>
+        {kind.value !== "css" && (
+          <Badge variant="secondary" className="mr-1 text-xs">
+            {kind.selectorLabel}
+          </Badge>
+        )}
         <code className="text-xs bg-muted px-1 py-0.5 rounded">{extractor.selector}</code>
         {extractor.attribute && (
           <Badge variant="outline" className="ml-1 text-xs">
-            @{extractor.attribute}
+            {extractor.kind === "regex" ? `group ${extractor.attribute}` : `@${extractor.attribute}`}
           </Badge>
         )}
       </TableCell>
@@ -35,6 +42,11 @@ export function ExtractorRow({ extractor, onEdit, onDelete, onToggleEnabled }: E
         <Badge variant={extractor.multiple ? "secondary" : "outline"} className="text-xs">
           {extractor.multiple ? "all" : "first"}
         </Badge>
+        {extractor.output && extractor.output !== "value" && (
+          <Badge variant="outline" className="ml-1 text-xs">
+            {extractor.output}
+          </Badge>
+        )}
       </TableCell>
       <TableCell>
         <Switch checked={extractor.enabled} onCheckedChange={() => onToggleEnabled(extractor)} />
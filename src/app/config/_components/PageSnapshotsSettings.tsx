@@ -0,0 +1,112 @@
+"use client";
+
+import { useEffect, useState } from "react";
+import useSWR from "swr";
+import { Save } from "lucide-react";
+import { toast } from "sonner";
+
+import { Button } from "@/src/components/ui/button";
+import { Input } from "@/src/components/ui/input";
+import { Label } from "@/src/components/ui/label";
+import { Switch } from "@/src/components/ui/switch";
+
+import {
+  getSnapshotSettings,
+  getSnapshotStorage,
+  setSnapshotSettings,
+  type SnapshotStorage,
+} from "@/src/api/snapshot";
+
+function formatBytes(bytes: number): string {
+  if (bytes === 0) return "0 B";
+  if (bytes >= 1e9) return `${(bytes / 1e9).toFixed(1)} GB`;
+  if (bytes >= 1e6) return `${(bytes / 1e6).toFixed(1)} MB`;
+  return `${(bytes / 1e3).toFixed(0)} KB`;
+}
+
+function StorageSummary({ storage }: { storage: SnapshotStorage }) {
+  return (
+    <p className="text-sm text-muted-foreground">
+      {storage.snapshots} snapshot(s) of {storage.distinct_sources} distinct page source(s):{" "}
+      {formatBytes(storage.html_bytes)} of HTML stored in {formatBytes(storage.stored_bytes)}.
+    </p>
+  );
+}
+
+/**
+ * Whether analysis saves each page's HTML and headers for the Source tab,
+ * and how long they're kept.
+ */
+export function PageSnapshotsSettings() {
+  const { data: settings } = useSWR("snapshot-settings", getSnapshotSettings);
+  const { data: storage, mutate: mutateStorage } = useSWR("snapshot-storage", getSnapshotStorage);
+
+  const [enabled, setEnabled] = useState(true);
+  const [retentionDays, setRetentionDays] = useState("");
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    if (!settings) return;
+    setEnabled(settings.enabled);
+    setRetentionDays(settings.retention_days?.toString() ?? "");
+  }, [settings]);
+
+  const days = retentionDays.trim() ? Number(retentionDays) : null;
+  const validDays = days === null || (Number.isInteger(days) && days > 0);
+
+  async function handleSave() {
+    setSaving(true);
+    try {
+      const remaining = await setSnapshotSettings({ enabled, retention_days: days });
+      mutateStorage(remaining, { revalidate: false });
+      toast.success("Snapshot settings saved");
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : String(e));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  return (
+    <div className="space-y-6">
+      <div className="flex items-center justify-between p-4 border border-border/50 rounded-lg bg-card/30">
+        <div className="space-y-0.5">
+          <Label htmlFor="snapshots-enabled" className="text-base">
+            Save page source
+          </Label>
+          <p className="text-sm text-muted-foreground">
+            Keep each analysed page&apos;s HTML and response headers to view, compare between
+            crawls and test checks against.
+          </p>
+        </div>
+        <Switch id="snapshots-enabled" checked={enabled} onCheckedChange={setEnabled} />
+      </div>
+
+      <div className="space-y-2 p-4 border border-border/50 rounded-lg bg-card/30">
+        <Label htmlFor="snapshots-retention">Keep snapshots for (days)</Label>
+        <Input
+          id="snapshots-retention"
+          type="number"
+          min={1}
+          value={retentionDays}
+          onChange={(e) => setRetentionDays(e.target.value)}
+          placeholder="Until the crawl is deleted"
+          className="max-w-xs"
+        />
+        <p className="text-xs text-muted-foreground">
+          Leave empty to keep snapshots until their crawl is deleted.
+        </p>
+        {!validDays && (
+          <p className="text-xs text-destructive">Enter a whole number of days, at least 1.</p>
+        )}
+      </div>
+
+      {storage && <StorageSummary storage={storage} />}
+
+      <Button onClick={handleSave} disabled={!settings || !validDays || saving}>
+        <Save className="h-4 w-4 mr-2" />
+        Save
+      </Button>
+    </div>
+  );
+}
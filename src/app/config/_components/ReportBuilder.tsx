@@ -42,8 +42,8 @@ export function ReportBuilder({ persona, setPersona }: ReportBuilderProps) {
         <TabsContent value="template" className="space-y-4">
           <div className="text-sm text-muted-foreground mb-2">
             The template defines <strong>what the report says</strong>. Drag
-            sections to reorder. AI sections are expanded by your local model
-            at render time.
+            sections to reorder. AI sections are written at render time by the
+            AI backend selected in Settings → AI.
           </div>
           <ReportTemplateEditor />
         </TabsContent>
@@ -33,6 +33,8 @@ import type {
   PatternCategory,
   PatternSeverity,
 } from "@/src/bindings";
+import { ConditionJsonField } from "./ConditionJsonField";
+import { THRESHOLD_PLACEHOLDERS } from "./custom-check-options";
 import {
   CATEGORY_OPTIONS,
   EFFORT_OPTIONS,
@@ -49,6 +51,7 @@ const EMPTY_FORM: ReportPatternParams = {
   field: "",
   operator: "missing",
   threshold: null,
+  condition: null,
   minPrevalence: 0.1,
   businessImpact: "medium",
   fixEffort: "medium",
@@ -65,6 +68,7 @@ function paramsFrom(p: ReportPattern): ReportPatternParams {
     field: p.field,
     operator: p.operator,
     threshold: p.threshold,
+    condition: p.condition ?? null,
     minPrevalence: p.minPrevalence,
     businessImpact: p.businessImpact,
     fixEffort: p.fixEffort,
@@ -92,6 +96,7 @@ export function ReportPatternDialog({
 }: ReportPatternDialogProps) {
   const [form, setForm] = useFormSync(open, editing, EMPTY_FORM, paramsFrom);
   const { tags } = useCheckFieldTags();
+  const [conditionInvalid, setConditionInvalid] = useState(false);
 
   const needsThreshold =
     OPERATOR_OPTIONS.find((o) => o.value === form.operator)?.needsThreshold ?? false;
@@ -101,6 +106,10 @@ export function ReportPatternDialog({
       onValidationError("Name, field, and recommendation are required");
       return;
     }
+    if (conditionInvalid) {
+      onValidationError("Compound condition must be a JSON object");
+      return;
+    }
     onSave(form);
   }
 
@@ -216,7 +225,7 @@ export function ReportPatternDialog({
             <div className="space-y-1.5">
               <Label>Threshold</Label>
               <Input
-                placeholder={form.operator === "lt" || form.operator === "gt" ? "300" : "value"}
+                placeholder={THRESHOLD_PLACEHOLDERS[form.operator] ?? "value"}
                 value={form.threshold ?? ""}
                 onChange={(e) =>
                   setForm((f) => ({ ...f, threshold: e.target.value || null }))
@@ -225,6 +234,13 @@ export function ReportPatternDialog({
             </div>
           )}
 
+          <ConditionJsonField
+            id="pattern-condition"
+            value={form.condition}
+            onChange={(condition) => setForm((f) => ({ ...f, condition }))}
+            onInvalidChange={setConditionInvalid}
+          />
+
           <div className="space-y-1.5">
             <Label>Min Prevalence (%)</Label>
             <Input
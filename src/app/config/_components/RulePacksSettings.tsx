@@ -0,0 +1,404 @@
+"use client";
+
+import { useEffect, useState } from "react";
+import useSWR, { useSWRConfig } from "swr";
+import { Download, FileUp, ShieldCheck, ShieldAlert } from "lucide-react";
+import { toast } from "sonner";
+import { open } from "@tauri-apps/plugin-dialog";
+import { readFile } from "@tauri-apps/plugin-fs";
+
+import { Badge } from "@/src/components/ui/badge";
+import { Button } from "@/src/components/ui/button";
+import { Checkbox } from "@/src/components/ui/checkbox";
+import { Input } from "@/src/components/ui/input";
+import { Label } from "@/src/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/src/components/ui/select";
+import { Separator } from "@/src/components/ui/separator";
+import { Switch } from "@/src/components/ui/switch";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "@/src/components/ui/table";
+
+import { listCustomChecks, listCustomExtractors } from "@/src/api/extension";
+import { listReportPatterns, listReportTemplates } from "@/src/api/report";
+import {
+  exportRulePack,
+  importRulePack,
+  previewRulePack,
+  type ImportStrategy,
+  type PackFormat,
+  type RulePackPreview,
+} from "@/src/api/rule-pack";
+import { saveFile } from "@/src/lib/export-utils";
+import {
+  FORMAT_OPTIONS,
+  KIND_LABELS,
+  OUTCOME_LABELS,
+  STRATEGY_OPTIONS,
+  packFilename,
+} from "./rule-pack-options";
+
+/** SWR keys of the lists an import can change. */
+const IMPORTED_KEYS = ["custom-checks", "custom-extractors", "report-patterns", "report-templates"];
+
+export function RulePacksSettings() {
+  return (
+    <div className="space-y-8">
+      <p className="text-sm text-muted-foreground">
+        Share checks, extractors, report patterns and templates as a single file. Extractors and
+        patterns the selected items rely on are included automatically.
+      </p>
+      <RulePackExportForm />
+      <Separator className="bg-border/40" />
+      <RulePackImportForm />
+    </div>
+  );
+}
+
+interface Selectable {
+  id: string;
+  name: string;
+}
+
+function SelectionList({
+  title,
+  items,
+  selected,
+  onToggle,
+}: {
+  title: string;
+  items: Selectable[];
+  selected: string[];
+  onToggle: (id: string) => void;
+}) {
+  return (
+    <div className="space-y-2">
+      <h4 className="text-sm font-medium">{title}</h4>
+      {items.length === 0 ? (
+        <p className="text-xs text-muted-foreground">None yet.</p>
+      ) : (
+        items.map((item) => (
+          <label key={item.id} className="flex items-center gap-2 text-sm">
+            <Checkbox
+              checked={selected.includes(item.id)}
+              onCheckedChange={() => onToggle(item.id)}
+              aria-label={`${title}: ${item.name}`}
+            />
+            {item.name}
+          </label>
+        ))
+      )}
+    </div>
+  );
+}
+
+function toggled(ids: string[], id: string): string[] {
+  return ids.includes(id) ? ids.filter((i) => i !== id) : [...ids, id];
+}
+
+function RulePackExportForm() {
+  const { data: checks = [] } = useSWR("custom-checks", listCustomChecks);
+  const { data: extractors = [] } = useSWR("custom-extractors", listCustomExtractors);
+  const { data: patterns = [] } = useSWR("report-patterns", listReportPatterns);
+  const { data: templates = [] } = useSWR("report-templates", listReportTemplates);
+
+  const [name, setName] = useState("");
+  const [description, setDescription] = useState("");
+  const [checkIds, setCheckIds] = useState<string[]>([]);
+  const [extractorIds, setExtractorIds] = useState<string[]>([]);
+  const [patternIds, setPatternIds] = useState<string[]>([]);
+  const [templateIds, setTemplateIds] = useState<string[]>([]);
+  const [format, setFormat] = useState<PackFormat>("json");
+  const [sign, setSign] = useState(true);
+  const [exporting, setExporting] = useState(false);
+
+  const nothingSelected =
+    checkIds.length + extractorIds.length + patternIds.length + templateIds.length === 0;
+
+  async function handleExport() {
+    setExporting(true);
+    try {
+      const content = await exportRulePack({
+        name: name.trim(),
+        description,
+        check_ids: checkIds,
+        extractor_ids: extractorIds,
+        pattern_ids: patternIds,
+        template_ids: templateIds,
+        format,
+        sign,
+      });
+      await saveFile(content, packFilename(name, format), [
+        { name: "Rule pack", extensions: [format] },
+      ]);
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : "Failed to export rule pack");
+    } finally {
+      setExporting(false);
+    }
+  }
+
+  return (
+    <section className="space-y-4">
+      <h3 className="text-lg font-semibold">Export</h3>
+      <div className="grid grid-cols-2 gap-4">
+        <div className="space-y-1.5">
+          <Label htmlFor="pack-name">Pack name</Label>
+          <Input
+            id="pack-name"
+            placeholder="Ecommerce essentials"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+          />
+        </div>
+        <div className="space-y-1.5">
+          <Label htmlFor="pack-description">
+            Description{" "}
+            <span className="text-xs text-muted-foreground font-normal">— optional</span>
+          </Label>
+          <Input
+            id="pack-description"
+            value={description}
+            onChange={(e) => setDescription(e.target.value)}
+          />
+        </div>
+      </div>
+
+      <div className="grid grid-cols-2 gap-6 rounded-md border p-4">
+        <SelectionList
+          title="Checks"
+          items={checks}
+          selected={checkIds}
+          onToggle={(id) => setCheckIds((ids) => toggled(ids, id))}
+        />
+        <SelectionList
+          title="Extractors"
+          items={extractors.map((e) => ({ id: e.id, name: `${e.name} (${e.tag})` }))}
+          selected={extractorIds}
+          onToggle={(id) => setExtractorIds((ids) => toggled(ids, id))}
+        />
+        <SelectionList
+          title="Patterns"
+          items={patterns}
+          selected={patternIds}
+          onToggle={(id) => setPatternIds((ids) => toggled(ids, id))}
+        />
+        <SelectionList
+          title="Templates"
+          items={templates}
+          selected={templateIds}
+          onToggle={(id) => setTemplateIds((ids) => toggled(ids, id))}
+        />
+      </div>
+
+      <div className="flex items-end gap-6">
+        <div className="space-y-1.5 w-40">
+          <Label>Format</Label>
+          <Select value={format} onValueChange={(v) => setFormat(v as PackFormat)}>
+            <SelectTrigger aria-label="Format">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {FORMAT_OPTIONS.map((o) => (
+                <SelectItem key={o.value} value={o.value}>
+                  {o.label}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="flex items-center gap-2 pb-2">
+          <Switch id="pack-sign" checked={sign} onCheckedChange={setSign} />
+          <Label htmlFor="pack-sign">Sign with this app&apos;s key</Label>
+        </div>
+        <Button
+          className="ml-auto"
+          onClick={handleExport}
+          disabled={!name.trim() || nothingSelected || exporting}
+        >
+          <Download className="h-4 w-4 mr-2" />
+          Export Pack
+        </Button>
+      </div>
+    </section>
+  );
+}
+
+function SignerBadge({ preview }: { preview: RulePackPreview }) {
+  if (!preview.signer) {
+    return <Badge variant="outline">Unsigned</Badge>;
+  }
+  const Icon = preview.signer.is_local ? ShieldCheck : ShieldAlert;
+  return (
+    <Badge variant="secondary" className="gap-1">
+      <Icon className="h-3 w-3" />
+      {preview.signer.is_local ? "Signed by you" : "Signed"}
+      <code className="text-[10px]">{preview.signer.fingerprint}</code>
+    </Badge>
+  );
+}
+
+function RulePackImportForm() {
+  const { mutate } = useSWRConfig();
+  const [content, setContent] = useState<string | null>(null);
+  const [strategy, setStrategy] = useState<ImportStrategy>("merge");
+  const [preview, setPreview] = useState<RulePackPreview | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [importing, setImporting] = useState(false);
+
+  useEffect(() => {
+    if (content === null) return;
+    let cancelled = false;
+    previewRulePack(content, strategy)
+      .then((p) => {
+        if (cancelled) return;
+        setPreview(p);
+        setError(null);
+      })
+      .catch((e: unknown) => {
+        if (cancelled) return;
+        setPreview(null);
+        setError(e instanceof Error ? e.message : String(e));
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [content, strategy]);
+
+  async function handleChooseFile() {
+    const path = await open({
+      multiple: false,
+      filters: [{ name: "Rule pack", extensions: ["json", "toml"] }],
+    });
+    if (typeof path !== "string") return;
+    try {
+      setContent(new TextDecoder().decode(await readFile(path)));
+    } catch {
+      toast.error("Couldn't read the file");
+    }
+  }
+
+  async function handleImport() {
+    if (content === null) return;
+    setImporting(true);
+    try {
+      const items = await importRulePack(content, strategy);
+      const written = items.filter((i) => i.outcome !== "skipped").length;
+      toast.success(
+        `Imported ${written} of ${items.length} items. New checks and extractors apply from the next app start.`,
+      );
+      IMPORTED_KEYS.forEach((key) => mutate(key));
+      setContent(null);
+      setPreview(null);
+    } catch (e) {
+      toast.error(e instanceof Error ? e.message : "Failed to import rule pack");
+    } finally {
+      setImporting(false);
+    }
+  }
+
+  const strategyOption = STRATEGY_OPTIONS.find((o) => o.value === strategy);
+
+  return (
+    <section className="space-y-4">
+      <div className="flex items-center justify-between">
+        <h3 className="text-lg font-semibold">Import</h3>
+        <Button variant="outline" size="sm" onClick={handleChooseFile}>
+          <FileUp className="h-4 w-4 mr-1" />
+          Choose File
+        </Button>
+      </div>
+
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      {preview && (
+        <div className="space-y-4">
+          <div className="flex items-center gap-3">
+            <span className="font-medium">{preview.name}</span>
+            <SignerBadge preview={preview} />
+          </div>
+          {preview.description && (
+            <p className="text-sm text-muted-foreground">{preview.description}</p>
+          )}
+
+          <div className="space-y-1.5 w-64">
+            <Label>When an item already exists</Label>
+            <Select value={strategy} onValueChange={(v) => setStrategy(v as ImportStrategy)}>
+              <SelectTrigger aria-label="When an item already exists">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {STRATEGY_OPTIONS.map((o) => (
+                  <SelectItem key={o.value} value={o.value}>
+                    {o.label}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <p className="text-xs text-muted-foreground">{strategyOption?.description}</p>
+          </div>
+
+          <Table>
+            <TableHeader>
+              <TableRow>
+                <TableHead>Type</TableHead>
+                <TableHead>Name</TableHead>
+                <TableHead>Result</TableHead>
+              </TableRow>
+            </TableHeader>
+            <TableBody>
+              {preview.items.map((item) => (
+                <TableRow key={`${item.kind}-${item.id}`}>
+                  <TableCell className="text-xs text-muted-foreground">
+                    {KIND_LABELS[item.kind]}
+                  </TableCell>
+                  <TableCell className="text-sm">
+                    {item.name}
+                    {item.collision && (
+                      <Badge variant="outline" className="ml-2 text-[10px] py-0">
+                        same {item.collision} as yours
+                      </Badge>
+                    )}
+                  </TableCell>
+                  <TableCell className="text-xs">
+                    {OUTCOME_LABELS[item.outcome]}
+                    {item.renamed_to && (
+                      <span className="text-muted-foreground"> → {item.renamed_to}</span>
+                    )}
+                  </TableCell>
+                </TableRow>
+              ))}
+            </TableBody>
+          </Table>
+
+          {preview.unresolved.length > 0 && (
+            <p className="text-sm text-destructive">
+              This pack relies on {preview.unresolved.join(", ")}, which neither it nor this app
+              provides.
+            </p>
+          )}
+
+          <div className="flex justify-end">
+            <Button
+              onClick={handleImport}
+              disabled={importing || preview.unresolved.length > 0}
+            >
+              Import Pack
+            </Button>
+          </div>
+        </div>
+      )}
+    </section>
+  );
+}
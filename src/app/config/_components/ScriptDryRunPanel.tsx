@@ -0,0 +1,106 @@
+"use client";
+
+import { useState } from "react";
+import { Play } from "lucide-react";
+
+import { Badge } from "@/src/components/ui/badge";
+import { Button } from "@/src/components/ui/button";
+import { Input } from "@/src/components/ui/input";
+import { Label } from "@/src/components/ui/label";
+
+import { dryRunCheckScript, type CustomCheckParams, type ScriptDryRun } from "@/src/api/extension";
+import type { AnalysisProgress } from "@/src/bindings";
+import { CompletedJobSelect } from "./CompletedJobSelect";
+
+/**
+ * Runs the script being edited against one page of a finished crawl and
+ * shows the issues it raises and what it prints.
+ */
+export function ScriptDryRunPanel({ params }: { params: CustomCheckParams }) {
+  const [jobId, setJobId] = useState("");
+  const [url, setUrl] = useState("");
+  const [running, setRunning] = useState(false);
+  const [run, setRun] = useState<ScriptDryRun | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  function handleJobChange(job: AnalysisProgress) {
+    setJobId(job.job_id);
+    setUrl(job.url);
+  }
+
+  async function handleRun() {
+    setRunning(true);
+    try {
+      setRun(await dryRunCheckScript(params, jobId, url.trim()));
+      setError(null);
+    } catch (e) {
+      setRun(null);
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setRunning(false);
+    }
+  }
+
+  return (
+    <div className="space-y-3 rounded-md border p-3">
+      <div className="grid grid-cols-[1fr_1.5fr_auto] items-end gap-2">
+        <CompletedJobSelect value={jobId} onChange={handleJobChange} />
+        <div className="space-y-1.5">
+          <Label htmlFor="chk-dry-run-url">Page URL</Label>
+          <Input
+            id="chk-dry-run-url"
+            placeholder="https://example.com/page"
+            value={url}
+            onChange={(e) => setUrl(e.target.value)}
+          />
+        </div>
+        <Button
+          variant="outline"
+          onClick={handleRun}
+          disabled={!jobId || !url.trim() || !params.script?.trim() || running}
+        >
+          <Play className="h-4 w-4 mr-1" />
+          Run
+        </Button>
+      </div>
+
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      {run && (
+        <div className="space-y-2 text-sm">
+          {run.error ? (
+            <pre className="whitespace-pre-wrap text-xs text-destructive">{run.error}</pre>
+          ) : run.issues.length === 0 ? (
+            <p className="text-muted-foreground">No issues on this page.</p>
+          ) : (
+            <ul className="space-y-1">
+              {run.issues.map((issue, i) => (
+                <li key={i} className="flex items-start gap-2">
+                  <Badge variant="outline" className="text-xs capitalize">
+                    {issue.severity}
+                  </Badge>
+                  <span>
+                    {issue.message}
+                    {issue.details && (
+                      <span className="block text-xs text-muted-foreground whitespace-pre-wrap">
+                        {issue.details}
+                      </span>
+                    )}
+                  </span>
+                </li>
+              ))}
+            </ul>
+          )}
+          {run.output.length > 0 && (
+            <pre
+              aria-label="Script output"
+              className="max-h-32 overflow-auto rounded bg-muted p-2 text-xs"
+            >
+              {run.output.join("\n")}
+            </pre>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -15,6 +15,7 @@ import type { TemplateSection } from "@/src/bindings";
 import { useExtractorTags } from "@/src/hooks/use-extractor-tags";
 import {
   CONDITION_TYPES,
+  SITE_CHECK_STATUSES,
   conditionInputValue,
   conditionSecondInputValue,
   buildCondition,
@@ -118,15 +119,37 @@ export function TemplateConditionalEditor({
                 ? (condDef as { secondLabel: string }).secondLabel
                 : "Value"}
             </Label>
-            <Input
-              className="h-8 text-sm"
-              value={conditionSecondInputValue(when)}
-              onChange={(e) => {
-                const first = conditionInputValue(when);
-                const newWhen = buildCondition(currentOp, first, e.target.value);
-                onChange({ ...section, when: newWhen as typeof section.when });
-              }}
-            />
+            {currentOp === "siteCheck" ? (
+              <Select
+                value={conditionSecondInputValue(when)}
+                onValueChange={(v) => {
+                  const first = conditionInputValue(when);
+                  const newWhen = buildCondition(currentOp, first, v);
+                  onChange({ ...section, when: newWhen as typeof section.when });
+                }}
+              >
+                <SelectTrigger className="h-8">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {SITE_CHECK_STATUSES.map((status) => (
+                    <SelectItem key={status} value={status}>
+                      {status}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            ) : (
+              <Input
+                className="h-8 text-sm"
+                value={conditionSecondInputValue(when)}
+                onChange={(e) => {
+                  const first = conditionInputValue(when);
+                  const newWhen = buildCondition(currentOp, first, e.target.value);
+                  onChange({ ...section, when: newWhen as typeof section.when });
+                }}
+              />
+            )}
           </div>
         )}
       </div>
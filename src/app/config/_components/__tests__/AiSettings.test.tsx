@@ -8,6 +8,10 @@ vi.mock("@/src/api/ai", () => ({
   setAiSource: vi.fn(),
   getApiKey: vi.fn(),
   setApiKey: vi.fn(),
+  getOpenAiSettings: vi.fn(),
+  setOpenAiSettings: vi.fn(),
+  getOllamaSettings: vi.fn(),
+  setOllamaSettings: vi.fn(),
 }));
 
 vi.mock("sonner", () => ({
@@ -22,12 +26,18 @@ import {
   getAiSource,
   setAiSource,
   getApiKey,
+  getOllamaSettings,
+  setOllamaSettings,
+  getOpenAiSettings,
 } from "@/src/api/ai";
 import { AiSettings } from "../AiSettings";
 
 const mockedGetSource = vi.mocked(getAiSource);
 const mockedSetSource = vi.mocked(setAiSource);
 const mockedGetKey = vi.mocked(getApiKey);
+const mockedGetOllama = vi.mocked(getOllamaSettings);
+const mockedSetOllama = vi.mocked(setOllamaSettings);
+const mockedGetOpenAi = vi.mocked(getOpenAiSettings);
 
 // Minimal Result-like objects
 const ok = <T,>(data: T) => ({ isOk: () => true, isErr: () => false, unwrap: () => data });
@@ -38,6 +48,13 @@ beforeEach(() => {
   mockedGetSource.mockResolvedValue(ok("gemini") as never);
   mockedGetKey.mockResolvedValue(ok("test-key") as never);
   mockedSetSource.mockResolvedValue(ok(null) as never);
+  mockedGetOllama.mockResolvedValue(
+    ok({ base_url: "http://localhost:11434", model: "llama3.1:8b" }) as never,
+  );
+  mockedSetOllama.mockResolvedValue(ok(null) as never);
+  mockedGetOpenAi.mockResolvedValue(
+    ok({ base_url: "https://api.openai.com/v1", model: "gpt-4o-mini", api_key: "" }) as never,
+  );
 });
 
 describe("AiSettings", () => {
@@ -89,4 +106,44 @@ describe("AiSettings", () => {
       expect(screen.getByRole("button", { name: /Save Key/i })).toBeInTheDocument();
     });
   });
+
+  it("lists the OpenAI-compatible and Ollama backends", async () => {
+    render(<AiSettings />);
+
+    await waitFor(() => {
+      expect(screen.getByText("OpenAI-compatible API")).toBeInTheDocument();
+      expect(screen.getByText("Ollama")).toBeInTheDocument();
+    });
+  });
+
+  it("loads the OpenAI-compatible settings when that backend is selected", async () => {
+    mockedGetSource.mockResolvedValue(ok("openai") as never);
+    render(<AiSettings />);
+
+    await waitFor(() => {
+      expect((screen.getByLabelText("Base URL") as HTMLInputElement).value).toBe(
+        "https://api.openai.com/v1",
+      );
+      expect((screen.getByLabelText("Model") as HTMLInputElement).value).toBe("gpt-4o-mini");
+      expect(screen.getByLabelText("API Key")).toBeInTheDocument();
+    });
+  });
+
+  it("saves edited Ollama settings", async () => {
+    const user = userEvent.setup();
+    mockedGetSource.mockResolvedValue(ok("ollama") as never);
+    render(<AiSettings />);
+
+    const model = (await screen.findByLabelText("Model")) as HTMLInputElement;
+    await user.clear(model);
+    await user.type(model, "qwen2.5:14b");
+    await user.click(screen.getByRole("button", { name: /Save Settings/i }));
+
+    await waitFor(() => {
+      expect(mockedSetOllama).toHaveBeenCalledWith({
+        base_url: "http://localhost:11434",
+        model: "qwen2.5:14b",
+      });
+    });
+  });
 });
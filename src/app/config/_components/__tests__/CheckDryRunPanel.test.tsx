@@ -0,0 +1,81 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { render, screen } from "@testing-library/react";
+import userEvent from "@testing-library/user-event";
+
+vi.mock("@/src/api/extension", () => ({
+  dryRunCustomCheck: vi.fn(),
+}));
+
+vi.mock("../CompletedJobSelect", () => ({
+  CompletedJobSelect: ({
+    value,
+    onChange,
+  }: {
+    value: string;
+    onChange: (job: { job_id: string }) => void;
+  }) => (
+    <select
+      aria-label="Test against crawl"
+      value={value}
+      onChange={(e) => onChange({ job_id: e.target.value })}
+    >
+      <option value="" />
+      <option value="job-1">https://example.com/</option>
+    </select>
+  ),
+}));
+
+import { dryRunCustomCheck } from "@/src/api/extension";
+import { CheckDryRunPanel } from "../CheckDryRunPanel";
+
+const params = {
+  name: "Thin content",
+  severity: "warning" as const,
+  field: "word_count",
+  operator: "lt" as const,
+  threshold: "300",
+  message_template: "Only {value} words",
+  enabled: false,
+};
+
+beforeEach(() => vi.clearAllMocks());
+
+describe("CheckDryRunPanel", () => {
+  it("runs the check against the chosen crawl and lists its issues", async () => {
+    const user = userEvent.setup();
+    vi.mocked(dryRunCustomCheck).mockResolvedValue({
+      pages_checked: 3,
+      issues: [
+        {
+          url: "https://example.com/about",
+          severity: "warning",
+          message: "Only 120 words",
+          details: null,
+        },
+      ],
+    });
+    render(<CheckDryRunPanel params={params} />);
+
+    const run = screen.getByRole("button", { name: /run/i });
+    expect(run).toBeDisabled();
+
+    await user.selectOptions(screen.getByLabelText("Test against crawl"), "job-1");
+    await user.click(run);
+
+    expect(dryRunCustomCheck).toHaveBeenCalledWith(params, "job-1");
+    expect(await screen.findByText("Only 120 words")).toBeInTheDocument();
+    expect(screen.getByText("https://example.com/about")).toBeInTheDocument();
+    expect(screen.getByText("1 issue(s) on 3 page(s).")).toBeInTheDocument();
+  });
+
+  it("shows why the run failed", async () => {
+    const user = userEvent.setup();
+    vi.mocked(dryRunCustomCheck).mockRejectedValue(new Error("job hasn't finished"));
+    render(<CheckDryRunPanel params={params} />);
+
+    await user.selectOptions(screen.getByLabelText("Test against crawl"), "job-1");
+    await user.click(screen.getByRole("button", { name: /run/i }));
+
+    expect(await screen.findByText("job hasn't finished")).toBeInTheDocument();
+  });
+});
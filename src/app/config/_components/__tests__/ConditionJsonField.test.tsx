@@ -0,0 +1,45 @@
+import { describe, it, expect, vi } from "vitest";
+import { fireEvent, render, screen } from "@testing-library/react";
+
+import { ConditionJsonField } from "../ConditionJsonField";
+
+describe("ConditionJsonField", () => {
+  it("reports parsed conditions and clears to null", () => {
+    const onChange = vi.fn();
+    render(
+      <ConditionJsonField id="c" value={null} onChange={onChange} onInvalidChange={vi.fn()} />,
+    );
+    const input = screen.getByLabelText(/compound condition/i);
+
+    fireEvent.change(input, {
+      target: { value: '{"kind":"not","inner":{"kind":"compare","field":"title","operator":"missing"}}' },
+    });
+    expect(onChange).toHaveBeenLastCalledWith({
+      kind: "not",
+      inner: { kind: "compare", field: "title", operator: "missing" },
+    });
+
+    fireEvent.change(input, { target: { value: "  " } });
+    expect(onChange).toHaveBeenLastCalledWith(null);
+  });
+
+  it("flags text that isn't a JSON object", () => {
+    const onChange = vi.fn();
+    const onInvalidChange = vi.fn();
+    render(
+      <ConditionJsonField
+        id="c"
+        value={null}
+        onChange={onChange}
+        onInvalidChange={onInvalidChange}
+      />,
+    );
+
+    fireEvent.change(screen.getByLabelText(/compound condition/i), {
+      target: { value: '{"kind": "all",' },
+    });
+    expect(onChange).not.toHaveBeenCalled();
+    expect(onInvalidChange).toHaveBeenLastCalledWith(true);
+    expect(screen.getByText("Not a valid JSON object")).toBeInTheDocument();
+  });
+});
@@ -12,6 +12,14 @@ vi.mock("@/src/hooks/use-check-field-tags", () => ({
   }),
 }));
 
+vi.mock("../ScriptDryRunPanel", () => ({
+  ScriptDryRunPanel: () => <div data-testid="script-dry-run" />,
+}));
+
+vi.mock("../CheckDryRunPanel", () => ({
+  CheckDryRunPanel: () => <div data-testid="check-dry-run" />,
+}));
+
 import { CustomCheckDialog } from "../CustomCheckDialog";
 
 const defaultProps = {
@@ -55,6 +63,100 @@ describe("CustomCheckDialog", () => {
     );
   });
 
+  it("shows aggregate inputs for a site check and requires a value", async () => {
+    const user = userEvent.setup();
+    const onSave = vi.fn();
+    const onValidationError = vi.fn();
+    const editing = {
+      id: "1",
+      name: "Shared titles",
+      severity: "warning" as const,
+      field: "title",
+      operator: "missing" as const,
+      threshold: null,
+      scope: "site" as const,
+      aggregate: { function: "duplicates" as const, operator: "gt" as const, threshold: null },
+      message_template: "{value} pages share a title",
+      enabled: true,
+    };
+    render(
+      <CustomCheckDialog
+        {...defaultProps}
+        editing={editing}
+        onSave={onSave}
+        onValidationError={onValidationError}
+      />,
+    );
+
+    await user.click(screen.getByRole("button", { name: /save changes/i }));
+    expect(onValidationError).toHaveBeenCalledWith(
+      "Site checks need a threshold to compare the aggregate against",
+    );
+
+    await user.type(screen.getByLabelText("Value"), "5");
+    await user.click(screen.getByRole("button", { name: /save changes/i }));
+    expect(onSave).toHaveBeenCalledWith(
+      expect.objectContaining({
+        scope: "site",
+        aggregate: { function: "duplicates", operator: "gt", threshold: "5" },
+      }),
+    );
+  });
+
+  it("edits a script check's script instead of its rule", async () => {
+    const user = userEvent.setup();
+    const onSave = vi.fn();
+    const editing = {
+      id: "1",
+      name: "Alt text",
+      severity: "warning" as const,
+      field: "",
+      operator: "present" as const,
+      threshold: null,
+      script: '"old"',
+      message_template: "",
+      enabled: true,
+    };
+    render(<CustomCheckDialog {...defaultProps} editing={editing} onSave={onSave} />);
+
+    expect(screen.queryByText("Field")).not.toBeInTheDocument();
+    expect(screen.queryByLabelText("Message Template")).not.toBeInTheDocument();
+    expect(screen.getByTestId("script-dry-run")).toBeInTheDocument();
+    expect(screen.queryByTestId("check-dry-run")).not.toBeInTheDocument();
+
+    const script = screen.getByLabelText("Script");
+    await user.clear(script);
+    await user.type(script, "()");
+    await user.click(screen.getByRole("button", { name: /save changes/i }));
+    expect(onSave).toHaveBeenCalledWith(expect.objectContaining({ script: "()", aggregate: null }));
+  });
+
+  it("requires a script for script checks", async () => {
+    const user = userEvent.setup();
+    const onValidationError = vi.fn();
+    const editing = {
+      id: "1",
+      name: "Alt text",
+      severity: "warning" as const,
+      field: "",
+      operator: "present" as const,
+      threshold: null,
+      script: "",
+      message_template: "",
+      enabled: true,
+    };
+    render(
+      <CustomCheckDialog
+        {...defaultProps}
+        editing={editing}
+        onValidationError={onValidationError}
+      />,
+    );
+
+    await user.click(screen.getByRole("button", { name: /save changes/i }));
+    expect(onValidationError).toHaveBeenCalledWith("Name and script are required");
+  });
+
   it("disables save button when saving is true", () => {
     render(<CustomCheckDialog {...defaultProps} saving={true} />);
     expect(screen.getByRole("button", { name: /create/i })).toBeDisabled();
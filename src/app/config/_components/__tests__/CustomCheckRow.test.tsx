@@ -42,6 +42,27 @@ describe("CustomCheckRow", () => {
     expect(screen.getByText("is missing")).toBeInTheDocument();
   });
 
+  it("summarises a site check's aggregate", () => {
+    render(
+      <Table>
+        <TableBody>
+          <CustomCheckRow
+            check={{
+              ...check,
+              scope: "site",
+              aggregate: { function: "percent", operator: "lt", threshold: "80" },
+            }}
+            onEdit={vi.fn()}
+            onDelete={vi.fn()}
+            onToggleEnabled={vi.fn()}
+          />
+        </TableBody>
+      </Table>,
+    );
+    expect(screen.getByText("site")).toBeInTheDocument();
+    expect(screen.getByText(/% of pages matching less than 80/)).toBeInTheDocument();
+  });
+
   it("invokes edit and delete callbacks", async () => {
     const user = userEvent.setup();
     const props = renderRow();
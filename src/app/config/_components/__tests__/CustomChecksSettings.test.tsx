@@ -12,6 +12,7 @@ vi.mock("@/src/api/extension", () => ({
   createCustomCheck: vi.fn(),
   updateCustomCheck: vi.fn(),
   deleteCustomCheck: vi.fn(),
+  applyCustomChecks: vi.fn(),
 }));
 
 vi.mock("sonner", () => ({
@@ -44,7 +45,26 @@ vi.mock("../CustomCheckRow", () => ({
   ),
 }));
 
+vi.mock("../CompletedJobSelect", () => ({
+  CompletedJobSelect: ({
+    label,
+    value,
+    onChange,
+  }: {
+    label: string;
+    value: string;
+    onChange: (job: { job_id: string }) => void;
+  }) => (
+    <select aria-label={label} value={value} onChange={(e) => onChange({ job_id: e.target.value })}>
+      <option value="" />
+      <option value="job-1">https://example.com/</option>
+    </select>
+  ),
+}));
+
 import useSWR from "swr";
+import { toast } from "sonner";
+import { applyCustomChecks } from "@/src/api/extension";
 import { CustomChecksSettings } from "../CustomChecksSettings";
 
 const mockedUseSWR = vi.mocked(useSWR);
@@ -114,4 +134,33 @@ describe("CustomChecksSettings", () => {
     render(<CustomChecksSettings />);
     expect(screen.getByRole("button", { name: /Add Check/i })).toBeInTheDocument();
   });
+
+  it("re-applies the checks to a finished crawl", async () => {
+    mockedUseSWR.mockReturnValue({
+      data: [{ id: "c1", name: "Missing OG Image", severity: "warning" }],
+      mutate,
+      isLoading: false,
+      isValidating: false,
+      error: undefined,
+    } as never);
+    vi.mocked(applyCustomChecks).mockResolvedValue({
+      pages_checked: 3,
+      pages_reextracted: 2,
+      issues_removed: 1,
+      issues_added: 2,
+    });
+
+    const user = userEvent.setup();
+    render(<CustomChecksSettings />);
+
+    const apply = screen.getByRole("button", { name: /Apply Checks/i });
+    expect(apply).toBeDisabled();
+    await user.selectOptions(screen.getByLabelText("Apply to finished crawl"), "job-1");
+    await user.click(apply);
+
+    expect(applyCustomChecks).toHaveBeenCalledWith("job-1");
+    expect(toast.success).toHaveBeenCalledWith(
+      "Re-checked 3 page(s): 2 issue(s) raised, 1 replaced",
+    );
+  });
 });
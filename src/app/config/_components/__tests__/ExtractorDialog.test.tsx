@@ -6,6 +6,10 @@ vi.mock("../SelectorLivePreview", () => ({
   SelectorLivePreview: () => <div data-testid="live-preview" />,
 }));
 
+vi.mock("../ExtractorDryRunPanel", () => ({
+  ExtractorDryRunPanel: () => <div data-testid="extractor-dry-run" />,
+}));
+
 import { ExtractorDialog } from "../ExtractorDialog";
 import type { CustomExtractor } from "@/src/api/extension";
 
@@ -51,6 +55,9 @@ describe("ExtractorDialog", () => {
       selector: "title",
       enabled: true,
       multiple: false,
+      kind: "css",
+      output: "value",
+      data_type: null,
     });
   });
 
@@ -96,4 +103,38 @@ describe("ExtractorDialog", () => {
     );
     expect(screen.getByRole("button", { name: /Save Changes/ })).toBeInTheDocument();
   });
+
+  it("labels the inputs for the extractor's kind and keeps its settings", async () => {
+    const user = userEvent.setup();
+    const onSave = vi.fn();
+    render(
+      <ExtractorDialog
+        open
+        editing={{
+          ...sampleExtractor,
+          kind: "json_ld",
+          selector: "$.offers.price",
+          attribute: null,
+          output: "value",
+          data_type: "number",
+        }}
+        saving={false}
+        onOpenChange={() => {}}
+        onSave={onSave}
+        onValidationError={() => {}}
+      />,
+    );
+
+    expect(screen.getByLabelText(/JSON Path/)).toHaveValue("$.offers.price");
+    // JSON-LD paths have no attribute, and the live preview is CSS-only.
+    expect(screen.queryByLabelText(/Attribute/)).not.toBeInTheDocument();
+    expect(screen.queryByTestId("live-preview")).not.toBeInTheDocument();
+
+    await user.click(screen.getByRole("button", { name: /Save Changes/ }));
+    expect(onSave.mock.calls[0][0]).toMatchObject({
+      kind: "json_ld",
+      selector: "$.offers.price",
+      data_type: "number",
+    });
+  });
 });
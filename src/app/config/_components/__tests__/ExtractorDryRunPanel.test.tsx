@@ -0,0 +1,72 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { render, screen } from "@testing-library/react";
+import userEvent from "@testing-library/user-event";
+
+vi.mock("@/src/api/extension", () => ({
+  dryRunCustomExtractor: vi.fn(),
+}));
+
+vi.mock("../CompletedJobSelect", () => ({
+  CompletedJobSelect: ({
+    value,
+    onChange,
+  }: {
+    value: string;
+    onChange: (job: { job_id: string }) => void;
+  }) => (
+    <select
+      aria-label="Test against crawl"
+      value={value}
+      onChange={(e) => onChange({ job_id: e.target.value })}
+    >
+      <option value="" />
+      <option value="job-1">https://example.com/</option>
+    </select>
+  ),
+}));
+
+import { dryRunCustomExtractor } from "@/src/api/extension";
+import { ExtractorDryRunPanel } from "../ExtractorDryRunPanel";
+
+const params = {
+  name: "OG Image",
+  tag: "og_image",
+  selector: "meta[property='og:image']",
+  attribute: "content",
+  multiple: false,
+  enabled: true,
+};
+
+beforeEach(() => vi.clearAllMocks());
+
+describe("ExtractorDryRunPanel", () => {
+  it("shows what the extractor finds on each page with saved HTML", async () => {
+    const user = userEvent.setup();
+    vi.mocked(dryRunCustomExtractor).mockResolvedValue({
+      pages: [
+        { url: "https://example.com/", value: "https://example.com/og.png" },
+        { url: "https://example.com/about", value: null },
+      ],
+      without_html: ["https://example.com/contact"],
+    });
+    render(<ExtractorDryRunPanel params={params} />);
+
+    await user.selectOptions(screen.getByLabelText("Test against crawl"), "job-1");
+    await user.click(screen.getByRole("button", { name: /run/i }));
+
+    expect(dryRunCustomExtractor).toHaveBeenCalledWith(params, "job-1");
+    expect(await screen.findByText("https://example.com/og.png")).toBeInTheDocument();
+    expect(screen.getByText("no match")).toBeInTheDocument();
+    expect(screen.getByText(/Matched 1 of 2 page\(s\)/)).toHaveTextContent(
+      "1 page(s) have no saved HTML",
+    );
+  });
+
+  it("needs a selector before it can run", async () => {
+    const user = userEvent.setup();
+    render(<ExtractorDryRunPanel params={{ ...params, selector: " " }} />);
+
+    await user.selectOptions(screen.getByLabelText("Test against crawl"), "job-1");
+    expect(screen.getByRole("button", { name: /run/i })).toBeDisabled();
+  });
+});
@@ -45,6 +45,21 @@ describe("ExtractorRow", () => {
     expect(screen.getByText("@href")).toBeInTheDocument();
   });
 
+  it("labels non-CSS kinds and non-value outputs", () => {
+    renderRow({
+      extractor: {
+        ...extractor,
+        kind: "regex",
+        selector: "SKU: (\\w+)",
+        attribute: "1",
+        output: "count",
+      },
+    });
+    expect(screen.getByText("Regex")).toBeInTheDocument();
+    expect(screen.getByText("group 1")).toBeInTheDocument();
+    expect(screen.getByText("count")).toBeInTheDocument();
+  });
+
   it("calls onEdit when the edit button is clicked", async () => {
     const user = userEvent.setup();
     const props = renderRow();
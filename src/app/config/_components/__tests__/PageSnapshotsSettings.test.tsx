@@ -0,0 +1,73 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { render, screen, waitFor } from "@testing-library/react";
+import userEvent from "@testing-library/user-event";
+
+const swrData: Record<string, unknown> = {
+  "snapshot-settings": { enabled: true, retention_days: 90 },
+  "snapshot-storage": {
+    snapshots: 12,
+    distinct_sources: 4,
+    html_bytes: 2_400_000,
+    stored_bytes: 300_000,
+  },
+};
+const mutateStorage = vi.fn();
+
+vi.mock("swr", () => ({
+  default: vi.fn((key: string) => ({ data: swrData[key], mutate: mutateStorage })),
+}));
+
+vi.mock("@/src/api/snapshot", () => ({
+  getSnapshotSettings: vi.fn(),
+  getSnapshotStorage: vi.fn(),
+  setSnapshotSettings: vi.fn(),
+}));
+
+vi.mock("sonner", () => ({
+  toast: { success: vi.fn(), error: vi.fn() },
+}));
+
+import { toast } from "sonner";
+import { setSnapshotSettings } from "@/src/api/snapshot";
+import { PageSnapshotsSettings } from "../PageSnapshotsSettings";
+
+beforeEach(() => vi.clearAllMocks());
+
+describe("PageSnapshotsSettings", () => {
+  it("shows the stored settings and what the snapshots take up", () => {
+    render(<PageSnapshotsSettings />);
+
+    expect(screen.getByRole("switch", { name: "Save page source" })).toBeChecked();
+    expect(screen.getByLabelText("Keep snapshots for (days)")).toHaveValue(90);
+    expect(screen.getByText(/12 snapshot\(s\) of 4 distinct page source\(s\)/)).toBeInTheDocument();
+    expect(screen.getByText(/2\.4 MB of HTML stored in 300 KB/)).toBeInTheDocument();
+  });
+
+  it("saves an empty retention as keeping snapshots until the crawl is deleted", async () => {
+    const user = userEvent.setup();
+    const remaining = { snapshots: 12, distinct_sources: 4, html_bytes: 1, stored_bytes: 1 };
+    vi.mocked(setSnapshotSettings).mockResolvedValue(remaining);
+    render(<PageSnapshotsSettings />);
+
+    await user.clear(screen.getByLabelText("Keep snapshots for (days)"));
+    await user.click(screen.getByRole("button", { name: /save/i }));
+
+    await waitFor(() =>
+      expect(setSnapshotSettings).toHaveBeenCalledWith({ enabled: true, retention_days: null }),
+    );
+    expect(mutateStorage).toHaveBeenCalledWith(remaining, { revalidate: false });
+    expect(toast.success).toHaveBeenCalledWith("Snapshot settings saved");
+  });
+
+  it("won't save a retention of zero days", async () => {
+    const user = userEvent.setup();
+    render(<PageSnapshotsSettings />);
+
+    const days = screen.getByLabelText("Keep snapshots for (days)");
+    await user.clear(days);
+    await user.type(days, "0");
+
+    expect(screen.getByText(/at least 1/)).toBeInTheDocument();
+    expect(screen.getByRole("button", { name: /save/i })).toBeDisabled();
+  });
+});
@@ -0,0 +1,123 @@
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { render, screen, waitFor } from "@testing-library/react";
+import userEvent from "@testing-library/user-event";
+
+const swrData: Record<string, unknown[]> = {
+  "custom-checks": [{ id: "c1", name: "OG image missing" }],
+  "custom-extractors": [{ id: "e1", name: "OG Image", tag: "og_image" }],
+  "report-patterns": [],
+  "report-templates": [],
+};
+
+vi.mock("swr", () => ({
+  default: vi.fn((key: string) => ({ data: swrData[key], mutate: vi.fn() })),
+  useSWRConfig: () => ({ mutate: vi.fn() }),
+}));
+
+vi.mock("@/src/api/extension", () => ({
+  listCustomChecks: vi.fn(),
+  listCustomExtractors: vi.fn(),
+}));
+
+vi.mock("@/src/api/report", () => ({
+  listReportPatterns: vi.fn(),
+  listReportTemplates: vi.fn(),
+}));
+
+vi.mock("@/src/api/rule-pack", () => ({
+  exportRulePack: vi.fn(),
+  previewRulePack: vi.fn(),
+  importRulePack: vi.fn(),
+}));
+
+vi.mock("@/src/lib/export-utils", () => ({
+  saveFile: vi.fn(),
+}));
+
+vi.mock("@tauri-apps/plugin-dialog", () => ({
+  open: vi.fn(),
+}));
+
+vi.mock("@tauri-apps/plugin-fs", () => ({
+  readFile: vi.fn(),
+}));
+
+vi.mock("sonner", () => ({
+  toast: { success: vi.fn(), error: vi.fn() },
+}));
+
+import { open } from "@tauri-apps/plugin-dialog";
+import { readFile } from "@tauri-apps/plugin-fs";
+import { exportRulePack, importRulePack, previewRulePack } from "@/src/api/rule-pack";
+import { saveFile } from "@/src/lib/export-utils";
+import { RulePacksSettings } from "../RulePacksSettings";
+
+beforeEach(() => vi.clearAllMocks());
+
+const preview = {
+  name: "Social",
+  description: "",
+  version: 1,
+  exported_at: "2026-01-01T00:00:00Z",
+  signer: { fingerprint: "ab12 cd34 ef56 7890", is_local: false },
+  items: [
+    { kind: "extractor", id: "e1", name: "OG Image", collision: "tag", outcome: "skipped", renamed_to: null },
+    { kind: "check", id: "c1", name: "OG image missing", collision: null, outcome: "created", renamed_to: null },
+  ],
+  unresolved: [],
+};
+
+describe("RulePacksSettings", () => {
+  it("exports the selected items under the pack name", async () => {
+    vi.mocked(exportRulePack).mockResolvedValue("{}");
+    const user = userEvent.setup();
+    render(<RulePacksSettings />);
+
+    const exportButton = screen.getByRole("button", { name: /Export Pack/ });
+    expect(exportButton).toBeDisabled();
+
+    await user.type(screen.getByLabelText("Pack name"), "Social Checks");
+    await user.click(screen.getByLabelText("Checks: OG image missing"));
+    await user.click(exportButton);
+
+    expect(exportRulePack).toHaveBeenCalledWith(
+      expect.objectContaining({ name: "Social Checks", check_ids: ["c1"], format: "json", sign: true }),
+    );
+    await waitFor(() =>
+      expect(saveFile).toHaveBeenCalledWith("{}", "social-checks.rule-pack.json", expect.anything()),
+    );
+  });
+
+  it("previews a chosen file and imports it", async () => {
+    vi.mocked(open).mockResolvedValue("/tmp/social.rule-pack.json");
+    vi.mocked(readFile).mockResolvedValue(new TextEncoder().encode("{}"));
+    vi.mocked(previewRulePack).mockResolvedValue(preview as never);
+    vi.mocked(importRulePack).mockResolvedValue(preview.items as never);
+    const user = userEvent.setup();
+    render(<RulePacksSettings />);
+
+    await user.click(screen.getByRole("button", { name: /Choose File/ }));
+
+    expect(await screen.findByText("Social")).toBeInTheDocument();
+    expect(previewRulePack).toHaveBeenCalledWith("{}", "merge");
+    expect(screen.getByText("Kept yours")).toBeInTheDocument();
+    expect(screen.getByText("same tag as yours")).toBeInTheDocument();
+    expect(screen.getByText("ab12 cd34 ef56 7890")).toBeInTheDocument();
+
+    await user.click(screen.getByRole("button", { name: "Import Pack" }));
+    expect(importRulePack).toHaveBeenCalledWith("{}", "merge");
+  });
+
+  it("blocks the import while references are unresolved", async () => {
+    vi.mocked(open).mockResolvedValue("/tmp/pack.toml");
+    vi.mocked(readFile).mockResolvedValue(new TextEncoder().encode("format = 'x'"));
+    vi.mocked(previewRulePack).mockResolvedValue({ ...preview, unresolved: ["tag:og_title"] } as never);
+    const user = userEvent.setup();
+    render(<RulePacksSettings />);
+
+    await user.click(screen.getByRole("button", { name: /Choose File/ }));
+
+    expect(await screen.findByText(/relies on tag:og_title/)).toBeInTheDocument();
+    expect(screen.getByRole("button", { name: "Import Pack" })).toBeDisabled();
+  });
+});
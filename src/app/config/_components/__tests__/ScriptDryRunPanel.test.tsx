@@ -0,0 +1,110 @@
+import type { ReactNode } from "react";
+import { describe, it, expect, vi, beforeEach } from "vitest";
+import { render, screen } from "@testing-library/react";
+import userEvent from "@testing-library/user-event";
+
+vi.mock("swr", () => ({
+  default: vi.fn(() => ({
+    data: [{ job_id: "job-1", url: "https://example.com/", job_status: "completed" }],
+  })),
+}));
+
+vi.mock("@/src/api/analysis", () => ({
+  getAllJobs: vi.fn(),
+}));
+
+vi.mock("@/src/api/extension", () => ({
+  dryRunCheckScript: vi.fn(),
+}));
+
+// A native select stands in for the Radix one, which jsdom can't open.
+vi.mock("@/src/components/ui/select", () => ({
+  Select: ({
+    value,
+    onValueChange,
+    children,
+  }: {
+    value: string;
+    onValueChange: (v: string) => void;
+    children: ReactNode;
+  }) => (
+    <select aria-label="Test against crawl" value={value} onChange={(e) => onValueChange(e.target.value)}>
+      <option value="" />
+      {children}
+    </select>
+  ),
+  SelectTrigger: () => null,
+  SelectValue: () => null,
+  SelectContent: ({ children }: { children: ReactNode }) => <>{children}</>,
+  SelectItem: ({ value, children }: { value: string; children: ReactNode }) => (
+    <option value={value}>{children}</option>
+  ),
+}));
+
+import { dryRunCheckScript } from "@/src/api/extension";
+import { ScriptDryRunPanel } from "../ScriptDryRunPanel";
+
+const params = {
+  name: "Alt text",
+  severity: "warning" as const,
+  field: "",
+  operator: "present" as const,
+  threshold: null,
+  script: "page.images.map(|img| img.src)",
+  message_template: "",
+  enabled: true,
+};
+
+beforeEach(() => vi.clearAllMocks());
+
+describe("ScriptDryRunPanel", () => {
+  it("runs the script against the chosen page and shows issues and output", async () => {
+    const user = userEvent.setup();
+    vi.mocked(dryRunCheckScript).mockResolvedValue({
+      issues: [{ severity: "critical", message: "/hero.jpg", details: null }],
+      output: ["checked 1 image"],
+      error: null,
+    });
+    render(<ScriptDryRunPanel params={params} />);
+
+    const run = screen.getByRole("button", { name: /run/i });
+    expect(run).toBeDisabled();
+
+    await user.selectOptions(screen.getByLabelText("Test against crawl"), "job-1");
+    const url = screen.getByLabelText("Page URL");
+    expect(url).toHaveValue("https://example.com/");
+    await user.type(url, "about");
+    await user.click(run);
+
+    expect(dryRunCheckScript).toHaveBeenCalledWith(params, "job-1", "https://example.com/about");
+    expect(await screen.findByText("/hero.jpg")).toBeInTheDocument();
+    expect(screen.getByText("critical")).toBeInTheDocument();
+    expect(screen.getByLabelText("Script output")).toHaveTextContent("checked 1 image");
+  });
+
+  it("shows the script's error", async () => {
+    const user = userEvent.setup();
+    vi.mocked(dryRunCheckScript).mockResolvedValue({
+      issues: [],
+      output: [],
+      error: "Variable not found: pge (line 1, position 1)",
+    });
+    render(<ScriptDryRunPanel params={params} />);
+
+    await user.selectOptions(screen.getByLabelText("Test against crawl"), "job-1");
+    await user.click(screen.getByRole("button", { name: /run/i }));
+
+    expect(await screen.findByText(/Variable not found/)).toBeInTheDocument();
+  });
+
+  it("shows a failed lookup", async () => {
+    const user = userEvent.setup();
+    vi.mocked(dryRunCheckScript).mockRejectedValue(new Error("page not found"));
+    render(<ScriptDryRunPanel params={params} />);
+
+    await user.selectOptions(screen.getByLabelText("Test against crawl"), "job-1");
+    await user.click(screen.getByRole("button", { name: /run/i }));
+
+    expect(await screen.findByText("page not found")).toBeInTheDocument();
+  });
+});
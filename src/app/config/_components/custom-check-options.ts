@@ -1,5 +1,17 @@
+import type { AggregateFunction, CheckScope, Operator } from "@/src/bindings";
+
 export type CheckSeverity = "info" | "warning" | "critical";
-export type CheckOperator = "missing" | "lt" | "gt" | "contains" | "not_contains";
+export type CheckOperator =
+  | "missing"
+  | "lt"
+  | "gt"
+  | "contains"
+  | "not_contains"
+  | "matches"
+  | "between"
+  | "length_lt"
+  | "length_gt"
+  | "in_list";
 
 export const SEVERITY_OPTIONS: { value: CheckSeverity; label: string }[] = [
   { value: "info", label: "Info" },
@@ -13,4 +25,60 @@ export const OPERATOR_OPTIONS: { value: CheckOperator; label: string }[] = [
   { value: "gt", label: "greater than" },
   { value: "contains", label: "contains" },
   { value: "not_contains", label: "does not contain" },
+  { value: "matches", label: "matches regex" },
+  { value: "between", label: "between" },
+  { value: "length_lt", label: "shorter than (chars)" },
+  { value: "length_gt", label: "longer than (chars)" },
+  { value: "in_list", label: "is one of" },
+];
+
+/** Example thresholds, shared with the report pattern dialog. */
+export const THRESHOLD_PLACEHOLDERS: Partial<Record<Operator, string>> = {
+  lt: "300",
+  gt: "300",
+  matches: "^https://www\\.",
+  between: "300,1500",
+  length_lt: "30",
+  length_gt: "60",
+  in_list: "200,301",
+};
+
+export const SCOPE_OPTIONS: { value: CheckScope; label: string }[] = [
+  { value: "page", label: "Each page" },
+  { value: "site", label: "Whole site" },
 ];
+
+export const AGGREGATE_OPTIONS: { value: AggregateFunction; label: string }[] = [
+  { value: "count", label: "Count of matching pages" },
+  { value: "percent", label: "% of pages matching" },
+  { value: "distinct", label: "Distinct field values" },
+  { value: "duplicates", label: "Pages sharing a field value" },
+  { value: "min", label: "Minimum field value" },
+  { value: "max", label: "Maximum field value" },
+  { value: "avg", label: "Average field value" },
+];
+
+/** Aggregates are numbers, so site checks compare them numerically. */
+export type AggregateOperator = "eq" | "lt" | "gt" | "between";
+
+export const AGGREGATE_OPERATOR_OPTIONS: { value: AggregateOperator; label: string }[] = [
+  { value: "gt", label: "greater than" },
+  { value: "lt", label: "less than" },
+  { value: "eq", label: "equals" },
+  { value: "between", label: "between" },
+];
+
+/** Whether a check evaluates a rule or runs a script. */
+export type CheckKind = "rule" | "script";
+
+export const CHECK_KIND_OPTIONS: { value: CheckKind; label: string }[] = [
+  { value: "rule", label: "Rule" },
+  { value: "script", label: "Script (Rhai)" },
+];
+
+/** Starting point for a new script check. */
+export const SCRIPT_TEMPLATE = `// \`page\` has the page's fields, tags, headers, headings, links and images.
+// Return a message, a #{ message, severity, details } map, or an array of them.
+let missing = page.images.filter(|img| img.alt == () && !img.decorative);
+missing.map(|img| \`Image \${img.src} has no alt text\`)
+`;
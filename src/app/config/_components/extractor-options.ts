@@ -0,0 +1,74 @@
+import type { ExtractorKind, ExtractorOutput, TagDataType } from "@/src/bindings";
+
+/** How each extractor kind labels and explains its two inputs. */
+export interface KindOption {
+  value: ExtractorKind;
+  label: string;
+  selectorLabel: string;
+  selectorPlaceholder: string;
+  /** `null` when the kind has no use for the attribute input. */
+  attributeLabel: string | null;
+  attributePlaceholder: string;
+}
+
+export const KIND_OPTIONS: KindOption[] = [
+  {
+    value: "css",
+    label: "CSS selector",
+    selectorLabel: "CSS Selector",
+    selectorPlaceholder: "meta[property='og:image']",
+    attributeLabel: "Attribute",
+    attributePlaceholder: 'e.g. "content" or "href" — blank = element text',
+  },
+  {
+    value: "xpath",
+    label: "XPath",
+    selectorLabel: "XPath",
+    selectorPlaceholder: "//dt[text()='SKU']/../dd[1]",
+    attributeLabel: "Attribute",
+    attributePlaceholder: "blank = element text, or end the path in /@href",
+  },
+  {
+    value: "regex",
+    label: "Regex over HTML",
+    selectorLabel: "Regex",
+    selectorPlaceholder: '"pageType":\\s*"(\\w+)"',
+    attributeLabel: "Capture group",
+    attributePlaceholder: "name or number — blank = first group",
+  },
+  {
+    value: "json_ld",
+    label: "JSON-LD path",
+    selectorLabel: "JSON Path",
+    selectorPlaceholder: "$.offers.price",
+    attributeLabel: null,
+    attributePlaceholder: "",
+  },
+  {
+    value: "header",
+    label: "Response header",
+    selectorLabel: "Header Name",
+    selectorPlaceholder: "Last-Modified",
+    attributeLabel: null,
+    attributePlaceholder: "",
+  },
+];
+
+export function kindOption(kind: ExtractorKind | undefined): KindOption {
+  return KIND_OPTIONS.find((o) => o.value === (kind ?? "css")) ?? KIND_OPTIONS[0];
+}
+
+export const OUTPUT_OPTIONS: { value: ExtractorOutput; label: string }[] = [
+  { value: "value", label: "Matched value" },
+  { value: "count", label: "Number of matches" },
+  { value: "length", label: "Length in characters" },
+];
+
+/** `"auto"` stands in for a `null` data type, which a select can't hold. */
+export const DATA_TYPE_OPTIONS: { value: TagDataType | "auto"; label: string }[] = [
+  { value: "auto", label: "Automatic" },
+  { value: "text", label: "Text" },
+  { value: "number", label: "Number" },
+  { value: "bool", label: "Yes / no" },
+  { value: "list", label: "List" },
+];
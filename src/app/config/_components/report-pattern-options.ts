@@ -11,6 +11,7 @@ export const CATEGORY_OPTIONS: { value: PatternCategory; label: string }[] = [
   { value: "content", label: "Content" },
   { value: "performance", label: "Performance" },
   { value: "accessibility", label: "Accessibility" },
+  { value: "security", label: "Security & Delivery" },
 ];
 
 export const SEVERITY_OPTIONS: { value: PatternSeverity; label: string }[] = [
@@ -27,6 +28,11 @@ export const OPERATOR_OPTIONS: { value: Operator; label: string; needsThreshold:
   { value: "gt", label: "greater than", needsThreshold: true },
   { value: "contains", label: "contains", needsThreshold: true },
   { value: "not_contains", label: "does not contain", needsThreshold: true },
+  { value: "matches", label: "matches regex", needsThreshold: true },
+  { value: "between", label: "between", needsThreshold: true },
+  { value: "length_lt", label: "shorter than (chars)", needsThreshold: true },
+  { value: "length_gt", label: "longer than (chars)", needsThreshold: true },
+  { value: "in_list", label: "is one of", needsThreshold: true },
 ];
 
 export const IMPACT_OPTIONS: { value: BusinessImpact; label: string }[] = [
@@ -0,0 +1,49 @@
+import type {
+  ImportOutcome,
+  ImportStrategy,
+  PackFormat,
+  RulePackItemKind,
+} from "@/src/bindings";
+
+export const FORMAT_OPTIONS: { value: PackFormat; label: string }[] = [
+  { value: "json", label: "JSON" },
+  { value: "toml", label: "TOML" },
+];
+
+export const STRATEGY_OPTIONS: { value: ImportStrategy; label: string; description: string }[] = [
+  {
+    value: "merge",
+    label: "Merge",
+    description: "Keep what you have; only add items that are new.",
+  },
+  {
+    value: "overwrite",
+    label: "Overwrite",
+    description: "Replace matching items with the pack's versions.",
+  },
+  {
+    value: "rename",
+    label: "Rename",
+    description: "Import matching items alongside yours under new names and tags.",
+  },
+];
+
+export const KIND_LABELS: Record<RulePackItemKind, string> = {
+  extractor: "Extractor",
+  check: "Check",
+  pattern: "Pattern",
+  template: "Template",
+};
+
+export const OUTCOME_LABELS: Record<ImportOutcome, string> = {
+  created: "New",
+  updated: "Overwrites yours",
+  renamed: "Renamed",
+  skipped: "Kept yours",
+};
+
+/** `Social Checks` → `social-checks.rule-pack.json`. */
+export function packFilename(name: string, format: PackFormat): string {
+  const slug = name.trim().toLowerCase().replace(/[^a-z0-9]+/g, "-").replace(/^-|-$/g, "");
+  return `${slug || "rules"}.rule-pack.${format}`;
+}
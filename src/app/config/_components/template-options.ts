@@ -1,7 +1,10 @@
+import type { SiteCheckStatus } from "@/src/bindings";
+
 /** Condition types for the template conditional editor picker. */
 export const CONDITION_TYPES = [
   { value: "sitemapMissing", label: "Sitemap is missing", hasInput: false },
   { value: "robotsMissing", label: "Robots.txt is missing", hasInput: false },
+  { value: "siteCheck", label: "Site file check ended with…", hasInput: true, inputLabel: "Check ID (e.g. security_txt)", hasSecondInput: true, secondLabel: "Status" },
   { value: "scoreLt", label: "SEO score less than…", hasInput: true, inputLabel: "Score threshold" },
   { value: "criticalIssuesGt", label: "Critical issues greater than…", hasInput: true, inputLabel: "Count threshold" },
   { value: "patternFired", label: "Specific pattern fired…", hasInput: true, inputLabel: "Pattern ID" },
@@ -11,17 +14,32 @@ export const CONDITION_TYPES = [
   { value: "tagContains", label: "Tag value contains…", hasInput: true, inputLabel: "Tag name", hasSecondInput: true, secondLabel: "Contains text" },
 ] as const;
 
+/** Every status a site file check can end with, for the `siteCheck` picker. */
+export const SITE_CHECK_STATUSES: readonly SiteCheckStatus[] = [
+  "found",
+  "invalid",
+  "unauthorized",
+  "missing",
+  "error",
+];
+
+function asSiteCheckStatus(value: string | undefined): SiteCheckStatus {
+  return SITE_CHECK_STATUSES.find((status) => status === value) ?? "missing";
+}
+
 export function conditionInputValue(when: Record<string, unknown>): string {
   const op = when.op as string;
   if (op === "scoreLt" || op === "criticalIssuesGt") return String(when.value ?? "");
   if (op === "patternFired") return String(when.patternId ?? "");
   if (op === "tagPresent" || op === "tagMissing") return String(when.tag ?? "");
   if (op === "tagContains") return String(when.tag ?? "");
+  if (op === "siteCheck") return String(when.check ?? "");
   return "";
 }
 
 export function conditionSecondInputValue(when: Record<string, unknown>): string {
   if ((when.op as string) === "tagContains") return String(when.value ?? "");
+  if ((when.op as string) === "siteCheck") return String(when.status ?? "");
   return "";
 }
 
@@ -44,6 +62,8 @@ export function buildCondition(op: string, inputValue: string, secondValue?: str
       return { op, tag: inputValue };
     case "tagContains":
       return { op, tag: inputValue, value: secondValue ?? "" };
+    case "siteCheck":
+      return { op, check: inputValue, status: asSiteCheckStatus(secondValue) };
     default:
       return { op };
   }
@@ -27,6 +27,8 @@ import { CustomChecksSettings } from "./_components/CustomChecksSettings";
 import { ExtractorsSettings } from "./_components/ExtractorsSettings";
 import { ReportPatternsSettings } from "./_components/ReportPatternsSettings";
 import { TagsSettings } from "./_components/TagsSettings";
+import { RulePacksSettings } from "./_components/RulePacksSettings";
+import { PageSnapshotsSettings } from "./_components/PageSnapshotsSettings";
 import { ReportBuilder } from "./_components/ReportBuilder";
 import { ErrorBoundary } from "@/src/components/ErrorBoundary";
 
@@ -164,6 +166,8 @@ function ConfigContent({
         {activeSection === "custom-checks" && <CustomChecksSettings />}
         {activeSection === "custom-extractors" && <ExtractorsSettings />}
         {activeSection === "tags" && <TagsSettings />}
+        {activeSection === "rule-packs" && <RulePacksSettings />}
+        {activeSection === "page-snapshots" && <PageSnapshotsSettings />}
         {activeSection === "licensing" && <LicensingSection />}
         {activeSection === "appearance" && <ThemeSettings />}
       </div>
@@ -125,6 +125,38 @@ async setAiSource(source: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async getOpenaiSettings() : Promise<Result<OpenAiCompatibleSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_openai_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setOpenaiSettings(settings: OpenAiCompatibleSettings) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_openai_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getOllamaSettings() : Promise<Result<OllamaSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_ollama_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setOllamaSettings(settings: OllamaSettings) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_ollama_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async startAnalysis(url: string, settings: AnalysisSettingsRequest | null) : Promise<Result<AnalysisJobResponse, CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("start_analysis", { url, settings }) };
@@ -133,6 +165,19 @@ async startAnalysis(url: string, settings: AnalysisSettingsRequest | null) : Pro
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Start a list-mode job: analyse exactly the given URLs, with no link
+ * discovery. URLs past the page limit, duplicates and unusable lines are
+ * left out and described in the returned report.
+ */
+async startListAnalysis(source: UrlListSource, settings: AnalysisSettingsRequest | null) : Promise<Result<ListAnalysisResponse, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_list_analysis", { source, settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getAnalysisProgress(jobId: string) : Promise<Result<AnalysisProgress, CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_analysis_progress", { jobId }) };
@@ -165,6 +210,22 @@ async cancelAnalysis(jobId: string) : Promise<Result<null, CommandError>> {
     else return { status: "error", error: e  as any };
 }
 },
+async pauseAnalysis(jobId: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pause_analysis", { jobId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resumeAnalysis(jobId: string) : Promise<Result<null, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_analysis", { jobId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getResult(jobId: string) : Promise<Result<CompleteAnalysisResponse, CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_result", { jobId }) };
@@ -173,6 +234,19 @@ async getResult(jobId: string) : Promise<Result<CompleteAnalysisResponse, Comman
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Re-fetch a sample of a job's pages as the job's own profile and as
+ * `compare_with`, and report where the two were served different
+ * content.
+ */
+async checkCloaking(jobId: string, compareWith: CrawlProfile, sampleSize: number | null) : Promise<Result<CloakingReport, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_cloaking", { jobId, compareWith, sampleSize }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getAnalysisDefaults() : Promise<Result<AnalysisSettingsRequest, CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_analysis_defaults") };
@@ -317,6 +391,44 @@ async deleteCustomCheck(id: string) : Promise<Result<null, CommandError>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Run a draft check's script against a stored page of `job_id`, for
+ * debugging it before it's saved. Script errors and printed output come
+ * back in the result.
+ */
+async dryRunCheckScript(params: CustomCheckParams, jobId: string, url: string) : Promise<Result<ScriptDryRun, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("dry_run_check_script", { params, jobId, url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run a draft check over every page of the finished job `job_id` and
+ * return the issues it would raise, without storing them.
+ */
+async dryRunCustomCheck(params: CustomCheckParams, jobId: string) : Promise<Result<CheckDryRun, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("dry_run_custom_check", { params, jobId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-run the saved custom checks on the finished job `job_id`,
+ * re-parsing its saved HTML with the current extractors, and replace
+ * the issues they raised during the crawl.
+ */
+async applyCustomChecks(jobId: string) : Promise<Result<AppliedChecks, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("apply_custom_checks", { jobId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async listCustomExtractors() : Promise<Result<CustomExtractor[], CommandError>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("list_custom_extractors") };
@@ -349,6 +461,18 @@ async deleteCustomExtractor(id: string) : Promise<Result<null, CommandError>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Run a draft extractor over the saved HTML of every page of the
+ * finished job `job_id` and return what it extracts from each.
+ */
+async dryRunCustomExtractor(params: CustomExtractorParams, jobId: string) : Promise<Result<ExtractorDryRun, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("dry_run_custom_extractor", { params, jobId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Return the full tag catalog so the frontend can render tag pickers
  * / autocomplete in the custom-check editor, template editor, and the
@@ -461,6 +585,95 @@ async deleteReportTemplate(id: string) : Promise<Result<null, CommandError>> {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+/**
+ * Bundle the selected checks, extractors, patterns and templates into a
+ * rule pack and return its contents for the frontend to save.
+ */
+async exportRulePack(request: RulePackExport) : Promise<Result<string, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_rule_pack", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Describe a rule pack file's contents and what importing it with
+ * `strategy` would do, without importing anything.
+ */
+async previewRulePack(content: string, strategy: ImportStrategy) : Promise<Result<RulePackPreview, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_rule_pack", { content, strategy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Import a rule pack. New checks and extractors take effect from the
+ * next app start, like ones created in the editors.
+ */
+async importRulePack(content: string, strategy: ImportStrategy) : Promise<Result<RulePackItem[], CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_rule_pack", { content, strategy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The HTML and response headers the page of `job_id` crawled at `url`
+ * was served with.
+ */
+async getPageSource(jobId: string, url: string) : Promise<Result<PageSnapshot, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_page_source", { jobId, url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compare the source of the page of `job_id` crawled at `url` with the
+ * same URL in `against_job_id`, or in the latest earlier crawl when
+ * `None`.
+ */
+async diffPageSource(jobId: string, url: string, againstJobId: string | null) : Promise<Result<SourceDiff, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("diff_page_source", { jobId, url, againstJobId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSnapshotSettings() : Promise<Result<SnapshotSettings, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_snapshot_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Save the snapshot settings, drop snapshots past the new retention
+ * period and return what the rest take up.
+ */
+async setSnapshotSettings(settings: SnapshotSettings) : Promise<Result<SnapshotStorage, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_snapshot_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSnapshotStorage() : Promise<Result<SnapshotStorage, CommandError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_snapshot_storage") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -474,6 +687,39 @@ async deleteReportTemplate(id: string) : Promise<Result<null, CommandError>> {
 
 /** user-defined types **/
 
+/**
+ * How a site check summarises the pages its condition matches.
+ */
+export type AggregateFunction = 
+/**
+ * Number of matching pages.
+ */
+"count" | 
+/**
+ * Matching pages as a percentage of all pages.
+ */
+"percent" | 
+/**
+ * Number of distinct `field` values among matching pages.
+ */
+"distinct" | 
+/**
+ * Number of matching pages whose `field` value another matching
+ * page shares.
+ */
+"duplicates" | 
+/**
+ * Smallest numeric `field` value among matching pages.
+ */
+"min" | 
+/**
+ * Largest numeric `field` value among matching pages.
+ */
+"max" | 
+/**
+ * Mean numeric `field` value among matching pages.
+ */
+"avg"
 export type AnalysisJobResponse = { job_id: string; url: string; status: JobStatus }
 /**
  * Analysis progress for frontend updates.
@@ -483,10 +729,42 @@ export type AnalysisJobResponse = { job_id: string; url: string; status: JobStat
  * null-checks on the TypeScript side with no upside.
  */
 export type AnalysisProgress = { job_id: string; url: string; job_status: JobStatus; result_id: string; progress: number; max_pages: number; is_deep_audit: boolean; total_issues: number }
-export type AnalysisResults = { id: string; url: string; status: JobStatus; progress: number; total_pages: number; analyzed_pages: number; started_at: string | null; completed_at: string | null; sitemap_found: boolean; robots_txt_found: boolean; ssl_certificate: boolean; created_at: string }
-export type AnalysisSettingsRequest = { max_pages: number; include_subdomains: boolean; check_images: boolean; mobile_analysis: boolean; lighthouse_analysis: boolean; delay_between_requests: number }
+export type AnalysisResults = { id: string; url: string; status: JobStatus; progress: number; total_pages: number; analyzed_pages: number; started_at: string | null; completed_at: string | null; sitemap_found: boolean; robots_txt_found: boolean; /**
+ * The site's certificate passed a verified handshake.
+ */
+ssl_certificate: boolean; tls: TlsCertificateInfo | null; created_at: string }
+export type AnalysisSettingsRequest = { max_pages: number; include_subdomains: boolean; check_images: boolean; mobile_analysis: boolean; lighthouse_analysis: boolean; delay_between_requests: number; url_rules?: UrlRules; crawl_profile?: CrawlProfile; resource_analysis?: boolean; 
+/**
+ * Extra headers, cookies and credentials. Not part of
+ * [`JobSettings`]: it is stored apart from the job row.
+ */
+http?: HttpSettingsRequest | null }
 export type AnalysisSummary = { analysis_id: string; seo_score: number; avg_load_time: number; total_words: number; total_issues: number }
+/**
+ * What re-running the custom checks on a finished job changed.
+ */
+export type AppliedChecks = { pages_checked: number; 
+/**
+ * Pages whose HTML was re-parsed with the current extractors.
+ */
+pages_reextracted: number; issues_removed: number; issues_added: number }
 export type BusinessImpact = "high" | "medium" | "low"
+/**
+ * What a draft check would raise over the stored pages of a finished
+ * job.
+ */
+export type CheckDryRun = { pages_checked: number; issues: DryRunIssue[] }
+/**
+ * Whether a custom check runs on every page or once over the whole site.
+ */
+export type CheckScope = "page" | "site"
+export type CloakingDifference = { field: string; baseline: string | null; compared: string | null }
+export type CloakingPageResult = { url: string; differences: CloakingDifference[]; 
+/**
+ * Set when either fetch failed; the page is then not compared.
+ */
+error: string | null }
+export type CloakingReport = { baseline: CrawlProfile; compared: CrawlProfile; pages: CloakingPageResult[] }
 /**
  * Wrapper for errors returned from Tauri commands.
  * This type is serializable and can be sent to the frontend.
@@ -522,6 +800,12 @@ export type Condition =
  * True if the robots.txt was NOT found during discovery.
  */
 { op: "robotsMissing" } | 
+/**
+ * True if the site-level check with id `check` (e.g.
+ * `"security_txt"`) ended with `status`. A check the job didn't run
+ * matches nothing.
+ */
+{ op: "siteCheck"; check: string; status: SiteCheckStatus } | 
 /**
  * True if the named tag has a non-empty aggregated value across
  * the crawled pages. The `tag` field is the bare extractor name
@@ -550,6 +834,18 @@ export type Condition =
  * Logical NOT.
  */
 { op: "not"; inner: Condition }
+/**
+ * Browser emulation and user agent for a job's requests.
+ */
+export type CrawlProfile = 
+/**
+ * Firefox desktop emulation, the crawler's long-standing default.
+ */
+{ kind: "default" } | { kind: "chrome" } | { kind: "chrome_android" } | { kind: "safari" } | { kind: "safari_ios" } | { kind: "edge" } | { kind: "googlebot_smartphone" } | { kind: "googlebot_desktop" } | { kind: "bingbot" } | 
+/**
+ * Any user-agent string, sent without browser emulation.
+ */
+{ kind: "custom"; user_agent: string }
 /**
  * A user-defined check that inspects extracted page data and produces an issue.
  */
@@ -563,13 +859,34 @@ field: string; operator: Operator;
  */
 threshold: string | null; 
 /**
- * Template for the issue message. `{value}` is replaced with the actual field value.
+ * Compound condition that replaces `field`/`operator`/`threshold`
+ * when set. `field` still supplies `{value}` for the message.
+ */
+condition?: RuleCondition | null; 
+/**
+ * `page` checks run on every page; `site` checks run once per job.
+ */
+scope?: CheckScope; 
+/**
+ * For site checks, what to compute over the pages the condition
+ * matches. Ignored for page checks.
+ */
+aggregate?: SiteAggregate | null; 
+/**
+ * A Rhai script that replaces the rule when set. It reads the page
+ * as `page` and returns its issues; see `checker::script`.
+ * Page checks only.
+ */
+script?: string | null; 
+/**
+ * Template for the issue message. `{value}` is replaced with the
+ * actual field value, or a site check's aggregate.
  */
 message_template: string; enabled: boolean }
 /**
  * Parameters for creating or updating a custom check.
  */
-export type CustomCheckParams = { name: string; severity: IssueSeverity; field: string; operator: Operator; threshold: string | null; message_template: string; enabled: boolean }
+export type CustomCheckParams = { name: string; severity: IssueSeverity; field: string; operator: Operator; threshold: string | null; condition?: RuleCondition | null; scope?: CheckScope; aggregate?: SiteAggregate | null; script?: string | null; message_template: string; enabled: boolean }
 /**
  * A user-defined CSS-selector extractor that populates `page.extracted_data`.
  * 
@@ -586,11 +903,21 @@ export type CustomExtractor = { id: string; name: string;
  * `page.extracted_data`. Must be unique across all extractors —
  * the DB column has a `UNIQUE` constraint.
  */
-tag: string; selector: string; attribute: string | null; multiple: boolean; enabled: boolean }
+tag: string; kind?: ExtractorKind; 
+/**
+ * The expression to evaluate, in `kind`'s syntax.
+ */
+selector: string; attribute: string | null; multiple: boolean; output?: ExtractorOutput; 
+/**
+ * The type extracted values are converted to; values that don't
+ * convert are dropped. `None` infers it, see
+ * [`CustomExtractor::data_type`].
+ */
+data_type?: TagDataType | null; enabled: boolean }
 /**
  * Parameters for creating or updating a custom extractor.
  */
-export type CustomExtractorParams = { name: string; tag: string; selector: string; attribute: string | null; multiple: boolean; enabled: boolean }
+export type CustomExtractorParams = { name: string; tag: string; kind?: ExtractorKind; selector: string; attribute: string | null; multiple: boolean; output?: ExtractorOutput; data_type?: TagDataType | null; enabled: boolean }
 /**
  * A pattern that fired during analysis of a specific job.
  */
@@ -607,6 +934,89 @@ priorityScore: number;
  * Up to 5 representative affected URLs.
  */
 sampleUrls: string[] }
+/**
+ * One changed region of a [`SourceDiff`].
+ */
+export type DiffHunk = { 
+/**
+ * First line of the region in the older source, 1-based.
+ */
+from_line: number; 
+/**
+ * First line of the region in the newer source, 1-based.
+ */
+to_line: number; lines: DiffLine[] }
+export type DiffLine = { kind: DiffLineKind; 
+/**
+ * The line without its line break.
+ */
+text: string }
+export type DiffLineKind = "context" | "added" | "removed"
+/**
+ * An issue a draft check would raise on a finished job.
+ */
+export type DryRunIssue = { 
+/**
+ * The page the issue is on; `None` for a site check's issue.
+ */
+url: string | null; severity: IssueSeverity; message: string; details: string | null }
+/**
+ * A draft extractor's value on one page; `None` when nothing matched.
+ */
+export type ExtractedValue = { url: string; value: JsonValue | null }
+/**
+ * What a draft extractor extracts from the pages of a finished job.
+ */
+export type ExtractorDryRun = { pages: ExtractedValue[]; 
+/**
+ * Pages whose HTML wasn't saved, so the extractor couldn't run on
+ * them.
+ */
+without_html: string[] }
+/**
+ * Where a custom extractor reads its value from. `selector` holds the
+ * expression in that kind's syntax.
+ */
+export type ExtractorKind = 
+/**
+ * A CSS selector; `attribute` reads an attribute instead of the text.
+ */
+"css" | 
+/**
+ * An XPath expression. `attribute` works as for CSS, or the path
+ * can end in `/@attr` or `/text()`.
+ */
+"xpath" | 
+/**
+ * A regex over the raw HTML. `attribute` names the capture group
+ * to read, by name or number; otherwise the first group, or the
+ * whole match when there are none.
+ */
+"regex" | 
+/**
+ * A JSON path (`$.author.name`) into each JSON-LD block.
+ */
+"json_ld" | 
+/**
+ * A response header, named by `selector`.
+ */
+"header"
+/**
+ * What an extractor publishes for its matches.
+ */
+export type ExtractorOutput = 
+/**
+ * The matched values themselves.
+ */
+"value" | 
+/**
+ * How many matches there are; 0 rather than missing when none.
+ */
+"count" | 
+/**
+ * The length in characters of each matched value.
+ */
+"length"
 export type Feature = "LinkAnalysis" | "GraphView" | "ExportReports"
 export type FixEffort = "low" | "medium" | "high"
 export type GeminiRequest = { analysis_id: string; url: string; seo_score: number; pages_count: number; total_issues: number; critical_issues: number; warning_issues: number; suggestion_issues: number; 
@@ -652,16 +1062,89 @@ error_pages_count?: number;
  */
 tag_values?: Partial<{ [key in string]: string }> }
 export type HeadingElement = { tag: string; text: string }
+/**
+ * A hosts-file entry: connect to `address` whenever the job requests
+ * `host`, e.g. to audit a site on its new server before DNS cutover.
+ * The URL's port is kept. Behind an HTTP or `socks5h` proxy, the proxy
+ * resolves names itself and these entries don't apply.
+ */
+export type HostOverride = { host: string; address: string }
+/**
+ * Credentials presented to the job's start URL origin (same scheme,
+ * host and port), never to the rest of its site.
+ */
+export type HttpAuth = { scheme: "basic"; username: string; password: string } | 
+/**
+ * Answered after the server's first `401` challenge; later requests
+ * reuse the challenge until the server issues a new one.
+ */
+{ scheme: "digest"; username: string; password: string } | { scheme: "bearer"; token: string }
+/**
+ * An extra header sent with every request to the job's site.
+ */
+export type HttpHeader = { name: string; value: string }
+/**
+ * HTTP configuration as entered in the UI. Resolved into an
+ * [`HttpConfig`](crate::contexts::analysis::HttpConfig) by reading
+ * `cookies_file` and `hosts_text` and merging what they hold with the
+ * entries typed in.
+ */
+export type HttpSettingsRequest = { headers?: HttpHeader[]; cookies?: SeedCookie[]; 
+/**
+ * Path to a Netscape-format cookies.txt exported from a browser.
+ */
+cookies_file?: string | null; auth?: HttpAuth | null; proxy?: ProxyConfig | null; hosts?: HostOverride[]; 
+/**
+ * Host overrides in `/etc/hosts` format, as pasted.
+ */
+hosts_text?: string | null }
 export type ImageElement = { src: string; alt: string | null }
+/**
+ * A line of list input that could not be used.
+ */
+/**
+ * What happened — or, in a preview, would happen — to a pack item on import.
+ */
+export type ImportOutcome = "created" | "updated" | "renamed" | "skipped"
+/**
+ * What to do with a pack item whose id — or, for an extractor, whose
+ * tag — already exists here.
+ */
+export type ImportStrategy = 
+/**
+ * Keep the local item and skip the pack's.
+ */
+"merge" | 
+/**
+ * Replace the local item with the pack's.
+ */
+"overwrite" | 
+/**
+ * Import the pack's item alongside under a new id, name or tag,
+ * updating the pack's references to it.
+ */
+"rename"
+export type InvalidUrlEntry = { 
+/**
+ * 1-based line (or position, for sitemap input).
+ */
+line: number; value: string; reason: string }
 export type IssueSeverity = "critical" | "warning" | "info"
 /**
  * Status of an SEO analysis job.
  */
-export type JobStatus = "pending" | "discovery" | "processing" | "completed" | "failed" | "cancelled"
+export type JobStatus = "pending" | "discovery" | "processing" | "completed" | "failed" | "cancelled" | 
+/**
+ * Stopped by the user with its discovery frontier and page queue
+ * checkpointed, so it can resume where it left off. Neither active
+ * (no worker holds it) nor terminal (it is expected to continue).
+ */
+"paused"
 export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
 export type LicenseTier = "Free" | "Premium"
 export type LinkDetail = { href: string; text: string; link_type: LinkType; is_broken: boolean; status_code: number | null }
 export type LinkType = "internal" | "subdomain" | "external" | "resource"
+export type ListAnalysisResponse = { job: AnalysisJobResponse; report: UrlListReport }
 /**
  * Runtime state of a model: registry metadata + whether it's on disk.
  */
@@ -678,13 +1161,88 @@ has_partial: boolean }
  * (`"small"` | `"medium"` | `"large"`) is unchanged.
  */
 export type ModelTier = "small" | "medium" | "large"
+/**
+ * Where to reach an Ollama server and which of its models to use.
+ */
+export type OllamaSettings = { base_url: string; 
+/**
+ * A model the server has pulled, e.g. `llama3.1:8b`.
+ */
+model: string }
+/**
+ * Where to reach an OpenAI-compatible chat completions API: OpenAI
+ * itself, or a server that speaks the same protocol such as LM Studio,
+ * vLLM or OpenRouter.
+ */
+export type OpenAiCompatibleSettings = { 
+/**
+ * API root that `/chat/completions` is appended to.
+ */
+base_url: string; model: string; 
+/**
+ * Sent as a bearer token; empty for servers that don't need one.
+ */
+api_key?: string }
 /**
  * Condition operator for a custom check.
  */
-export type Operator = "missing" | "present" | "eq" | "lt" | "gt" | "contains" | "not_contains"
+export type Operator = "missing" | "present" | "eq" | "lt" | "gt" | "contains" | "not_contains" | 
+/**
+ * Text matches the regular expression in the threshold.
+ */
+"matches" | 
+/**
+ * Number within the inclusive `min,max` range in the threshold.
+ */
+"between" | 
+/**
+ * Text has fewer characters than the threshold.
+ */
+"length_lt" | 
+/**
+ * Text has more characters than the threshold.
+ */
+"length_gt" | 
+/**
+ * Value equals one of the comma-separated values in the threshold.
+ */
+"in_list"
+/**
+ * File format a pack is written in.
+ */
+export type PackFormat = "json" | "toml"
+/**
+ * Who signed a pack.
+ */
+export type PackSigner = { 
+/**
+ * Short hex digest of the signer's public key, for comparing by eye.
+ */
+fingerprint: string; 
+/**
+ * Signed with this app's own key.
+ */
+is_local: boolean }
 export type PageAnalysisData = { analysis_id: string; url: string; title: string | null; meta_description: string | null; meta_keywords: string | null; canonical_url: string | null; word_count: number; image_count: number; images_without_alt: number; internal_links: number; external_links: number; load_time: number; status_code: number | null; content_size: number; mobile_friendly: boolean; has_structured_data: boolean; lighthouse_performance: number | null; lighthouse_accessibility: number | null; lighthouse_best_practices: number | null; lighthouse_seo: number | null; lighthouse_seo_audits: JsonValue | null; lighthouse_performance_metrics: JsonValue | null; images: ImageElement[]; detailed_links: LinkDetail[]; headings: HeadingElement[]; extracted_data: Partial<{ [key in string]: JsonValue }> }
+/**
+ * The HTML and response headers one page of a job was served with.
+ */
+export type PageSnapshot = { job_id: string; url: string; html: string; headers: ResponseHeaders; 
+/**
+ * Hex SHA-256 of the HTML; snapshots with the same hash have the
+ * same source.
+ */
+hash: string; 
+/**
+ * When the page was crawled, RFC 3339.
+ */
+captured_at: string }
 export type PaginatedJobsResponse = { items: AnalysisProgress[]; total: number }
-export type PatternCategory = "technical" | "content" | "performance" | "accessibility"
+export type PatternCategory = "technical" | "content" | "performance" | "accessibility" | 
+/**
+ * "Security & Delivery": response headers, caching and compression.
+ */
+"security"
 /**
  * Filter applied to the detected-pattern list before iterating. Used by
  * [`TemplateSection::PatternSummary`](super::TemplateSection::PatternSummary)
@@ -720,15 +1278,40 @@ export type PatternSeverity = "critical" | "warning" | "suggestion"
  * [`PillarScores::from_pillars`] and read via the typed accessors.
  * The `overall` average is derived in the constructor so the
  * invariant `overall == mean(technical, content, performance,
- * accessibility)` is enforced once at construction time.
+ * accessibility[, security])` is enforced once at construction time.
+ * 
+ * `security` is `None` when no page had response headers to judge
+ * (deep-audited jobs, jobs from before headers were captured); the
+ * pillar is then left out of the average rather than scored 100.
  */
-export type PillarScores = { technical: number; content: number; performance: number; accessibility: number; overall: number }
+export type PillarScores = { technical: number; content: number; performance: number; accessibility: number; security: number | null; overall: number }
 export type Policy = { tier: LicenseTier; max_pages: number; enabled_features: Feature[]; 
 /**
  * True when the installed build is newer than the license's update window.
  * The app still works — this flag drives a renewal banner in the UI.
  */
 updates_expired: boolean }
+/**
+ * An HTTP(S) or SOCKS5 proxy all of the job's traffic goes through.
+ */
+export type ProxyConfig = { 
+/**
+ * `http://`, `https://`, `socks5://` or `socks5h://` (the proxy
+ * resolves host names) followed by the proxy's host and port.
+ */
+url: string; username?: string | null; password?: string | null }
+/**
+ * What to do with query parameters before a URL is queued or stored.
+ */
+export type QueryParamPolicy = { mode: "keep_all" } | { mode: "strip_all" } | 
+/**
+ * Drop the listed parameter names and keep the rest.
+ */
+{ mode: "strip_listed"; params: string[] } | 
+/**
+ * Keep only the listed parameter names.
+ */
+{ mode: "keep_listed"; params: string[] }
 /**
  * The full output of the report engine — ready for frontend rendering / PDF export.
  */
@@ -747,10 +1330,16 @@ aiBrief: string }
 export type ReportPattern = { id: string; name: string; description: string; category: PatternCategory; severity: PatternSeverity; 
 /**
  * Page field to evaluate. Built-in fields: `meta_description`, `title`, `word_count`,
- * `load_time_ms`, `status_code`, `has_viewport`, `has_structured_data`, `canonical_url`,
- * `h1_count`. Custom extractor tags use `tag:<tag>`.
+ * `load_time_ms`, `ttfb_ms`, `download_ms`, `status_code`, `has_viewport`,
+ * `has_structured_data`, `canonical_url`, `h1_count`, `headings`, and from the resource pass `total_weight_bytes`, `request_count`,
+ * `render_blocking_count`, `third_party_share` (percent). Custom extractor tags
+ * use `tag:<tag>`; response headers use `header:<name>`.
  */
 field: string; operator: Operator; threshold: string | null; 
+/**
+ * Compound condition that replaces `field`/`operator`/`threshold` when set.
+ */
+condition?: RuleCondition | null; 
 /**
  * Minimum fraction of pages (0.0–1.0) that must match before the pattern is "detected".
  */
@@ -762,7 +1351,7 @@ isBuiltin: boolean; enabled: boolean }
 /**
  * Parameters for creating or updating a user-defined pattern.
  */
-export type ReportPatternParams = { name: string; description: string; category: PatternCategory; severity: PatternSeverity; field: string; operator: Operator; threshold: string | null; minPrevalence: number; businessImpact: BusinessImpact; fixEffort: FixEffort; recommendation: string; enabled: boolean }
+export type ReportPatternParams = { name: string; description: string; category: PatternCategory; severity: PatternSeverity; field: string; operator: Operator; threshold: string | null; condition?: RuleCondition | null; minPrevalence: number; businessImpact: BusinessImpact; fixEffort: FixEffort; recommendation: string; enabled: boolean }
 /**
  * A named, reorderable report template authored by the consultant.
  */
@@ -781,7 +1370,202 @@ isBuiltin: boolean; sections: TemplateSection[];
  * Non-empty = only these tag names (e.g. `["og_image", "author"]`).
  */
 selectedTags?: string[] }
+/**
+ * Response headers in the order the server sent them, names lower-cased.
+ * Empty when the page was fetched before headers were captured or by the
+ * deep auditor, which doesn't expose them.
+ */
+export type ResponseHeaders = ([string, string])[]
+/**
+ * A condition over page fields: one comparison, or a combination of
+ * them. Shared by custom checks and report patterns.
+ * 
+ * Serialized with a `kind` tag, e.g.
+ * `{"kind":"all","children":[{"kind":"compare","field":"title","operator":"contains","threshold":"Acme"}, ...]}`.
+ * Mirrors the report template `Condition` tree.
+ */
+export type RuleCondition = 
+/**
+ * `field <operator> threshold`, or `field <operator> compare_field`
+ * when comparing two fields (e.g. `title` eq `tag:og_title`).
+ */
+{ kind: "compare"; field: string; operator: Operator; threshold?: string | null; 
+/**
+ * Another field whose value stands in for `threshold`.
+ */
+compare_field?: string | null } | 
+/**
+ * Every condition holds.
+ */
+{ kind: "all"; children: RuleCondition[] } | 
+/**
+ * At least one condition holds.
+ */
+{ kind: "any"; children: RuleCondition[] } | 
+/**
+ * The condition doesn't hold.
+ */
+{ kind: "not"; inner: RuleCondition }
+/**
+ * How a pack item clashes with what's already here.
+ */
+export type RulePackCollision = 
+/**
+ * An item of the same kind has the same id.
+ */
+"id" | 
+/**
+ * An extractor already publishes the same tag.
+ */
+"tag"
+/**
+ * Which local items to export. Extractors and patterns the selection
+ * refers to are added even when not listed.
+ */
+export type RulePackExport = { name: string; description?: string; check_ids?: string[]; extractor_ids?: string[]; pattern_ids?: string[]; template_ids?: string[]; format?: PackFormat; 
+/**
+ * Sign the pack with this app's rule pack key.
+ */
+sign?: boolean }
+/**
+ * One item of a pack and where it lands on import.
+ */
+export type RulePackItem = { kind: RulePackItemKind; 
+/**
+ * The item's id in the pack.
+ */
+id: string; 
+/**
+ * The item's name in the pack.
+ */
+name: string; collision: RulePackCollision | null; outcome: ImportOutcome; 
+/**
+ * The name — or, for an extractor, the tag — it's imported under
+ * when renamed.
+ */
+renamed_to: string | null }
+export type RulePackItemKind = "extractor" | "check" | "pattern" | "template"
+/**
+ * What a pack holds and how importing it with a given strategy would
+ * go, shown before importing.
+ */
+export type RulePackPreview = { name: string; description: string; version: number; exported_at: string; 
+/**
+ * `None` for an unsigned pack. A pack whose signature doesn't match
+ * fails to preview at all.
+ */
+signer: PackSigner | null; items: RulePackItem[]; 
+/**
+ * `tag:<tag>` and pattern references nothing would provide after
+ * the import; importing fails while there are any.
+ */
+unresolved: string[] }
+/**
+ * What a check script did when run against one stored page.
+ */
+export type ScriptDryRun = { issues: ScriptIssue[]; 
+/**
+ * Lines the script wrote with `print` and `debug`.
+ */
+output: string[]; 
+/**
+ * Why the script didn't compile or didn't run to the end.
+ */
+error: string | null }
+/**
+ * An issue a check script raised during a dry run.
+ */
+export type ScriptIssue = { severity: IssueSeverity; message: string; details: string | null }
+/**
+ * A cookie placed in the job's cookie jar before the first request.
+ * Cookies the site sets while it is crawled are kept in the same jar.
+ */
+export type SeedCookie = { name: string; value: string; 
+/**
+ * Cookie domain; `None` scopes it to the job's start host.
+ */
+domain?: string | null; 
+/**
+ * Also send the cookie to subdomains of `domain`.
+ */
+include_subdomains?: boolean; path?: string; 
+/**
+ * Only send the cookie over https.
+ */
+secure?: boolean }
 export type SeoIssue = { page_id: string; severity: IssueSeverity; title: string; description: string; page_url: string; element: string | null; recommendation: string; line_number: number | null }
+/**
+ * The aggregate a site check computes and the comparison that raises
+ * its issue, e.g. `percent` `lt` `80`.
+ */
+export type SiteAggregate = { function: AggregateFunction; 
+/**
+ * One of `eq`, `lt`, `gt` or `between`.
+ */
+operator: Operator; threshold: string | null }
+/**
+ * What a site check found.
+ */
+export type SiteCheckStatus = 
+/**
+ * Served, and its content is what the check expects.
+ */
+"found" | 
+/**
+ * Served, but the content has problems.
+ */
+"invalid" | 
+/**
+ * Behind a 401 or 403; it exists but can't be read.
+ */
+"unauthorized" | "missing" | 
+/**
+ * Every request for it failed.
+ */
+"error"
+/**
+ * Whether page source is saved during analysis, and for how long.
+ */
+export type SnapshotSettings = { 
+/**
+ * Save each analysed page's HTML and headers.
+ */
+enabled: boolean; 
+/**
+ * Days a snapshot is kept after its page was crawled. `None` keeps
+ * them until the job is deleted.
+ */
+retention_days: number | null }
+/**
+ * What the saved snapshots take up.
+ */
+export type SnapshotStorage = { snapshots: number; 
+/**
+ * Distinct page sources among them.
+ */
+distinct_sources: number; 
+/**
+ * Bytes of HTML the snapshots hold, counting repeated sources each
+ * time.
+ */
+html_bytes: number; 
+/**
+ * Bytes actually stored, after deduplication and compression.
+ */
+stored_bytes: number }
+/**
+ * Line-by-line changes to a page's source between two crawls.
+ */
+export type SourceDiff = { 
+/**
+ * The older job.
+ */
+from_job_id: string; from_captured_at: string; to_job_id: string; to_captured_at: string; url: string; 
+/**
+ * Changed regions with a few lines of context; empty when the
+ * source is unchanged.
+ */
+hunks: DiffHunk[] }
 /**
  * A named symbol the consultant can reference when authoring a custom
  * check, a report template, or an AI prompt. `name` is exactly what
@@ -948,6 +1732,85 @@ export type TemplateSection =
  * Horizontal divider. Renders as a markdown `---`.
  */
 { kind: "divider" }
+export type TlsCertificateInfo = { host: string; valid: boolean; protocol: string | null; subject: string | null; subject_alt_names: string[]; issuer: string | null; not_before: string | null; not_after: string | null; chain_length: number; 
+/**
+ * Why the certificate isn't trusted, or why no connection was made.
+ */
+error: string | null; 
+/**
+ * Why the certificate wasn't inspected at all.
+ */
+skipped: string | null; checked_at: string }
+/**
+ * Trailing-slash normalization for the URL path. The root path `/` is
+ * never changed.
+ */
+export type TrailingSlash = "keep" | "strip" | 
+/**
+ * Add a slash to paths whose last segment has no file extension.
+ */
+"add"
+/**
+ * Outcome of parsing a URL list.
+ */
+export type UrlListReport = { 
+/**
+ * URLs that will be queued, in input order.
+ */
+accepted: string[]; 
+/**
+ * Repeats of an already accepted URL.
+ */
+duplicates: string[]; invalid: InvalidUrlEntry[]; 
+/**
+ * Valid, unique URLs dropped because the job's page limit was reached.
+ */
+over_limit: number }
+/**
+ * Where a list-mode job gets its URLs from.
+ */
+export type UrlListSource = 
+/**
+ * URLs pasted by the user, one per line.
+ */
+{ kind: "text"; text: string } | 
+/**
+ * A local text or CSV file with one URL per row.
+ */
+{ kind: "file"; path: string } | 
+/**
+ * A sitemap (or sitemap index) whose `<loc>` entries are audited.
+ */
+{ kind: "sitemap"; url: string }
+/**
+ * A single include or exclude pattern.
+ */
+export type UrlPattern = { kind: "glob"; pattern: string } | { kind: "regex"; pattern: string }
+/**
+ * Crawl scoping rules attached to a job.
+ */
+export type UrlRules = { 
+/**
+ * When non-empty, only URLs matching at least one pattern are crawled.
+ */
+include?: UrlPattern[]; 
+/**
+ * URLs matching any of these are never crawled. Wins over `include`.
+ */
+exclude?: UrlPattern[]; query_params?: QueryParamPolicy; trailing_slash?: TrailingSlash; 
+/**
+ * Lowercase the path (the host is always lowercased by the URL parser).
+ */
+lowercase_path?: boolean; 
+/**
+ * Cap on distinct URLs sharing one path shape, where numeric
+ * segments such as `/2024/05` count as the same shape.
+ */
+max_urls_per_pattern?: number | null; 
+/**
+ * Cap on distinct query strings crawled for a single path.
+ */
+max_query_combinations?: number | null }
 
 /** tauri-specta globals **/
 
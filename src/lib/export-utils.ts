@@ -21,7 +21,7 @@ function formatDate(date: Date = new Date()): string {
   return date.toISOString().split("T")[0];
 }
 
-async function saveFile(
+export async function saveFile(
   content: string | Uint8Array<ArrayBuffer>,
   defaultFilename: string,
   filters?: { name: string; extensions: string[] }[],
@@ -349,7 +349,8 @@ function CoverPage({ data, brief, date }: { data: ReportData; brief: BriefSectio
             ["Content",       data.pillarScores.content],
             ["Performance",   data.pillarScores.performance],
             ["Accessibility", data.pillarScores.accessibility],
-          ].map(([label, score]) => {
+            ["Security",      data.pillarScores.security],
+          ].filter(([, score]) => score !== null).map(([label, score]) => {
             const n = score as number;
             return (
               <View key={label as string} style={{ flex: 1, alignItems: "center" }}>
@@ -423,7 +424,8 @@ function WhatsHurtingPage({ data, brief }: { data: ReportData; brief: BriefSecti
             ["Content",       pillarScores.content],
             ["Performance",   pillarScores.performance],
             ["Accessibility", pillarScores.accessibility],
-          ].map(([label, score]) => (
+            ["Security",      pillarScores.security],
+          ].filter(([, score]) => score !== null).map(([label, score]) => (
             <PillarBar key={label as string} label={label as string} score={Math.round(score as number)} />
           ))}
         </View>
@@ -454,7 +456,7 @@ function WhatsHurtingPage({ data, brief }: { data: ReportData; brief: BriefSecti
 
         <Text style={s.sectionLabel}>Patterns by Pillar</Text>
         <View style={{ marginTop: SP.gap.md }}>
-          {["technical", "content", "performance", "accessibility"].map((cat) => {
+          {["technical", "content", "performance", "accessibility", "security"].map((cat) => {
             const items = data.detectedPatterns.filter((d) => d.pattern.category === cat);
             return (
               <View key={cat} style={{ flexDirection: "row", alignItems: "center", justifyContent: "space-between", marginBottom: SP.gap.md }}>
@@ -702,7 +704,7 @@ function splitParagraphs(md: string): string[] {
   return md
     .split(/\n\s*\n/)
     .map((p) => p.replace(/^---+$/gm, "").trim())
-    .filter((p) => p && !/^[-*]\s*(Technical|Content|Performance|Accessibility)\s*[:\-—]\s*\d+/i.test(p));
+    .filter((p) => p && !/^[-*]\s*(Technical|Content|Performance|Accessibility|Security(?: & Delivery)?)\s*[:\-—]\s*\d+/i.test(p));
 }
 
 function NextStepsPage({ brief, domain }: { brief: BriefSections; domain: string }) {
@@ -102,5 +102,5 @@ export function isJustScoreList(s: string): boolean {
   if (!s) return true;
   const lines = s.split("\n").map((l) => l.trim()).filter(Boolean);
   if (lines.length === 0) return true;
-  return lines.every((l) => /^[-*•]?\s*(Technical|Content|Performance|Accessibility)\s*[:\-—]\s*\d+/i.test(l));
+  return lines.every((l) => /^[-*•]?\s*(Technical|Content|Performance|Accessibility|Security(?: & Delivery)?)\s*[:\-—]\s*\d+/i.test(l));
 }
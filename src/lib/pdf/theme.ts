@@ -44,6 +44,7 @@ export const C = {
   pillarContent:      "#7C5ABF",
   pillarPerformance:  "#B07A1E",
   pillarAccessibility:"#2D8A52",
+  pillarSecurity:     "#A8464B",
 
   // Score colour thresholds
   scoreGood: "#15803D",
@@ -109,6 +110,7 @@ export function pillarColor(cat: string): string {
     case "content":      return C.pillarContent;
     case "performance":  return C.pillarPerformance;
     case "accessibility":return C.pillarAccessibility;
+    case "security":     return C.pillarSecurity;
     default:             return C.muted;
   }
 }